@@ -0,0 +1,80 @@
+//! Benchmarks for event wire formats.
+//!
+//! Encodes and decodes a batch of LLM chunk events with each [`EventFormat`] to
+//! compare serialization cost; the encoded size of each batch is printed once
+//! per format. Requires the `msgpack` and `cbor` features:
+//!
+//! ```bash
+//! cargo bench --bench event_encoding --features msgpack,cbor
+//! ```
+
+use std::io::Cursor;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rustc_hash::FxHashMap;
+use weavegraph::event_bus::{Event, EventDecoder, EventFormat, LLMStreamingEvent};
+
+const BATCH: usize = 512;
+const FORMATS: &[EventFormat] = &[EventFormat::Json, EventFormat::MsgPack, EventFormat::Cbor];
+
+fn chunk_events() -> Vec<Event> {
+    (0..BATCH)
+        .map(|i| {
+            let mut metadata = FxHashMap::default();
+            metadata.insert("model".to_string(), serde_json::json!("gpt-4o-mini"));
+            metadata.insert("index".to_string(), serde_json::json!(i));
+            Event::LLM(LLMStreamingEvent::chunk_event(
+                Some("session-1".into()),
+                Some("generate".into()),
+                Some("stream-1".into()),
+                format!("token-{i} "),
+                metadata,
+            ))
+        })
+        .collect()
+}
+
+fn encode_batch(format: EventFormat, events: &[Event]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for event in events {
+        format.write_frame(&mut out, event).expect("encode");
+    }
+    out
+}
+
+fn event_encoding(c: &mut Criterion) {
+    let events = chunk_events();
+    let mut group = c.benchmark_group("event_encode");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    for &format in FORMATS {
+        println!(
+            "{format}: {} bytes for {BATCH} chunk events",
+            encode_batch(format, &events).len()
+        );
+        group.bench_with_input(BenchmarkId::from_parameter(format), &events, |b, events| {
+            b.iter(|| encode_batch(format, events));
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("event_decode");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    for &format in FORMATS {
+        let encoded = encode_batch(format, &events);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    for event in EventDecoder::new(Cursor::new(encoded.as_slice()), format) {
+                        std::hint::black_box(event.expect("decode"));
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, event_encoding);
+criterion_main!(benches);
@@ -0,0 +1,144 @@
+//! Compatibility Fixtures: Capture a Release's Persisted Formats
+//!
+//! Runs a small workflow against a SQLite checkpointer and writes what the
+//! running release persists into `tests/fixtures/compat/v<version>/`:
+//!
+//! - `sqlite.sql`: built with `sqlite3 sqlite.db .dump` after this example runs
+//! - `checkpoint.json`: the latest checkpoint in its persisted JSON shape
+//! - `events.jsonl`: every event the run published, one JSON object per line
+//!
+//! The compatibility tests in `tests/compat.rs` load these files with the
+//! current code. Capture a new corpus from each release tag before changing
+//! a persisted format; see `tests/fixtures/compat/README.md`.
+//!
+//! This example only uses APIs available since v0.6.0 so it can be run
+//! unchanged at older tags.
+//!
+//! Running This Example:
+//! ```bash
+//! cargo run --example compat_fixtures --features sqlite-migrations
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use weavegraph::channels::errors::{ErrorEvent, WeaveError};
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::message::Message;
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::runtimes::persistence::PersistedCheckpoint;
+use weavegraph::runtimes::{AppRunner, Checkpointer, SQLiteCheckpointer};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::types::NodeKind;
+
+type ExampleResult<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+const SESSION_ID: &str = "compat-session";
+
+/// Streams a reply and records it with some extra data.
+struct Reply;
+
+#[async_trait]
+impl Node for Reply {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        ctx.emit("reply", "drafting reply")?;
+        ctx.emit_llm_chunk(None, Some("reply-1".into()), "Hello", None)?;
+        ctx.emit_llm_final(
+            None,
+            Some("reply-1".into()),
+            "Hello from the archive.",
+            None,
+        )?;
+        let mut extra = FxHashMap::default();
+        extra.insert("reply_count".to_string(), json!(1));
+        extra.insert(
+            "profile".to_string(),
+            json!({"tier": "gold", "tags": ["a", "b"]}),
+        );
+        Ok(NodePartial::new()
+            .with_messages(vec![Message::assistant("Hello from the archive.")])
+            .with_extra(extra))
+    }
+}
+
+/// Records a recoverable error.
+struct Audit;
+
+#[async_trait]
+impl Node for Audit {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        ctx.emit_diagnostic("audit", "policy check skipped")?;
+        let error = ErrorEvent::node("audit", 2, WeaveError::msg("policy service unavailable"))
+            .with_tag("transient")
+            .with_context(json!({"attempt": 1}));
+        Ok(NodePartial::new().with_errors(vec![error]))
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExampleResult<()> {
+    let version = env!("CARGO_PKG_VERSION");
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/compat")
+        .join(format!("v{version}"));
+    fs::create_dir_all(&dir)?;
+    let db_path = dir.join("sqlite.db");
+    let _ = fs::remove_file(&db_path);
+
+    let reply = NodeKind::Custom("reply".into());
+    let audit = NodeKind::Custom("audit".into());
+    let app = GraphBuilder::new()
+        .add_node(reply.clone(), Reply)
+        .add_node(audit.clone(), Audit)
+        .add_edge(NodeKind::Start, reply.clone())
+        .add_edge(reply, audit.clone())
+        .add_edge(audit, NodeKind::End)
+        .compile()?;
+
+    let checkpointer = Arc::new(
+        SQLiteCheckpointer::connect(&format!("sqlite://{}?mode=rwc", db_path.display())).await?,
+    );
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(checkpointer.clone())
+        .autosave(true)
+        .build()
+        .await;
+    let mut stream = runner.event_stream().ok_or("event stream already taken")?;
+
+    runner
+        .create_session(
+            SESSION_ID.to_string(),
+            VersionedState::new_with_user_message("Say hello"),
+        )
+        .await?;
+    runner.run_until_complete(SESSION_ID).await?;
+
+    let latest = checkpointer
+        .load_latest(SESSION_ID)
+        .await?
+        .ok_or("no checkpoint saved")?;
+    let persisted = PersistedCheckpoint::from(&latest);
+    fs::write(
+        dir.join("checkpoint.json"),
+        serde_json::to_string_pretty(&persisted)? + "\n",
+    )?;
+
+    let mut events = String::new();
+    while let Ok(event) = stream.try_recv() {
+        events.push_str(&serde_json::to_string(&event)?);
+        events.push('\n');
+    }
+    fs::write(dir.join("events.jsonl"), events)?;
+
+    println!("wrote fixtures to {}", dir.display());
+    println!(
+        "dump the database with: sqlite3 {} .dump",
+        db_path.display()
+    );
+    Ok(())
+}
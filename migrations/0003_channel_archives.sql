@@ -0,0 +1,17 @@
+-- 0003_channel_archives.sql
+--
+-- Messages and errors removed from a session's in-memory state by compaction.
+-- One row per archive; archive_json holds the serialized ChannelArchive:
+--   {"session_id": "...", "sequence": 0, "step": 40, "messages_offset": 0,
+--    "messages": [...], "errors_offset": 0, "errors": [...], "created_at": "..."}
+-- Rows are not tied to `sessions` by a foreign key so archives can be written
+-- before the first checkpoint; delete_session removes them explicitly.
+
+CREATE TABLE IF NOT EXISTS channel_archives (
+    session_id   TEXT    NOT NULL,
+    sequence     INTEGER NOT NULL,
+    step         INTEGER NOT NULL,
+    archive_json TEXT    NOT NULL,
+    created_at   TEXT    NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (session_id, sequence)
+);
@@ -0,0 +1,17 @@
+-- 0004_node_inputs.sql
+--
+-- Opt-in debugging record of the state snapshot nodes received in a superstep.
+-- One row per (session, step); capture_json holds the serialized
+-- NodeInputCapture with the runtime's redaction policy already applied:
+--   {"session_id": "...", "step": 3, "nodes": [...], "input": {...},
+--    "captured_at": "..."}
+-- Rows are not tied to `sessions` by a foreign key; delete_session removes
+-- them explicitly.
+
+CREATE TABLE IF NOT EXISTS node_inputs (
+    session_id   TEXT    NOT NULL,
+    step         INTEGER NOT NULL,
+    capture_json TEXT    NOT NULL,
+    created_at   TEXT    NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (session_id, step)
+);
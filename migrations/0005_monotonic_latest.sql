@@ -0,0 +1,22 @@
+-- 0005_monotonic_latest.sql
+--
+-- Keep the denormalized latest snapshot on `sessions` monotonic: saving an
+-- older step (for example a late write from a slow replica) still records the
+-- step row but no longer moves `last_step` backwards. This matches the
+-- PostgreSQL backend.
+
+DROP TRIGGER IF EXISTS trg_steps_after_insert;
+
+CREATE TRIGGER IF NOT EXISTS trg_steps_after_insert
+AFTER INSERT ON steps
+BEGIN
+    UPDATE sessions
+    SET
+        updated_at              = strftime('%Y-%m-%dT%H:%M:%fZ','now'),
+        last_step               = NEW.step,
+        last_state_json         = NEW.state_json,
+        last_frontier_json      = NEW.frontier_json,
+        last_versions_seen_json = NEW.versions_seen_json
+    WHERE id = NEW.session_id
+      AND (last_step <= NEW.step OR last_state_json IS NULL);
+END;
@@ -0,0 +1,20 @@
+-- 0007_llm_audit.sql
+--
+-- Opt-in audit log of LLM calls made by nodes. One row per call; record_json
+-- holds the serialized LlmAuditRecord (prompt, parameters, response, token
+-- usage, latency) with the runtime's redaction policy already applied.
+-- (session_id, step) references the step row the call contributed to;
+-- attempt and call_index distinguish re-executions and calls within a step.
+-- Rows are not tied to `sessions` by a foreign key so calls from steps that
+-- were rolled back are kept; delete_session removes them explicitly.
+
+CREATE TABLE IF NOT EXISTS llm_audit (
+    session_id  TEXT    NOT NULL,
+    step        INTEGER NOT NULL,
+    attempt     INTEGER NOT NULL,
+    call_index  INTEGER NOT NULL,
+    node        TEXT    NOT NULL,
+    record_json TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (session_id, step, attempt, call_index)
+);
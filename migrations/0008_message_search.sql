@@ -0,0 +1,17 @@
+-- 0008_message_search.sql
+--
+-- Optional full-text index over persisted messages, filled only by
+-- checkpointers built with `with_message_search`. Each session's rows mirror
+-- the messages of its latest saved checkpoint and are replaced on every save.
+-- unicode61 splits words on non-alphanumeric characters and folds case,
+-- matching `Checkpointer::search_messages`. delete_session removes a
+-- session's rows explicitly.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS message_search USING fts5(
+    content,
+    session_id    UNINDEXED,
+    step          UNINDEXED,
+    message_index UNINDEXED,
+    role          UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 0'
+);
@@ -0,0 +1,8 @@
+-- 0002_step_node_metrics.sql
+--
+-- Per-node execution metrics (duration, emitted events, token usage) recorded
+-- for each step. Stored as a JSONB array of PersistedNodeMetrics objects:
+--   [{"node": "Custom:fetch", "duration_micros": 1200, "events_emitted": 2}]
+-- NULL for rows written before this migration.
+
+ALTER TABLE steps ADD COLUMN IF NOT EXISTS node_metrics_json JSONB;
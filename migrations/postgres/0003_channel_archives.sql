@@ -0,0 +1,15 @@
+-- 0003_channel_archives.sql
+--
+-- Messages and errors removed from a session's in-memory state by compaction.
+-- One row per archive; archive_json holds the serialized ChannelArchive.
+-- Rows are not tied to `sessions` by a foreign key so archives can be written
+-- before the first checkpoint; delete_session removes them explicitly.
+
+CREATE TABLE IF NOT EXISTS channel_archives (
+    session_id   TEXT        NOT NULL,
+    sequence     BIGINT      NOT NULL,
+    step         BIGINT      NOT NULL,
+    archive_json JSONB       NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, sequence)
+);
@@ -0,0 +1,15 @@
+-- 0004_node_inputs.sql
+--
+-- Opt-in debugging record of the state snapshot nodes received in a superstep.
+-- One row per (session, step); capture_json holds the serialized
+-- NodeInputCapture with the runtime's redaction policy already applied.
+-- Rows are not tied to `sessions` by a foreign key; delete_session removes
+-- them explicitly.
+
+CREATE TABLE IF NOT EXISTS node_inputs (
+    session_id   TEXT        NOT NULL,
+    step         BIGINT      NOT NULL,
+    capture_json JSONB       NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, step)
+);
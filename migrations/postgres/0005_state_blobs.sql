@@ -0,0 +1,15 @@
+-- 0005_state_blobs.sql
+--
+-- Payloads of the blobs state channel. steps.state_json records each blob's
+-- key, media type, length, and digest; the bytes live here once per
+-- (session, digest) no matter how many steps reference them.
+-- Rows are not tied to `sessions` by a foreign key; delete_session removes
+-- them explicitly and delete_steps_before drops payloads no step references.
+
+CREATE TABLE IF NOT EXISTS state_blobs (
+    session_id TEXT        NOT NULL,
+    digest     TEXT        NOT NULL,
+    data       BYTEA       NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, digest)
+);
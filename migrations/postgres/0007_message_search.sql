@@ -0,0 +1,24 @@
+-- 0007_message_search.sql
+--
+-- Optional full-text index over persisted messages, filled only by
+-- checkpointers built with `with_message_search`. Each session's rows mirror
+-- the messages of its latest saved checkpoint and are replaced on every save.
+-- Content is split on non-alphanumeric characters before the `simple`
+-- configuration folds case, without stemming or stop words, matching
+-- `Checkpointer::search_messages`. delete_session removes a session's rows
+-- explicitly.
+
+CREATE TABLE IF NOT EXISTS message_search (
+    session_id    TEXT     NOT NULL,
+    message_index BIGINT   NOT NULL,
+    step          BIGINT   NOT NULL,
+    role          TEXT     NOT NULL,
+    content       TEXT     NOT NULL,
+    content_tsv   TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector('simple', regexp_replace(content, '[^[:alnum:]]+', ' ', 'g'))
+    ) STORED,
+    PRIMARY KEY (session_id, message_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_search_tsv
+    ON message_search USING GIN (content_tsv);
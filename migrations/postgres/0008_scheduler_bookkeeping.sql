@@ -0,0 +1,12 @@
+-- 0008_scheduler_bookkeeping.sql
+--
+-- Scheduler bookkeeping (pending wait-for-all join arrivals, frontier slot
+-- origins, and quota run counts) recorded for each step. Stored as a JSONB
+-- object of PersistedSchedulerBookkeeping:
+--   {"pending_joins": {"Custom:merge": {"Custom:a": 3}},
+--    "frontier_origins": ["Custom:a", null], "run_counts": {"Custom:critic": 2}}
+-- NULL for rows written before this migration; those rows kept the same data
+-- under reserved `__weavegraph_*` keys in versions_seen_json, which is moved
+-- out when the checkpoint is restored.
+
+ALTER TABLE steps ADD COLUMN IF NOT EXISTS scheduler_json JSONB;
@@ -0,0 +1,336 @@
+//! Python bindings for weavegraph.
+//!
+//! Python nodes are plain callables wrapped in [`BlockingNode`], so they run on
+//! Tokio's blocking pool and take the GIL only while executing. State crosses
+//! the boundary as JSON: nodes receive a `dict` shaped like [`StateSnapshot`]
+//! and return a `dict` shaped like [`NodePartial`] (or `None`).
+//!
+//! ```python
+//! import weavegraph
+//!
+//! def greet(snapshot, ctx):
+//!     ctx.emit("greet", "running")
+//!     return {"messages": [{"role": "assistant", "content": "hello"}]}
+//!
+//! builder = weavegraph.GraphBuilder()
+//! builder.add_node("greet", greet)
+//! builder.add_edge("Start", "greet")
+//! builder.add_edge("greet", "End")
+//! app = builder.compile()
+//!
+//! final = app.invoke("hi")
+//! for event in app.invoke_streaming("hi"):
+//!     print(event["scope"], event["message"])
+//! ```
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+use pyo3::exceptions::{PyRuntimeError, PyStopIteration, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyString};
+use serde_json::Value;
+use tokio::runtime::Runtime;
+use weavegraph::app::{App, InvocationHandle};
+use weavegraph::event_bus::{BlockingEventIter, STREAM_END_SCOPE};
+use weavegraph::graphs::{EdgePredicate, GraphBuilder};
+use weavegraph::message::Message;
+use weavegraph::node::{BlockingNode, NodeContext, NodeError, NodePartial};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::types::NodeKind;
+
+// ============================================================================
+// Runtime and conversion helpers
+// ============================================================================
+
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("weavegraph-py")
+            .build()
+            .expect("failed to start weavegraph runtime")
+    })
+}
+
+fn to_py(py: Python<'_>, value: &Value) -> PyResult<Py<PyAny>> {
+    let text = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (text,))?.unbind())
+}
+
+fn from_py(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let text: String = obj
+        .py()
+        .import("json")?
+        .call_method1("dumps", (obj,))?
+        .extract()?;
+    serde_json::from_str(&text).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn to_node_error(err: PyErr) -> NodeError {
+    NodeError::Provider {
+        provider: "python",
+        message: err.to_string(),
+    }
+}
+
+fn node_kind(name: &str) -> NodeKind {
+    NodeKind::decode(name)
+}
+
+/// Build initial state from a user message string or a
+/// `{"messages": [...], "extra": {...}}` dict.
+fn initial_state(input: &Bound<'_, PyAny>) -> PyResult<VersionedState> {
+    if input.is_instance_of::<PyString>() {
+        let text: String = input.extract()?;
+        return Ok(VersionedState::new_with_user_message(&text));
+    }
+    let value = from_py(input)?;
+    let Value::Object(mut map) = value else {
+        return Err(PyTypeError::new_err("state must be a str or dict"));
+    };
+    let messages: Vec<Message> = match map.remove("messages") {
+        Some(messages) => serde_json::from_value(messages)
+            .map_err(|e| PyValueError::new_err(format!("invalid messages: {e}")))?,
+        None => Vec::new(),
+    };
+    let mut state = VersionedState::new_with_messages(messages);
+    if let Some(Value::Object(extra)) = map.remove("extra") {
+        for (key, value) in extra {
+            let _ = state.add_extra(&key, value);
+        }
+    }
+    Ok(state)
+}
+
+fn snapshot_to_py(py: Python<'_>, snapshot: &StateSnapshot) -> PyResult<Py<PyAny>> {
+    let value = serde_json::to_value(snapshot).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    to_py(py, &value)
+}
+
+// ============================================================================
+// Node context
+// ============================================================================
+
+/// Context passed to Python nodes as their second argument.
+#[pyclass(name = "NodeContext", module = "weavegraph", frozen)]
+struct PyNodeContext {
+    inner: NodeContext,
+}
+
+#[pymethods]
+impl PyNodeContext {
+    #[getter]
+    fn node_id(&self) -> &str {
+        &self.inner.node_id
+    }
+
+    #[getter]
+    fn step(&self) -> u64 {
+        self.inner.step
+    }
+
+    /// Emit a node event onto the run's event stream.
+    fn emit(&self, scope: String, message: String) -> PyResult<()> {
+        self.inner
+            .emit(scope, message)
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    }
+}
+
+// ============================================================================
+// GraphBuilder
+// ============================================================================
+
+/// Python wrapper around [`GraphBuilder`]. Methods mutate the builder in place.
+#[pyclass(name = "GraphBuilder", module = "weavegraph")]
+struct PyGraphBuilder {
+    inner: Option<GraphBuilder>,
+}
+
+impl PyGraphBuilder {
+    fn update(&mut self, f: impl FnOnce(GraphBuilder) -> GraphBuilder) -> PyResult<()> {
+        let builder = self
+            .inner
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("GraphBuilder was already compiled"))?;
+        self.inner = Some(f(builder));
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyGraphBuilder {
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: Some(GraphBuilder::new()),
+        }
+    }
+
+    /// Register `func(snapshot: dict, ctx: NodeContext) -> dict | None` as a node.
+    fn add_node(&mut self, name: &str, func: Py<PyAny>) -> PyResult<()> {
+        let node = BlockingNode::new(move |snapshot: StateSnapshot, ctx: NodeContext| {
+            Python::attach(|py| {
+                let snapshot = snapshot_to_py(py, &snapshot).map_err(to_node_error)?;
+                let ctx = Py::new(py, PyNodeContext { inner: ctx }).map_err(to_node_error)?;
+                let result = func
+                    .bind(py)
+                    .call1((snapshot, ctx))
+                    .map_err(to_node_error)?;
+                if result.is_none() {
+                    return Ok(NodePartial::default());
+                }
+                let value = from_py(&result).map_err(to_node_error)?;
+                serde_json::from_value(value).map_err(NodeError::Serde)
+            })
+        });
+        let id = node_kind(name);
+        self.update(|builder| builder.add_node(id, node))
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str) -> PyResult<()> {
+        let (from, to) = (node_kind(from), node_kind(to));
+        self.update(|builder| builder.add_edge(from, to))
+    }
+
+    /// Route from `from` using `predicate(snapshot: dict) -> str | list[str]`.
+    ///
+    /// A predicate that raises routes nowhere; the traceback is printed.
+    fn add_conditional_edge(&mut self, from: &str, predicate: Py<PyAny>) -> PyResult<()> {
+        let predicate: EdgePredicate = Arc::new(move |snapshot: StateSnapshot| {
+            Python::attach(|py| {
+                let routes = snapshot_to_py(py, &snapshot).and_then(|snapshot| {
+                    let result = predicate.bind(py).call1((snapshot,))?;
+                    if let Ok(target) = result.extract::<String>() {
+                        Ok(vec![target])
+                    } else {
+                        result.cast::<PyList>()?.extract::<Vec<String>>()
+                    }
+                });
+                routes.unwrap_or_else(|err| {
+                    err.print(py);
+                    Vec::new()
+                })
+            })
+        });
+        let from = node_kind(from);
+        self.update(|builder| builder.add_conditional_edge(from, predicate))
+    }
+
+    /// Validate the graph and return an [`App`].
+    fn compile(&mut self) -> PyResult<PyApp> {
+        let builder = self
+            .inner
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("GraphBuilder was already compiled"))?;
+        let app = builder
+            .compile()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyApp {
+            inner: Arc::new(app),
+        })
+    }
+}
+
+// ============================================================================
+// App and event iteration
+// ============================================================================
+
+/// A compiled workflow.
+#[pyclass(name = "App", module = "weavegraph", frozen)]
+struct PyApp {
+    inner: Arc<App>,
+}
+
+#[pymethods]
+impl PyApp {
+    /// Run to completion and return the final state as a dict.
+    fn invoke(&self, py: Python<'_>, state: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let state = initial_state(state)?;
+        let app = Arc::clone(&self.inner);
+        let final_state = py
+            .detach(|| runtime().block_on(async move { app.invoke(state).await }))
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        snapshot_to_py(py, &final_state.snapshot())
+    }
+
+    /// Start a run and return an iterator of event dicts.
+    ///
+    /// Iteration ends after the stream-end marker; call `result()` on the
+    /// iterator afterwards for the final state.
+    fn invoke_streaming(
+        &self,
+        py: Python<'_>,
+        state: &Bound<'_, PyAny>,
+    ) -> PyResult<EventIterator> {
+        let state = initial_state(state)?;
+        let app = Arc::clone(&self.inner);
+        let (handle, stream) =
+            py.detach(|| runtime().block_on(async move { app.invoke_streaming(state).await }));
+        Ok(EventIterator {
+            events: Mutex::new(Some(stream.into_blocking_iter())),
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+}
+
+/// Iterator over events from [`PyApp::invoke_streaming`].
+#[pyclass(module = "weavegraph")]
+struct EventIterator {
+    events: Mutex<Option<BlockingEventIter>>,
+    handle: Mutex<Option<InvocationHandle>>,
+}
+
+#[pymethods]
+impl EventIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let event = py.detach(|| {
+            let mut events = self.events.lock().expect("event iterator lock poisoned");
+            let event = events.as_mut().and_then(Iterator::next);
+            if event.is_none() {
+                *events = None;
+            }
+            event
+        });
+        let Some(event) = event else {
+            return Err(PyStopIteration::new_err(()));
+        };
+        if event.scope_label() == Some(STREAM_END_SCOPE) {
+            *self.events.lock().expect("event iterator lock poisoned") = None;
+        }
+        to_py(py, &event.to_json_value())
+    }
+
+    /// Wait for the run to finish and return the final state as a dict.
+    fn result(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let handle = self
+            .handle
+            .lock()
+            .expect("event iterator lock poisoned")
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("result() was already called"))?;
+        let final_state = py
+            .detach(|| runtime().block_on(handle.join()))
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        snapshot_to_py(py, &final_state.snapshot())
+    }
+}
+
+// ============================================================================
+// Module
+// ============================================================================
+
+#[pymodule(name = "weavegraph")]
+fn weavegraph_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyGraphBuilder>()?;
+    m.add_class::<PyApp>()?;
+    m.add_class::<PyNodeContext>()?;
+    m.add_class::<EventIterator>()?;
+    m.add("STREAM_END_SCOPE", STREAM_END_SCOPE)?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+"""Smoke tests for the weavegraph Python bindings.
+
+Build the extension with `maturin develop` and run `python -m pytest tests`.
+"""
+
+import weavegraph
+
+
+def build_app():
+    def greet(snapshot, ctx):
+        ctx.emit("greet", f"step {ctx.step}")
+        count = len(snapshot["messages"])
+        return {
+            "messages": [{"role": "assistant", "content": f"saw {count}"}],
+            "extra": {"greeted": True},
+        }
+
+    def finish(snapshot, ctx):
+        return None
+
+    builder = weavegraph.GraphBuilder()
+    builder.add_node("greet", greet)
+    builder.add_node("finish", finish)
+    builder.add_edge("Start", "greet")
+    builder.add_conditional_edge(
+        "greet", lambda s: "finish" if s["extra"].get("greeted") else "End"
+    )
+    builder.add_edge("finish", "End")
+    return builder.compile()
+
+
+def test_invoke_returns_final_state():
+    final = build_app().invoke("hi")
+    assert [m["content"] for m in final["messages"]] == ["hi", "saw 1"]
+    assert final["extra"]["greeted"] is True
+
+
+def test_invoke_accepts_dict_state():
+    final = build_app().invoke({"messages": [], "extra": {"seed": 1}})
+    assert final["extra"]["seed"] == 1
+
+
+def test_streaming_yields_node_events_then_ends():
+    events = build_app().invoke_streaming("hi")
+    scopes = [event.get("scope") for event in events]
+    assert "greet" in scopes
+    assert scopes[-1] == weavegraph.STREAM_END_SCOPE
+    assert events.result()["extra"]["greeted"] is True
+
+
+def test_node_errors_surface_as_runtime_error():
+    def boom(snapshot, ctx):
+        raise ValueError("boom")
+
+    builder = weavegraph.GraphBuilder()
+    builder.add_node("boom", boom)
+    builder.add_edge("Start", "boom")
+    builder.add_edge("boom", "End")
+    try:
+        builder.compile().invoke("hi")
+    except RuntimeError as err:
+        assert "boom" in str(err)
+    else:
+        raise AssertionError("expected RuntimeError")
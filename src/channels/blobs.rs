@@ -0,0 +1,317 @@
+//! Binary state payloads: the [`Blob`] value type and the channel that stores them.
+//!
+//! `extra` holds `serde_json::Value`s, so images or audio stored there have to
+//! be base64 strings that every snapshot, merge, and checkpoint re-encodes. The
+//! blobs channel keeps raw [`Bytes`] instead: snapshots share the buffer rather
+//! than copying it, and the SQL checkpointers store each distinct payload once
+//! per session in a binary column. Human-readable formats such as JSON still
+//! see base64; binary formats (MessagePack, CBOR) see raw bytes.
+use std::fmt;
+
+use bytes::Bytes;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::Channel;
+use crate::types::ChannelType;
+
+type ChannelValue = FxHashMap<String, Blob>;
+
+/// A binary payload with an optional media type.
+///
+/// Cloning a `Blob` is cheap: the bytes are reference counted.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::channels::Blob;
+///
+/// let image = Blob::new(vec![0x89, b'P', b'N', b'G']).with_content_type("image/png");
+/// assert_eq!(image.len(), 4);
+/// assert_eq!(image.content_type(), Some("image/png"));
+/// assert_eq!(image.digest().len(), 32);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Blob {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    #[serde(with = "base64_bytes")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
+    data: Bytes,
+}
+
+impl Blob {
+    /// Wrap `data` without a media type.
+    #[must_use]
+    pub fn new(data: impl Into<Bytes>) -> Self {
+        Self {
+            content_type: None,
+            data: data.into(),
+        }
+    }
+
+    /// Set the media type, e.g. `"image/png"`.
+    #[must_use]
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// The media type, if one was set.
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// The payload bytes.
+    #[must_use]
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Payload size in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the payload is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Content address of the payload: 32 lowercase hex digits of its
+    /// 128-bit FNV-1a hash.
+    ///
+    /// Stable across platforms and releases; the SQL checkpointers use it to
+    /// store identical payloads once per session. It is not a cryptographic hash.
+    #[must_use]
+    pub fn digest(&self) -> String {
+        digest(&self.data)
+    }
+}
+
+impl fmt::Debug for Blob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blob")
+            .field("content_type", &self.content_type)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+impl From<Bytes> for Blob {
+    fn from(data: Bytes) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<Vec<u8>> for Blob {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+/// 128-bit FNV-1a of `data` as 32 lowercase hex digits.
+pub(crate) fn digest(data: &[u8]) -> String {
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    let hash = data.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u128::from(*byte)).wrapping_mul(PRIME)
+    });
+    format!("{hash:032x}")
+}
+
+/// Channel that stores named binary payloads for the workflow state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobsChannel {
+    value: ChannelValue,
+    version: u32,
+}
+
+impl BlobsChannel {
+    /// Create a new `BlobsChannel` with the given map and version counter.
+    pub fn new(blobs: ChannelValue, version: u32) -> Self {
+        Self {
+            value: blobs,
+            version,
+        }
+    }
+
+    /// Summed payload size of all blobs, in bytes.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.value.values().map(Blob::len).sum()
+    }
+}
+
+impl Channel<ChannelValue> for BlobsChannel {
+    fn get_channel_type(&self) -> ChannelType {
+        ChannelType::Blob
+    }
+
+    fn snapshot(&self) -> ChannelValue {
+        self.value.clone()
+    }
+
+    fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn get_mut(&mut self) -> &mut ChannelValue {
+        &mut self.value
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version
+    }
+
+    fn persistent(&self) -> bool {
+        true
+    }
+}
+
+impl Default for BlobsChannel {
+    fn default() -> Self {
+        Self {
+            value: FxHashMap::default(),
+            version: 1,
+        }
+    }
+}
+
+/// Serde adapter writing [`Bytes`] as standard padded base64 in human-readable
+/// formats and as a native byte string otherwise.
+pub(crate) mod base64_bytes {
+    use bytes::Bytes;
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    pub(crate) fn serialize<S: Serializer>(data: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&encode(data))
+        } else {
+            serializer.serialize_bytes(data)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Bytes, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BytesVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Bytes;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a base64 string or a byte string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Bytes, E> {
+            decode(v)
+                .map(Bytes::from)
+                .ok_or_else(|| E::custom("invalid base64"))
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Bytes, E> {
+            Ok(Bytes::copy_from_slice(v))
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Bytes, E> {
+            Ok(Bytes::from(v))
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Bytes, A::Error> {
+            let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element::<u8>()? {
+                out.push(byte);
+            }
+            Ok(Bytes::from(out))
+        }
+    }
+
+    /// The same encoding for optional payloads.
+    pub(crate) mod option {
+        use bytes::Bytes;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        #[derive(Serialize, Deserialize)]
+        struct Payload(#[serde(with = "super")] Bytes);
+
+        pub(crate) fn serialize<S: Serializer>(
+            data: &Option<Bytes>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            data.clone().map(Payload).serialize(serializer)
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Bytes>, D::Error> {
+            Ok(Option::<Payload>::deserialize(deserializer)?.map(|p| p.0))
+        }
+    }
+
+    pub(crate) fn encode(data: &[u8]) -> String {
+        let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    pub(crate) fn decode(text: &str) -> Option<Vec<u8>> {
+        let input = text.as_bytes();
+        if !input.len().is_multiple_of(4) {
+            return None;
+        }
+        let mut out = Vec::with_capacity(input.len() / 4 * 3);
+        for (index, chunk) in input.chunks(4).enumerate() {
+            let last = index + 1 == input.len() / 4;
+            let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+            if padding > 2 || (padding > 0 && !last) {
+                return None;
+            }
+            let mut n = 0u32;
+            for &c in &chunk[..4 - padding] {
+                let value = ALPHABET.iter().position(|a| *a == c)? as u32;
+                n = (n << 6) | value;
+            }
+            n <<= 6 * padding as u32;
+            let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+            out.extend_from_slice(&bytes[..3 - padding]);
+        }
+        Some(out)
+    }
+}
@@ -33,7 +33,8 @@ use crate::telemetry::{FormatterMode, PlainFormatter, TelemetryFormatter};
 ///   "context": {
 ///     "file": "/tmp/input.json",
 ///     "user_id": 12345
-///   }
+///   },
+///   "severity": "warning"
 /// }
 /// ```
 ///
@@ -44,6 +45,9 @@ use crate::telemetry::{FormatterMode, PlainFormatter, TelemetryFormatter};
 /// - `"runner"`: Requires `session` (string) and `step` (u64)
 /// - `"app"`: No additional fields
 ///
+/// `severity` is omitted for the default [`ErrorSeverity::Error`], so events
+/// written before severities existed read back unchanged.
+///
 /// See `docs/schemas/error_event.json` for the complete JSON Schema specification.
 ///
 /// # Examples
@@ -62,6 +66,7 @@ use crate::telemetry::{FormatterMode, PlainFormatter, TelemetryFormatter};
 /// let json_str = serde_json::to_string(&event).unwrap();
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ErrorEvent {
     /// Timestamp at which the error occurred.
     #[serde(default = "chrono::Utc::now")]
@@ -78,6 +83,9 @@ pub struct ErrorEvent {
     /// Optional additional context data as a JSON value.
     #[serde(default)]
     pub context: serde_json::Value,
+    /// How serious the event is.
+    #[serde(default, skip_serializing_if = "ErrorSeverity::is_default")]
+    pub severity: ErrorSeverity,
 }
 
 impl ErrorEvent {
@@ -99,6 +107,7 @@ impl ErrorEvent {
             error,
             tags: Vec::new(),
             context: serde_json::Value::Null,
+            severity: ErrorSeverity::default(),
         }
     }
 
@@ -117,6 +126,7 @@ impl ErrorEvent {
             error,
             tags: Vec::new(),
             context: serde_json::Value::Null,
+            severity: ErrorSeverity::default(),
         }
     }
 
@@ -138,6 +148,7 @@ impl ErrorEvent {
             error,
             tags: Vec::new(),
             context: serde_json::Value::Null,
+            severity: ErrorSeverity::default(),
         }
     }
 
@@ -156,6 +167,7 @@ impl ErrorEvent {
             error,
             tags: Vec::new(),
             context: serde_json::Value::Null,
+            severity: ErrorSeverity::default(),
         }
     }
 
@@ -201,10 +213,141 @@ impl ErrorEvent {
         self.context = context;
         self
     }
+
+    /// Set the severity of this error event.
+    ///
+    /// # Example
+    /// ```
+    /// use weavegraph::channels::errors::{ErrorEvent, ErrorSeverity, WeaveError};
+    ///
+    /// let warning = ErrorEvent::node("my_node", 1, WeaveError::msg("Empty input"))
+    ///     .with_severity(ErrorSeverity::Warning);
+    /// assert!(!warning.is_fatal());
+    /// ```
+    #[must_use]
+    pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Whether this event has [`ErrorSeverity::Fatal`] severity.
+    #[must_use]
+    pub fn is_fatal(&self) -> bool {
+        self.severity == ErrorSeverity::Fatal
+    }
+
+    /// The workflow step recorded in the scope, if any.
+    ///
+    /// App-scoped events carry no step.
+    #[must_use]
+    pub fn step(&self) -> Option<u64> {
+        match &self.scope {
+            ErrorScope::Node { step, .. }
+            | ErrorScope::Scheduler { step }
+            | ErrorScope::Runner { step, .. } => Some(*step),
+            ErrorScope::App => None,
+        }
+    }
+}
+
+/// How serious an [`ErrorEvent`] is, from least to most severe.
+///
+/// Events default to [`Error`](Self::Error). Reducers such as
+/// [`BoundedErrors`](crate::reducers::BoundedErrors) use the severity to
+/// decide what to keep.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    /// Informational record, such as an audit entry.
+    Info,
+    /// Something unexpected that the workflow recovered from.
+    Warning,
+    /// A failure that did not end the run on its own.
+    #[default]
+    Error,
+    /// A failure that ended the run.
+    Fatal,
+}
+
+impl ErrorSeverity {
+    /// Every severity, from least to most severe.
+    pub const ALL: [ErrorSeverity; 4] = [Self::Info, Self::Warning, Self::Error, Self::Fatal];
+
+    /// Stable lowercase label, matching the serialized form.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::fmt::Display for ErrorSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Filtering helpers over a list of [`ErrorEvent`]s, such as the `errors`
+/// field of a [`StateSnapshot`](crate::state::StateSnapshot).
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::channels::errors::{ErrorEvent, ErrorEventsExt, ErrorSeverity, WeaveError};
+///
+/// let errors = vec![
+///     ErrorEvent::node("a", 1, WeaveError::msg("retrying")).with_severity(ErrorSeverity::Warning),
+///     ErrorEvent::node("b", 3, WeaveError::msg("gave up")).with_severity(ErrorSeverity::Fatal),
+/// ];
+/// assert_eq!(errors.fatal().len(), 1);
+/// assert_eq!(errors.since_step(2).len(), 1);
+/// assert_eq!(errors.at_least(ErrorSeverity::Warning).len(), 2);
+/// ```
+pub trait ErrorEventsExt {
+    /// Events with [`ErrorSeverity::Fatal`] severity, oldest first.
+    fn fatal(&self) -> Vec<&ErrorEvent>;
+
+    /// Events at `severity` or above, oldest first.
+    fn at_least(&self, severity: ErrorSeverity) -> Vec<&ErrorEvent>;
+
+    /// Events recorded at `step` or later, oldest first.
+    ///
+    /// App-scoped events carry no step and are never included.
+    fn since_step(&self, step: u64) -> Vec<&ErrorEvent>;
+}
+
+impl ErrorEventsExt for [ErrorEvent] {
+    fn fatal(&self) -> Vec<&ErrorEvent> {
+        self.iter().filter(|event| event.is_fatal()).collect()
+    }
+
+    fn at_least(&self, severity: ErrorSeverity) -> Vec<&ErrorEvent> {
+        self.iter()
+            .filter(|event| event.severity >= severity)
+            .collect()
+    }
+
+    fn since_step(&self, step: u64) -> Vec<&ErrorEvent> {
+        self.iter()
+            .filter(|event| event.step().is_some_and(|at| at >= step))
+            .collect()
+    }
 }
 
 /// Scope metadata describing where an [`ErrorEvent`] originated.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "scope", rename_all = "snake_case")]
 pub enum ErrorScope {
     /// Error originated in a node execution.
@@ -235,6 +378,7 @@ pub enum ErrorScope {
 ///
 /// This type supports nested causes and optional machine-readable details.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WeaveError {
     /// Primary human-readable error message.
     pub message: String,
@@ -289,6 +433,33 @@ impl WeaveError {
         self.cause = Some(Box::new(cause));
         self
     }
+
+    /// Convert any error and its [`source`](std::error::Error::source) chain
+    /// into a `WeaveError` ladder, one level per source.
+    ///
+    /// A `WeaveError` anywhere in the chain is kept as is, details included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::channels::errors::WeaveError;
+    ///
+    /// let io = std::io::Error::other("disk full");
+    /// let error = WeaveError::from_error(&io);
+    /// assert_eq!(error.message, "disk full");
+    /// assert!(error.cause.is_none());
+    /// ```
+    #[must_use]
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        if let Some(weave) = error.downcast_ref::<WeaveError>() {
+            return weave.clone();
+        }
+        let converted = WeaveError::msg(error.to_string());
+        match error.source() {
+            Some(source) => converted.with_cause(WeaveError::from_error(source)),
+            None => converted,
+        }
+    }
 }
 
 /// Format error events with explicit color mode control.
@@ -1,12 +1,16 @@
 //! Channel types that form the typed state slots of a workflow's [`VersionedState`](crate::state::VersionedState).
 use crate::types::ChannelType;
+use thiserror::Error;
 
+mod blobs;
 /// Error event and scope types for structured workflow error capture.
 pub mod errors;
 mod errors_channel;
 mod extras;
 mod messages;
 
+pub use blobs::{Blob, BlobsChannel};
+pub(crate) use blobs::{base64_bytes, digest};
 pub use errors::*;
 pub use errors_channel::ErrorsChannel;
 pub use extras::ExtrasChannel;
@@ -33,4 +37,50 @@ pub trait Channel<T>: Sync + Send {
     fn get_mut(&mut self) -> &mut T;
     /// Returns `true` if this channel's data should be persisted across steps.
     fn persistent(&self) -> bool;
+
+    /// Increments the version counter, failing instead of wrapping at `u32::MAX`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChannelVersionOverflow`] if the version is already `u32::MAX`;
+    /// the version is left unchanged.
+    fn bump_version(&mut self) -> Result<u32, ChannelVersionOverflow> {
+        let next = ChannelVersionOverflow::next(self.get_channel_type(), self.version())?;
+        self.set_version(next);
+        Ok(next)
+    }
+}
+
+/// A channel version counter would exceed `u32::MAX`.
+///
+/// Versions only grow, so a session that hits this limit must be continued
+/// in a new session (for example from its final state).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[error("version overflow on {channel} channel at version {version}")]
+#[cfg_attr(
+    feature = "diagnostics",
+    diagnostic(
+        code(weavegraph::channels::version_overflow),
+        help(
+            "Channel versions are u32 and never wrap; start a new session from the current state."
+        )
+    )
+)]
+pub struct ChannelVersionOverflow {
+    /// The channel whose version overflowed.
+    pub channel: ChannelType,
+    /// The version that could not be incremented.
+    pub version: u32,
+}
+
+impl ChannelVersionOverflow {
+    /// Return `version + 1`, or an overflow error for `channel`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChannelVersionOverflow` when `version` is `u32::MAX`.
+    pub fn next(channel: ChannelType, version: u32) -> Result<u32, Self> {
+        version.checked_add(1).ok_or(Self { channel, version })
+    }
 }
@@ -4,11 +4,23 @@
 //! express routing intent without mutating application state directly. The
 //! barrier aggregates these directives in a deterministic order and the runner
 //! reconciles them with unconditional / conditional edges.
+//!
+//! [`NodeCommand`]s are addressed to a specific node instead: the barrier
+//! queues them in a per-node inbox that the target reads from its snapshot
+//! with [`StateSnapshot::commands_for`](crate::state::StateSnapshot::commands_for)
+//! the next time it runs, after which the runner clears them.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::types::NodeKind;
 
+/// `extra` key holding the queue of undelivered [`NodeCommand`]s.
+pub const COMMAND_INBOX_KEY: &str = "__weavegraph_inbox__";
+
 /// Route identifier used by frontier commands.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NodeRoute {
     /// Route to another node in the graph.
     Node(NodeKind),
@@ -37,10 +49,152 @@ impl From<NodeKind> for NodeRoute {
 }
 
 /// Command emitted by a node to manipulate the next frontier.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FrontierCommand {
     /// Append additional routes to the existing frontier calculation.
     Append(Vec<NodeRoute>),
     /// Replace the default routes emitted for the node.
     Replace(Vec<NodeRoute>),
 }
+
+// ============================================================================
+// Node commands
+// ============================================================================
+
+/// A typed directive from one node to another.
+///
+/// Attach commands to a [`NodePartial`](crate::node::NodePartial) with
+/// [`with_command`](crate::node::NodePartial::with_command). The target sees
+/// every command queued before its superstep began, highest priority first,
+/// and the runner removes them once the target has run. Commands for a node
+/// that is not scheduled wait in the inbox until it is.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use weavegraph::control::NodeCommand;
+/// use weavegraph::types::NodeKind;
+///
+/// let command = NodeCommand::new(NodeKind::Custom("crawler".into()), "throttle")
+///     .with_payload(json!({"max_rps": 2}))
+///     .with_priority(10);
+/// assert_eq!(command.payload_as::<serde_json::Value>().unwrap()["max_rps"], 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeCommand {
+    /// Node the command is addressed to.
+    pub target: NodeKind,
+    /// Directive name, interpreted by the target.
+    pub name: String,
+    /// Directive arguments.
+    #[serde(default)]
+    pub payload: Value,
+    /// Delivery priority; higher values are delivered first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Node that sent the command, filled in by the barrier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<NodeKind>,
+    /// Queue position, filled in by the barrier; breaks priority ties.
+    #[serde(default)]
+    pub sequence: u64,
+}
+
+impl NodeCommand {
+    /// Create a command with a `null` payload and priority 0.
+    #[must_use]
+    pub fn new(target: NodeKind, name: impl Into<String>) -> Self {
+        Self {
+            target,
+            name: name.into(),
+            payload: Value::Null,
+            priority: 0,
+            from: None,
+            sequence: 0,
+        }
+    }
+
+    /// Set the directive arguments.
+    #[must_use]
+    pub fn with_payload(mut self, payload: Value) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Serialize `payload` as the directive arguments.
+    pub fn with_typed_payload<T: Serialize>(mut self, payload: &T) -> serde_json::Result<Self> {
+        self.payload = serde_json::to_value(payload)?;
+        Ok(self)
+    }
+
+    /// Set the delivery priority.
+    #[must_use]
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Deserialize the directive arguments.
+    pub fn payload_as<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        T::deserialize(&self.payload)
+    }
+}
+
+/// Undelivered commands as stored under [`COMMAND_INBOX_KEY`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct CommandInbox {
+    /// Sequence number assigned to the next queued command.
+    pub(crate) next_sequence: u64,
+    /// Queued commands in sequence order.
+    pub(crate) pending: Vec<NodeCommand>,
+}
+
+impl CommandInbox {
+    /// Read the inbox from an `extra` value; malformed values read as empty.
+    pub(crate) fn from_value(value: Option<&Value>) -> Self {
+        value
+            .and_then(|value| Self::deserialize(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Queue `command` from `from`, assigning its sequence number.
+    pub(crate) fn push(&mut self, from: &NodeKind, mut command: NodeCommand) {
+        command.from = Some(from.clone());
+        command.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.pending.push(command);
+    }
+
+    /// Drop commands for `ran` nodes queued before sequence `watermark`.
+    ///
+    /// Returns whether anything was removed.
+    pub(crate) fn consume(&mut self, ran: &[NodeKind], watermark: u64) -> bool {
+        let before = self.pending.len();
+        self.pending
+            .retain(|command| command.sequence >= watermark || !ran.contains(&command.target));
+        self.pending.len() != before
+    }
+
+    /// Commands for `node`, highest priority first, then in queue order.
+    pub(crate) fn for_node(&self, node: &NodeKind) -> Vec<NodeCommand> {
+        let mut commands: Vec<NodeCommand> = self
+            .pending
+            .iter()
+            .filter(|command| command.target == *node)
+            .cloned()
+            .collect();
+        commands.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.sequence.cmp(&b.sequence))
+        });
+        commands
+    }
+}
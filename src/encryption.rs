@@ -0,0 +1,320 @@
+//! Envelope encryption with per-tenant keys.
+//!
+//! A [`KeyProvider`] maps a tenant (a customer, a workspace, or a single
+//! session) to the key its data is sealed with. [`Envelope::seal`] encrypts a
+//! byte string with the tenant's current key and records the tenant and key id
+//! alongside the ciphertext, so [`Envelope::open`] can find the right key after
+//! a rotation. Data sealed for one tenant cannot be opened with another
+//! tenant's key: the tenant and key id are bound in as associated data.
+//!
+//! Keys are never part of the crate's configuration; implement [`KeyProvider`]
+//! over your KMS or secret store, or use [`StaticKeyProvider`] for tests and
+//! single-process deployments. The trait is not tied to events, so the same
+//! provider can back encryption of other persisted data.
+//!
+//! Event sinks use this module through
+//! [`EncryptedSink`](crate::event_bus::EncryptedSink).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use weavegraph::encryption::{EncryptionKey, Envelope, StaticKeyProvider};
+//!
+//! let keys = StaticKeyProvider::new()
+//!     .with_key("acme", EncryptionKey::new("acme-2024", [7; 32]))
+//!     .with_key("globex", EncryptionKey::new("globex-2024", [9; 32]));
+//!
+//! let envelope = Envelope::seal(&keys, "acme", b"quarterly numbers", b"").unwrap();
+//! assert_eq!(envelope.key_id, "acme-2024");
+//! assert_eq!(envelope.open(&keys, b"").unwrap(), b"quarterly numbers");
+//! ```
+
+use std::fmt;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::channels::base64_bytes;
+
+/// Algorithm identifier recorded in every [`Envelope`].
+pub const ALGORITHM: &str = "xchacha20poly1305";
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors raised while sealing or opening an [`Envelope`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum EncryptionError {
+    /// The provider has no key for the tenant.
+    #[error("no encryption key for tenant {tenant:?}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::encryption::unknown_tenant),
+            help("Register a key for the tenant with your KeyProvider.")
+        )
+    )]
+    UnknownTenant {
+        /// Tenant that was looked up.
+        tenant: String,
+    },
+
+    /// The provider does not know the key an envelope was sealed with.
+    #[error("tenant {tenant:?} has no key {key_id:?}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::encryption::unknown_key),
+            help("Keep retired keys available for decryption after a rotation.")
+        )
+    )]
+    UnknownKey {
+        /// Tenant named by the envelope.
+        tenant: String,
+        /// Key id named by the envelope.
+        key_id: String,
+    },
+
+    /// The envelope names an algorithm this build does not support.
+    #[error("unsupported envelope algorithm {0:?}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::encryption::unsupported_algorithm))
+    )]
+    UnsupportedAlgorithm(String),
+
+    /// The envelope is not well formed.
+    #[error("malformed envelope: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::encryption::malformed))
+    )]
+    Malformed(String),
+
+    /// Encryption failed, or the ciphertext did not authenticate.
+    #[error("envelope for tenant {tenant:?} failed to authenticate")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::encryption::authentication),
+            help("The data was altered or sealed with a different key or associated data.")
+        )
+    )]
+    Authentication {
+        /// Tenant named by the envelope.
+        tenant: String,
+    },
+}
+
+// ============================================================================
+// Keys
+// ============================================================================
+
+/// A 256-bit data key with a stable identifier.
+///
+/// The id is stored in every envelope sealed with the key; `Debug` never
+/// prints the key material.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey {
+    id: String,
+    key: [u8; 32],
+}
+
+impl EncryptionKey {
+    /// Create a key from its id and raw bytes.
+    pub fn new(id: impl Into<String>, key: [u8; 32]) -> Self {
+        Self { id: id.into(), key }
+    }
+
+    /// The key id recorded in envelopes.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.key))
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Source of per-tenant data keys.
+///
+/// Implementations are called once per sealed or opened envelope and should
+/// cache keys fetched from a remote KMS.
+pub trait KeyProvider: Send + Sync {
+    /// The key new data for `tenant` is sealed with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncryptionError::UnknownTenant`] if the tenant has no key.
+    fn encryption_key(&self, tenant: &str) -> Result<EncryptionKey, EncryptionError>;
+
+    /// The key with id `key_id` for `tenant`, current or retired.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EncryptionError::UnknownKey`] if the key is not available.
+    fn decryption_key(&self, tenant: &str, key_id: &str) -> Result<EncryptionKey, EncryptionError>;
+}
+
+/// In-memory [`KeyProvider`] holding a fixed set of keys per tenant.
+///
+/// The most recently added key for a tenant seals new data; earlier keys
+/// remain available for opening, which models a rotation.
+#[derive(Clone, Debug, Default)]
+pub struct StaticKeyProvider {
+    keys: FxHashMap<String, Vec<EncryptionKey>>,
+}
+
+impl StaticKeyProvider {
+    /// Create a provider with no keys.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` for `tenant`, making it the tenant's current key.
+    #[must_use]
+    pub fn with_key(mut self, tenant: impl Into<String>, key: EncryptionKey) -> Self {
+        self.keys.entry(tenant.into()).or_default().push(key);
+        self
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn encryption_key(&self, tenant: &str) -> Result<EncryptionKey, EncryptionError> {
+        self.keys
+            .get(tenant)
+            .and_then(|keys| keys.last())
+            .cloned()
+            .ok_or_else(|| EncryptionError::UnknownTenant {
+                tenant: tenant.to_string(),
+            })
+    }
+
+    fn decryption_key(&self, tenant: &str, key_id: &str) -> Result<EncryptionKey, EncryptionError> {
+        self.keys
+            .get(tenant)
+            .and_then(|keys| keys.iter().find(|key| key.id == key_id))
+            .cloned()
+            .ok_or_else(|| EncryptionError::UnknownKey {
+                tenant: tenant.to_string(),
+                key_id: key_id.to_string(),
+            })
+    }
+}
+
+// ============================================================================
+// Envelope
+// ============================================================================
+
+/// Ciphertext sealed for one tenant, with what is needed to open it.
+///
+/// Serializes as a JSON object with base64 `nonce` and `ciphertext`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Envelope {
+    /// Cipher used; always [`ALGORITHM`] today.
+    pub alg: String,
+    /// Tenant whose key sealed the data.
+    pub tenant: String,
+    /// Id of the key that sealed the data.
+    pub key_id: String,
+    /// Base64 nonce.
+    pub nonce: String,
+    /// Base64 ciphertext including the authentication tag.
+    pub ciphertext: String,
+}
+
+impl Envelope {
+    /// Encrypt `plaintext` with `tenant`'s current key.
+    ///
+    /// `aad` is authenticated but not encrypted; the same bytes must be
+    /// passed to [`open`](Self::open).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider has no key for `tenant`.
+    pub fn seal(
+        provider: &dyn KeyProvider,
+        tenant: &str,
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Self, EncryptionError> {
+        let key = provider.encryption_key(tenant)?;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = key
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: &bound_aad(tenant, &key.id, aad),
+                },
+            )
+            .map_err(|_| EncryptionError::Authentication {
+                tenant: tenant.to_string(),
+            })?;
+        Ok(Self {
+            alg: ALGORITHM.to_string(),
+            tenant: tenant.to_string(),
+            key_id: key.id,
+            nonce: base64_bytes::encode(&nonce),
+            ciphertext: base64_bytes::encode(&ciphertext),
+        })
+    }
+
+    /// Decrypt the envelope with the key it names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is unavailable, the envelope is malformed,
+    /// or the ciphertext or `aad` were altered.
+    pub fn open(&self, provider: &dyn KeyProvider, aad: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        if self.alg != ALGORITHM {
+            return Err(EncryptionError::UnsupportedAlgorithm(self.alg.clone()));
+        }
+        let nonce = base64_bytes::decode(&self.nonce)
+            .filter(|nonce| nonce.len() == 24)
+            .ok_or_else(|| EncryptionError::Malformed("invalid nonce".into()))?;
+        let ciphertext = base64_bytes::decode(&self.ciphertext)
+            .ok_or_else(|| EncryptionError::Malformed("invalid ciphertext".into()))?;
+        let key = provider.decryption_key(&self.tenant, &self.key_id)?;
+        key.cipher()
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &bound_aad(&self.tenant, &self.key_id, aad),
+                },
+            )
+            .map_err(|_| EncryptionError::Authentication {
+                tenant: self.tenant.clone(),
+            })
+    }
+}
+
+/// Associated data binding the tenant and key id to the caller's `aad`.
+fn bound_aad(tenant: &str, key_id: &str, aad: &[u8]) -> Vec<u8> {
+    let mut bound =
+        Vec::with_capacity(ALGORITHM.len() + tenant.len() + key_id.len() + aad.len() + 3);
+    for part in [ALGORITHM.as_bytes(), tenant.as_bytes(), key_id.as_bytes()] {
+        bound.extend_from_slice(part);
+        bound.push(0);
+    }
+    bound.extend_from_slice(aad);
+    bound
+}
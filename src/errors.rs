@@ -0,0 +1,77 @@
+//! Context and conversions for errors that cross crate boundaries.
+//!
+//! Nodes often call into other libraries (a vector store, a guard pipeline,
+//! a checkpointer) whose error types know nothing about [`NodeError`].
+//! [`ErrorExt::context`] wraps any error in a [`WeaveError`] ladder: the new
+//! message on top, the original error and its
+//! [`source`](std::error::Error::source) chain as causes. `WeaveError`
+//! converts into [`NodeError::Context`], so `?` works in node bodies without
+//! a `map_err` per call, and the ladder can be recorded as an
+//! [`ErrorEvent`](crate::channels::errors::ErrorEvent) unchanged.
+//!
+//! [`CheckpointerError`](crate::runtimes::CheckpointerError) converts into
+//! [`NodeError::Checkpointer`] directly. Companion crates provide `From`
+//! impls for their own error types behind their weavegraph integration
+//! features.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::errors::ErrorExt;
+//! use weavegraph::node::NodeError;
+//!
+//! fn load_threshold(raw: &str) -> Result<f32, NodeError> {
+//!     let threshold = raw
+//!         .parse::<f32>()
+//!         .with_context(|| format!("reading threshold {raw:?}"))?;
+//!     Ok(threshold)
+//! }
+//!
+//! let NodeError::Context(error) = load_threshold("high").unwrap_err() else {
+//!     unreachable!()
+//! };
+//! assert_eq!(error.message, "reading threshold \"high\"");
+//! assert_eq!(error.cause.unwrap().message, "invalid float literal");
+//! ```
+
+pub use crate::channels::errors::WeaveError;
+#[cfg(doc)]
+use crate::node::NodeError;
+
+/// Attach a context message to a failed [`Result`].
+pub trait ErrorExt<T> {
+    /// Wrap the error in a [`WeaveError`] whose message is `message` and whose
+    /// cause is the original error chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wrapped error if `self` is `Err`.
+    fn context(self, message: impl Into<String>) -> Result<T, WeaveError>;
+
+    /// Like [`context`](Self::context), building the message only on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the wrapped error if `self` is `Err`.
+    fn with_context<M, F>(self, message: F) -> Result<T, WeaveError>
+    where
+        M: Into<String>,
+        F: FnOnce() -> M;
+}
+
+impl<T, E> ErrorExt<T> for Result<T, E>
+where
+    E: std::error::Error + 'static,
+{
+    fn context(self, message: impl Into<String>) -> Result<T, WeaveError> {
+        self.map_err(|error| WeaveError::msg(message).with_cause(WeaveError::from_error(&error)))
+    }
+
+    fn with_context<M, F>(self, message: F) -> Result<T, WeaveError>
+    where
+        M: Into<String>,
+        F: FnOnce() -> M,
+    {
+        self.map_err(|error| WeaveError::msg(message()).with_cause(WeaveError::from_error(&error)))
+    }
+}
@@ -0,0 +1,402 @@
+//! In-memory rollups of the event stream for lightweight monitoring.
+//!
+//! [`AggregatingSink`] keeps running counts instead of events: event rates per
+//! scope over a sliding window, error counts per tag, and per-node latency.
+//! Read them with [`AggregatingSink::snapshot`], or have the sink forward a
+//! summary event to another sink at a fixed interval. Node latency and error
+//! tags come from the [`STEP_METRICS_SCOPE`] events a runner publishes when
+//! built with
+//! [`AppRunnerBuilder::publish_step_metrics`](crate::runtimes::AppRunnerBuilder::publish_step_metrics);
+//! failed LLM streams count under the `llm` tag.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Result as IoResult;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::event::{
+    Event, LLMStreamingEventScope, NodeEvent, ROLLUP_SUMMARY_SCOPE, STEP_METRICS_SCOPE,
+};
+use super::sink::EventSink;
+use crate::runtimes::StepReport;
+
+/// Sliding window used for event rates unless configured otherwise.
+pub const DEFAULT_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Tag under which failed LLM streams are counted.
+const LLM_ERROR_TAG: &str = "llm";
+
+/// Tag under which errors without tags are counted.
+const UNTAGGED: &str = "untagged";
+
+// ============================================================================
+// Rollups
+// ============================================================================
+
+/// Latency rollup for one node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeLatency {
+    /// Completed runs observed.
+    pub runs: u64,
+    /// Sum of run durations, in microseconds.
+    pub total_micros: u64,
+    /// Longest run, in microseconds.
+    pub max_micros: u64,
+}
+
+impl NodeLatency {
+    /// Mean run duration, or zero before the first run.
+    #[must_use]
+    pub fn average(&self) -> Duration {
+        Duration::from_micros(self.total_micros.checked_div(self.runs).unwrap_or(0))
+    }
+
+    fn record(&mut self, micros: u64) {
+        self.runs += 1;
+        self.total_micros = self.total_micros.saturating_add(micros);
+        self.max_micros = self.max_micros.max(micros);
+    }
+}
+
+/// Point-in-time view of an [`AggregatingSink`]'s rollups.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct EventRollup {
+    /// Length of the window behind `rate_by_scope`, in microseconds.
+    pub window_micros: u64,
+    /// Events handled since the sink was created or reset.
+    pub total_events: u64,
+    /// Events per scope since the sink was created or reset.
+    pub events_by_scope: BTreeMap<String, u64>,
+    /// Events per second per scope over the sliding window.
+    pub rate_by_scope: BTreeMap<String, f64>,
+    /// Errors per tag; an error with several tags counts once under each.
+    pub errors_by_tag: BTreeMap<String, u64>,
+    /// Latency per encoded node kind.
+    pub node_latency: BTreeMap<String, NodeLatency>,
+}
+
+impl EventRollup {
+    /// Events per second for `scope` over the window, zero if none arrived.
+    #[must_use]
+    pub fn events_per_sec(&self, scope: &str) -> f64 {
+        self.rate_by_scope.get(scope).copied().unwrap_or(0.0)
+    }
+
+    /// Errors counted under `tag`.
+    #[must_use]
+    pub fn error_count(&self, tag: &str) -> u64 {
+        self.errors_by_tag.get(tag).copied().unwrap_or(0)
+    }
+
+    /// Mean latency of `node` (as encoded by [`NodeKind::encode`](crate::types::NodeKind::encode)).
+    #[must_use]
+    pub fn average_latency(&self, node: &str) -> Option<Duration> {
+        self.node_latency.get(node).map(NodeLatency::average)
+    }
+
+    /// The summary event an [`AggregatingSink`] forwards, with the rollup as
+    /// `rollup` metadata.
+    #[must_use]
+    pub fn to_event(&self) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert(
+            "rollup".to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        );
+        let message = format!(
+            "events={} scopes={} errors={} nodes={}",
+            self.total_events,
+            self.events_by_scope.len(),
+            self.errors_by_tag.values().sum::<u64>(),
+            self.node_latency.len()
+        );
+        Event::Node(
+            NodeEvent::new(None, None, ROLLUP_SUMMARY_SCOPE.to_string(), message)
+                .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a summary event, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node_event) = event else {
+            return None;
+        };
+        if node_event.scope() != ROLLUP_SUMMARY_SCOPE {
+            return None;
+        }
+        serde_json::from_value(node_event.metadata().get("rollup")?.clone()).ok()
+    }
+}
+
+// ============================================================================
+// Step metrics events
+// ============================================================================
+
+/// The [`STEP_METRICS_SCOPE`] event describing one completed superstep.
+pub(crate) fn step_metrics_event(session_id: &str, report: &StepReport) -> Event {
+    let nodes: Vec<Value> = report
+        .node_metrics
+        .iter()
+        .map(|(node, metrics)| {
+            json!({"node": node.encode(), "duration_micros": metrics.duration_micros})
+        })
+        .collect();
+    let mut error_tags: BTreeMap<&str, u64> = BTreeMap::new();
+    for error in &report.barrier_outcome.errors {
+        if error.tags.is_empty() {
+            *error_tags.entry(UNTAGGED).or_default() += 1;
+        }
+        for tag in &error.tags {
+            *error_tags.entry(tag).or_default() += 1;
+        }
+    }
+    let mut metadata = FxHashMap::default();
+    metadata.insert("session_id".to_string(), json!(session_id));
+    metadata.insert("nodes".to_string(), Value::Array(nodes));
+    metadata.insert("error_tags".to_string(), json!(error_tags));
+    let message = format!(
+        "step={} nodes={} errors={}",
+        report.step,
+        report.node_metrics.len(),
+        report.barrier_outcome.errors.len()
+    );
+    Event::Node(
+        NodeEvent::new(
+            None,
+            Some(report.step),
+            STEP_METRICS_SCOPE.to_string(),
+            message,
+        )
+        .with_metadata(metadata),
+    )
+}
+
+// ============================================================================
+// Sink
+// ============================================================================
+
+#[derive(Default)]
+struct Aggregates {
+    arrivals: VecDeque<(Instant, String)>,
+    rollup: EventRollup,
+}
+
+impl Aggregates {
+    fn record(&mut self, event: &Event, now: Instant, window: Duration) {
+        let scope = event.scope_label().unwrap_or_default().to_string();
+        self.rollup.total_events += 1;
+        *self
+            .rollup
+            .events_by_scope
+            .entry(scope.clone())
+            .or_default() += 1;
+        self.arrivals.push_back((now, scope));
+        self.prune(now, window);
+
+        match event {
+            Event::Node(node_event) if node_event.scope() == STEP_METRICS_SCOPE => {
+                self.record_step(node_event.metadata());
+            }
+            Event::LLM(llm) if matches!(llm.scope(), LLMStreamingEventScope::Error) => {
+                *self
+                    .rollup
+                    .errors_by_tag
+                    .entry(LLM_ERROR_TAG.to_string())
+                    .or_default() += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn record_step(&mut self, metadata: &FxHashMap<String, Value>) {
+        for entry in metadata
+            .get("nodes")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(node), Some(micros)) = (
+                entry.get("node").and_then(Value::as_str),
+                entry.get("duration_micros").and_then(Value::as_u64),
+            ) {
+                self.rollup
+                    .node_latency
+                    .entry(node.to_string())
+                    .or_default()
+                    .record(micros);
+            }
+        }
+        for (tag, count) in metadata
+            .get("error_tags")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            *self.rollup.errors_by_tag.entry(tag.clone()).or_default() +=
+                count.as_u64().unwrap_or(0);
+        }
+    }
+
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while let Some((at, _)) = self.arrivals.front() {
+            if now.duration_since(*at) <= window {
+                break;
+            }
+            self.arrivals.pop_front();
+        }
+    }
+
+    fn snapshot(&mut self, now: Instant, window: Duration) -> EventRollup {
+        self.prune(now, window);
+        let seconds = window.as_secs_f64().max(f64::EPSILON);
+        let mut rates: BTreeMap<String, f64> = BTreeMap::new();
+        for (_, scope) in &self.arrivals {
+            *rates.entry(scope.clone()).or_default() += 1.0;
+        }
+        for rate in rates.values_mut() {
+            *rate /= seconds;
+        }
+        EventRollup {
+            window_micros: u64::try_from(window.as_micros()).unwrap_or(u64::MAX),
+            rate_by_scope: rates,
+            ..self.rollup.clone()
+        }
+    }
+}
+
+struct Summaries {
+    interval: Duration,
+    last: Instant,
+    target: Box<dyn EventSink>,
+}
+
+/// Sink that maintains rollups of the events it handles.
+///
+/// Clones share the same rollups, so keep one clone to query while the event
+/// bus owns another.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use weavegraph::event_bus::{AggregatingSink, Event, EventSink, MemorySink};
+///
+/// let summaries = MemorySink::new();
+/// let mut sink = AggregatingSink::new()
+///     .with_summaries(Duration::from_secs(30), summaries.clone());
+/// sink.handle(&Event::diagnostic("router", "picked tool")).unwrap();
+/// sink.handle(&Event::diagnostic("router", "picked answer")).unwrap();
+///
+/// let rollup = sink.snapshot();
+/// assert_eq!(rollup.events_by_scope["router"], 2);
+/// assert!(rollup.events_per_sec("router") > 0.0);
+/// ```
+#[derive(Clone)]
+pub struct AggregatingSink {
+    window: Duration,
+    aggregates: Arc<Mutex<Aggregates>>,
+    summaries: Option<Arc<Mutex<Summaries>>>,
+}
+
+impl Default for AggregatingSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregatingSink {
+    /// A sink with a [`DEFAULT_RATE_WINDOW`] rate window and no summaries.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            window: DEFAULT_RATE_WINDOW,
+            aggregates: Arc::default(),
+            summaries: None,
+        }
+    }
+
+    /// Compute event rates over the last `window` instead.
+    #[must_use]
+    pub fn with_rate_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Forward an [`EventRollup::to_event`] summary to `target` whenever
+    /// `interval` has passed since the previous one.
+    ///
+    /// Summaries are produced while handling events, so an idle bus
+    /// publishes none.
+    #[must_use]
+    pub fn with_summaries(mut self, interval: Duration, target: impl EventSink + 'static) -> Self {
+        self.summaries = Some(Arc::new(Mutex::new(Summaries {
+            interval,
+            last: Instant::now(),
+            target: Box::new(target),
+        })));
+        self
+    }
+
+    /// Current rollups.
+    #[must_use]
+    pub fn snapshot(&self) -> EventRollup {
+        self.aggregates
+            .lock()
+            .expect("AggregatingSink mutex poisoned")
+            .snapshot(Instant::now(), self.window)
+    }
+
+    /// Discard all rollups.
+    pub fn reset(&self) {
+        *self
+            .aggregates
+            .lock()
+            .expect("AggregatingSink mutex poisoned") = Aggregates::default();
+    }
+}
+
+impl EventSink for AggregatingSink {
+    fn handle(&mut self, event: &Event) -> IoResult<()> {
+        let now = Instant::now();
+        let summary = {
+            let mut aggregates = self
+                .aggregates
+                .lock()
+                .expect("AggregatingSink mutex poisoned");
+            aggregates.record(event, now, self.window);
+            let due = self.summaries.as_ref().is_some_and(|summaries| {
+                let summaries = summaries.lock().expect("AggregatingSink mutex poisoned");
+                now.duration_since(summaries.last) >= summaries.interval
+            });
+            due.then(|| aggregates.snapshot(now, self.window))
+        };
+        if let (Some(rollup), Some(summaries)) = (summary, &self.summaries) {
+            let mut summaries = summaries.lock().expect("AggregatingSink mutex poisoned");
+            summaries.last = now;
+            summaries.target.handle(&rollup.to_event())?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        match &self.summaries {
+            Some(summaries) => summaries
+                .lock()
+                .expect("AggregatingSink mutex poisoned")
+                .target
+                .flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "AggregatingSink".to_string()
+    }
+}
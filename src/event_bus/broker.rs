@@ -0,0 +1,285 @@
+//! Publishing events to message brokers such as Kafka and NATS.
+//!
+//! [`BrokerSink`] encodes each event in an [`EventFormat`], batches the
+//! resulting [`BrokerRecord`]s, and hands each batch to a [`BrokerPublisher`].
+//! Publishers for Kafka (`KafkaPublisher`, `kafka` feature) and NATS
+//! (`NatsPublisher`, `nats` feature) are provided; any other broker only
+//! needs a [`BrokerPublisher`] implementation.
+//!
+//! Records are partitioned by session: each record's key is the event's
+//! [`Event::sequence_key`], which Kafka hashes to choose a partition. The
+//! destination (Kafka topic or NATS subject) may contain the
+//! [`SESSION_PLACEHOLDER`], replaced with the session id (characters other
+//! than ASCII letters, digits, `-`, and `_` become `_`) or [`NO_SESSION`] for
+//! events without one, e.g. `weavegraph.events.{session}` on NATS.
+//!
+//! A batch is published when it reaches the batch size, when the oldest
+//! buffered record is older than the linger time and another event arrives,
+//! and on [`EventBus::flush`](super::EventBus::flush), which runners call
+//! when a run finishes. Records from a failed batch stay buffered and are
+//! retried with the next batch; beyond the pending limit the oldest are
+//! dropped. Either way the sink reports an error, which the bus surfaces as a
+//! [`SinkDiagnostic`](super::SinkDiagnostic) and in [`SinkHealth`](super::SinkHealth).
+//!
+//! Publishing blocks the bus's sink worker (sinks run on the blocking thread
+//! pool), so a sink must be driven by an [`EventBus`](super::EventBus) inside
+//! a Tokio runtime.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use weavegraph::event_bus::{
+//!     BrokerPublishError, BrokerPublisher, BrokerRecord, BrokerSink, EventBus,
+//! };
+//!
+//! struct Print;
+//!
+//! #[async_trait]
+//! impl BrokerPublisher for Print {
+//!     async fn publish(&self, batch: Vec<BrokerRecord>) -> Result<(), BrokerPublishError> {
+//!         for record in batch {
+//!             println!("{} [{:?}] {} bytes", record.destination, record.key, record.payload.len());
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let sink = BrokerSink::new(Print, "weavegraph.events.{session}").with_batch_size(50);
+//! let bus = EventBus::with_sink(sink);
+//! ```
+
+use std::any::type_name;
+use std::collections::VecDeque;
+use std::io::{self, Result as IoResult};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use super::codec::EventFormat;
+use super::event::Event;
+use super::sink::EventSink;
+
+/// Placeholder in a destination replaced with the event's session id.
+pub const SESSION_PLACEHOLDER: &str = "{session}";
+
+/// Destination token used for events that belong to no session.
+pub const NO_SESSION: &str = "_";
+
+/// Records published per batch by default.
+pub const DEFAULT_BROKER_BATCH_SIZE: usize = 100;
+
+/// How long a record may wait for its batch to fill by default.
+pub const DEFAULT_BROKER_LINGER: Duration = Duration::from_millis(100);
+
+// ============================================================================
+// Records and publishers
+// ============================================================================
+
+/// One encoded event addressed to a broker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BrokerRecord {
+    /// Kafka topic or NATS subject.
+    pub destination: String,
+    /// Partitioning key: the event's session id, if any.
+    pub key: Option<String>,
+    /// The event encoded in the sink's [`EventFormat`].
+    pub payload: Vec<u8>,
+}
+
+/// A batch, or part of one, that a broker did not accept.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[error("{message} ({} record(s) undelivered)", failed.len())]
+#[non_exhaustive]
+pub struct BrokerPublishError {
+    /// Records to retry with the next batch, in their original order.
+    pub failed: Vec<BrokerRecord>,
+    /// Description of the failure.
+    pub message: String,
+}
+
+impl BrokerPublishError {
+    /// `failed` could not be delivered because of `message`.
+    pub fn new(failed: Vec<BrokerRecord>, message: impl Into<String>) -> Self {
+        Self {
+            failed,
+            message: message.into(),
+        }
+    }
+}
+
+/// Client that delivers batches of records to a broker.
+#[async_trait]
+pub trait BrokerPublisher: Send + Sync + 'static {
+    /// Deliver `batch`, returning the records that were not accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the undelivered records; the sink retries them with its next batch.
+    async fn publish(&self, batch: Vec<BrokerRecord>) -> Result<(), BrokerPublishError>;
+
+    /// Identifier used in the sink's name, defaulting to the type name.
+    fn name(&self) -> String {
+        type_name::<Self>().to_string()
+    }
+}
+
+// ============================================================================
+// Sink
+// ============================================================================
+
+/// Sink that publishes batches of encoded events through a [`BrokerPublisher`].
+///
+/// See the [module documentation](self) for batching and partitioning.
+pub struct BrokerSink {
+    publisher: Arc<dyn BrokerPublisher>,
+    destination: String,
+    format: EventFormat,
+    batch_size: usize,
+    linger: Duration,
+    max_pending: usize,
+    pending: VecDeque<BrokerRecord>,
+    oldest: Option<Instant>,
+}
+
+impl BrokerSink {
+    /// Publish JSON-encoded events to `destination` through `publisher`.
+    pub fn new(publisher: impl BrokerPublisher, destination: impl Into<String>) -> Self {
+        Self {
+            publisher: Arc::new(publisher),
+            destination: destination.into(),
+            format: EventFormat::Json,
+            batch_size: DEFAULT_BROKER_BATCH_SIZE,
+            linger: DEFAULT_BROKER_LINGER,
+            max_pending: DEFAULT_BROKER_BATCH_SIZE * 10,
+            pending: VecDeque::new(),
+            oldest: None,
+        }
+    }
+
+    /// Encode events in `format` (one unframed event per record).
+    #[must_use]
+    pub fn with_format(mut self, format: EventFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Publish batches of up to `size` records (at least 1).
+    #[must_use]
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self.max_pending = self.max_pending.max(self.batch_size);
+        self
+    }
+
+    /// Publish a partial batch once its oldest record is `linger` old.
+    #[must_use]
+    pub fn with_linger(mut self, linger: Duration) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// Keep at most `limit` undelivered records (at least the batch size),
+    /// dropping the oldest beyond it.
+    #[must_use]
+    pub fn with_max_pending(mut self, limit: usize) -> Self {
+        self.max_pending = limit.max(self.batch_size);
+        self
+    }
+
+    /// Destination of events belonging to `session`.
+    ///
+    /// ```rust
+    /// # use async_trait::async_trait;
+    /// # use weavegraph::event_bus::{BrokerPublishError, BrokerPublisher, BrokerRecord, BrokerSink};
+    /// # struct Noop;
+    /// # #[async_trait]
+    /// # impl BrokerPublisher for Noop {
+    /// #     async fn publish(&self, _: Vec<BrokerRecord>) -> Result<(), BrokerPublishError> { Ok(()) }
+    /// # }
+    /// let sink = BrokerSink::new(Noop, "events.{session}");
+    /// assert_eq!(sink.destination_for(Some("user.42")), "events.user_42");
+    /// assert_eq!(sink.destination_for(None), "events._");
+    /// ```
+    #[must_use]
+    pub fn destination_for(&self, session: Option<&str>) -> String {
+        if !self.destination.contains(SESSION_PLACEHOLDER) {
+            return self.destination.clone();
+        }
+        let token = session.map_or_else(
+            || NO_SESSION.to_string(),
+            |session| {
+                session
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect()
+            },
+        );
+        self.destination.replace(SESSION_PLACEHOLDER, &token)
+    }
+
+    /// Publish full batches, or everything buffered when `all` is set or the
+    /// oldest record has lingered long enough.
+    fn publish_pending(&mut self, all: bool) -> IoResult<()> {
+        let lingered = self.oldest.is_some_and(|at| at.elapsed() >= self.linger);
+        while self.pending.len() >= self.batch_size
+            || ((all || lingered) && !self.pending.is_empty())
+        {
+            let count = self.pending.len().min(self.batch_size);
+            let batch: Vec<BrokerRecord> = self.pending.drain(..count).collect();
+            let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+                io::Error::other("broker sink must be driven by an event bus in a Tokio runtime")
+            })?;
+            if let Err(err) = runtime.block_on(self.publisher.publish(batch)) {
+                let message = err.message;
+                for record in err.failed.into_iter().rev() {
+                    self.pending.push_front(record);
+                }
+                let dropped = self.pending.len().saturating_sub(self.max_pending);
+                self.pending.drain(..dropped);
+                self.oldest = (!self.pending.is_empty()).then(Instant::now);
+                let mut report = format!(
+                    "broker publish failed: {message}; {} record(s) pending",
+                    self.pending.len()
+                );
+                if dropped > 0 {
+                    report.push_str(&format!(", {dropped} dropped"));
+                }
+                return Err(io::Error::other(report));
+            }
+        }
+        self.oldest = (!self.pending.is_empty()).then(|| self.oldest.unwrap_or_else(Instant::now));
+        Ok(())
+    }
+}
+
+impl EventSink for BrokerSink {
+    fn handle(&mut self, event: &Event) -> IoResult<()> {
+        let key = event.sequence_key();
+        self.pending.push_back(BrokerRecord {
+            destination: self.destination_for(key),
+            key: key.map(str::to_string),
+            payload: self.format.encode(event)?,
+        });
+        self.oldest.get_or_insert_with(Instant::now);
+        self.publish_pending(false)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.publish_pending(true)
+    }
+
+    fn name(&self) -> String {
+        format!("BrokerSink({})", self.publisher.name())
+    }
+}
@@ -3,13 +3,19 @@ use std::io;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use tokio::sync::{broadcast, oneshot};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio::task;
 
 use super::diagnostics::{DiagnosticsStream, HealthState, SinkDiagnostic, SinkHealth};
 use super::emitter::EventEmitter;
+use super::event::EventPriority;
 use super::hub::{EventHub, EventHubMetrics, EventStream};
+use super::ordering::SequenceReorderer;
 use super::sink::{EventSink, StdOutSink};
+use crate::redaction::{RedactingEmitter, RedactionPolicy};
+use crate::schedulers::saturation::SchedulerTelemetry;
 use chrono::Utc;
 
 /// Central event broadcasting system for workflow execution events.
@@ -154,6 +160,42 @@ use chrono::Utc;
 /// - Example: `examples/streaming_events.rs` - Complete streaming demonstration
 const DEFAULT_BUFFER_CAPACITY: usize = 1024;
 
+/// Errors returned by [`EventBus::flush`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum FlushError {
+    /// Some sinks had not finished draining when the timeout elapsed.
+    #[error("timed out after {timeout:?} waiting for sinks to drain: {}", pending.join(", "))]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::event_bus::flush_timeout),
+            help(
+                "A sink is slow or blocked; raise the flush timeout or check the sink's destination."
+            )
+        )
+    )]
+    Timeout {
+        /// The timeout that elapsed.
+        timeout: Duration,
+        /// Names of the sinks still draining.
+        pending: Vec<String>,
+    },
+    /// A sink reported an error while flushing.
+    #[error("sink {sink} failed to flush: {message}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::event_bus::flush_failed))
+    )]
+    Sink {
+        /// Name of the failing sink.
+        sink: String,
+        /// Error reported by the sink.
+        message: String,
+    },
+}
+
 /// Central event broadcasting system that fans out workflow events to registered sinks.
 ///
 /// Create with [`EventBus::with_sink`] or [`EventBus::with_sinks`] and pass to
@@ -170,6 +212,14 @@ pub struct EventBus {
     health: Arc<Mutex<std::collections::HashMap<String, HealthState>>>,
     diagnostics_enabled: bool,
     diagnostics_emit_to_events: bool,
+    /// Consecutive failures after which a sink stops receiving events.
+    sink_disable_threshold: Option<u64>,
+    /// Redaction applied to events published through [`EventBus::get_emitter`].
+    redaction: Option<Arc<RedactionPolicy>>,
+    /// Whether sink workers reorder sequenced events per session before delivery.
+    strict_ordering: bool,
+    /// Saturation samples of the runner using this bus, reported by [`EventBus::metrics`].
+    scheduler: Mutex<Option<Arc<SchedulerTelemetry>>>,
 }
 
 impl Default for EventBus {
@@ -221,9 +271,170 @@ impl EventBus {
             health: Arc::new(Mutex::new(std::collections::HashMap::new())),
             diagnostics_enabled,
             diagnostics_emit_to_events,
+            sink_disable_threshold: None,
+            redaction: None,
+            strict_ordering: false,
+            scheduler: Mutex::new(None),
         }
     }
 
+    /// Disable a sink after `threshold` consecutive failures instead of
+    /// retrying it on every event.
+    ///
+    /// The failure that trips the threshold is reported with
+    /// [`SinkDiagnostic::disabled`] set; afterwards the sink is skipped until
+    /// [`EventBus::enable_sink`] is called. A threshold of `0` is treated as `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{EventBus, MemorySink};
+    ///
+    /// let bus = EventBus::with_sink(MemorySink::new()).with_sink_disable_threshold(5);
+    /// assert_eq!(bus.sink_disable_threshold(), Some(5));
+    /// ```
+    #[must_use]
+    pub fn with_sink_disable_threshold(mut self, threshold: u64) -> Self {
+        self.sink_disable_threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Returns the configured auto-disable threshold, if any.
+    #[must_use]
+    pub fn sink_disable_threshold(&self) -> Option<u64> {
+        self.sink_disable_threshold
+    }
+
+    /// Redact events emitted through this bus before they reach sinks or
+    /// subscribers.
+    ///
+    /// Applies to every emitter returned by [`EventBus::get_emitter`]; see
+    /// [`RedactionPolicy`] for the available rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use weavegraph::event_bus::{EventBus, MemorySink};
+    /// use weavegraph::redaction::RedactionPolicy;
+    ///
+    /// let policy = Arc::new(RedactionPolicy::new().redact_key("api_key"));
+    /// let bus = EventBus::with_sink(MemorySink::new()).with_redaction(policy);
+    /// assert!(bus.redaction().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_redaction(mut self, policy: Arc<RedactionPolicy>) -> Self {
+        self.redaction = Some(policy);
+        self
+    }
+
+    /// Returns the redaction policy applied to emitted events, if any.
+    #[must_use]
+    pub fn redaction(&self) -> Option<&Arc<RedactionPolicy>> {
+        self.redaction.as_ref()
+    }
+
+    /// Stamp every event published through this bus with a per-session
+    /// sequence number ([`Event::sequence`](crate::event_bus::Event::sequence)).
+    ///
+    /// Consumers can detect dropped events from a jump in the sequence. Events
+    /// are still delivered in channel order; use
+    /// [`EventBus::with_strict_ordering`] to have sinks receive them in
+    /// sequence order.
+    #[must_use]
+    pub fn with_sequence_numbers(self) -> Self {
+        self.hub.enable_sequencing();
+        self
+    }
+
+    /// Deliver events to sinks in per-session sequence order.
+    ///
+    /// Implies [`EventBus::with_sequence_numbers`]. Each sink worker holds
+    /// events that arrive ahead of a predecessor and releases them once the
+    /// predecessor arrives. If the worker lags, is flushed, or holds more than
+    /// the bus capacity for one session, it stops waiting: held events are
+    /// delivered and the skipped numbers are counted in
+    /// [`EventHubMetrics::sequence_gaps`]. Ordering applies to sinks only;
+    /// [`EventBus::subscribe`] streams see channel order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{EventBus, MemorySink};
+    ///
+    /// let bus = EventBus::with_sink(MemorySink::new()).with_strict_ordering();
+    /// assert!(bus.strict_ordering());
+    /// ```
+    #[must_use]
+    pub fn with_strict_ordering(mut self) -> Self {
+        self.hub.enable_sequencing();
+        self.strict_ordering = true;
+        self
+    }
+
+    /// Whether sinks receive events in per-session sequence order.
+    #[must_use]
+    pub fn strict_ordering(&self) -> bool {
+        self.strict_ordering
+    }
+
+    /// Deliver critical events to sinks ahead of regular traffic.
+    ///
+    /// Events whose [`Event::priority`](crate::event_bus::Event::priority) is
+    /// [`High`](crate::event_bus::EventPriority::High) — diagnostics, stream
+    /// ends, LLM errors, retractions, cancellations, SLA breaches, and events
+    /// marked with [`Event::with_high_priority`](crate::event_bus::Event::with_high_priority)
+    /// — are copied to a second lane that every sink worker drains before
+    /// the normal lane, so they do not wait behind thousands of LLM chunks.
+    ///
+    /// Within a lane, events keep their channel order. Across lanes there is
+    /// no ordering: a high-priority event can reach a sink before normal
+    /// events published earlier. Each lane has its own buffer of the bus
+    /// capacity, so a chunk flood does not make a sink lose high-priority
+    /// events. With [`EventBus::with_strict_ordering`], only normal events
+    /// are reordered. [`EventBus::subscribe`] streams are not affected and
+    /// see every event in channel order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{EventBus, MemorySink};
+    ///
+    /// let bus = EventBus::with_sink(MemorySink::new()).with_priority_lanes();
+    /// assert!(bus.priority_lanes());
+    /// ```
+    #[must_use]
+    pub fn with_priority_lanes(self) -> Self {
+        self.hub.enable_priority_lanes();
+        self
+    }
+
+    /// Whether sinks receive high-priority events ahead of regular traffic.
+    #[must_use]
+    pub fn priority_lanes(&self) -> bool {
+        self.hub.has_priority_lanes()
+    }
+
+    /// Re-enable a sink previously disabled after repeated failures.
+    ///
+    /// Resets the sink's consecutive-failure counter. Returns `true` if a sink
+    /// with the given name was disabled.
+    pub fn enable_sink(&self, name: &str) -> bool {
+        let sinks = self.sinks.lock().expect("EventBus sinks mutex poisoned");
+        let mut reenabled = false;
+        for entry in sinks.iter().filter(|entry| entry.name == name) {
+            entry.failures.store(0, Ordering::SeqCst);
+            reenabled |= entry.disabled.swap(false, Ordering::SeqCst);
+        }
+        drop(sinks);
+        let mut health = self.health.lock().expect("EventBus health mutex poisoned");
+        if let Some(state) = health.get_mut(name) {
+            state.consecutive_failures = 0;
+            state.disabled = false;
+        }
+        reenabled
+    }
+
     /// Add a typed sink to this bus, starting a worker if the bus is already live.
     pub fn add_sink<T: EventSink + 'static>(&self, sink: T) {
         self.add_boxed_sink(Box::new(sink));
@@ -243,6 +454,8 @@ impl EventBus {
                 Arc::clone(&self.health),
                 self.diagnostics_enabled,
                 self.diagnostics_emit_to_events,
+                self.sink_disable_threshold,
+                self.reorder_window(),
             );
         }
         sinks_guard.push(entry);
@@ -250,12 +463,38 @@ impl EventBus {
 
     /// Return an [`EventEmitter`] handle for publishing events to this bus.
     pub fn get_emitter(&self) -> Arc<dyn EventEmitter> {
-        Arc::new(self.hub.emitter())
+        let emitter: Arc<dyn EventEmitter> = Arc::new(self.hub.emitter());
+        match &self.redaction {
+            Some(policy) => Arc::new(RedactingEmitter {
+                inner: emitter,
+                policy: Arc::clone(policy),
+            }),
+            None => emitter,
+        }
     }
 
-    /// Return current hub metrics (buffer capacity and cumulative drop count).
+    /// Return current hub metrics (buffer capacity, cumulative drop count,
+    /// per-sink health, and the owning runner's scheduler saturation).
     pub fn metrics(&self) -> EventHubMetrics {
-        self.hub.metrics()
+        let mut metrics = self.hub.metrics();
+        metrics.sinks = self.sink_health();
+        metrics.scheduler = self
+            .scheduler
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|telemetry| telemetry.snapshot());
+        metrics
+    }
+
+    /// Report `telemetry` from [`EventBus::metrics`]; the most recent runner wins.
+    pub(crate) fn attach_scheduler_telemetry(&self, telemetry: Arc<SchedulerTelemetry>) {
+        *self.scheduler.lock().unwrap_or_else(|e| e.into_inner()) = Some(telemetry);
+    }
+
+    /// Reorder window handed to sink workers when strict ordering is on.
+    fn reorder_window(&self) -> Option<usize> {
+        self.strict_ordering.then(|| self.hub.capacity())
     }
 
     /// Subscribe to the event stream, starting workers if not yet started.
@@ -274,17 +513,24 @@ impl EventBus {
     }
 
     /// Return a snapshot of per-sink health counters and last error details.
+    ///
+    /// Only sinks that have reported at least one error appear, sorted by name.
+    /// Health is tracked whether or not the diagnostics stream is enabled.
     pub fn sink_health(&self) -> Vec<SinkHealth> {
         let health = self.health.lock().expect("EventBus health mutex poisoned");
-        health
+        let mut snapshot: Vec<SinkHealth> = health
             .iter()
             .map(|(sink, state)| SinkHealth {
                 sink: sink.clone(),
                 error_count: state.error_count,
                 last_error: state.last_error.clone(),
                 last_error_at: state.last_error_at,
+                consecutive_failures: state.consecutive_failures,
+                disabled: state.disabled,
             })
-            .collect()
+            .collect();
+        snapshot.sort_by(|a, b| a.sink.cmp(&b.sink));
+        snapshot
     }
 
     /// Spawn workers for every registered sink. Safe to call multiple times.
@@ -303,6 +549,8 @@ impl EventBus {
                 Arc::clone(&self.health),
                 self.diagnostics_enabled,
                 self.diagnostics_emit_to_events,
+                self.sink_disable_threshold,
+                self.reorder_window(),
             );
         }
     }
@@ -324,12 +572,87 @@ impl EventBus {
             collected
         };
         for worker in workers {
-            let SinkWorker { shutdown, handle } = worker;
+            let SinkWorker {
+                shutdown, handle, ..
+            } = worker;
             let _ = shutdown.send(());
             let _ = handle.await;
         }
     }
 
+    /// Wait until every sink has handled all events published so far, then
+    /// flush each sink.
+    ///
+    /// Events published before this call are guaranteed to be handed to every
+    /// running sink before [`EventSink::flush`] is invoked. Disabled sinks are
+    /// skipped, and a bus that was never started returns immediately. The
+    /// runner calls this before closing the stream so short-lived runs do not
+    /// lose trailing events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FlushError::Timeout`] naming the sinks still draining when
+    /// `timeout` elapses, or [`FlushError::Sink`] if a sink's flush failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use weavegraph::event_bus::{Event, EventBus, MemorySink};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let sink = MemorySink::new();
+    /// let bus = EventBus::with_sink(sink.clone());
+    /// bus.listen_for_events();
+    /// bus.get_emitter().emit(Event::diagnostic("cli", "done")).unwrap();
+    ///
+    /// bus.flush(Duration::from_secs(1)).await.unwrap();
+    /// assert_eq!(sink.snapshot().len(), 1);
+    /// # }
+    /// ```
+    pub async fn flush(&self, timeout: Duration) -> Result<(), FlushError> {
+        let requests: Vec<(String, oneshot::Receiver<io::Result<()>>)> = {
+            let sinks = self.sinks.lock().expect("EventBus sinks mutex poisoned");
+            sinks
+                .iter()
+                .filter_map(|entry| {
+                    let worker = entry.worker.as_ref()?;
+                    let (ack_tx, ack_rx) = oneshot::channel();
+                    worker.flush.send(ack_tx).ok()?;
+                    Some((entry.name.clone(), ack_rx))
+                })
+                .collect()
+        };
+        let deadline = tokio::time::Instant::now() + timeout;
+        let results =
+            futures_util::future::join_all(requests.into_iter().map(|(sink, ack)| async move {
+                let outcome = tokio::time::timeout_at(deadline, ack).await;
+                (sink, outcome)
+            }))
+            .await;
+
+        let mut pending = Vec::new();
+        let mut failure = None;
+        for (sink, outcome) in results {
+            match outcome {
+                Err(_) => pending.push(sink),
+                // A worker that exited (bus stopped or closed) has nothing left to drain.
+                Ok(Err(_)) | Ok(Ok(Ok(()))) => {}
+                Ok(Ok(Err(err))) => {
+                    failure.get_or_insert(FlushError::Sink {
+                        sink,
+                        message: err.to_string(),
+                    });
+                }
+            }
+        }
+        if !pending.is_empty() {
+            return Err(FlushError::Timeout { timeout, pending });
+        }
+        failure.map_or(Ok(()), Err)
+    }
+
     /// Close the underlying hub channel, signalling all subscribers that the stream has ended.
     pub fn close_channel(&self) {
         self.hub.close();
@@ -352,6 +675,10 @@ struct SinkEntry {
     sink: Arc<Mutex<Box<dyn EventSink>>>,
     /// Resolved once at registration to avoid recomputing on error paths.
     name: String,
+    /// Back-to-back failures, shared with the worker so they survive restarts.
+    failures: Arc<AtomicU64>,
+    /// Set once the failure threshold trips; the worker skips events while set.
+    disabled: Arc<AtomicBool>,
     worker: Option<SinkWorker>,
 }
 
@@ -368,6 +695,8 @@ impl SinkEntry {
         Self {
             sink: Arc::new(Mutex::new(sink)),
             name,
+            failures: Arc::new(AtomicU64::new(0)),
+            disabled: Arc::new(AtomicBool::new(false)),
             worker: None,
         }
     }
@@ -382,110 +711,92 @@ impl SinkEntry {
         health: Arc<Mutex<std::collections::HashMap<String, HealthState>>>,
         diagnostics_enabled: bool,
         diagnostics_emit_to_events: bool,
+        disable_threshold: Option<u64>,
+        reorder_window: Option<usize>,
     ) {
         if self.worker.is_some() {
             return;
         }
         // Each worker holds an `Arc` to the sink so consumers can add/remove sinks without
         // racing the async tasks we spawn here.
-        let sink = Arc::clone(&self.sink);
-        let sink_name = self.name.clone();
+        let dispatcher = SinkDispatcher {
+            sink: Arc::clone(&self.sink),
+            sink_name: self.name.clone(),
+            failures: Arc::clone(&self.failures),
+            disabled: Arc::clone(&self.disabled),
+            diagnostics_tx,
+            health,
+            diagnostics_enabled,
+            diagnostics_emit_to_events,
+            disable_threshold,
+            hub: Arc::clone(&hub),
+        };
         let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        let (flush_tx, mut flush_rx) = mpsc::unbounded_channel::<FlushRequest>();
         let mut stream = hub.subscribe();
-        let de_enabled = diagnostics_enabled;
-        let de_emit = diagnostics_emit_to_events;
-        let hub_clone = Arc::clone(&hub);
+        let mut priority = hub.subscribe_priority_lane();
+        let mut ordered = OrderedDispatch {
+            dispatcher,
+            reorderer: reorder_window.map(SequenceReorderer::new),
+            skip_high_priority: priority.is_some(),
+        };
         let handle = task::spawn(async move {
-            fn record_sink_error(
-                health: &Arc<Mutex<std::collections::HashMap<String, HealthState>>>,
-                diagnostics_tx: &broadcast::Sender<SinkDiagnostic>,
-                sink_name: &str,
-                err_msg: &str,
-                diagnostics_enabled: bool,
-            ) {
-                if diagnostics_enabled {
-                    let mut map = health.lock().expect("health mutex poisoned");
-                    let entry = map.entry(sink_name.to_string()).or_default();
-                    entry.error_count = entry.error_count.saturating_add(1);
-                    entry.last_error = Some(err_msg.to_string());
-                    entry.last_error_at = Some(Utc::now());
-                    let occurrence = entry.error_count;
-                    drop(map);
-                    let _ = diagnostics_tx.send(SinkDiagnostic {
-                        sink: sink_name.to_string(),
-                        error: err_msg.to_string(),
-                        when: Utc::now(),
-                        occurrence,
-                    });
-                }
-            }
             loop {
                 // Bail out early if the bus has been stopped/restarted since this worker spawned.
                 if generation_state.load(Ordering::SeqCst) != active_generation {
                     break;
                 }
                 tokio::select! {
+                    biased;
                     _ = &mut shutdown_rx => break,
-                    event = stream.recv() => match event {
-                        Ok(event) => {
-                            let sink = Arc::clone(&sink);
-                            let sink_name = sink_name.clone();
-                            let diagnostics_tx = diagnostics_tx.clone();
-                            let health = Arc::clone(&health);
-                            let diagnostics_enabled = de_enabled;
-                            let hub_for_emit = Arc::clone(&hub_clone);
-                            let diagnostics_emit_to_events = de_emit;
-                            // Dispatch potentially blocking sink logic onto the dedicated
-                            // blocking pool so we never park the async runtime thread.
-                            let dispatch = task::spawn_blocking(move || -> io::Result<()> {
-                                let mut guard = sink.lock().expect("sink mutex poisoned");
-                                guard.handle(&event)
-                            });
-                            match dispatch.await {
-                                Ok(Ok(())) => {}
-                                Ok(Err(err)) => {
-                                    let err_msg = err.to_string();
-                                    tracing::error!(
-                                        target: "weavegraph::event_bus",
-                                        error = %err_msg,
-                                        sink = %sink_name,
-                                        "event sink reported an error while handling event"
-                                    );
-                                    record_sink_error(&health, &diagnostics_tx, &sink_name, &err_msg, diagnostics_enabled);
-                                    if diagnostics_emit_to_events {
-                                        let _ = hub_for_emit.publish(super::event::Event::diagnostic(
-                                            "event_bus.sink_error",
-                                            format!("{sink}: {err}", sink=sink_name, err=err_msg),
-                                        ));
-                                    }
+                    Some(ack) = flush_rx.recv() => {
+                        // Everything published before the flush request is already queued
+                        // on these receivers, so draining them preserves the flush guarantee.
+                        // Only what is queued now is drained, so steady publishing cannot
+                        // keep the flush from completing.
+                        if let Some(lane) = &mut priority {
+                            let mut queued = lane.queued();
+                            loop {
+                                match lane.try_recv_within(&mut queued) {
+                                    Ok(event) => ordered.dispatcher.dispatch(event).await,
+                                    Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                                    Err(_) => break,
                                 }
-                                Err(err) => {
-                                    let err_msg = err.to_string();
-                                    tracing::error!(
-                                        target: "weavegraph::event_bus",
-                                        error = %err_msg,
-                                        sink = %sink_name,
-                                        "event sink worker task failed to join"
-                                    );
-                                    // Treat join failures as sink errors for health/diagnostics
-                                    record_sink_error(&health, &diagnostics_tx, &sink_name, &err_msg, diagnostics_enabled);
-                                    if diagnostics_emit_to_events {
-                                        let _ = hub_for_emit.publish(super::event::Event::diagnostic(
-                                            "event_bus.sink_join_error",
-                                            format!("{sink}: {err}", sink=sink_name, err=err_msg),
-                                        ));
-                                    }
+                            }
+                        }
+                        let mut queued = stream.queued();
+                        loop {
+                            match stream.try_recv_within(&mut queued) {
+                                Ok(event) => ordered.dispatch(event).await,
+                                Err(broadcast::error::TryRecvError::Lagged(_)) => {
+                                    ordered.release_all().await;
                                 }
+                                Err(_) => break,
                             }
                         }
-                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
-                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        ordered.release_all().await;
+                        let _ = ack.send(ordered.dispatcher.flush().await);
+                    }
+                    event = recv_lane(&mut priority) => match event {
+                        // High-priority events skip the reorder buffer.
+                        Ok(event) => ordered.dispatcher.dispatch(event).await,
+                        Err(broadcast::error::RecvError::Closed) => priority = None,
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    },
+                    event = stream.recv() => match event {
+                        Ok(event) => ordered.dispatch(event).await,
+                        Err(broadcast::error::RecvError::Closed) => {
+                            ordered.release_all().await;
+                            break;
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => ordered.release_all().await,
                     }
                 }
             }
         });
         self.worker = Some(SinkWorker {
             shutdown: shutdown_tx,
+            flush: flush_tx,
             handle,
         });
     }
@@ -498,7 +809,206 @@ impl SinkEntry {
     }
 }
 
+/// Receive from the priority lane; never resolves when there is none.
+async fn recv_lane(
+    lane: &mut Option<EventStream>,
+) -> Result<super::event::Event, broadcast::error::RecvError> {
+    match lane {
+        Some(stream) => stream.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Reply channel for a single sink flush request.
+type FlushRequest = oneshot::Sender<io::Result<()>>;
+
 struct SinkWorker {
     shutdown: oneshot::Sender<()>,
+    flush: mpsc::UnboundedSender<FlushRequest>,
     handle: task::JoinHandle<()>,
 }
+
+/// Sink dispatch with an optional per-session reorder buffer in front.
+struct OrderedDispatch {
+    dispatcher: SinkDispatcher,
+    reorderer: Option<SequenceReorderer>,
+    /// Whether high-priority events on the normal lane were already delivered
+    /// from the priority lane.
+    skip_high_priority: bool,
+}
+
+impl OrderedDispatch {
+    /// Whether `event` was delivered from the priority lane.
+    fn delivered_early(&self, event: &super::event::Event) -> bool {
+        self.skip_high_priority && event.priority() == EventPriority::High
+    }
+
+    async fn dispatch(&mut self, event: super::event::Event) {
+        match &mut self.reorderer {
+            Some(reorderer) => {
+                let ready = reorderer.push(event);
+                self.dispatch_ready(ready).await;
+            }
+            None => {
+                if !self.delivered_early(&event) {
+                    self.dispatcher.dispatch(event).await;
+                }
+            }
+        }
+    }
+
+    /// Stop waiting for missing sequence numbers and deliver everything held.
+    async fn release_all(&mut self) {
+        if let Some(reorderer) = &mut self.reorderer {
+            let ready = reorderer.release_all();
+            self.dispatch_ready(ready).await;
+        }
+    }
+
+    async fn dispatch_ready(&mut self, ready: Vec<super::event::Event>) {
+        if let Some(reorderer) = &mut self.reorderer {
+            self.dispatcher
+                .hub
+                .record_sequence_gaps(reorderer.take_gaps());
+        }
+        // Early-delivered events still pass through the reorderer so their
+        // sequence numbers do not count as gaps.
+        for event in ready {
+            if !self.delivered_early(&event) {
+                self.dispatcher.dispatch(event).await;
+            }
+        }
+    }
+}
+
+/// Per-worker state needed to hand events to a sink and record the outcome.
+struct SinkDispatcher {
+    sink: Arc<Mutex<Box<dyn EventSink>>>,
+    sink_name: String,
+    failures: Arc<AtomicU64>,
+    disabled: Arc<AtomicBool>,
+    diagnostics_tx: broadcast::Sender<SinkDiagnostic>,
+    health: Arc<Mutex<std::collections::HashMap<String, HealthState>>>,
+    diagnostics_enabled: bool,
+    diagnostics_emit_to_events: bool,
+    disable_threshold: Option<u64>,
+    hub: Arc<EventHub>,
+}
+
+impl SinkDispatcher {
+    async fn dispatch(&self, event: super::event::Event) {
+        if self.disabled.load(Ordering::SeqCst) {
+            return;
+        }
+        let sink = Arc::clone(&self.sink);
+        // Dispatch potentially blocking sink logic onto the dedicated
+        // blocking pool so we never park the async runtime thread.
+        let result = task::spawn_blocking(move || -> io::Result<()> {
+            let mut guard = sink.lock().expect("sink mutex poisoned");
+            guard.handle(&event)
+        })
+        .await;
+        let failure_state = if matches!(result, Ok(Ok(()))) {
+            if self.failures.swap(0, Ordering::SeqCst) > 0 {
+                self.record_recovery();
+            }
+            (0, false)
+        } else {
+            let consecutive = self.failures.fetch_add(1, Ordering::SeqCst) + 1;
+            let trips = self
+                .disable_threshold
+                .is_some_and(|limit| consecutive >= limit);
+            (
+                consecutive,
+                trips && !self.disabled.swap(true, Ordering::SeqCst),
+            )
+        };
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                let err_msg = err.to_string();
+                tracing::error!(
+                    target: "weavegraph::event_bus",
+                    error = %err_msg,
+                    sink = %self.sink_name,
+                    "event sink reported an error while handling event"
+                );
+                self.record_error(&err_msg, failure_state);
+                self.publish_diagnostic("event_bus.sink_error", &err_msg);
+            }
+            Err(err) => {
+                let err_msg = err.to_string();
+                tracing::error!(
+                    target: "weavegraph::event_bus",
+                    error = %err_msg,
+                    sink = %self.sink_name,
+                    "event sink worker task failed to join"
+                );
+                // Treat join failures as sink errors for health/diagnostics
+                self.record_error(&err_msg, failure_state);
+                self.publish_diagnostic("event_bus.sink_join_error", &err_msg);
+            }
+        }
+        if let (consecutive, true) = failure_state {
+            tracing::warn!(
+                target: "weavegraph::event_bus",
+                sink = %self.sink_name,
+                consecutive_failures = consecutive,
+                "event sink disabled after repeated failures"
+            );
+            self.publish_diagnostic(
+                "event_bus.sink_disabled",
+                &format!("disabled after {consecutive} consecutive failures"),
+            );
+        }
+    }
+
+    async fn flush(&self) -> io::Result<()> {
+        if self.disabled.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let sink = Arc::clone(&self.sink);
+        task::spawn_blocking(move || sink.lock().expect("sink mutex poisoned").flush())
+            .await
+            .unwrap_or_else(|join| Err(io::Error::other(join.to_string())))
+    }
+
+    fn publish_diagnostic(&self, scope: &str, message: &str) {
+        if self.diagnostics_emit_to_events {
+            let _ = self.hub.publish(super::event::Event::diagnostic(
+                scope,
+                format!("{}: {message}", self.sink_name),
+            ));
+        }
+    }
+
+    fn record_error(&self, err_msg: &str, (consecutive_failures, disabled): (u64, bool)) {
+        let mut map = self.health.lock().expect("health mutex poisoned");
+        let entry = map.entry(self.sink_name.clone()).or_default();
+        entry.error_count = entry.error_count.saturating_add(1);
+        entry.last_error = Some(err_msg.to_string());
+        entry.last_error_at = Some(Utc::now());
+        entry.consecutive_failures = consecutive_failures;
+        entry.disabled = disabled;
+        let occurrence = entry.error_count;
+        drop(map);
+        if !self.diagnostics_enabled {
+            return;
+        }
+        let _ = self.diagnostics_tx.send(SinkDiagnostic {
+            sink: self.sink_name.clone(),
+            error: err_msg.to_string(),
+            when: Utc::now(),
+            occurrence,
+            consecutive_failures,
+            disabled,
+        });
+    }
+
+    fn record_recovery(&self) {
+        let mut map = self.health.lock().expect("health mutex poisoned");
+        if let Some(entry) = map.get_mut(&self.sink_name) {
+            entry.consecutive_failures = 0;
+        }
+    }
+}
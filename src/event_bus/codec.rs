@@ -0,0 +1,268 @@
+//! Pluggable wire formats for event sinks.
+//!
+//! [`JsonLinesSink`](super::JsonLinesSink) writes the normalized JSON envelope
+//! and is the right choice for log aggregation. For high-volume streams (LLM
+//! chunk streams in particular) the JSON text itself can dominate the cost of
+//! the sink. [`EncodedSink`] writes the serde representation of [`Event`] in a
+//! selectable [`EventFormat`], and [`EventDecoder`] reads such a stream back
+//! into events.
+//!
+//! | Format | Feature | Framing |
+//! |--------|---------|---------|
+//! | [`EventFormat::Json`] | always | one compact JSON document per line |
+//! | `EventFormat::MsgPack` | `msgpack` | 4-byte big-endian length prefix |
+//! | `EventFormat::Cbor` | `cbor` | 4-byte big-endian length prefix |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::io::Cursor;
+//! use weavegraph::event_bus::{Event, EventDecoder, EventFormat};
+//!
+//! let mut buffer = Vec::new();
+//! let event = Event::diagnostic("codec", "hello");
+//! EventFormat::Json.write_frame(&mut buffer, &event).unwrap();
+//!
+//! let decoded: Vec<Event> = EventDecoder::new(Cursor::new(buffer), EventFormat::Json)
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(decoded, vec![event]);
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, Result as IoResult, Write};
+use std::path::Path;
+
+use super::event::Event;
+use super::sink::EventSink;
+
+/// Largest frame [`EventDecoder`] accepts before treating the stream as corrupt.
+pub const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+// ============================================================================
+// Formats
+// ============================================================================
+
+/// Serialization format used by [`EncodedSink`] and [`EventDecoder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EventFormat {
+    /// Compact JSON, newline-delimited.
+    Json,
+    /// MessagePack with named fields, length-prefixed.
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+    /// CBOR, length-prefixed.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl EventFormat {
+    /// Short lowercase name (`json`, `msgpack`, `cbor`).
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => "msgpack",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "cbor",
+        }
+    }
+
+    /// Encode a single event without framing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] if the event cannot be serialized.
+    pub fn encode(&self, event: &Event) -> IoResult<Vec<u8>> {
+        match self {
+            Self::Json => serde_json::to_vec(event).map_err(invalid_data),
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => rmp_serde::to_vec_named(event).map_err(invalid_data),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut out = Vec::new();
+                ciborium::into_writer(event, &mut out).map_err(invalid_data)?;
+                Ok(out)
+            }
+        }
+    }
+
+    /// Decode a single unframed event produced by [`encode`](Self::encode).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] if the bytes are not a valid event.
+    pub fn decode(&self, bytes: &[u8]) -> IoResult<Event> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(invalid_data),
+            #[cfg(feature = "msgpack")]
+            Self::MsgPack => rmp_serde::from_slice(bytes).map_err(invalid_data),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(bytes).map_err(invalid_data),
+        }
+    }
+
+    /// Encode an event and write it with this format's framing.
+    ///
+    /// # Errors
+    ///
+    /// Propagates serialization and I/O errors.
+    pub fn write_frame<W: Write + ?Sized>(&self, writer: &mut W, event: &Event) -> IoResult<()> {
+        let bytes = self.encode(event)?;
+        if self.is_length_prefixed() {
+            let len = u32::try_from(bytes.len())
+                .map_err(|_| invalid_data("event frame exceeds u32::MAX bytes"))?;
+            writer.write_all(&len.to_be_bytes())?;
+            writer.write_all(&bytes)
+        } else {
+            writer.write_all(&bytes)?;
+            writer.write_all(b"\n")
+        }
+    }
+
+    fn is_length_prefixed(&self) -> bool {
+        !matches!(self, Self::Json)
+    }
+}
+
+impl fmt::Display for EventFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+// ============================================================================
+// Sink
+// ============================================================================
+
+/// Sink that writes events in a selectable [`EventFormat`].
+///
+/// Frames are not flushed individually: buffered output is written out when
+/// [`EventBus::flush`](crate::event_bus::EventBus::flush) drains the sink or
+/// the sink is dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use weavegraph::event_bus::{EncodedSink, EventBus, EventFormat};
+///
+/// let sink = EncodedSink::to_file("events.json", EventFormat::Json).unwrap();
+/// let bus = EventBus::with_sinks(vec![Box::new(sink)]);
+/// ```
+pub struct EncodedSink {
+    handle: Box<dyn Write + Send + Sync>,
+    format: EventFormat,
+}
+
+impl EncodedSink {
+    /// Create a sink writing frames to `handle`.
+    pub fn new(handle: Box<dyn Write + Send + Sync>, format: EventFormat) -> Self {
+        Self { handle, format }
+    }
+
+    /// Create a sink writing to a file (created or truncated).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn to_file(path: impl AsRef<Path>, format: EventFormat) -> IoResult<Self> {
+        let file = File::create(path)?;
+        Ok(Self::new(Box::new(io::BufWriter::new(file)), format))
+    }
+
+    /// The format this sink writes.
+    #[must_use]
+    pub fn format(&self) -> EventFormat {
+        self.format
+    }
+}
+
+impl EventSink for EncodedSink {
+    fn handle(&mut self, event: &Event) -> IoResult<()> {
+        self.format.write_frame(&mut self.handle, event)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.handle.flush()
+    }
+
+    fn name(&self) -> String {
+        format!("EncodedSink({})", self.format)
+    }
+}
+
+// ============================================================================
+// Decoder
+// ============================================================================
+
+/// Iterator over events read from a stream written by [`EncodedSink`].
+///
+/// Yields `Err` for corrupt frames; iteration stops at a clean end of input.
+pub struct EventDecoder<R> {
+    reader: R,
+    format: EventFormat,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> EventDecoder<R> {
+    /// Create a decoder for `format` over a buffered reader.
+    pub fn new(reader: R, format: EventFormat) -> Self {
+        Self {
+            reader,
+            format,
+            buf: Vec::new(),
+        }
+    }
+
+    fn next_frame(&mut self) -> IoResult<Option<&[u8]>> {
+        self.buf.clear();
+        if !self.format.is_length_prefixed() {
+            loop {
+                if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+                    return Ok(None);
+                }
+                if !self.buf.trim_ascii().is_empty() {
+                    return Ok(Some(self.buf.trim_ascii()));
+                }
+                self.buf.clear();
+            }
+        }
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(invalid_data(format!(
+                "event frame of {len} bytes exceeds MAX_FRAME_LEN"
+            )));
+        }
+        self.buf.resize(len, 0);
+        self.reader.read_exact(&mut self.buf)?;
+        Ok(Some(&self.buf))
+    }
+}
+
+impl<R: BufRead> Iterator for EventDecoder<R> {
+    type Item = IoResult<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let format = self.format;
+        match self.next_frame() {
+            Ok(Some(frame)) => Some(format.decode(frame)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
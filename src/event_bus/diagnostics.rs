@@ -18,6 +18,13 @@ pub struct SinkDiagnostic {
     pub when: DateTime<Utc>,
     /// Monotonic occurrence counter for this sink's errors.
     pub occurrence: u64,
+    /// Number of back-to-back failures, reset whenever the sink handles an event.
+    #[serde(default)]
+    pub consecutive_failures: u64,
+    /// `true` when this failure tripped the bus's disable threshold and the
+    /// sink will receive no further events until re-enabled.
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 /// Public snapshot type representing per-sink health.
@@ -33,6 +40,12 @@ pub struct SinkHealth {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Timestamp of the most recent error, if any.
     pub last_error_at: Option<DateTime<Utc>>,
+    /// Number of back-to-back failures since the sink last handled an event.
+    #[serde(default)]
+    pub consecutive_failures: u64,
+    /// Whether the sink has been disabled after exceeding the failure threshold.
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 /// Internal accumulator for health tracking.
@@ -44,6 +57,10 @@ pub struct HealthState {
     pub last_error: Option<String>,
     /// Timestamp of the most recent error, if any.
     pub last_error_at: Option<DateTime<Utc>>,
+    /// Back-to-back failures since the last successful delivery.
+    pub consecutive_failures: u64,
+    /// Whether the sink was disabled after exceeding the failure threshold.
+    pub disabled: bool,
 }
 
 /// Stream wrapper for sink diagnostics, mirroring the EventStream API surface.
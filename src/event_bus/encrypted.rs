@@ -0,0 +1,203 @@
+//! Per-tenant envelope encryption for sink output.
+//!
+//! [`EncryptedSink`] wraps any [`EventSink`] ([`ChannelSink`](super::ChannelSink),
+//! [`JsonLinesSink`](super::JsonLinesSink), a network sink) and seals each
+//! event's content with the key of the tenant it belongs to before the inner
+//! sink sees it. A log pipeline shared by many tenants then carries only
+//! ciphertext it cannot read.
+//!
+//! Sealing moves the message (or LLM chunk), metadata, and payload into an
+//! [`Envelope`] stored under the [`SEALED_METADATA_KEY`] metadata key. The
+//! scope, node id, step, sequence number, LLM session/stream ids, and the
+//! node event's `invocation_id` stay in clear so events can still be routed,
+//! ordered, and filtered. Diagnostics carry framework text only and pass
+//! through unchanged. [`open_event`] restores a sealed event.
+//!
+//! By default an event's tenant is its session
+//! ([`Event::sequence_key`]); events without a session use
+//! [`SHARED_TENANT`]. Map sessions to tenants with
+//! [`EncryptedSink::with_tenant_resolver`]. An event whose tenant has no key
+//! is not delivered: the sink reports an error instead of writing plaintext.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use weavegraph::encryption::{EncryptionKey, StaticKeyProvider};
+//! use weavegraph::event_bus::{ChannelSink, EncryptedSink, Event, EventSink, open_event};
+//!
+//! let keys = Arc::new(
+//!     StaticKeyProvider::new().with_key("shared", EncryptionKey::new("k1", [1; 32])),
+//! );
+//! let (tx, rx) = flume::unbounded();
+//! let mut sink = EncryptedSink::new(ChannelSink::new(tx), keys.clone());
+//!
+//! sink.handle(&Event::node_message("billing", "card ending 4242")).unwrap();
+//! let sealed = rx.recv().unwrap();
+//! assert_eq!(sealed.message(), "");
+//! assert_eq!(open_event(&sealed, keys.as_ref()).unwrap().message(), "card ending 4242");
+//! ```
+
+use std::io::{self, Result as IoResult};
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use serde_json::{Value, json};
+
+use super::event::Event;
+use super::sink::EventSink;
+use crate::encryption::{EncryptionError, Envelope, KeyProvider};
+
+/// Metadata key holding the [`Envelope`] of a sealed event.
+pub const SEALED_METADATA_KEY: &str = "sealed";
+
+/// Tenant used for events that belong to no session.
+pub const SHARED_TENANT: &str = "shared";
+
+/// Node event metadata kept in clear when sealing.
+const CLEAR_METADATA_KEYS: &[&str] = &["invocation_id"];
+
+type TenantResolver = Arc<dyn Fn(&Event) -> Option<String> + Send + Sync>;
+
+/// Sink wrapper that seals event content per tenant before forwarding it.
+///
+/// See the [module documentation](self) for what is sealed.
+pub struct EncryptedSink<S> {
+    inner: S,
+    keys: Arc<dyn KeyProvider>,
+    tenant: Option<TenantResolver>,
+}
+
+impl<S: EventSink> EncryptedSink<S> {
+    /// Seal events with keys from `keys` and forward them to `inner`.
+    pub fn new(inner: S, keys: Arc<dyn KeyProvider>) -> Self {
+        Self {
+            inner,
+            keys,
+            tenant: None,
+        }
+    }
+
+    /// Decide each event's tenant with `resolver` instead of its session id.
+    ///
+    /// Returning `None` seals the event under [`SHARED_TENANT`].
+    #[must_use]
+    pub fn with_tenant_resolver(
+        mut self,
+        resolver: impl Fn(&Event) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.tenant = Some(Arc::new(resolver));
+        self
+    }
+
+    /// The wrapped sink.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn tenant_of(&self, event: &Event) -> String {
+        match &self.tenant {
+            Some(resolver) => resolver(event),
+            None => event.sequence_key().map(str::to_owned),
+        }
+        .unwrap_or_else(|| SHARED_TENANT.to_string())
+    }
+}
+
+impl<S: EventSink> EventSink for EncryptedSink<S> {
+    fn handle(&mut self, event: &Event) -> IoResult<()> {
+        let tenant = self.tenant_of(event);
+        let sealed = seal_event(event, &tenant, self.keys.as_ref()).map_err(io::Error::other)?;
+        self.inner.handle(&sealed)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.inner.flush()
+    }
+
+    fn name(&self) -> String {
+        format!("EncryptedSink({})", self.inner.name())
+    }
+}
+
+/// Seal `event`'s content with `tenant`'s current key.
+///
+/// Diagnostics are returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the provider has no key for `tenant`.
+pub fn seal_event(
+    event: &Event,
+    tenant: &str,
+    keys: &dyn KeyProvider,
+) -> Result<Event, EncryptionError> {
+    let mut sealed = event.clone();
+    let aad = associated_data(&sealed);
+    let Some((message, metadata, payload)) = sealed.sealable_parts_mut() else {
+        return Ok(sealed);
+    };
+
+    let mut clear: FxHashMap<String, Value> = CLEAR_METADATA_KEYS
+        .iter()
+        .filter_map(|key| Some(((*key).to_string(), metadata.get(*key)?.clone())))
+        .collect();
+    let content = json!({
+        "message": std::mem::take(message),
+        "metadata": std::mem::take(metadata),
+        "payload": payload.and_then(Option::take),
+    });
+    let envelope = Envelope::seal(keys, tenant, content.to_string().as_bytes(), &aad)?;
+    clear.insert(
+        SEALED_METADATA_KEY.to_string(),
+        serde_json::to_value(envelope).expect("envelope serializes to JSON"),
+    );
+    *metadata = clear;
+    Ok(sealed)
+}
+
+/// Restore an event sealed by [`seal_event`] or [`EncryptedSink`].
+///
+/// Events without a sealed envelope are returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the key is unavailable or the event was altered.
+pub fn open_event(event: &Event, keys: &dyn KeyProvider) -> Result<Event, EncryptionError> {
+    let mut opened = event.clone();
+    let aad = associated_data(&opened);
+    let Some((message, metadata, payload)) = opened.sealable_parts_mut() else {
+        return Ok(opened);
+    };
+    let Some(envelope) = metadata.get(SEALED_METADATA_KEY) else {
+        return Ok(opened);
+    };
+
+    let envelope: Envelope = serde_json::from_value(envelope.clone())
+        .map_err(|err| EncryptionError::Malformed(err.to_string()))?;
+    let content: Value = serde_json::from_slice(&envelope.open(keys, &aad)?)
+        .map_err(|err| EncryptionError::Malformed(err.to_string()))?;
+    let Value::Object(mut content) = content else {
+        return Err(EncryptionError::Malformed(
+            "sealed content is not an object".into(),
+        ));
+    };
+
+    if let Some(Value::String(text)) = content.remove("message") {
+        *message = text;
+    }
+    *metadata = match content.remove("metadata") {
+        Some(Value::Object(map)) => map.into_iter().collect(),
+        _ => FxHashMap::default(),
+    };
+    if let Some(slot) = payload {
+        *slot = content.remove("payload").filter(|value| !value.is_null());
+    }
+    Ok(opened)
+}
+
+/// Clear fields bound to the ciphertext, so a sealed body cannot be moved to
+/// an event with another scope.
+fn associated_data(event: &Event) -> Vec<u8> {
+    event.scope_label().unwrap_or_default().as_bytes().to_vec()
+}
@@ -26,8 +26,115 @@ pub const INVOCATION_END_SCOPE: &str = "__weavegraph_invocation_end__";
 /// telemetry without polluting the main event stream.
 pub const DIAGNOSTIC_SCOPE: &str = "__weavegraph_diagnostic__";
 
+/// Scope constant for diagnostics reporting conflicting `extra` writes.
+///
+/// The runner emits one event per contested key when several nodes in one
+/// superstep write different values to it. The message lists the step, key,
+/// writers, and the [`ConflictPolicy`](crate::reducers::ConflictPolicy) that
+/// resolved it, e.g. `step=2 key=result nodes=a,b policy=last_wins`.
+pub const EXTRA_CONFLICT_SCOPE: &str = "__weavegraph_extra_conflict__";
+
+/// Scope constant for `tracing` events forwarded from inside node runs.
+///
+/// [`EventBusLayer`](crate::telemetry::EventBusLayer) publishes each captured
+/// `tracing` event as a node event with this scope, carrying the node id and
+/// step of the enclosing run plus `level`, `target`, and `fields` metadata.
+pub const TRACING_SCOPE: &str = "__weavegraph_tracing__";
+
+/// Scope constant for SLA breaches detected by the runner.
+///
+/// Each breach of a graph's [`SlaPolicy`](crate::graphs::SlaPolicy) is
+/// published as a node event with this scope and `metric`, `limit`,
+/// `observed`, and optionally `node` metadata; see
+/// [`SlaBreach`](crate::graphs::SlaBreach).
+pub const SLA_BREACH_SCOPE: &str = "__weavegraph_sla_breach__";
+
+/// Scope constant for state size limits exceeded at a barrier.
+///
+/// Each limit of the runtime's [`StateLimits`](crate::runtimes::StateLimits)
+/// exceeded after a superstep is published as a node event with this scope
+/// and `limit`, `max`, `observed`, and optionally `node` metadata; see
+/// [`StateLimitBreach`](crate::runtimes::StateLimitBreach).
+pub const STATE_LIMIT_SCOPE: &str = "__weavegraph_state_limit__";
+
+/// Scope constant for the summary published when a run completes.
+///
+/// The runner publishes one node event with this scope at the end of every
+/// completed run, carrying `invocation_id` (the session id) and the
+/// [`RunSummary`](crate::runtimes::RunSummary) as `summary` metadata.
+pub const RUN_SUMMARY_SCOPE: &str = "__weavegraph_run_summary__";
+
+/// Scope constant for per-superstep metrics published by the runner.
+///
+/// Runners built with
+/// [`AppRunnerBuilder::publish_step_metrics`](crate::runtimes::AppRunnerBuilder::publish_step_metrics)
+/// publish one node event with this scope after every barrier, carrying
+/// `session_id`, per-node `nodes` durations, and `error_tags` counts for the
+/// errors recorded in that step. [`AggregatingSink`](crate::event_bus::AggregatingSink)
+/// rolls them up.
+pub const STEP_METRICS_SCOPE: &str = "__weavegraph_step_metrics__";
+
+/// Scope constant for routing decisions along labelled edges.
+///
+/// After every barrier the runner publishes one node event with this scope
+/// for each labelled edge it followed, carrying `invocation_id` (the session
+/// id), `from`, `to`, `kind`, `label`, and any `edge_metadata`; see
+/// [`FrontierEdge`](crate::runtimes::FrontierEdge). Unlabelled edges are only
+/// reported in [`StepReport::frontier_edges`](crate::runtimes::StepReport::frontier_edges).
+pub const ROUTING_SCOPE: &str = "__weavegraph_routing__";
+
+/// Scope constant for retractions of a failed step attempt.
+///
+/// When a superstep's results are discarded (a node failed, or its checkpoint
+/// could not be saved and the step was rolled back or re-executed), the
+/// runner publishes one node event with this scope carrying `invocation_id`
+/// (the session id), `attempt`, and `reason`, with the step in the event's
+/// step field. Events received earlier for the same session, step, and
+/// attempt should be discounted; see
+/// [`AttemptRetraction`](crate::event_bus::AttemptRetraction).
+pub const RETRACT_SCOPE: &str = "__weavegraph_retract__";
+
+/// Scope constant for run cancellation events.
+///
+/// When a run's [`CancellationToken`](crate::node::CancellationToken) is
+/// cancelled mid-step, the scheduler publishes one event with `phase` metadata
+/// `"cooperative"` listing the unfinished `nodes` and the `grace_period_ms`
+/// they have to return, then one event with `phase` `"abort"` for each node it
+/// drops after the grace period.
+pub const CANCELLATION_SCOPE: &str = "__weavegraph_cancellation__";
+
+/// Scope constant for summaries published by an
+/// [`AggregatingSink`](crate::event_bus::AggregatingSink).
+pub const ROLLUP_SUMMARY_SCOPE: &str = "__weavegraph_rollup_summary__";
+
+/// Metadata key marking a node or LLM event's [`EventPriority`].
+///
+/// Set it to `"high"` (or use [`Event::with_high_priority`]) on events that
+/// must not wait behind streaming traffic, such as security incidents.
+pub const PRIORITY_METADATA_KEY: &str = "priority";
+
+/// Delivery lane of an event on a bus with
+/// [`EventBus::with_priority_lanes`](crate::event_bus::EventBus::with_priority_lanes).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EventPriority {
+    /// Regular traffic: node messages, LLM chunks, metrics.
+    #[default]
+    Normal,
+    /// Critical diagnostics delivered to sinks ahead of normal traffic.
+    High,
+}
+
+/// Message, metadata map, and payload slot of an event, for sealing.
+#[cfg(feature = "encryption")]
+pub(crate) type SealableParts<'a> = (
+    &'a mut String,
+    &'a mut FxHashMap<String, Value>,
+    Option<&'a mut Option<Value>>,
+);
+
 /// A workflow event that can be emitted by nodes or the framework itself.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Event {
     /// A structured event emitted by a workflow node.
     Node(NodeEvent),
@@ -77,11 +184,20 @@ impl Event {
         )
     }
 
+    /// Create a node event carrying a structured JSON payload instead of a message.
+    ///
+    /// The message text is left empty; sinks read the payload from
+    /// [`NodeEvent::payload`] or the `payload` field of [`to_json_value`](Self::to_json_value).
+    pub fn node_payload(scope: impl Into<String>, payload: Value) -> Self {
+        Event::Node(NodeEvent::new(None, None, scope.into(), String::new()).with_payload(payload))
+    }
+
     /// Create a diagnostic event with the given scope and message.
     pub fn diagnostic(scope: impl Into<String>, message: impl Into<String>) -> Self {
         Event::Diagnostic(DiagnosticEvent {
             scope: scope.into(),
             message: message.into(),
+            sequence: None,
         })
     }
 
@@ -103,6 +219,138 @@ impl Event {
         }
     }
 
+    /// Per-session sequence number stamped by a sequencing [`EventHub`](crate::event_bus::EventHub).
+    ///
+    /// `None` unless the bus was built with
+    /// [`EventBus::with_sequence_numbers`](crate::event_bus::EventBus::with_sequence_numbers)
+    /// or [`EventBus::with_strict_ordering`](crate::event_bus::EventBus::with_strict_ordering).
+    /// Sequences start at 1 and increase by one per event within a
+    /// [`sequence_key`](Self::sequence_key), so a jump reveals dropped events.
+    pub fn sequence(&self) -> Option<u64> {
+        match self {
+            Event::Node(node) => node.sequence,
+            Event::Diagnostic(diag) => diag.sequence,
+            Event::LLM(llm) => llm.sequence,
+        }
+    }
+
+    /// Return this event with its sequence number set.
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        let slot = match &mut self {
+            Event::Node(node) => &mut node.sequence,
+            Event::Diagnostic(diag) => &mut diag.sequence,
+            Event::LLM(llm) => &mut llm.sequence,
+        };
+        *slot = Some(sequence);
+        self
+    }
+
+    /// The session an event is sequenced under.
+    ///
+    /// Node events use their `invocation_id` metadata (the runner's session
+    /// id) and LLM events their session id. Diagnostics and events without a
+    /// session share one bus-wide sequence, reported as `None`.
+    pub fn sequence_key(&self) -> Option<&str> {
+        match self {
+            Event::Node(node) => node.metadata.get("invocation_id").and_then(Value::as_str),
+            Event::Diagnostic(_) => None,
+            Event::LLM(llm) => llm.session_id(),
+        }
+    }
+
+    /// The step attempt a node event was emitted in, starting at 1.
+    ///
+    /// Read from `attempt` metadata, which is only present on re-executions;
+    /// events without it, and all non-node events, report 1.
+    #[must_use]
+    pub fn attempt(&self) -> u32 {
+        match self {
+            Event::Node(node) => node
+                .metadata
+                .get("attempt")
+                .and_then(Value::as_u64)
+                .and_then(|attempt| u32::try_from(attempt).ok())
+                .unwrap_or(1),
+            Event::Diagnostic(_) | Event::LLM(_) => 1,
+        }
+    }
+
+    /// The lane this event travels in on a bus with priority lanes.
+    ///
+    /// Diagnostics, LLM final and error events, and node events in
+    /// [`STREAM_END_SCOPE`], [`INVOCATION_END_SCOPE`], [`RETRACT_SCOPE`],
+    /// [`CANCELLATION_SCOPE`], [`SLA_BREACH_SCOPE`], or [`RUN_SUMMARY_SCOPE`] are
+    /// [`High`](EventPriority::High), as is any node or LLM event whose
+    /// [`PRIORITY_METADATA_KEY`] metadata is `"high"`. Everything else is
+    /// [`Normal`](EventPriority::Normal).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{Event, EventPriority};
+    ///
+    /// assert_eq!(Event::node_message("plan", "thinking").priority(), EventPriority::Normal);
+    /// assert_eq!(Event::diagnostic("run", "sink failed").priority(), EventPriority::High);
+    /// assert_eq!(
+    ///     Event::node_message("guard", "prompt injection blocked")
+    ///         .with_high_priority()
+    ///         .priority(),
+    ///     EventPriority::High,
+    /// );
+    /// ```
+    #[must_use]
+    pub fn priority(&self) -> EventPriority {
+        let (critical, metadata) = match self {
+            Event::Diagnostic(_) => return EventPriority::High,
+            Event::Node(node) => (
+                matches!(
+                    node.scope.as_str(),
+                    STREAM_END_SCOPE
+                        | INVOCATION_END_SCOPE
+                        | RETRACT_SCOPE
+                        | CANCELLATION_SCOPE
+                        | SLA_BREACH_SCOPE
+                        | RUN_SUMMARY_SCOPE
+                ),
+                &node.metadata,
+            ),
+            Event::LLM(llm) => (
+                matches!(
+                    llm.scope,
+                    LLMStreamingEventScope::Final | LLMStreamingEventScope::Error
+                ),
+                &llm.metadata,
+            ),
+        };
+        let marked = metadata
+            .get(PRIORITY_METADATA_KEY)
+            .and_then(Value::as_str)
+            .is_some_and(|priority| priority.eq_ignore_ascii_case("high"));
+        if critical || marked {
+            EventPriority::High
+        } else {
+            EventPriority::Normal
+        }
+    }
+
+    /// Mark a node or LLM event as [`High`](EventPriority::High) priority.
+    ///
+    /// Diagnostics are always high priority and are returned unchanged.
+    #[must_use]
+    pub fn with_high_priority(mut self) -> Self {
+        let metadata = match &mut self {
+            Event::Node(node) => &mut node.metadata,
+            Event::LLM(llm) => &mut llm.metadata,
+            Event::Diagnostic(_) => return self,
+        };
+        metadata.insert(
+            PRIORITY_METADATA_KEY.to_string(),
+            Value::String("high".to_string()),
+        );
+        self
+    }
+
     /// Convert event to structured JSON value with normalized schema.
     ///
     /// Returns a JSON object with the following structure:
@@ -116,6 +364,9 @@ impl Event {
     /// }
     /// ```
     ///
+    /// Node events with a structured payload also carry it under `"payload"`,
+    /// and sequenced events carry their number under `"sequence"`.
+    ///
     /// # Example
     ///
     /// ```
@@ -178,13 +429,58 @@ impl Event {
             _ => Utc::now(),
         };
 
-        json!({
+        let mut json = json!({
             "type": event_type,
             "scope": self.scope_label(),
             "message": self.message(),
             "timestamp": timestamp.to_rfc3339(),
             "metadata": metadata,
-        })
+        });
+        if let Event::Node(node) = self
+            && let Some(payload) = node.payload()
+        {
+            json["payload"] = payload.clone();
+        }
+        if let Some(sequence) = self.sequence() {
+            json["sequence"] = json!(sequence);
+        }
+        json
+    }
+
+    /// Mutable access to the free-text message, metadata map, and structured
+    /// payload, for redaction.
+    pub(crate) fn redactable_parts_mut(
+        &mut self,
+    ) -> (
+        &mut String,
+        Option<&mut FxHashMap<String, Value>>,
+        Option<&mut Value>,
+    ) {
+        match self {
+            Event::Node(node) => (
+                &mut node.message,
+                Some(&mut node.metadata),
+                node.payload.as_mut(),
+            ),
+            Event::Diagnostic(diag) => (&mut diag.message, None, None),
+            Event::LLM(llm) => (&mut llm.chunk, Some(&mut llm.metadata), None),
+        }
+    }
+
+    /// Mutable access to the message, metadata map, and payload slot that
+    /// envelope encryption seals. Diagnostics carry no tenant content and
+    /// return `None`.
+    #[cfg(feature = "encryption")]
+    pub(crate) fn sealable_parts_mut(&mut self) -> Option<SealableParts<'_>> {
+        match self {
+            Event::Node(node) => Some((
+                &mut node.message,
+                &mut node.metadata,
+                Some(&mut node.payload),
+            )),
+            Event::Diagnostic(_) => None,
+            Event::LLM(llm) => Some((&mut llm.chunk, &mut llm.metadata, None)),
+        }
     }
 
     /// Convert event to compact JSON string representation.
@@ -223,12 +519,18 @@ impl Event {
 impl fmt::Display for Event {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Event::Node(node) => match (node.node_id(), node.step()) {
-                (Some(id), Some(step)) => write!(f, "[{id}@{step}] {}", node.message()),
-                (Some(id), None) => write!(f, "[{id}] {}", node.message()),
-                (None, Some(step)) => write!(f, "[step {step}] {}", node.message()),
-                (None, None) => write!(f, "{}", node.message()),
-            },
+            Event::Node(node) => {
+                let text = match node.payload() {
+                    Some(payload) if node.message().is_empty() => payload.to_string(),
+                    _ => node.message().to_string(),
+                };
+                match (node.node_id(), node.step()) {
+                    (Some(id), Some(step)) => write!(f, "[{id}@{step}] {text}"),
+                    (Some(id), None) => write!(f, "[{id}] {text}"),
+                    (None, Some(step)) => write!(f, "[step {step}] {text}"),
+                    (None, None) => write!(f, "{text}"),
+                }
+            }
             Event::Diagnostic(diag) => write!(f, "{}", diag.message()),
             Event::LLM(llm) => {
                 if let Some(stream_id) = llm.stream_id() {
@@ -245,6 +547,7 @@ impl fmt::Display for Event {
 
 /// A structured event emitted by a workflow node during execution.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NodeEvent {
     node_id: Option<String>,
     step: Option<u64>,
@@ -252,6 +555,10 @@ pub struct NodeEvent {
     message: String,
     #[serde(default)]
     metadata: FxHashMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sequence: Option<u64>,
 }
 
 impl NodeEvent {
@@ -263,6 +570,8 @@ impl NodeEvent {
             scope,
             message,
             metadata: FxHashMap::default(),
+            payload: None,
+            sequence: None,
         }
     }
 
@@ -296,13 +605,27 @@ impl NodeEvent {
         self.metadata = metadata;
         self
     }
+
+    /// Returns the structured payload, if the event was emitted with one.
+    pub fn payload(&self) -> Option<&Value> {
+        self.payload.as_ref()
+    }
+
+    /// Return a new node event carrying the given structured payload.
+    pub fn with_payload(mut self, payload: Value) -> Self {
+        self.payload = Some(payload);
+        self
+    }
 }
 
 /// A framework-internal diagnostic event emitted outside normal node execution.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DiagnosticEvent {
     scope: String,
     message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sequence: Option<u64>,
 }
 
 impl DiagnosticEvent {
@@ -319,6 +642,7 @@ impl DiagnosticEvent {
 
 /// Scope discriminant for LLM streaming events.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum LLMStreamingEventScope {
     /// An in-progress streaming session (default scope).
     Streaming,
@@ -343,6 +667,7 @@ impl AsRef<str> for LLMStreamingEventScope {
 
 /// An event carrying an LLM response chunk, final marker, or error from a streaming session.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LLMStreamingEvent {
     session_id: Option<String>,
     node_id: Option<String>,
@@ -352,6 +677,8 @@ pub struct LLMStreamingEvent {
     scope: LLMStreamingEventScope,
     metadata: FxHashMap<String, Value>,
     timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sequence: Option<u64>,
 }
 
 impl LLMStreamingEvent {
@@ -376,6 +703,7 @@ impl LLMStreamingEvent {
             scope: scope.unwrap_or(LLMStreamingEventScope::Streaming),
             metadata,
             timestamp,
+            sequence: None,
         }
     }
 
@@ -1,26 +1,55 @@
 //! [`EventHub`] broadcast channel, [`EventStream`] receiver, and blocking iterator.
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::Duration;
 
+use futures_util::future;
 use futures_util::stream::{self, BoxStream, StreamExt};
-use std::sync::RwLock;
+use rustc_hash::FxHashMap;
+use std::sync::{Mutex, RwLock};
 use tokio::sync::{
     broadcast::{self, Receiver, Sender},
     watch,
 };
-use tokio::time::timeout;
+use tokio::time::{Instant, timeout, timeout_at};
 
+use super::diagnostics::SinkHealth;
 use super::emitter::{EmitterError, EventEmitter};
-use super::event::Event;
+use super::event::{Event, EventPriority};
+use crate::schedulers::SchedulerMetrics;
 
 /// Snapshot of hub health for monitoring and diagnostics.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct EventHubMetrics {
     /// Maximum number of events buffered per subscriber before lag occurs.
     pub capacity: usize,
     /// Total count of events dropped due to slow subscribers.
     pub dropped: usize,
+    /// Sequence numbers skipped by strict-ordering sink workers, summed
+    /// across sinks.
+    ///
+    /// Always `0` unless the bus uses
+    /// [`EventBus::with_strict_ordering`](crate::event_bus::EventBus::with_strict_ordering).
+    pub sequence_gaps: u64,
+    /// Per-sink health, populated by [`EventBus::metrics`](crate::event_bus::EventBus::metrics).
+    ///
+    /// Empty when read directly from an [`EventHub`], which has no knowledge of sinks.
+    pub sinks: Vec<SinkHealth>,
+    /// Scheduler saturation of the runner using this bus, populated by
+    /// [`EventBus::metrics`](crate::event_bus::EventBus::metrics).
+    ///
+    /// `None` until the bus is handed to an
+    /// [`AppRunner`](crate::runtimes::AppRunner).
+    pub scheduler: Option<SchedulerMetrics>,
+}
+
+impl EventHubMetrics {
+    /// Number of sinks currently disabled after exceeding the failure threshold.
+    #[must_use]
+    pub fn disabled_sinks(&self) -> usize {
+        self.sinks.iter().filter(|health| health.disabled).count()
+    }
 }
 
 /// Broadcast hub that owns the Tokio broadcast channel used by [`EventBus`](crate::event_bus::EventBus).
@@ -29,6 +58,14 @@ pub struct EventHub {
     sender: RwLock<Option<Sender<Event>>>,
     dropped_events: AtomicUsize,
     capacity: usize,
+    /// Whether [`publish`](Self::publish) stamps per-session sequence numbers.
+    sequencing: AtomicBool,
+    /// Last sequence number issued per session; `None` keys the bus-wide sequence.
+    sequences: Mutex<FxHashMap<Option<String>, u64>>,
+    sequence_gaps: AtomicU64,
+    /// Second channel carrying copies of high-priority events, once
+    /// [`enable_priority_lanes`](Self::enable_priority_lanes) is called.
+    priority_sender: RwLock<Option<Sender<Event>>>,
 }
 
 impl EventHub {
@@ -42,13 +79,99 @@ impl EventHub {
             sender: RwLock::new(Some(sender)),
             dropped_events: AtomicUsize::new(0),
             capacity,
+            sequencing: AtomicBool::new(false),
+            sequences: Mutex::new(FxHashMap::default()),
+            sequence_gaps: AtomicU64::new(0),
+            priority_sender: RwLock::new(None),
         })
     }
 
+    /// Open a second channel for [`EventPriority::High`] events.
+    ///
+    /// Every event is still published to the main channel, so
+    /// [`subscribe`](Self::subscribe) streams are unaffected. High-priority
+    /// events are additionally copied to a lane of the same capacity that
+    /// [`EventBus`](crate::event_bus::EventBus) sink workers drain first.
+    /// Calling this again, or after [`close`](Self::close), has no effect.
+    pub fn enable_priority_lanes(&self) {
+        if self.current_sender().is_none() {
+            return;
+        }
+        let mut lane = self
+            .priority_sender
+            .write()
+            .expect("EventHub sender RwLock poisoned");
+        if lane.is_none() {
+            *lane = Some(broadcast::channel(self.capacity).0);
+        }
+    }
+
+    /// Whether high-priority events are copied to a separate lane.
+    pub fn has_priority_lanes(&self) -> bool {
+        self.priority_sender
+            .read()
+            .expect("EventHub sender RwLock poisoned")
+            .is_some()
+    }
+
+    /// Stamp every event published from now on with a per-session sequence
+    /// number; see [`Event::sequence`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{Event, EventHub};
+    ///
+    /// let hub = EventHub::new(16);
+    /// hub.enable_sequencing();
+    /// let mut stream = hub.subscribe();
+    /// hub.publish(Event::diagnostic("run", "first")).unwrap();
+    /// hub.publish(Event::diagnostic("run", "second")).unwrap();
+    /// assert_eq!(stream.try_recv().unwrap().sequence(), Some(1));
+    /// assert_eq!(stream.try_recv().unwrap().sequence(), Some(2));
+    /// ```
+    pub fn enable_sequencing(&self) {
+        self.sequencing.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether published events are stamped with sequence numbers.
+    pub fn is_sequencing(&self) -> bool {
+        self.sequencing.load(Ordering::SeqCst)
+    }
+
     /// Publish an event to all subscribers.
     ///
+    /// On a sequencing hub, events without a sequence number are stamped with
+    /// the next number for their [`sequence_key`](Event::sequence_key).
+    /// Numbers are issued before the event enters the broadcast channel, so
+    /// events published concurrently may reach subscribers out of order;
+    /// strict-ordering sinks put them back in order.
+    ///
+    /// With [priority lanes](Self::enable_priority_lanes), high-priority
+    /// events are copied to the priority lane before entering the main
+    /// channel.
+    ///
     /// Returns [`EmitterError::Closed`] if the hub has been shut down.
     pub fn publish(&self, event: Event) -> Result<(), EmitterError> {
+        let event = if self.is_sequencing() && event.sequence().is_none() {
+            let sequence = {
+                let mut sequences = self.sequences.lock().unwrap_or_else(|e| e.into_inner());
+                let next = sequences
+                    .entry(event.sequence_key().map(str::to_owned))
+                    .or_insert(0);
+                *next += 1;
+                *next
+            };
+            event.with_sequence(sequence)
+        } else {
+            event
+        };
+        if event.priority() == EventPriority::High
+            && let Some(lane) = self.current_priority_sender()
+        {
+            // No receivers yet is not an error: the main channel decides.
+            let _ = lane.send(event.clone());
+        }
         match self.current_sender() {
             Some(sender) => match sender.send(event) {
                 Ok(_) => Ok(()),
@@ -78,9 +201,23 @@ impl EventHub {
             receiver,
             hub: Arc::clone(self),
             shutdown: None,
+            filter: None,
+            missed: 0,
         }
     }
 
+    /// Subscribe to the priority lane, or `None` if it is not enabled.
+    pub(crate) fn subscribe_priority_lane(self: &Arc<Self>) -> Option<EventStream> {
+        let receiver = self.current_priority_sender()?.subscribe();
+        Some(EventStream {
+            receiver,
+            hub: Arc::clone(self),
+            shutdown: None,
+            filter: None,
+            missed: 0,
+        })
+    }
+
     /// Returns the configured buffer capacity of the underlying broadcast channel.
     pub fn capacity(&self) -> usize {
         self.capacity
@@ -96,6 +233,9 @@ impl EventHub {
         EventHubMetrics {
             capacity: self.capacity(),
             dropped: self.dropped(),
+            sequence_gaps: self.sequence_gaps.load(Ordering::Relaxed),
+            sinks: Vec::new(),
+            scheduler: None,
         }
     }
 
@@ -113,6 +253,11 @@ impl EventHub {
             .write()
             .expect("EventHub sender RwLock poisoned")
             .take();
+        let _ = self
+            .priority_sender
+            .write()
+            .expect("EventHub sender RwLock poisoned")
+            .take();
     }
 
     fn current_sender(&self) -> Option<Sender<Event>> {
@@ -122,6 +267,29 @@ impl EventHub {
             .clone()
     }
 
+    fn current_priority_sender(&self) -> Option<Sender<Event>> {
+        self.priority_sender
+            .read()
+            .expect("EventHub sender RwLock poisoned")
+            .clone()
+    }
+
+    pub(crate) fn record_sequence_gaps(&self, skipped: u64) {
+        if skipped == 0 {
+            return;
+        }
+        let total = self
+            .sequence_gaps
+            .fetch_add(skipped, Ordering::Relaxed)
+            .saturating_add(skipped);
+        tracing::warn!(
+            target: "weavegraph::event_bus",
+            skipped,
+            total_gaps = total,
+            "strict ordering skipped missing sequence numbers"
+        );
+    }
+
     fn record_lag(&self, missed: u64) {
         if missed == 0 {
             return;
@@ -152,40 +320,112 @@ impl EventEmitter for HubEmitter {
     }
 }
 
+/// Predicate deciding which events an [`EventStream`] yields.
+type EventPredicate = Arc<dyn Fn(&Event) -> bool + Send + Sync>;
+
 /// Async receive handle for a subscription to an [`EventHub`].
-#[derive(Debug)]
+///
+/// [`filter`](Self::filter), [`filter_scope`](Self::filter_scope), and
+/// [`only_llm`](Self::only_llm) narrow the events every receive method
+/// yields; [`map`](Self::map), [`tee`](Self::tee), and
+/// [`buffer_batched`](Self::buffer_batched) turn the subscription into new
+/// streams.
 pub struct EventStream {
     receiver: Receiver<Event>,
     hub: Arc<EventHub>,
     shutdown: Option<watch::Receiver<bool>>,
+    filter: Option<EventPredicate>,
+    /// Events this subscription lost to lag.
+    missed: u64,
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("receiver", &self.receiver)
+            .field("hub", &self.hub)
+            .field("shutdown", &self.shutdown)
+            .field("filtered", &self.filter.is_some())
+            .field("missed", &self.missed)
+            .finish()
+    }
 }
 
 impl EventStream {
+    fn accepts(&self, event: &Event) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter(event))
+    }
+
     /// Receive the next event, awaiting if the channel is empty.
     pub async fn recv(&mut self) -> Result<Event, broadcast::error::RecvError> {
-        match self.receiver.recv().await {
-            Ok(event) => Ok(event),
-            Err(broadcast::error::RecvError::Lagged(missed)) => {
-                self.hub.record_lag(missed);
-                Err(broadcast::error::RecvError::Lagged(missed))
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.accepts(&event) => return Ok(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    self.hub.record_lag(missed);
+                    self.missed = self.missed.saturating_add(missed);
+                    return Err(broadcast::error::RecvError::Lagged(missed));
+                }
+                Err(err) => return Err(err),
             }
-            Err(err) => Err(err),
         }
     }
 
     /// Try to receive an event without blocking; returns immediately if none is available.
     pub fn try_recv(&mut self) -> Result<Event, broadcast::error::TryRecvError> {
-        match self.receiver.try_recv() {
-            Ok(event) => Ok(event),
-            Err(broadcast::error::TryRecvError::Lagged(missed)) => {
-                self.hub.record_lag(missed);
-                Err(broadcast::error::TryRecvError::Lagged(missed))
+        let mut unbounded = usize::MAX;
+        self.try_recv_within(&mut unbounded)
+    }
+
+    /// Number of events queued for this subscription, including ones its
+    /// filter will skip and ones already lost to lag.
+    pub(crate) fn queued(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Like [`try_recv`](Self::try_recv), but consumes at most `*budget`
+    /// queued events (skipped and lagged ones included) and reports `Empty`
+    /// once the budget is spent.
+    pub(crate) fn try_recv_within(
+        &mut self,
+        budget: &mut usize,
+    ) -> Result<Event, broadcast::error::TryRecvError> {
+        loop {
+            if *budget == 0 {
+                return Err(broadcast::error::TryRecvError::Empty);
+            }
+            match self.receiver.try_recv() {
+                Ok(event) => {
+                    *budget -= 1;
+                    if self.accepts(&event) {
+                        return Ok(event);
+                    }
+                }
+                Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                    self.hub.record_lag(missed);
+                    self.missed = self.missed.saturating_add(missed);
+                    *budget = budget.saturating_sub(usize::try_from(missed).unwrap_or(usize::MAX));
+                    return Err(broadcast::error::TryRecvError::Lagged(missed));
+                }
+                Err(err) => return Err(err),
             }
-            Err(err) => Err(err),
         }
     }
 
+    /// Number of events this subscription has lost because it fell more than
+    /// the hub's capacity behind.
+    ///
+    /// Counted by [`recv`](Self::recv), [`try_recv`](Self::try_recv), and
+    /// [`next_timeout`](Self::next_timeout); the hub-wide total is
+    /// [`EventHubMetrics::dropped`].
+    pub fn dropped(&self) -> u64 {
+        self.missed
+    }
+
     /// Consume the stream and return the raw broadcast receiver.
+    ///
+    /// The receiver yields every event; filters are not applied.
     pub fn into_inner(self) -> Receiver<Event> {
         self.receiver
     }
@@ -195,7 +435,155 @@ impl EventStream {
         BlockingEventIter {
             receiver: self.receiver,
             hub: self.hub,
+            filter: self.filter,
+        }
+    }
+
+    /// Only yield events for which `predicate` returns `true`.
+    ///
+    /// Filters compose: calling this on an already filtered stream keeps
+    /// events that pass both predicates. Skipped events still count towards
+    /// the subscription's buffer, so a heavily filtered stream can lag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{Event, EventHub};
+    ///
+    /// let hub = EventHub::new(16);
+    /// let mut errors = hub
+    ///     .subscribe()
+    ///     .filter(|event| event.message().contains("error"));
+    /// hub.publish(Event::diagnostic("run", "all good")).unwrap();
+    /// hub.publish(Event::diagnostic("run", "error: boom")).unwrap();
+    /// assert_eq!(errors.try_recv().unwrap().message(), "error: boom");
+    /// ```
+    #[must_use]
+    pub fn filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Event) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => Arc::new(move |event: &Event| existing(event) && predicate(event)),
+            None => Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Only yield events whose [`scope_label`](Event::scope_label) is `label`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{Event, EventHub};
+    ///
+    /// let hub = EventHub::new(16);
+    /// let mut search = hub.subscribe().filter_scope("search");
+    /// hub.publish(Event::node_message("plan", "thinking")).unwrap();
+    /// hub.publish(Event::node_message("search", "3 hits")).unwrap();
+    /// assert_eq!(search.try_recv().unwrap().message(), "3 hits");
+    /// ```
+    #[must_use]
+    pub fn filter_scope(self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        self.filter(move |event| event.scope_label() == Some(label.as_str()))
+    }
+
+    /// Only yield LLM streaming events.
+    #[must_use]
+    pub fn only_llm(self) -> Self {
+        self.filter(|event| matches!(event, Event::LLM(_)))
+    }
+
+    /// Convert each event with `f`, yielding a stream of the results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::EventHub;
+    ///
+    /// let hub = EventHub::new(16);
+    /// let sse_lines = hub
+    ///     .subscribe()
+    ///     .map(|event| format!("data: {}\n\n", event.to_json_value()));
+    /// # drop(sse_lines);
+    /// ```
+    pub fn map<T, F>(self, f: F) -> BoxStream<'static, T>
+    where
+        T: Send + 'static,
+        F: FnMut(Event) -> T + Send + 'static,
+    {
+        self.into_async_stream().map(f).boxed()
+    }
+
+    /// Split the subscription into `n` streams that each receive every event.
+    ///
+    /// Each copy keeps this stream's filter and shutdown signal and has its
+    /// own buffer, so a slow consumer lags only its own copy. Events already
+    /// buffered in this stream are delivered to the first copy only; tee
+    /// before the run starts when every copy must see the full sequence.
+    /// Returns an empty vector when `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::{Event, EventHub};
+    ///
+    /// let hub = EventHub::new(16);
+    /// let mut copies = hub.subscribe().tee(2);
+    /// hub.publish(Event::diagnostic("run", "hello")).unwrap();
+    /// for copy in &mut copies {
+    ///     assert_eq!(copy.try_recv().unwrap().message(), "hello");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn tee(self, n: usize) -> Vec<EventStream> {
+        if n == 0 {
+            return Vec::new();
         }
+        let mut copies: Vec<EventStream> = (1..n)
+            .map(|_| EventStream {
+                receiver: self.receiver.resubscribe(),
+                hub: Arc::clone(&self.hub),
+                shutdown: self.shutdown.clone(),
+                filter: self.filter.clone(),
+                missed: 0,
+            })
+            .collect();
+        copies.insert(0, self);
+        copies
+    }
+
+    /// Group events into batches collected over `window`.
+    ///
+    /// A batch starts with the next event to arrive and closes `window`
+    /// later, so batches are never empty and an idle stream yields nothing.
+    /// Useful for writing events to a socket or database in chunks.
+    pub fn buffer_batched(self, window: Duration) -> BoxStream<'static, Vec<Event>> {
+        stream::unfold(
+            (self.into_async_stream(), false),
+            move |(mut inner, ended)| async move {
+                if ended {
+                    return None;
+                }
+                let first = inner.next().await?;
+                let deadline = Instant::now() + window;
+                let mut batch = vec![first];
+                let mut ended = false;
+                loop {
+                    match timeout_at(deadline, inner.next()).await {
+                        Ok(Some(event)) => batch.push(event),
+                        Ok(None) => {
+                            ended = true;
+                            break;
+                        }
+                        Err(_) => break,
+                    }
+                }
+                Some((batch, (inner, ended)))
+            },
+        )
+        .boxed()
     }
 
     /// Attach a shutdown watch channel; the stream ends when the watch value becomes `true`.
@@ -214,42 +602,52 @@ impl EventStream {
             receiver,
             hub,
             shutdown,
+            filter,
+            ..
         } = self;
-        stream::unfold((receiver, hub, shutdown), |(mut receiver, hub, mut shutdown)| async move {
-            loop {
-                if let Some(ref mut shutdown_rx) = shutdown {
-                    tokio::select! {
-                        biased;
-                        changed = shutdown_rx.changed() => {
-                            if changed.is_ok() && *shutdown_rx.borrow() {
-                                return None;
+        let events = stream::unfold(
+            (receiver, hub, shutdown),
+            |(mut receiver, hub, mut shutdown)| async move {
+                loop {
+                    if let Some(ref mut shutdown_rx) = shutdown {
+                        tokio::select! {
+                            biased;
+                            changed = shutdown_rx.changed() => {
+                                if changed.is_ok() && *shutdown_rx.borrow() {
+                                    return None;
+                                }
+                                continue;
                             }
-                            continue;
-                        }
-                        recv = receiver.recv() => {
-                            match recv {
-                                Ok(event) => return Some((event, (receiver, hub.clone(), shutdown))),
-                                Err(broadcast::error::RecvError::Lagged(missed)) => {
-                                    hub.record_lag(missed);
-                                    continue;
+                            recv = receiver.recv() => {
+                                match recv {
+                                    Ok(event) => return Some((event, (receiver, hub.clone(), shutdown))),
+                                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                                        hub.record_lag(missed);
+                                        continue;
+                                    }
+                                    Err(broadcast::error::RecvError::Closed) => return None,
                                 }
-                                Err(broadcast::error::RecvError::Closed) => return None,
                             }
                         }
-                    }
-                } else {
-                    match receiver.recv().await {
-                        Ok(event) => return Some((event, (receiver, hub.clone(), shutdown))),
-                        Err(broadcast::error::RecvError::Lagged(missed)) => {
-                            hub.record_lag(missed);
-                            continue;
+                    } else {
+                        match receiver.recv().await {
+                            Ok(event) => return Some((event, (receiver, hub.clone(), shutdown))),
+                            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                                hub.record_lag(missed);
+                                continue;
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return None,
                         }
-                        Err(broadcast::error::RecvError::Closed) => return None,
                     }
                 }
-            }
-        })
-        .boxed()
+            },
+        );
+        match filter {
+            Some(filter) => events
+                .filter(move |event| future::ready(filter(event)))
+                .boxed(),
+            None => events.boxed(),
+        }
     }
 
     /// Receive the next event, waiting at most `duration`; returns `None` on timeout or close.
@@ -271,6 +669,7 @@ impl EventStream {
 pub struct BlockingEventIter {
     receiver: Receiver<Event>,
     hub: Arc<EventHub>,
+    filter: Option<EventPredicate>,
 }
 
 impl Iterator for BlockingEventIter {
@@ -279,7 +678,10 @@ impl Iterator for BlockingEventIter {
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.receiver.blocking_recv() {
-                Ok(event) => return Some(event),
+                Ok(event) if self.filter.as_ref().is_none_or(|filter| filter(&event)) => {
+                    return Some(event);
+                }
+                Ok(_) => continue,
                 Err(broadcast::error::RecvError::Lagged(missed)) => {
                     self.hub.record_lag(missed);
                     continue;
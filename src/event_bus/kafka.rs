@@ -0,0 +1,103 @@
+//! Kafka publisher for [`BrokerSink`](super::BrokerSink).
+//!
+//! Records are produced to the topic in [`BrokerRecord::destination`] with the
+//! session id as message key, so all events of a session land on one
+//! partition in order (with the default partitioner).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use weavegraph::event_bus::{BrokerSink, EventBus, KafkaPublisher};
+//!
+//! let publisher = KafkaPublisher::from_brokers("localhost:9092").unwrap();
+//! let bus = EventBus::with_sink(BrokerSink::new(publisher, "weavegraph-events"));
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::ClientConfig;
+use rdkafka::error::KafkaError;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+
+use super::broker::{BrokerPublishError, BrokerPublisher, BrokerRecord};
+
+/// Publishes [`BrokerRecord`]s with an `rdkafka` [`FutureProducer`].
+pub struct KafkaPublisher {
+    producer: FutureProducer,
+    timeout: Duration,
+}
+
+impl KafkaPublisher {
+    /// Publish through an already configured producer.
+    pub fn new(producer: FutureProducer) -> Self {
+        Self {
+            producer,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Connect a producer to `bootstrap_servers` with default settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns the client error if the producer cannot be created.
+    pub fn from_brokers(bootstrap_servers: &str) -> Result<Self, KafkaError> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", bootstrap_servers)
+            .create()?;
+        Ok(Self::new(producer))
+    }
+
+    /// Wait at most `timeout` for a record's delivery report (default 5 s).
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl BrokerPublisher for KafkaPublisher {
+    async fn publish(&self, batch: Vec<BrokerRecord>) -> Result<(), BrokerPublishError> {
+        // Enqueue the whole batch first so librdkafka can batch the sends.
+        let mut deliveries = Vec::with_capacity(batch.len());
+        let mut failed = Vec::new();
+        let mut error = None;
+        for record in batch {
+            let queued = {
+                let mut message =
+                    FutureRecord::<str, [u8]>::to(&record.destination).payload(&record.payload);
+                if let Some(key) = &record.key {
+                    message = message.key(key.as_str());
+                }
+                self.producer.send_result(message).map_err(|(err, _)| err)
+            };
+            match queued {
+                Ok(delivery) => deliveries.push((record, delivery)),
+                Err(err) => {
+                    error.get_or_insert_with(|| err.to_string());
+                    failed.push(record);
+                }
+            }
+        }
+        for (record, delivery) in deliveries {
+            let outcome = match tokio::time::timeout(self.timeout, delivery).await {
+                Ok(Ok(Ok(_))) => continue,
+                Ok(Ok(Err((err, _)))) => err.to_string(),
+                Ok(Err(_)) => "delivery report dropped".to_string(),
+                Err(_) => format!("no delivery report within {:?}", self.timeout),
+            };
+            error.get_or_insert(outcome);
+            failed.push(record);
+        }
+        match error {
+            None => Ok(()),
+            Some(message) => Err(BrokerPublishError::new(failed, message)),
+        }
+    }
+
+    fn name(&self) -> String {
+        "kafka".to_string()
+    }
+}
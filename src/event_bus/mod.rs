@@ -11,20 +11,85 @@
 //! - [`Event::to_json_pretty()`] - Pretty-printed JSON for debugging
 //!
 //! The [`JsonLinesSink`] provides machine-readable JSON Lines output for log
-//! aggregation systems and monitoring tools.
+//! aggregation systems and monitoring tools. [`EncodedSink`] writes events in a
+//! selectable [`EventFormat`] (JSON, plus MessagePack and CBOR behind the
+//! `msgpack` / `cbor` features), and [`EventDecoder`] reads them back.
+//! [`AggregatingSink`] keeps in-memory rollups (event rates, error counts by
+//! tag, node latency) for deployments without a metrics stack.
+//! With the `encryption` feature, `EncryptedSink` wraps any sink and seals
+//! event content with per-tenant keys before it leaves the process.
+//! [`BrokerSink`] publishes batches of encoded events to a message broker,
+//! partitioned by session, with publishers for Kafka and NATS behind the
+//! `kafka` / `nats` features.
+//!
+//! # Ordering and delivery
+//!
+//! - Events from one emitter reach each subscriber and sink in emission order.
+//! - Events from nodes running concurrently in a superstep interleave
+//!   arbitrarily; there is no ordering between sessions.
+//! - Every subscriber and sink worker has a buffer of the bus capacity. A
+//!   consumer that falls further behind loses the oldest events; losses are
+//!   counted in [`EventHubMetrics::dropped`] and, per subscription, by
+//!   [`EventStream::dropped`].
+//!
+//! [`EventBus::with_sequence_numbers`] stamps each event with a per-session
+//! [`Event::sequence`], so consumers can detect gaps themselves.
+//! [`EventBus::with_strict_ordering`] also makes sink workers buffer and
+//! reorder events per session before delivery. Numbers that never arrive are
+//! skipped and counted in [`EventHubMetrics::sequence_gaps`].
+//!
+//! [`EventBus::with_priority_lanes`] gives sinks a second lane for events
+//! whose [`Event::priority`] is [`EventPriority::High`] (diagnostics, stream
+//! ends, LLM errors, and events marked with [`Event::with_high_priority`]).
+//! Sink workers drain it before the normal lane, so critical events do not
+//! queue behind LLM chunks. Each lane keeps channel order on its own; a
+//! high-priority event may overtake normal events published before it.
+//!
+//! Steps can run more than once when a run fails or is retried; see
+//! [`retract`] for reconciling events from discarded attempts.
 
+pub mod aggregate;
+pub mod broker;
 pub mod bus;
+pub mod codec;
 pub mod diagnostics;
 pub mod emitter;
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+pub mod encrypted;
 pub mod event;
 pub mod hub;
+#[cfg(feature = "kafka")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kafka")))]
+pub mod kafka;
+#[cfg(feature = "nats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nats")))]
+pub mod nats;
+mod ordering;
+pub mod retract;
 pub mod sink;
 
-pub use bus::EventBus;
-pub use diagnostics::{DiagnosticsStream, SinkDiagnostic};
+pub use aggregate::{AggregatingSink, DEFAULT_RATE_WINDOW, EventRollup, NodeLatency};
+pub use broker::{
+    BrokerPublishError, BrokerPublisher, BrokerRecord, BrokerSink, DEFAULT_BROKER_BATCH_SIZE,
+    DEFAULT_BROKER_LINGER, NO_SESSION, SESSION_PLACEHOLDER,
+};
+pub use bus::{EventBus, FlushError};
+pub use codec::{EncodedSink, EventDecoder, EventFormat};
+pub use diagnostics::{DiagnosticsStream, SinkDiagnostic, SinkHealth};
 pub use emitter::{EmitterError, EventEmitter};
+#[cfg(feature = "encryption")]
+pub use encrypted::{EncryptedSink, SEALED_METADATA_KEY, SHARED_TENANT, open_event, seal_event};
 pub use event::{
-    DIAGNOSTIC_SCOPE, Event, INVOCATION_END_SCOPE, LLMStreamingEvent, NodeEvent, STREAM_END_SCOPE,
+    CANCELLATION_SCOPE, DIAGNOSTIC_SCOPE, EXTRA_CONFLICT_SCOPE, Event, EventPriority,
+    INVOCATION_END_SCOPE, LLMStreamingEvent, NodeEvent, PRIORITY_METADATA_KEY, RETRACT_SCOPE,
+    ROLLUP_SUMMARY_SCOPE, ROUTING_SCOPE, RUN_SUMMARY_SCOPE, SLA_BREACH_SCOPE, STATE_LIMIT_SCOPE,
+    STEP_METRICS_SCOPE, STREAM_END_SCOPE, TRACING_SCOPE,
 };
 pub use hub::{BlockingEventIter, EventHub, EventHubMetrics, EventStream, HubEmitter};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaPublisher;
+#[cfg(feature = "nats")]
+pub use nats::{NatsPublisher, SESSION_HEADER};
+pub use retract::AttemptRetraction;
 pub use sink::{ChannelSink, EventSink, JsonLinesSink, MemorySink, StdOutSink};
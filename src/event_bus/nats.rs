@@ -0,0 +1,85 @@
+//! NATS publisher for [`BrokerSink`](super::BrokerSink).
+//!
+//! Records are published to the subject in [`BrokerRecord::destination`];
+//! use a destination such as `weavegraph.events.{session}` to give each
+//! session its own subject. The session id is also sent in the
+//! [`SESSION_HEADER`] header. Each batch ends with a client flush, so a batch
+//! counts as delivered once the server has received it.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use weavegraph::event_bus::{BrokerSink, EventBus, NatsPublisher};
+//!
+//! # async fn example() -> Result<(), async_nats::ConnectError> {
+//! let publisher = NatsPublisher::connect("nats://localhost:4222").await?;
+//! let bus = EventBus::with_sink(BrokerSink::new(publisher, "weavegraph.events.{session}"));
+//! # Ok(())
+//! # }
+//! ```
+
+use async_nats::{Client, ConnectError, HeaderMap, ToServerAddrs};
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::broker::{BrokerPublishError, BrokerPublisher, BrokerRecord};
+
+/// Header carrying the record's session id.
+pub const SESSION_HEADER: &str = "Weavegraph-Session";
+
+/// Publishes [`BrokerRecord`]s with an `async-nats` [`Client`].
+pub struct NatsPublisher {
+    client: Client,
+}
+
+impl NatsPublisher {
+    /// Publish through a connected client.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Connect to the NATS server(s) at `addrs` with default options.
+    ///
+    /// # Errors
+    ///
+    /// Returns the connection error if no server can be reached.
+    pub async fn connect<A: ToServerAddrs>(addrs: A) -> Result<Self, ConnectError> {
+        Ok(Self::new(async_nats::connect(addrs).await?))
+    }
+}
+
+#[async_trait]
+impl BrokerPublisher for NatsPublisher {
+    async fn publish(&self, batch: Vec<BrokerRecord>) -> Result<(), BrokerPublishError> {
+        for (index, record) in batch.iter().enumerate() {
+            let payload = Bytes::copy_from_slice(&record.payload);
+            let subject = record.destination.clone();
+            let sent = match &record.key {
+                Some(key) => {
+                    let mut headers = HeaderMap::new();
+                    headers.insert(SESSION_HEADER, key.as_str());
+                    self.client
+                        .publish_with_headers(subject, headers, payload)
+                        .await
+                }
+                None => self.client.publish(subject, payload).await,
+            };
+            if let Err(err) = sent {
+                return Err(BrokerPublishError::new(
+                    batch[index..].to_vec(),
+                    err.to_string(),
+                ));
+            }
+        }
+        // Publishing only queues messages in the client; a failed flush
+        // leaves the whole batch's delivery unknown, so all of it is retried.
+        self.client
+            .flush()
+            .await
+            .map_err(|err| BrokerPublishError::new(batch, err.to_string()))
+    }
+
+    fn name(&self) -> String {
+        "nats".to_string()
+    }
+}
@@ -0,0 +1,103 @@
+//! Per-session reorder buffer used by strict-ordering sink workers.
+//!
+//! A sequencing [`EventHub`](super::EventHub) numbers events before they enter
+//! the broadcast channel, so two nodes emitting at the same moment can land in
+//! the channel in the opposite order. [`SequenceReorderer`] holds early events
+//! until their predecessors arrive and releases each session's events in
+//! sequence order.
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+
+use super::event::Event;
+
+/// Reorders sequenced events per session before they reach a sink.
+#[derive(Debug)]
+pub(crate) struct SequenceReorderer {
+    /// Maximum events held for one session before the missing predecessor is
+    /// given up on.
+    window: usize,
+    sessions: FxHashMap<Option<String>, SessionOrder>,
+    /// Sequence numbers skipped since the last [`take_gaps`](Self::take_gaps).
+    gaps: u64,
+}
+
+#[derive(Debug)]
+struct SessionOrder {
+    next: u64,
+    pending: BTreeMap<u64, Event>,
+}
+
+impl Default for SessionOrder {
+    fn default() -> Self {
+        Self {
+            next: 1,
+            pending: BTreeMap::new(),
+        }
+    }
+}
+
+impl SequenceReorderer {
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            sessions: FxHashMap::default(),
+            gaps: 0,
+        }
+    }
+
+    /// Accept `event` and return the events now ready for delivery, in order.
+    ///
+    /// Unsequenced events pass straight through. An event whose number was
+    /// already skipped is delivered immediately rather than dropped.
+    pub(crate) fn push(&mut self, event: Event) -> Vec<Event> {
+        let Some(sequence) = event.sequence() else {
+            return vec![event];
+        };
+        let key = event.sequence_key().map(str::to_owned);
+        let order = self.sessions.entry(key).or_default();
+        if sequence < order.next {
+            return vec![event];
+        }
+        order.pending.insert(sequence, event);
+        if order.pending.len() > self.window
+            && let Some(&first) = order.pending.keys().next()
+        {
+            self.gaps += first - order.next;
+            order.next = first;
+        }
+        order.release_ready()
+    }
+
+    /// Release every held event in order, skipping the numbers still missing.
+    ///
+    /// Called when the subscription lags (the missing events are gone) and
+    /// before a flush or shutdown.
+    pub(crate) fn release_all(&mut self) -> Vec<Event> {
+        let mut released = Vec::new();
+        for order in self.sessions.values_mut() {
+            while let Some(&first) = order.pending.keys().next() {
+                self.gaps += first - order.next;
+                order.next = first;
+                released.extend(order.release_ready());
+            }
+        }
+        released
+    }
+
+    /// Return and reset the number of skipped sequence numbers.
+    pub(crate) fn take_gaps(&mut self) -> u64 {
+        std::mem::take(&mut self.gaps)
+    }
+}
+
+impl SessionOrder {
+    fn release_ready(&mut self) -> Vec<Event> {
+        let mut ready = Vec::new();
+        while let Some(event) = self.pending.remove(&self.next) {
+            ready.push(event);
+            self.next += 1;
+        }
+        ready
+    }
+}
@@ -0,0 +1,128 @@
+//! Reconciling events from step attempts whose results were discarded.
+//!
+//! A runner can execute a superstep more than once: a node fails and the
+//! caller retries, or the step's checkpoint cannot be saved and the step is
+//! rolled back or re-executed under a
+//! [`CheckpointFailurePolicy`](crate::runtimes::CheckpointFailurePolicy).
+//! Events the nodes emitted meanwhile have already reached the sinks, so a
+//! dashboard counting them would count retried work twice.
+//!
+//! Node events emitted on a re-execution carry an `attempt` number in their
+//! metadata ([`Event::attempt`]). When an attempt's results are discarded the
+//! runner publishes an [`AttemptRetraction`] under
+//! [`RETRACT_SCOPE`](super::RETRACT_SCOPE). Consumers that need exact counts
+//! drop the events the retraction [`covers`](AttemptRetraction::covers);
+//! consumers that do not care can ignore the scope.
+//!
+//! Only node events carry a step, so LLM streaming chunks are not covered.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::event_bus::{AttemptRetraction, Event};
+//!
+//! let mut received: Vec<Event> = Vec::new();
+//! let mut on_event = |event: Event| match AttemptRetraction::from_event(&event) {
+//!     Some(retraction) => received.retain(|seen| !retraction.covers(seen)),
+//!     None => received.push(event),
+//! };
+//!
+//! on_event(Event::node_message("progress", "unrelated"));
+//! on_event(AttemptRetraction::new("s-1", 3, 1, "step_error").to_event());
+//! ```
+
+use rustc_hash::FxHashMap;
+use serde_json::{Value, json};
+
+use super::event::{Event, NodeEvent, RETRACT_SCOPE};
+
+/// A discarded attempt of one session step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttemptRetraction {
+    /// Session whose step was discarded.
+    pub session_id: String,
+    /// Step number of the discarded attempt.
+    pub step: u64,
+    /// Attempt number, starting at 1.
+    pub attempt: u32,
+    /// Why the attempt was discarded: `step_error`, `checkpoint_rerun`, or
+    /// `checkpoint_rollback` for retractions published by the runner.
+    pub reason: String,
+}
+
+impl AttemptRetraction {
+    /// Retract `attempt` of `step` in `session_id`.
+    pub fn new(
+        session_id: impl Into<String>,
+        step: u64,
+        attempt: u32,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            step,
+            attempt,
+            reason: reason.into(),
+        }
+    }
+
+    /// Whether `event` was emitted by the retracted attempt.
+    ///
+    /// Matches node events of the same session and step whose
+    /// [`attempt`](Event::attempt) equals the retracted one. Retractions never
+    /// cover other retractions.
+    #[must_use]
+    pub fn covers(&self, event: &Event) -> bool {
+        let Event::Node(node) = event else {
+            return false;
+        };
+        node.scope() != RETRACT_SCOPE
+            && node.step() == Some(self.step)
+            && event.sequence_key() == Some(self.session_id.as_str())
+            && event.attempt() == self.attempt
+    }
+
+    /// The [`RETRACT_SCOPE`] event announcing this retraction.
+    #[must_use]
+    pub fn to_event(&self) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert("invocation_id".to_string(), json!(self.session_id));
+        metadata.insert("attempt".to_string(), json!(self.attempt));
+        metadata.insert("reason".to_string(), json!(self.reason));
+        Event::Node(
+            NodeEvent::new(
+                None,
+                Some(self.step),
+                RETRACT_SCOPE.to_string(),
+                format!(
+                    "step {} attempt {} retracted ({})",
+                    self.step, self.attempt, self.reason
+                ),
+            )
+            .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a retraction event, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node) = event else {
+            return None;
+        };
+        if node.scope() != RETRACT_SCOPE {
+            return None;
+        }
+        let metadata = node.metadata();
+        Some(Self {
+            session_id: metadata.get("invocation_id")?.as_str()?.to_string(),
+            step: node.step()?,
+            attempt: u32::try_from(metadata.get("attempt")?.as_u64()?).ok()?,
+            reason: metadata
+                .get("reason")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+}
@@ -17,6 +17,15 @@ pub trait EventSink: Sync + Send {
     /// hand the call off to `spawn_blocking` to keep the async runtime responsive.
     fn handle(&mut self, event: &Event) -> IoResult<()>;
 
+    /// Push any buffered output to its destination.
+    ///
+    /// Called by [`EventBus::flush`](crate::event_bus::EventBus::flush) after
+    /// every event published before the flush has been handled. Sinks that
+    /// write through immediately can rely on the default no-op.
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+
     /// A stable, human-friendly identifier for this sink instance.
     ///
     /// Defaults to the concrete type name; implementors may override to provide
@@ -57,6 +66,10 @@ impl<F: TelemetryFormatter> EventSink for StdOutSink<F> {
         self.handle.write_all(rendered.as_bytes())?;
         self.handle.flush()
     }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.handle.flush()
+    }
 }
 
 /// In-memory sink for testing and snapshots.
@@ -245,6 +258,10 @@ impl EventSink for JsonLinesSink {
         self.handle.flush()
     }
 
+    fn flush(&mut self) -> IoResult<()> {
+        self.handle.flush()
+    }
+
     fn name(&self) -> String {
         if self.pretty {
             "JsonLinesSink(pretty)".to_string()
@@ -0,0 +1,273 @@
+//! Runtime feature flags for toggling behaviour per session without redeploys.
+//!
+//! A [`FeatureFlagProvider`] set with
+//! [`RuntimeConfig::with_feature_flags`](crate::runtimes::RuntimeConfig::with_feature_flags)
+//! is asked for flag values whenever a runner creates or resumes a session.
+//! The resolved [`FeatureFlags`] stay fixed for that session in the runner and
+//! are visible to nodes through
+//! [`NodeContext::feature_flags`](crate::node::NodeContext::feature_flags) and
+//! to conditional edges through
+//! [`RoutingContext::feature_flags`](crate::graphs::RoutingContext::feature_flags)
+//! or the `flags` root of routing [expressions](crate::graphs::Expression).
+//!
+//! Two providers ship with the crate: [`StaticFlags`] for fixed values and
+//! [`EnvFlags`] for environment variables. Implement the trait to query a
+//! remote flag service.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::feature_flags::StaticFlags;
+//! use weavegraph::runtimes::RuntimeConfig;
+//!
+//! let config = RuntimeConfig::default().with_feature_flags(
+//!     StaticFlags::new()
+//!         .with_flag("new_prompt", true)
+//!         .with_flag("ranker", "bm25"),
+//! );
+//! assert!(config.feature_flags().is_some());
+//! ```
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Flag values resolved for one session.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use weavegraph::feature_flags::FeatureFlags;
+///
+/// let flags = FeatureFlags::from_iter([
+///     ("new_prompt".to_string(), json!(true)),
+///     ("ranker".to_string(), json!("bm25")),
+/// ]);
+/// assert!(flags.is_enabled("new_prompt"));
+/// assert!(!flags.is_enabled("missing"));
+/// assert_eq!(flags.variant("ranker"), Some("bm25"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureFlags {
+    values: BTreeMap<String, Value>,
+}
+
+/// Flags seen by contexts that were not given any.
+pub(crate) static NO_FLAGS: FeatureFlags = FeatureFlags {
+    values: BTreeMap::new(),
+};
+
+impl FeatureFlags {
+    /// An empty set of flags.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw value of a flag.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Whether a flag is on.
+    ///
+    /// `true`, non-zero numbers, and the strings `"true"`, `"on"`, `"yes"`,
+    /// and `"1"` (case-insensitive) count as on; anything else, including a
+    /// missing flag, is off.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        match self.values.get(name) {
+            Some(Value::Bool(on)) => *on,
+            Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+            Some(Value::String(s)) => {
+                matches!(s.to_ascii_lowercase().as_str(), "true" | "on" | "yes" | "1")
+            }
+            _ => false,
+        }
+    }
+
+    /// String value of a multi-variant flag, such as an experiment arm.
+    #[must_use]
+    pub fn variant(&self, name: &str) -> Option<&str> {
+        self.values.get(name).and_then(Value::as_str)
+    }
+
+    /// Deserialize a flag into `T`.
+    ///
+    /// Returns `None` if the flag is missing and `Some(Err(_))` if it does
+    /// not have the expected shape.
+    pub fn get_typed<T: DeserializeOwned>(&self, name: &str) -> Option<serde_json::Result<T>> {
+        self.values
+            .get(name)
+            .map(|value| serde_json::from_value(value.clone()))
+    }
+
+    /// All flags, sorted by name.
+    #[must_use]
+    pub fn values(&self) -> &BTreeMap<String, Value> {
+        &self.values
+    }
+
+    /// Number of flags.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no flags are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl FromIterator<(String, Value)> for FeatureFlags {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl From<BTreeMap<String, Value>> for FeatureFlags {
+    fn from(values: BTreeMap<String, Value>) -> Self {
+        Self { values }
+    }
+}
+
+// ============================================================================
+// Providers
+// ============================================================================
+
+/// Errors a [`FeatureFlagProvider`] reports when flags cannot be resolved.
+///
+/// Runners do not fail the session on these errors; they emit a
+/// `runner.feature_flags` diagnostic and run the session with no flags.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum FeatureFlagError {
+    /// The flag source could not be reached or returned invalid data.
+    #[error("feature flag provider failed: {message}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::feature_flags::provider))
+    )]
+    Provider {
+        /// Description of the failure.
+        message: String,
+    },
+}
+
+/// Source of feature flag values.
+///
+/// Called once per session when a runner creates or resumes it, with the
+/// session id so providers can bucket sessions into experiment arms.
+#[async_trait]
+pub trait FeatureFlagProvider: Send + Sync {
+    /// Resolve the flags for `session_id`.
+    async fn resolve(&self, session_id: &str) -> Result<FeatureFlags, FeatureFlagError>;
+
+    /// Short description used in runtime metadata and config hashes.
+    fn describe(&self) -> String {
+        "custom".to_string()
+    }
+}
+
+/// A fixed set of flags shared by every session.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFlags {
+    flags: FeatureFlags,
+}
+
+impl StaticFlags {
+    /// An empty set of static flags.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a flag.
+    #[must_use]
+    pub fn with_flag(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.flags.values.insert(name.into(), value.into());
+        self
+    }
+}
+
+#[async_trait]
+impl FeatureFlagProvider for StaticFlags {
+    async fn resolve(&self, _session_id: &str) -> Result<FeatureFlags, FeatureFlagError> {
+        Ok(self.flags.clone())
+    }
+
+    fn describe(&self) -> String {
+        let values: Vec<String> = self
+            .flags
+            .values
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        format!("static({})", values.join(","))
+    }
+}
+
+/// Flags read from environment variables with a common prefix.
+///
+/// `WEAVEGRAPH_FLAG_NEW_PROMPT=true` becomes the flag `new_prompt` with the
+/// prefix `WEAVEGRAPH_FLAG_` (the default). Names are lowercased after the
+/// prefix is stripped; values that parse as JSON keep their type, anything
+/// else is a string. The environment is read on every resolve, so new
+/// sessions pick up changed variables without a restart.
+#[derive(Debug, Clone)]
+pub struct EnvFlags {
+    prefix: String,
+}
+
+impl EnvFlags {
+    /// Read variables starting with `WEAVEGRAPH_FLAG_`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_prefix("WEAVEGRAPH_FLAG_")
+    }
+
+    /// Read variables starting with `prefix`.
+    #[must_use]
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn read(&self) -> FeatureFlags {
+        std::env::vars()
+            .filter_map(|(key, raw)| {
+                let name = key.strip_prefix(&self.prefix)?.to_ascii_lowercase();
+                let value = serde_json::from_str(&raw).unwrap_or(Value::String(raw));
+                Some((name, value))
+            })
+            .collect()
+    }
+}
+
+impl Default for EnvFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FeatureFlagProvider for EnvFlags {
+    async fn resolve(&self, _session_id: &str) -> Result<FeatureFlags, FeatureFlagError> {
+        Ok(self.read())
+    }
+
+    fn describe(&self) -> String {
+        format!("env({})", self.prefix)
+    }
+}
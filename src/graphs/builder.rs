@@ -3,12 +3,22 @@
 //! This module contains the main GraphBuilder type and its fluent API
 //! for constructing workflow graphs with nodes, edges, and configuration.
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::Arc;
 
-use super::edges::{ConditionalEdge, EdgePredicate};
+use super::derived::{DerivedChannel, DerivedChannels};
+use super::edges::{ConditionalEdge, EdgeLabel, EdgePredicate, RoutingPredicate};
+use super::expr::{Expression, ExpressionError};
+use super::joins::{JoinPolicies, JoinPolicy};
+use super::quotas::NodeQuotas;
+use super::sla::SlaPolicy;
+use super::subgraph::{StateScope, SubgraphNode};
+use super::validation::{OutputValidationPolicy, OutputValidator, OutputValidators};
+use super::views::{SnapshotView, SnapshotViews};
+use crate::app::App;
+use crate::llm::{ReplaceSummarizedSpan, SummarizeHistoryNode};
 use crate::node::Node;
-use crate::reducers::{Reducer, ReducerRegistry};
+use crate::reducers::{BoundedErrors, ConflictPolicy, Reducer, ReducerRegistry};
 use crate::runtimes::{EventBusConfig, RuntimeConfig};
 use crate::types::{ChannelType, NodeKind};
 
@@ -20,6 +30,15 @@ type GraphParts = (
     Vec<ConditionalEdge>,
     RuntimeConfig,
     ReducerRegistry,
+    FxHashMap<String, Vec<NodeKind>>,
+    OutputValidators,
+    JoinPolicies,
+    SlaPolicy,
+    NodeQuotas,
+    SnapshotViews,
+    FxHashSet<NodeKind>,
+    FxHashMap<(NodeKind, NodeKind), EdgeLabel>,
+    DerivedChannels,
 );
 
 /// Builder for constructing workflow graphs with fluent API.
@@ -103,6 +122,25 @@ pub struct GraphBuilder {
     runtime_config: RuntimeConfig,
     /// Reducer registry for channel update operations.
     reducer_registry: ReducerRegistry,
+    /// Named entry points selectable when a session is created.
+    entry_points: FxHashMap<String, Vec<NodeKind>>,
+    /// Output validators per node kind and the policy for violations.
+    output_validators: OutputValidators,
+    /// Join policies and declared AND-join upstreams per node.
+    join_policies: JoinPolicies,
+    /// Expression edges that failed to parse, reported by `compile`.
+    expression_errors: Vec<(NodeKind, ExpressionError)>,
+    /// Limits checked by the runner after every superstep.
+    sla: SlaPolicy,
+    /// Per-session run limits enforced before every superstep.
+    node_quotas: NodeQuotas,
+    /// Message windows applied to the snapshots of individual nodes.
+    snapshot_views: SnapshotViews,
+    derived_channels: DerivedChannels,
+    /// Nodes that act on the outside world, stubbed out by simulations.
+    side_effecting: FxHashSet<NodeKind>,
+    /// Labels of unconditional edges, keyed by `(from, to)`.
+    edge_labels: FxHashMap<(NodeKind, NodeKind), EdgeLabel>,
 }
 
 impl Default for GraphBuilder {
@@ -134,6 +172,16 @@ impl GraphBuilder {
             conditional_edges: Vec::new(),
             runtime_config: RuntimeConfig::default(),
             reducer_registry: ReducerRegistry::default(),
+            entry_points: FxHashMap::default(),
+            output_validators: OutputValidators::default(),
+            join_policies: JoinPolicies::default(),
+            sla: SlaPolicy::default(),
+            node_quotas: NodeQuotas::default(),
+            snapshot_views: SnapshotViews::default(),
+            derived_channels: DerivedChannels::default(),
+            expression_errors: Vec::new(),
+            side_effecting: FxHashSet::default(),
+            edge_labels: FxHashMap::default(),
         }
     }
 
@@ -155,6 +203,160 @@ impl GraphBuilder {
         self
     }
 
+    /// Adds a conditional edge whose predicate receives a [`RoutingContext`](crate::graphs::RoutingContext).
+    ///
+    /// Use this when routing depends on more than state: the step number, the
+    /// nodes that ran, barrier errors, typed slots, or values configured with
+    /// [`RuntimeConfig::with_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::runtimes::RuntimeConfig;
+    /// use weavegraph::types::NodeKind;
+    /// # struct Work;
+    /// # #[async_trait::async_trait]
+    /// # impl weavegraph::node::Node for Work {
+    /// #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+    /// #         Ok(weavegraph::node::NodePartial::default())
+    /// #     }
+    /// # }
+    ///
+    /// let work = NodeKind::Custom("work".into());
+    /// let app = GraphBuilder::new()
+    ///     .add_node(work.clone(), Work)
+    ///     .add_edge(NodeKind::Start, work.clone())
+    ///     .add_conditional_edge_with_context(
+    ///         work.clone(),
+    ///         Arc::new(|ctx| {
+    ///             let limit = ctx.config_value("rounds").and_then(|v| v.as_u64()).unwrap_or(1);
+    ///             if ctx.step() >= limit {
+    ///                 vec![NodeKind::end_target()]
+    ///             } else {
+    ///                 vec![ctx.from().as_target()]
+    ///             }
+    ///         }),
+    ///     )
+    ///     .with_runtime_config(RuntimeConfig::default().with_value("rounds", 3))
+    ///     .compile()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn add_conditional_edge_with_context(
+        mut self,
+        from: NodeKind,
+        predicate: RoutingPredicate,
+    ) -> Self {
+        self.conditional_edges
+            .push(ConditionalEdge::with_context(from, predicate));
+        self
+    }
+
+    /// Adds a prebuilt [`ConditionalEdge`], e.g. one carrying a label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use weavegraph::graphs::{ConditionalEdge, GraphBuilder};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().add_conditional_edge_spec(
+    ///     ConditionalEdge::new(NodeKind::Start, Arc::new(|_| vec![NodeKind::end_target()]))
+    ///         .with_label("skip to end"),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn add_conditional_edge_spec(mut self, edge: ConditionalEdge) -> Self {
+        self.conditional_edges.push(edge);
+        self
+    }
+
+    /// Adds a conditional edge that routes to `then` when the
+    /// [`Expression`] `expr` is truthy and to `otherwise` when it is not.
+    ///
+    /// The edge is labelled with the expression. A malformed expression makes
+    /// [`compile`](Self::compile) fail with
+    /// [`GraphCompileError::InvalidExpression`](super::GraphCompileError::InvalidExpression).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().add_expression_edge(
+    ///     NodeKind::Custom("grade".into()),
+    ///     "extra.score > 0.8 && messages.len < 20",
+    ///     NodeKind::Custom("publish".into()),
+    ///     NodeKind::Custom("revise".into()),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn add_expression_edge(
+        self,
+        from: NodeKind,
+        expr: &str,
+        then: NodeKind,
+        otherwise: NodeKind,
+    ) -> Self {
+        self.push_expression_edge(
+            from,
+            expr,
+            vec![then.as_target()],
+            vec![otherwise.as_target()],
+        )
+    }
+
+    /// Adds an edge from `from` to `to` that is only followed while the
+    /// [`Expression`] `guard` is truthy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().add_guarded_edge(
+    ///     NodeKind::Custom("draft".into()),
+    ///     NodeKind::Custom("review".into()),
+    ///     "extra.needs_review == true",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn add_guarded_edge(self, from: NodeKind, to: NodeKind, guard: &str) -> Self {
+        self.push_expression_edge(from, guard, vec![to.as_target()], Vec::new())
+    }
+
+    fn push_expression_edge(
+        mut self,
+        from: NodeKind,
+        expr: &str,
+        then: Vec<String>,
+        otherwise: Vec<String>,
+    ) -> Self {
+        let expression = match Expression::parse(expr) {
+            Ok(expression) => expression,
+            Err(err) => {
+                self.expression_errors.push((from, err));
+                return self;
+            }
+        };
+        let label = expression.source().to_string();
+        let predicate: RoutingPredicate = Arc::new(move |ctx| {
+            if expression.matches(ctx) {
+                then.clone()
+            } else {
+                otherwise.clone()
+            }
+        });
+        self.conditional_edges
+            .push(ConditionalEdge::with_context(from, predicate).with_label(label));
+        self
+    }
+
     /// Adds a node to the graph.
     ///
     /// NOTE: `NodeKind::Start` and `NodeKind::End` are virtual structural endpoints.
@@ -172,7 +374,141 @@ impl GraphBuilder {
     /// - `id`: Unique identifier for this node in the graph
     /// - `node`: Implementation of the [`Node`] trait
     #[must_use]
-    pub fn add_node(mut self, id: NodeKind, node: impl Node + 'static) -> Self {
+    pub fn add_node(self, id: NodeKind, node: impl Node + 'static) -> Self {
+        self.add_shared_node(id, Arc::new(node))
+    }
+
+    /// Adds a node that acts on the outside world: sends email, charges a
+    /// card, writes to another system.
+    ///
+    /// The node runs normally under [`App::invoke`](crate::app::App::invoke)
+    /// and [`AppRunner`](crate::runtimes::AppRunner).
+    /// [`SimulationRunner`](crate::runtimes::SimulationRunner) replaces it with
+    /// a stub and reports what it would have done.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// # struct SendEmail;
+    /// # #[async_trait::async_trait]
+    /// # impl weavegraph::node::Node for SendEmail {
+    /// #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+    /// #         Ok(weavegraph::node::NodePartial::default())
+    /// #     }
+    /// # }
+    /// let notify = NodeKind::Custom("notify".into());
+    /// let app = GraphBuilder::new()
+    ///     .add_side_effect_node(notify.clone(), SendEmail)
+    ///     .add_edge(NodeKind::Start, notify.clone())
+    ///     .add_edge(notify.clone(), NodeKind::End)
+    ///     .compile()
+    ///     .unwrap();
+    /// assert!(app.is_side_effecting(&notify));
+    /// ```
+    #[must_use]
+    pub fn add_side_effect_node(mut self, id: NodeKind, node: impl Node + 'static) -> Self {
+        if !matches!(id, NodeKind::Start | NodeKind::End) {
+            self.side_effecting.insert(id.clone());
+        }
+        self.add_node(id, node)
+    }
+
+    /// Adds a node that receives only the messages selected by `view`.
+    ///
+    /// Equivalent to [`add_node`](Self::add_node) followed by
+    /// [`with_snapshot_view`](Self::with_snapshot_view).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, SnapshotView};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// # struct Summarize;
+    /// # #[async_trait::async_trait]
+    /// # impl weavegraph::node::Node for Summarize {
+    /// #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+    /// #         Ok(weavegraph::node::NodePartial::default())
+    /// #     }
+    /// # }
+    /// let builder = GraphBuilder::new().add_node_with_view(
+    ///     NodeKind::Custom("summarize".into()),
+    ///     Summarize,
+    ///     SnapshotView::new().last_messages(50).max_tokens(4_000),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn add_node_with_view(
+        self,
+        id: NodeKind,
+        node: impl Node + 'static,
+        view: SnapshotView,
+    ) -> Self {
+        self.add_node(id.clone(), node).with_snapshot_view(id, view)
+    }
+
+    /// Adds a compiled child graph as a node with a scoped view of the state.
+    ///
+    /// The child reads only what `scope` whitelists, and its `extra` writes
+    /// are merged into the parent under `extra[scope.namespace()]`. See
+    /// [`SubgraphNode`] for how the child is run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, StateScope};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// # struct Search;
+    /// # #[async_trait::async_trait]
+    /// # impl weavegraph::node::Node for Search {
+    /// #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+    /// #         Ok(weavegraph::node::NodePartial::default())
+    /// #     }
+    /// # }
+    /// let search = NodeKind::Custom("search".into());
+    /// let research = GraphBuilder::new()
+    ///     .add_node(search.clone(), Search)
+    ///     .add_edge(NodeKind::Start, search.clone())
+    ///     .add_edge(search, NodeKind::End)
+    ///     .compile()
+    ///     .unwrap();
+    ///
+    /// let researcher = NodeKind::Custom("researcher".into());
+    /// let app = GraphBuilder::new()
+    ///     .add_subgraph(
+    ///         researcher.clone(),
+    ///         research,
+    ///         StateScope::new("research").read_key("question"),
+    ///     )
+    ///     .add_edge(NodeKind::Start, researcher.clone())
+    ///     .add_edge(researcher, NodeKind::End)
+    ///     .compile()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn add_subgraph(self, id: NodeKind, app: App, scope: StateScope) -> Self {
+        self.add_node(id, SubgraphNode::new(app, scope))
+    }
+
+    /// Adds a [`SummarizeHistoryNode`] and installs the reducer that applies
+    /// its summaries.
+    ///
+    /// The [`ReplaceSummarizedSpan`] reducer is registered on
+    /// [`ChannelType::Extra`] after the reducers already there, so call
+    /// [`with_reducer_registry`](Self::with_reducer_registry) first if you
+    /// replace the registry. See [`crate::llm::summarize`] for an example.
+    #[must_use]
+    pub fn add_summarizer(self, id: NodeKind, node: SummarizeHistoryNode) -> Self {
+        self.add_node(id, node)
+            .with_reducer(ChannelType::Extra, Arc::new(ReplaceSummarizedSpan))
+    }
+
+    /// Registers an already shared node implementation (used by templates).
+    pub(crate) fn add_shared_node(mut self, id: NodeKind, node: Arc<dyn Node>) -> Self {
         // Ignore attempts to register virtual Start/End node kinds; emit a warning.
         match id {
             NodeKind::Start | NodeKind::End => {
@@ -183,7 +519,7 @@ impl GraphBuilder {
                 // Do not insert into registry.
             }
             _ => {
-                self.nodes.insert(id, Arc::new(node));
+                self.nodes.insert(id, node);
             }
         }
         self
@@ -207,6 +543,94 @@ impl GraphBuilder {
         self
     }
 
+    /// Adds an unconditional edge carrying a label and optional metadata.
+    ///
+    /// Routes exactly like [`add_edge`](Self::add_edge). The label explains
+    /// the edge to observability tooling: it appears in
+    /// [`App::describe`](crate::app::App::describe), [`to_mermaid`](Self::to_mermaid),
+    /// the DOT export, [`ROUTING_SCOPE`](crate::event_bus::ROUTING_SCOPE)
+    /// events, and [`StepReport::frontier_edges`](crate::runtimes::StepReport::frontier_edges).
+    /// Labelling the same pair twice keeps the last label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use weavegraph::graphs::{EdgeLabel, GraphBuilder};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let review = NodeKind::Custom("review".into());
+    /// let publish = NodeKind::Custom("publish".into());
+    /// let builder = GraphBuilder::new()
+    ///     .add_edge_labeled(review.clone(), publish.clone(), "on_success")
+    ///     .add_edge_labeled(
+    ///         publish,
+    ///         NodeKind::End,
+    ///         EdgeLabel::new("done").with_metadata("audited", json!(true)),
+    ///     );
+    /// assert!(builder.to_mermaid().contains("review -->|on_success| publish"));
+    /// ```
+    #[must_use]
+    pub fn add_edge_labeled(
+        mut self,
+        from: NodeKind,
+        to: NodeKind,
+        label: impl Into<EdgeLabel>,
+    ) -> Self {
+        self.edge_labels
+            .insert((from.clone(), to.clone()), label.into());
+        self.add_edge(from, to)
+    }
+
+    /// Declares a named entry point that starts execution at `nodes`.
+    ///
+    /// Named entry points let one compiled [`App`](crate::app::App) serve several
+    /// flows. Select one with
+    /// [`AppRunner::create_session_with_entry`](crate::runtimes::AppRunner::create_session_with_entry)
+    /// or [`App::invoke_entry`](crate::app::App::invoke_entry); sessions created
+    /// without an entry keep using the edges from [`NodeKind::Start`]. A graph
+    /// may define only named entry points and no Start edges.
+    ///
+    /// Nodes reachable from any entry point count as reachable during
+    /// validation. Declaring the same name twice replaces the earlier nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// # struct MyNode;
+    /// # #[async_trait::async_trait]
+    /// # impl weavegraph::node::Node for MyNode {
+    /// #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+    /// #         Ok(weavegraph::node::NodePartial::default())
+    /// #     }
+    /// # }
+    /// let ingest = NodeKind::Custom("ingest".into());
+    /// let query = NodeKind::Custom("query".into());
+    /// let app = GraphBuilder::new()
+    ///     .add_node(ingest.clone(), MyNode)
+    ///     .add_node(query.clone(), MyNode)
+    ///     .add_entry("ingest", [ingest.clone()])
+    ///     .add_entry("query", [query.clone()])
+    ///     .add_edge(ingest, NodeKind::End)
+    ///     .add_edge(query, NodeKind::End)
+    ///     .compile()?;
+    /// assert_eq!(app.entry_points().len(), 2);
+    /// # Ok::<_, weavegraph::graphs::GraphCompileError>(())
+    /// ```
+    #[must_use]
+    pub fn add_entry(
+        mut self,
+        name: impl Into<String>,
+        nodes: impl IntoIterator<Item = NodeKind>,
+    ) -> Self {
+        self.entry_points
+            .insert(name.into(), nodes.into_iter().collect());
+        self
+    }
+
     /// Configures runtime settings for the compiled application.
     ///
     /// Runtime configuration controls execution behavior such as concurrency
@@ -313,6 +737,245 @@ impl GraphBuilder {
         self
     }
 
+    /// Sets how concurrent writes to one `extra` key are resolved.
+    ///
+    /// When several nodes in one superstep write different values to `key`,
+    /// `policy` decides the result. Conflicts on other keys keep the default
+    /// last-writer-wins behaviour unless
+    /// [`with_default_conflict_policy`](Self::with_default_conflict_policy) is set.
+    /// Call this after [`with_reducer_registry`](Self::with_reducer_registry),
+    /// which replaces any policies configured earlier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::reducers::ConflictPolicy;
+    ///
+    /// let builder = GraphBuilder::new()
+    ///     .with_conflict_policy("findings", ConflictPolicy::MergeArray)
+    ///     .with_conflict_policy("answer", ConflictPolicy::Error);
+    /// ```
+    #[must_use]
+    pub fn with_conflict_policy(mut self, key: impl Into<String>, policy: ConflictPolicy) -> Self {
+        self.reducer_registry.set_conflict_policy(key, policy);
+        self
+    }
+
+    /// Sets the conflict policy for `extra` keys without a specific policy.
+    #[must_use]
+    pub fn with_default_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.reducer_registry.set_default_conflict_policy(policy);
+        self
+    }
+
+    /// Replaces the errors-channel reducers with `retention`, bounding how
+    /// many error events of each severity a session keeps.
+    ///
+    /// See [`BoundedErrors`] for an example.
+    #[must_use]
+    pub fn with_bounded_errors(mut self, retention: BoundedErrors) -> Self {
+        self.reducer_registry
+            .replace(ChannelType::Error, Arc::new(retention));
+        self
+    }
+
+    /// Registers a validator for the outputs of `node`.
+    ///
+    /// The validator sees every [`NodePartial`](crate::node::NodePartial) the
+    /// node produces before the barrier applies it. Several validators may be
+    /// registered for one node; their violations are combined and handled by
+    /// the [`OutputValidationPolicy`] set with
+    /// [`with_output_validation_policy`](Self::with_output_validation_policy).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, MaxMessages, RequiredExtraKeys};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let summarize = NodeKind::Custom("summarize".into());
+    /// let builder = GraphBuilder::new()
+    ///     .with_output_validator(summarize.clone(), MaxMessages::new(1))
+    ///     .with_output_validator(summarize, RequiredExtraKeys::new(["summary"]));
+    /// ```
+    #[must_use]
+    pub fn with_output_validator(
+        mut self,
+        node: NodeKind,
+        validator: impl OutputValidator + 'static,
+    ) -> Self {
+        self.output_validators.register(node, Arc::new(validator));
+        self
+    }
+
+    /// Sets what happens to node outputs that fail validation.
+    ///
+    /// Defaults to [`OutputValidationPolicy::Fail`].
+    #[must_use]
+    pub fn with_output_validation_policy(mut self, policy: OutputValidationPolicy) -> Self {
+        self.output_validators.set_policy(policy);
+        self
+    }
+
+    /// Sets how `node` handles several predecessors routing to it.
+    ///
+    /// Defaults to [`JoinPolicy::Dedupe`]. See [`JoinPolicy`] for the
+    /// alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, JoinPolicy};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let review = NodeKind::Custom("review".into());
+    /// let builder = GraphBuilder::new()
+    ///     .add_edge(NodeKind::Custom("draft_a".into()), review.clone())
+    ///     .add_edge(NodeKind::Custom("draft_b".into()), review.clone())
+    ///     .with_join_policy(review, JoinPolicy::PerIncomingEdge);
+    /// ```
+    #[must_use]
+    pub fn with_join_policy(mut self, node: NodeKind, policy: JoinPolicy) -> Self {
+        self.join_policies.set_policy(node, policy);
+        self
+    }
+
+    /// Declares `node` as an AND-join over `upstreams`.
+    ///
+    /// The node stays out of the frontier until every listed upstream node has
+    /// routed to it, through any kind of edge, in some superstep since the
+    /// node last ran. Arrivals are tracked in the
+    /// [`SchedulerState`](crate::schedulers::SchedulerState) and persisted with
+    /// checkpoints, so a join survives resume. This is
+    /// [`JoinPolicy::WaitForAll`] with an explicit branch list instead of the
+    /// node's unconditional predecessors.
+    ///
+    /// Compilation fails with [`GraphCompileError::EmptyJoin`](super::GraphCompileError::EmptyJoin)
+    /// if `upstreams` is empty and with
+    /// [`GraphCompileError::InvalidJoinNode`](super::GraphCompileError::InvalidJoinNode)
+    /// if the join or an upstream is not a registered node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().add_join(
+    ///     NodeKind::Custom("merge".into()),
+    ///     [NodeKind::Custom("search".into()), NodeKind::Custom("fetch".into())],
+    /// );
+    /// ```
+    #[must_use]
+    pub fn add_join(
+        mut self,
+        node: NodeKind,
+        upstreams: impl IntoIterator<Item = NodeKind>,
+    ) -> Self {
+        self.join_policies
+            .declare_join(node, upstreams.into_iter().collect());
+        self
+    }
+
+    /// Declares SLAs the runner checks after every superstep barrier.
+    ///
+    /// Each breach is published as an event with scope
+    /// [`SLA_BREACH_SCOPE`](crate::event_bus::SLA_BREACH_SCOPE); see
+    /// [`SlaBreach`](super::SlaBreach). Calling this again replaces the policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use weavegraph::graphs::{GraphBuilder, SlaPolicy};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().with_sla(
+    ///     SlaPolicy::new()
+    ///         .max_node_duration(NodeKind::Custom("search".into()), Duration::from_secs(2))
+    ///         .max_errors(5),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_sla(mut self, sla: SlaPolicy) -> Self {
+        self.sla = sla;
+        self
+    }
+
+    /// Limits the messages `node` receives in its snapshot to `view`.
+    ///
+    /// The scheduler clones only the selected messages, which keeps per-node
+    /// snapshots small in sessions with long histories. The node's writes are
+    /// unaffected. Calling this again for the same node replaces its view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, SnapshotView};
+    /// use weavegraph::message::Role;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().with_snapshot_view(
+    ///     NodeKind::Custom("router".into()),
+    ///     SnapshotView::new().roles([Role::User]).last_messages(1),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_snapshot_view(mut self, node: NodeKind, view: SnapshotView) -> Self {
+        self.snapshot_views = self.snapshot_views.with_view(node, view);
+        self
+    }
+
+    /// Registers a [`DerivedChannel`] computed on demand from the state.
+    ///
+    /// Nodes read it with [`NodeContext::derived`](crate::node::NodeContext::derived)
+    /// and conditional edges with
+    /// [`RoutingContext::derived`](crate::graphs::RoutingContext::derived).
+    /// Registering a channel with the same name again replaces it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{DerivedChannel, GraphBuilder};
+    ///
+    /// let builder = GraphBuilder::new()
+    ///     .with_derived_channel(DerivedChannel::message_count())
+    ///     .with_derived_channel(DerivedChannel::last_user_message());
+    /// ```
+    #[must_use]
+    pub fn with_derived_channel(mut self, channel: DerivedChannel) -> Self {
+        self.derived_channels = self.derived_channels.with_channel(channel);
+        self
+    }
+
+    /// Caps how many times nodes may run per session.
+    ///
+    /// Before each superstep the runner applies the node's
+    /// [`QuotaFallback`](super::QuotaFallback) to frontier entries whose quota
+    /// is used up. Compilation fails with
+    /// [`GraphCompileError::InvalidQuotaNode`](super::GraphCompileError::InvalidQuotaNode)
+    /// if a quota or its route target is not a registered node (`End` is a
+    /// valid target). Calling this again replaces the quotas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{GraphBuilder, NodeQuotas, QuotaFallback};
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let builder = GraphBuilder::new().with_node_quotas(NodeQuotas::new().max_runs_or(
+    ///     NodeKind::Custom("critic".into()),
+    ///     3,
+    ///     QuotaFallback::RouteTo(NodeKind::Custom("publish".into())),
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn with_node_quotas(mut self, quotas: NodeQuotas) -> Self {
+        self.node_quotas = quotas;
+        self
+    }
+
     // =========================================================================
     // Iterators (petgraph-style API)
     // =========================================================================
@@ -524,7 +1187,33 @@ impl GraphBuilder {
     #[cfg_attr(docsrs, doc(cfg(feature = "petgraph-compat")))]
     #[must_use]
     pub fn to_dot(&self) -> String {
-        super::petgraph_compat::to_dot(&self.edges)
+        super::petgraph_compat::to_dot(&self.edges, &self.edge_labels)
+    }
+
+    /// Renders the unconditional edges as a Mermaid flowchart.
+    ///
+    /// Edges added with [`add_edge_labeled`](Self::add_edge_labeled) carry
+    /// their label. Conditional edges pick targets at runtime and are not
+    /// drawn. Node names are mapped to Mermaid ids by replacing characters
+    /// other than ASCII letters, digits, and `_` with `_`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let mermaid = GraphBuilder::new()
+    ///     .add_edge(NodeKind::Start, NodeKind::Custom("draft".into()))
+    ///     .add_edge_labeled(NodeKind::Custom("draft".into()), NodeKind::End, "approved")
+    ///     .to_mermaid();
+    /// assert!(mermaid.starts_with("flowchart TD"));
+    /// assert!(mermaid.contains("Start --> draft"));
+    /// assert!(mermaid.contains("draft -->|approved| End"));
+    /// ```
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        super::iteration::to_mermaid(&self.edges, &self.edge_labels)
     }
 
     /// Checks if the graph contains cycles using petgraph's algorithm.
@@ -546,6 +1235,33 @@ impl GraphBuilder {
     // Internal Helpers
     // =========================================================================
 
+    /// Rebuilds a builder from a compiled app's definition (used by [`DynamicGraph`](super::DynamicGraph)).
+    pub(crate) fn from_app(app: &App) -> Self {
+        Self {
+            nodes: app.nodes().clone(),
+            edges: app.edges().clone(),
+            conditional_edges: app.conditional_edges().clone(),
+            runtime_config: app.runtime_config().clone(),
+            reducer_registry: app.reducer_registry().clone(),
+            entry_points: app.entry_points().clone(),
+            output_validators: app.output_validators().clone(),
+            join_policies: app.join_policies().clone(),
+            expression_errors: Vec::new(),
+            sla: app.sla().clone(),
+            node_quotas: app.node_quotas().clone(),
+            snapshot_views: app.snapshot_views().clone(),
+            derived_channels: app.derived_channels().clone(),
+            side_effecting: app.side_effecting_nodes().clone(),
+            edge_labels: app.edge_labels().clone(),
+        }
+    }
+
+    /// Appends an already constructed conditional edge.
+    pub(crate) fn push_conditional_edge(mut self, edge: ConditionalEdge) -> Self {
+        self.conditional_edges.push(edge);
+        self
+    }
+
     /// Extracts the components for compilation (internal use only).
     pub(super) fn into_parts(self) -> GraphParts {
         (
@@ -554,6 +1270,15 @@ impl GraphBuilder {
             self.conditional_edges,
             self.runtime_config,
             self.reducer_registry,
+            self.entry_points,
+            self.output_validators,
+            self.join_policies,
+            self.sla,
+            self.node_quotas,
+            self.snapshot_views,
+            self.side_effecting,
+            self.edge_labels,
+            self.derived_channels,
         )
     }
 
@@ -567,4 +1292,16 @@ impl GraphBuilder {
     pub(super) fn conditional_edges_ref(&self) -> &Vec<ConditionalEdge> {
         &self.conditional_edges
     }
+    pub(super) fn join_policies_ref(&self) -> &JoinPolicies {
+        &self.join_policies
+    }
+    pub(super) fn node_quotas_ref(&self) -> &NodeQuotas {
+        &self.node_quotas
+    }
+    pub(super) fn expression_errors_ref(&self) -> &[(NodeKind, ExpressionError)] {
+        &self.expression_errors
+    }
+    pub(super) fn entry_points_ref(&self) -> &FxHashMap<String, Vec<NodeKind>> {
+        &self.entry_points
+    }
 }
@@ -3,6 +3,7 @@
 //! This module contains the logic for compiling a GraphBuilder into an
 //! executable App, including structural validation and actionable errors.
 
+use super::QuotaFallback;
 use crate::app::App;
 use crate::types::NodeKind;
 use rustc_hash::FxHashMap;
@@ -10,6 +11,7 @@ use rustc_hash::FxHashMap;
 /// Errors that can occur when compiling a graph.
 #[derive(Debug, thiserror::Error)]
 #[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
 pub enum GraphCompileError {
     /// No entry edge was defined from the virtual Start node.
     #[error("missing entry: no edge or conditional edge originates from Start")]
@@ -44,6 +46,46 @@ pub enum GraphCompileError {
         nodes: Vec<NodeKind>,
     },
 
+    /// A named entry point lists no nodes.
+    #[error("entry point '{0}' has no nodes")]
+    EmptyEntryPoint(String),
+
+    /// A named entry point references Start, End, or an unregistered node.
+    #[error("entry point '{entry}' references invalid node: {node}")]
+    InvalidEntryNode {
+        /// Name of the entry point.
+        entry: String,
+        /// The node that cannot start execution.
+        node: NodeKind,
+    },
+
+    /// An AND-join declared with `add_join` lists no upstream nodes.
+    #[error("join '{0}' declares no upstream nodes")]
+    EmptyJoin(NodeKind),
+
+    /// An AND-join or one of its declared upstreams is not a registered node.
+    #[error("join '{join}' references invalid node: {node}")]
+    InvalidJoinNode {
+        /// The join node.
+        join: NodeKind,
+        /// The node that cannot take part in the join.
+        node: NodeKind,
+    },
+
+    /// A run quota or its route target is not a registered node.
+    #[error("run quota references invalid node: {0}")]
+    InvalidQuotaNode(NodeKind),
+
+    /// An expression edge or guard failed to parse.
+    #[error("invalid expression on edge from {from}: {source}")]
+    InvalidExpression {
+        /// Source node of the edge.
+        from: NodeKind,
+        /// The parse error.
+        #[source]
+        source: super::ExpressionError,
+    },
+
     /// A duplicate edge was detected.
     #[error("duplicate edge detected: {} -> {}", .from, .to)]
     DuplicateEdge {
@@ -116,14 +158,38 @@ impl super::builder::GraphBuilder {
         // Validate without consuming self
         self.validate()?;
 
-        let (nodes, edges, conditional_edges, runtime_config, reducer_registry) = self.into_parts();
+        let (
+            nodes,
+            edges,
+            conditional_edges,
+            runtime_config,
+            reducer_registry,
+            entry_points,
+            output_validators,
+            join_policies,
+            sla,
+            node_quotas,
+            snapshot_views,
+            side_effecting,
+            edge_labels,
+            derived_channels,
+        ) = self.into_parts();
         Ok(App::from_parts(
             nodes,
             edges,
             conditional_edges,
             runtime_config,
             reducer_registry,
-        ))
+        )
+        .with_entry_points(entry_points)
+        .with_output_validators(output_validators)
+        .with_join_policies(join_policies)
+        .with_sla(sla)
+        .with_node_quotas(node_quotas)
+        .with_snapshot_views(snapshot_views)
+        .with_side_effecting_nodes(side_effecting)
+        .with_edge_labels(edge_labels)
+        .with_derived_channels(derived_channels))
     }
 
     /// Detects cycles in the graph using DFS with color marking.
@@ -201,19 +267,24 @@ impl super::builder::GraphBuilder {
         None
     }
 
-    /// Detects unreachable nodes (nodes with no path from Start).
+    /// Detects unreachable nodes (nodes with no path from Start or a named entry point).
     ///
     /// Only checks unconditional edges. Returns registered Custom nodes that
-    /// cannot be reached from Start via unconditional edges.
+    /// cannot be reached from Start or any entry point via unconditional edges.
     fn detect_unreachable_nodes(&self) -> Vec<NodeKind> {
         use std::collections::VecDeque;
 
         let mut reachable: FxHashMap<NodeKind, bool> = FxHashMap::default();
         let mut queue: VecDeque<NodeKind> = VecDeque::new();
 
-        // Start BFS from Start node
+        // Start BFS from Start node and every named entry point
         queue.push_back(NodeKind::Start);
         reachable.insert(NodeKind::Start, true);
+        for node in self.entry_points_ref().values().flatten() {
+            if reachable.insert(node.clone(), true).is_none() {
+                queue.push_back(node.clone());
+            }
+        }
 
         while let Some(node) = queue.pop_front() {
             if let Some(neighbors) = self.edges_ref().get(&node) {
@@ -304,17 +375,78 @@ impl super::builder::GraphBuilder {
         None
     }
 
+    /// Every custom node referenced by the builder but never registered with
+    /// [`add_node`](Self::add_node), sorted by name.
+    ///
+    /// Covers both ends of unconditional edges, sources of conditional edges,
+    /// entry points, joins, and run quotas. Unlike [`compile`](Self::compile),
+    /// which stops at the first problem, this reports every misspelled or
+    /// forgotten node at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::GraphBuilder;
+    /// use weavegraph::node_kind;
+    ///
+    /// let builder = GraphBuilder::new()
+    ///     .add_edge(node_kind!(Start), node_kind!("fetch"))
+    ///     .add_edge(node_kind!("fetch"), node_kind!("sumarize"))
+    ///     .add_edge(node_kind!("sumarize"), node_kind!(End));
+    /// assert_eq!(builder.unregistered_nodes(), vec![node_kind!("fetch"), node_kind!("sumarize")]);
+    /// ```
+    #[must_use]
+    pub fn unregistered_nodes(&self) -> Vec<NodeKind> {
+        let referenced = self
+            .edges_ref()
+            .iter()
+            .flat_map(|(from, tos)| std::iter::once(from).chain(tos))
+            .chain(self.conditional_edges_ref().iter().map(|edge| edge.from()))
+            .chain(self.entry_points_ref().values().flatten())
+            .chain(
+                self.join_policies_ref()
+                    .declared()
+                    .iter()
+                    .flat_map(|(join, upstreams)| std::iter::once(join).chain(upstreams)),
+            )
+            .chain(self.node_quotas_ref().nodes().flat_map(|(node, fallback)| {
+                let target = match fallback {
+                    QuotaFallback::RouteTo(target) => Some(target),
+                    _ => None,
+                };
+                std::iter::once(node).chain(target)
+            }));
+        let mut missing: Vec<NodeKind> = referenced
+            .filter(|node| node.is_custom() && !self.nodes_ref().contains_key(*node))
+            .cloned()
+            .collect();
+        missing.sort_by_key(|node| node.to_string());
+        missing.dedup();
+        missing
+    }
+
     /// Validates the graph for common structural issues.
     ///
     /// Validation rules:
     /// - There must be at least one entry edge from Start (unconditional or conditional)
+    ///   or a named entry point
+    /// - Named entry points must list at least one registered Custom node
     /// - No edge may originate from End
-    /// - Any Custom node referenced by an edge (as from/to) must be registered
+    /// - Any Custom node referenced by an edge (as from/to, including the source
+    ///   of a conditional edge) must be registered
     /// - The graph must not contain cycles (checked on unconditional edges only)
     /// - All registered nodes must be reachable from Start (unconditional edges only)
     /// - All registered nodes must have a path to End (unconditional edges only)
     /// - No duplicate edges are allowed
     pub fn validate(&self) -> Result<(), GraphCompileError> {
+        // Rule 0: Expression edges parsed
+        if let Some((from, err)) = self.expression_errors_ref().first() {
+            return Err(GraphCompileError::InvalidExpression {
+                from: from.clone(),
+                source: err.clone(),
+            });
+        }
+
         // Rule 1: Entry edge from Start exists (either unconditional or conditional)
         let has_start_edge = self
             .edges_ref()
@@ -326,10 +458,74 @@ impl super::builder::GraphBuilder {
                 .iter()
                 .any(|ce| ce.from() == &NodeKind::Start);
 
-        if !has_start_edge {
+        if !has_start_edge && self.entry_points_ref().is_empty() {
             return Err(GraphCompileError::MissingEntry);
         }
 
+        // Rule 1b: Named entry points must start at registered custom nodes
+        let mut entry_names: Vec<&String> = self.entry_points_ref().keys().collect();
+        entry_names.sort();
+        for name in entry_names {
+            let nodes = &self.entry_points_ref()[name];
+            if nodes.is_empty() {
+                return Err(GraphCompileError::EmptyEntryPoint(name.clone()));
+            }
+            if let Some(node) = nodes
+                .iter()
+                .find(|node| !self.nodes_ref().contains_key(*node))
+            {
+                return Err(GraphCompileError::InvalidEntryNode {
+                    entry: name.clone(),
+                    node: node.clone(),
+                });
+            }
+        }
+
+        // Rule 1c: Declared joins wait on registered nodes only
+        let mut joins: Vec<(&NodeKind, &Vec<NodeKind>)> =
+            self.join_policies_ref().declared().iter().collect();
+        joins.sort_by_key(|(join, _)| join.encode());
+        for (join, upstreams) in joins {
+            if upstreams.is_empty() {
+                return Err(GraphCompileError::EmptyJoin(join.clone()));
+            }
+            if let Some(node) = std::iter::once(join)
+                .chain(upstreams)
+                .find(|node| !self.nodes_ref().contains_key(*node))
+            {
+                return Err(GraphCompileError::InvalidJoinNode {
+                    join: join.clone(),
+                    node: node.clone(),
+                });
+            }
+        }
+
+        // Rule 1d: Quotas apply to registered nodes and route to registered nodes or End
+        let mut quotas: Vec<(&NodeKind, &QuotaFallback)> = self.node_quotas_ref().nodes().collect();
+        quotas.sort_by_key(|(node, _)| node.encode());
+        for (node, fallback) in quotas {
+            if !self.nodes_ref().contains_key(node) {
+                return Err(GraphCompileError::InvalidQuotaNode(node.clone()));
+            }
+            if let QuotaFallback::RouteTo(target) = fallback
+                && *target != NodeKind::End
+                && !self.nodes_ref().contains_key(target)
+            {
+                return Err(GraphCompileError::InvalidQuotaNode(target.clone()));
+            }
+        }
+
+        // Rule 1e: Conditional edges leave registered nodes (targets are runtime-determined)
+        for edge in self.conditional_edges_ref() {
+            match edge.from() {
+                NodeKind::End => return Err(GraphCompileError::EdgeFromEnd),
+                from @ NodeKind::Custom(_) if !self.nodes_ref().contains_key(from) => {
+                    return Err(GraphCompileError::UnknownNode(from.clone()));
+                }
+                _ => {}
+            }
+        }
+
         // Rule 2: Detect cycles in unconditional edges
         if let Some(cycle) = self.detect_cycle() {
             return Err(GraphCompileError::CycleDetected { cycle });
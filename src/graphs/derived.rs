@@ -0,0 +1,321 @@
+//! Derived channels: values computed from the state on demand.
+//!
+//! A [`DerivedChannel`] names a value computed from one or more base channels,
+//! such as the message count or the latest user message. Nodes read it with
+//! [`NodeContext::derived`](crate::node::NodeContext::derived) and conditional
+//! edges with [`RoutingContext::derived`](crate::graphs::RoutingContext::derived),
+//! instead of re-scanning the raw messages every superstep.
+//!
+//! Values are computed the first time they are read and cached per session,
+//! keyed by the versions of the channels they read. The cache is reused until
+//! one of those channels changes. Derived values are always computed from the
+//! full state, including for nodes that receive a
+//! [`SnapshotView`](crate::graphs::SnapshotView).
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::message::Role;
+use crate::state::{StateSlotError, StateSnapshot};
+use crate::types::ChannelType;
+
+use super::estimate_tokens;
+
+/// Function computing a derived value from a snapshot.
+pub type DeriveFn = Arc<dyn Fn(&StateSnapshot) -> Value + Send + Sync + 'static>;
+
+// ============================================================================
+// Channel
+// ============================================================================
+
+/// A named value computed from base channels.
+///
+/// `inputs` lists the channels the function reads; the cached value is
+/// recomputed only when one of their versions changes. A function reading a
+/// channel missing from `inputs` can return stale values.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use weavegraph::graphs::DerivedChannel;
+/// use weavegraph::types::ChannelType;
+///
+/// let pending = DerivedChannel::new("pending_tasks", [ChannelType::Extra], |snapshot| {
+///     json!(snapshot.extra.get("tasks").and_then(|t| t.as_array()).map_or(0, Vec::len))
+/// });
+/// assert_eq!(pending.name(), "pending_tasks");
+/// assert_eq!(pending.inputs(), &[ChannelType::Extra]);
+/// ```
+#[derive(Clone)]
+pub struct DerivedChannel {
+    name: String,
+    inputs: Vec<ChannelType>,
+    compute: DeriveFn,
+}
+
+impl DerivedChannel {
+    /// Derive `name` from the `inputs` channels with `compute`.
+    pub fn new<F>(
+        name: impl Into<String>,
+        inputs: impl IntoIterator<Item = ChannelType>,
+        compute: F,
+    ) -> Self
+    where
+        F: Fn(&StateSnapshot) -> Value + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            inputs: inputs.into_iter().collect(),
+            compute: Arc::new(compute),
+        }
+    }
+
+    /// `message_count`: number of messages in the conversation.
+    #[must_use]
+    pub fn message_count() -> Self {
+        Self::new("message_count", [ChannelType::Message], |snapshot| {
+            Value::from(snapshot.messages.len())
+        })
+    }
+
+    /// `last_user_message`: content of the newest user message, or `null`.
+    #[must_use]
+    pub fn last_user_message() -> Self {
+        Self::new("last_user_message", [ChannelType::Message], |snapshot| {
+            snapshot
+                .messages
+                .iter()
+                .rev()
+                .find(|message| message.role == Role::User)
+                .map_or(Value::Null, |message| Value::from(message.content.clone()))
+        })
+    }
+
+    /// `estimated_tokens`: sum of [`estimate_tokens`] over all messages.
+    #[must_use]
+    pub fn estimated_tokens() -> Self {
+        Self::new("estimated_tokens", [ChannelType::Message], |snapshot| {
+            Value::from(snapshot.messages.iter().map(estimate_tokens).sum::<usize>())
+        })
+    }
+
+    /// Name the value is read by.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Channels the value is computed from.
+    #[must_use]
+    pub fn inputs(&self) -> &[ChannelType] {
+        &self.inputs
+    }
+
+    /// Compute the value for `snapshot`, bypassing any cache.
+    #[must_use]
+    pub fn compute(&self, snapshot: &StateSnapshot) -> Value {
+        (self.compute)(snapshot)
+    }
+
+    fn versions(&self, snapshot: &StateSnapshot) -> Vec<u32> {
+        self.inputs
+            .iter()
+            .map(|channel| match channel {
+                ChannelType::Message => snapshot.messages_version,
+                ChannelType::Extra => snapshot.extra_version,
+                ChannelType::Error => snapshot.errors_version,
+                ChannelType::Blob => snapshot.blobs_version,
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for DerivedChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedChannel")
+            .field("name", &self.name)
+            .field("inputs", &self.inputs)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Derived channels keyed by name.
+///
+/// Registered with
+/// [`GraphBuilder::with_derived_channel`](crate::graphs::GraphBuilder::with_derived_channel).
+/// Cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct DerivedChannels {
+    channels: Arc<BTreeMap<String, DerivedChannel>>,
+}
+
+impl DerivedChannels {
+    /// No derived channels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `channel`, replacing any channel with the same name.
+    #[must_use]
+    pub fn with_channel(mut self, channel: DerivedChannel) -> Self {
+        Arc::make_mut(&mut self.channels).insert(channel.name.clone(), channel);
+        self
+    }
+
+    /// Channel registered under `name`, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&DerivedChannel> {
+        self.channels.get(name)
+    }
+
+    /// Whether no channels are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Input channel names per derived channel.
+    pub(crate) fn labels(&self) -> BTreeMap<String, Vec<String>> {
+        self.channels
+            .iter()
+            .map(|(name, channel)| {
+                let inputs = channel.inputs.iter().map(ToString::to_string).collect();
+                (name.clone(), inputs)
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Cache and values
+// ============================================================================
+
+/// Computed values of one session, keyed by the input versions they were computed at.
+#[derive(Debug, Default)]
+pub struct DerivedCache {
+    entries: Mutex<FxHashMap<String, (Vec<u32>, Value)>>,
+    last_step: Mutex<u64>,
+}
+
+impl DerivedCache {
+    /// An empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `step` is about to run, discarding every entry when the
+    /// session did not move forward (a rerun, rewind, or recreated session),
+    /// since channel versions may then repeat with different contents.
+    pub(crate) fn begin_step(&self, step: u64) {
+        let mut last = self.last_step.lock().unwrap_or_else(|e| e.into_inner());
+        if step <= *last {
+            self.entries
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
+        }
+        *last = step;
+    }
+
+    fn get_or_compute(&self, channel: &DerivedChannel, snapshot: &StateSnapshot) -> Value {
+        let versions = channel.versions(snapshot);
+        if let Some((cached, value)) = self
+            .entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(channel.name())
+            && *cached == versions
+        {
+            return value.clone();
+        }
+        // Computed outside the lock so slow functions do not block other readers.
+        let value = channel.compute(snapshot);
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(channel.name.clone(), (versions, value.clone()));
+        value
+    }
+}
+
+/// Derived channels bound to one state snapshot.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use serde_json::json;
+/// use weavegraph::graphs::{DerivedChannel, DerivedChannels, DerivedValues};
+/// use weavegraph::state::VersionedState;
+///
+/// let channels = DerivedChannels::new()
+///     .with_channel(DerivedChannel::message_count())
+///     .with_channel(DerivedChannel::last_user_message());
+/// let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+/// let values = DerivedValues::new(channels, Arc::new(snapshot));
+/// assert_eq!(values.get("message_count"), Some(json!(1)));
+/// assert_eq!(values.get_as::<String>("last_user_message").unwrap().as_deref(), Some("hi"));
+/// assert_eq!(values.get("unknown"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DerivedValues {
+    channels: DerivedChannels,
+    cache: Arc<DerivedCache>,
+    snapshot: Arc<StateSnapshot>,
+}
+
+impl DerivedValues {
+    /// Bind `channels` to `snapshot` with a fresh cache.
+    #[must_use]
+    pub fn new(channels: DerivedChannels, snapshot: Arc<StateSnapshot>) -> Self {
+        Self::with_cache(channels, Arc::new(DerivedCache::new()), snapshot)
+    }
+
+    /// Bind `channels` to `snapshot`, reusing values in `cache`.
+    #[must_use]
+    pub fn with_cache(
+        channels: DerivedChannels,
+        cache: Arc<DerivedCache>,
+        snapshot: Arc<StateSnapshot>,
+    ) -> Self {
+        Self {
+            channels,
+            cache,
+            snapshot,
+        }
+    }
+
+    /// Value of the derived channel `name`, or `None` if none is registered.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Value> {
+        let channel = self.channels.get(name)?;
+        Some(self.cache.get_or_compute(channel, &self.snapshot))
+    }
+
+    /// Typed value of the derived channel `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateSlotError::Deserialize`] if the value has the wrong shape.
+    pub fn get_as<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, StateSlotError> {
+        self.get(name)
+            .map(|value| {
+                serde_json::from_value(value).map_err(|source| StateSlotError::Deserialize {
+                    key: name.to_string(),
+                    source,
+                })
+            })
+            .transpose()
+    }
+}
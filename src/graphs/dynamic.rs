@@ -0,0 +1,326 @@
+//! Runtime graph mutation for plugin-style node discovery.
+//!
+//! A compiled [`App`] is immutable. [`DynamicGraph`] wraps one and accepts
+//! [`GraphPatch`]es that add nodes and edges after startup, for example when an
+//! MCP server advertises a new tool mid-session. Each patch is validated as a
+//! whole with the same rules as [`GraphBuilder::compile`] and, when accepted,
+//! produces a new immutable revision with a higher version number. Runners
+//! attached through
+//! [`AppRunnerBuilder::dynamic_graph`](crate::runtimes::AppRunnerBuilder::dynamic_graph)
+//! switch to the latest revision before each step.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::{DynamicGraph, GraphBuilder, GraphPatch};
+//! use weavegraph::types::NodeKind;
+//!
+//! # struct Tool;
+//! # #[async_trait::async_trait]
+//! # impl weavegraph::node::Node for Tool {
+//! #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+//! #         Ok(weavegraph::node::NodePartial::default())
+//! #     }
+//! # }
+//! let search = NodeKind::Custom("search".into());
+//! let app = GraphBuilder::new()
+//!     .add_node(search.clone(), Tool)
+//!     .add_edge(NodeKind::Start, search.clone())
+//!     .add_edge(search.clone(), NodeKind::End)
+//!     .compile()
+//!     .unwrap();
+//! let graph = DynamicGraph::new(app);
+//!
+//! let summarize = NodeKind::Custom("summarize".into());
+//! let revision = graph
+//!     .apply(
+//!         GraphPatch::new()
+//!             .add_node(summarize.clone(), Tool)
+//!             .add_edge(NodeKind::Start, summarize.clone())
+//!             .add_edge(summarize, NodeKind::End),
+//!     )
+//!     .unwrap();
+//! assert_eq!(revision.version, 1);
+//! assert_eq!(graph.app().nodes().len(), 2);
+//! ```
+
+use std::sync::{Arc, RwLock};
+
+use thiserror::Error;
+
+use super::builder::GraphBuilder;
+use super::compilation::GraphCompileError;
+use super::edges::{ConditionalEdge, EdgePredicate, RoutingPredicate};
+use crate::app::App;
+use crate::node::Node;
+use crate::types::NodeKind;
+
+// ============================================================================
+// Patches
+// ============================================================================
+
+/// A batch of graph additions applied atomically by [`DynamicGraph::apply`].
+///
+/// New nodes usually need edges in the same patch to be reachable, so a patch
+/// is validated as a whole rather than per call.
+#[derive(Clone, Default)]
+#[must_use]
+pub struct GraphPatch {
+    nodes: Vec<(NodeKind, Arc<dyn Node>)>,
+    edges: Vec<(NodeKind, NodeKind)>,
+    conditional_edges: Vec<ConditionalEdge>,
+}
+
+impl GraphPatch {
+    /// Create an empty patch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node. The id must not exist in the graph or earlier in the patch.
+    pub fn add_node(self, id: NodeKind, node: impl Node + 'static) -> Self {
+        self.add_shared_node(id, Arc::new(node))
+    }
+
+    /// Add an already shared node implementation.
+    pub fn add_shared_node(mut self, id: NodeKind, node: Arc<dyn Node>) -> Self {
+        self.nodes.push((id, node));
+        self
+    }
+
+    /// Add an unconditional edge.
+    pub fn add_edge(mut self, from: NodeKind, to: NodeKind) -> Self {
+        self.edges.push((from, to));
+        self
+    }
+
+    /// Add a snapshot-based conditional edge.
+    pub fn add_conditional_edge(mut self, from: NodeKind, predicate: EdgePredicate) -> Self {
+        self.conditional_edges
+            .push(ConditionalEdge::new(from, predicate));
+        self
+    }
+
+    /// Add a [`RoutingContext`](super::RoutingContext)-based conditional edge.
+    pub fn add_conditional_edge_with_context(
+        mut self,
+        from: NodeKind,
+        predicate: RoutingPredicate,
+    ) -> Self {
+        self.conditional_edges
+            .push(ConditionalEdge::with_context(from, predicate));
+        self
+    }
+
+    /// Whether the patch adds nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty() && self.conditional_edges.is_empty()
+    }
+}
+
+// ============================================================================
+// Revisions
+// ============================================================================
+
+/// Record of one accepted graph revision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GraphRevision {
+    /// Monotonic revision number; the initial graph is version 0.
+    pub version: u64,
+    /// [`App::graph_definition_hash`] of the revision.
+    pub graph_hash: String,
+    /// Nodes added by the patch, in patch order.
+    pub added_nodes: Vec<NodeKind>,
+    /// Unconditional edges added by the patch.
+    pub added_edges: Vec<(NodeKind, NodeKind)>,
+    /// Number of conditional edges added by the patch.
+    pub added_conditional_edges: usize,
+}
+
+/// Errors returned when a [`GraphPatch`] is rejected. The graph is left unchanged.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum DynamicGraphError {
+    /// The patch adds nothing.
+    #[error("graph patch is empty")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::graphs::dynamic::empty_patch))
+    )]
+    EmptyPatch,
+
+    /// The patch registers `Start` or `End`, which are virtual.
+    #[error("cannot register virtual node {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::graphs::dynamic::virtual_node))
+    )]
+    VirtualNode(NodeKind),
+
+    /// The node id is already registered.
+    #[error("node {0} is already registered")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::graphs::dynamic::duplicate_node),
+            help("Dynamic graphs only add nodes; choose a new id for the replacement.")
+        )
+    )]
+    DuplicateNode(NodeKind),
+
+    /// The patched graph fails compile-time validation.
+    #[error("patched graph is invalid: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::graphs::dynamic::invalid))
+    )]
+    Invalid(#[from] GraphCompileError),
+}
+
+// ============================================================================
+// DynamicGraph
+// ============================================================================
+
+struct DynamicGraphState {
+    app: Arc<App>,
+    revisions: Vec<GraphRevision>,
+}
+
+/// A versioned, mutable wrapper around a compiled [`App`].
+///
+/// Share it with `Arc<DynamicGraph>`; [`apply`](Self::apply) takes `&self`.
+/// Readers always observe a complete revision, never a partially applied patch.
+pub struct DynamicGraph {
+    state: RwLock<DynamicGraphState>,
+}
+
+impl DynamicGraph {
+    /// Wrap `app` as revision 0.
+    #[must_use]
+    pub fn new(app: App) -> Self {
+        let revision = GraphRevision {
+            version: 0,
+            graph_hash: app.graph_definition_hash(),
+            added_nodes: Vec::new(),
+            added_edges: Vec::new(),
+            added_conditional_edges: 0,
+        };
+        Self {
+            state: RwLock::new(DynamicGraphState {
+                app: Arc::new(app),
+                revisions: vec![revision],
+            }),
+        }
+    }
+
+    /// The latest revision of the compiled graph.
+    #[must_use]
+    pub fn app(&self) -> Arc<App> {
+        Arc::clone(&self.read().app)
+    }
+
+    /// The latest revision number.
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.read().revisions.last().map_or(0, |r| r.version)
+    }
+
+    /// The latest revision together with its number, read atomically.
+    #[must_use]
+    pub fn current(&self) -> (u64, Arc<App>) {
+        let state = self.read();
+        let version = state.revisions.last().map_or(0, |r| r.version);
+        (version, Arc::clone(&state.app))
+    }
+
+    /// Every accepted revision, oldest first.
+    #[must_use]
+    pub fn revisions(&self) -> Vec<GraphRevision> {
+        self.read().revisions.clone()
+    }
+
+    /// Validate `patch` against the latest revision and, if valid, publish the
+    /// result as a new revision.
+    ///
+    /// Nodes that were already registered by a running session are not
+    /// registered again; runners call `on_register` for the added nodes when
+    /// they switch to the new revision.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DynamicGraphError`] if the patch is empty, registers a virtual
+    /// or existing node, or produces a graph that fails validation.
+    pub fn apply(&self, patch: GraphPatch) -> Result<GraphRevision, DynamicGraphError> {
+        if patch.is_empty() {
+            return Err(DynamicGraphError::EmptyPatch);
+        }
+        let mut state = self.state.write().expect("DynamicGraph lock poisoned");
+
+        let mut builder = GraphBuilder::from_app(&state.app);
+        let mut added_nodes = Vec::with_capacity(patch.nodes.len());
+        for (id, node) in patch.nodes {
+            if matches!(id, NodeKind::Start | NodeKind::End) {
+                return Err(DynamicGraphError::VirtualNode(id));
+            }
+            if state.app.nodes().contains_key(&id) || added_nodes.contains(&id) {
+                return Err(DynamicGraphError::DuplicateNode(id));
+            }
+            added_nodes.push(id.clone());
+            builder = builder.add_shared_node(id, node);
+        }
+        for (from, to) in &patch.edges {
+            builder = builder.add_edge(from.clone(), to.clone());
+        }
+        let added_conditional_edges = patch.conditional_edges.len();
+        for edge in patch.conditional_edges {
+            builder = builder.push_conditional_edge(edge);
+        }
+
+        let app = builder.compile()?.sharing_lifecycle_with(&state.app);
+        let version = state.revisions.last().map_or(0, |r| r.version) + 1;
+        let revision = GraphRevision {
+            version,
+            graph_hash: app.graph_definition_hash(),
+            added_nodes,
+            added_edges: patch.edges,
+            added_conditional_edges,
+        };
+        tracing::info!(
+            target: "weavegraph::graphs",
+            version,
+            graph_hash = %revision.graph_hash,
+            added_nodes = ?revision.added_nodes,
+            "dynamic graph revision applied"
+        );
+        state.app = Arc::new(app);
+        state.revisions.push(revision.clone());
+        Ok(revision)
+    }
+
+    /// Nodes added by revisions after `version`, oldest first.
+    #[must_use]
+    pub fn nodes_added_since(&self, version: u64) -> Vec<NodeKind> {
+        self.read()
+            .revisions
+            .iter()
+            .filter(|r| r.version > version)
+            .flat_map(|r| r.added_nodes.iter().cloned())
+            .collect()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, DynamicGraphState> {
+        self.state.read().expect("DynamicGraph lock poisoned")
+    }
+}
+
+impl std::fmt::Debug for DynamicGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.read();
+        f.debug_struct("DynamicGraph")
+            .field("revisions", &state.revisions)
+            .finish_non_exhaustive()
+    }
+}
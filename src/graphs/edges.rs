@@ -4,7 +4,17 @@
 //! in workflow graphs, including conditional edges that can route based
 //! on runtime state evaluation.
 
+use super::derived::DerivedValues;
+use crate::app::BarrierOutcome;
+use crate::channels::errors::ErrorEvent;
+use crate::feature_flags::{FeatureFlags, NO_FLAGS};
+use crate::runtimes::RuntimeConfig;
+use crate::state::{StateKey, StateSlotError, StateSnapshot};
 use crate::types::NodeKind;
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::sync::Arc;
 
 /// Predicate function for conditional edge routing.
@@ -45,6 +55,265 @@ use std::sync::Arc;
 pub type EdgePredicate =
     Arc<dyn Fn(crate::state::StateSnapshot) -> Vec<String> + Send + Sync + 'static>;
 
+/// Context-aware predicate for conditional edge routing.
+///
+/// Receives a [`RoutingContext`] describing the step that just completed:
+/// the state snapshot, step number, which nodes ran, what the barrier
+/// updated, and the graph's [`RuntimeConfig`] values. Register it with
+/// [`GraphBuilder::add_conditional_edge_with_context`](crate::graphs::GraphBuilder::add_conditional_edge_with_context).
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::RoutingPredicate;
+/// use weavegraph::types::NodeKind;
+/// use std::sync::Arc;
+///
+/// // Stop after a configured number of steps.
+/// let bounded: RoutingPredicate = Arc::new(|ctx| {
+///     let limit = ctx.config_value("max_steps").and_then(|v| v.as_u64()).unwrap_or(3);
+///     if ctx.step() >= limit {
+///         vec![NodeKind::end_target()]
+///     } else {
+///         vec![NodeKind::Custom("loop".into()).as_target()]
+///     }
+/// });
+/// ```
+pub type RoutingPredicate =
+    Arc<dyn for<'a> Fn(&RoutingContext<'a>) -> Vec<String> + Send + Sync + 'static>;
+
+// ============================================================================
+// Routing Context
+// ============================================================================
+
+/// Everything a [`RoutingPredicate`] can inspect when choosing targets.
+///
+/// Built by the runner after each barrier for every conditional edge whose
+/// source node ran in that step. Outside the runner (for example when unit
+/// testing a predicate) use [`RoutingContext::new`] and the `with_*` methods;
+/// fields that are not set report empty values.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::RoutingContext;
+/// use weavegraph::runtimes::RuntimeConfig;
+/// use weavegraph::state::VersionedState;
+/// use weavegraph::types::NodeKind;
+///
+/// let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+/// let config = RuntimeConfig::default().with_value("mode", "strict");
+/// let from = NodeKind::Custom("classify".into());
+/// let ctx = RoutingContext::new(&from, &snapshot)
+///     .with_step(4)
+///     .with_config(&config);
+///
+/// assert_eq!(ctx.step(), 4);
+/// assert_eq!(ctx.config_value("mode").and_then(|v| v.as_str()), Some("strict"));
+/// assert!(ctx.ran_nodes().is_empty());
+/// ```
+#[derive(Clone, Copy)]
+pub struct RoutingContext<'a> {
+    from: &'a NodeKind,
+    snapshot: &'a StateSnapshot,
+    step: u64,
+    ran_nodes: &'a [NodeKind],
+    barrier: Option<&'a BarrierOutcome>,
+    config: Option<&'a RuntimeConfig>,
+    flags: Option<&'a FeatureFlags>,
+    derived: Option<&'a DerivedValues>,
+}
+
+impl<'a> RoutingContext<'a> {
+    /// Create a context for the edge leaving `from` with the given state.
+    #[must_use]
+    pub fn new(from: &'a NodeKind, snapshot: &'a StateSnapshot) -> Self {
+        Self {
+            from,
+            snapshot,
+            step: 0,
+            ran_nodes: &[],
+            barrier: None,
+            config: None,
+            flags: None,
+            derived: None,
+        }
+    }
+
+    /// Set the step number that just completed.
+    #[must_use]
+    pub fn with_step(mut self, step: u64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the nodes that ran in the step.
+    #[must_use]
+    pub fn with_ran_nodes(mut self, ran_nodes: &'a [NodeKind]) -> Self {
+        self.ran_nodes = ran_nodes;
+        self
+    }
+
+    /// Set the barrier outcome of the step.
+    #[must_use]
+    pub fn with_barrier(mut self, barrier: &'a BarrierOutcome) -> Self {
+        self.barrier = Some(barrier);
+        self
+    }
+
+    /// Set the runtime configuration of the graph.
+    #[must_use]
+    pub fn with_config(mut self, config: &'a RuntimeConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Set the feature flags resolved for the session.
+    #[must_use]
+    pub fn with_feature_flags(mut self, flags: &'a FeatureFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Set the derived channels bound to the step's state.
+    #[must_use]
+    pub fn with_derived(mut self, derived: &'a DerivedValues) -> Self {
+        self.derived = Some(derived);
+        self
+    }
+
+    /// Source node of the conditional edge being evaluated.
+    #[must_use]
+    pub fn from(&self) -> &'a NodeKind {
+        self.from
+    }
+
+    /// State after the barrier of the step.
+    #[must_use]
+    pub fn snapshot(&self) -> &'a StateSnapshot {
+        self.snapshot
+    }
+
+    /// Step number that just completed (`0` outside the runner).
+    #[must_use]
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Nodes that ran in the step, in execution order.
+    #[must_use]
+    pub fn ran_nodes(&self) -> &'a [NodeKind] {
+        self.ran_nodes
+    }
+
+    /// Channels updated by the step's barrier (e.g. `"messages"`, `"extra"`).
+    #[must_use]
+    pub fn updated_channels(&self) -> &'a [&'static str] {
+        self.barrier.map_or(&[], |b| b.updated_channels.as_slice())
+    }
+
+    /// Errors collected by the step's barrier.
+    #[must_use]
+    pub fn errors(&self) -> &'a [ErrorEvent] {
+        self.barrier.map_or(&[], |b| b.errors.as_slice())
+    }
+
+    /// The graph's runtime configuration, if available.
+    #[must_use]
+    pub fn config(&self) -> Option<&'a RuntimeConfig> {
+        self.config
+    }
+
+    /// Look up a value set with [`RuntimeConfig::with_value`].
+    #[must_use]
+    pub fn config_value(&self, key: &str) -> Option<&'a Value> {
+        self.config.and_then(|config| config.value(key))
+    }
+
+    /// Feature flags resolved for the session; empty when none are configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use weavegraph::feature_flags::FeatureFlags;
+    /// use weavegraph::graphs::RoutingContext;
+    /// use weavegraph::state::VersionedState;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let from = NodeKind::Custom("draft".into());
+    /// let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+    /// let flags = FeatureFlags::from_iter([("new_prompt".to_string(), json!(true))]);
+    /// let ctx = RoutingContext::new(&from, &snapshot).with_feature_flags(&flags);
+    /// assert!(ctx.feature_flags().is_enabled("new_prompt"));
+    /// ```
+    #[must_use]
+    pub fn feature_flags(&self) -> &'a FeatureFlags {
+        self.flags.unwrap_or(&NO_FLAGS)
+    }
+
+    /// Value of the derived channel `name` after the step's barrier.
+    ///
+    /// Returns `None` when no channel with that name is registered with
+    /// [`GraphBuilder::with_derived_channel`](crate::graphs::GraphBuilder::with_derived_channel).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use serde_json::json;
+    /// use weavegraph::graphs::{DerivedChannel, DerivedChannels, DerivedValues, RoutingContext};
+    /// use weavegraph::state::VersionedState;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let from = NodeKind::Custom("chat".into());
+    /// let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+    /// let channels = DerivedChannels::new().with_channel(DerivedChannel::message_count());
+    /// let derived = DerivedValues::new(channels, Arc::new(snapshot.clone()));
+    /// let ctx = RoutingContext::new(&from, &snapshot).with_derived(&derived);
+    /// assert_eq!(ctx.derived("message_count"), Some(json!(1)));
+    /// ```
+    #[must_use]
+    pub fn derived(&self, name: &str) -> Option<Value> {
+        self.derived?.get(name)
+    }
+
+    /// Typed value of the derived channel `name`; see [`derived`](Self::derived).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateSlotError::Deserialize`] if the value has the wrong shape.
+    pub fn derived_as<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, StateSlotError> {
+        match self.derived {
+            Some(derived) => derived.get_as(name),
+            None => Ok(None),
+        }
+    }
+
+    /// Read a typed slot from the state's extra channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateSlotError::Deserialize`] if the stored value has the wrong shape.
+    pub fn get_typed<T: DeserializeOwned>(
+        &self,
+        key: StateKey<T>,
+    ) -> Result<Option<T>, StateSlotError> {
+        self.snapshot.get_typed(key)
+    }
+}
+
+impl std::fmt::Debug for RoutingContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutingContext")
+            .field("from", self.from)
+            .field("step", &self.step)
+            .field("ran_nodes", &self.ran_nodes)
+            .field("updated_channels", &self.updated_channels())
+            .finish_non_exhaustive()
+    }
+}
+
 /// A conditional edge that routes based on a predicate function.
 ///
 /// Conditional edges allow dynamic routing in workflows based on the current
@@ -79,6 +348,10 @@ pub struct ConditionalEdge {
     from: NodeKind,
     /// The predicate function that determines target node.
     predicate: EdgePredicate,
+    /// Context-aware predicate; when set, the runner evaluates this instead.
+    routing: Option<RoutingPredicate>,
+    /// Human-readable description of the routing decision.
+    label: Option<String>,
 }
 
 impl ConditionalEdge {
@@ -110,16 +383,157 @@ impl ConditionalEdge {
         Self {
             from: from.into(),
             predicate,
+            routing: None,
+            label: None,
+        }
+    }
+
+    /// Creates a conditional edge whose predicate receives a [`RoutingContext`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{ConditionalEdge, RoutingPredicate};
+    /// use weavegraph::types::NodeKind;
+    /// use std::sync::Arc;
+    ///
+    /// let predicate: RoutingPredicate = Arc::new(|ctx| {
+    ///     if ctx.errors().is_empty() {
+    ///         vec![NodeKind::end_target()]
+    ///     } else {
+    ///         vec![NodeKind::Custom("recover".into()).as_target()]
+    ///     }
+    /// });
+    /// let edge = ConditionalEdge::with_context(NodeKind::Custom("work".into()), predicate);
+    /// assert!(edge.uses_context());
+    /// ```
+    pub fn with_context(from: impl Into<NodeKind>, predicate: RoutingPredicate) -> Self {
+        let from = from.into();
+        let source = from.clone();
+        let routing = Arc::clone(&predicate);
+        Self {
+            from,
+            predicate: Arc::new(move |snapshot| routing(&RoutingContext::new(&source, &snapshot))),
+            routing: Some(predicate),
+            label: None,
         }
     }
 
+    /// Attach a human-readable label describing the routing decision.
+    ///
+    /// Labels are reported by [`App::describe`](crate::app::App::describe)
+    /// and do not affect routing or the graph hash.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::graphs::{ConditionalEdge, EdgePredicate};
+    /// use weavegraph::types::NodeKind;
+    /// use std::sync::Arc;
+    ///
+    /// let predicate: EdgePredicate = Arc::new(|_| vec![NodeKind::end_target()]);
+    /// let edge = ConditionalEdge::new(NodeKind::Start, predicate).with_label("always finish");
+    /// assert_eq!(edge.label(), Some("always finish"));
+    /// ```
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Returns the label set with [`with_label`](Self::with_label), if any.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     /// Returns the source node of this conditional edge.
     pub fn from(&self) -> &NodeKind {
         &self.from
     }
 
     /// Returns the predicate function of this conditional edge.
+    ///
+    /// For edges created with [`with_context`](Self::with_context) this is a
+    /// snapshot-only view: the context carries no step, barrier, or config data.
     pub fn predicate(&self) -> &EdgePredicate {
         &self.predicate
     }
+
+    /// Whether this edge was registered with a [`RoutingPredicate`].
+    pub fn uses_context(&self) -> bool {
+        self.routing.is_some()
+    }
+
+    /// Evaluate the edge against a full routing context.
+    pub fn evaluate(&self, ctx: &RoutingContext<'_>) -> Vec<String> {
+        match &self.routing {
+            Some(routing) => routing(ctx),
+            None => (self.predicate)(ctx.snapshot().clone()),
+        }
+    }
+}
+
+/// Label and metadata attached to an unconditional edge.
+///
+/// Added with [`GraphBuilder::add_edge_labeled`](crate::graphs::GraphBuilder::add_edge_labeled).
+/// Labels appear in [`App::describe`](crate::app::App::describe), the DOT and
+/// Mermaid exports, routing events, and
+/// [`StepReport::frontier_edges`](crate::runtimes::StepReport::frontier_edges).
+/// They do not affect routing.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use weavegraph::graphs::EdgeLabel;
+///
+/// let label = EdgeLabel::new("on_success").with_metadata("sla_ms", json!(200));
+/// assert_eq!(label.label(), "on_success");
+/// assert_eq!(label.metadata()["sla_ms"], 200);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeLabel {
+    label: String,
+    #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+    metadata: FxHashMap<String, Value>,
+}
+
+impl EdgeLabel {
+    /// Create a label without metadata.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            metadata: FxHashMap::default(),
+        }
+    }
+
+    /// Attach a metadata entry.
+    #[must_use]
+    pub fn with_metadata(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+
+    /// The label text.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Metadata entries attached with [`with_metadata`](Self::with_metadata).
+    pub fn metadata(&self) -> &FxHashMap<String, Value> {
+        &self.metadata
+    }
+}
+
+impl From<&str> for EdgeLabel {
+    fn from(label: &str) -> Self {
+        Self::new(label)
+    }
+}
+
+impl From<String> for EdgeLabel {
+    fn from(label: String) -> Self {
+        Self::new(label)
+    }
 }
@@ -0,0 +1,607 @@
+//! A small, side-effect-free expression language for routing predicates.
+//!
+//! Expressions read workflow state and evaluate to a JSON value, so simple
+//! routing decisions and guards can live in configuration as strings instead
+//! of compiled closures:
+//!
+//! ```text
+//! extra.score > 0.8 && messages.len < 20
+//! extra["review-state"] == "approved" || step >= config.max_steps
+//! !(messages.last.role == "assistant")
+//! ```
+//!
+//! # Syntax
+//!
+//! - Literals: numbers, `"strings"` or `'strings'`, `true`, `false`, `null`.
+//! - Paths start at `extra`, `messages`, `errors`, `step`, `config` or
+//!   `flags` (the session's [feature flags](crate::feature_flags)) and
+//!   continue with `.name`, `["key"]` or `[index]` (negative indexes count
+//!   from the end). `len` works on arrays, strings and objects without a
+//!   `len` key; `first` and `last` work on arrays. Missing values read as
+//!   `null`.
+//! - Operators, loosest first: `||`, `&&`, comparisons (`==`, `!=`, `<`,
+//!   `<=`, `>`, `>=`), prefix `!` and `-`. Parentheses group.
+//!
+//! `&&`, `||` and `!` use truthiness: `null`, `false`, `0`, `""`, `[]` and
+//! `{}` are false. Numbers compare numerically; `<` and friends compare
+//! numbers or strings and are false for anything else.
+//!
+//! Expressions cannot call functions, loop, or modify state, and are parsed
+//! once when the edge is added, so errors surface from
+//! [`GraphBuilder::compile`](crate::graphs::GraphBuilder::compile).
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::{Expression, RoutingContext};
+//! use weavegraph::state::VersionedState;
+//! use weavegraph::types::NodeKind;
+//!
+//! let expr = Expression::parse("extra.score > 0.8 && messages.len < 20").unwrap();
+//! let state = VersionedState::builder()
+//!     .with_user_message("hi")
+//!     .with_extra("score", serde_json::json!(0.93))
+//!     .build();
+//! let snapshot = state.snapshot();
+//! let from = NodeKind::Custom("grade".into());
+//! assert!(expr.matches(&RoutingContext::new(&from, &snapshot)));
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde_json::{Number, Value};
+use thiserror::Error;
+
+use super::edges::RoutingContext;
+
+/// Nesting limit for parentheses and prefix operators.
+const MAX_DEPTH: usize = 64;
+
+/// Error raised when an expression cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[error("invalid expression `{source_text}` at offset {offset}: {message}")]
+#[cfg_attr(
+    feature = "diagnostics",
+    diagnostic(
+        code(weavegraph::graphs::expression),
+        help("See weavegraph::graphs::Expression for the supported syntax.")
+    )
+)]
+#[non_exhaustive]
+pub struct ExpressionError {
+    /// The expression as written.
+    pub source_text: String,
+    /// Byte offset of the problem.
+    pub offset: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+/// A parsed routing expression.
+///
+/// See the [module documentation](self) for the syntax.
+#[derive(Clone, PartialEq)]
+pub struct Expression {
+    source: String,
+    root: Expr,
+}
+
+impl Expression {
+    /// Parse `source`, rejecting syntax errors and unknown path roots.
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let tokens = lex(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(parser.error(token.offset, "unexpected trailing input"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// The expression as written.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate against the state and step described by `ctx`.
+    #[must_use]
+    pub fn evaluate(&self, ctx: &RoutingContext<'_>) -> Value {
+        self.root.eval(ctx)
+    }
+
+    /// Whether the expression is truthy for `ctx`.
+    #[must_use]
+    pub fn matches(&self, ctx: &RoutingContext<'_>) -> bool {
+        truthy(&self.evaluate(ctx))
+    }
+}
+
+impl fmt::Debug for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Expression").field(&self.source).finish()
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+// ============================================================================
+// Syntax tree and evaluation
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Root {
+    Extra,
+    Messages,
+    Errors,
+    Step,
+    Config,
+    Flags,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Path(Root, Vec<Segment>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CmpOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, ctx: &RoutingContext<'_>) -> Value {
+        match self {
+            Expr::Literal(value) => value.clone(),
+            Expr::Path(root, segments) => resolve(ctx, *root, segments),
+            Expr::Not(inner) => Value::Bool(!truthy(&inner.eval(ctx))),
+            Expr::Neg(inner) => inner
+                .eval(ctx)
+                .as_f64()
+                .and_then(|n| Number::from_f64(-n))
+                .map_or(Value::Null, Value::Number),
+            Expr::And(left, right) => {
+                Value::Bool(truthy(&left.eval(ctx)) && truthy(&right.eval(ctx)))
+            }
+            Expr::Or(left, right) => {
+                Value::Bool(truthy(&left.eval(ctx)) || truthy(&right.eval(ctx)))
+            }
+            Expr::Compare(op, left, right) => {
+                Value::Bool(compare(*op, &left.eval(ctx), &right.eval(ctx)))
+            }
+        }
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn compare(op: CmpOp, left: &Value, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .zip(b.as_f64())
+            .and_then(|(a, b)| a.partial_cmp(&b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        CmpOp::Eq => ordering.map_or_else(|| left == right, Ordering::is_eq),
+        CmpOp::Ne => ordering.map_or_else(|| left != right, Ordering::is_ne),
+        CmpOp::Lt => ordering.is_some_and(Ordering::is_lt),
+        CmpOp::Le => ordering.is_some_and(Ordering::is_le),
+        CmpOp::Gt => ordering.is_some_and(Ordering::is_gt),
+        CmpOp::Ge => ordering.is_some_and(Ordering::is_ge),
+    }
+}
+
+/// Resolve a path, serializing only the part of state it reaches into.
+fn resolve(ctx: &RoutingContext<'_>, root: Root, segments: &[Segment]) -> Value {
+    let snapshot = ctx.snapshot();
+    match root {
+        Root::Step => walk(Value::from(ctx.step()), segments),
+        Root::Config => match segments.split_first() {
+            Some((Segment::Key(key), rest)) => {
+                walk(ctx.config_value(key).cloned().unwrap_or(Value::Null), rest)
+            }
+            _ => Value::Null,
+        },
+        Root::Flags => match segments.split_first() {
+            Some((Segment::Key(key), rest)) => walk(
+                ctx.feature_flags().get(key).cloned().unwrap_or(Value::Null),
+                rest,
+            ),
+            _ => Value::Null,
+        },
+        Root::Extra => match segments.split_first() {
+            // Look the first key up directly rather than cloning the whole map.
+            Some((Segment::Key(key), rest)) if snapshot.extra.contains_key(key) => {
+                walk(snapshot.extra[key].clone(), rest)
+            }
+            Some((Segment::Key(key), _)) if key != "len" => Value::Null,
+            _ => walk(
+                Value::Object(
+                    snapshot
+                        .extra
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                ),
+                segments,
+            ),
+        },
+        Root::Messages => walk_list(&snapshot.messages, segments),
+        Root::Errors => walk_list(&snapshot.errors, segments),
+    }
+}
+
+fn walk_list<T: serde::Serialize>(items: &[T], segments: &[Segment]) -> Value {
+    let Some((segment, rest)) = segments.split_first() else {
+        return serde_json::to_value(items).unwrap_or(Value::Null);
+    };
+    if matches!(segment, Segment::Key(key) if key == "len") {
+        return walk(Value::from(items.len()), rest);
+    }
+    match index_of(items.len(), segment) {
+        Some(index) => walk(
+            serde_json::to_value(&items[index]).unwrap_or(Value::Null),
+            rest,
+        ),
+        None => Value::Null,
+    }
+}
+
+fn walk(mut value: Value, segments: &[Segment]) -> Value {
+    for segment in segments {
+        value = step_into(value, segment);
+    }
+    value
+}
+
+fn step_into(value: Value, segment: &Segment) -> Value {
+    match value {
+        Value::Object(mut map) => match segment {
+            Segment::Key(key) => match map.remove(key) {
+                Some(inner) => inner,
+                None if key == "len" => Value::from(map.len()),
+                None => Value::Null,
+            },
+            Segment::Index(_) => Value::Null,
+        },
+        Value::Array(mut items) => match segment {
+            Segment::Key(key) if key == "len" => Value::from(items.len()),
+            _ => index_of(items.len(), segment).map_or(Value::Null, |i| items.swap_remove(i)),
+        },
+        Value::String(text) => match segment {
+            Segment::Key(key) if key == "len" => Value::from(text.chars().count()),
+            _ => Value::Null,
+        },
+        _ => Value::Null,
+    }
+}
+
+fn index_of(len: usize, segment: &Segment) -> Option<usize> {
+    let index = match segment {
+        Segment::Key(key) if key == "first" => 0,
+        Segment::Key(key) if key == "last" => len.checked_sub(1)?,
+        Segment::Index(index) if *index < 0 => len.checked_sub(index.unsigned_abs() as usize)?,
+        Segment::Index(index) => *index as usize,
+        Segment::Key(_) => return None,
+    };
+    (index < len).then_some(index)
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Dot,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Minus,
+    Cmp(CmpOp),
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    tok: Tok,
+    offset: usize,
+}
+
+fn lex(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let error = |offset: usize, message: &str| ExpressionError {
+        source_text: source.to_string(),
+        offset,
+        message: message.to_string(),
+    };
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let two = |next: u8| bytes.get(i + 1) == Some(&next);
+        let (tok, width) = match c {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'.' => (Tok::Dot, 1),
+            b'[' => (Tok::LBracket, 1),
+            b']' => (Tok::RBracket, 1),
+            b'(' => (Tok::LParen, 1),
+            b')' => (Tok::RParen, 1),
+            b'-' => (Tok::Minus, 1),
+            b'&' if two(b'&') => (Tok::And, 2),
+            b'|' if two(b'|') => (Tok::Or, 2),
+            b'=' if two(b'=') => (Tok::Cmp(CmpOp::Eq), 2),
+            b'!' if two(b'=') => (Tok::Cmp(CmpOp::Ne), 2),
+            b'!' => (Tok::Not, 1),
+            b'<' if two(b'=') => (Tok::Cmp(CmpOp::Le), 2),
+            b'<' => (Tok::Cmp(CmpOp::Lt), 1),
+            b'>' if two(b'=') => (Tok::Cmp(CmpOp::Ge), 2),
+            b'>' => (Tok::Cmp(CmpOp::Gt), 1),
+            b'"' | b'\'' => {
+                let mut text = String::new();
+                let mut chars = source[i + 1..].char_indices();
+                let end = loop {
+                    match chars.next() {
+                        None => return Err(error(start, "unterminated string")),
+                        Some((at, ch)) if ch as u32 == u32::from(c) => break i + 1 + at,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, 'n')) => text.push('\n'),
+                            Some((_, 't')) => text.push('\t'),
+                            Some((_, ch)) => text.push(ch),
+                            None => return Err(error(start, "unterminated string")),
+                        },
+                        Some((_, ch)) => text.push(ch),
+                    }
+                };
+                (Tok::Str(text), end + 1 - start)
+            }
+            b'0'..=b'9' => {
+                let len = source[i..]
+                    .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+                    .unwrap_or(source.len() - i);
+                let number = source[i..i + len]
+                    .parse::<f64>()
+                    .map_err(|_| error(start, "invalid number"))?;
+                (Tok::Number(number), len)
+            }
+            c if c == b'_' || c.is_ascii_alphabetic() => {
+                let len = source[i..]
+                    .find(|ch: char| !(ch == '_' || ch.is_ascii_alphanumeric()))
+                    .unwrap_or(source.len() - i);
+                (Tok::Ident(source[i..i + len].to_string()), len)
+            }
+            _ => return Err(error(start, "unexpected character")),
+        };
+        tokens.push(Token { tok, offset: start });
+        i += width;
+    }
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct Parser<'s> {
+    source: &'s str,
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, offset: usize, message: impl Into<String>) -> ExpressionError {
+        ExpressionError {
+            source_text: self.source.to_string(),
+            offset,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.source.len(), |t| t.offset)
+    }
+
+    fn eat(&mut self, tok: &Tok) -> bool {
+        if self.peek() == Some(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, tok: &Tok, what: &str) -> Result<(), ExpressionError> {
+        if self.eat(tok) {
+            Ok(())
+        } else {
+            Err(self.error(self.offset(), format!("expected {what}")))
+        }
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ExpressionError>,
+    ) -> Result<T, ExpressionError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(self.offset(), "expression is nested too deeply"));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_and()?;
+        while self.eat(&Tok::Or) {
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_compare()?;
+        while self.eat(&Tok::And) {
+            left = Expr::And(Box::new(left), Box::new(self.parse_compare()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_compare(&mut self) -> Result<Expr, ExpressionError> {
+        let left = self.parse_unary()?;
+        if let Some(Tok::Cmp(op)) = self.peek().cloned() {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            if let Some(Tok::Cmp(_)) = self.peek() {
+                return Err(self.error(self.offset(), "comparisons cannot be chained"));
+            }
+            return Ok(Expr::Compare(op, Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.eat(&Tok::Not) {
+            return self.nested(|p| Ok(Expr::Not(Box::new(p.parse_unary()?))));
+        }
+        if self.eat(&Tok::Minus) {
+            return self.nested(|p| Ok(Expr::Neg(Box::new(p.parse_unary()?))));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExpressionError> {
+        let offset = self.offset();
+        let Some(tok) = self.peek().cloned() else {
+            return Err(self.error(offset, "unexpected end of expression"));
+        };
+        self.pos += 1;
+        match tok {
+            Tok::Number(n) => Ok(Expr::Literal(
+                Number::from_f64(n).map_or(Value::Null, Value::Number),
+            )),
+            Tok::Str(text) => Ok(Expr::Literal(Value::String(text))),
+            Tok::LParen => self.nested(|p| {
+                let inner = p.parse_or()?;
+                p.expect(&Tok::RParen, "`)`")?;
+                Ok(inner)
+            }),
+            Tok::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                "extra" => self.parse_path(Root::Extra),
+                "messages" => self.parse_path(Root::Messages),
+                "errors" => self.parse_path(Root::Errors),
+                "step" => self.parse_path(Root::Step),
+                "config" => self.parse_path(Root::Config),
+                "flags" => self.parse_path(Root::Flags),
+                _ => Err(self.error(
+                    offset,
+                    format!(
+                        "unknown name `{name}`; paths start at extra, messages, errors, step, config or flags"
+                    ),
+                )),
+            },
+            _ => Err(self.error(offset, "expected a value")),
+        }
+    }
+
+    fn parse_path(&mut self, root: Root) -> Result<Expr, ExpressionError> {
+        let mut segments = Vec::new();
+        loop {
+            if self.eat(&Tok::Dot) {
+                match self.peek().cloned() {
+                    Some(Tok::Ident(name)) => {
+                        self.pos += 1;
+                        segments.push(Segment::Key(name));
+                    }
+                    _ => return Err(self.error(self.offset(), "expected a field name")),
+                }
+            } else if self.eat(&Tok::LBracket) {
+                let negative = self.eat(&Tok::Minus);
+                let offset = self.offset();
+                match self.peek().cloned() {
+                    Some(Tok::Str(key)) if !negative => segments.push(Segment::Key(key)),
+                    Some(Tok::Number(n)) if n.fract() == 0.0 && n <= i64::MAX as f64 => {
+                        let index = n as i64;
+                        segments.push(Segment::Index(if negative { -index } else { index }));
+                    }
+                    _ => return Err(self.error(offset, "expected a string key or integer index")),
+                }
+                self.pos += 1;
+                self.expect(&Tok::RBracket, "`]`")?;
+            } else {
+                return Ok(Expr::Path(root, segments));
+            }
+        }
+    }
+}
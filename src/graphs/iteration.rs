@@ -49,6 +49,7 @@
 //! println!("Topological order: {:?}", sorted);
 //! ```
 
+use super::edges::EdgeLabel;
 use crate::types::NodeKind;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -257,6 +258,70 @@ pub(super) fn topological_sort(edges: &FxHashMap<NodeKind, Vec<NodeKind>>) -> Ve
     result
 }
 
+/// Render the edge map as a Mermaid flowchart.
+///
+/// Sources are emitted in encoded-id order and targets in insertion order, so
+/// the output is stable across runs. Labelled edges use `-->|label|`.
+pub(super) fn to_mermaid(
+    edges: &FxHashMap<NodeKind, Vec<NodeKind>>,
+    labels: &FxHashMap<(NodeKind, NodeKind), EdgeLabel>,
+) -> String {
+    use std::fmt::Write;
+
+    let mut sources: Vec<&NodeKind> = edges.keys().collect();
+    sources.sort_by_key(|node| node.encode());
+
+    let mut declared: FxHashSet<&NodeKind> = FxHashSet::default();
+    let mut output = String::from("flowchart TD\n");
+    for from in sources {
+        for to in &edges[from] {
+            for node in [from, to] {
+                if declared.insert(node) {
+                    writeln!(output, "    {}", mermaid_node(node)).unwrap();
+                }
+            }
+            match labels.get(&(from.clone(), to.clone())) {
+                Some(label) => writeln!(
+                    output,
+                    "    {} -->|{}| {}",
+                    mermaid_id(from),
+                    label.label().replace('"', "#quot;").replace('|', "#124;"),
+                    mermaid_id(to)
+                ),
+                None => writeln!(output, "    {} --> {}", mermaid_id(from), mermaid_id(to)),
+            }
+            .unwrap();
+        }
+    }
+    output
+}
+
+fn mermaid_id(node: &NodeKind) -> String {
+    match node {
+        NodeKind::Start => "Start".to_string(),
+        NodeKind::End => "End".to_string(),
+        NodeKind::Custom(name) => name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect(),
+    }
+}
+
+fn mermaid_node(node: &NodeKind) -> String {
+    match node {
+        NodeKind::Start | NodeKind::End => format!("{0}([{0}])", mermaid_id(node)),
+        NodeKind::Custom(name) => {
+            format!("{}[\"{}\"]", mermaid_id(node), name.replace('"', "#quot;"))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
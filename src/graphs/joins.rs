@@ -0,0 +1,152 @@
+//! Join semantics for nodes reached by several edges in one workflow.
+//!
+//! By default a node that several predecessors route to in the same superstep
+//! runs once against a single snapshot. [`JoinPolicy`] changes that per node:
+//! run once per incoming branch, or wait until every predecessor has arrived.
+
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+
+use crate::types::NodeKind;
+
+/// How the frontier merges multiple arrivals at one node.
+///
+/// Set per node with
+/// [`GraphBuilder::with_join_policy`](crate::graphs::GraphBuilder::with_join_policy).
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::{GraphBuilder, JoinPolicy};
+/// use weavegraph::types::NodeKind;
+///
+/// let builder = GraphBuilder::new()
+///     .with_join_policy(NodeKind::Custom("merge".into()), JoinPolicy::WaitForAll);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JoinPolicy {
+    /// Run once per superstep no matter how many predecessors routed here.
+    #[default]
+    Dedupe,
+    /// Run once per distinct predecessor that routed here in the previous
+    /// superstep. Each run sees its origin through
+    /// [`NodeContext::incoming_from`](crate::node::NodeContext::incoming_from).
+    PerIncomingEdge,
+    /// Run only after every upstream branch has routed here, possibly across
+    /// several supersteps (an AND-join).
+    ///
+    /// The upstream branches are those declared with
+    /// [`GraphBuilder::add_join`](crate::graphs::GraphBuilder::add_join), or
+    /// else every node with an unconditional edge to this one. Arrivals
+    /// accumulate in the checkpointed scheduler state and reset each time the
+    /// node is scheduled; arrivals from other nodes are recorded but do not
+    /// count towards the gate. A node without upstream branches behaves like
+    /// [`Dedupe`](Self::Dedupe).
+    WaitForAll,
+}
+
+impl JoinPolicy {
+    /// Stable label used in introspection and logs.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dedupe => "dedupe",
+            Self::PerIncomingEdge => "per_incoming_edge",
+            Self::WaitForAll => "wait_for_all",
+        }
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Join policies per node, plus upstream branches declared for AND-joins.
+#[derive(Clone, Default)]
+pub(crate) struct JoinPolicies {
+    policies: FxHashMap<NodeKind, JoinPolicy>,
+    upstreams: FxHashMap<NodeKind, Vec<NodeKind>>,
+}
+
+impl JoinPolicies {
+    pub(crate) fn set_policy(&mut self, node: NodeKind, policy: JoinPolicy) {
+        if policy != JoinPolicy::WaitForAll {
+            self.upstreams.remove(&node);
+        }
+        if policy == JoinPolicy::Dedupe {
+            self.policies.remove(&node);
+        } else {
+            self.policies.insert(node, policy);
+        }
+    }
+
+    /// Make `node` a wait-for-all join over exactly `upstreams`.
+    pub(crate) fn declare_join(&mut self, node: NodeKind, upstreams: Vec<NodeKind>) {
+        self.policies.insert(node.clone(), JoinPolicy::WaitForAll);
+        self.upstreams.insert(node, upstreams);
+    }
+
+    pub(crate) fn policy(&self, node: &NodeKind) -> JoinPolicy {
+        self.policies.get(node).copied().unwrap_or_default()
+    }
+
+    /// Declared upstream branches per join node.
+    pub(crate) fn declared(&self) -> &FxHashMap<NodeKind, Vec<NodeKind>> {
+        &self.upstreams
+    }
+
+    /// Wait-for-all join nodes, sorted by encoded name.
+    pub(crate) fn wait_for_all(&self) -> Vec<&NodeKind> {
+        let mut joins: Vec<&NodeKind> = self
+            .policies
+            .iter()
+            .filter(|(_, policy)| **policy == JoinPolicy::WaitForAll)
+            .map(|(node, _)| node)
+            .collect();
+        joins.sort_by_key(|node| node.encode());
+        joins
+    }
+
+    /// Branches `target` waits for: the declared upstreams, or else every node
+    /// with an unconditional edge to it, sorted by encoded name.
+    pub(crate) fn required_upstreams(
+        &self,
+        edges: &FxHashMap<NodeKind, Vec<NodeKind>>,
+        target: &NodeKind,
+    ) -> Vec<NodeKind> {
+        let mut upstreams: Vec<NodeKind> = match self.upstreams.get(target) {
+            Some(declared) => declared.clone(),
+            None => edges
+                .iter()
+                .filter(|(_, targets)| targets.contains(target))
+                .map(|(from, _)| from.clone())
+                .collect(),
+        };
+        upstreams.sort_by_key(NodeKind::encode);
+        upstreams.dedup();
+        upstreams
+    }
+
+    /// Policy labels per node, keyed by the node's display name.
+    pub(crate) fn labels(&self) -> BTreeMap<String, String> {
+        self.policies
+            .iter()
+            .map(|(node, policy)| (node.to_string(), policy.label().to_string()))
+            .collect()
+    }
+
+    /// Declared upstream names per join node, keyed by the node's display name.
+    pub(crate) fn upstream_labels(&self) -> BTreeMap<String, Vec<String>> {
+        self.upstreams
+            .iter()
+            .map(|(node, upstreams)| {
+                (
+                    node.to_string(),
+                    upstreams.iter().map(ToString::to_string).collect(),
+                )
+            })
+            .collect()
+    }
+}
@@ -9,9 +9,16 @@
 //!
 //! - **Nodes**: Executable units of work implementing the [`Node`](crate::node::Node) trait
 //! - **Edges**: Connections between nodes defining execution flow
-//! - **Conditional Edges**: Dynamic routing based on state predicates
+//! - **Conditional Edges**: Dynamic routing based on state predicates, or on a
+//!   full [`RoutingContext`] (step, ran nodes, barrier, config values)
 //! - **Virtual Endpoints**: `NodeKind::Start` and `NodeKind::End` for structural definition
 //! - **Compilation**: Validation and conversion to executable [`App`](crate::app::App)
+//! - **Dynamic Graphs**: [`DynamicGraph`] adds nodes and edges to a compiled
+//!   graph at runtime as validated, versioned revisions
+//! - **Subgraphs**: [`GraphBuilder::add_subgraph`] runs a compiled child graph
+//!   as one node, seeing only the state its [`StateScope`] whitelists
+//! - **Snapshot Views**: a [`SnapshotView`] limits the conversation a node
+//!   receives to a recent or role-filtered window
 //!
 //! # Graph Iteration
 //!
@@ -141,8 +148,18 @@
 // Internal module declarations
 mod builder;
 mod compilation;
+mod derived;
+mod dynamic;
 mod edges;
+mod expr;
 mod iteration;
+mod joins;
+mod quotas;
+mod sla;
+mod subgraph;
+pub mod templates;
+mod validation;
+mod views;
 
 #[cfg(feature = "petgraph-compat")]
 mod petgraph_compat;
@@ -150,8 +167,23 @@ mod petgraph_compat;
 // Public re-exports for backward compatibility
 pub use builder::GraphBuilder;
 pub use compilation::GraphCompileError;
-pub use edges::{ConditionalEdge, EdgePredicate};
+pub use derived::{DeriveFn, DerivedCache, DerivedChannel, DerivedChannels, DerivedValues};
+pub use dynamic::{DynamicGraph, DynamicGraphError, GraphPatch, GraphRevision};
+pub use edges::{ConditionalEdge, EdgeLabel, EdgePredicate, RoutingContext, RoutingPredicate};
+pub use expr::{Expression, ExpressionError};
 pub use iteration::{EdgesIter, NodesIter};
+pub(crate) use joins::JoinPolicies;
+pub use joins::JoinPolicy;
+pub use quotas::{NodeQuotas, QuotaFallback};
+pub(crate) use sla::SlaTracker;
+pub use sla::{SlaBreach, SlaMetric, SlaPolicy};
+pub use subgraph::{StateScope, SubgraphNode};
+pub(crate) use validation::OutputValidators;
+pub use validation::{
+    ExtraValueTypes, JsonType, MaxMessages, OUTPUT_VALIDATION_TAG, OutputValidationError,
+    OutputValidationPolicy, OutputValidator, OutputViolation, RequiredExtraKeys,
+};
+pub use views::{SnapshotView, SnapshotViews, estimate_tokens};
 
 #[cfg(feature = "petgraph-compat")]
 #[cfg_attr(docsrs, doc(cfg(feature = "petgraph-compat")))]
@@ -56,6 +56,7 @@
 //! // }
 //! ```
 
+use super::edges::EdgeLabel;
 use crate::types::NodeKind;
 use petgraph::graph::{DiGraph, NodeIndex};
 use rustc_hash::FxHashMap;
@@ -166,7 +167,10 @@ pub(super) fn to_petgraph(edges: &FxHashMap<NodeKind, Vec<NodeKind>>) -> Petgrap
 /// std::fs::write("workflow.dot", dot)?;
 /// // Then: dot -Tpng workflow.dot -o workflow.png
 /// ```
-pub(super) fn to_dot(edges: &FxHashMap<NodeKind, Vec<NodeKind>>) -> String {
+pub(super) fn to_dot(
+    edges: &FxHashMap<NodeKind, Vec<NodeKind>>,
+    labels: &FxHashMap<(NodeKind, NodeKind), EdgeLabel>,
+) -> String {
     use std::fmt::Write;
 
     let conversion = to_petgraph(edges);
@@ -199,7 +203,18 @@ pub(super) fn to_dot(edges: &FxHashMap<NodeKind, Vec<NodeKind>>) -> String {
     // Write edges
     for edge in conversion.graph.edge_indices() {
         let (from, to) = conversion.graph.edge_endpoints(edge).unwrap();
-        writeln!(output, "    {} -> {};", from.index(), to.index()).unwrap();
+        let key = (conversion.graph[from].clone(), conversion.graph[to].clone());
+        match labels.get(&key) {
+            Some(label) => writeln!(
+                output,
+                "    {} -> {} [ label=\"{}\" ];",
+                from.index(),
+                to.index(),
+                label.label().replace('"', "\\\"")
+            )
+            .unwrap(),
+            None => writeln!(output, "    {} -> {};", from.index(), to.index()).unwrap(),
+        }
     }
 
     writeln!(output, "}}").unwrap();
@@ -271,7 +286,7 @@ mod tests {
     #[test]
     fn test_to_dot_output() {
         let edges = make_linear_graph();
-        let dot = to_dot(&edges);
+        let dot = to_dot(&edges, &FxHashMap::default());
 
         assert!(dot.contains("digraph {"));
         assert!(dot.contains("Start"));
@@ -279,6 +294,20 @@ mod tests {
         assert!(dot.contains("->"));
     }
 
+    #[test]
+    fn test_to_dot_includes_edge_labels() {
+        let edges = make_linear_graph();
+        let mut labels = FxHashMap::default();
+        labels.insert(
+            (NodeKind::Start, NodeKind::Custom("A".into())),
+            EdgeLabel::new("kick \"off\""),
+        );
+        let dot = to_dot(&edges, &labels);
+
+        assert!(dot.contains("[ label=\"kick \\\"off\\\"\" ];"));
+        assert_eq!(dot.matches("label=").count(), 4);
+    }
+
     #[test]
     fn test_deterministic_indices() {
         // Same graph should produce same indices across calls
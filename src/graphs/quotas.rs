@@ -0,0 +1,180 @@
+//! Per-session run quotas for individual nodes.
+//!
+//! A [`NodeQuotas`] caps how many times a node may run in one session, so a
+//! reflection loop such as draft → critic → draft cannot keep spending on LLM
+//! calls forever. Run counts are kept in the checkpointed
+//! [`SchedulerState`](crate::schedulers::SchedulerState), so quotas hold across
+//! resumes. When a node whose quota is used up reaches the frontier, the
+//! runner applies the node's [`QuotaFallback`] before the superstep starts.
+
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+
+use crate::schedulers::SchedulerState;
+use crate::types::NodeKind;
+
+/// What the runner does when a node with an exhausted quota is scheduled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuotaFallback {
+    /// Stop the session with
+    /// [`RunnerError::NodeQuotaExhausted`](crate::runtimes::runner::RunnerError::NodeQuotaExhausted).
+    #[default]
+    Fail,
+    /// Drop the node from the frontier; the session ends if nothing else is scheduled.
+    Skip,
+    /// Run `target` in the node's place. Use [`NodeKind::End`] to finish the
+    /// branch, or a node that wraps up with the best result so far.
+    RouteTo(NodeKind),
+}
+
+impl QuotaFallback {
+    /// Stable label used in introspection and diagnostics.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Fail => "fail".to_string(),
+            Self::Skip => "skip".to_string(),
+            Self::RouteTo(target) => format!("route_to:{}", target.encode()),
+        }
+    }
+}
+
+/// Maximum runs per session for individual nodes.
+///
+/// Attach with
+/// [`GraphBuilder::with_node_quotas`](crate::graphs::GraphBuilder::with_node_quotas).
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::{NodeQuotas, QuotaFallback};
+/// use weavegraph::types::NodeKind;
+///
+/// let critic = NodeKind::Custom("critic".into());
+/// let quotas = NodeQuotas::new()
+///     .max_runs(NodeKind::Custom("search".into()), 10)
+///     .max_runs_or(critic.clone(), 3, QuotaFallback::RouteTo(NodeKind::End));
+/// assert_eq!(quotas.limit(&critic), Some(3));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeQuotas {
+    quotas: FxHashMap<NodeKind, (u32, QuotaFallback)>,
+}
+
+/// A quota that ran out while the runner prepared a superstep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QuotaHit {
+    pub(crate) node: NodeKind,
+    pub(crate) max_runs: u32,
+    pub(crate) fallback: QuotaFallback,
+}
+
+impl NodeQuotas {
+    /// An empty set of quotas.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `node` to run at most `limit` times per session, failing the
+    /// session when it is scheduled again.
+    #[must_use]
+    pub fn max_runs(self, node: NodeKind, limit: u32) -> Self {
+        self.max_runs_or(node, limit, QuotaFallback::Fail)
+    }
+
+    /// Allow `node` to run at most `limit` times per session, applying
+    /// `fallback` when it is scheduled again.
+    #[must_use]
+    pub fn max_runs_or(mut self, node: NodeKind, limit: u32, fallback: QuotaFallback) -> Self {
+        self.quotas.insert(node, (limit, fallback));
+        self
+    }
+
+    /// Run limit for `node`, if any.
+    #[must_use]
+    pub fn limit(&self, node: &NodeKind) -> Option<u32> {
+        self.quotas.get(node).map(|(limit, _)| *limit)
+    }
+
+    /// Fallback for `node`, if it has a quota.
+    #[must_use]
+    pub fn fallback(&self, node: &NodeKind) -> Option<&QuotaFallback> {
+        self.quotas.get(node).map(|(_, fallback)| fallback)
+    }
+
+    /// Whether no quotas are declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.quotas.is_empty()
+    }
+
+    /// Whether `node` has a quota.
+    pub(crate) fn tracks(&self, node: &NodeKind) -> bool {
+        self.quotas.contains_key(node)
+    }
+
+    /// Nodes with quotas and their fallback targets, for validation.
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = (&NodeKind, &QuotaFallback)> {
+        self.quotas
+            .iter()
+            .map(|(node, (_, fallback))| (node, fallback))
+    }
+
+    /// `"<limit> then <fallback>"` per encoded node.
+    pub(crate) fn labels(&self) -> BTreeMap<String, String> {
+        self.quotas
+            .iter()
+            .map(|(node, (limit, fallback))| {
+                (node.encode(), format!("{limit} then {}", fallback.label()))
+            })
+            .collect()
+    }
+
+    /// Apply fallbacks to frontier entries whose quota is used up.
+    ///
+    /// Skipped entries become [`NodeKind::End`] so per-slot bookkeeping keeps
+    /// its indices. Several runs of one node in the same frontier each count
+    /// against its quota. Returns the quotas that ran out, or the first one
+    /// whose fallback is [`QuotaFallback::Fail`] (also returned when routing
+    /// loops between exhausted nodes).
+    pub(crate) fn enforce(
+        &self,
+        frontier: &mut [NodeKind],
+        state: &SchedulerState,
+    ) -> Result<Vec<QuotaHit>, QuotaHit> {
+        let mut scheduled: FxHashMap<NodeKind, u64> = FxHashMap::default();
+        let mut hits = Vec::new();
+        for slot in frontier.iter_mut() {
+            let mut hops = 0;
+            while let Some((limit, fallback)) = self.quotas.get(slot) {
+                let pending = scheduled.entry(slot.clone()).or_default();
+                if state.run_count(slot) + *pending < u64::from(*limit) {
+                    *pending += 1;
+                    break;
+                }
+                let hit = QuotaHit {
+                    node: slot.clone(),
+                    max_runs: *limit,
+                    fallback: fallback.clone(),
+                };
+                hops += 1;
+                match fallback {
+                    QuotaFallback::Fail => return Err(hit),
+                    QuotaFallback::RouteTo(_) if hops > self.quotas.len() => {
+                        return Err(QuotaHit {
+                            fallback: QuotaFallback::Fail,
+                            ..hit
+                        });
+                    }
+                    QuotaFallback::Skip => *slot = NodeKind::End,
+                    QuotaFallback::RouteTo(target) => *slot = target.clone(),
+                }
+                hits.push(hit);
+            }
+        }
+        Ok(hits)
+    }
+}
@@ -0,0 +1,315 @@
+//! Service-level agreements declared on a workflow graph.
+//!
+//! An [`SlaPolicy`] bounds node latency, total run time, and error count. The
+//! runner checks it after every superstep barrier and publishes each breach as
+//! a node event with scope [`SLA_BREACH_SCOPE`], so alerting can subscribe to
+//! the event stream instead of parsing logs.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use serde_json::{Value, json};
+
+use crate::event_bus::{Event, NodeEvent, SLA_BREACH_SCOPE};
+use crate::node::NodeMetrics;
+use crate::types::NodeKind;
+
+/// Limits a workflow run is expected to stay within.
+///
+/// Attach with [`GraphBuilder::with_sla`](crate::graphs::GraphBuilder::with_sla).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use weavegraph::graphs::SlaPolicy;
+/// use weavegraph::types::NodeKind;
+///
+/// let sla = SlaPolicy::new()
+///     .max_node_duration(NodeKind::Custom("search".into()), Duration::from_secs(2))
+///     .max_run_duration(Duration::from_secs(30))
+///     .max_errors(3);
+/// assert_eq!(sla.run_duration_limit(), Some(Duration::from_secs(30)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlaPolicy {
+    node_durations: FxHashMap<NodeKind, Duration>,
+    run_duration: Option<Duration>,
+    errors: Option<usize>,
+}
+
+impl SlaPolicy {
+    /// An empty policy that never reports a breach.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report a breach whenever a single run of `node` takes longer than `limit`.
+    #[must_use]
+    pub fn max_node_duration(mut self, node: NodeKind, limit: Duration) -> Self {
+        self.node_durations.insert(node, limit);
+        self
+    }
+
+    /// Report a breach once the session's run time exceeds `limit`.
+    ///
+    /// Run time is measured from the first superstep a runner executes for
+    /// the session, so time spent before a resume from a checkpoint is not
+    /// counted.
+    #[must_use]
+    pub fn max_run_duration(mut self, limit: Duration) -> Self {
+        self.run_duration = Some(limit);
+        self
+    }
+
+    /// Report a breach once the session's `errors` channel holds more than `limit` events.
+    #[must_use]
+    pub fn max_errors(mut self, limit: usize) -> Self {
+        self.errors = Some(limit);
+        self
+    }
+
+    /// Duration limit for a single run of `node`, if any.
+    #[must_use]
+    pub fn node_duration_limit(&self, node: &NodeKind) -> Option<Duration> {
+        self.node_durations.get(node).copied()
+    }
+
+    /// Total run time limit, if any.
+    #[must_use]
+    pub fn run_duration_limit(&self) -> Option<Duration> {
+        self.run_duration
+    }
+
+    /// Error count limit, if any.
+    #[must_use]
+    pub fn error_limit(&self) -> Option<usize> {
+        self.errors
+    }
+
+    /// Whether the policy declares no limits.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.node_durations.is_empty() && self.run_duration.is_none() && self.errors.is_none()
+    }
+
+    /// Limit labels keyed by metric (and node, for node durations).
+    pub(crate) fn labels(&self) -> BTreeMap<String, String> {
+        let mut labels: BTreeMap<String, String> = self
+            .node_durations
+            .iter()
+            .map(|(node, limit)| (format!("node_duration:{node}"), format!("{limit:?}")))
+            .collect();
+        if let Some(limit) = self.run_duration {
+            labels.insert("run_duration".to_string(), format!("{limit:?}"));
+        }
+        if let Some(limit) = self.errors {
+            labels.insert("error_count".to_string(), limit.to_string());
+        }
+        labels
+    }
+
+    /// Check one completed superstep, returning breaches not yet reported.
+    pub(crate) fn evaluate(
+        &self,
+        tracker: &mut SlaTracker,
+        step: u64,
+        node_metrics: &[(NodeKind, NodeMetrics)],
+        error_count: usize,
+    ) -> Vec<SlaBreach> {
+        let mut breaches = Vec::new();
+        for (node, metrics) in node_metrics {
+            let Some(limit) = self.node_duration_limit(node) else {
+                continue;
+            };
+            if metrics.duration() > limit {
+                breaches.push(SlaBreach {
+                    metric: SlaMetric::NodeDuration,
+                    node: Some(node.clone()),
+                    step,
+                    limit: duration_micros(limit),
+                    observed: metrics.duration_micros,
+                });
+            }
+        }
+        if let Some(limit) = self.run_duration {
+            let elapsed = tracker.started.elapsed();
+            if !tracker.run_duration_reported && elapsed > limit {
+                tracker.run_duration_reported = true;
+                breaches.push(SlaBreach {
+                    metric: SlaMetric::RunDuration,
+                    node: None,
+                    step,
+                    limit: duration_micros(limit),
+                    observed: duration_micros(elapsed),
+                });
+            }
+        }
+        if let Some(limit) = self.errors
+            && !tracker.error_count_reported
+            && error_count > limit
+        {
+            tracker.error_count_reported = true;
+            breaches.push(SlaBreach {
+                metric: SlaMetric::ErrorCount,
+                node: None,
+                step,
+                limit: limit as u64,
+                observed: error_count as u64,
+            });
+        }
+        breaches
+    }
+}
+
+fn duration_micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Per-session bookkeeping so run-level breaches are reported once.
+#[derive(Debug)]
+pub(crate) struct SlaTracker {
+    started: Instant,
+    run_duration_reported: bool,
+    error_count_reported: bool,
+}
+
+impl Default for SlaTracker {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            run_duration_reported: false,
+            error_count_reported: false,
+        }
+    }
+}
+
+// ============================================================================
+// Breaches
+// ============================================================================
+
+/// The measurement an [`SlaBreach`] exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SlaMetric {
+    /// A single node run took longer than its limit; values are microseconds.
+    NodeDuration,
+    /// The session's run time exceeded its limit; values are microseconds.
+    RunDuration,
+    /// The `errors` channel holds more events than allowed; values are counts.
+    ErrorCount,
+}
+
+impl SlaMetric {
+    /// Stable label used in event metadata.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NodeDuration => "node_duration",
+            Self::RunDuration => "run_duration",
+            Self::ErrorCount => "error_count",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "node_duration" => Some(Self::NodeDuration),
+            "run_duration" => Some(Self::RunDuration),
+            "error_count" => Some(Self::ErrorCount),
+            _ => None,
+        }
+    }
+}
+
+/// One SLA limit exceeded during a run.
+///
+/// Published on the event bus as a node event with scope
+/// [`SLA_BREACH_SCOPE`] and `metric`, `limit`, `observed`, and (for node
+/// durations) `node` metadata. Recover the structured form with
+/// [`SlaBreach::from_event`].
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::event_bus::Event;
+/// use weavegraph::graphs::{SlaBreach, SlaMetric};
+///
+/// fn alert(event: &Event) {
+///     if let Some(breach) = SlaBreach::from_event(event)
+///         && breach.metric == SlaMetric::ErrorCount
+///     {
+///         eprintln!("{} errors (limit {})", breach.observed, breach.limit);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SlaBreach {
+    /// The metric that exceeded its limit.
+    pub metric: SlaMetric,
+    /// The offending node, for [`SlaMetric::NodeDuration`].
+    pub node: Option<NodeKind>,
+    /// Superstep whose barrier detected the breach.
+    pub step: u64,
+    /// Declared limit, in the metric's unit.
+    pub limit: u64,
+    /// Observed value, in the metric's unit.
+    pub observed: u64,
+}
+
+impl SlaBreach {
+    /// The event the runner publishes for this breach.
+    #[must_use]
+    pub fn to_event(&self) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert("metric".to_string(), json!(self.metric.label()));
+        metadata.insert("limit".to_string(), json!(self.limit));
+        metadata.insert("observed".to_string(), json!(self.observed));
+        if let Some(node) = &self.node {
+            metadata.insert("node".to_string(), json!(node.encode()));
+        }
+        let subject = match &self.node {
+            Some(node) => format!(" node={node}"),
+            None => String::new(),
+        };
+        let message = format!(
+            "sla breach metric={}{subject} observed={} limit={}",
+            self.metric.label(),
+            self.observed,
+            self.limit
+        );
+        Event::Node(
+            NodeEvent::new(
+                self.node.as_ref().map(|node| format!("{node:?}")),
+                Some(self.step),
+                SLA_BREACH_SCOPE.to_string(),
+                message,
+            )
+            .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a breach published by the runner, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node_event) = event else {
+            return None;
+        };
+        if node_event.scope() != SLA_BREACH_SCOPE {
+            return None;
+        }
+        let metadata = node_event.metadata();
+        Some(Self {
+            metric: SlaMetric::from_label(metadata.get("metric")?.as_str()?)?,
+            node: metadata
+                .get("node")
+                .and_then(Value::as_str)
+                .map(NodeKind::decode),
+            step: node_event.step()?,
+            limit: metadata.get("limit")?.as_u64()?,
+            observed: metadata.get("observed")?.as_u64()?,
+        })
+    }
+}
@@ -0,0 +1,259 @@
+//! Running a compiled [`App`] as a node of another graph.
+//!
+//! A [`SubgraphNode`] runs a child graph to completion inside one superstep
+//! of its parent. The child never sees the parent's full state: a
+//! [`StateScope`] lists the `extra` keys (and optionally the conversation) it
+//! may read, and everything the child writes to `extra` lands under a single
+//! namespace key in the parent when the node's partial is merged. Declare the
+//! mapping with [`GraphBuilder::add_subgraph`](crate::graphs::GraphBuilder::add_subgraph).
+//!
+//! The child runs in its own in-memory session, so its intermediate steps are
+//! not checkpointed; the parent checkpoints the merged result like any other
+//! node output. Child events are forwarded to the parent's event stream.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use serde_json::{Map, Value};
+
+use crate::app::App;
+use crate::channels::Channel;
+use crate::event_bus::{EventBus, INVOCATION_END_SCOPE, STREAM_END_SCOPE};
+use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::runtimes::{AppRunner, CheckpointerType};
+use crate::state::{StateSnapshot, VersionedState};
+
+/// Prefix of framework-owned `extra` keys, which never cross a scope boundary.
+const RESERVED_PREFIX: &str = "__weavegraph";
+
+// ============================================================================
+// Scope
+// ============================================================================
+
+/// What a subgraph may read from its parent and where its writes land.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::StateScope;
+///
+/// let scope = StateScope::new("research")
+///     .read_key("question")
+///     .read_key_as("user_profile", "profile")
+///     .read_messages();
+/// assert_eq!(scope.namespace(), "research");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct StateScope {
+    namespace: String,
+    reads: Vec<(String, String)>,
+    read_messages: bool,
+    write_messages: bool,
+}
+
+impl StateScope {
+    /// A scope whose `extra` writes land under `extra[namespace]` in the parent.
+    ///
+    /// By default the child reads no parent keys, starts with an empty
+    /// conversation, and its new messages are appended to the parent's.
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            reads: Vec::new(),
+            read_messages: false,
+            write_messages: true,
+        }
+    }
+
+    /// Let the child read the parent's `extra[key]` under the same key.
+    pub fn read_key(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        self.read_key_as(key.clone(), key)
+    }
+
+    /// Let the child read the parent's `extra[parent_key]` as `extra[child_key]`.
+    pub fn read_key_as(
+        mut self,
+        parent_key: impl Into<String>,
+        child_key: impl Into<String>,
+    ) -> Self {
+        self.reads.push((parent_key.into(), child_key.into()));
+        self
+    }
+
+    /// Start the child with the parent's conversation.
+    pub fn read_messages(mut self) -> Self {
+        self.read_messages = true;
+        self
+    }
+
+    /// Keep the child's new messages out of the parent's conversation.
+    pub fn discard_messages(mut self) -> Self {
+        self.write_messages = false;
+        self
+    }
+
+    /// Parent `extra` key holding the child's writes.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Build the child's initial state from a parent snapshot.
+    ///
+    /// The child sees its own earlier writes from the namespace object, with
+    /// whitelisted parent keys taking precedence. Reserved keys are dropped.
+    fn child_state(&self, parent: &StateSnapshot) -> VersionedState {
+        let mut state = VersionedState::new_with_messages(if self.read_messages {
+            parent.messages.clone()
+        } else {
+            Vec::new()
+        });
+        let extra = state.extra.get_mut();
+        if let Some(Value::Object(own)) = parent.extra.get(&self.namespace) {
+            for (key, value) in own {
+                if !key.starts_with(RESERVED_PREFIX) {
+                    extra.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        for (parent_key, child_key) in &self.reads {
+            if let Some(value) = parent.extra.get(parent_key) {
+                extra.insert(child_key.clone(), value.clone());
+            }
+        }
+        state
+    }
+
+    /// Translate the child's final state into a partial for the parent.
+    fn merge_back(
+        &self,
+        parent: &StateSnapshot,
+        initial: &VersionedState,
+        finished: &VersionedState,
+    ) -> NodePartial {
+        let before = initial.extra.snapshot();
+        let mut namespaced = match parent.extra.get(&self.namespace) {
+            Some(Value::Object(own)) => own.clone(),
+            _ => Map::new(),
+        };
+        for (key, value) in finished.extra.snapshot() {
+            if key.starts_with(RESERVED_PREFIX) || before.get(&key) == Some(&value) {
+                continue;
+            }
+            namespaced.insert(key, value);
+        }
+        let mut extra = FxHashMap::default();
+        extra.insert(self.namespace.clone(), Value::Object(namespaced));
+        let mut partial = NodePartial::new().with_extra(extra);
+
+        if self.write_messages {
+            let messages = finished.messages.snapshot();
+            let new = messages
+                .get(initial.messages.len()..)
+                .map(<[_]>::to_vec)
+                .unwrap_or_default();
+            if !new.is_empty() {
+                partial = partial.with_messages(new);
+            }
+        }
+        let errors = finished.errors.snapshot();
+        if !errors.is_empty() {
+            partial = partial.with_errors(errors);
+        }
+        partial
+    }
+}
+
+// ============================================================================
+// Node
+// ============================================================================
+
+/// Runs a child [`App`] within a [`StateScope`].
+///
+/// Usually registered through
+/// [`GraphBuilder::add_subgraph`](crate::graphs::GraphBuilder::add_subgraph).
+/// Each run starts a fresh child session named
+/// `<invocation>/<namespace>/<step>`; a child failure fails the node.
+pub struct SubgraphNode {
+    app: Arc<App>,
+    scope: StateScope,
+}
+
+impl SubgraphNode {
+    /// Wrap `app` so it runs with the state view described by `scope`.
+    #[must_use]
+    pub fn new(app: App, scope: StateScope) -> Self {
+        Self {
+            app: Arc::new(app),
+            scope,
+        }
+    }
+
+    /// The scope the child runs in.
+    pub fn scope(&self) -> &StateScope {
+        &self.scope
+    }
+}
+
+#[async_trait]
+impl Node for SubgraphNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let initial = self.scope.child_state(&snapshot);
+        let session_id = format!(
+            "{}/{}/{}",
+            ctx.invocation_id().unwrap_or(&ctx.node_id),
+            self.scope.namespace,
+            ctx.step
+        );
+
+        let mut runner = AppRunner::builder()
+            .app_arc(Arc::clone(&self.app))
+            .checkpointer(CheckpointerType::InMemory)
+            .autosave(false)
+            .event_bus(EventBus::with_sinks(Vec::new()))
+            .build()
+            .await;
+        let mut events = runner
+            .event_stream()
+            .expect("a new runner has not handed out its event stream");
+        let forward = |event: crate::event_bus::Event| {
+            let scope = event.scope_label();
+            if scope != Some(STREAM_END_SCOPE) && scope != Some(INVOCATION_END_SCOPE) {
+                // Forwarding is best effort, like any other node event.
+                let _ = ctx.event_emitter.emit(event);
+            }
+        };
+
+        let run = async {
+            runner
+                .create_session(session_id.clone(), initial.clone())
+                .await?;
+            runner.run_until_complete(&session_id).await
+        };
+        tokio::pin!(run);
+        let result = loop {
+            tokio::select! {
+                biased;
+                result = &mut run => break result,
+                Ok(event) = events.recv() => forward(event),
+            }
+        };
+        while let Ok(event) = events.try_recv() {
+            forward(event);
+        }
+
+        let finished = result.map_err(NodeError::other)?;
+        Ok(self.scope.merge_back(&snapshot, &initial, &finished))
+    }
+
+    fn definition_label(&self) -> &'static str {
+        "weavegraph::subgraph"
+    }
+}
@@ -0,0 +1,504 @@
+//! Ready-made graph assemblies for common workflow shapes.
+//!
+//! Each template wires user-supplied nodes into a [`GraphBuilder`] using the
+//! recommended pattern for that shape. Templates return the builder rather
+//! than a compiled [`App`](crate::app::App), so callers can add extra nodes,
+//! edges, or a [`RuntimeConfig`](crate::runtimes::RuntimeConfig) before
+//! compiling.
+//!
+//! | Template | Shape |
+//! |----------|-------|
+//! | [`LinearPipeline`] | `Start → a → b → … → End` |
+//! | [`SupervisorWorkers`] | supervisor picks a worker via `extra["next"]`; workers report back |
+//! | [`MapReduce`] | a mapper node runs once per item of a collection, then a reducer |
+//! | [`ReflectionLoop`] | generate → critique → revise until approved or out of revisions |
+//!
+//! Nodes revisited by a loop (supervisor, critic) only run again when state has
+//! changed since their last run, so every node in a loop should write state.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::templates::LinearPipeline;
+//! # use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+//! # use weavegraph::state::StateSnapshot;
+//! # struct Step;
+//! # #[async_trait::async_trait]
+//! # impl Node for Step {
+//! #     async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+//! #         Ok(NodePartial::default())
+//! #     }
+//! # }
+//!
+//! let app = LinearPipeline::new()
+//!     .stage("fetch", Step)
+//!     .stage("summarize", Step)
+//!     .into_builder()
+//!     .compile()
+//!     .unwrap();
+//! assert_eq!(app.nodes().len(), 2);
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use serde_json::Value;
+
+use super::builder::GraphBuilder;
+use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::state::StateSnapshot;
+use crate::types::NodeKind;
+
+/// Default `extra` key a supervisor writes to pick the next worker.
+pub const DEFAULT_ROUTE_KEY: &str = "next";
+
+/// Default `extra` key holding the collection a [`MapReduce`] iterates.
+pub const DEFAULT_ITEMS_KEY: &str = "items";
+
+/// Default `extra` key under which each mapper run sees its item.
+pub const DEFAULT_ITEM_KEY: &str = "item";
+
+/// Default `extra` key receiving the per-item mapper outputs.
+pub const DEFAULT_RESULTS_KEY: &str = "map_results";
+
+/// Default `extra` key a critic sets to `true` to accept a draft.
+pub const DEFAULT_APPROVED_KEY: &str = "approved";
+
+// ============================================================================
+// Linear Pipeline
+// ============================================================================
+
+/// Nodes run one after another in registration order.
+#[derive(Default)]
+#[must_use]
+pub struct LinearPipeline {
+    stages: Vec<(NodeKind, Arc<dyn Node>)>,
+}
+
+impl LinearPipeline {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage.
+    pub fn stage(mut self, kind: impl Into<NodeKind>, node: impl Node + 'static) -> Self {
+        self.stages.push((kind.into(), Arc::new(node)));
+        self
+    }
+
+    /// Wire the stages into a builder. An empty pipeline yields an empty builder.
+    pub fn into_builder(self) -> GraphBuilder {
+        let mut builder = GraphBuilder::new();
+        let mut previous = NodeKind::Start;
+        for (kind, node) in self.stages {
+            builder = builder
+                .add_shared_node(kind.clone(), node)
+                .add_edge(previous, kind.clone());
+            previous = kind;
+        }
+        if previous != NodeKind::Start {
+            builder = builder.add_edge(previous, NodeKind::End);
+        }
+        builder
+    }
+}
+
+// ============================================================================
+// Supervisor / Workers
+// ============================================================================
+
+/// A supervisor node delegates to workers, which always report back to it.
+///
+/// After each supervisor run, the value at `extra[route_key]` picks the next
+/// step: a worker name, an array of worker names (run in parallel), or
+/// anything else (absent, `"End"`, unknown) to finish.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::templates::SupervisorWorkers;
+/// # use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+/// # use weavegraph::state::StateSnapshot;
+/// # struct Agent;
+/// # #[async_trait::async_trait]
+/// # impl Node for Agent {
+/// #     async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+/// #         Ok(NodePartial::default())
+/// #     }
+/// # }
+///
+/// let app = SupervisorWorkers::new("supervisor", Agent)
+///     .worker("research", Agent)
+///     .worker("write", Agent)
+///     .into_builder()
+///     .compile()
+///     .unwrap();
+/// assert_eq!(app.conditional_edges().len(), 1);
+/// ```
+#[must_use]
+pub struct SupervisorWorkers {
+    supervisor: (NodeKind, Arc<dyn Node>),
+    workers: Vec<(NodeKind, Arc<dyn Node>)>,
+    route_key: String,
+}
+
+impl SupervisorWorkers {
+    /// Create a template around the supervisor node.
+    pub fn new(kind: impl Into<NodeKind>, supervisor: impl Node + 'static) -> Self {
+        Self {
+            supervisor: (kind.into(), Arc::new(supervisor)),
+            workers: Vec::new(),
+            route_key: DEFAULT_ROUTE_KEY.to_string(),
+        }
+    }
+
+    /// Add a worker the supervisor can route to.
+    pub fn worker(mut self, kind: impl Into<NodeKind>, node: impl Node + 'static) -> Self {
+        self.workers.push((kind.into(), Arc::new(node)));
+        self
+    }
+
+    /// Read the routing decision from a different `extra` key.
+    pub fn route_key(mut self, key: impl Into<String>) -> Self {
+        self.route_key = key.into();
+        self
+    }
+
+    /// Wire the supervisor and workers into a builder.
+    pub fn into_builder(self) -> GraphBuilder {
+        let (supervisor, supervisor_node) = self.supervisor;
+        let workers: Vec<String> = self.workers.iter().map(|(k, _)| k.as_target()).collect();
+        let route_key = self.route_key;
+
+        let mut builder = GraphBuilder::new()
+            .add_shared_node(supervisor.clone(), supervisor_node)
+            .add_edge(NodeKind::Start, supervisor.clone());
+        for (kind, node) in self.workers {
+            builder = builder
+                .add_shared_node(kind.clone(), node)
+                .add_edge(kind, supervisor.clone());
+        }
+        builder.add_conditional_edge(
+            supervisor,
+            Arc::new(move |snapshot| {
+                let chosen: Vec<String> = match snapshot.extra.get(&route_key) {
+                    Some(Value::String(name)) => vec![name.clone()],
+                    Some(Value::Array(names)) => names
+                        .iter()
+                        .filter_map(|n| n.as_str().map(str::to_string))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let targets: Vec<String> = chosen
+                    .into_iter()
+                    .filter(|name| workers.contains(name))
+                    .collect();
+                if targets.is_empty() {
+                    vec![NodeKind::end_target()]
+                } else {
+                    targets
+                }
+            }),
+        )
+    }
+}
+
+// ============================================================================
+// Map / Reduce
+// ============================================================================
+
+/// Runs a mapper node once per item of a collection, then a reducer node.
+///
+/// The map stage reads the array at `extra[items_key]` and runs the mapper
+/// concurrently for each element, with the element placed at
+/// `extra[item_key]` of the mapper's snapshot. Mapper messages and errors are
+/// appended in item order; each mapper's `extra` output becomes one element of
+/// the array stored at `extra[results_key]` (`null` when the mapper wrote no
+/// extra). The reducer then runs on the combined state.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::templates::MapReduce;
+/// # use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+/// # use weavegraph::state::StateSnapshot;
+/// # struct Step;
+/// # #[async_trait::async_trait]
+/// # impl Node for Step {
+/// #     async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+/// #         Ok(NodePartial::default())
+/// #     }
+/// # }
+///
+/// let app = MapReduce::new(Step, Step)
+///     .items_key("documents")
+///     .concurrency(4)
+///     .into_builder()
+///     .compile()
+///     .unwrap();
+/// assert_eq!(app.nodes().len(), 2);
+/// ```
+#[must_use]
+pub struct MapReduce {
+    map_kind: NodeKind,
+    reduce_kind: NodeKind,
+    mapper: Arc<dyn Node>,
+    reducer: Arc<dyn Node>,
+    config: MapConfig,
+}
+
+#[derive(Clone)]
+struct MapConfig {
+    items_key: String,
+    item_key: String,
+    results_key: String,
+    concurrency: usize,
+}
+
+impl MapReduce {
+    /// Create a template with the default node names `map` and `reduce`.
+    pub fn new(mapper: impl Node + 'static, reducer: impl Node + 'static) -> Self {
+        Self {
+            map_kind: NodeKind::Custom("map".into()),
+            reduce_kind: NodeKind::Custom("reduce".into()),
+            mapper: Arc::new(mapper),
+            reducer: Arc::new(reducer),
+            config: MapConfig {
+                items_key: DEFAULT_ITEMS_KEY.to_string(),
+                item_key: DEFAULT_ITEM_KEY.to_string(),
+                results_key: DEFAULT_RESULTS_KEY.to_string(),
+                concurrency: 8,
+            },
+        }
+    }
+
+    /// Rename the map and reduce nodes.
+    pub fn node_names(mut self, map: impl Into<NodeKind>, reduce: impl Into<NodeKind>) -> Self {
+        self.map_kind = map.into();
+        self.reduce_kind = reduce.into();
+        self
+    }
+
+    /// `extra` key holding the input collection.
+    pub fn items_key(mut self, key: impl Into<String>) -> Self {
+        self.config.items_key = key.into();
+        self
+    }
+
+    /// `extra` key under which each mapper run sees its item.
+    pub fn item_key(mut self, key: impl Into<String>) -> Self {
+        self.config.item_key = key.into();
+        self
+    }
+
+    /// `extra` key receiving the mapper outputs.
+    pub fn results_key(mut self, key: impl Into<String>) -> Self {
+        self.config.results_key = key.into();
+        self
+    }
+
+    /// Maximum number of mapper runs in flight (at least 1).
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.config.concurrency = limit.max(1);
+        self
+    }
+
+    /// Wire the map and reduce stages into a builder.
+    pub fn into_builder(self) -> GraphBuilder {
+        let map_node = MapNode {
+            mapper: self.mapper,
+            config: self.config,
+        };
+        GraphBuilder::new()
+            .add_node(self.map_kind.clone(), map_node)
+            .add_shared_node(self.reduce_kind.clone(), self.reducer)
+            .add_edge(NodeKind::Start, self.map_kind.clone())
+            .add_edge(self.map_kind, self.reduce_kind.clone())
+            .add_edge(self.reduce_kind, NodeKind::End)
+    }
+}
+
+struct MapNode {
+    mapper: Arc<dyn Node>,
+    config: MapConfig,
+}
+
+#[async_trait]
+impl Node for MapNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let items = match snapshot.extra.get(&self.config.items_key) {
+            Some(Value::Array(items)) => items.clone(),
+            Some(_) => {
+                return Err(NodeError::ValidationFailed(format!(
+                    "map-reduce items at extra[{:?}] must be an array",
+                    self.config.items_key
+                )));
+            }
+            None => {
+                return Err(NodeError::MissingInput {
+                    what: "map-reduce items collection",
+                });
+            }
+        };
+
+        let partials: Vec<NodePartial> = stream::iter(items)
+            .map(|item| {
+                let mut item_snapshot = snapshot.clone();
+                item_snapshot
+                    .extra
+                    .insert(self.config.item_key.clone(), item);
+                self.mapper.run(item_snapshot, ctx.clone())
+            })
+            .buffered(self.config.concurrency)
+            .try_collect()
+            .await?;
+
+        let mut messages = Vec::new();
+        let mut errors = Vec::new();
+        let mut results = Vec::with_capacity(partials.len());
+        for partial in partials {
+            messages.extend(partial.messages.unwrap_or_default());
+            errors.extend(partial.errors.unwrap_or_default());
+            results.push(partial.extra.map_or(Value::Null, |extra| {
+                Value::Object(extra.into_iter().collect())
+            }));
+        }
+
+        let mut extra = crate::utils::collections::new_extra_map();
+        extra.insert(self.config.results_key.clone(), Value::Array(results));
+        let mut partial = NodePartial::new().with_extra(extra);
+        if !messages.is_empty() {
+            partial = partial.with_messages(messages);
+        }
+        if !errors.is_empty() {
+            partial = partial.with_errors(errors);
+        }
+        Ok(partial)
+    }
+}
+
+// ============================================================================
+// Reflection Loop
+// ============================================================================
+
+/// Generate a draft, critique it, and revise until approved.
+///
+/// The critic sets `extra[approved_key]` to `true` to accept the current
+/// draft. Otherwise the reviser runs and the critic looks again, up to
+/// `max_revisions` revisions, after which the loop ends regardless.
+/// Revisions are counted from the session's step number, so the loop is
+/// meant to be the whole graph of a fresh invocation.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::graphs::templates::ReflectionLoop;
+/// # use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+/// # use weavegraph::state::StateSnapshot;
+/// # struct Step;
+/// # #[async_trait::async_trait]
+/// # impl Node for Step {
+/// #     async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+/// #         Ok(NodePartial::default())
+/// #     }
+/// # }
+///
+/// let app = ReflectionLoop::new(Step, Step, Step)
+///     .max_revisions(2)
+///     .into_builder()
+///     .compile()
+///     .unwrap();
+/// assert_eq!(app.nodes().len(), 3);
+/// ```
+#[must_use]
+pub struct ReflectionLoop {
+    generate: (NodeKind, Arc<dyn Node>),
+    critique: (NodeKind, Arc<dyn Node>),
+    revise: (NodeKind, Arc<dyn Node>),
+    approved_key: String,
+    max_revisions: u64,
+}
+
+impl ReflectionLoop {
+    /// Create a loop with the default node names `generate`, `critique`, and `revise`.
+    pub fn new(
+        generator: impl Node + 'static,
+        critic: impl Node + 'static,
+        reviser: impl Node + 'static,
+    ) -> Self {
+        Self {
+            generate: (NodeKind::Custom("generate".into()), Arc::new(generator)),
+            critique: (NodeKind::Custom("critique".into()), Arc::new(critic)),
+            revise: (NodeKind::Custom("revise".into()), Arc::new(reviser)),
+            approved_key: DEFAULT_APPROVED_KEY.to_string(),
+            max_revisions: 3,
+        }
+    }
+
+    /// Rename the three nodes.
+    pub fn node_names(
+        mut self,
+        generate: impl Into<NodeKind>,
+        critique: impl Into<NodeKind>,
+        revise: impl Into<NodeKind>,
+    ) -> Self {
+        self.generate.0 = generate.into();
+        self.critique.0 = critique.into();
+        self.revise.0 = revise.into();
+        self
+    }
+
+    /// `extra` key the critic sets to `true` to accept the draft.
+    pub fn approved_key(mut self, key: impl Into<String>) -> Self {
+        self.approved_key = key.into();
+        self
+    }
+
+    /// Maximum number of revisions before the loop ends.
+    pub fn max_revisions(mut self, max: u64) -> Self {
+        self.max_revisions = max;
+        self
+    }
+
+    /// Wire the loop into a builder.
+    pub fn into_builder(self) -> GraphBuilder {
+        let (generate, generator) = self.generate;
+        let (critique, critic) = self.critique;
+        let (revise, reviser) = self.revise;
+        let approved_key = self.approved_key;
+        let max_revisions = self.max_revisions;
+        let revise_target = revise.as_target();
+
+        GraphBuilder::new()
+            .add_shared_node(generate.clone(), generator)
+            .add_shared_node(critique.clone(), critic)
+            .add_shared_node(revise.clone(), reviser)
+            .add_edge(NodeKind::Start, generate.clone())
+            .add_edge(generate, critique.clone())
+            .add_edge(revise, critique.clone())
+            .add_conditional_edge_with_context(
+                critique,
+                Arc::new(move |ctx| {
+                    let approved = ctx
+                        .snapshot()
+                        .extra
+                        .get(&approved_key)
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    // Steps run generate, critique, then (revise, critique) pairs.
+                    let revisions = ctx.step().saturating_sub(2) / 2;
+                    if approved || revisions >= max_revisions {
+                        vec![NodeKind::end_target()]
+                    } else {
+                        vec![revise_target.clone()]
+                    }
+                }),
+            )
+    }
+}
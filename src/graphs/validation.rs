@@ -0,0 +1,466 @@
+//! Validators that inspect node outputs before they reach the barrier.
+//!
+//! An [`OutputValidator`] registered for a [`NodeKind`] through
+//! [`GraphBuilder::with_output_validator`](crate::graphs::GraphBuilder::with_output_validator)
+//! sees every [`NodePartial`] that node produces, including partials yielded
+//! mid-run, before any reducer touches state. Violations are handled according
+//! to the graph's [`OutputValidationPolicy`]: recorded as node-scoped
+//! [`ErrorEvent`]s tagged [`OUTPUT_VALIDATION_TAG`], used to reject the output,
+//! or turned into an [`OutputValidationError`] that fails the step.
+//!
+//! Built-in validators cover message count limits ([`MaxMessages`]), required
+//! `extra` keys ([`RequiredExtraKeys`]) and JSON type constraints on `extra`
+//! values ([`ExtraValueTypes`]). Any
+//! `Fn(&NodeKind, &NodePartial) -> Vec<OutputViolation>` closure is a validator
+//! too.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::{JsonType, MaxMessages, OutputValidator, ExtraValueTypes};
+//! use weavegraph::node::NodePartial;
+//! use weavegraph::types::NodeKind;
+//!
+//! let kind = NodeKind::Custom("summarize".into());
+//! let partial = NodePartial::new().with_extra(
+//!     [("score".to_string(), serde_json::json!("high"))].into_iter().collect(),
+//! );
+//!
+//! assert!(MaxMessages::new(3).validate(&kind, &partial).is_empty());
+//! let violations = ExtraValueTypes::new()
+//!     .require("score", JsonType::Number)
+//!     .validate(&kind, &partial);
+//! assert_eq!(violations.len(), 1);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::channels::errors::{ErrorEvent, ErrorSeverity, WeaveError};
+use crate::node::NodePartial;
+use crate::types::NodeKind;
+
+/// Tag attached to error events produced from output violations.
+pub const OUTPUT_VALIDATION_TAG: &str = "output_validation";
+
+// ============================================================================
+// Violations and policy
+// ============================================================================
+
+/// One problem an [`OutputValidator`] found in a node's output.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct OutputViolation {
+    /// Name of the validator that reported the violation.
+    pub validator: String,
+    /// Human-readable description.
+    pub message: String,
+    /// Structured details, `Null` when there are none.
+    pub details: Value,
+}
+
+impl OutputViolation {
+    /// Create a violation without details.
+    #[must_use]
+    pub fn new(validator: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            validator: validator.into(),
+            message: message.into(),
+            details: Value::Null,
+        }
+    }
+
+    /// Attach structured details.
+    #[must_use]
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Convert into a node-scoped [`ErrorSeverity::Warning`] event for `node` at `step`.
+    #[must_use]
+    pub fn to_error_event(&self, node: &NodeKind, step: u64) -> ErrorEvent {
+        let error = WeaveError::msg(self.message.clone()).with_details(self.details.clone());
+        ErrorEvent::node(node.to_string(), step, error)
+            .with_tag(OUTPUT_VALIDATION_TAG)
+            .with_context(json!({ "validator": self.validator }))
+            .with_severity(ErrorSeverity::Warning)
+    }
+}
+
+impl fmt::Display for OutputViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.validator, self.message)
+    }
+}
+
+/// What the runner does with a node output that has violations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OutputValidationPolicy {
+    /// Fail the step with an [`OutputValidationError`].
+    #[default]
+    Fail,
+    /// Apply the output unchanged and append one error event per violation.
+    Record,
+    /// Drop the output's messages, `extra` writes, frontier command and node
+    /// commands, keep its own error events, and append one error event per
+    /// violation.
+    Reject,
+}
+
+impl OutputValidationPolicy {
+    /// Stable label used in introspection and logs.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Fail => "fail",
+            Self::Record => "record",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// A node output failed validation under [`OutputValidationPolicy::Fail`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[error("output of node {node} at step {step} failed validation: {}", summary(.violations))]
+#[cfg_attr(
+    feature = "diagnostics",
+    diagnostic(
+        code(weavegraph::graphs::output_validation),
+        help(
+            "Fix the node's output, or register the validators with OutputValidationPolicy::Record or Reject to keep running."
+        )
+    )
+)]
+#[non_exhaustive]
+pub struct OutputValidationError {
+    /// Node whose output was rejected.
+    pub node: NodeKind,
+    /// Step the node ran in.
+    pub step: u64,
+    /// Every violation reported for the output.
+    pub violations: Vec<OutputViolation>,
+}
+
+fn summary(violations: &[OutputViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// ============================================================================
+// Validator trait
+// ============================================================================
+
+/// Inspects a node's output before the barrier applies it.
+///
+/// Return an empty vector when the output is acceptable.
+pub trait OutputValidator: Send + Sync {
+    /// Check `partial`, produced by `node`.
+    fn validate(&self, node: &NodeKind, partial: &NodePartial) -> Vec<OutputViolation>;
+
+    /// Label used in introspection; defaults to the implementing type's name.
+    fn label(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+}
+
+impl<F> OutputValidator for F
+where
+    F: Fn(&NodeKind, &NodePartial) -> Vec<OutputViolation> + Send + Sync,
+{
+    fn validate(&self, node: &NodeKind, partial: &NodePartial) -> Vec<OutputViolation> {
+        self(node, partial)
+    }
+
+    fn label(&self) -> String {
+        "closure".to_string()
+    }
+}
+
+// ============================================================================
+// Built-in validators
+// ============================================================================
+
+/// Limits the number of messages one output may add.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MaxMessages {
+    /// Largest accepted message count.
+    pub limit: usize,
+}
+
+impl MaxMessages {
+    /// Accept at most `limit` messages per output.
+    #[must_use]
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+}
+
+impl OutputValidator for MaxMessages {
+    fn validate(&self, _node: &NodeKind, partial: &NodePartial) -> Vec<OutputViolation> {
+        let count = partial.messages.as_ref().map_or(0, Vec::len);
+        if count <= self.limit {
+            return Vec::new();
+        }
+        vec![
+            OutputViolation::new(
+                "max_messages",
+                format!("{count} messages exceed the limit of {}", self.limit),
+            )
+            .with_details(json!({ "count": count, "limit": self.limit })),
+        ]
+    }
+
+    fn label(&self) -> String {
+        format!("max_messages({})", self.limit)
+    }
+}
+
+/// Requires every output to write the listed `extra` keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequiredExtraKeys {
+    /// Keys that must be present.
+    pub keys: Vec<String>,
+}
+
+impl RequiredExtraKeys {
+    /// Require each of `keys`.
+    #[must_use]
+    pub fn new<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            keys: keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl OutputValidator for RequiredExtraKeys {
+    fn validate(&self, _node: &NodeKind, partial: &NodePartial) -> Vec<OutputViolation> {
+        self.keys
+            .iter()
+            .filter(|key| {
+                !partial
+                    .extra
+                    .as_ref()
+                    .is_some_and(|extra| extra.contains_key(key.as_str()))
+            })
+            .map(|key| {
+                OutputViolation::new("required_extra_keys", format!("missing extra key {key:?}"))
+                    .with_details(json!({ "key": key }))
+            })
+            .collect()
+    }
+
+    fn label(&self) -> String {
+        format!("required_extra_keys({})", self.keys.join(","))
+    }
+}
+
+/// JSON value type accepted by [`ExtraValueTypes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonType {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool,
+    /// Any number.
+    Number,
+    /// A string.
+    String,
+    /// An array.
+    Array,
+    /// An object.
+    Object,
+}
+
+impl JsonType {
+    /// Type of `value`.
+    #[must_use]
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Number(_) => Self::Number,
+            Value::String(_) => Self::String,
+            Value::Array(_) => Self::Array,
+            Value::Object(_) => Self::Object,
+        }
+    }
+
+    /// Lowercase JSON name of the type.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool => "boolean",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
+
+/// Constrains the JSON type of `extra` values when they are written.
+///
+/// Keys the output does not write are not checked; combine with
+/// [`RequiredExtraKeys`] to make them mandatory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[must_use]
+pub struct ExtraValueTypes {
+    /// Expected type per key, in registration order.
+    pub constraints: Vec<(String, JsonType)>,
+}
+
+impl ExtraValueTypes {
+    /// Start with no constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require values written to `key` to be of type `ty`.
+    pub fn require(mut self, key: impl Into<String>, ty: JsonType) -> Self {
+        self.constraints.push((key.into(), ty));
+        self
+    }
+}
+
+impl OutputValidator for ExtraValueTypes {
+    fn validate(&self, _node: &NodeKind, partial: &NodePartial) -> Vec<OutputViolation> {
+        let Some(extra) = &partial.extra else {
+            return Vec::new();
+        };
+        self.constraints
+            .iter()
+            .filter_map(|(key, expected)| {
+                let actual = JsonType::of(extra.get(key)?);
+                (actual != *expected).then(|| {
+                    OutputViolation::new(
+                        "extra_value_types",
+                        format!(
+                            "extra key {key:?} is {}, expected {}",
+                            actual.as_str(),
+                            expected.as_str()
+                        ),
+                    )
+                    .with_details(json!({
+                        "key": key,
+                        "expected": expected.as_str(),
+                        "actual": actual.as_str(),
+                    }))
+                })
+            })
+            .collect()
+    }
+
+    fn label(&self) -> String {
+        let keys: Vec<String> = self
+            .constraints
+            .iter()
+            .map(|(key, ty)| format!("{key}:{}", ty.as_str()))
+            .collect();
+        format!("extra_value_types({})", keys.join(","))
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Validators registered per node kind, plus the policy applied to violations.
+#[derive(Clone, Default)]
+pub(crate) struct OutputValidators {
+    validators: FxHashMap<NodeKind, Vec<Arc<dyn OutputValidator>>>,
+    policy: OutputValidationPolicy,
+}
+
+impl OutputValidators {
+    pub(crate) fn register(&mut self, node: NodeKind, validator: Arc<dyn OutputValidator>) {
+        self.validators.entry(node).or_default().push(validator);
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: OutputValidationPolicy) {
+        self.policy = policy;
+    }
+
+    pub(crate) fn policy(&self) -> OutputValidationPolicy {
+        self.policy
+    }
+
+    /// Validator labels per node, keyed by the node's display name.
+    pub(crate) fn labels(&self) -> BTreeMap<String, Vec<String>> {
+        self.validators
+            .iter()
+            .map(|(node, validators)| {
+                let labels = validators.iter().map(|v| v.label()).collect();
+                (node.to_string(), labels)
+            })
+            .collect()
+    }
+
+    /// Run `node`'s validators on `partial` and apply the policy in place.
+    pub(crate) fn check(
+        &self,
+        node: &NodeKind,
+        step: u64,
+        partial: &mut NodePartial,
+    ) -> Result<(), OutputValidationError> {
+        let Some(validators) = self.validators.get(node) else {
+            return Ok(());
+        };
+        let violations: Vec<OutputViolation> = validators
+            .iter()
+            .flat_map(|validator| validator.validate(node, partial))
+            .collect();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        tracing::warn!(
+            target: "weavegraph::graphs",
+            node = %node,
+            step,
+            violations = violations.len(),
+            policy = self.policy.label(),
+            "node output failed validation"
+        );
+        match self.policy {
+            OutputValidationPolicy::Fail => {
+                return Err(OutputValidationError {
+                    node: node.clone(),
+                    step,
+                    violations,
+                });
+            }
+            OutputValidationPolicy::Reject => {
+                partial.messages = None;
+                partial.extra = None;
+                partial.frontier = None;
+                partial.commands = None;
+            }
+            OutputValidationPolicy::Record => {}
+        }
+        partial.errors.get_or_insert_with(Vec::new).extend(
+            violations
+                .iter()
+                .map(|violation| violation.to_error_event(node, step)),
+        );
+        Ok(())
+    }
+}
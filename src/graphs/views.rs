@@ -0,0 +1,209 @@
+//! Per-node windows over the conversation in a [`StateSnapshot`].
+//!
+//! By default every node receives a clone of the full snapshot. For sessions
+//! with very long histories most nodes only need the recent tail, so a
+//! [`SnapshotView`] declared for a node makes the scheduler clone just the
+//! messages the node will read: the last N, only some roles, or as many of the
+//! newest messages as fit a token budget. `extra`, `errors` and all channel
+//! versions are passed through unchanged, and writes are unaffected: new
+//! messages are still appended to the full conversation.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+use crate::message::{Message, Role};
+use crate::state::StateSnapshot;
+use crate::types::NodeKind;
+
+/// Approximate tokens per message for [`SnapshotView::max_tokens`].
+///
+/// Uses the common four-characters-per-token heuristic, so budgets are
+/// estimates rather than exact tokenizer counts.
+#[must_use]
+pub fn estimate_tokens(message: &Message) -> usize {
+    message.content.chars().count().div_ceil(4)
+}
+
+// ============================================================================
+// View
+// ============================================================================
+
+/// Which messages of the conversation a node sees.
+///
+/// Filters apply in order: roles first, then the message count, then the
+/// token budget, always keeping the newest messages in conversation order.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::channels::Channel;
+/// use weavegraph::graphs::SnapshotView;
+/// use weavegraph::message::{Message, Role};
+/// use weavegraph::state::VersionedState;
+///
+/// let mut state = VersionedState::new_with_user_message("first");
+/// state.messages.get_mut().push(Message::assistant("second"));
+/// state.messages.get_mut().push(Message::user("third"));
+///
+/// let view = SnapshotView::new().roles([Role::User]).last_messages(1);
+/// let windowed = view.apply(&state.snapshot());
+/// assert_eq!(windowed.messages, vec![Message::user("third")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct SnapshotView {
+    last_messages: Option<usize>,
+    roles: Option<Vec<Role>>,
+    max_tokens: Option<usize>,
+}
+
+impl SnapshotView {
+    /// A view that passes every message through.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most the `count` newest messages.
+    pub fn last_messages(mut self, count: usize) -> Self {
+        self.last_messages = Some(count);
+        self
+    }
+
+    /// Keep only messages whose role is one of `roles`.
+    pub fn roles(mut self, roles: impl IntoIterator<Item = Role>) -> Self {
+        self.roles = Some(roles.into_iter().collect());
+        self
+    }
+
+    /// Keep the newest messages whose combined [`estimate_tokens`] fits `budget`.
+    ///
+    /// A message that would overflow the budget ends the window, so older
+    /// messages never appear without the newer ones.
+    pub fn max_tokens(mut self, budget: usize) -> Self {
+        self.max_tokens = Some(budget);
+        self
+    }
+
+    /// Copy of `snapshot` holding only the messages this view selects.
+    ///
+    /// Only the selected messages are cloned.
+    #[must_use]
+    pub fn apply(&self, snapshot: &StateSnapshot) -> StateSnapshot {
+        let mut selected: Vec<&Message> = Vec::new();
+        let mut tokens = 0usize;
+        for message in snapshot.messages.iter().rev() {
+            if self
+                .roles
+                .as_ref()
+                .is_some_and(|roles| !roles.contains(&message.role))
+            {
+                continue;
+            }
+            if self
+                .last_messages
+                .is_some_and(|limit| selected.len() >= limit)
+            {
+                break;
+            }
+            if let Some(budget) = self.max_tokens {
+                tokens = tokens.saturating_add(estimate_tokens(message));
+                if tokens > budget {
+                    break;
+                }
+            }
+            selected.push(message);
+        }
+
+        StateSnapshot {
+            messages: selected.into_iter().rev().cloned().collect(),
+            messages_version: snapshot.messages_version,
+            extra: snapshot.extra.clone(),
+            extra_version: snapshot.extra_version,
+            errors: snapshot.errors.clone(),
+            errors_version: snapshot.errors_version,
+            blobs: snapshot.blobs.clone(),
+            blobs_version: snapshot.blobs_version,
+        }
+    }
+
+    /// Stable label used in introspection, e.g. `roles=user,assistant last=20 tokens=4000`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(roles) = &self.roles {
+            let roles: Vec<&str> = roles.iter().map(Role::as_str).collect();
+            parts.push(format!("roles={}", roles.join(",")));
+        }
+        if let Some(count) = self.last_messages {
+            parts.push(format!("last={count}"));
+        }
+        if let Some(budget) = self.max_tokens {
+            parts.push(format!("tokens={budget}"));
+        }
+        if parts.is_empty() {
+            "all".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Snapshot views keyed by node.
+///
+/// Declared per node with
+/// [`GraphBuilder::add_node_with_view`](crate::graphs::GraphBuilder::add_node_with_view)
+/// or [`GraphBuilder::with_snapshot_view`](crate::graphs::GraphBuilder::with_snapshot_view).
+/// Cheap to clone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotViews {
+    views: Arc<FxHashMap<NodeKind, SnapshotView>>,
+}
+
+impl SnapshotViews {
+    /// No views; every node sees the full snapshot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give `node` the snapshot selected by `view`, replacing any earlier view.
+    #[must_use]
+    pub fn with_view(mut self, node: NodeKind, view: SnapshotView) -> Self {
+        Arc::make_mut(&mut self.views).insert(node, view);
+        self
+    }
+
+    /// View declared for `node`, if any.
+    #[must_use]
+    pub fn get(&self, node: &NodeKind) -> Option<&SnapshotView> {
+        self.views.get(node)
+    }
+
+    /// Whether no views are declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// The snapshot `node` receives: windowed if it has a view, else a full clone.
+    pub(crate) fn snapshot_for(&self, node: &NodeKind, snapshot: &StateSnapshot) -> StateSnapshot {
+        match self.views.get(node) {
+            Some(view) => view.apply(snapshot),
+            None => snapshot.clone(),
+        }
+    }
+
+    /// View label per encoded node.
+    pub(crate) fn labels(&self) -> BTreeMap<String, String> {
+        self.views
+            .iter()
+            .map(|(node, view)| (node.encode(), view.label()))
+            .collect()
+    }
+}
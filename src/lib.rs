@@ -70,6 +70,13 @@
 //! | `diagnostics` | no | Adds `miette` diagnostic metadata to error types. |
 //! | `examples` | no | Pulls additional deps used by selected examples. |
 //! | `petgraph-compat` | no | Exposes petgraph conversion helpers for graph analysis and visualization. |
+//! | `schema` | no | Derives JSON Schemas for persisted models and events (`weavegraph::schema`). |
+//! | `msgpack` | no | MessagePack [`EventFormat`](event_bus::EventFormat) for [`EncodedSink`](event_bus::EncodedSink). |
+//! | `cbor` | no | CBOR [`EventFormat`](event_bus::EventFormat) for [`EncodedSink`](event_bus::EncodedSink). |
+//! | `encryption` | no | Per-tenant envelope encryption (`weavegraph::encryption`) and `EncryptedSink` for event sinks. |
+//! | `kafka` | no | `KafkaPublisher` for publishing events to Kafka through a [`BrokerSink`](event_bus::BrokerSink). |
+//! | `nats` | no | `NatsPublisher` for publishing events to NATS through a [`BrokerSink`](event_bus::BrokerSink). |
+//! | `macros` | no | The [`#[node]`](node::node) attribute for writing nodes as async functions. |
 //!
 //! # Documentation
 //!
@@ -146,17 +153,35 @@
 pub mod app;
 pub mod channels;
 pub mod control;
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+pub mod encryption;
+pub mod errors;
 pub mod event_bus;
+pub mod feature_flags;
 pub mod graphs;
 pub mod llm;
 pub mod message;
 pub mod node;
+pub mod redaction;
 pub mod reducers;
 pub mod runtimes;
 pub mod schedulers;
+#[cfg(feature = "schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+pub mod schema;
 pub mod state;
 pub mod telemetry;
+pub mod testing;
 pub mod types;
 pub mod utils;
 
-pub use control::{FrontierCommand, NodeRoute};
+pub use control::{FrontierCommand, NodeCommand, NodeRoute};
+
+/// Paths used by code generated from `weavegraph-macros`; not public API.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait::async_trait;
+    pub use tracing;
+}
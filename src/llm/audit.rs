@@ -0,0 +1,209 @@
+//! Prompt/response audit log for LLM-calling nodes.
+//!
+//! With [`RuntimeConfig::with_llm_audit`](crate::runtimes::RuntimeConfig::with_llm_audit)
+//! enabled, every model call a node reports through
+//! [`NodeContext::record_llm_call`](crate::node::NodeContext::record_llm_call)
+//! (or makes through [`NodeContext::chat_audited`](crate::node::NodeContext::chat_audited))
+//! is stored as an [`LlmAuditRecord`]: the rendered prompt, model
+//! parameters, response, token usage, and latency. Records are persisted with
+//! [`Checkpointer::save_llm_audit`](crate::runtimes::Checkpointer::save_llm_audit)
+//! after each superstep, including failed and re-executed ones, and carry the
+//! `(session_id, step)` of the checkpoint step row they belong to, so
+//! compliance review and offline evaluation can line a model's behavior up
+//! with the state it saw.
+//!
+//! The runtime's redaction policy is applied before records are saved, with
+//! paths rooted at `llm_audit` (for example `llm_audit.call.prompt.0.content`).
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use serde_json::json;
+//! use weavegraph::llm::{LlmCall, LlmResponse};
+//! use weavegraph::message::Message;
+//! use weavegraph::node::TokenUsage;
+//!
+//! let response = LlmResponse {
+//!     content: "Paris".into(),
+//!     metadata: json!({ "usage": { "input_tokens": 12, "output_tokens": 1 } }),
+//! };
+//! let call = LlmCall::new(vec![Message::user("Capital of France?")])
+//!     .with_parameters(json!({ "model": "small", "temperature": 0.0 }))
+//!     .with_response(&response)
+//!     .with_latency(Duration::from_millis(420));
+//!
+//! assert_eq!(call.response.as_deref(), Some("Paris"));
+//! assert_eq!(call.token_usage, Some(TokenUsage::new(12, 1)));
+//! ```
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::llm::LlmResponse;
+use crate::message::Message;
+use crate::node::TokenUsage;
+
+/// Format version written to [`LlmAuditRecord::format_version`].
+pub const LLM_AUDIT_FORMAT_VERSION: u32 = 1;
+
+/// One model call as reported by a node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LlmCall {
+    /// Messages sent to the model, after all templating.
+    pub prompt: Vec<Message>,
+    /// Model name, sampling settings, and other request parameters.
+    #[serde(default)]
+    pub parameters: Value,
+    /// Text the model returned, or `None` if the call failed.
+    #[serde(default)]
+    pub response: Option<String>,
+    /// Provider metadata returned with the response.
+    #[serde(default)]
+    pub response_metadata: Value,
+    /// Error message of a failed call.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Tokens consumed, when the provider reports them.
+    #[serde(default)]
+    pub token_usage: Option<TokenUsage>,
+    /// Wall-clock time of the call in milliseconds.
+    #[serde(default)]
+    pub latency_ms: u64,
+}
+
+impl LlmCall {
+    /// A call sending `prompt`.
+    #[must_use]
+    pub fn new(prompt: Vec<Message>) -> Self {
+        Self {
+            prompt,
+            ..Self::default()
+        }
+    }
+
+    /// Record the request parameters.
+    #[must_use]
+    pub fn with_parameters(mut self, parameters: Value) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Record a successful response.
+    ///
+    /// Token usage is taken from a `usage` object with `input_tokens` and
+    /// `output_tokens` in the response metadata, if present; use
+    /// [`with_token_usage`](Self::with_token_usage) for other layouts.
+    #[must_use]
+    pub fn with_response(mut self, response: &LlmResponse) -> Self {
+        self.response = Some(response.content.clone());
+        self.response_metadata = response.metadata.clone();
+        if let Some(usage) = response
+            .metadata
+            .get("usage")
+            .and_then(|usage| serde_json::from_value(usage.clone()).ok())
+        {
+            self.token_usage = Some(usage);
+        }
+        self
+    }
+
+    /// Record a failed call.
+    #[must_use]
+    pub fn with_error(mut self, error: impl std::fmt::Display) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+
+    /// Record the tokens the call consumed.
+    #[must_use]
+    pub fn with_token_usage(mut self, usage: TokenUsage) -> Self {
+        self.token_usage = Some(usage);
+        self
+    }
+
+    /// Record how long the call took.
+    #[must_use]
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+}
+
+/// A model call made during one node run, as persisted in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LlmAuditRecord {
+    /// Record layout version; see [`LLM_AUDIT_FORMAT_VERSION`].
+    pub format_version: u32,
+    /// Session the call was made in.
+    pub session_id: String,
+    /// Superstep of the node run; matches the checkpoint step row.
+    pub step: u64,
+    /// Execution attempt of the step; see
+    /// [`NodeContext::attempt`](crate::node::NodeContext::attempt).
+    pub attempt: u32,
+    /// Position of the call among all calls recorded in this step attempt.
+    pub index: u32,
+    /// Id of the node that made the call.
+    pub node: String,
+    /// The call itself.
+    pub call: LlmCall,
+    /// When the call was recorded.
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl LlmAuditRecord {
+    /// A record of `call`, timestamped now, with `index` 0.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        step: u64,
+        attempt: u32,
+        node: impl Into<String>,
+        call: LlmCall,
+    ) -> Self {
+        Self {
+            format_version: LLM_AUDIT_FORMAT_VERSION,
+            session_id: session_id.into(),
+            step,
+            attempt,
+            index: 0,
+            node: node.into(),
+            call,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// Set the record's position within its step attempt.
+    #[must_use]
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+}
+
+/// Records collected from the nodes of one superstep.
+#[derive(Debug, Default)]
+pub(crate) struct LlmAuditLog {
+    records: Mutex<Vec<LlmAuditRecord>>,
+}
+
+impl LlmAuditLog {
+    /// Append `record`, numbering it in arrival order.
+    pub(crate) fn push(&self, record: LlmAuditRecord) {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        let index = u32::try_from(records.len()).unwrap_or(u32::MAX);
+        records.push(record.with_index(index));
+    }
+
+    /// Take every record collected so far.
+    pub(crate) fn drain(&self) -> Vec<LlmAuditRecord> {
+        std::mem::take(&mut *self.records.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
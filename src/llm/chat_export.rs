@@ -0,0 +1,372 @@
+//! Conversation export and import in the OpenAI chat format.
+//!
+//! [`StateSnapshot::to_chat_export`] turns the messages channel into the
+//! `{"messages": [...]}` shape accepted by OpenAI-compatible chat completion
+//! APIs, evaluation harnesses and fine-tuning pipelines. [`ChatExport::to_state`]
+//! goes the other way, so a conversation recorded elsewhere can be replayed
+//! through a graph or against a different provider.
+//!
+//! [`Message`] only carries a role and text. Fields the chat format adds
+//! (`tool_calls`, `tool_call_id`, `name`) live in the `extra` channel under
+//! [`CHAT_METADATA_KEY`]: an object mapping a message's index in the messages
+//! channel to its [`ChatMessageMetadata`]. The importer writes that table, so
+//! export → import → export is lossless. Nodes that issue tool calls can add
+//! entries with [`chat_metadata_entry`].
+//!
+//! Roles are exported verbatim, including custom ones.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::llm::ChatExport;
+//! use weavegraph::state::VersionedState;
+//!
+//! let state = VersionedState::builder()
+//!     .with_system_message("You are terse.")
+//!     .with_user_message("Hi")
+//!     .with_assistant_message("Hello.")
+//!     .build();
+//!
+//! let export = state.snapshot().to_chat_export();
+//! let json = export.to_json().unwrap();
+//! assert!(json.starts_with(r#"{"messages":[{"role":"system""#));
+//!
+//! let restored = ChatExport::from_json(&json).unwrap().to_state();
+//! assert_eq!(restored.snapshot().messages, state.snapshot().messages);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::channels::Channel;
+use crate::message::{Message, Role};
+use crate::state::{StateSnapshot, VersionedState};
+
+/// `extra` key holding per-message chat metadata, keyed by message index.
+pub const CHAT_METADATA_KEY: &str = "__weavegraph_chat__";
+
+// ============================================================================
+// Wire types
+// ============================================================================
+
+/// A conversation in the OpenAI chat format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChatExport {
+    /// Messages in conversation order.
+    pub messages: Vec<ChatMessage>,
+}
+
+/// One entry of the `messages` array.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChatMessage {
+    /// `system`, `user`, `assistant`, `tool`, or a custom role.
+    pub role: String,
+    /// Text content. `None` for assistant messages that only call tools.
+    ///
+    /// On import, an array of content parts is joined from its text parts.
+    #[serde(default, deserialize_with = "content_text")]
+    pub content: Option<String>,
+    /// Optional participant name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool calls requested by an assistant message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ChatToolCall>,
+    /// Tool call a `tool` message answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    /// Create a message with text content.
+    #[must_use]
+    pub fn new(role: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            role: role.into(),
+            content: Some(content.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Metadata that has no place on [`Message`], or `None` if there is none.
+    fn metadata(&self) -> Option<ChatMessageMetadata> {
+        let metadata = ChatMessageMetadata {
+            name: self.name.clone(),
+            tool_calls: self.tool_calls.clone(),
+            tool_call_id: self.tool_call_id.clone(),
+        };
+        (!metadata.is_empty()).then_some(metadata)
+    }
+}
+
+/// A function call requested by the assistant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChatToolCall {
+    /// Call id, echoed by the answering `tool` message.
+    pub id: String,
+    /// Always `"function"` in the current format.
+    #[serde(rename = "type", default = "function_type")]
+    pub kind: String,
+    /// Function name and arguments.
+    pub function: ChatFunctionCall,
+}
+
+impl ChatToolCall {
+    /// Create a function call; `arguments` is the JSON-encoded argument object.
+    #[must_use]
+    pub fn function(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        arguments: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind: function_type(),
+            function: ChatFunctionCall {
+                name: name.into(),
+                arguments: arguments.into(),
+            },
+        }
+    }
+}
+
+/// Function name and JSON-encoded arguments of a [`ChatToolCall`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChatFunctionCall {
+    /// Function name.
+    pub name: String,
+    /// Arguments as a JSON string, as the chat format specifies.
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// Chat fields stored under [`CHAT_METADATA_KEY`] for one message.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChatMessageMetadata {
+    /// Participant name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool calls requested by the message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ChatToolCall>,
+    /// Tool call the message answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMessageMetadata {
+    /// Metadata for an assistant message that calls tools.
+    #[must_use]
+    pub fn tool_calls(calls: Vec<ChatToolCall>) -> Self {
+        Self {
+            tool_calls: calls,
+            ..Self::default()
+        }
+    }
+
+    /// Metadata for a `tool` message answering `call_id`.
+    #[must_use]
+    pub fn tool_result(call_id: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: Some(call_id.into()),
+            ..Self::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.name.is_none() && self.tool_calls.is_empty() && self.tool_call_id.is_none()
+    }
+}
+
+/// Errors raised while reading or writing a [`ChatExport`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum ChatExportError {
+    /// The document is not valid chat JSON.
+    #[error("invalid chat export JSON: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::llm::chat_export_json),
+            help("Expected an object with a `messages` array of {{role, content}} entries.")
+        )
+    )]
+    Json(#[from] serde_json::Error),
+}
+
+// ============================================================================
+// Conversion
+// ============================================================================
+
+impl ChatExport {
+    /// Wrap a list of chat messages.
+    #[must_use]
+    pub fn new(messages: Vec<ChatMessage>) -> Self {
+        Self { messages }
+    }
+
+    /// Build an export from a snapshot's messages and chat metadata.
+    ///
+    /// Malformed metadata entries are ignored.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &StateSnapshot) -> Self {
+        let table = snapshot
+            .extra
+            .get(CHAT_METADATA_KEY)
+            .and_then(Value::as_object);
+        let messages = snapshot
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let metadata: ChatMessageMetadata = table
+                    .and_then(|table| table.get(&index.to_string()))
+                    .and_then(|value| serde_json::from_value(value.clone()).ok())
+                    .unwrap_or_default();
+                let content = if message.content.is_empty() && !metadata.tool_calls.is_empty() {
+                    None
+                } else {
+                    Some(message.content.clone())
+                };
+                ChatMessage {
+                    role: message.role.as_str().to_string(),
+                    content,
+                    name: metadata.name,
+                    tool_calls: metadata.tool_calls,
+                    tool_call_id: metadata.tool_call_id,
+                }
+            })
+            .collect();
+        Self { messages }
+    }
+
+    /// Parse an export from JSON.
+    ///
+    /// Accepts either an object with a `messages` array or a bare array.
+    pub fn from_json(json: &str) -> Result<Self, ChatExportError> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Document {
+            Object(ChatExport),
+            Array(Vec<ChatMessage>),
+        }
+        Ok(match serde_json::from_str(json)? {
+            Document::Object(export) => export,
+            Document::Array(messages) => Self { messages },
+        })
+    }
+
+    /// Serialize as `{"messages": [...]}`.
+    pub fn to_json(&self) -> Result<String, ChatExportError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Messages as they would be stored in the messages channel.
+    #[must_use]
+    pub fn to_messages(&self) -> Vec<Message> {
+        self.messages
+            .iter()
+            .map(|message| {
+                Message::with_role(
+                    Role::from(message.role.as_str()),
+                    message.content.as_deref().unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Build a fresh state holding the conversation.
+    ///
+    /// Chat-only fields are written under [`CHAT_METADATA_KEY`] when any
+    /// message has them.
+    #[must_use]
+    pub fn to_state(&self) -> VersionedState {
+        let mut state = VersionedState::new_with_messages(self.to_messages());
+        let table: serde_json::Map<String, Value> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter_map(|(index, message)| {
+                let metadata = serde_json::to_value(message.metadata()?).ok()?;
+                Some((index.to_string(), metadata))
+            })
+            .collect();
+        if !table.is_empty() {
+            state
+                .extra
+                .get_mut()
+                .insert(CHAT_METADATA_KEY.to_string(), Value::Object(table));
+        }
+        state
+    }
+}
+
+/// Build the `extra` entry recording `metadata` for the message at `index`.
+///
+/// Merge the result into [`CHAT_METADATA_KEY`] alongside existing entries.
+/// `index` is the message's position in the messages channel, so it is only
+/// stable for nodes that are the sole message writer in their superstep.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::llm::{ChatMessageMetadata, ChatToolCall, chat_metadata_entry};
+///
+/// let call = ChatToolCall::function("call_1", "lookup", r#"{"city":"Oslo"}"#);
+/// let (key, value) = chat_metadata_entry(3, &ChatMessageMetadata::tool_calls(vec![call]));
+/// assert_eq!(key, "3");
+/// assert_eq!(value["tool_calls"][0]["function"]["name"], "lookup");
+/// ```
+#[must_use]
+pub fn chat_metadata_entry(index: usize, metadata: &ChatMessageMetadata) -> (String, Value) {
+    let value = serde_json::to_value(metadata).unwrap_or(Value::Null);
+    (index.to_string(), value)
+}
+
+impl StateSnapshot {
+    /// Export the conversation in the OpenAI chat format.
+    ///
+    /// See [`ChatExport`](crate::llm::ChatExport) for how tool calls are recovered.
+    #[must_use]
+    pub fn to_chat_export(&self) -> ChatExport {
+        ChatExport::from_snapshot(self)
+    }
+}
+
+impl VersionedState {
+    /// Build a state from an imported conversation.
+    #[must_use]
+    pub fn from_chat_export(export: &ChatExport) -> Self {
+        export.to_state()
+    }
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// Accept string content, `null`, or an array of content parts.
+fn content_text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => None,
+        Some(Value::String(text)) => Some(text),
+        Some(Value::Array(parts)) => Some(
+            parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join(""),
+        ),
+        Some(other) => Some(other.to_string()),
+    })
+}
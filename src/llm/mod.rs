@@ -3,10 +3,26 @@
 //! This module defines provider traits that are independent of any specific
 //! LLM SDK. The Rig adapter is available behind the `rig` feature.
 
+pub mod audit;
+pub mod chat_export;
+pub mod streaming;
+pub mod summarize;
 pub mod traits;
 
 #[cfg(feature = "rig")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rig")))]
 pub mod rig_adapter;
 
+pub use audit::{LLM_AUDIT_FORMAT_VERSION, LlmAuditRecord, LlmCall};
+pub use chat_export::{
+    CHAT_METADATA_KEY, ChatExport, ChatExportError, ChatFunctionCall, ChatMessage,
+    ChatMessageMetadata, ChatToolCall, chat_metadata_entry,
+};
+pub use streaming::{
+    ChunkTiming, MessageAssembler, STREAM_TIMING_KEY, StreamTiming, stream_timing_entry,
+};
+pub use summarize::{
+    DEFAULT_SUMMARY_PROMPT, ReplaceSummarizedSpan, SUMMARY_LOG_KEY, SUMMARY_REPLACEMENT_KEY,
+    SummarizeHistoryNode, SummaryRecord,
+};
 pub use traits::{LlmError, LlmProvider, LlmResponse, LlmStreamProvider};
@@ -0,0 +1,247 @@
+//! Assembling streamed LLM chunks into messages with token timing.
+//!
+//! A [`MessageAssembler`] collects the chunks of one streamed completion and
+//! records when each arrived. The finished [`Message`] only carries text, so
+//! the timing lives in the `extra` channel under [`STREAM_TIMING_KEY`]: an
+//! object mapping a message's index in the messages channel to its
+//! [`StreamTiming`], the same layout as
+//! [`CHAT_METADATA_KEY`](crate::llm::CHAT_METADATA_KEY). Analytics can read
+//! first-token latency and per-chunk durations back with
+//! [`StreamTiming::from_snapshot`] instead of replaying the event log.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use weavegraph::llm::MessageAssembler;
+//! use weavegraph::message::Role;
+//!
+//! let mut assembler = MessageAssembler::new(Role::Assistant);
+//! assembler.push_at("Hel", Duration::from_millis(120));
+//! assembler.push_at("lo.", Duration::from_millis(150));
+//!
+//! let (message, timing) = assembler.finish();
+//! assert_eq!(message.content, "Hello.");
+//! assert_eq!(timing.first_token_latency(), Some(Duration::from_millis(120)));
+//! assert_eq!(timing.chunk_texts(&message.content), vec!["Hel", "lo."]);
+//! ```
+
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::message::{Message, Role};
+use crate::node::NodePartial;
+use crate::state::StateSnapshot;
+
+/// `extra` key holding per-message stream timing, keyed by message index.
+pub const STREAM_TIMING_KEY: &str = "__weavegraph_stream_timing__";
+
+// ============================================================================
+// Timing
+// ============================================================================
+
+/// Boundary and arrival time of one streamed chunk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChunkTiming {
+    /// Byte offset of the chunk in the assembled content.
+    pub offset: usize,
+    /// Length of the chunk in bytes.
+    pub len: usize,
+    /// Time from the start of the stream to the chunk's arrival, in microseconds.
+    pub at_micros: u64,
+    /// Time since the previous chunk (or the start, for the first), in microseconds.
+    pub duration_micros: u64,
+}
+
+/// Token timing of one streamed message.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct StreamTiming {
+    /// Time from the start of the stream to the first non-empty chunk, in microseconds.
+    pub first_token_micros: Option<u64>,
+    /// Time from the start of the stream until it finished, in microseconds.
+    pub total_micros: u64,
+    /// Chunks in arrival order.
+    pub chunks: Vec<ChunkTiming>,
+}
+
+impl StreamTiming {
+    /// Time to the first token, or `None` if no chunk arrived.
+    #[must_use]
+    pub fn first_token_latency(&self) -> Option<Duration> {
+        self.first_token_micros.map(Duration::from_micros)
+    }
+
+    /// Total stream duration.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        Duration::from_micros(self.total_micros)
+    }
+
+    /// Gap before each chunk, in arrival order.
+    #[must_use]
+    pub fn chunk_durations(&self) -> Vec<Duration> {
+        self.chunks
+            .iter()
+            .map(|chunk| Duration::from_micros(chunk.duration_micros))
+            .collect()
+    }
+
+    /// Split assembled `content` back into its chunks.
+    ///
+    /// Chunks that fall outside `content` (for example after the message was
+    /// edited) are skipped.
+    #[must_use]
+    pub fn chunk_texts<'a>(&self, content: &'a str) -> Vec<&'a str> {
+        self.chunks
+            .iter()
+            .filter_map(|chunk| content.get(chunk.offset..chunk.offset + chunk.len))
+            .collect()
+    }
+
+    /// Summary suitable for the metadata of a final
+    /// [`LLMStreamingEvent`](crate::event_bus::LLMStreamingEvent).
+    #[must_use]
+    pub fn event_metadata(&self) -> FxHashMap<String, Value> {
+        let mut metadata = FxHashMap::default();
+        if let Some(first) = self.first_token_micros {
+            metadata.insert("first_token_micros".to_string(), json!(first));
+        }
+        metadata.insert("total_micros".to_string(), json!(self.total_micros));
+        metadata.insert("chunk_count".to_string(), json!(self.chunks.len()));
+        metadata
+    }
+
+    /// Timing recorded for the message at `index`, if any.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &StateSnapshot, index: usize) -> Option<Self> {
+        let entry = snapshot
+            .extra
+            .get(STREAM_TIMING_KEY)?
+            .as_object()?
+            .get(&index.to_string())?;
+        serde_json::from_value(entry.clone()).ok()
+    }
+}
+
+/// Build the `extra` entry recording `timing` for the message at `index`.
+///
+/// Merge the result into [`STREAM_TIMING_KEY`] alongside existing entries.
+/// As with [`chat_metadata_entry`](crate::llm::chat_metadata_entry), `index`
+/// is only stable for nodes that are the sole message writer in their
+/// superstep.
+#[must_use]
+pub fn stream_timing_entry(index: usize, timing: &StreamTiming) -> (String, Value) {
+    let value = serde_json::to_value(timing).unwrap_or(Value::Null);
+    (index.to_string(), value)
+}
+
+// ============================================================================
+// Assembler
+// ============================================================================
+
+/// Accumulates streamed chunks into a [`Message`] and its [`StreamTiming`].
+///
+/// The clock starts when the assembler is created, so create it just before
+/// sending the request. [`push`](Self::push) timestamps chunks on arrival;
+/// [`push_at`](Self::push_at) takes an offset for providers that report their
+/// own timings. Empty chunks are ignored.
+#[derive(Debug, Clone)]
+pub struct MessageAssembler {
+    role: Role,
+    content: String,
+    started: Instant,
+    timing: StreamTiming,
+}
+
+impl MessageAssembler {
+    /// Start assembling a message with `role`.
+    #[must_use]
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            content: String::new(),
+            started: Instant::now(),
+            timing: StreamTiming::default(),
+        }
+    }
+
+    /// Append a chunk that arrived now.
+    pub fn push(&mut self, chunk: &str) {
+        let at = self.started.elapsed();
+        self.push_at(chunk, at);
+    }
+
+    /// Append a chunk that arrived `at` after the start of the stream.
+    ///
+    /// Offsets earlier than the previous chunk are treated as simultaneous
+    /// with it, so durations never go negative.
+    pub fn push_at(&mut self, chunk: &str, at: Duration) {
+        if chunk.is_empty() {
+            return;
+        }
+        let previous = self.timing.chunks.last().map_or(0, |c| c.at_micros);
+        let at_micros = micros(at).max(previous);
+        self.timing.first_token_micros.get_or_insert(at_micros);
+        self.timing.chunks.push(ChunkTiming {
+            offset: self.content.len(),
+            len: chunk.len(),
+            at_micros,
+            duration_micros: at_micros - previous,
+        });
+        self.timing.total_micros = at_micros;
+        self.content.push_str(chunk);
+    }
+
+    /// Content assembled so far.
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Timing recorded so far.
+    #[must_use]
+    pub fn timing(&self) -> &StreamTiming {
+        &self.timing
+    }
+
+    /// Finish the stream, returning the message and its timing.
+    ///
+    /// The total duration runs until now, or until the last chunk if that
+    /// was reported later.
+    #[must_use]
+    pub fn finish(mut self) -> (Message, StreamTiming) {
+        self.timing.total_micros = self.timing.total_micros.max(micros(self.started.elapsed()));
+        (Message::with_role(self.role, &self.content), self.timing)
+    }
+
+    /// Finish the stream and build a partial that appends the message to
+    /// `snapshot` with its timing recorded under [`STREAM_TIMING_KEY`].
+    ///
+    /// Existing timing entries in `snapshot` are kept.
+    #[must_use]
+    pub fn into_partial(self, snapshot: &StateSnapshot) -> NodePartial {
+        let (message, timing) = self.finish();
+        let mut table = snapshot
+            .extra
+            .get(STREAM_TIMING_KEY)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_else(Map::new);
+        let (key, value) = stream_timing_entry(snapshot.messages.len(), &timing);
+        table.insert(key, value);
+        let mut extra = FxHashMap::default();
+        extra.insert(STREAM_TIMING_KEY.to_string(), Value::Object(table));
+        NodePartial::new()
+            .with_messages(vec![message])
+            .with_extra(extra)
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
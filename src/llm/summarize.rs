@@ -0,0 +1,311 @@
+//! Summarizing old conversation history to stay inside a context window.
+//!
+//! A [`SummarizeHistoryNode`] estimates the size of the messages channel with
+//! [`estimate_tokens`]. Once it exceeds the node's threshold, the node asks an
+//! [`LlmProvider`] to summarize everything except a leading system prompt and
+//! the most recent messages, and replaces that span with a single system
+//! message holding the summary. Later runs fold the previous summary into the
+//! next one, so the conversation carries a running summary.
+//!
+//! Reducers only ever append to the messages channel, so the node does not
+//! write messages itself. It writes a replacement directive to `extra` under
+//! [`SUMMARY_REPLACEMENT_KEY`], and the [`ReplaceSummarizedSpan`] reducer
+//! splices the summary in at the barrier. The reducer appends a
+//! [`SummaryRecord`] with the span's location to [`SUMMARY_LOG_KEY`], so an
+//! audit can tell which messages a summary stands for. Register the node with
+//! [`GraphBuilder::add_summarizer`](crate::graphs::GraphBuilder::add_summarizer),
+//! which installs the reducer as well.
+//!
+//! Index-keyed metadata such as [`CHAT_METADATA_KEY`](crate::llm::CHAT_METADATA_KEY)
+//! is not re-keyed when a span is replaced.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use weavegraph::graphs::GraphBuilder;
+//! use weavegraph::llm::{LlmError, LlmProvider, LlmResponse, SummarizeHistoryNode};
+//! use weavegraph::message::Message;
+//! use weavegraph::types::NodeKind;
+//!
+//! struct Model;
+//!
+//! #[async_trait::async_trait]
+//! impl LlmProvider for Model {
+//!     async fn chat(&self, _messages: &[Message]) -> Result<LlmResponse, LlmError> {
+//!         Ok(LlmResponse { content: "The user asked about pricing.".into(), ..Default::default() })
+//!     }
+//! }
+//!
+//! let compact = NodeKind::Custom("compact".into());
+//! let app = GraphBuilder::new()
+//!     .add_summarizer(
+//!         compact.clone(),
+//!         SummarizeHistoryNode::new(Arc::new(Model))
+//!             .with_token_threshold(2_000)
+//!             .keep_recent(6),
+//!     )
+//!     .add_edge(NodeKind::Start, compact.clone())
+//!     .add_edge(compact, NodeKind::End)
+//!     .compile()
+//!     .unwrap();
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::channels::Channel;
+use crate::graphs::estimate_tokens;
+use crate::llm::LlmProvider;
+use crate::message::{Message, Role};
+use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::reducers::Reducer;
+use crate::state::{StateSnapshot, VersionedState};
+
+/// `extra` key carrying a pending span replacement from a summarizer.
+///
+/// [`ReplaceSummarizedSpan`] consumes and removes it in the same barrier.
+pub const SUMMARY_REPLACEMENT_KEY: &str = "__weavegraph_summary_replace__";
+
+/// `extra` key holding the list of [`SummaryRecord`]s, oldest first.
+pub const SUMMARY_LOG_KEY: &str = "__weavegraph_summaries__";
+
+/// Instructions sent ahead of the transcript unless replaced with
+/// [`SummarizeHistoryNode::with_prompt`].
+pub const DEFAULT_SUMMARY_PROMPT: &str = "Summarize the conversation transcript below so it can \
+     replace the original messages. Keep facts, decisions, names, and open questions; drop \
+     pleasantries. Reply with the summary only.";
+
+// ============================================================================
+// Records
+// ============================================================================
+
+/// Where a summary was spliced into the messages channel.
+///
+/// `start..end` are indices into the messages channel as it was when the span
+/// was replaced; the summary now sits at `start`. Records are kept in order,
+/// so earlier replacements can be replayed to map a span back to the full
+/// conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SummaryRecord {
+    /// Superstep in which the summary was produced.
+    pub step: u64,
+    /// First replaced message.
+    pub start: usize,
+    /// One past the last replaced message.
+    pub end: usize,
+    /// Estimated tokens of the replaced messages.
+    pub replaced_tokens: usize,
+    /// Estimated tokens of the summary message.
+    pub summary_tokens: usize,
+}
+
+impl SummaryRecord {
+    /// Number of messages the summary replaced.
+    #[must_use]
+    pub fn replaced_messages(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// All summaries recorded in `snapshot`, oldest first.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &StateSnapshot) -> Vec<Self> {
+        snapshot
+            .extra
+            .get(SUMMARY_LOG_KEY)
+            .and_then(|log| serde_json::from_value(log.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Directive written by the node and applied by [`ReplaceSummarizedSpan`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Replacement {
+    summary: Message,
+    record: SummaryRecord,
+}
+
+// ============================================================================
+// Node
+// ============================================================================
+
+/// Node that replaces old messages with a running summary.
+///
+/// See the [module documentation](self) for how the replacement is applied.
+#[derive(Clone)]
+#[must_use]
+pub struct SummarizeHistoryNode {
+    provider: Arc<dyn LlmProvider>,
+    token_threshold: usize,
+    keep_recent: usize,
+    prompt: String,
+}
+
+impl SummarizeHistoryNode {
+    /// Summarize with `provider` once the history exceeds 4000 estimated
+    /// tokens, keeping the last 4 messages verbatim.
+    pub fn new(provider: Arc<dyn LlmProvider>) -> Self {
+        Self {
+            provider,
+            token_threshold: 4000,
+            keep_recent: 4,
+            prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+        }
+    }
+
+    /// Summarize only when the history exceeds `tokens` estimated tokens.
+    pub fn with_token_threshold(mut self, tokens: usize) -> Self {
+        self.token_threshold = tokens;
+        self
+    }
+
+    /// Keep the last `count` messages out of the summary.
+    pub fn keep_recent(mut self, count: usize) -> Self {
+        self.keep_recent = count;
+        self
+    }
+
+    /// Replace the instructions sent ahead of the transcript.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// The span of `snapshot`'s messages that would be summarized, if the
+    /// history is over the threshold and the span holds at least two messages.
+    #[must_use]
+    pub fn span(&self, snapshot: &StateSnapshot) -> Option<(usize, usize)> {
+        let messages = &snapshot.messages;
+        let total: usize = messages.iter().map(estimate_tokens).sum();
+        if total <= self.token_threshold {
+            return None;
+        }
+        // A leading system message is kept unless it is an earlier summary.
+        let summary_first = SummaryRecord::from_snapshot(snapshot)
+            .last()
+            .is_some_and(|record| record.start == 0);
+        let keep_prompt =
+            !summary_first && messages.first().is_some_and(|m| m.role == Role::System);
+        let start = usize::from(keep_prompt);
+        let end = messages.len().saturating_sub(self.keep_recent);
+        // A one-message span would not shrink the history.
+        (end >= start + 2).then_some((start, end))
+    }
+
+    fn transcript(span: &[Message]) -> String {
+        span.iter()
+            .map(|message| format!("{}: {}", message.role.as_str(), message.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[async_trait]
+impl Node for SummarizeHistoryNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let Some((start, end)) = self.span(&snapshot) else {
+            return Ok(NodePartial::new());
+        };
+        let span = &snapshot.messages[start..end];
+        let request = [
+            Message::system(&self.prompt),
+            Message::user(&Self::transcript(span)),
+        ];
+        let response = ctx
+            .chat_audited(self.provider.as_ref(), &request, serde_json::Value::Null)
+            .await
+            .map_err(|err| NodeError::Provider {
+                provider: "summarize_history",
+                message: err.to_string(),
+            })?;
+
+        let summary = Message::system(response.content.trim());
+        let replacement = Replacement {
+            record: SummaryRecord {
+                step: ctx.step,
+                start,
+                end,
+                replaced_tokens: span.iter().map(estimate_tokens).sum(),
+                summary_tokens: estimate_tokens(&summary),
+            },
+            summary,
+        };
+        let mut extra = FxHashMap::default();
+        extra.insert(
+            SUMMARY_REPLACEMENT_KEY.to_string(),
+            serde_json::to_value(replacement)?,
+        );
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+// ============================================================================
+// Reducer
+// ============================================================================
+
+/// `extra` reducer that applies a [`SummarizeHistoryNode`]'s replacement.
+///
+/// Register it after [`MapMerge`](crate::reducers::MapMerge) on
+/// [`ChannelType::Extra`](crate::types::ChannelType::Extra). Directives whose
+/// span no longer fits the messages channel are dropped with a warning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReplaceSummarizedSpan;
+
+impl Reducer for ReplaceSummarizedSpan {
+    fn definition_label(&self) -> &'static str {
+        "weavegraph::llm::ReplaceSummarizedSpan"
+    }
+
+    fn apply(&self, state: &mut VersionedState, update: &NodePartial) {
+        let Some(directive) = update
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get(SUMMARY_REPLACEMENT_KEY))
+        else {
+            return;
+        };
+        state.extra.get_mut().remove(SUMMARY_REPLACEMENT_KEY);
+
+        let replacement: Replacement = match serde_json::from_value(directive.clone()) {
+            Ok(replacement) => replacement,
+            Err(err) => {
+                tracing::warn!(target: "weavegraph::llm", %err, "ignoring malformed summary directive");
+                return;
+            }
+        };
+        let Replacement { summary, record } = replacement;
+        let messages = state.messages.get_mut();
+        if record.start >= record.end || record.end > messages.len() {
+            tracing::warn!(
+                target: "weavegraph::llm",
+                start = record.start,
+                end = record.end,
+                len = messages.len(),
+                "ignoring summary directive outside the messages channel"
+            );
+            return;
+        }
+        messages.splice(record.start..record.end, [summary]);
+
+        let log = state
+            .extra
+            .get_mut()
+            .entry(SUMMARY_LOG_KEY.to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if !log.is_array() {
+            *log = Value::Array(Vec::new());
+        }
+        if let (Value::Array(entries), Ok(entry)) = (log, serde_json::to_value(record)) {
+            entries.push(entry);
+        }
+    }
+}
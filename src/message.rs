@@ -122,11 +122,13 @@ impl From<Role> for String {
 /// let function_msg = Message::with_role(Role::Custom("function".into()), "Result: 42");
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Message {
     /// The role of the message sender.
     ///
     /// This field is serialized as a string for backward compatibility.
     #[serde(with = "role_serde")]
+    #[cfg_attr(feature = "schema", schemars(with = "String"))]
     pub role: Role,
     /// The text content of the message.
     pub content: String,
@@ -9,14 +9,50 @@ use serde_json;
 use thiserror::Error;
 
 // Internal crate modules
-use crate::channels::errors::ErrorEvent;
-use crate::control::{FrontierCommand, NodeRoute};
+use crate::channels::Blob;
+use crate::channels::errors::{ErrorEvent, WeaveError};
+use crate::control::{FrontierCommand, NodeCommand, NodeRoute};
 use crate::event_bus::{Event, EventEmitter, LLMStreamingEvent};
+use crate::feature_flags::{FeatureFlags, NO_FLAGS};
+use crate::graphs::DerivedValues;
+use crate::llm::audit::LlmAuditLog;
+use crate::llm::{LlmAuditRecord, LlmCall, LlmError, LlmProvider, LlmResponse};
 use crate::message::Message;
+use crate::runtimes::CheckpointerError;
 use crate::state::{StateKey, StateSlotError, StateSnapshot};
 use crate::types::NodeKind;
 use crate::utils::clock::Clock;
-use std::sync::Arc;
+use crate::utils::deterministic_rng::DeterministicRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+pub use tokio_util::sync::CancellationToken;
+/// # Examples
+///
+/// ```
+/// use weavegraph::message::Message;
+/// use weavegraph::node::{Node, NodeContext, NodeError, NodePartial, node};
+/// use weavegraph::state::StateSnapshot;
+///
+/// /// Greets the most recent speaker.
+/// #[node(name = "greeter")]
+/// async fn greet(snapshot: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+///     ctx.emit("greet", "greeting")?;
+///     let reply = format!("hello after {} messages", snapshot.messages.len());
+///     Ok(NodePartial::new().with_messages(vec![Message::assistant(&reply)]))
+/// }
+///
+/// assert_eq!(Greet.definition_label(), "greeter");
+/// // `GraphBuilder::new().add_node(NodeKind::Custom("greet".into()), Greet)`
+/// ```
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use weavegraph_macros::node;
 
 // ============================================================================
 // Core Trait
@@ -80,6 +116,16 @@ use std::sync::Arc;
 ///     }
 /// }
 /// ```
+///
+/// # Lifecycle Hooks
+///
+/// Nodes that hold connection pools or model handles can override
+/// [`on_register`](Node::on_register) and [`on_shutdown`](Node::on_shutdown).
+/// `on_register` runs once per [`App`](crate::app::App) before the first
+/// session is created; `on_shutdown` runs when
+/// [`AppRunner::shutdown`](crate::runtimes::AppRunner::shutdown) or
+/// [`App::shutdown_nodes`](crate::app::App::shutdown_nodes) is called. Both
+/// default to no-ops.
 #[async_trait]
 pub trait Node: Send + Sync {
     /// Execute this node with the given state snapshot and context.
@@ -88,12 +134,118 @@ pub trait Node: Send + Sync {
         snapshot: StateSnapshot,
         ctx: NodeContext,
     ) -> Result<NodePartial, NodeError>;
+
+    /// Stable label describing this node's implementation.
+    ///
+    /// Reported by [`App::describe`](crate::app::App::describe). The default is
+    /// the concrete Rust type path; override it with a durable name when the
+    /// type path is too noisy or leaks internal module layout.
+    fn definition_label(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Prepare long-lived resources before the node first runs.
+    ///
+    /// Called once per app (step `0` in `ctx`), in node-name order. Returning an
+    /// error aborts session creation with
+    /// [`RunnerError::NodeLifecycle`](crate::runtimes::runner::RunnerError::NodeLifecycle)
+    /// after shutting down the nodes already registered, in reverse order;
+    /// registration is retried on the next session.
+    async fn on_register(&self, _ctx: NodeContext) -> Result<(), NodeError> {
+        Ok(())
+    }
+
+    /// Release resources acquired in [`on_register`](Node::on_register).
+    ///
+    /// Called in reverse node-name order and only for apps whose nodes were
+    /// registered, by [`App::shutdown_nodes`](crate::app::App::shutdown_nodes)
+    /// or when a later node fails to register. Runs never call it on their own.
+    async fn on_shutdown(&self) {}
+}
+
+// ============================================================================
+// Blocking Nodes
+// ============================================================================
+
+/// Adapter that runs a synchronous function as a [`Node`] on Tokio's blocking pool.
+///
+/// This is the boundary for nodes implemented outside async Rust, such as
+/// Python callables that must hold an interpreter lock or CPU-bound code that
+/// would otherwise stall the scheduler. The function receives an owned
+/// [`StateSnapshot`] and [`NodeContext`] and returns a [`NodePartial`].
+/// Because it runs on a blocking-pool thread, the function must be
+/// `Send + Sync + 'static`: it cannot borrow from the caller, and anything it
+/// captures is shared across runs.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::message::{Message, Role};
+/// use weavegraph::node::{BlockingNode, NodePartial};
+///
+/// let node = BlockingNode::new(|snapshot, _ctx| {
+///     let count = snapshot.messages.len();
+///     Ok(NodePartial::new().with_messages(vec![Message::with_role(
+///         Role::Assistant,
+///         &format!("saw {count} messages"),
+///     )]))
+/// });
+/// ```
+pub struct BlockingNode<F> {
+    func: Arc<F>,
+}
+
+impl<F> BlockingNode<F>
+where
+    F: Fn(StateSnapshot, NodeContext) -> Result<NodePartial, NodeError> + Send + Sync + 'static,
+{
+    /// Wrap `func` as a node.
+    #[must_use]
+    pub fn new(func: F) -> Self {
+        Self {
+            func: Arc::new(func),
+        }
+    }
+}
+
+#[async_trait]
+impl<F> Node for BlockingNode<F>
+where
+    F: Fn(StateSnapshot, NodeContext) -> Result<NodePartial, NodeError> + Send + Sync + 'static,
+{
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let func = Arc::clone(&self.func);
+        // Carry the node span onto the blocking thread so logs stay attributed.
+        let span = tracing::Span::current();
+        match tokio::task::spawn_blocking(move || span.in_scope(|| func(snapshot, ctx))).await {
+            Ok(result) => result,
+            // Propagate panics so the scheduler reports them as node panics.
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(err) => Err(NodeError::other(err)),
+        }
+    }
 }
 
 // ============================================================================
 // Execution Context
 // ============================================================================
 
+/// FNV-1a over `bytes`, starting from `seed` mixed into the offset basis.
+///
+/// Used for RNG seeds because `std`'s hashers are not stable across releases.
+fn stable_hash(bytes: &[u8], seed: u64) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64 ^ seed;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
 /// Execution context passed to nodes during workflow execution.
 ///
 /// Provides nodes with access to their execution environment, including step
@@ -111,6 +263,24 @@ pub struct NodeContext {
     pub clock: Option<Arc<dyn Clock>>,
     /// Optional invocation or run identifier attached to node events.
     pub invocation_id: Option<String>,
+    /// Optional incremental partial stream wired by the runner.
+    pub(crate) partial_stream: Option<PartialStream>,
+    /// Configured base seed for [`rng`](Self::rng); derived from the invocation id when unset.
+    pub(crate) rng_seed: Option<u64>,
+    /// Execution attempt of the current step; see [`attempt`](Self::attempt).
+    pub(crate) attempt: u32,
+    /// Per-node metrics recorder wired by the scheduler.
+    pub(crate) metrics: Option<Arc<NodeMetricsRecorder>>,
+    /// Superstep audit log, wired by the scheduler when LLM auditing is enabled.
+    pub(crate) llm_audit: Option<Arc<LlmAuditLog>>,
+    /// Predecessor this run was scheduled for under [`JoinPolicy::PerIncomingEdge`](crate::graphs::JoinPolicy::PerIncomingEdge).
+    pub(crate) incoming: Option<NodeKind>,
+    /// Feature flags resolved for the session by the runner.
+    pub(crate) feature_flags: Option<Arc<FeatureFlags>>,
+    /// Derived channels bound to the superstep's input snapshot.
+    pub(crate) derived: Option<DerivedValues>,
+    /// Cancelled when the run is aborted; a child of the runner's token.
+    pub(crate) cancellation: CancellationToken,
 }
 
 impl NodeContext {
@@ -126,9 +296,249 @@ impl NodeContext {
             event_emitter,
             clock: None,
             invocation_id: None,
+            partial_stream: None,
+            metrics: None,
+            llm_audit: None,
+            rng_seed: None,
+            attempt: 1,
+            incoming: None,
+            feature_flags: None,
+            derived: None,
+            cancellation: CancellationToken::new(),
         }
     }
 
+    /// Use `seed` instead of the invocation id as the base of [`rng`](Self::rng).
+    #[must_use]
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Mark this context as belonging to re-execution number `attempt` of its step.
+    #[must_use]
+    pub fn with_attempt(mut self, attempt: u32) -> Self {
+        self.attempt = attempt.max(1);
+        self
+    }
+
+    /// Attach the feature flags resolved for the session.
+    #[must_use]
+    pub fn with_feature_flags(mut self, flags: Arc<FeatureFlags>) -> Self {
+        self.feature_flags = Some(flags);
+        self
+    }
+
+    /// Feature flags resolved for the session; empty when none are configured.
+    ///
+    /// Flags come from the
+    /// [`FeatureFlagProvider`](crate::feature_flags::FeatureFlagProvider) set with
+    /// [`RuntimeConfig::with_feature_flags`](crate::runtimes::RuntimeConfig::with_feature_flags).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use serde_json::json;
+    /// use weavegraph::event_bus::EventBus;
+    /// use weavegraph::feature_flags::FeatureFlags;
+    /// use weavegraph::node::NodeContext;
+    ///
+    /// let bus = EventBus::default();
+    /// let flags = FeatureFlags::from_iter([("ranker".to_string(), json!("bm25"))]);
+    /// let ctx = NodeContext::new("search", 1, bus.get_emitter())
+    ///     .with_feature_flags(Arc::new(flags));
+    /// assert_eq!(ctx.feature_flags().variant("ranker"), Some("bm25"));
+    /// ```
+    #[must_use]
+    pub fn feature_flags(&self) -> &FeatureFlags {
+        self.feature_flags.as_deref().unwrap_or(&NO_FLAGS)
+    }
+
+    /// Attach derived channels bound to the step's input snapshot.
+    #[must_use]
+    pub fn with_derived(mut self, derived: DerivedValues) -> Self {
+        self.derived = Some(derived);
+        self
+    }
+
+    /// Value of the derived channel `name` for the step's input state.
+    ///
+    /// Returns `None` when no channel with that name is registered with
+    /// [`GraphBuilder::with_derived_channel`](crate::graphs::GraphBuilder::with_derived_channel).
+    /// The value is computed from the full state even if the node has a
+    /// [`SnapshotView`](crate::graphs::SnapshotView).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use serde_json::json;
+    /// use weavegraph::event_bus::EventBus;
+    /// use weavegraph::graphs::{DerivedChannel, DerivedChannels, DerivedValues};
+    /// use weavegraph::node::NodeContext;
+    /// use weavegraph::state::VersionedState;
+    ///
+    /// let bus = EventBus::default();
+    /// let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+    /// let channels = DerivedChannels::new().with_channel(DerivedChannel::message_count());
+    /// let ctx = NodeContext::new("summarize", 1, bus.get_emitter())
+    ///     .with_derived(DerivedValues::new(channels, Arc::new(snapshot)));
+    /// assert_eq!(ctx.derived("message_count"), Some(json!(1)));
+    /// ```
+    #[must_use]
+    pub fn derived(&self, name: &str) -> Option<Value> {
+        self.derived.as_ref()?.get(name)
+    }
+
+    /// Typed value of the derived channel `name`; see [`derived`](Self::derived).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateSlotError::Deserialize`] if the value has the wrong shape.
+    pub fn derived_as<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, StateSlotError> {
+        match &self.derived {
+            Some(derived) => derived.get_as(name),
+            None => Ok(None),
+        }
+    }
+
+    /// Use `token` as this run's cancellation token.
+    #[must_use]
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Token cancelled when the run this node belongs to is aborted.
+    ///
+    /// The scheduler hands every node run its own child of the runner's
+    /// token (see
+    /// [`AppRunnerBuilder::cancellation_token`](crate::runtimes::AppRunnerBuilder::cancellation_token)).
+    /// Pass it (or a child of it) to HTTP or gRPC clients, or race long awaits
+    /// against [`cancelled`](Self::cancelled). Nodes that do not return within
+    /// the grace period after cancellation are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::EventBus;
+    /// use weavegraph::node::{CancellationToken, NodeContext};
+    ///
+    /// let bus = EventBus::default();
+    /// let token = CancellationToken::new();
+    /// let ctx = NodeContext::new("fetch", 1, bus.get_emitter())
+    ///     .with_cancellation_token(token.child_token());
+    /// token.cancel();
+    /// assert!(ctx.is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Whether the run this node belongs to has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Wait until the run this node belongs to is cancelled.
+    ///
+    /// The standard pattern for an external call is to race it against this
+    /// future and return early when cancellation wins:
+    ///
+    /// ```no_run
+    /// use weavegraph::node::{NodeContext, NodeError, NodePartial};
+    ///
+    /// # async fn call_api() -> String { String::new() }
+    /// async fn fetch(ctx: &NodeContext) -> Result<NodePartial, NodeError> {
+    ///     tokio::select! {
+    ///         body = call_api() => Ok(NodePartial::new().with_extra(
+    ///             [("body".to_string(), body.into())].into_iter().collect(),
+    ///         )),
+    ///         () = ctx.cancelled() => Err(NodeError::Other("request cancelled".into())),
+    ///     }
+    /// }
+    /// ```
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await;
+    }
+
+    /// Mark this run as scheduled for the branch arriving from `origin`.
+    #[must_use]
+    pub fn with_incoming_from(mut self, origin: NodeKind) -> Self {
+        self.incoming = Some(origin);
+        self
+    }
+
+    /// The predecessor whose branch this run handles.
+    ///
+    /// Set only for nodes using
+    /// [`JoinPolicy::PerIncomingEdge`](crate::graphs::JoinPolicy::PerIncomingEdge),
+    /// which run once per predecessor that routed to them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::EventBus;
+    /// use weavegraph::node::NodeContext;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let bus = EventBus::default();
+    /// let ctx = NodeContext::new("review", 2, bus.get_emitter())
+    ///     .with_incoming_from(NodeKind::Custom("draft_a".into()));
+    /// assert_eq!(ctx.incoming_from(), Some(&NodeKind::Custom("draft_a".into())));
+    /// ```
+    #[must_use]
+    pub fn incoming_from(&self) -> Option<&NodeKind> {
+        self.incoming.as_ref()
+    }
+
+    /// Seed of the generator returned by [`rng`](Self::rng).
+    ///
+    /// Derived from the base seed (the runtime-configured seed, or a hash of
+    /// the invocation id), the step, and the node id, so it is stable across
+    /// replays of the same session and distinct per node and step. Runs for
+    /// different [incoming branches](Self::incoming_from) also get distinct seeds.
+    #[must_use]
+    pub fn rng_seed(&self) -> u64 {
+        let base = self.rng_seed.unwrap_or_else(|| {
+            stable_hash(self.invocation_id.as_deref().unwrap_or("").as_bytes(), 0)
+        });
+        let seed = stable_hash(&self.step.to_le_bytes(), base);
+        let seed = stable_hash(self.node_id.as_bytes(), seed);
+        match &self.incoming {
+            Some(origin) => stable_hash(origin.encode().as_bytes(), seed),
+            None => seed,
+        }
+    }
+
+    /// A deterministic random number generator for this node execution.
+    ///
+    /// Every call returns a fresh generator with the same seed, so call it once
+    /// per run and keep the result when several values are needed. Configure the
+    /// base seed with
+    /// [`RuntimeConfig::with_rng_seed`](crate::runtimes::RuntimeConfig::with_rng_seed)
+    /// to make runs reproducible across session ids.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::event_bus::EventBus;
+    /// use weavegraph::node::NodeContext;
+    ///
+    /// let bus = EventBus::default();
+    /// let ctx = NodeContext::new("sampler", 3, bus.get_emitter()).with_rng_seed(7);
+    /// let mut a = ctx.rng();
+    /// let mut b = ctx.rng();
+    /// assert_eq!(a.random_u64(), b.random_u64());
+    /// ```
+    #[must_use]
+    pub fn rng(&self) -> DeterministicRng {
+        DeterministicRng::new(self.rng_seed())
+    }
+
     /// Return the current runtime clock timestamp in Unix milliseconds, if configured.
     #[must_use]
     pub fn now_unix_ms(&self) -> Option<i64> {
@@ -141,6 +551,17 @@ impl NodeContext {
         self.invocation_id.as_deref()
     }
 
+    /// Which execution of the current step this run belongs to, starting at 1.
+    ///
+    /// The runner executes a step again when its checkpoint could not be
+    /// saved (see [`CheckpointFailurePolicy`](crate::runtimes::CheckpointFailurePolicy)).
+    /// Events emitted on a re-execution carry the number as `attempt`
+    /// metadata; see [`RETRACT_SCOPE`](crate::event_bus::RETRACT_SCOPE).
+    #[must_use]
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
     /// Emit a node-scoped event enriched with this context's metadata.
     ///
     /// Creates structured events that include the node's ID and step information,
@@ -159,6 +580,57 @@ impl NodeContext {
         scope: impl Into<String>,
         message: impl Into<String>,
     ) -> Result<(), NodeContextError> {
+        self.emit_event(self.node_event(scope.into(), message.into()))
+    }
+
+    /// Emit a node event carrying `payload` as structured JSON.
+    ///
+    /// The payload is kept as a [`serde_json::Value`] on the event
+    /// ([`NodeEvent::payload`](crate::event_bus::NodeEvent::payload)) rather than
+    /// formatted into the message, so sinks can read fields without parsing text.
+    ///
+    /// ```rust
+    /// use weavegraph::node::NodeContext;
+    /// # fn example(ctx: &NodeContext) -> Result<(), weavegraph::node::NodeContextError> {
+    /// ctx.emit_json("progress", serde_json::json!({ "done": 3, "total": 10 }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn emit_json(
+        &self,
+        scope: impl Into<String>,
+        payload: serde_json::Value,
+    ) -> Result<(), NodeContextError> {
+        let event = match self.node_event(scope.into(), String::new()) {
+            Event::Node(node) => Event::Node(node.with_payload(payload)),
+            other => other,
+        };
+        self.emit_event(event)
+    }
+
+    /// Serialize `payload` to JSON and emit it as with [`emit_json`](Self::emit_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeContextError::PayloadSerialization`] if `payload` cannot be
+    /// represented as JSON, or [`NodeContextError::EventBusUnavailable`] if the
+    /// event cannot be sent.
+    pub fn emit_typed<T: Serialize + ?Sized>(
+        &self,
+        scope: impl Into<String>,
+        payload: &T,
+    ) -> Result<(), NodeContextError> {
+        let scope = scope.into();
+        let value = serde_json::to_value(payload).map_err(|source| {
+            NodeContextError::PayloadSerialization {
+                scope: scope.clone(),
+                source,
+            }
+        })?;
+        self.emit_json(scope, value)
+    }
+
+    fn node_event(&self, scope: String, message: String) -> Event {
         let mut metadata = FxHashMap::default();
         if let Some(invocation_id) = &self.invocation_id {
             metadata.insert(
@@ -169,22 +641,21 @@ impl NodeContext {
         if let Some(now_unix_ms) = self.now_unix_ms() {
             metadata.insert("now_unix_ms".to_string(), serde_json::json!(now_unix_ms));
         }
+        // First attempts stay untagged so existing event streams are unchanged.
+        if self.attempt > 1 {
+            metadata.insert("attempt".to_string(), serde_json::json!(self.attempt));
+        }
 
         if metadata.is_empty() {
-            self.emit_event(Event::node_message_with_meta(
-                self.node_id.clone(),
-                self.step,
-                scope,
-                message,
-            ))
+            Event::node_message_with_meta(self.node_id.clone(), self.step, scope, message)
         } else {
-            self.emit_event(Event::node_message_with_metadata(
+            Event::node_message_with_metadata(
                 self.node_id.clone(),
                 self.step,
                 scope,
                 message,
                 metadata,
-            ))
+            )
         }
     }
 
@@ -249,10 +720,300 @@ impl NodeContext {
         self.emit_event(Event::LLM(event))
     }
 
+    /// Publish an intermediate [`NodePartial`] while this node is still running.
+    ///
+    /// The runner applies incremental partials at micro-barriers as they arrive,
+    /// bumping channel versions and making the results visible to other nodes in
+    /// the same superstep (via [`live_snapshot`](Self::live_snapshot)) and to
+    /// conditional edges evaluated at the end of the step. The partial returned
+    /// from [`Node::run`] is still applied at the regular barrier afterwards.
+    ///
+    /// Frontier commands carried by incremental partials are merged into the
+    /// step's barrier outcome in arrival order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeContextError::PartialStreamUnavailable`] when the node is
+    /// executed outside a runner (for example via a bare
+    /// [`Scheduler::superstep`](crate::schedulers::Scheduler::superstep) call) or
+    /// after the superstep has finished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+    /// use weavegraph::state::StateSnapshot;
+    /// use async_trait::async_trait;
+    /// use serde_json::json;
+    ///
+    /// struct Indexer;
+    ///
+    /// #[async_trait]
+    /// impl Node for Indexer {
+    ///     async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+    ///         for batch in 0..3 {
+    ///             let mut extra = weavegraph::utils::collections::new_extra_map();
+    ///             extra.insert("indexed_batches".into(), json!(batch + 1));
+    ///             ctx.yield_partial(NodePartial::new().with_extra(extra))?;
+    ///         }
+    ///         Ok(NodePartial::default())
+    ///     }
+    /// }
+    /// ```
+    pub fn yield_partial(&self, partial: NodePartial) -> Result<(), NodeContextError> {
+        let stream = self
+            .partial_stream
+            .as_ref()
+            .ok_or(NodeContextError::PartialStreamUnavailable)?;
+        stream
+            .tx
+            .send((stream.kind.clone(), partial))
+            .map_err(|_| NodeContextError::PartialStreamUnavailable)
+    }
+
+    /// Return the latest state snapshot including any micro-barrier updates
+    /// applied during the current superstep.
+    ///
+    /// Returns `None` when no incremental partial stream is attached. The
+    /// snapshot passed to [`Node::run`] stays fixed for the whole step; use this
+    /// accessor to observe progress published by sibling branches.
+    #[must_use]
+    pub fn live_snapshot(&self) -> Option<StateSnapshot> {
+        self.partial_stream
+            .as_ref()
+            .map(|stream| stream.live.borrow().clone())
+    }
+
+    /// Report token usage for this node's current execution.
+    ///
+    /// Calls accumulate; the total is attached to the step's
+    /// [`NodeMetrics`] and aggregated by
+    /// [`AppRunner::session_metrics`](crate::runtimes::AppRunner::session_metrics).
+    /// Does nothing when the node runs outside a scheduler.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use weavegraph::node::{NodeContext, TokenUsage};
+    /// # fn example(ctx: &NodeContext) {
+    /// ctx.record_token_usage(TokenUsage::new(512, 128));
+    /// # }
+    /// ```
+    pub fn record_token_usage(&self, usage: TokenUsage) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_tokens(usage);
+        }
+    }
+
+    /// Add a model call to the audit log.
+    ///
+    /// The call is stored with this run's session, step, attempt, and node id
+    /// as an [`LlmAuditRecord`](crate::llm::LlmAuditRecord). Does nothing
+    /// unless the runtime was configured with
+    /// [`RuntimeConfig::with_llm_audit`](crate::runtimes::RuntimeConfig::with_llm_audit).
+    /// Token usage is not added to [`NodeMetrics`]; call
+    /// [`record_token_usage`](Self::record_token_usage) for that.
+    pub fn record_llm_call(&self, call: LlmCall) {
+        if let Some(log) = &self.llm_audit {
+            log.push(LlmAuditRecord::new(
+                self.invocation_id.clone().unwrap_or_default(),
+                self.step,
+                self.attempt,
+                self.node_id.clone(),
+                call,
+            ));
+        }
+    }
+
+    /// Send `messages` to `provider` and add the call to the audit log.
+    ///
+    /// Records the prompt, `parameters`, the response or error, and the
+    /// latency as described in [`record_llm_call`](Self::record_llm_call).
+    ///
+    /// # Errors
+    ///
+    /// Returns the provider's error unchanged.
+    pub async fn chat_audited(
+        &self,
+        provider: &dyn LlmProvider,
+        messages: &[Message],
+        parameters: serde_json::Value,
+    ) -> Result<LlmResponse, LlmError> {
+        let started = std::time::Instant::now();
+        let result = provider.chat(messages).await;
+        let call = LlmCall::new(messages.to_vec())
+            .with_parameters(parameters)
+            .with_latency(started.elapsed());
+        self.record_llm_call(match &result {
+            Ok(response) => call.with_response(response),
+            Err(error) => call.with_error(error),
+        });
+        result
+    }
+
     fn emit_event(&self, event: Event) -> Result<(), NodeContextError> {
         self.event_emitter
             .emit(event)
-            .map_err(|_| NodeContextError::EventBusUnavailable)
+            .map_err(|_| NodeContextError::EventBusUnavailable)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.record_event();
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Incremental Partials
+// ============================================================================
+
+/// Runtime plumbing that carries incremental [`NodePartial`] updates from a
+/// running node back to the runner.
+///
+/// Created by the runner for each superstep and attached to node contexts via
+/// `SchedulerRunContext::with_partial_stream`.
+#[derive(Clone, Debug)]
+pub(crate) struct PartialStream {
+    kind: NodeKind,
+    tx: flume::Sender<(NodeKind, NodePartial)>,
+    live: watch::Receiver<StateSnapshot>,
+}
+
+impl PartialStream {
+    /// Create a stream handle plus the receiving ends used by the runner.
+    pub(crate) fn channel(
+        initial: StateSnapshot,
+    ) -> (
+        Self,
+        flume::Receiver<(NodeKind, NodePartial)>,
+        watch::Sender<StateSnapshot>,
+    ) {
+        let (tx, rx) = flume::unbounded();
+        let (live_tx, live) = watch::channel(initial);
+        (
+            Self {
+                kind: NodeKind::Start,
+                tx,
+                live,
+            },
+            rx,
+            live_tx,
+        )
+    }
+
+    /// Return a copy of this handle bound to the given node.
+    pub(crate) fn for_node(&self, kind: NodeKind) -> Self {
+        Self {
+            kind,
+            tx: self.tx.clone(),
+            live: self.live.clone(),
+        }
+    }
+}
+
+// ============================================================================
+// Node Metrics
+// ============================================================================
+
+/// Token counts reported by a node through [`NodeContext::record_token_usage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TokenUsage {
+    /// Prompt / input tokens consumed.
+    pub input_tokens: u64,
+    /// Completion / output tokens produced.
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Create a usage record from input and output token counts.
+    #[must_use]
+    pub const fn new(input_tokens: u64, output_tokens: u64) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    /// Input plus output tokens.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        self.input_tokens += rhs.input_tokens;
+        self.output_tokens += rhs.output_tokens;
+    }
+}
+
+/// Execution metrics for one node in one superstep.
+///
+/// Collected by the scheduler for every node that ran and attached to
+/// [`StepReport`](crate::runtimes::StepReport) and
+/// [`Checkpoint`](crate::runtimes::Checkpoint).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct NodeMetrics {
+    /// Wall-clock time spent in [`Node::run`], in microseconds.
+    pub duration_micros: u64,
+    /// Events emitted through the node's [`NodeContext`].
+    pub events_emitted: u64,
+    /// Token usage, if the node reported any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<TokenUsage>,
+}
+
+impl NodeMetrics {
+    /// Create metrics for a run of the given duration.
+    #[must_use]
+    pub fn new(duration: Duration, events_emitted: u64) -> Self {
+        Self {
+            duration_micros: u64::try_from(duration.as_micros()).unwrap_or(u64::MAX),
+            events_emitted,
+            token_usage: None,
+        }
+    }
+
+    /// Attach reported token usage.
+    #[must_use]
+    pub fn with_token_usage(mut self, usage: TokenUsage) -> Self {
+        self.token_usage = Some(usage);
+        self
+    }
+
+    /// Wall-clock time spent in [`Node::run`].
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_micros(self.duration_micros)
+    }
+}
+
+/// Shared counters the scheduler attaches to each node context.
+#[derive(Debug, Default)]
+pub(crate) struct NodeMetricsRecorder {
+    events: AtomicU64,
+    tokens: Mutex<Option<TokenUsage>>,
+}
+
+impl NodeMetricsRecorder {
+    fn record_event(&self) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_tokens(&self, usage: TokenUsage) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        *tokens.get_or_insert_with(TokenUsage::default) += usage;
+    }
+
+    /// Snapshot the counters together with the measured run time.
+    pub(crate) fn finish(&self, elapsed: Duration) -> NodeMetrics {
+        NodeMetrics {
+            token_usage: *self.tokens.lock().unwrap_or_else(|e| e.into_inner()),
+            ..NodeMetrics::new(elapsed, self.events.load(Ordering::Relaxed))
+        }
     }
 }
 
@@ -305,16 +1066,31 @@ impl NodeContext {
 ///     )])
 ///     .with_errors(errors);
 /// ```
-#[derive(Clone, Debug, Default)]
+///
+/// Partials deserialize from JSON objects in which every field is optional,
+/// e.g. `{"messages": [{"role": "assistant", "content": "hi"}]}`, so nodes
+/// written in other languages can return plain JSON.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NodePartial {
     /// Messages to add to the workflow's message history.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub messages: Option<Vec<Message>>,
     /// Additional key-value data to merge into the workflow's extra storage.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<FxHashMap<String, serde_json::Value>>,
     /// Errors to add to the workflow's error collection.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub errors: Option<Vec<ErrorEvent>>,
     /// Frontier commands emitted by the node to influence subsequent routing.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub frontier: Option<FrontierCommand>,
+    /// Commands queued for other nodes' inboxes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<Vec<NodeCommand>>,
+    /// Binary payloads to store in the blobs channel; `None` values remove the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blobs: Option<FxHashMap<String, Option<Blob>>>,
 }
 
 impl NodePartial {
@@ -361,6 +1137,40 @@ impl NodePartial {
         Ok(self)
     }
 
+    /// Store a binary payload under `key` in the blobs channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use weavegraph::channels::Blob;
+    /// use weavegraph::node::NodePartial;
+    ///
+    /// let partial = NodePartial::new()
+    ///     .with_blob("thumbnail", Blob::new(vec![0xff, 0xd8]).with_content_type("image/jpeg"));
+    /// assert_eq!(partial.blobs.unwrap()["thumbnail"].as_ref().unwrap().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn with_blob(mut self, key: impl Into<String>, blob: Blob) -> Self {
+        self.blobs
+            .get_or_insert_with(FxHashMap::default)
+            .insert(key.into(), Some(blob));
+        self
+    }
+
+    /// Remove the given blob keys from state on the next barrier application.
+    #[must_use]
+    pub fn clear_blobs<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let blobs = self.blobs.get_or_insert_with(FxHashMap::default);
+        for key in keys {
+            blobs.insert(key.into(), None);
+        }
+        self
+    }
+
     /// Create a `NodePartial` with one or more errors.
     #[must_use]
     pub fn with_errors(mut self, errors: Vec<ErrorEvent>) -> Self {
@@ -403,6 +1213,29 @@ impl NodePartial {
         self
     }
 
+    /// Queue a command for another node's inbox.
+    ///
+    /// The target reads it with
+    /// [`StateSnapshot::commands_for`](crate::state::StateSnapshot::commands_for)
+    /// the next time it runs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use weavegraph::node::NodePartial;
+    /// use weavegraph::NodeCommand;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let partial = NodePartial::new()
+    ///     .with_command(NodeCommand::new(NodeKind::Custom("indexer".into()), "flush"));
+    /// assert_eq!(partial.commands.unwrap().len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_command(mut self, command: NodeCommand) -> Self {
+        self.commands.get_or_insert_with(Vec::new).push(command);
+        self
+    }
+
     /// Remove the given extra keys from state on the next barrier application.
     ///
     /// Writes `serde_json::Value::Null` markers into the partial. [`MapMerge`](crate::reducers::MapMerge)
@@ -464,6 +1297,7 @@ impl NodePartial {
 /// Errors that can occur when using NodeContext methods.
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
 pub enum NodeContextError {
     /// Event could not be sent due to event bus disconnection or capacity issues.
     #[error("failed to emit event: event bus unavailable")]
@@ -475,6 +1309,36 @@ pub enum NodeContextError {
         )
     )]
     EventBusUnavailable,
+
+    /// Incremental partial could not be delivered because no runner is listening.
+    #[error("failed to yield partial: no incremental partial stream attached")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::node::partial_stream_unavailable),
+            help("Incremental partials are only applied when the node runs under an AppRunner.")
+        )
+    )]
+    PartialStreamUnavailable,
+
+    /// A typed event payload could not be serialized to JSON.
+    #[error("failed to serialize payload for event scope `{scope}`: {source}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::node::payload_serialization),
+            help(
+                "Payloads passed to `emit_typed` must serialize to JSON (e.g. map keys must be strings)."
+            )
+        )
+    )]
+    PayloadSerialization {
+        /// Scope of the event that was being emitted.
+        scope: String,
+        /// Underlying serialization error.
+        #[source]
+        source: serde_json::Error,
+    },
 }
 
 /// Errors that can occur during node execution.
@@ -538,6 +1402,21 @@ pub enum NodeError {
     #[error("event bus error: {0}")]
     #[cfg_attr(feature = "diagnostics", diagnostic(code(weavegraph::node::event_bus)))]
     EventBus(#[from] NodeContextError),
+
+    /// Checkpointer error raised while a node read or wrote session history.
+    #[error("checkpointer error: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::node::checkpointer))
+    )]
+    Checkpointer(#[from] CheckpointerError),
+
+    /// Error with context attached through [`ErrorExt`](crate::errors::ErrorExt).
+    ///
+    /// The outermost context is the message; earlier errors are its causes.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(code(weavegraph::node::context)))]
+    Context(#[from] WeaveError),
 }
 
 impl NodeError {
@@ -0,0 +1,493 @@
+//! Policy-driven redaction of sensitive data in events and checkpoints.
+//!
+//! A [`RedactionPolicy`] is a list of rules applied to event payloads before
+//! they reach sinks or stream subscribers, and to session state before it is
+//! written by a [`Checkpointer`](crate::runtimes::Checkpointer). Configure it
+//! on [`RuntimeConfig::with_redaction`](crate::runtimes::RuntimeConfig::with_redaction).
+//!
+//! Rules come in three flavours:
+//!
+//! - **Keys** ([`redact_key`](RedactionPolicy::redact_key)): replace the value
+//!   of any JSON object field with the given name, at any depth
+//!   (case-insensitive). Use for `api_key`, `password`, `authorization`.
+//! - **Paths** ([`redact_path`](RedactionPolicy::redact_path)): replace the
+//!   value at a dotted path such as `extra.user.email` or `metadata.headers.*`.
+//!   `*` matches any single object key or array index.
+//! - **Patterns** ([`mask_pattern`](RedactionPolicy::mask_pattern)): replace
+//!   regex matches inside string values, message contents, and event messages.
+//!
+//! Paths are rooted at `extra`, `messages`, and `errors` for state, and at
+//! `message` and `metadata` for events. Each message is matched as a
+//! `{"role": .., "content": ..}` object, so `messages.*.content` masks every
+//! message body.
+//!
+//! # Report mode
+//!
+//! [`report_only`](RedactionPolicy::report_only) turns the policy into a dry
+//! run: nothing is modified, and every match is logged through `tracing` and
+//! recorded in [`reported_findings`](RedactionPolicy::reported_findings), so
+//! rules can be validated against production traffic before they are enforced.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serde_json::json;
+//! use weavegraph::redaction::RedactionPolicy;
+//!
+//! let policy = RedactionPolicy::new()
+//!     .redact_key("api_key")
+//!     .mask_pattern("email", r"[\w.+-]+@[\w-]+\.[\w.]+")
+//!     .unwrap();
+//!
+//! let mut value = json!({"api_key": "sk-123", "note": "mail bob@example.com"});
+//! let findings = policy.redact_value(&mut value, "extra");
+//! assert_eq!(value, json!({"api_key": "[REDACTED]", "note": "mail [REDACTED]"}));
+//! assert_eq!(findings.len(), 2);
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::channels::Channel;
+use crate::channels::errors::ErrorEvent;
+use crate::event_bus::{EmitterError, Event, EventEmitter};
+use crate::message::{Message, Role};
+use crate::state::{StateSnapshot, VersionedState};
+
+/// Default text substituted for redacted values.
+pub const DEFAULT_REPLACEMENT: &str = "[REDACTED]";
+
+/// Maximum number of findings retained by a report-only policy.
+pub const MAX_REPORTED_FINDINGS: usize = 1024;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors raised while building a [`RedactionPolicy`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum RedactionError {
+    /// A pattern rule did not compile.
+    #[error("invalid redaction pattern {name:?}: {source}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::redaction::invalid_pattern),
+            help("Patterns use the `regex` crate syntax.")
+        )
+    )]
+    InvalidPattern {
+        /// Name given to the rule.
+        name: String,
+        /// Underlying regex error.
+        #[source]
+        source: regex::Error,
+    },
+}
+
+// ============================================================================
+// Rules and findings
+// ============================================================================
+
+#[derive(Clone, Debug)]
+enum Rule {
+    Key(String),
+    Path(Vec<String>),
+    Pattern { name: String, regex: Regex },
+}
+
+impl Rule {
+    fn label(&self) -> String {
+        match self {
+            Rule::Key(key) => format!("key:{key}"),
+            Rule::Path(path) => format!("path:{}", path.join(".")),
+            Rule::Pattern { name, .. } => format!("pattern:{name}"),
+        }
+    }
+}
+
+/// A single match produced by a [`RedactionPolicy`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct RedactionFinding {
+    /// Rule label: `key:<name>`, `path:<path>`, or `pattern:<name>`.
+    pub rule: String,
+    /// Dotted location of the match, e.g. `extra.user.email` or `messages.0.content`.
+    pub location: String,
+}
+
+/// Whether a policy rewrites data or only reports matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RedactionMode {
+    /// Replace matched data (default).
+    #[default]
+    Enforce,
+    /// Leave data untouched and record what would have been redacted.
+    Report,
+}
+
+// ============================================================================
+// Policy
+// ============================================================================
+
+/// Ordered set of redaction rules. See the [module docs](self).
+#[derive(Clone, Debug)]
+pub struct RedactionPolicy {
+    rules: Vec<Rule>,
+    replacement: String,
+    mode: RedactionMode,
+    reported: Arc<Mutex<Vec<RedactionFinding>>>,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedactionPolicy {
+    /// Create an empty policy in [`RedactionMode::Enforce`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            replacement: DEFAULT_REPLACEMENT.to_string(),
+            mode: RedactionMode::Enforce,
+            reported: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Replace the value of every object field named `key` (case-insensitive).
+    #[must_use]
+    pub fn redact_key(mut self, key: impl Into<String>) -> Self {
+        self.rules.push(Rule::Key(key.into().to_ascii_lowercase()));
+        self
+    }
+
+    /// Replace the value at a dotted path such as `extra.user.email`.
+    ///
+    /// `*` matches any single object key or array index.
+    #[must_use]
+    pub fn redact_path(mut self, path: &str) -> Self {
+        self.rules
+            .push(Rule::Path(path.split('.').map(str::to_string).collect()));
+        self
+    }
+
+    /// Replace matches of `pattern` inside string values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedactionError::InvalidPattern`] if the regex does not compile.
+    pub fn mask_pattern(
+        mut self,
+        name: impl Into<String>,
+        pattern: &str,
+    ) -> Result<Self, RedactionError> {
+        let name = name.into();
+        let regex = Regex::new(pattern).map_err(|source| RedactionError::InvalidPattern {
+            name: name.clone(),
+            source,
+        })?;
+        self.rules.push(Rule::Pattern { name, regex });
+        Ok(self)
+    }
+
+    /// Use `replacement` instead of [`DEFAULT_REPLACEMENT`].
+    #[must_use]
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = replacement.into();
+        self
+    }
+
+    /// Switch to [`RedactionMode::Report`]: report matches without modifying data.
+    #[must_use]
+    pub fn report_only(mut self) -> Self {
+        self.mode = RedactionMode::Report;
+        self
+    }
+
+    /// The configured mode.
+    #[must_use]
+    pub fn mode(&self) -> RedactionMode {
+        self.mode
+    }
+
+    /// Whether the policy has no rules.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rule labels plus mode, used for runtime config hashing.
+    #[must_use]
+    pub fn signature(&self) -> Vec<String> {
+        let mut parts: Vec<String> = self.rules.iter().map(Rule::label).collect();
+        parts.push(format!("mode:{:?}", self.mode));
+        parts
+    }
+
+    /// Findings recorded in report mode (oldest first, at most
+    /// [`MAX_REPORTED_FINDINGS`]). Always empty in enforce mode.
+    #[must_use]
+    pub fn reported_findings(&self) -> Vec<RedactionFinding> {
+        self.reported
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Clear findings recorded in report mode.
+    pub fn clear_reported_findings(&self) {
+        self.reported
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Apply the policy to a JSON value located at `root`.
+    ///
+    /// Returns every match; in report mode the value is left unchanged.
+    pub fn redact_value(&self, value: &mut Value, root: &str) -> Vec<RedactionFinding> {
+        let mut path: Vec<String> = root.split('.').map(str::to_string).collect();
+        let mut findings = Vec::new();
+        self.walk(value, &mut path, &mut findings);
+        self.record(&findings);
+        findings
+    }
+
+    /// Apply the rules to `value` in enforce mode regardless of
+    /// [`mode`](Self::mode), without recording findings.
+    pub(crate) fn mask_value(&self, value: &mut Value, root: &str) {
+        let mut enforced = self.clone();
+        enforced.mode = RedactionMode::Enforce;
+        let mut path: Vec<String> = root.split('.').map(str::to_string).collect();
+        enforced.walk(value, &mut path, &mut Vec::new());
+    }
+
+    /// Apply the policy to an event's message text, metadata, and structured payload.
+    pub fn redact_event(&self, event: &mut Event) -> Vec<RedactionFinding> {
+        let mut findings = Vec::new();
+        let (message, metadata, payload) = event.redactable_parts_mut();
+        self.redact_text(message, "message", &mut findings);
+        if let Some(metadata) = metadata {
+            for (key, value) in metadata.iter_mut() {
+                let mut path = vec!["metadata".to_string(), key.clone()];
+                self.visit_field(key, value, &mut path, &mut findings);
+            }
+        }
+        if let Some(payload) = payload {
+            self.walk(payload, &mut vec!["payload".to_string()], &mut findings);
+        }
+        self.record(&findings);
+        findings
+    }
+
+    /// Apply the policy to messages, `extra` entries, and error contexts.
+    ///
+    /// Channel versions are not changed; the runner applies this to the copy
+    /// of state that is persisted.
+    pub fn redact_state(&self, state: &mut VersionedState) -> Vec<RedactionFinding> {
+        self.redact_channels(
+            state.messages.get_mut(),
+            state.extra.get_mut(),
+            state.errors.get_mut(),
+        )
+    }
+
+    /// Apply the policy to a [`StateSnapshot`] the same way as
+    /// [`redact_state`](Self::redact_state).
+    pub fn redact_snapshot(&self, snapshot: &mut StateSnapshot) -> Vec<RedactionFinding> {
+        self.redact_channels(
+            &mut snapshot.messages,
+            &mut snapshot.extra,
+            &mut snapshot.errors,
+        )
+    }
+
+    fn redact_channels(
+        &self,
+        messages: &mut [Message],
+        extra: &mut FxHashMap<String, Value>,
+        errors: &mut [ErrorEvent],
+    ) -> Vec<RedactionFinding> {
+        let mut findings = Vec::new();
+        let structural = self
+            .rules
+            .iter()
+            .any(|rule| !matches!(rule, Rule::Pattern { .. }));
+        for (index, message) in messages.iter_mut().enumerate() {
+            if !structural {
+                self.redact_text(
+                    &mut message.content,
+                    &format!("messages.{index}.content"),
+                    &mut findings,
+                );
+                continue;
+            }
+            // Key and path rules see the message as `{"role": .., "content": ..}`.
+            let mut view = serde_json::json!({
+                "role": message.role.as_str(),
+                "content": std::mem::take(&mut message.content),
+            });
+            let mut path = vec!["messages".to_string(), index.to_string()];
+            self.walk(&mut view, &mut path, &mut findings);
+            if let (Some(role), Some(content)) = (view["role"].as_str(), view["content"].as_str()) {
+                message.role = Role::from(role);
+                message.content = content.to_string();
+            }
+        }
+        for (key, value) in extra.iter_mut() {
+            let mut path = vec!["extra".to_string(), key.clone()];
+            self.visit_field(key, value, &mut path, &mut findings);
+        }
+        for (index, error) in errors.iter_mut().enumerate() {
+            let mut path = vec![
+                "errors".to_string(),
+                index.to_string(),
+                "context".to_string(),
+            ];
+            self.walk(&mut error.context, &mut path, &mut findings);
+        }
+        self.record(&findings);
+        findings
+    }
+
+    fn enforce(&self) -> bool {
+        self.mode == RedactionMode::Enforce
+    }
+
+    fn record(&self, findings: &[RedactionFinding]) {
+        if self.enforce() || findings.is_empty() {
+            return;
+        }
+        let mut reported = self.reported.lock().unwrap_or_else(|e| e.into_inner());
+        for finding in findings {
+            tracing::info!(rule = %finding.rule, location = %finding.location, "redaction match (report only)");
+            if reported.len() < MAX_REPORTED_FINDINGS {
+                reported.push(finding.clone());
+            }
+        }
+    }
+
+    /// Check key/path rules for an object field, then recurse into its value.
+    fn visit_field(
+        &self,
+        key: &str,
+        value: &mut Value,
+        path: &mut Vec<String>,
+        findings: &mut Vec<RedactionFinding>,
+    ) {
+        let matched = self.rules.iter().find(|rule| match rule {
+            Rule::Key(name) => key.eq_ignore_ascii_case(name),
+            Rule::Path(pattern) => path_matches(pattern, path),
+            Rule::Pattern { .. } => false,
+        });
+        if let Some(rule) = matched {
+            findings.push(RedactionFinding {
+                rule: rule.label(),
+                location: path.join("."),
+            });
+            if self.enforce() {
+                *value = Value::String(self.replacement.clone());
+            }
+            return;
+        }
+        self.walk(value, path, findings);
+    }
+
+    fn walk(
+        &self,
+        value: &mut Value,
+        path: &mut Vec<String>,
+        findings: &mut Vec<RedactionFinding>,
+    ) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    path.push(key.clone());
+                    self.visit_field(key, child, path, findings);
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (index, child) in items.iter_mut().enumerate() {
+                    path.push(index.to_string());
+                    let matched = self.rules.iter().find(
+                        |rule| matches!(rule, Rule::Path(pattern) if path_matches(pattern, path)),
+                    );
+                    if let Some(rule) = matched {
+                        findings.push(RedactionFinding {
+                            rule: rule.label(),
+                            location: path.join("."),
+                        });
+                        if self.enforce() {
+                            *child = Value::String(self.replacement.clone());
+                        }
+                    } else {
+                        self.walk(child, path, findings);
+                    }
+                    path.pop();
+                }
+            }
+            Value::String(text) => {
+                let location = path.join(".");
+                self.redact_text(text, &location, findings);
+            }
+            _ => {}
+        }
+    }
+
+    fn redact_text(&self, text: &mut String, location: &str, findings: &mut Vec<RedactionFinding>) {
+        for rule in &self.rules {
+            let Rule::Pattern { regex, .. } = rule else {
+                continue;
+            };
+            if !regex.is_match(text) {
+                continue;
+            }
+            findings.push(RedactionFinding {
+                rule: rule.label(),
+                location: location.to_string(),
+            });
+            if self.enforce() {
+                *text = regex
+                    .replace_all(text, regex::NoExpand(&self.replacement))
+                    .into_owned();
+            }
+        }
+    }
+}
+
+fn path_matches(pattern: &[String], path: &[String]) -> bool {
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(expected, actual)| expected == "*" || expected == actual)
+}
+
+// ============================================================================
+// Event bus integration
+// ============================================================================
+
+/// Emitter wrapper that redacts events before they reach the hub.
+#[derive(Debug)]
+pub(crate) struct RedactingEmitter {
+    pub(crate) inner: Arc<dyn EventEmitter>,
+    pub(crate) policy: Arc<RedactionPolicy>,
+}
+
+impl EventEmitter for RedactingEmitter {
+    fn emit(&self, mut event: Event) -> Result<(), EmitterError> {
+        self.policy.redact_event(&mut event);
+        self.inner.emit(event)
+    }
+}
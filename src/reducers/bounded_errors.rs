@@ -0,0 +1,96 @@
+//! Reducer that appends [`ErrorEvent`](crate::channels::errors::ErrorEvent) entries with severity filtering and per-severity retention limits.
+use std::collections::BTreeMap;
+
+use super::Reducer;
+use crate::{
+    channels::{Channel, errors::ErrorSeverity},
+    node::NodePartial,
+    state::VersionedState,
+};
+
+/// Errors-channel reducer that drops events below a minimum severity and keeps
+/// at most a fixed number of events per severity, discarding the oldest first.
+///
+/// Use it in place of [`AddErrors`](crate::reducers::AddErrors) so long
+/// sessions do not accumulate thousands of low-severity warnings.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::channels::errors::ErrorSeverity;
+/// use weavegraph::graphs::GraphBuilder;
+/// use weavegraph::reducers::BoundedErrors;
+///
+/// let retention = BoundedErrors::new()
+///     .with_min_severity(ErrorSeverity::Warning)
+///     .with_limit(ErrorSeverity::Warning, 100)
+///     .with_limit(ErrorSeverity::Error, 500);
+/// let builder = GraphBuilder::new().with_bounded_errors(retention);
+/// ```
+#[derive(Debug, Default, PartialEq, Clone, Hash, Eq)]
+pub struct BoundedErrors {
+    min_severity: Option<ErrorSeverity>,
+    limits: BTreeMap<ErrorSeverity, usize>,
+}
+
+impl BoundedErrors {
+    /// Keep every event, like [`AddErrors`](crate::reducers::AddErrors).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop incoming events less severe than `severity`.
+    #[must_use]
+    pub fn with_min_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Keep at most `max` events of `severity`, discarding the oldest first.
+    #[must_use]
+    pub fn with_limit(mut self, severity: ErrorSeverity, max: usize) -> Self {
+        self.limits.insert(severity, max);
+        self
+    }
+
+    /// The retention limit for `severity`, if one is set.
+    #[must_use]
+    pub fn limit(&self, severity: ErrorSeverity) -> Option<usize> {
+        self.limits.get(&severity).copied()
+    }
+
+    fn admits(&self, severity: ErrorSeverity) -> bool {
+        self.min_severity.is_none_or(|min| severity >= min)
+    }
+}
+
+impl Reducer for BoundedErrors {
+    fn apply(&self, state: &mut VersionedState, update: &NodePartial) {
+        let Some(incoming) = &update.errors else {
+            return;
+        };
+        let events = state.errors.get_mut();
+        events.extend(
+            incoming
+                .iter()
+                .filter(|event| self.admits(event.severity))
+                .cloned(),
+        );
+        for (&severity, &max) in &self.limits {
+            let count = events.iter().filter(|e| e.severity == severity).count();
+            let mut excess = count.saturating_sub(max);
+            if excess == 0 {
+                continue;
+            }
+            events.retain(|event| {
+                if excess > 0 && event.severity == severity {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    }
+}
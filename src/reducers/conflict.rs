@@ -0,0 +1,227 @@
+//! Policies for resolving concurrent writes to the same `extra` key.
+//!
+//! When several nodes run in one superstep and write the same `extra` key,
+//! the barrier has to pick a value. Without configuration the last writer in
+//! execution order wins, as it always has. A [`ConflictPolicy`] registered on
+//! the [`ReducerRegistry`](super::ReducerRegistry) (or through
+//! [`GraphBuilder::with_conflict_policy`](crate::graphs::GraphBuilder::with_conflict_policy))
+//! changes that per key or for every key.
+//!
+//! Writes of identical values are not conflicts. Every real conflict is
+//! recorded in [`BarrierOutcome::conflicts`](crate::app::BarrierOutcome::conflicts)
+//! and surfaced by the runner as an
+//! [`EXTRA_CONFLICT_SCOPE`](crate::event_bus::EXTRA_CONFLICT_SCOPE) diagnostic.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde_json::json;
+//! use weavegraph::reducers::{ConflictPolicy, ExtraWrite};
+//! use weavegraph::types::NodeKind;
+//!
+//! let writes = vec![
+//!     ExtraWrite::new(NodeKind::Custom("a".into()), json!([1])),
+//!     ExtraWrite::new(NodeKind::Custom("b".into()), json!(2)),
+//! ];
+//! let merged = ConflictPolicy::MergeArray.resolve("results", &writes).unwrap();
+//! assert_eq!(merged, json!([1, 2]));
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::types::NodeKind;
+
+/// Custom merge function: receives the key and the writes in execution order.
+pub type ConflictMerger = Arc<dyn Fn(&str, &[ExtraWrite]) -> Result<Value, String> + Send + Sync>;
+
+/// One node's write to an `extra` key during a barrier.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ExtraWrite {
+    /// Node that produced the value.
+    pub node: NodeKind,
+    /// Value the node wrote.
+    pub value: Value,
+}
+
+impl ExtraWrite {
+    /// Create a write record.
+    #[must_use]
+    pub fn new(node: NodeKind, value: Value) -> Self {
+        Self { node, value }
+    }
+}
+
+/// How to resolve different values written to one `extra` key in one superstep.
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum ConflictPolicy {
+    /// The last writer in execution order wins (historical behaviour).
+    #[default]
+    LastWins,
+    /// The first writer in execution order wins.
+    FirstWins,
+    /// Fail the step with [`ExtraConflictError::Rejected`].
+    Error,
+    /// Concatenate the writes into one array; array values are flattened one level.
+    MergeArray,
+    /// Delegate to a user function.
+    Custom {
+        /// Stable label used in diagnostics and graph metadata.
+        label: &'static str,
+        /// The merge function.
+        merger: ConflictMerger,
+    },
+}
+
+impl ConflictPolicy {
+    /// Build a [`ConflictPolicy::Custom`] policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use weavegraph::reducers::ConflictPolicy;
+    ///
+    /// // Keep the largest number.
+    /// let max = ConflictPolicy::custom("max", |_key, writes| {
+    ///     writes
+    ///         .iter()
+    ///         .filter_map(|w| w.value.as_i64())
+    ///         .max()
+    ///         .map(|n| json!(n))
+    ///         .ok_or_else(|| "expected integers".to_string())
+    /// });
+    /// assert_eq!(max.label(), "custom:max");
+    /// ```
+    #[must_use]
+    pub fn custom<F>(label: &'static str, merger: F) -> Self
+    where
+        F: Fn(&str, &[ExtraWrite]) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        Self::Custom {
+            label,
+            merger: Arc::new(merger),
+        }
+    }
+
+    /// Stable label for diagnostics and metadata hashing.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::LastWins => "last_wins".to_string(),
+            Self::FirstWins => "first_wins".to_string(),
+            Self::Error => "error".to_string(),
+            Self::MergeArray => "merge_array".to_string(),
+            Self::Custom { label, .. } => format!("custom:{label}"),
+        }
+    }
+
+    /// Resolve `writes` (in execution order, at least one) to a single value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExtraConflictError::Rejected`] for [`ConflictPolicy::Error`]
+    /// and [`ExtraConflictError::MergeFailed`] when a custom merger fails.
+    pub fn resolve(&self, key: &str, writes: &[ExtraWrite]) -> Result<Value, ExtraConflictError> {
+        let nodes = || writes.iter().map(|w| w.node.clone()).collect::<Vec<_>>();
+        match self {
+            Self::LastWins => Ok(writes.last().map_or(Value::Null, |w| w.value.clone())),
+            Self::FirstWins => Ok(writes.first().map_or(Value::Null, |w| w.value.clone())),
+            Self::Error => Err(ExtraConflictError::Rejected {
+                key: key.to_string(),
+                nodes: nodes(),
+            }),
+            Self::MergeArray => {
+                let mut merged = Vec::new();
+                for write in writes {
+                    match &write.value {
+                        Value::Array(items) => merged.extend(items.iter().cloned()),
+                        other => merged.push(other.clone()),
+                    }
+                }
+                Ok(Value::Array(merged))
+            }
+            Self::Custom { merger, .. } => {
+                merger(key, writes).map_err(|message| ExtraConflictError::MergeFailed {
+                    key: key.to_string(),
+                    nodes: nodes(),
+                    message,
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+/// A conflicting write detected at a barrier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExtraConflict {
+    /// The contested `extra` key.
+    pub key: String,
+    /// Writers in execution order.
+    pub nodes: Vec<NodeKind>,
+    /// Label of the policy that resolved it (see [`ConflictPolicy::label`]).
+    pub policy: String,
+}
+
+impl fmt::Display for ExtraConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes: Vec<String> = self.nodes.iter().map(NodeKind::to_string).collect();
+        write!(
+            f,
+            "key={} nodes={} policy={}",
+            self.key,
+            nodes.join(","),
+            self.policy
+        )
+    }
+}
+
+/// Errors raised while resolving conflicting `extra` writes.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum ExtraConflictError {
+    /// The key's policy is [`ConflictPolicy::Error`] and several nodes wrote it.
+    #[error("conflicting writes to extra key {key:?} from {nodes:?}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::reducers::extra_conflict),
+            help(
+                "Write to distinct keys from parallel branches, or choose a merging ConflictPolicy."
+            )
+        )
+    )]
+    Rejected {
+        /// The contested key.
+        key: String,
+        /// Writers in execution order.
+        nodes: Vec<NodeKind>,
+    },
+    /// A custom merger returned an error.
+    #[error("custom merge of extra key {key:?} from {nodes:?} failed: {message}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::reducers::extra_merge_failed))
+    )]
+    MergeFailed {
+        /// The contested key.
+        key: String,
+        /// Writers in execution order.
+        nodes: Vec<NodeKind>,
+        /// Error reported by the merger.
+        message: String,
+    },
+}
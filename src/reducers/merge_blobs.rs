@@ -0,0 +1,29 @@
+//! Reducer that merges incoming binary payloads into the blobs channel.
+use super::Reducer;
+use crate::{channels::Channel, node::NodePartial, state::VersionedState};
+
+/// Reducer that merges blob updates from a [`NodePartial`] into the state blobs channel.
+///
+/// A `None` entry, as written by
+/// [`NodePartial::clear_blobs`](crate::node::NodePartial::clear_blobs), removes the key.
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+pub struct MergeBlobs;
+impl Reducer for MergeBlobs {
+    fn apply(&self, state: &mut VersionedState, update: &NodePartial) {
+        if let Some(blobs_update) = &update.blobs
+            && !blobs_update.is_empty()
+        {
+            let state_map = state.blobs.get_mut();
+            for (k, v) in blobs_update.iter() {
+                match v {
+                    Some(blob) => {
+                        state_map.insert(k.clone(), blob.clone());
+                    }
+                    None => {
+                        state_map.remove(k);
+                    }
+                }
+            }
+        }
+    }
+}
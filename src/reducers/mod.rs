@@ -1,12 +1,18 @@
 //! State reducers that apply [`NodePartial`] updates to [`VersionedState`].
 mod add_errors;
 mod add_messages;
+mod bounded_errors;
+mod conflict;
 mod map_merge;
+mod merge_blobs;
 mod reducer_registry;
 
 pub use add_errors::AddErrors;
 pub use add_messages::AddMessages;
+pub use bounded_errors::BoundedErrors;
+pub use conflict::*;
 pub use map_merge::MapMerge;
+pub use merge_blobs::MergeBlobs;
 pub use reducer_registry::*;
 
 use crate::node::NodePartial;
@@ -15,7 +21,8 @@ use crate::types::ChannelType;
 use thiserror::Error;
 
 /// Unified reducer trait: every reducer mutates VersionedState using a NodePartial delta.
-/// Channels currently implemented: messages (append) and extra (shallow JSON map merge).
+/// Channels currently implemented: messages (append), extra (shallow JSON map merge),
+/// errors (append), and blobs (keyed replace).
 pub trait Reducer: Send + Sync {
     /// Stable-ish reducer identity included in graph definition metadata.
     ///
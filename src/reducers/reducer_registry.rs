@@ -1,9 +1,12 @@
 use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::{
     node::NodePartial,
-    reducers::{AddErrors, AddMessages, MapMerge, Reducer, ReducerError},
+    reducers::{
+        AddErrors, AddMessages, ConflictPolicy, MapMerge, MergeBlobs, Reducer, ReducerError,
+    },
     state::VersionedState,
     types::ChannelType,
 };
@@ -13,6 +16,8 @@ use tracing::instrument;
 #[derive(Clone)]
 pub struct ReducerRegistry {
     reducer_map: FxHashMap<ChannelType, Vec<Arc<dyn Reducer>>>,
+    default_conflict_policy: ConflictPolicy,
+    conflict_policies: FxHashMap<String, ConflictPolicy>,
 }
 
 /// Guard that checks whether a NodePartial actually has meaningful data
@@ -35,6 +40,11 @@ fn channel_guard(channel: &ChannelType, partial: &NodePartial) -> bool {
             .as_ref()
             .map(|v| !v.is_empty())
             .unwrap_or(false),
+        ChannelType::Blob => partial
+            .blobs
+            .as_ref()
+            .map(|m| !m.is_empty())
+            .unwrap_or(false),
     }
 }
 
@@ -44,7 +54,8 @@ impl Default for ReducerRegistry {
         registry
             .register(ChannelType::Message, Arc::new(AddMessages))
             .register(ChannelType::Extra, Arc::new(MapMerge))
-            .register(ChannelType::Error, Arc::new(AddErrors));
+            .register(ChannelType::Error, Arc::new(AddErrors))
+            .register(ChannelType::Blob, Arc::new(MergeBlobs));
         registry
     }
 }
@@ -54,6 +65,8 @@ impl ReducerRegistry {
     pub fn new() -> Self {
         Self {
             reducer_map: FxHashMap::default(),
+            default_conflict_policy: ConflictPolicy::default(),
+            conflict_policies: FxHashMap::default(),
         }
     }
 
@@ -74,6 +87,12 @@ impl ReducerRegistry {
         self
     }
 
+    /// Replaces every reducer registered for `channel` with `reducer`.
+    pub fn replace(&mut self, channel: ChannelType, reducer: Arc<dyn Reducer>) -> &mut Self {
+        self.reducer_map.insert(channel, vec![reducer]);
+        self
+    }
+
     /// Builder-style method for registering a reducer.
     ///
     /// This is a convenience method that consumes self and returns it,
@@ -100,6 +119,84 @@ impl ReducerRegistry {
         self
     }
 
+    /// Set the [`ConflictPolicy`] for concurrent writes to one `extra` key.
+    ///
+    /// # Examples
+    /// ```
+    /// use weavegraph::reducers::{ConflictPolicy, ReducerRegistry};
+    ///
+    /// let registry = ReducerRegistry::default()
+    ///     .with_conflict_policy("results", ConflictPolicy::MergeArray);
+    /// assert_eq!(registry.conflict_policy("results").label(), "merge_array");
+    /// assert_eq!(registry.conflict_policy("other").label(), "last_wins");
+    /// ```
+    #[must_use]
+    pub fn with_conflict_policy(mut self, key: impl Into<String>, policy: ConflictPolicy) -> Self {
+        self.set_conflict_policy(key, policy);
+        self
+    }
+
+    /// In-place variant of [`with_conflict_policy`](Self::with_conflict_policy).
+    pub fn set_conflict_policy(
+        &mut self,
+        key: impl Into<String>,
+        policy: ConflictPolicy,
+    ) -> &mut Self {
+        self.conflict_policies.insert(key.into(), policy);
+        self
+    }
+
+    /// Set the [`ConflictPolicy`] used for keys without a specific policy.
+    ///
+    /// Defaults to [`ConflictPolicy::LastWins`].
+    #[must_use]
+    pub fn with_default_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.set_default_conflict_policy(policy);
+        self
+    }
+
+    /// In-place variant of [`with_default_conflict_policy`](Self::with_default_conflict_policy).
+    pub fn set_default_conflict_policy(&mut self, policy: ConflictPolicy) -> &mut Self {
+        self.default_conflict_policy = policy;
+        self
+    }
+
+    /// The [`ConflictPolicy`] that applies to `key`.
+    #[must_use]
+    pub fn conflict_policy(&self, key: &str) -> &ConflictPolicy {
+        self.conflict_policies
+            .get(key)
+            .unwrap_or(&self.default_conflict_policy)
+    }
+
+    /// Reducer [`definition_label`](Reducer::definition_label)s per channel name,
+    /// in the order they are applied.
+    #[must_use]
+    pub fn reducer_labels(&self) -> BTreeMap<String, Vec<String>> {
+        self.reducer_map
+            .iter()
+            .map(|(channel, reducers)| {
+                let labels = reducers
+                    .iter()
+                    .map(|reducer| reducer.definition_label().to_string())
+                    .collect();
+                (channel.to_string(), labels)
+            })
+            .collect()
+    }
+
+    /// Conflict policy labels per `extra` key, with the default policy under `"*"`.
+    #[must_use]
+    pub fn conflict_policy_labels(&self) -> BTreeMap<String, String> {
+        let mut labels: BTreeMap<String, String> = self
+            .conflict_policies
+            .iter()
+            .map(|(key, policy)| (key.clone(), policy.label()))
+            .collect();
+        labels.insert("*".to_string(), self.default_conflict_policy.label());
+        labels
+    }
+
     /// Return a deterministic summary of registered reducers for metadata hashing.
     ///
     /// Reducer labels are recorded in registration order for each channel. It
@@ -120,6 +217,19 @@ impl ReducerRegistry {
                 format!("{}:[{}]", channel, labels)
             })
             .collect();
+        // Only non-default conflict policies are recorded so graphs that never
+        // configure them keep their existing metadata hash.
+        if !matches!(self.default_conflict_policy, ConflictPolicy::LastWins) {
+            signature.push(format!(
+                "conflict:*={}",
+                self.default_conflict_policy.label()
+            ));
+        }
+        signature.extend(
+            self.conflict_policies
+                .iter()
+                .map(|(key, policy)| format!("conflict:{key}={}", policy.label())),
+        );
         signature.sort();
         signature
     }
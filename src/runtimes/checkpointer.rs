@@ -21,7 +21,9 @@
 //! - **InMemoryCheckpointer**: Stores only the latest checkpoint per session
 //!   (implicit retention; no history).
 //! - **SQLiteCheckpointer**: Stores full step history for durable audit and
-//!   replay; use external SQL maintenance for long‑running deployments.
+//!   replay; prune it with [`Checkpointer::delete_steps_before`].
+//! - All built-in backends implement [`Checkpointer::delete_session`] and
+//!   [`Checkpointer::stats`] for retention policies and erasure requests.
 //!
 //! See type‑level docs for cleanup guidance.
 
@@ -30,8 +32,17 @@ use chrono::{DateTime, Utc};
 use rustc_hash::FxHashMap;
 use std::sync::RwLock;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    runtimes::session::SessionState, schedulers::SchedulerState, state::VersionedState,
+    channels::{Channel, errors::ErrorEvent},
+    llm::LlmAuditRecord,
+    message::Message,
+    node::NodeMetrics,
+    runtimes::session::SessionState,
+    schedulers::SchedulerState,
+    state::StateSnapshot,
+    state::VersionedState,
     types::NodeKind,
 };
 
@@ -51,6 +62,12 @@ pub struct Checkpoint {
     pub frontier: Vec<NodeKind>,
     /// Scheduler version-gating state for change detection.
     pub versions_seen: FxHashMap<String, FxHashMap<String, u64>>, // scheduler gating
+    /// Pending wait-for-all join arrivals (`SchedulerState::pending_joins`).
+    pub pending_joins: FxHashMap<String, FxHashMap<String, u64>>,
+    /// Origins of per-incoming-edge frontier slots (`SchedulerState::frontier_origins`).
+    pub frontier_origins: Vec<Option<String>>,
+    /// Run counts of nodes with quotas (`SchedulerState::run_counts`).
+    pub run_counts: FxHashMap<String, u64>,
     /// Maximum concurrent nodes configured for this session.
     pub concurrency_limit: usize,
     /// Timestamp at which this checkpoint was created.
@@ -61,6 +78,8 @@ pub struct Checkpoint {
     pub skipped_nodes: Vec<NodeKind>,
     /// Channels that were updated in this step (empty for step 0)
     pub updated_channels: Vec<String>,
+    /// Execution metrics for the nodes that ran in this step (empty for step 0)
+    pub node_metrics: Vec<(NodeKind, NodeMetrics)>,
 }
 
 impl Checkpoint {
@@ -95,11 +114,15 @@ impl Checkpoint {
             state: session.state.clone(),
             frontier: session.frontier.clone(),
             versions_seen: session.scheduler_state.versions_seen.clone(),
+            pending_joins: session.scheduler_state.pending_joins.clone(),
+            frontier_origins: session.scheduler_state.frontier_origins.clone(),
+            run_counts: session.scheduler_state.run_counts.clone(),
             concurrency_limit: session.scheduler.concurrency_limit,
             created_at: Utc::now(),
             ran_nodes: vec![], // No execution history for raw session state
             skipped_nodes: vec![],
             updated_channels: vec![],
+            node_metrics: vec![],
         }
     }
 
@@ -142,6 +165,9 @@ impl Checkpoint {
             state: session_state.state.clone(),
             frontier: session_state.frontier.clone(),
             versions_seen: session_state.scheduler_state.versions_seen.clone(),
+            pending_joins: session_state.scheduler_state.pending_joins.clone(),
+            frontier_origins: session_state.scheduler_state.frontier_origins.clone(),
+            run_counts: session_state.scheduler_state.run_counts.clone(),
             concurrency_limit: session_state.scheduler.concurrency_limit,
             created_at: Utc::now(),
             ran_nodes: step_report.ran_nodes.clone(),
@@ -152,6 +178,7 @@ impl Checkpoint {
                 .iter()
                 .map(|s| (*s).to_string())
                 .collect(),
+            node_metrics: step_report.node_metrics.clone(),
         }
     }
 }
@@ -191,6 +218,42 @@ pub enum CheckpointerError {
         message: String,
     },
 
+    /// An optimistic-concurrency save found a different latest step.
+    #[error(
+        "concurrency conflict for session {session_id}: expected step {expected}, found {found}"
+    )]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::checkpointer::conflict),
+            help("Another writer advanced the session; reload the latest checkpoint and retry.")
+        )
+    )]
+    Conflict {
+        /// The session whose latest step changed.
+        session_id: String,
+        /// Latest step the writer expected.
+        expected: u64,
+        /// Latest step found in storage.
+        found: u64,
+    },
+
+    /// The backend does not implement the requested operation.
+    #[error("checkpointer operation not supported: {operation}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::checkpointer::unsupported),
+            help(
+                "Use a backend that implements `{operation}` or override it in your checkpointer."
+            )
+        )
+    )]
+    Unsupported {
+        /// Name of the unsupported trait operation.
+        operation: &'static str,
+    },
+
     /// Other checkpointer errors.
     #[error("checkpointer error: {message}")]
     #[cfg_attr(
@@ -203,6 +266,164 @@ pub enum CheckpointerError {
     },
 }
 
+/// Per-session storage statistics reported by [`Checkpointer::stats`].
+///
+/// Sizes are approximate: backends report the byte length of the serialized
+/// checkpoint payloads they store, excluding indexes and row overhead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionStats {
+    /// Session identifier.
+    pub session_id: String,
+    /// Number of stored checkpoints (step rows) for the session.
+    pub step_count: u64,
+    /// Step number of the latest checkpoint.
+    pub latest_step: u64,
+    /// Approximate serialized payload size in bytes.
+    pub approx_bytes: u64,
+}
+
+impl SessionStats {
+    /// Construct a statistics entry.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        step_count: u64,
+        latest_step: u64,
+        approx_bytes: u64,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            step_count,
+            latest_step,
+            approx_bytes,
+        }
+    }
+}
+
+/// Messages and errors removed from a session's in-memory state by compaction.
+///
+/// Archives are numbered per session from 0. The offsets give the logical
+/// position of the first archived item in the session's full history, so
+/// concatenating archives in sequence order followed by the live state
+/// reconstructs every message and error.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ChannelArchive {
+    /// Session the archived items belong to.
+    pub session_id: String,
+    /// Archive number within the session, starting at 0.
+    pub sequence: u64,
+    /// Step at which the session was compacted.
+    pub step: u64,
+    /// Logical index of the first archived message.
+    pub messages_offset: u64,
+    /// Archived messages, oldest first.
+    pub messages: Vec<Message>,
+    /// Logical index of the first archived error.
+    pub errors_offset: u64,
+    /// Archived error events, oldest first.
+    pub errors: Vec<ErrorEvent>,
+    /// When the archive was written.
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChannelArchive {
+    /// Construct an empty archive created now; fill in offsets and items.
+    #[must_use]
+    pub fn new(session_id: impl Into<String>, sequence: u64, step: u64) -> Self {
+        Self {
+            session_id: session_id.into(),
+            sequence,
+            step,
+            messages_offset: 0,
+            messages: Vec::new(),
+            errors_offset: 0,
+            errors: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// The state snapshot the nodes of one superstep received as input.
+///
+/// Written by runners with node input capture enabled (see
+/// [`RuntimeConfig::with_node_input_capture`](crate::runtimes::RuntimeConfig::with_node_input_capture)).
+/// Every node scheduled in a superstep reads the same pre-barrier snapshot,
+/// so one capture covers all of them. The snapshot has the runtime's
+/// redaction policy applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeInputCapture {
+    /// Session the capture belongs to.
+    pub session_id: String,
+    /// Superstep the nodes ran in.
+    pub step: u64,
+    /// Nodes that ran with this input, in scheduling order.
+    pub nodes: Vec<NodeKind>,
+    /// The snapshot passed to [`Node::run`](crate::node::Node::run).
+    pub input: StateSnapshot,
+    /// When the capture was taken.
+    pub captured_at: DateTime<Utc>,
+}
+
+impl NodeInputCapture {
+    /// Capture `input` for `nodes` at `step`, timestamped now.
+    #[must_use]
+    pub fn new(
+        session_id: impl Into<String>,
+        step: u64,
+        nodes: Vec<NodeKind>,
+        input: StateSnapshot,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            step,
+            nodes,
+            input,
+            captured_at: Utc::now(),
+        }
+    }
+}
+
+/// A persisted message found by [`Checkpointer::search_messages`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct MessageMatch {
+    /// Session the message belongs to.
+    pub session_id: String,
+    /// Step of the checkpoint the message was indexed from.
+    pub step: u64,
+    /// Position of the message in the session's messages channel.
+    pub index: u64,
+    /// The matching message.
+    pub message: Message,
+}
+
+impl MessageMatch {
+    /// A match for `message` at `index` of `session_id`'s checkpoint at `step`.
+    #[must_use]
+    pub fn new(session_id: impl Into<String>, step: u64, index: u64, message: Message) -> Self {
+        Self {
+            session_id: session_id.into(),
+            step,
+            index,
+            message,
+        }
+    }
+}
+
+/// Lowercased words of `text`, split on anything that is not alphanumeric.
+///
+/// Mirrors the word splitting of the SQLite and PostgreSQL indexes so the
+/// in-memory backend matches the same messages.
+pub(crate) fn search_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
 /// Selects the backing implementation of the `Checkpointer` trait.
 ///
 /// Variants:
@@ -352,6 +573,179 @@ pub trait Checkpointer: Send + Sync {
     ///
     /// * `Backend` - Storage backend error
     async fn list_sessions(&self) -> Result<Vec<String>>;
+
+    /// Delete every checkpoint stored for a session.
+    ///
+    /// Intended for data-retention requirements (e.g. erasure requests). The
+    /// operation is idempotent: deleting an unknown session succeeds and
+    /// returns `false`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The session existed and was removed
+    /// * `Ok(false)` - No data was stored for the session
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not implement deletion (default)
+    async fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let _ = session_id;
+        Err(CheckpointerError::Unsupported {
+            operation: "delete_session",
+        })
+    }
+
+    /// Delete historical checkpoints with a step number lower than `step`.
+    ///
+    /// The latest checkpoint is always retained so the session stays
+    /// resumable. Backends that only keep the latest checkpoint have nothing
+    /// to prune and return `0`.
+    ///
+    /// # Returns
+    ///
+    /// The number of checkpoints removed.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not implement pruning (default)
+    async fn delete_steps_before(&self, session_id: &str, step: u64) -> Result<u64> {
+        let _ = (session_id, step);
+        Err(CheckpointerError::Unsupported {
+            operation: "delete_steps_before",
+        })
+    }
+
+    /// Report per-session step counts and approximate storage sizes.
+    ///
+    /// Results are ordered by session ID.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not report statistics (default)
+    async fn stats(&self) -> Result<Vec<SessionStats>> {
+        Err(CheckpointerError::Unsupported { operation: "stats" })
+    }
+
+    /// Store a [`ChannelArchive`] written by session compaction.
+    ///
+    /// Saving an archive with an existing `(session_id, sequence)` replaces
+    /// it. Archives are removed by [`delete_session`](Self::delete_session).
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store archives (default)
+    async fn save_archive(&self, archive: ChannelArchive) -> Result<()> {
+        let _ = archive;
+        Err(CheckpointerError::Unsupported {
+            operation: "save_archive",
+        })
+    }
+
+    /// Load every archive for a session, ordered by sequence.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store archives (default)
+    async fn load_archives(&self, session_id: &str) -> Result<Vec<ChannelArchive>> {
+        let _ = session_id;
+        Err(CheckpointerError::Unsupported {
+            operation: "load_archives",
+        })
+    }
+
+    /// Store the input snapshot of one superstep.
+    ///
+    /// Saving a capture with an existing `(session_id, step)` replaces it.
+    /// Captures are removed by [`delete_session`](Self::delete_session).
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store node inputs (default)
+    async fn save_node_inputs(&self, capture: NodeInputCapture) -> Result<()> {
+        let _ = capture;
+        Err(CheckpointerError::Unsupported {
+            operation: "save_node_inputs",
+        })
+    }
+
+    /// Load every input capture for a session, ordered by step.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store node inputs (default)
+    async fn load_node_inputs(&self, session_id: &str) -> Result<Vec<NodeInputCapture>> {
+        let _ = session_id;
+        Err(CheckpointerError::Unsupported {
+            operation: "load_node_inputs",
+        })
+    }
+
+    /// Store one audited LLM call.
+    ///
+    /// Records are keyed by `(session_id, step, attempt, index)`; saving an
+    /// existing key replaces it. `(session_id, step)` matches the step row of
+    /// the checkpoint the call contributed to. Records are removed by
+    /// [`delete_session`](Self::delete_session).
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store an audit log (default)
+    async fn save_llm_audit(&self, record: LlmAuditRecord) -> Result<()> {
+        let _ = record;
+        Err(CheckpointerError::Unsupported {
+            operation: "save_llm_audit",
+        })
+    }
+
+    /// Load every audited LLM call for a session, ordered by step, attempt,
+    /// and index.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend does not store an audit log (default)
+    async fn load_llm_audit(&self, session_id: &str) -> Result<Vec<LlmAuditRecord>> {
+        let _ = session_id;
+        Err(CheckpointerError::Unsupported {
+            operation: "load_llm_audit",
+        })
+    }
+
+    /// Find persisted messages containing every word of `query`.
+    ///
+    /// Words are runs of letters and digits, matched whole and
+    /// case-insensitively without stemming, so `"order 4821 failed"` finds
+    /// messages mentioning all three. Only the messages of each session's
+    /// latest saved checkpoint are searched. `session_id` restricts the search
+    /// to one session. Matches are ordered by session ID, then position. A
+    /// query without words matches nothing.
+    ///
+    /// The SQLite and PostgreSQL backends only search sessions saved while
+    /// their message index was enabled; see
+    /// `SQLiteCheckpointer::with_message_search`.
+    ///
+    /// # Errors
+    ///
+    /// * `Backend` - Storage backend error
+    /// * `Unsupported` - The backend cannot search messages (default)
+    async fn search_messages(
+        &self,
+        session_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<MessageMatch>> {
+        let _ = (session_id, query);
+        Err(CheckpointerError::Unsupported {
+            operation: "search_messages",
+        })
+    }
 }
 
 /// Simple in‑memory checkpointer with implicit retention.
@@ -369,6 +763,9 @@ pub trait Checkpointer: Send + Sync {
 #[derive(Default)]
 pub struct InMemoryCheckpointer {
     inner: RwLock<FxHashMap<String, Checkpoint>>,
+    archives: RwLock<FxHashMap<String, Vec<ChannelArchive>>>,
+    node_inputs: RwLock<FxHashMap<String, Vec<NodeInputCapture>>>,
+    llm_audit: RwLock<FxHashMap<String, Vec<LlmAuditRecord>>>,
 }
 
 impl InMemoryCheckpointer {
@@ -381,6 +778,9 @@ impl InMemoryCheckpointer {
     pub fn new() -> Self {
         Self {
             inner: RwLock::new(FxHashMap::default()),
+            archives: RwLock::new(FxHashMap::default()),
+            node_inputs: RwLock::new(FxHashMap::default()),
+            llm_audit: RwLock::new(FxHashMap::default()),
         }
     }
 }
@@ -414,6 +814,163 @@ impl Checkpointer for InMemoryCheckpointer {
             .expect("InMemoryCheckpointer RwLock poisoned");
         Ok(map.keys().cloned().collect())
     }
+
+    #[tracing::instrument(skip(self), fields(session_id = %session_id))]
+    async fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let had_archives = self
+            .archives
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned")
+            .remove(session_id)
+            .is_some();
+        let had_inputs = self
+            .node_inputs
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned")
+            .remove(session_id)
+            .is_some();
+        let had_audit = self
+            .llm_audit
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned")
+            .remove(session_id)
+            .is_some();
+        let mut map = self
+            .inner
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        Ok(map.remove(session_id).is_some() || had_archives || had_inputs || had_audit)
+    }
+
+    #[tracing::instrument(skip(self), fields(session_id = %session_id))]
+    async fn delete_steps_before(&self, session_id: &str, step: u64) -> Result<u64> {
+        // Only the latest checkpoint is retained, and it is never pruned.
+        let _ = step;
+        Ok(0)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn stats(&self) -> Result<Vec<SessionStats>> {
+        let map = self
+            .inner
+            .read()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        let mut stats = map
+            .values()
+            .map(|cp| {
+                let approx_bytes = serde_json::to_vec(
+                    &crate::runtimes::persistence::PersistedState::from(&cp.state),
+                )
+                .map(|bytes| bytes.len() as u64)
+                .unwrap_or(0);
+                SessionStats::new(cp.session_id.clone(), 1, cp.step, approx_bytes)
+            })
+            .collect::<Vec<_>>();
+        stats.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        Ok(stats)
+    }
+
+    #[tracing::instrument(skip(self, archive), fields(session_id = %archive.session_id, sequence = archive.sequence))]
+    async fn save_archive(&self, archive: ChannelArchive) -> Result<()> {
+        let mut map = self
+            .archives
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        let archives = map.entry(archive.session_id.clone()).or_default();
+        archives.retain(|existing| existing.sequence != archive.sequence);
+        archives.push(archive);
+        archives.sort_by_key(|a| a.sequence);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(session_id = %session_id))]
+    async fn load_archives(&self, session_id: &str) -> Result<Vec<ChannelArchive>> {
+        let map = self
+            .archives
+            .read()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        Ok(map.get(session_id).cloned().unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip(self, capture), fields(session_id = %capture.session_id, step = capture.step))]
+    async fn save_node_inputs(&self, capture: NodeInputCapture) -> Result<()> {
+        let mut map = self
+            .node_inputs
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        let captures = map.entry(capture.session_id.clone()).or_default();
+        captures.retain(|existing| existing.step != capture.step);
+        captures.push(capture);
+        captures.sort_by_key(|c| c.step);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(session_id = %session_id))]
+    async fn load_node_inputs(&self, session_id: &str) -> Result<Vec<NodeInputCapture>> {
+        let map = self
+            .node_inputs
+            .read()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        Ok(map.get(session_id).cloned().unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip(self, record), fields(session_id = %record.session_id, step = record.step))]
+    async fn save_llm_audit(&self, record: LlmAuditRecord) -> Result<()> {
+        let mut map = self
+            .llm_audit
+            .write()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        let records = map.entry(record.session_id.clone()).or_default();
+        let key = |r: &LlmAuditRecord| (r.step, r.attempt, r.index);
+        records.retain(|existing| key(existing) != key(&record));
+        records.push(record);
+        records.sort_by_key(key);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(session_id = %session_id))]
+    async fn load_llm_audit(&self, session_id: &str) -> Result<Vec<LlmAuditRecord>> {
+        let map = self
+            .llm_audit
+            .read()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        Ok(map.get(session_id).cloned().unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn search_messages(
+        &self,
+        session_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<MessageMatch>> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let map = self
+            .inner
+            .read()
+            .expect("InMemoryCheckpointer RwLock poisoned");
+        let mut matches = Vec::new();
+        for cp in map
+            .values()
+            .filter(|cp| session_id.is_none_or(|id| cp.session_id == id))
+        {
+            for (index, message) in cp.state.messages.snapshot().into_iter().enumerate() {
+                let words = search_terms(&message.content);
+                if terms.iter().all(|term| words.contains(term)) {
+                    matches.push(MessageMatch::new(
+                        cp.session_id.clone(),
+                        cp.step,
+                        index as u64,
+                        message,
+                    ));
+                }
+            }
+        }
+        matches.sort_by(|a, b| (&a.session_id, a.index).cmp(&(&b.session_id, b.index)));
+        Ok(matches)
+    }
 }
 
 /// Restore a `SessionState` from a persisted `Checkpoint`.
@@ -435,6 +992,29 @@ impl Checkpointer for InMemoryCheckpointer {
 /// - Reconstructed scheduler with original concurrency limits
 /// - Preserved version tracking for proper barrier coordination
 ///
+/// # Format
+///
+/// The mapping is stable, so external stores that hydrate sessions without a
+/// [`Checkpointer`] only need to persist these fields:
+///
+/// | `Checkpoint` field  | `SessionState` field                        |
+/// |---------------------|---------------------------------------------|
+/// | `state`             | `state`                                     |
+/// | `step`              | `step`                                      |
+/// | `frontier`          | `frontier`                                  |
+/// | `concurrency_limit` | `scheduler` (`Scheduler::new(limit)`)       |
+/// | `versions_seen`     | `scheduler_state.versions_seen`             |
+/// | `pending_joins`     | `scheduler_state.pending_joins`             |
+/// | `frontier_origins`  | `scheduler_state.frontier_origins`          |
+/// | `run_counts`        | `scheduler_state.run_counts`                |
+///
+/// `session_id`, `created_at`, and the step history (`ran_nodes`,
+/// `skipped_nodes`, `updated_channels`, `node_metrics`) are ignored. Reserved
+/// `__weavegraph_*` entries that older checkpoints kept in `versions_seen` are
+/// moved into the scheduler bookkeeping fields.
+/// [`Checkpoint::from_session`] is the inverse. Install the result on a
+/// runner with [`AppRunner::adopt_session`](crate::runtimes::AppRunner::adopt_session).
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -449,13 +1029,18 @@ impl Checkpointer for InMemoryCheckpointer {
 #[must_use = "restored session state should be used to continue execution"]
 pub fn restore_session_state(cp: &Checkpoint) -> SessionState {
     use crate::schedulers::Scheduler;
+    let mut scheduler_state = SchedulerState {
+        versions_seen: cp.versions_seen.clone(),
+        pending_joins: cp.pending_joins.clone(),
+        frontier_origins: cp.frontier_origins.clone(),
+        run_counts: cp.run_counts.clone(),
+    };
+    scheduler_state.migrate_legacy_entries();
     SessionState {
         state: cp.state.clone(),
         step: cp.step,
         frontier: cp.frontier.clone(),
         scheduler: Scheduler::new(cp.concurrency_limit),
-        scheduler_state: SchedulerState {
-            versions_seen: cp.versions_seen.clone(),
-        },
+        scheduler_state,
     }
 }
@@ -0,0 +1,572 @@
+/*!
+Object Storage Checkpointer
+
+This module provides `ObjectStoreCheckpointer`, a `Checkpointer` that writes
+each checkpoint as an immutable JSON object through the
+[`object_store`](https://docs.rs/object_store) crate. It works with any
+`ObjectStore` implementation (Amazon S3, Google Cloud Storage, Azure Blob
+Storage, local files, or in-memory), which suits serverless deployments that
+have no database.
+
+## Object Layout
+
+```text
+<prefix>/sessions/<session_id>/<step:020>.json   one PersistedCheckpoint per step
+<prefix>/archives/<session_id>/<seq:020>.json    compacted channel archives
+<prefix>/inputs/<session_id>/<step:020>.json     captured node inputs (opt-in)
+<prefix>/exports/<session_id>.parquet            optional analytics export
+```
+
+Step numbers are zero-padded so lexicographic listing order is step order.
+Session IDs are percent-encoded by `object_store` where required.
+
+## Behavior
+
+- `save` writes a new object per step; saving the same step again overwrites it.
+- `load_latest` lists the session prefix and reads the highest step.
+- `list_sessions` uses a delimiter listing of `<prefix>/sessions/`.
+- With the `object-store-parquet` feature, `export_parquet` writes a session's
+  full step history as a single Parquet file with one row per step.
+
+The backend never modifies objects in place, so bucket versioning and
+lifecycle rules apply to checkpoints as they would to any other object.
+*/
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use object_store::path::Path;
+use object_store::{ObjectMeta, ObjectStore, ObjectStoreExt, PutPayload};
+use tracing::instrument;
+
+use crate::runtimes::checkpointer::{
+    ChannelArchive, Checkpoint, Checkpointer, CheckpointerError, NodeInputCapture, Result,
+    SessionStats,
+};
+use crate::runtimes::persistence::PersistedCheckpoint;
+
+const SESSIONS_DIR: &str = "sessions";
+const EXPORTS_DIR: &str = "exports";
+const ARCHIVES_DIR: &str = "archives";
+const INPUTS_DIR: &str = "inputs";
+const CHECKPOINT_EXT: &str = ".json";
+
+fn backend_error(err: object_store::Error) -> CheckpointerError {
+    CheckpointerError::Backend {
+        message: err.to_string(),
+    }
+}
+
+/// Reverse the percent-encoding `object_store` applies to path segments.
+fn decode_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parse the step number from an object named `<step:020>.json`.
+fn step_of(meta: &ObjectMeta) -> Option<u64> {
+    meta.location
+        .filename()?
+        .strip_suffix(CHECKPOINT_EXT)?
+        .parse()
+        .ok()
+}
+
+/// Checkpointer that stores one JSON object per session step in object storage.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use object_store::memory::InMemory;
+/// use weavegraph::runtimes::{AppRunner, ObjectStoreCheckpointer};
+/// # async fn example(app: weavegraph::app::App) {
+///
+/// // Swap InMemory for AmazonS3Builder / GoogleCloudStorageBuilder in production.
+/// let checkpointer = ObjectStoreCheckpointer::new(Arc::new(InMemory::new()), "weavegraph/prod");
+/// let runner = AppRunner::builder()
+///     .app(app)
+///     .checkpointer_custom(Arc::new(checkpointer))
+///     .build()
+///     .await;
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObjectStoreCheckpointer {
+    store: Arc<dyn ObjectStore>,
+    prefix: Path,
+}
+
+impl ObjectStoreCheckpointer {
+    /// Create a checkpointer writing below `prefix` (e.g. `"weavegraph/prod"`).
+    ///
+    /// An empty prefix writes at the root of the store.
+    #[must_use]
+    pub fn new(store: Arc<dyn ObjectStore>, prefix: impl AsRef<str>) -> Self {
+        Self {
+            store,
+            prefix: Path::from(prefix.as_ref()),
+        }
+    }
+
+    /// The underlying object store.
+    #[must_use]
+    pub fn store(&self) -> &Arc<dyn ObjectStore> {
+        &self.store
+    }
+
+    /// The prefix all objects are written below.
+    #[must_use]
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    fn sessions_root(&self) -> Path {
+        self.prefix.clone().join(SESSIONS_DIR)
+    }
+
+    fn session_prefix(&self, session_id: &str) -> Path {
+        self.sessions_root().join(session_id)
+    }
+
+    /// Object path of the checkpoint for `session_id` at `step`.
+    #[must_use]
+    pub fn checkpoint_path(&self, session_id: &str, step: u64) -> Path {
+        self.session_prefix(session_id)
+            .join(format!("{step:020}{CHECKPOINT_EXT}"))
+    }
+
+    fn archive_prefix(&self, session_id: &str) -> Path {
+        self.prefix.clone().join(ARCHIVES_DIR).join(session_id)
+    }
+
+    /// Object path of the channel archive for `session_id` with `sequence`.
+    #[must_use]
+    pub fn archive_path(&self, session_id: &str, sequence: u64) -> Path {
+        self.archive_prefix(session_id)
+            .join(format!("{sequence:020}{CHECKPOINT_EXT}"))
+    }
+
+    fn inputs_prefix(&self, session_id: &str) -> Path {
+        self.prefix.clone().join(INPUTS_DIR).join(session_id)
+    }
+
+    /// Object path of the node input capture for `session_id` at `step`.
+    #[must_use]
+    pub fn node_inputs_path(&self, session_id: &str, step: u64) -> Path {
+        self.inputs_prefix(session_id)
+            .join(format!("{step:020}{CHECKPOINT_EXT}"))
+    }
+
+    /// Object path of the Parquet export for `session_id`.
+    #[must_use]
+    pub fn export_path(&self, session_id: &str) -> Path {
+        self.prefix
+            .clone()
+            .join(EXPORTS_DIR)
+            .join(format!("{session_id}.parquet"))
+    }
+
+    /// Checkpoint objects for a session, sorted by step.
+    async fn step_objects(&self, session_id: &str) -> Result<Vec<(u64, ObjectMeta)>> {
+        let prefix = self.session_prefix(session_id);
+        let objects: Vec<ObjectMeta> = self
+            .store
+            .list(Some(&prefix))
+            .try_collect()
+            .await
+            .map_err(backend_error)?;
+        let mut steps: Vec<(u64, ObjectMeta)> = objects
+            .into_iter()
+            .filter_map(|meta| step_of(&meta).map(|step| (step, meta)))
+            .collect();
+        steps.sort_by_key(|(step, _)| *step);
+        Ok(steps)
+    }
+
+    /// Read and decode a checkpoint object; `None` if it does not exist.
+    async fn read_checkpoint(&self, location: &Path) -> Result<Option<Checkpoint>> {
+        let result = match self.store.get(location).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(err) => return Err(backend_error(err)),
+        };
+        let bytes = result.bytes().await.map_err(backend_error)?;
+        let persisted: PersistedCheckpoint =
+            serde_json::from_slice(&bytes).map_err(|e| CheckpointerError::Other {
+                message: format!("failed to decode checkpoint {location}: {e}"),
+            })?;
+        Checkpoint::try_from(persisted)
+            .map(Some)
+            .map_err(|e| CheckpointerError::Other {
+                message: format!("failed to restore checkpoint {location}: {e}"),
+            })
+    }
+
+    /// Step numbers stored for a session, in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Backend` if the listing fails.
+    #[instrument(skip(self), err)]
+    pub async fn list_steps(&self, session_id: &str) -> Result<Vec<u64>> {
+        Ok(self
+            .step_objects(session_id)
+            .await?
+            .into_iter()
+            .map(|(step, _)| step)
+            .collect())
+    }
+
+    /// Load the checkpoint stored for `session_id` at `step`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Backend` on storage errors and `Other` if the object cannot be decoded.
+    #[instrument(skip(self), err)]
+    pub async fn load_step(&self, session_id: &str, step: u64) -> Result<Option<Checkpoint>> {
+        self.read_checkpoint(&self.checkpoint_path(session_id, step))
+            .await
+    }
+}
+
+#[async_trait]
+impl Checkpointer for ObjectStoreCheckpointer {
+    #[instrument(skip(self, checkpoint), fields(session_id = %checkpoint.session_id, step = checkpoint.step), err)]
+    async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
+        let persisted = PersistedCheckpoint::from(&checkpoint);
+        let body = serde_json::to_vec(&persisted).map_err(|e| CheckpointerError::Other {
+            message: format!("failed to encode checkpoint: {e}"),
+        })?;
+        let location = self.checkpoint_path(&checkpoint.session_id, checkpoint.step);
+        self.store
+            .put(&location, PutPayload::from(body))
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_latest(&self, session_id: &str) -> Result<Option<Checkpoint>> {
+        match self.step_objects(session_id).await?.pop() {
+            Some((_, meta)) => self.read_checkpoint(&meta.location).await,
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self), err)]
+    async fn list_sessions(&self) -> Result<Vec<String>> {
+        let listing = self
+            .store
+            .list_with_delimiter(Some(&self.sessions_root()))
+            .await
+            .map_err(backend_error)?;
+        let mut sessions: Vec<String> = listing
+            .common_prefixes
+            .iter()
+            .filter_map(|path| path.filename().map(decode_segment))
+            .collect();
+        sessions.sort();
+        Ok(sessions)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let objects = self.step_objects(session_id).await?;
+        let archive_prefix = self.archive_prefix(session_id);
+        let archives: Vec<ObjectMeta> = self
+            .store
+            .list(Some(&archive_prefix))
+            .try_collect()
+            .await
+            .map_err(backend_error)?;
+        let inputs_prefix = self.inputs_prefix(session_id);
+        let inputs: Vec<ObjectMeta> = self
+            .store
+            .list(Some(&inputs_prefix))
+            .try_collect()
+            .await
+            .map_err(backend_error)?;
+        let locations = objects
+            .iter()
+            .map(|(_, meta)| &meta.location)
+            .chain(archives.iter().map(|meta| &meta.location))
+            .chain(inputs.iter().map(|meta| &meta.location));
+        for location in locations {
+            self.store.delete(location).await.map_err(backend_error)?;
+        }
+        Ok(!objects.is_empty() || !archives.is_empty() || !inputs.is_empty())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn delete_steps_before(&self, session_id: &str, step: u64) -> Result<u64> {
+        let mut objects = self.step_objects(session_id).await?;
+        // The latest checkpoint is always retained.
+        objects.pop();
+        let mut removed = 0;
+        for (_, meta) in objects.iter().filter(|(s, _)| *s < step) {
+            self.store
+                .delete(&meta.location)
+                .await
+                .map_err(backend_error)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn stats(&self) -> Result<Vec<SessionStats>> {
+        let mut stats = Vec::new();
+        for session_id in self.list_sessions().await? {
+            let objects = self.step_objects(&session_id).await?;
+            let Some((latest_step, _)) = objects.last() else {
+                continue;
+            };
+            let bytes = objects.iter().map(|(_, meta)| meta.size).sum();
+            stats.push(SessionStats::new(
+                session_id.clone(),
+                objects.len() as u64,
+                *latest_step,
+                bytes,
+            ));
+        }
+        Ok(stats)
+    }
+
+    #[instrument(skip(self, archive), fields(session_id = %archive.session_id, sequence = archive.sequence), err)]
+    async fn save_archive(&self, archive: ChannelArchive) -> Result<()> {
+        let body = serde_json::to_vec(&archive).map_err(|e| CheckpointerError::Other {
+            message: format!("failed to encode archive: {e}"),
+        })?;
+        let location = self.archive_path(&archive.session_id, archive.sequence);
+        self.store
+            .put(&location, PutPayload::from(body))
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_archives(&self, session_id: &str) -> Result<Vec<ChannelArchive>> {
+        let prefix = self.archive_prefix(session_id);
+        let mut objects: Vec<ObjectMeta> = self
+            .store
+            .list(Some(&prefix))
+            .try_collect()
+            .await
+            .map_err(backend_error)?;
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        let mut archives = Vec::with_capacity(objects.len());
+        for meta in objects {
+            let bytes = self
+                .store
+                .get(&meta.location)
+                .await
+                .map_err(backend_error)?
+                .bytes()
+                .await
+                .map_err(backend_error)?;
+            archives.push(serde_json::from_slice(&bytes).map_err(|e| {
+                CheckpointerError::Other {
+                    message: format!("failed to decode archive {}: {e}", meta.location),
+                }
+            })?);
+        }
+        Ok(archives)
+    }
+
+    #[instrument(skip(self, capture), fields(session_id = %capture.session_id, step = capture.step), err)]
+    async fn save_node_inputs(&self, capture: NodeInputCapture) -> Result<()> {
+        let body = serde_json::to_vec(&capture).map_err(|e| CheckpointerError::Other {
+            message: format!("failed to encode node inputs: {e}"),
+        })?;
+        let location = self.node_inputs_path(&capture.session_id, capture.step);
+        self.store
+            .put(&location, PutPayload::from(body))
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_node_inputs(&self, session_id: &str) -> Result<Vec<NodeInputCapture>> {
+        let prefix = self.inputs_prefix(session_id);
+        let mut objects: Vec<ObjectMeta> = self
+            .store
+            .list(Some(&prefix))
+            .try_collect()
+            .await
+            .map_err(backend_error)?;
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+        let mut captures = Vec::with_capacity(objects.len());
+        for meta in objects {
+            let bytes = self
+                .store
+                .get(&meta.location)
+                .await
+                .map_err(backend_error)?
+                .bytes()
+                .await
+                .map_err(backend_error)?;
+            captures.push(serde_json::from_slice(&bytes).map_err(|e| {
+                CheckpointerError::Other {
+                    message: format!("failed to decode node inputs {}: {e}", meta.location),
+                }
+            })?);
+        }
+        Ok(captures)
+    }
+}
+
+// ============================================================================
+// Parquet export
+// ============================================================================
+
+#[cfg(feature = "object-store-parquet")]
+mod parquet_export {
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, DataType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::{SerializedFileWriter, SerializedRowGroupWriter};
+    use parquet::schema::parser::parse_message_type;
+
+    use crate::runtimes::checkpointer::{CheckpointerError, Result};
+    use crate::runtimes::persistence::PersistedCheckpoint;
+
+    /// One row per step; nested values are stored as JSON strings.
+    const SCHEMA: &str = "
+        message weavegraph_checkpoint {
+            REQUIRED BYTE_ARRAY session_id (UTF8);
+            REQUIRED INT64 step;
+            REQUIRED BYTE_ARRAY created_at (UTF8);
+            REQUIRED BYTE_ARRAY frontier_json (UTF8);
+            REQUIRED BYTE_ARRAY ran_nodes_json (UTF8);
+            REQUIRED BYTE_ARRAY skipped_nodes_json (UTF8);
+            REQUIRED BYTE_ARRAY updated_channels_json (UTF8);
+            REQUIRED BYTE_ARRAY state_json (UTF8);
+        }
+    ";
+
+    fn parquet_error(err: parquet::errors::ParquetError) -> CheckpointerError {
+        CheckpointerError::Other {
+            message: format!("parquet export failed: {err}"),
+        }
+    }
+
+    fn json_column<T: serde::Serialize>(
+        rows: &[PersistedCheckpoint],
+        field: impl Fn(&PersistedCheckpoint) -> &T,
+    ) -> Result<Vec<ByteArray>> {
+        rows.iter()
+            .map(|row| {
+                serde_json::to_vec(field(row))
+                    .map(ByteArray::from)
+                    .map_err(|e| CheckpointerError::Other {
+                        message: format!("parquet export failed: {e}"),
+                    })
+            })
+            .collect()
+    }
+
+    fn write_column<T: DataType>(
+        row_group: &mut SerializedRowGroupWriter<'_, Vec<u8>>,
+        values: &[T::T],
+    ) -> Result<()> {
+        let mut column = row_group
+            .next_column()
+            .map_err(parquet_error)?
+            .ok_or_else(|| CheckpointerError::Other {
+                message: "parquet export failed: schema has fewer columns than written".into(),
+            })?;
+        column
+            .typed::<T>()
+            .write_batch(values, None, None)
+            .map_err(parquet_error)?;
+        column.close().map_err(parquet_error)
+    }
+
+    pub(super) fn encode(rows: &[PersistedCheckpoint]) -> Result<Vec<u8>> {
+        let schema = Arc::new(parse_message_type(SCHEMA).map_err(parquet_error)?);
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), schema, props).map_err(parquet_error)?;
+        let strings = |field: fn(&PersistedCheckpoint) -> &str| -> Vec<ByteArray> {
+            rows.iter().map(|row| ByteArray::from(field(row))).collect()
+        };
+        let steps: Vec<i64> = rows.iter().map(|row| row.step as i64).collect();
+
+        let mut row_group = writer.next_row_group().map_err(parquet_error)?;
+        write_column::<ByteArrayType>(&mut row_group, &strings(|row| &row.session_id))?;
+        write_column::<Int64Type>(&mut row_group, &steps)?;
+        write_column::<ByteArrayType>(&mut row_group, &strings(|row| &row.created_at))?;
+        write_column::<ByteArrayType>(&mut row_group, &json_column(rows, |row| &row.frontier)?)?;
+        write_column::<ByteArrayType>(&mut row_group, &json_column(rows, |row| &row.ran_nodes)?)?;
+        write_column::<ByteArrayType>(
+            &mut row_group,
+            &json_column(rows, |row| &row.skipped_nodes)?,
+        )?;
+        write_column::<ByteArrayType>(
+            &mut row_group,
+            &json_column(rows, |row| &row.updated_channels)?,
+        )?;
+        write_column::<ByteArrayType>(&mut row_group, &json_column(rows, |row| &row.state)?)?;
+        row_group.close().map_err(parquet_error)?;
+        writer.into_inner().map_err(parquet_error)
+    }
+}
+
+#[cfg(feature = "object-store-parquet")]
+#[cfg_attr(docsrs, doc(cfg(feature = "object-store-parquet")))]
+impl ObjectStoreCheckpointer {
+    /// Write every stored step of `session_id` to [`export_path`](Self::export_path)
+    /// as a Parquet file, one row per step, and return its location.
+    ///
+    /// Columns: `session_id`, `step`, `created_at`, and JSON-encoded
+    /// `frontier_json`, `ran_nodes_json`, `skipped_nodes_json`,
+    /// `updated_channels_json`, and `state_json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the session has no checkpoints, `Backend` on
+    /// storage errors, and `Other` if a checkpoint cannot be decoded or encoded.
+    #[instrument(skip(self), err)]
+    pub async fn export_parquet(&self, session_id: &str) -> Result<Path> {
+        let objects = self.step_objects(session_id).await?;
+        if objects.is_empty() {
+            return Err(CheckpointerError::NotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        let mut rows = Vec::with_capacity(objects.len());
+        for (_, meta) in &objects {
+            if let Some(checkpoint) = self.read_checkpoint(&meta.location).await? {
+                rows.push(PersistedCheckpoint::from(&checkpoint));
+            }
+        }
+        let body = parquet_export::encode(&rows)?;
+        let location = self.export_path(session_id);
+        self.store
+            .put(&location, PutPayload::from(body))
+            .await
+            .map_err(backend_error)?;
+        Ok(location)
+    }
+}
@@ -40,6 +40,15 @@ The checkpoint data maps to database tables as follows:
 - `steps.ran_nodes_json` ← JSON array of executed nodes (JSONB)
 - `steps.skipped_nodes_json` ← JSON array of skipped nodes (JSONB)
 - `steps.updated_channels_json` ← JSON array of updated channel names (JSONB)
+- `steps.node_metrics_json` ← JSON array of per-node execution metrics (JSONB)
+- `steps.scheduler_json` ← JSON object of join, frontier-origin, and quota
+  bookkeeping (`PersistedSchedulerBookkeeping`) (JSONB)
+- `state_blobs.data` ← blob channel payloads (BYTEA), stored once per session
+  and digest; `steps.state_json` keeps only each blob's metadata and digest
+- `llm_audit.record_json` ← audited LLM calls (JSONB), keyed by the step
+  row's `(session_id, step)` plus attempt and call index
+- `message_search` ← the latest checkpoint's messages with a GIN-indexed
+  `tsvector`, filled only with [`PostgresCheckpointer::with_message_search`]
 
 ## NodeKind Encoding
 
@@ -51,14 +60,24 @@ NodeKinds are encoded as strings for JSON storage:
 
 use std::sync::Arc;
 
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
 use serde_json::Value;
-use sqlx::{PgPool, Row, postgres::PgRow};
+use sqlx::{PgConnection, PgPool, Row, postgres::PgRow};
 use tracing::instrument;
 
 use crate::{
-    runtimes::checkpointer::{Checkpoint, Checkpointer, CheckpointerError, Result},
-    runtimes::persistence::{PersistedState, PersistedVersionsSeen},
+    channels::Channel,
+    llm::LlmAuditRecord,
+    message::Message,
+    runtimes::checkpointer::{
+        ChannelArchive, Checkpoint, Checkpointer, CheckpointerError, MessageMatch,
+        NodeInputCapture, Result, SessionStats, search_terms,
+    },
+    runtimes::persistence::{
+        PersistedNodeMetrics, PersistedSchedulerBookkeeping, PersistedState, PersistedVersionsSeen,
+    },
     state::VersionedState,
     types::NodeKind,
 };
@@ -67,44 +86,8 @@ use super::checkpointer_postgres_helpers::{
     deserialize_json_value, require_json_field, serialize_json,
 };
 
-/// Query parameters for filtering step history.
-#[derive(Debug, Clone, Default)]
-pub struct StepQuery {
-    /// Maximum number of results to return (capped at 1000)
-    pub limit: Option<u32>,
-    /// Number of results to skip (for pagination)
-    pub offset: Option<u32>,
-    /// Filter by minimum step number (inclusive)
-    pub min_step: Option<u64>,
-    /// Filter by maximum step number (inclusive)
-    pub max_step: Option<u64>,
-    /// Only return steps that executed the specified node
-    pub ran_node: Option<NodeKind>,
-    /// Only return steps that skipped the specified node
-    pub skipped_node: Option<NodeKind>,
-}
-
-/// Pagination information for query results.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct PageInfo {
-    /// Total number of matching records
-    pub total_count: u64,
-    /// Number of records returned in this page
-    pub page_size: u32,
-    /// Zero-based offset of the first record in this page
-    pub offset: u32,
-    /// Whether there are more records after this page
-    pub has_next_page: bool,
-}
-
-/// Paginated query result for step history.
-#[derive(Debug, Clone)]
-pub struct StepQueryResult {
-    /// The matching checkpoints
-    pub checkpoints: Vec<Checkpoint>,
-    /// Pagination metadata
-    pub page_info: PageInfo,
-}
+use super::step_query::StepHistory;
+pub use super::step_query::{PageInfo, StepQuery, StepQueryResult};
 
 /// PostgreSQL-backed checkpointer with full step history.
 ///
@@ -147,11 +130,15 @@ pub struct StepQueryResult {
 pub struct PostgresCheckpointer {
     /// Shared PostgreSQL connection pool for concurrent checkpoint operations
     pool: Arc<PgPool>,
+    /// Whether saves refresh the `message_search` full-text index.
+    message_search: bool,
 }
 
 impl std::fmt::Debug for PostgresCheckpointer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("PostgresCheckpointer").finish()
+        f.debug_struct("PostgresCheckpointer")
+            .field("message_search", &self.message_search)
+            .finish()
     }
 }
 
@@ -183,8 +170,28 @@ impl PostgresCheckpointer {
         }
         Ok(Self {
             pool: Arc::new(pool),
+            message_search: false,
         })
     }
+
+    /// Index the messages of every saved checkpoint for
+    /// [`Checkpointer::search_messages`].
+    ///
+    /// Each save replaces the session's rows in the `message_search` table,
+    /// whose `tsvector` column is GIN-indexed, inside the save transaction.
+    /// Sessions are searchable from their next save; searching works on any
+    /// checkpointer connected to the database, indexing or not.
+    #[must_use]
+    pub fn with_message_search(mut self) -> Self {
+        self.message_search = true;
+        self
+    }
+
+    /// Whether saves refresh the message search index.
+    #[must_use]
+    pub fn message_search(&self) -> bool {
+        self.message_search
+    }
 }
 
 #[async_trait::async_trait]
@@ -192,12 +199,17 @@ impl Checkpointer for PostgresCheckpointer {
     #[instrument(skip(self, checkpoint), err)]
     async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
         // Serialize using persistence module (serde-based)
-        let persisted_state = PersistedState::from(&checkpoint.state);
+        let mut persisted_state = PersistedState::from(&checkpoint.state);
+        let blob_payloads = persisted_state.take_blob_payloads();
         let state_json = serialize_json(&persisted_state, "state")?;
         let frontier_enc: Vec<String> = checkpoint.frontier.iter().map(|k| k.encode()).collect();
         let frontier_json = serialize_json(&frontier_enc, "frontier")?;
         let persisted_vs = PersistedVersionsSeen(checkpoint.versions_seen.clone());
         let versions_seen_json = serialize_json(&persisted_vs, "versions_seen")?;
+        let scheduler_json = serialize_json(
+            &PersistedSchedulerBookkeeping::from(&checkpoint),
+            "scheduler",
+        )?;
 
         // Serialize step execution metadata
         let ran_nodes_enc: Vec<String> = checkpoint.ran_nodes.iter().map(|k| k.encode()).collect();
@@ -210,6 +222,10 @@ impl Checkpointer for PostgresCheckpointer {
         let skipped_nodes_json = serialize_json(&skipped_nodes_enc, "skipped_nodes")?;
         let updated_channels_json =
             serialize_json(&checkpoint.updated_channels, "updated_channels")?;
+        let node_metrics_json = serialize_json(
+            &PersistedNodeMetrics::encode_all(&checkpoint.node_metrics),
+            "node_metrics",
+        )?;
 
         let mut tx = self
             .pool
@@ -246,15 +262,22 @@ impl Checkpointer for PostgresCheckpointer {
                 versions_seen_json,
                 ran_nodes_json,
                 skipped_nodes_json,
-                updated_channels_json
-            ) VALUES ($1, $2, $3::jsonb, $4::jsonb, $5::jsonb, $6::jsonb, $7::jsonb, $8::jsonb)
+                updated_channels_json,
+                node_metrics_json,
+                scheduler_json
+            ) VALUES (
+                $1, $2, $3::jsonb, $4::jsonb, $5::jsonb, $6::jsonb, $7::jsonb, $8::jsonb, $9::jsonb,
+                $10::jsonb
+            )
             ON CONFLICT (session_id, step) DO UPDATE SET
                 state_json = EXCLUDED.state_json,
                 frontier_json = EXCLUDED.frontier_json,
                 versions_seen_json = EXCLUDED.versions_seen_json,
                 ran_nodes_json = EXCLUDED.ran_nodes_json,
                 skipped_nodes_json = EXCLUDED.skipped_nodes_json,
-                updated_channels_json = EXCLUDED.updated_channels_json
+                updated_channels_json = EXCLUDED.updated_channels_json,
+                node_metrics_json = EXCLUDED.node_metrics_json,
+                scheduler_json = EXCLUDED.scheduler_json
             "#,
         )
         .bind(&checkpoint.session_id)
@@ -265,6 +288,8 @@ impl Checkpointer for PostgresCheckpointer {
         .bind(&ran_nodes_json)
         .bind(&skipped_nodes_json)
         .bind(&updated_channels_json)
+        .bind(&node_metrics_json)
+        .bind(&scheduler_json)
         .execute(&mut *tx)
         .await
         .map_err(|e| CheckpointerError::Backend {
@@ -298,6 +323,16 @@ impl Checkpointer for PostgresCheckpointer {
         .map_err(|e| CheckpointerError::Backend {
             message: format!("update session latest: {e}"),
         })?;
+        save_blob_payloads(&mut tx, &checkpoint.session_id, &blob_payloads).await?;
+        if self.message_search {
+            index_messages(
+                &mut tx,
+                &checkpoint.session_id,
+                checkpoint.step,
+                &checkpoint.state.messages.snapshot(),
+            )
+            .await?;
+        }
 
         tx.commit().await.map_err(|e| CheckpointerError::Backend {
             message: format!("tx commit: {e}"),
@@ -316,6 +351,10 @@ impl Checkpointer for PostgresCheckpointer {
                 s.last_state_json,
                 s.last_frontier_json,
                 s.last_versions_seen_json,
+                (
+                    SELECT st.scheduler_json FROM steps st
+                    WHERE st.session_id = s.id AND st.step = s.last_step
+                ) AS last_scheduler_json,
                 s.concurrency_limit,
                 s.updated_at
             FROM sessions s
@@ -351,6 +390,11 @@ impl Checkpointer for PostgresCheckpointer {
                 .map_err(|e| CheckpointerError::Backend {
                     message: format!("last_versions_seen_json read: {e}"),
                 })?;
+        let scheduler_json: Option<Value> =
+            row.try_get("last_scheduler_json")
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("last_scheduler_json read: {e}"),
+                })?;
         let concurrency_limit: i64 = row.get("concurrency_limit");
         let updated_at: DateTime<Utc> = row.get("updated_at");
 
@@ -364,7 +408,9 @@ impl Checkpointer for PostgresCheckpointer {
         let versions_seen_val = require_json_field(versions_seen_json, "versions_seen_json")?;
 
         // Deserialize using persistence models
-        let persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        let mut persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        self.restore_blob_payloads(session_id, &mut persisted_state)
+            .await?;
         let state =
             VersionedState::try_from(persisted_state).map_err(|e| CheckpointerError::Other {
                 message: format!("state convert: {e}"),
@@ -381,6 +427,10 @@ impl Checkpointer for PostgresCheckpointer {
         let persisted_vs: PersistedVersionsSeen =
             deserialize_json_value(versions_seen_val, "versions_seen")?;
         let versions_seen = persisted_vs.0;
+        let scheduler: PersistedSchedulerBookkeeping = match scheduler_json {
+            Some(v) => deserialize_json_value(v, "scheduler")?,
+            None => PersistedSchedulerBookkeeping::default(),
+        };
 
         Ok(Some(Checkpoint {
             session_id: session_id.to_string(),
@@ -388,6 +438,9 @@ impl Checkpointer for PostgresCheckpointer {
             state,
             frontier,
             versions_seen,
+            pending_joins: scheduler.pending_joins,
+            frontier_origins: scheduler.frontier_origins,
+            run_counts: scheduler.run_counts,
             concurrency_limit: concurrency_limit as usize,
             created_at: updated_at,
             // Note: load_latest uses denormalized session data which doesn't include
@@ -395,6 +448,7 @@ impl Checkpointer for PostgresCheckpointer {
             ran_nodes: vec![],
             skipped_nodes: vec![],
             updated_channels: vec![],
+            node_metrics: vec![],
         }))
     }
 
@@ -414,6 +468,330 @@ impl Checkpointer for PostgresCheckpointer {
 
         Ok(rows.into_iter().map(|r| r.get::<String, _>("id")).collect())
     }
+
+    #[instrument(skip(self), err)]
+    async fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let archives = sqlx::query("DELETE FROM channel_archives WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete archives: {e}"),
+            })?;
+        let inputs = sqlx::query("DELETE FROM node_inputs WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete node inputs: {e}"),
+            })?;
+        let audit = sqlx::query("DELETE FROM llm_audit WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete llm audit: {e}"),
+            })?;
+        sqlx::query("DELETE FROM message_search WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete message index: {e}"),
+            })?;
+        sqlx::query("DELETE FROM state_blobs WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete blobs: {e}"),
+            })?;
+        // Step rows are removed by the ON DELETE CASCADE foreign key.
+        let result = sqlx::query("DELETE FROM sessions WHERE id = $1")
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete session: {e}"),
+            })?;
+
+        Ok(result.rows_affected() > 0
+            || archives.rows_affected() > 0
+            || inputs.rows_affected() > 0
+            || audit.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn delete_steps_before(&self, session_id: &str, step: u64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM steps
+            WHERE session_id = $1
+              AND step < $2
+              AND step <> COALESCE((SELECT last_step FROM sessions WHERE id = $1), -1)
+            "#,
+        )
+        .bind(session_id)
+        .bind(step as i64)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("delete steps: {e}"),
+        })?;
+
+        if result.rows_affected() > 0 {
+            // Drop payloads that no remaining step references.
+            sqlx::query(
+                r#"
+                DELETE FROM state_blobs b
+                WHERE b.session_id = $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM steps st
+                      WHERE st.session_id = b.session_id
+                        AND position(b.digest IN st.state_json::TEXT) > 0
+                  )
+                "#,
+            )
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete unreferenced blobs: {e}"),
+            })?;
+        }
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn stats(&self) -> Result<Vec<SessionStats>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                s.id,
+                s.last_step,
+                COUNT(st.step)::BIGINT AS step_count,
+                COALESCE(SUM(
+                    octet_length(st.state_json::TEXT)
+                    + octet_length(st.frontier_json::TEXT)
+                    + octet_length(st.versions_seen_json::TEXT)
+                    + octet_length(st.ran_nodes_json::TEXT)
+                    + octet_length(st.skipped_nodes_json::TEXT)
+                    + COALESCE(octet_length(st.updated_channels_json::TEXT), 0)
+                    + COALESCE(octet_length(st.node_metrics_json::TEXT), 0)
+                    + COALESCE(octet_length(st.scheduler_json::TEXT), 0)
+                ), 0)::BIGINT + COALESCE((
+                    SELECT SUM(octet_length(b.data)) FROM state_blobs b WHERE b.session_id = s.id
+                ), 0)::BIGINT AS approx_bytes
+            FROM sessions s
+            LEFT JOIN steps st ON st.session_id = s.id
+            GROUP BY s.id, s.last_step
+            ORDER BY s.id
+            "#,
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("session stats: {e}"),
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                SessionStats::new(
+                    r.get::<String, _>("id"),
+                    r.get::<i64, _>("step_count") as u64,
+                    r.get::<i64, _>("last_step") as u64,
+                    r.get::<i64, _>("approx_bytes") as u64,
+                )
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self, archive), fields(session_id = %archive.session_id, sequence = archive.sequence), err)]
+    async fn save_archive(&self, archive: ChannelArchive) -> Result<()> {
+        let archive_json = serialize_json(&archive, "archive")?;
+        sqlx::query(
+            r#"
+            INSERT INTO channel_archives (session_id, sequence, step, archive_json)
+            VALUES ($1, $2, $3, $4::jsonb)
+            ON CONFLICT (session_id, sequence) DO UPDATE SET
+                step = EXCLUDED.step,
+                archive_json = EXCLUDED.archive_json
+            "#,
+        )
+        .bind(&archive.session_id)
+        .bind(archive.sequence as i64)
+        .bind(archive.step as i64)
+        .bind(&archive_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert archive: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_archives(&self, session_id: &str) -> Result<Vec<ChannelArchive>> {
+        let rows = sqlx::query(
+            "SELECT archive_json FROM channel_archives WHERE session_id = $1 ORDER BY sequence",
+        )
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("select archives: {e}"),
+        })?;
+        rows.iter()
+            .map(|row| {
+                let value: Value =
+                    row.try_get("archive_json")
+                        .map_err(|e| CheckpointerError::Backend {
+                            message: format!("archive_json read: {e}"),
+                        })?;
+                deserialize_json_value(value, "archive")
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self, capture), fields(session_id = %capture.session_id, step = capture.step), err)]
+    async fn save_node_inputs(&self, capture: NodeInputCapture) -> Result<()> {
+        let capture_json = serialize_json(&capture, "node inputs")?;
+        sqlx::query(
+            r#"
+            INSERT INTO node_inputs (session_id, step, capture_json)
+            VALUES ($1, $2, $3::jsonb)
+            ON CONFLICT (session_id, step) DO UPDATE SET
+                capture_json = EXCLUDED.capture_json
+            "#,
+        )
+        .bind(&capture.session_id)
+        .bind(capture.step as i64)
+        .bind(&capture_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert node inputs: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_node_inputs(&self, session_id: &str) -> Result<Vec<NodeInputCapture>> {
+        let rows =
+            sqlx::query("SELECT capture_json FROM node_inputs WHERE session_id = $1 ORDER BY step")
+                .bind(session_id)
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("select node inputs: {e}"),
+                })?;
+        rows.iter()
+            .map(|row| {
+                let value: Value =
+                    row.try_get("capture_json")
+                        .map_err(|e| CheckpointerError::Backend {
+                            message: format!("capture_json read: {e}"),
+                        })?;
+                deserialize_json_value(value, "node inputs")
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self, record), fields(session_id = %record.session_id, step = record.step), err)]
+    async fn save_llm_audit(&self, record: LlmAuditRecord) -> Result<()> {
+        let record_json = serialize_json(&record, "llm audit")?;
+        sqlx::query(
+            r#"
+            INSERT INTO llm_audit (session_id, step, attempt, call_index, node, record_json)
+            VALUES ($1, $2, $3, $4, $5, $6::jsonb)
+            ON CONFLICT (session_id, step, attempt, call_index) DO UPDATE SET
+                node = EXCLUDED.node,
+                record_json = EXCLUDED.record_json
+            "#,
+        )
+        .bind(&record.session_id)
+        .bind(record.step as i64)
+        .bind(i32::try_from(record.attempt).unwrap_or(i32::MAX))
+        .bind(i32::try_from(record.index).unwrap_or(i32::MAX))
+        .bind(&record.node)
+        .bind(&record_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert llm audit: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_llm_audit(&self, session_id: &str) -> Result<Vec<LlmAuditRecord>> {
+        let rows = sqlx::query(
+            "SELECT record_json FROM llm_audit WHERE session_id = $1 \
+             ORDER BY step, attempt, call_index",
+        )
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("select llm audit: {e}"),
+        })?;
+        rows.iter()
+            .map(|row| {
+                let value: Value =
+                    row.try_get("record_json")
+                        .map_err(|e| CheckpointerError::Backend {
+                            message: format!("record_json read: {e}"),
+                        })?;
+                deserialize_json_value(value, "llm audit")
+            })
+            .collect()
+    }
+
+    #[instrument(skip(self), err)]
+    async fn search_messages(
+        &self,
+        session_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<MessageMatch>> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        // Terms are alphanumeric only, so they need no tsquery escaping.
+        let ts_query = terms.join(" & ");
+        let rows = sqlx::query(
+            r#"
+            SELECT session_id, step, message_index, role, content
+            FROM message_search
+            WHERE content_tsv @@ to_tsquery('simple', $1)
+              AND ($2::TEXT IS NULL OR session_id = $2)
+            ORDER BY session_id, message_index
+            "#,
+        )
+        .bind(&ts_query)
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("search messages: {e}"),
+        })?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                MessageMatch::new(
+                    r.get::<String, _>("session_id"),
+                    r.get::<i64, _>("step") as u64,
+                    r.get::<i64, _>("message_index") as u64,
+                    Message::with_role(
+                        r.get::<String, _>("role").into(),
+                        &r.get::<String, _>("content"),
+                    ),
+                )
+            })
+            .collect())
+    }
 }
 
 // Extended PostgresCheckpointer methods (not part of base Checkpointer trait)
@@ -484,8 +862,7 @@ impl PostgresCheckpointer {
         // Count total matching records
         let count_sql = format!("SELECT COUNT(*) as total FROM steps st WHERE {where_clause}");
 
-        let limit = query.limit.unwrap_or(100).min(1000); // Cap at 1000
-        let offset = query.offset.unwrap_or(0);
+        let (limit, offset) = query.page();
 
         // Query with pagination
         let select_sql = format!(
@@ -498,6 +875,8 @@ impl PostgresCheckpointer {
                 st.ran_nodes_json,
                 st.skipped_nodes_json,
                 st.updated_channels_json,
+                st.node_metrics_json,
+                st.scheduler_json,
                 st.created_at,
                 s.concurrency_limit
                FROM steps st
@@ -556,16 +935,11 @@ impl PostgresCheckpointer {
         // Convert rows to checkpoints
         let mut checkpoints = Vec::new();
         for row in rows {
-            let checkpoint = self.row_to_checkpoint(session_id, &row)?;
+            let checkpoint = self.row_to_checkpoint(session_id, &row).await?;
             checkpoints.push(checkpoint);
         }
 
-        let page_info = PageInfo {
-            total_count: total_count as u64,
-            page_size: checkpoints.len() as u32,
-            offset,
-            has_next_page: (offset + limit) < total_count as u32,
-        };
+        let page_info = PageInfo::new(total_count as u64, checkpoints.len() as u32, offset);
 
         Ok(StepQueryResult {
             checkpoints,
@@ -587,7 +961,8 @@ impl PostgresCheckpointer {
     /// # Returns
     ///
     /// * `Ok(())` - Checkpoint saved successfully
-    /// * `Err(CheckpointerError::Backend)` - Concurrency conflict or storage error
+    /// * `Err(CheckpointerError::Conflict)` - The latest step is not `expected_last_step`
+    /// * `Err(CheckpointerError::Backend)` - Storage error
     ///
     /// # Examples
     ///
@@ -613,12 +988,17 @@ impl PostgresCheckpointer {
         expected_last_step: Option<u64>,
     ) -> Result<()> {
         // Serialize checkpoint data
-        let persisted_state = PersistedState::from(&checkpoint.state);
+        let mut persisted_state = PersistedState::from(&checkpoint.state);
+        let blob_payloads = persisted_state.take_blob_payloads();
         let state_json = serialize_json(&persisted_state, "state")?;
         let frontier_enc: Vec<String> = checkpoint.frontier.iter().map(|k| k.encode()).collect();
         let frontier_json = serialize_json(&frontier_enc, "frontier")?;
         let persisted_vs = PersistedVersionsSeen(checkpoint.versions_seen.clone());
         let versions_seen_json = serialize_json(&persisted_vs, "versions_seen")?;
+        let scheduler_json = serialize_json(
+            &PersistedSchedulerBookkeeping::from(&checkpoint),
+            "scheduler",
+        )?;
         let ran_nodes_enc: Vec<String> = checkpoint.ran_nodes.iter().map(|k| k.encode()).collect();
         let ran_nodes_json = serialize_json(&ran_nodes_enc, "ran_nodes")?;
         let skipped_nodes_enc: Vec<String> = checkpoint
@@ -629,6 +1009,10 @@ impl PostgresCheckpointer {
         let skipped_nodes_json = serialize_json(&skipped_nodes_enc, "skipped_nodes")?;
         let updated_channels_json =
             serialize_json(&checkpoint.updated_channels, "updated_channels")?;
+        let node_metrics_json = serialize_json(
+            &PersistedNodeMetrics::encode_all(&checkpoint.node_metrics),
+            "node_metrics",
+        )?;
 
         let mut tx = self
             .pool
@@ -668,11 +1052,10 @@ impl PostgresCheckpointer {
                     })?;
 
             if current_step != expected_step as i64 {
-                return Err(CheckpointerError::Backend {
-                    message: format!(
-                        "concurrency conflict: expected step {}, found {}",
-                        expected_step, current_step
-                    ),
+                return Err(CheckpointerError::Conflict {
+                    session_id: checkpoint.session_id.clone(),
+                    expected: expected_step,
+                    found: current_step as u64,
                 });
             }
         }
@@ -688,15 +1071,22 @@ impl PostgresCheckpointer {
                 versions_seen_json,
                 ran_nodes_json,
                 skipped_nodes_json,
-                updated_channels_json
-            ) VALUES ($1, $2, $3::jsonb, $4::jsonb, $5::jsonb, $6::jsonb, $7::jsonb, $8::jsonb)
+                updated_channels_json,
+                node_metrics_json,
+                scheduler_json
+            ) VALUES (
+                $1, $2, $3::jsonb, $4::jsonb, $5::jsonb, $6::jsonb, $7::jsonb, $8::jsonb, $9::jsonb,
+                $10::jsonb
+            )
             ON CONFLICT (session_id, step) DO UPDATE SET
                 state_json = EXCLUDED.state_json,
                 frontier_json = EXCLUDED.frontier_json,
                 versions_seen_json = EXCLUDED.versions_seen_json,
                 ran_nodes_json = EXCLUDED.ran_nodes_json,
                 skipped_nodes_json = EXCLUDED.skipped_nodes_json,
-                updated_channels_json = EXCLUDED.updated_channels_json
+                updated_channels_json = EXCLUDED.updated_channels_json,
+                node_metrics_json = EXCLUDED.node_metrics_json,
+                scheduler_json = EXCLUDED.scheduler_json
             "#,
         )
         .bind(&checkpoint.session_id)
@@ -707,6 +1097,8 @@ impl PostgresCheckpointer {
         .bind(&ran_nodes_json)
         .bind(&skipped_nodes_json)
         .bind(&updated_channels_json)
+        .bind(&node_metrics_json)
+        .bind(&scheduler_json)
         .execute(&mut *tx)
         .await
         .map_err(|e| CheckpointerError::Backend {
@@ -736,6 +1128,16 @@ impl PostgresCheckpointer {
         .map_err(|e| CheckpointerError::Backend {
             message: format!("update session latest: {e}"),
         })?;
+        save_blob_payloads(&mut tx, &checkpoint.session_id, &blob_payloads).await?;
+        if self.message_search {
+            index_messages(
+                &mut tx,
+                &checkpoint.session_id,
+                checkpoint.step,
+                &checkpoint.state.messages.snapshot(),
+            )
+            .await?;
+        }
 
         tx.commit().await.map_err(|e| CheckpointerError::Backend {
             message: format!("tx commit: {e}"),
@@ -744,8 +1146,39 @@ impl PostgresCheckpointer {
         Ok(())
     }
 
+    /// Load blob payloads that `state` references by digest only.
+    async fn restore_blob_payloads(
+        &self,
+        session_id: &str,
+        state: &mut PersistedState,
+    ) -> Result<()> {
+        let digests = state.missing_blob_payloads();
+        if digests.is_empty() {
+            return Ok(());
+        }
+        let rows = sqlx::query(
+            "SELECT digest, data FROM state_blobs WHERE session_id = $1 AND digest = ANY($2)",
+        )
+        .bind(session_id)
+        .bind(&digests)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("select blobs: {e}"),
+        })?;
+        let payloads: FxHashMap<String, Bytes> = rows
+            .into_iter()
+            .map(|row| {
+                let data: Vec<u8> = row.get("data");
+                (row.get::<String, _>("digest"), Bytes::from(data))
+            })
+            .collect();
+        state.restore_blob_payloads(&payloads);
+        Ok(())
+    }
+
     /// Helper to convert a database row to a Checkpoint.
-    fn row_to_checkpoint(&self, session_id: &str, row: &PgRow) -> Result<Checkpoint> {
+    async fn row_to_checkpoint(&self, session_id: &str, row: &PgRow) -> Result<Checkpoint> {
         let step: i64 = row.get("step");
         let state_json: Value = row.get("state_json");
         let frontier_json: Value = row.get("frontier_json");
@@ -757,11 +1190,23 @@ impl PostgresCheckpointer {
                 .map_err(|e| CheckpointerError::Backend {
                     message: format!("updated_channels_json read: {e}"),
                 })?;
+        let node_metrics_json: Option<Value> =
+            row.try_get("node_metrics_json")
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("node_metrics_json read: {e}"),
+                })?;
+        let scheduler_json: Option<Value> =
+            row.try_get("scheduler_json")
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("scheduler_json read: {e}"),
+                })?;
         let created_at: DateTime<Utc> = row.get("created_at");
         let concurrency_limit: i64 = row.get("concurrency_limit");
 
         // Deserialize using persistence models
-        let persisted_state: PersistedState = deserialize_json_value(state_json, "state")?;
+        let mut persisted_state: PersistedState = deserialize_json_value(state_json, "state")?;
+        self.restore_blob_payloads(session_id, &mut persisted_state)
+            .await?;
         let state =
             VersionedState::try_from(persisted_state).map_err(|e| CheckpointerError::Other {
                 message: format!("state convert: {e}"),
@@ -810,9 +1255,18 @@ impl PostgresCheckpointer {
                 .collect(),
         };
 
+        let node_metrics = match node_metrics_json {
+            None => vec![],
+            Some(v) => PersistedNodeMetrics::decode_all(deserialize_json_value(v, "node_metrics")?),
+        };
+
         let persisted_vs: PersistedVersionsSeen =
             deserialize_json_value(versions_seen_json, "versions_seen")?;
         let versions_seen = persisted_vs.0;
+        let scheduler: PersistedSchedulerBookkeeping = match scheduler_json {
+            Some(v) => deserialize_json_value(v, "scheduler")?,
+            None => PersistedSchedulerBookkeeping::default(),
+        };
 
         Ok(Checkpoint {
             session_id: session_id.to_string(),
@@ -820,11 +1274,86 @@ impl PostgresCheckpointer {
             state,
             frontier,
             versions_seen,
+            pending_joins: scheduler.pending_joins,
+            frontier_origins: scheduler.frontier_origins,
+            run_counts: scheduler.run_counts,
             concurrency_limit: concurrency_limit as usize,
             created_at,
             ran_nodes,
             skipped_nodes,
             updated_channels,
+            node_metrics,
         })
     }
 }
+
+/// Store blob payloads once per session and digest.
+async fn save_blob_payloads(
+    conn: &mut PgConnection,
+    session_id: &str,
+    payloads: &FxHashMap<String, Bytes>,
+) -> Result<()> {
+    for (digest, data) in payloads {
+        sqlx::query(
+            "INSERT INTO state_blobs (session_id, digest, data) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(session_id)
+        .bind(digest)
+        .bind(data.as_ref())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert blob: {e}"),
+        })?;
+    }
+    Ok(())
+}
+
+/// Replace `session_id`'s rows in the message search index with `messages`.
+async fn index_messages(
+    conn: &mut PgConnection,
+    session_id: &str,
+    step: u64,
+    messages: &[Message],
+) -> Result<()> {
+    sqlx::query("DELETE FROM message_search WHERE session_id = $1")
+        .bind(session_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("clear message index: {e}"),
+        })?;
+    for (index, message) in messages.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO message_search (session_id, message_index, step, role, content) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(session_id)
+        .bind(index as i64)
+        .bind(step as i64)
+        .bind(message.role.as_str())
+        .bind(&message.content)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("index message: {e}"),
+        })?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl StepHistory for PostgresCheckpointer {
+    async fn query_steps(&self, session_id: &str, query: StepQuery) -> Result<StepQueryResult> {
+        PostgresCheckpointer::query_steps(self, session_id, query).await
+    }
+
+    async fn save_with_concurrency_check(
+        &self,
+        checkpoint: Checkpoint,
+        expected_last_step: Option<u64>,
+    ) -> Result<()> {
+        PostgresCheckpointer::save_with_concurrency_check(self, checkpoint, expected_last_step)
+            .await
+    }
+}
@@ -40,6 +40,15 @@ The checkpoint data maps to database tables as follows:
 - `steps.ran_nodes_json` ← JSON array of executed nodes
 - `steps.skipped_nodes_json` ← JSON array of skipped nodes
 - `steps.updated_channels_json` ← JSON array of updated channel names
+- `steps.node_metrics_json` ← JSON array of per-node execution metrics
+- `steps.scheduler_json` ← JSON object of join, frontier-origin, and quota
+  bookkeeping (`PersistedSchedulerBookkeeping`)
+- `state_blobs.data` ← blob channel payloads, stored once per session and
+  digest; `steps.state_json` keeps only each blob's metadata and digest
+- `llm_audit.record_json` ← audited LLM calls, keyed by the step row's
+  `(session_id, step)` plus attempt and call index
+- `message_search` ← FTS5 index over the latest checkpoint's messages, filled
+  only with [`SQLiteCheckpointer::with_message_search`]
 
 ## NodeKind Encoding
 
@@ -51,15 +60,25 @@ NodeKinds are encoded as strings for JSON storage:
 
 use std::sync::Arc;
 
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
 use serde_json::Value;
-use sqlx::{Row, SqlitePool, sqlite::SqliteRow};
+use sqlx::{Row, SqliteConnection, SqlitePool, sqlite::SqliteRow};
 use thiserror::Error;
 use tracing::instrument;
 
 use crate::{
-    runtimes::checkpointer::{Checkpoint, Checkpointer, CheckpointerError, Result},
-    runtimes::persistence::{PersistedState, PersistedVersionsSeen},
+    channels::Channel,
+    llm::LlmAuditRecord,
+    message::Message,
+    runtimes::checkpointer::{
+        ChannelArchive, Checkpoint, Checkpointer, CheckpointerError, MessageMatch,
+        NodeInputCapture, Result, SessionStats, search_terms,
+    },
+    runtimes::persistence::{
+        PersistedNodeMetrics, PersistedSchedulerBookkeeping, PersistedState, PersistedVersionsSeen,
+    },
     state::VersionedState,
     types::NodeKind,
 };
@@ -68,44 +87,8 @@ use super::checkpointer_sqlite_helpers::{
     deserialize_json, deserialize_json_value, require_json_field, serialize_json,
 };
 
-/// Query parameters for filtering step history.
-#[derive(Debug, Clone, Default)]
-pub struct StepQuery {
-    /// Maximum number of results to return (capped at 1000)
-    pub limit: Option<u32>,
-    /// Number of results to skip (for pagination)
-    pub offset: Option<u32>,
-    /// Filter by minimum step number (inclusive)
-    pub min_step: Option<u64>,
-    /// Filter by maximum step number (inclusive)
-    pub max_step: Option<u64>,
-    /// Only return steps that executed the specified node
-    pub ran_node: Option<NodeKind>,
-    /// Only return steps that skipped the specified node
-    pub skipped_node: Option<NodeKind>,
-}
-
-/// Pagination information for query results.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct PageInfo {
-    /// Total number of matching records
-    pub total_count: u64,
-    /// Number of records returned in this page
-    pub page_size: u32,
-    /// Zero-based offset of the first record in this page
-    pub offset: u32,
-    /// Whether there are more records after this page
-    pub has_next_page: bool,
-}
-
-/// Paginated query result for step history.
-#[derive(Debug, Clone)]
-pub struct StepQueryResult {
-    /// The matching checkpoints
-    pub checkpoints: Vec<Checkpoint>,
-    /// Pagination metadata
-    pub page_info: PageInfo,
-}
+use super::step_query::StepHistory;
+pub use super::step_query::{PageInfo, StepQuery, StepQueryResult};
 
 /// Errors that can occur within the SQLite-backed checkpointer.
 #[derive(Debug, Error)]
@@ -208,17 +191,23 @@ impl From<SQLiteCheckpointerError> for CheckpointerError {
 ///
 /// ## Option 2: Application lifecycle management
 ///
-/// Delete entire sessions when workflows complete or expire. The schema includes
-/// timestamps (`created_at` on steps, `updated_at` on sessions) to facilitate
-/// time-based policies.
+/// Delete entire sessions when workflows complete or expire with
+/// [`Checkpointer::delete_session`], prune history with
+/// [`Checkpointer::delete_steps_before`], and monitor growth with
+/// [`Checkpointer::stats`]. The schema includes timestamps (`created_at` on
+/// steps, `updated_at` on sessions) to facilitate time-based policies.
 pub struct SQLiteCheckpointer {
     /// Shared SQLite connection pool for concurrent checkpoint operations
     pool: Arc<SqlitePool>,
+    /// Whether saves refresh the `message_search` full-text index.
+    message_search: bool,
 }
 
 impl std::fmt::Debug for SQLiteCheckpointer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("SQLiteCheckpointer").finish()
+        f.debug_struct("SQLiteCheckpointer")
+            .field("message_search", &self.message_search)
+            .finish()
     }
 }
 
@@ -251,8 +240,44 @@ impl SQLiteCheckpointer {
         }
         Ok(Self {
             pool: Arc::new(pool),
+            message_search: false,
         })
     }
+
+    /// Index the messages of every saved checkpoint for
+    /// [`Checkpointer::search_messages`].
+    ///
+    /// Each save replaces the session's rows in the FTS5 `message_search`
+    /// table with the checkpoint's messages, inside the save transaction.
+    /// Sessions are searchable from their next save; searching works on any
+    /// checkpointer connected to the database, indexing or not.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::runtimes::{Checkpointer, SQLiteCheckpointer};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let checkpointer = SQLiteCheckpointer::connect("sqlite://app.db")
+    ///     .await?
+    ///     .with_message_search();
+    /// for hit in checkpointer.search_messages(None, "cust-4821").await? {
+    ///     println!("{} #{}: {}", hit.session_id, hit.index, hit.message.content);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_message_search(mut self) -> Self {
+        self.message_search = true;
+        self
+    }
+
+    /// Whether saves refresh the message search index.
+    #[must_use]
+    pub fn message_search(&self) -> bool {
+        self.message_search
+    }
 }
 
 #[async_trait::async_trait]
@@ -260,12 +285,17 @@ impl Checkpointer for SQLiteCheckpointer {
     #[instrument(skip(self, checkpoint), err)]
     async fn save(&self, checkpoint: Checkpoint) -> Result<()> {
         // Serialize using persistence module (serde-based)
-        let persisted_state = PersistedState::from(&checkpoint.state);
+        let mut persisted_state = PersistedState::from(&checkpoint.state);
+        let blob_payloads = persisted_state.take_blob_payloads();
         let state_json = serialize_json(&persisted_state, "state")?;
         let frontier_enc: Vec<String> = checkpoint.frontier.iter().map(|k| k.encode()).collect();
         let frontier_json = serialize_json(&frontier_enc, "frontier")?;
         let persisted_vs = PersistedVersionsSeen(checkpoint.versions_seen.clone());
         let versions_seen_json = serialize_json(&persisted_vs, "versions_seen")?;
+        let scheduler_json = serialize_json(
+            &PersistedSchedulerBookkeeping::from(&checkpoint),
+            "scheduler",
+        )?;
 
         // Serialize step execution metadata
         let ran_nodes_enc: Vec<String> = checkpoint.ran_nodes.iter().map(|k| k.encode()).collect();
@@ -278,6 +308,10 @@ impl Checkpointer for SQLiteCheckpointer {
         let skipped_nodes_json = serialize_json(&skipped_nodes_enc, "skipped_nodes")?;
         let updated_channels_json =
             serialize_json(&checkpoint.updated_channels, "updated_channels")?;
+        let node_metrics_json = serialize_json(
+            &PersistedNodeMetrics::encode_all(&checkpoint.node_metrics),
+            "node_metrics",
+        )?;
 
         let mut tx = self
             .pool
@@ -313,8 +347,10 @@ impl Checkpointer for SQLiteCheckpointer {
                 versions_seen_json,
                 ran_nodes_json,
                 skipped_nodes_json,
-                updated_channels_json
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                updated_channels_json,
+                node_metrics_json,
+                scheduler_json
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
         "#,
         )
         .bind(&checkpoint.session_id)
@@ -325,11 +361,23 @@ impl Checkpointer for SQLiteCheckpointer {
         .bind(&ran_nodes_json)
         .bind(&skipped_nodes_json)
         .bind(&updated_channels_json)
+        .bind(&node_metrics_json)
+        .bind(&scheduler_json)
         .execute(&mut *tx)
         .await
         .map_err(|e| CheckpointerError::Backend {
             message: format!("insert step: {e}"),
         })?;
+        save_blob_payloads(&mut tx, &checkpoint.session_id, &blob_payloads).await?;
+        if self.message_search {
+            index_messages(
+                &mut tx,
+                &checkpoint.session_id,
+                checkpoint.step,
+                &checkpoint.state.messages.snapshot(),
+            )
+            .await?;
+        }
 
         tx.commit().await.map_err(|e| CheckpointerError::Backend {
             message: format!("tx commit: {e}"),
@@ -348,6 +396,10 @@ impl Checkpointer for SQLiteCheckpointer {
                 s.last_state_json,
                 s.last_frontier_json,
                 s.last_versions_seen_json,
+                (
+                    SELECT st.scheduler_json FROM steps st
+                    WHERE st.session_id = s.id AND st.step = s.last_step
+                ) AS last_scheduler_json,
                 s.concurrency_limit,
                 s.updated_at
             FROM sessions s
@@ -383,6 +435,11 @@ impl Checkpointer for SQLiteCheckpointer {
                 .map_err(|e| CheckpointerError::Backend {
                     message: format!("last_versions_seen_json read: {e}"),
                 })?;
+        let scheduler_json: Option<String> =
+            row.try_get("last_scheduler_json")
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("last_scheduler_json read: {e}"),
+                })?;
         let concurrency_limit: i64 = row.get("concurrency_limit");
         let updated_at_str: String = row.get("updated_at");
 
@@ -400,7 +457,9 @@ impl Checkpointer for SQLiteCheckpointer {
         let versions_seen_val: Value = deserialize_json(&versions_seen_payload, "versions_seen")?;
 
         // Deserialize using persistence models
-        let persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        let mut persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        self.restore_blob_payloads(session_id, &mut persisted_state)
+            .await?;
         let state =
             VersionedState::try_from(persisted_state).map_err(|e| CheckpointerError::Other {
                 message: format!("state convert: {e}"),
@@ -417,6 +476,10 @@ impl Checkpointer for SQLiteCheckpointer {
         let persisted_vs: PersistedVersionsSeen =
             deserialize_json_value(versions_seen_val, "versions_seen")?;
         let versions_seen = persisted_vs.0;
+        let scheduler: PersistedSchedulerBookkeeping = match scheduler_json {
+            Some(json) => deserialize_json(&json, "scheduler")?,
+            None => PersistedSchedulerBookkeeping::default(),
+        };
 
         let created_at = DateTime::parse_from_rfc3339(&updated_at_str)
             .map(|dt| dt.with_timezone(&Utc))
@@ -428,6 +491,9 @@ impl Checkpointer for SQLiteCheckpointer {
             state,
             frontier,
             versions_seen,
+            pending_joins: scheduler.pending_joins,
+            frontier_origins: scheduler.frontier_origins,
+            run_counts: scheduler.run_counts,
             concurrency_limit: concurrency_limit as usize,
             created_at,
             // Note: load_latest uses denormalized session data which doesn't include
@@ -435,6 +501,7 @@ impl Checkpointer for SQLiteCheckpointer {
             ran_nodes: vec![],
             skipped_nodes: vec![],
             updated_channels: vec![],
+            node_metrics: vec![],
         }))
     }
 
@@ -454,6 +521,333 @@ impl Checkpointer for SQLiteCheckpointer {
 
         Ok(rows.into_iter().map(|r| r.get::<String, _>("id")).collect())
     }
+
+    #[instrument(skip(self), err)]
+    async fn delete_session(&self, session_id: &str) -> Result<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("tx begin: {e}"),
+            })?;
+
+        let archives = sqlx::query("DELETE FROM channel_archives WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete archives: {e}"),
+            })?
+            .rows_affected();
+        let inputs = sqlx::query("DELETE FROM node_inputs WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete node inputs: {e}"),
+            })?
+            .rows_affected();
+        let audit = sqlx::query("DELETE FROM llm_audit WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete llm audit: {e}"),
+            })?
+            .rows_affected();
+        sqlx::query("DELETE FROM message_search WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete message index: {e}"),
+            })?;
+        sqlx::query("DELETE FROM state_blobs WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete blobs: {e}"),
+            })?;
+        // Delete steps explicitly: foreign key cascades depend on a per-connection pragma.
+        sqlx::query("DELETE FROM steps WHERE session_id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete steps: {e}"),
+            })?;
+        let deleted = sqlx::query("DELETE FROM sessions WHERE id = ?1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete session: {e}"),
+            })?
+            .rows_affected();
+
+        tx.commit().await.map_err(|e| CheckpointerError::Backend {
+            message: format!("tx commit: {e}"),
+        })?;
+
+        Ok(deleted > 0 || archives > 0 || inputs > 0 || audit > 0)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn delete_steps_before(&self, session_id: &str, step: u64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM steps
+            WHERE session_id = ?1
+              AND step < ?2
+              AND step <> COALESCE((SELECT last_step FROM sessions WHERE id = ?1), -1)
+            "#,
+        )
+        .bind(session_id)
+        .bind(step as i64)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("delete steps: {e}"),
+        })?;
+
+        if result.rows_affected() > 0 {
+            // Drop payloads that no remaining step references.
+            sqlx::query(
+                r#"
+                DELETE FROM state_blobs
+                WHERE session_id = ?1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM steps st
+                      WHERE st.session_id = state_blobs.session_id
+                        AND instr(st.state_json, state_blobs.digest) > 0
+                  )
+                "#,
+            )
+            .bind(session_id)
+            .execute(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("delete unreferenced blobs: {e}"),
+            })?;
+        }
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn stats(&self) -> Result<Vec<SessionStats>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                s.id,
+                s.last_step,
+                COUNT(st.step) AS step_count,
+                COALESCE(SUM(
+                    LENGTH(st.state_json)
+                    + LENGTH(st.frontier_json)
+                    + LENGTH(st.versions_seen_json)
+                    + LENGTH(st.ran_nodes_json)
+                    + LENGTH(st.skipped_nodes_json)
+                    + COALESCE(LENGTH(st.updated_channels_json), 0)
+                    + COALESCE(LENGTH(st.node_metrics_json), 0)
+                    + COALESCE(LENGTH(st.scheduler_json), 0)
+                ), 0) + COALESCE((
+                    SELECT SUM(LENGTH(b.data)) FROM state_blobs b WHERE b.session_id = s.id
+                ), 0) AS approx_bytes
+            FROM sessions s
+            LEFT JOIN steps st ON st.session_id = s.id
+            GROUP BY s.id, s.last_step
+            ORDER BY s.id
+            "#,
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("session stats: {e}"),
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                SessionStats::new(
+                    r.get::<String, _>("id"),
+                    r.get::<i64, _>("step_count") as u64,
+                    r.get::<i64, _>("last_step") as u64,
+                    r.get::<i64, _>("approx_bytes") as u64,
+                )
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self, archive), fields(session_id = %archive.session_id, sequence = archive.sequence), err)]
+    async fn save_archive(&self, archive: ChannelArchive) -> Result<()> {
+        let archive_json = serialize_json(&archive, "archive")?;
+        sqlx::query(
+            r#"
+            INSERT INTO channel_archives (session_id, sequence, step, archive_json)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(session_id, sequence) DO UPDATE SET
+                step = excluded.step,
+                archive_json = excluded.archive_json
+            "#,
+        )
+        .bind(&archive.session_id)
+        .bind(archive.sequence as i64)
+        .bind(archive.step as i64)
+        .bind(&archive_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert archive: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_archives(&self, session_id: &str) -> Result<Vec<ChannelArchive>> {
+        let rows = sqlx::query(
+            "SELECT archive_json FROM channel_archives WHERE session_id = ?1 ORDER BY sequence",
+        )
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("select archives: {e}"),
+        })?;
+        rows.iter()
+            .map(|row| deserialize_json(&row.get::<String, _>("archive_json"), "archive"))
+            .collect()
+    }
+
+    #[instrument(skip(self, capture), fields(session_id = %capture.session_id, step = capture.step), err)]
+    async fn save_node_inputs(&self, capture: NodeInputCapture) -> Result<()> {
+        let capture_json = serialize_json(&capture, "node inputs")?;
+        sqlx::query(
+            r#"
+            INSERT INTO node_inputs (session_id, step, capture_json)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(session_id, step) DO UPDATE SET
+                capture_json = excluded.capture_json
+            "#,
+        )
+        .bind(&capture.session_id)
+        .bind(capture.step as i64)
+        .bind(&capture_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert node inputs: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_node_inputs(&self, session_id: &str) -> Result<Vec<NodeInputCapture>> {
+        let rows =
+            sqlx::query("SELECT capture_json FROM node_inputs WHERE session_id = ?1 ORDER BY step")
+                .bind(session_id)
+                .fetch_all(&*self.pool)
+                .await
+                .map_err(|e| CheckpointerError::Backend {
+                    message: format!("select node inputs: {e}"),
+                })?;
+        rows.iter()
+            .map(|row| deserialize_json(&row.get::<String, _>("capture_json"), "node inputs"))
+            .collect()
+    }
+
+    #[instrument(skip(self, record), fields(session_id = %record.session_id, step = record.step), err)]
+    async fn save_llm_audit(&self, record: LlmAuditRecord) -> Result<()> {
+        let record_json = serialize_json(&record, "llm audit")?;
+        sqlx::query(
+            r#"
+            INSERT INTO llm_audit (session_id, step, attempt, call_index, node, record_json)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(session_id, step, attempt, call_index) DO UPDATE SET
+                node = excluded.node,
+                record_json = excluded.record_json
+            "#,
+        )
+        .bind(&record.session_id)
+        .bind(record.step as i64)
+        .bind(i64::from(record.attempt))
+        .bind(i64::from(record.index))
+        .bind(&record.node)
+        .bind(&record_json)
+        .execute(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert llm audit: {e}"),
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn load_llm_audit(&self, session_id: &str) -> Result<Vec<LlmAuditRecord>> {
+        let rows = sqlx::query(
+            "SELECT record_json FROM llm_audit WHERE session_id = ?1 \
+             ORDER BY step, attempt, call_index",
+        )
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("select llm audit: {e}"),
+        })?;
+        rows.iter()
+            .map(|row| deserialize_json(&row.get::<String, _>("record_json"), "llm audit"))
+            .collect()
+    }
+
+    #[instrument(skip(self), err)]
+    async fn search_messages(
+        &self,
+        session_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<MessageMatch>> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        // Quoted terms are plain strings to FTS5; adjacent strings are ANDed.
+        let fts_query = terms
+            .iter()
+            .map(|term| format!("\"{term}\""))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let rows = sqlx::query(
+            r#"
+            SELECT session_id, step, message_index, role, content
+            FROM message_search
+            WHERE message_search MATCH ?1
+              AND (?2 IS NULL OR session_id = ?2)
+            ORDER BY session_id, message_index
+            "#,
+        )
+        .bind(&fts_query)
+        .bind(session_id)
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("search messages: {e}"),
+        })?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                MessageMatch::new(
+                    r.get::<String, _>("session_id"),
+                    r.get::<i64, _>("step") as u64,
+                    r.get::<i64, _>("message_index") as u64,
+                    Message::with_role(
+                        r.get::<String, _>("role").into(),
+                        &r.get::<String, _>("content"),
+                    ),
+                )
+            })
+            .collect())
+    }
 }
 
 // Extended SQLiteCheckpointer methods (not part of base Checkpointer trait)
@@ -513,13 +907,13 @@ impl SQLiteCheckpointer {
         if query.ran_node.is_some() {
             param_count += 1;
             conditions.push(format!(
-                "JSON_EXTRACT(ran_nodes_json, '$') LIKE ?{param_count}"
+                "EXISTS (SELECT 1 FROM json_each(ran_nodes_json) WHERE value = ?{param_count})"
             ));
         }
         if query.skipped_node.is_some() {
             param_count += 1;
             conditions.push(format!(
-                "JSON_EXTRACT(skipped_nodes_json, '$') LIKE ?{param_count}"
+                "EXISTS (SELECT 1 FROM json_each(skipped_nodes_json) WHERE value = ?{param_count})"
             ));
         }
 
@@ -528,14 +922,14 @@ impl SQLiteCheckpointer {
         // Count total matching records
         let count_sql = format!("SELECT COUNT(*) as total FROM steps WHERE {where_clause}");
 
-        let limit = query.limit.unwrap_or(100).min(1000); // Cap at 1000
-        let offset = query.offset.unwrap_or(0);
+        let (limit, offset) = query.page();
 
         // Query with pagination
         let select_sql = format!(
             r#"SELECT
                 session_id, step, state_json, frontier_json, versions_seen_json,
-                ran_nodes_json, skipped_nodes_json, updated_channels_json, created_at
+                ran_nodes_json, skipped_nodes_json, updated_channels_json, node_metrics_json,
+                scheduler_json, created_at
                FROM steps
                WHERE {where_clause}
                ORDER BY step DESC
@@ -551,10 +945,10 @@ impl SQLiteCheckpointer {
             count_query = count_query.bind(max_step as i64);
         }
         if let Some(ran_node) = &query.ran_node {
-            count_query = count_query.bind(format!("%{}%", ran_node.encode()));
+            count_query = count_query.bind(ran_node.encode());
         }
         if let Some(skipped_node) = &query.skipped_node {
-            count_query = count_query.bind(format!("%{}%", skipped_node.encode()));
+            count_query = count_query.bind(skipped_node.encode());
         }
 
         let total_count: i64 = count_query
@@ -574,10 +968,10 @@ impl SQLiteCheckpointer {
             select_query = select_query.bind(max_step as i64);
         }
         if let Some(ran_node) = &query.ran_node {
-            select_query = select_query.bind(format!("%{}%", ran_node.encode()));
+            select_query = select_query.bind(ran_node.encode());
         }
         if let Some(skipped_node) = &query.skipped_node {
-            select_query = select_query.bind(format!("%{}%", skipped_node.encode()));
+            select_query = select_query.bind(skipped_node.encode());
         }
 
         let rows =
@@ -595,12 +989,7 @@ impl SQLiteCheckpointer {
             checkpoints.push(checkpoint);
         }
 
-        let page_info = PageInfo {
-            total_count: total_count as u64,
-            page_size: checkpoints.len() as u32,
-            offset,
-            has_next_page: (offset + limit) < total_count as u32,
-        };
+        let page_info = PageInfo::new(total_count as u64, checkpoints.len() as u32, offset);
 
         Ok(StepQueryResult {
             checkpoints,
@@ -622,7 +1011,8 @@ impl SQLiteCheckpointer {
     /// # Returns
     ///
     /// * `Ok(())` - Checkpoint saved successfully
-    /// * `Err(CheckpointerError::Backend)` - Concurrency conflict or storage error
+    /// * `Err(CheckpointerError::Conflict)` - The latest step is not `expected_last_step`
+    /// * `Err(CheckpointerError::Backend)` - Storage error
     ///
     /// # Examples
     ///
@@ -648,12 +1038,17 @@ impl SQLiteCheckpointer {
         expected_last_step: Option<u64>,
     ) -> Result<()> {
         // Serialize checkpoint data
-        let persisted_state = PersistedState::from(&checkpoint.state);
+        let mut persisted_state = PersistedState::from(&checkpoint.state);
+        let blob_payloads = persisted_state.take_blob_payloads();
         let state_json = serialize_json(&persisted_state, "state")?;
         let frontier_enc: Vec<String> = checkpoint.frontier.iter().map(|k| k.encode()).collect();
         let frontier_json = serialize_json(&frontier_enc, "frontier")?;
         let persisted_vs = PersistedVersionsSeen(checkpoint.versions_seen.clone());
         let versions_seen_json = serialize_json(&persisted_vs, "versions_seen")?;
+        let scheduler_json = serialize_json(
+            &PersistedSchedulerBookkeeping::from(&checkpoint),
+            "scheduler",
+        )?;
         let ran_nodes_enc: Vec<String> = checkpoint.ran_nodes.iter().map(|k| k.encode()).collect();
         let ran_nodes_json = serialize_json(&ran_nodes_enc, "ran_nodes")?;
         let skipped_nodes_enc: Vec<String> = checkpoint
@@ -664,6 +1059,10 @@ impl SQLiteCheckpointer {
         let skipped_nodes_json = serialize_json(&skipped_nodes_enc, "skipped_nodes")?;
         let updated_channels_json =
             serialize_json(&checkpoint.updated_channels, "updated_channels")?;
+        let node_metrics_json = serialize_json(
+            &PersistedNodeMetrics::encode_all(&checkpoint.node_metrics),
+            "node_metrics",
+        )?;
 
         let mut tx = self
             .pool
@@ -673,39 +1072,9 @@ impl SQLiteCheckpointer {
                 message: format!("tx begin: {e}"),
             })?;
 
-        // Check concurrency constraint if specified
-        if let Some(expected_step) = expected_last_step {
-            let current_step: Option<i64> =
-                sqlx::query_scalar("SELECT last_step FROM sessions WHERE id = ?1")
-                    .bind(&checkpoint.session_id)
-                    .fetch_optional(&mut *tx)
-                    .await
-                    .map_err(|e| CheckpointerError::Backend {
-                        message: format!("concurrency check: {e}"),
-                    })?;
-
-            match current_step {
-                Some(step) if step != expected_step as i64 => {
-                    return Err(CheckpointerError::Backend {
-                        message: format!(
-                            "concurrency conflict: expected step {}, found {}",
-                            expected_step, step
-                        ),
-                    });
-                }
-                None if expected_step != 0 => {
-                    return Err(CheckpointerError::Backend {
-                        message: format!(
-                            "concurrency conflict: session not found, expected step {}",
-                            expected_step
-                        ),
-                    });
-                }
-                _ => {} // Check passed
-            }
-        }
-
-        // Ensure session row exists
+        // Ensure the session row exists. As the transaction's first write this
+        // also takes SQLite's write lock, so concurrent writers serialize
+        // their checks.
         sqlx::query(
             r#"
             INSERT OR IGNORE INTO sessions (id, concurrency_limit)
@@ -720,7 +1089,27 @@ impl SQLiteCheckpointer {
             message: format!("insert session: {e}"),
         })?;
 
-        // Insert step row (fail if step already exists to prevent overwrites)
+        // Check concurrency constraint if specified
+        if let Some(expected_step) = expected_last_step {
+            let current_step: i64 =
+                sqlx::query_scalar("SELECT last_step FROM sessions WHERE id = ?1")
+                    .bind(&checkpoint.session_id)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(|e| CheckpointerError::Backend {
+                        message: format!("concurrency check: {e}"),
+                    })?;
+
+            if current_step != expected_step as i64 {
+                return Err(CheckpointerError::Conflict {
+                    session_id: checkpoint.session_id.clone(),
+                    expected: expected_step,
+                    found: current_step as u64,
+                });
+            }
+        }
+
+        // Insert or update step row (idempotent re-save of the same step)
         sqlx::query(
             r#"
             INSERT INTO steps (
@@ -731,8 +1120,19 @@ impl SQLiteCheckpointer {
                 versions_seen_json,
                 ran_nodes_json,
                 skipped_nodes_json,
-                updated_channels_json
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                updated_channels_json,
+                node_metrics_json,
+                scheduler_json
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (session_id, step) DO UPDATE SET
+                state_json = excluded.state_json,
+                frontier_json = excluded.frontier_json,
+                versions_seen_json = excluded.versions_seen_json,
+                ran_nodes_json = excluded.ran_nodes_json,
+                skipped_nodes_json = excluded.skipped_nodes_json,
+                updated_channels_json = excluded.updated_channels_json,
+                node_metrics_json = excluded.node_metrics_json,
+                scheduler_json = excluded.scheduler_json
         "#,
         )
         .bind(&checkpoint.session_id)
@@ -743,11 +1143,23 @@ impl SQLiteCheckpointer {
         .bind(&ran_nodes_json)
         .bind(&skipped_nodes_json)
         .bind(&updated_channels_json)
+        .bind(&node_metrics_json)
+        .bind(&scheduler_json)
         .execute(&mut *tx)
         .await
         .map_err(|e| CheckpointerError::Backend {
             message: format!("insert step: {e}"),
         })?;
+        save_blob_payloads(&mut tx, &checkpoint.session_id, &blob_payloads).await?;
+        if self.message_search {
+            index_messages(
+                &mut tx,
+                &checkpoint.session_id,
+                checkpoint.step,
+                &checkpoint.state.messages.snapshot(),
+            )
+            .await?;
+        }
 
         tx.commit().await.map_err(|e| CheckpointerError::Backend {
             message: format!("tx commit: {e}"),
@@ -756,6 +1168,32 @@ impl SQLiteCheckpointer {
         Ok(())
     }
 
+    /// Load blob payloads that `state` references by digest only.
+    async fn restore_blob_payloads(
+        &self,
+        session_id: &str,
+        state: &mut PersistedState,
+    ) -> Result<()> {
+        let mut payloads = FxHashMap::default();
+        for digest in state.missing_blob_payloads() {
+            let data: Option<Vec<u8>> = sqlx::query_scalar(
+                "SELECT data FROM state_blobs WHERE session_id = ?1 AND digest = ?2",
+            )
+            .bind(session_id)
+            .bind(&digest)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| CheckpointerError::Backend {
+                message: format!("select blob: {e}"),
+            })?;
+            if let Some(data) = data {
+                payloads.insert(digest, Bytes::from(data));
+            }
+        }
+        state.restore_blob_payloads(&payloads);
+        Ok(())
+    }
+
     /// Helper to convert a database row to a Checkpoint.
     async fn row_to_checkpoint(
         &self,
@@ -769,6 +1207,8 @@ impl SQLiteCheckpointer {
         let ran_nodes_json: String = row.get("ran_nodes_json");
         let skipped_nodes_json: String = row.get("skipped_nodes_json");
         let updated_channels_json: String = row.get("updated_channels_json");
+        let node_metrics_json: Option<String> = row.get("node_metrics_json");
+        let scheduler_json: Option<String> = row.get("scheduler_json");
         let created_at_str: String = row.get("created_at");
 
         // Deserialize using persistence models
@@ -779,8 +1219,16 @@ impl SQLiteCheckpointer {
         let skipped_nodes_val: Value = deserialize_json(&skipped_nodes_json, "skipped_nodes")?;
         let updated_channels_val: Value =
             deserialize_json(&updated_channels_json, "updated_channels")?;
+        let node_metrics = match node_metrics_json {
+            Some(json) => {
+                PersistedNodeMetrics::decode_all(deserialize_json(&json, "node_metrics")?)
+            }
+            None => Vec::new(),
+        };
 
-        let persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        let mut persisted_state: PersistedState = deserialize_json_value(state_val, "state")?;
+        self.restore_blob_payloads(session_id, &mut persisted_state)
+            .await?;
         let state =
             VersionedState::try_from(persisted_state).map_err(|e| CheckpointerError::Other {
                 message: format!("state convert: {e}"),
@@ -829,6 +1277,10 @@ impl SQLiteCheckpointer {
         let persisted_vs: PersistedVersionsSeen =
             deserialize_json_value(versions_seen_val, "versions_seen")?;
         let versions_seen = persisted_vs.0;
+        let scheduler: PersistedSchedulerBookkeeping = match scheduler_json {
+            Some(json) => deserialize_json(&json, "scheduler")?,
+            None => PersistedSchedulerBookkeeping::default(),
+        };
 
         let created_at = DateTime::parse_from_rfc3339(&created_at_str)
             .map(|dt| dt.with_timezone(&Utc))
@@ -840,11 +1292,85 @@ impl SQLiteCheckpointer {
             state,
             frontier,
             versions_seen,
+            pending_joins: scheduler.pending_joins,
+            frontier_origins: scheduler.frontier_origins,
+            run_counts: scheduler.run_counts,
             concurrency_limit: 1, // Will need to be retrieved from session table if needed
             created_at,
             ran_nodes,
             skipped_nodes,
             updated_channels,
+            node_metrics,
         })
     }
 }
+
+/// Store blob payloads once per session and digest.
+async fn save_blob_payloads(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+    payloads: &FxHashMap<String, Bytes>,
+) -> Result<()> {
+    for (digest, data) in payloads {
+        sqlx::query(
+            "INSERT OR IGNORE INTO state_blobs (session_id, digest, data) VALUES (?1, ?2, ?3)",
+        )
+        .bind(session_id)
+        .bind(digest)
+        .bind(data.as_ref())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("insert blob: {e}"),
+        })?;
+    }
+    Ok(())
+}
+
+/// Replace `session_id`'s rows in the message search index with `messages`.
+async fn index_messages(
+    conn: &mut SqliteConnection,
+    session_id: &str,
+    step: u64,
+    messages: &[Message],
+) -> Result<()> {
+    sqlx::query("DELETE FROM message_search WHERE session_id = ?1")
+        .bind(session_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("clear message index: {e}"),
+        })?;
+    for (index, message) in messages.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO message_search (content, session_id, step, message_index, role) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(&message.content)
+        .bind(session_id)
+        .bind(step as i64)
+        .bind(index as i64)
+        .bind(message.role.as_str())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| CheckpointerError::Backend {
+            message: format!("index message: {e}"),
+        })?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl StepHistory for SQLiteCheckpointer {
+    async fn query_steps(&self, session_id: &str, query: StepQuery) -> Result<StepQueryResult> {
+        SQLiteCheckpointer::query_steps(self, session_id, query).await
+    }
+
+    async fn save_with_concurrency_check(
+        &self,
+        checkpoint: Checkpoint,
+        expected_last_step: Option<u64>,
+    ) -> Result<()> {
+        SQLiteCheckpointer::save_with_concurrency_check(self, checkpoint, expected_last_step).await
+    }
+}
@@ -0,0 +1,136 @@
+//! Session compaction: moving old messages and errors out of live state.
+//!
+//! Long-lived sessions accumulate messages and error events forever, and every
+//! checkpoint carries the full history. [`AppRunner::compact_session`] writes
+//! the oldest items to the checkpointer as a [`ChannelArchive`] and then drops
+//! them from the in-memory channels, keeping the most recent items as set by
+//! a [`CompactionPolicy`].
+//!
+//! Continuity is recorded in the session's `extra` channel under
+//! [`COMPACTION_MARKER_KEY`] as a [`CompactionMarker`]: the number of archives
+//! written and how many messages and errors precede the live ones. The logical
+//! index of live message `i` is `marker.archived_messages + i`. Channel
+//! versions are never reset; compaction bumps them like any other edit.
+//!
+//! [`AppRunner::compact_session`]: crate::runtimes::AppRunner::compact_session
+//! [`ChannelArchive`]: crate::runtimes::ChannelArchive
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::StateSnapshot;
+
+/// `extra` key holding the session's [`CompactionMarker`].
+pub const COMPACTION_MARKER_KEY: &str = "__weavegraph_compaction__";
+
+// ============================================================================
+// Policy
+// ============================================================================
+
+/// How many recent items [`AppRunner::compact_session`] keeps in live state.
+///
+/// [`AppRunner::compact_session`]: crate::runtimes::AppRunner::compact_session
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::runtimes::CompactionPolicy;
+///
+/// let policy = CompactionPolicy::new()
+///     .keep_last_messages(50)
+///     .keep_last_errors(10);
+/// assert_eq!(policy.messages_to_keep(), 50);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+#[must_use]
+pub struct CompactionPolicy {
+    keep_last_messages: usize,
+    keep_last_errors: usize,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompactionPolicy {
+    /// Keep the last 20 messages and the last 20 error events.
+    pub fn new() -> Self {
+        Self {
+            keep_last_messages: 20,
+            keep_last_errors: 20,
+        }
+    }
+
+    /// Keep the last `count` messages.
+    pub fn keep_last_messages(mut self, count: usize) -> Self {
+        self.keep_last_messages = count;
+        self
+    }
+
+    /// Keep the last `count` error events.
+    pub fn keep_last_errors(mut self, count: usize) -> Self {
+        self.keep_last_errors = count;
+        self
+    }
+
+    /// Number of messages kept in live state.
+    #[must_use]
+    pub fn messages_to_keep(&self) -> usize {
+        self.keep_last_messages
+    }
+
+    /// Number of error events kept in live state.
+    #[must_use]
+    pub fn errors_to_keep(&self) -> usize {
+        self.keep_last_errors
+    }
+}
+
+// ============================================================================
+// Marker and report
+// ============================================================================
+
+/// Continuity record stored in `extra` under [`COMPACTION_MARKER_KEY`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CompactionMarker {
+    /// Number of archives written; the next archive uses this as its sequence.
+    pub archives: u64,
+    /// Messages moved to archives, i.e. the logical index of the first live message.
+    pub archived_messages: u64,
+    /// Error events moved to archives.
+    pub archived_errors: u64,
+    /// Step of the most recent compaction.
+    pub last_step: u64,
+}
+
+impl CompactionMarker {
+    /// Read the marker from a snapshot, if the session was ever compacted.
+    ///
+    /// A marker that does not deserialize is treated as absent.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &StateSnapshot) -> Option<Self> {
+        snapshot
+            .extra
+            .get(COMPACTION_MARKER_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+/// Outcome of [`AppRunner::compact_session`].
+///
+/// [`AppRunner::compact_session`]: crate::runtimes::AppRunner::compact_session
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CompactionReport {
+    /// Sequence of the archive written, or `None` if nothing was old enough.
+    pub archive_sequence: Option<u64>,
+    /// Messages moved out of live state.
+    pub archived_messages: usize,
+    /// Error events moved out of live state.
+    pub archived_errors: usize,
+    /// Marker stored in the session after compaction.
+    pub marker: CompactionMarker,
+}
@@ -0,0 +1,211 @@
+//! Step-by-step execution for notebooks and examples.
+//!
+//! [`DebugSession`] wraps an [`AppRunner`] with one in-memory session, so a
+//! graph can be advanced one superstep at a time from an evcxr/Jupyter cell
+//! or a tutorial without setting up checkpointers, session ids, or event
+//! subscriptions. After each [`step`](DebugSession::step) the current state,
+//! the next frontier, and the events the step emitted are available directly.
+//!
+//! Use [`AppRunner`] for anything beyond exploration: a debug session never
+//! persists state and holds every event of the last step in memory.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::GraphBuilder;
+//! use weavegraph::node::NodePartial;
+//! use weavegraph::runtimes::DebugSession;
+//! use weavegraph::state::VersionedState;
+//! use weavegraph::types::NodeKind;
+//!
+//! # struct Greet;
+//! # #[async_trait::async_trait]
+//! # impl weavegraph::node::Node for Greet {
+//! #     async fn run(&self, _: weavegraph::state::StateSnapshot, ctx: weavegraph::node::NodeContext) -> Result<NodePartial, weavegraph::node::NodeError> {
+//! #         ctx.emit("greet", "hello")?;
+//! #         Ok(NodePartial::default())
+//! #     }
+//! # }
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let greet = NodeKind::Custom("greet".into());
+//! let app = GraphBuilder::new()
+//!     .add_node(greet.clone(), Greet)
+//!     .add_edge(NodeKind::Start, greet.clone())
+//!     .add_edge(greet.clone(), NodeKind::End)
+//!     .compile()
+//!     .unwrap();
+//!
+//! let mut session = DebugSession::new(app, VersionedState::new_with_user_message("hi"))
+//!     .await
+//!     .unwrap();
+//! assert_eq!(session.frontier(), &[greet.clone()]);
+//!
+//! let report = session.step().await.unwrap();
+//! assert_eq!(report.ran_nodes, vec![greet]);
+//! assert!(session.is_complete());
+//! assert!(session.events_since_last_step().iter().any(|e| e.message() == "hello"));
+//! # }
+//! ```
+
+use crate::app::App;
+use crate::event_bus::{ChannelSink, Event};
+use crate::runtimes::runner::RunnerError;
+use crate::runtimes::{
+    AppRunner, CheckpointerType, SessionState, StepOptions, StepReport, StepResult,
+};
+use crate::state::VersionedState;
+use crate::types::NodeKind;
+
+/// Session id of the single session a debug session drives.
+const DEBUG_SESSION_ID: &str = "debug";
+
+/// Interactive, one-superstep-at-a-time run of a graph.
+///
+/// See the [module documentation](self) for an example.
+pub struct DebugSession {
+    runner: AppRunner,
+    events: flume::Receiver<Event>,
+    last_events: Vec<Event>,
+    last_report: Option<StepReport>,
+}
+
+impl DebugSession {
+    /// Start a session of `app` at `state`, ready for its first step.
+    ///
+    /// Events go to the sinks of the app's
+    /// [`EventBusConfig`](crate::runtimes::EventBusConfig) and are also
+    /// captured for [`events_since_last_step`](Self::events_since_last_step).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session cannot be created, for example when
+    /// the graph has no edges leaving [`NodeKind::Start`].
+    pub async fn new(app: App, state: VersionedState) -> Result<Self, RunnerError> {
+        let (tx, rx) = flume::unbounded();
+        let event_bus = app.runtime_config().event_bus.build_event_bus();
+        event_bus.add_sink(ChannelSink::new(tx));
+        let mut runner = AppRunner::builder()
+            .app(app)
+            .checkpointer(CheckpointerType::InMemory)
+            .autosave(false)
+            .event_bus(event_bus)
+            .start_listener(true)
+            .build()
+            .await;
+        runner
+            .create_session(DEBUG_SESSION_ID.to_string(), state)
+            .await?;
+        Ok(Self {
+            runner,
+            events: rx,
+            last_events: Vec::new(),
+            last_report: None,
+        })
+    }
+
+    /// Run the next superstep and return its report.
+    ///
+    /// Stepping a completed session runs nothing and returns a report with
+    /// `completed` set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the runner's error if a node fails, or
+    /// [`RunnerError::UnexpectedPause`] if a node interrupts the run.
+    pub async fn step(&mut self) -> Result<&StepReport, RunnerError> {
+        let result = self
+            .runner
+            .run_step(DEBUG_SESSION_ID, StepOptions::default())
+            .await;
+        // Events are captured even when the step fails; they explain why.
+        self.collect_events().await;
+        match result? {
+            StepResult::Completed(report) => Ok(self.last_report.insert(report)),
+            StepResult::Paused(_) => Err(RunnerError::UnexpectedPause),
+        }
+    }
+
+    /// Step until the session completes, returning the number of supersteps run.
+    ///
+    /// [`events_since_last_step`](Self::events_since_last_step) afterwards
+    /// holds the events of the final step only.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`step`](Self::step).
+    pub async fn run_to_end(&mut self) -> Result<u64, RunnerError> {
+        let mut steps = 0;
+        while !self.is_complete() {
+            self.step().await?;
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    /// The session's current state.
+    #[must_use]
+    pub fn state(&self) -> &VersionedState {
+        &self.session().state
+    }
+
+    /// Nodes the next [`step`](Self::step) will run.
+    #[must_use]
+    pub fn frontier(&self) -> &[NodeKind] {
+        &self.session().frontier
+    }
+
+    /// Number of supersteps run so far.
+    #[must_use]
+    pub fn step_number(&self) -> u64 {
+        self.session().step
+    }
+
+    /// Whether no nodes are left to run.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.frontier().iter().all(|node| *node == NodeKind::End)
+    }
+
+    /// Report of the most recent step, if any step has run.
+    #[must_use]
+    pub fn last_report(&self) -> Option<&StepReport> {
+        self.last_report.as_ref()
+    }
+
+    /// Events emitted during the most recent [`step`](Self::step).
+    ///
+    /// Events from session creation are included with the first step.
+    #[must_use]
+    pub fn events_since_last_step(&self) -> &[Event] {
+        &self.last_events
+    }
+
+    /// The wrapped runner, for APIs the debug session does not expose.
+    pub fn runner(&mut self) -> &mut AppRunner {
+        &mut self.runner
+    }
+
+    fn session(&self) -> &SessionState {
+        self.runner
+            .get_session(DEBUG_SESSION_ID)
+            .expect("debug session exists for the lifetime of DebugSession")
+    }
+
+    async fn collect_events(&mut self) {
+        if let Err(err) = self.runner.flush_events().await {
+            tracing::warn!(target: "weavegraph::runtimes", %err, "debug session event flush timed out");
+        }
+        self.last_events = self.events.try_iter().collect();
+    }
+}
+
+impl std::fmt::Debug for DebugSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugSession")
+            .field("step", &self.step_number())
+            .field("frontier", &self.frontier())
+            .field("last_events", &self.last_events.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -3,9 +3,20 @@
 //! This module defines the types used to represent step execution results,
 //! pause conditions, and execution options during workflow processing.
 
+use std::time::Duration;
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
 use crate::app::BarrierOutcome;
-use crate::node::NodePartial;
+use crate::event_bus::{Event, NodeEvent, ROUTING_SCOPE};
+use crate::graphs::EdgeLabel;
+use crate::node::{NodeMetrics, NodePartial, TokenUsage};
+use crate::runtimes::profiling::SuperstepProfile;
 use crate::runtimes::session::{SessionState, StateVersions};
+use crate::runtimes::state_limits::StateLimitBreach;
+use crate::schedulers::{NodeTiming, SuperstepSaturation};
 use crate::types::NodeKind;
 
 /// Result of executing one superstep in a session.
@@ -29,6 +40,7 @@ use crate::types::NodeKind;
 /// }
 /// ```
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct StepReport {
     /// The step number that was executed.
     pub step: u64,
@@ -40,10 +52,152 @@ pub struct StepReport {
     pub barrier_outcome: BarrierOutcome,
     /// The frontier for the next step.
     pub next_frontier: Vec<NodeKind>,
+    /// Edges followed from the nodes that ran, with their labels.
+    ///
+    /// One entry per route taken, including routes into an AND-join that is
+    /// not released yet; targets that were skipped as unknown are omitted.
+    pub frontier_edges: Vec<FrontierEdge>,
     /// Channel versions after this step completed.
     pub state_versions: StateVersions,
     /// Whether the workflow has completed (reached End or empty frontier).
     pub completed: bool,
+    /// Per-node execution metrics for the nodes that ran, in `ran_nodes` order.
+    pub node_metrics: Vec<(NodeKind, NodeMetrics)>,
+    /// Timing profile of the step, when the runner was built with
+    /// [`profile_supersteps`](crate::runtimes::AppRunnerBuilder::profile_supersteps).
+    pub profile: Option<SuperstepProfile>,
+    /// State limits exceeded at this step's barrier, when the runtime sets
+    /// [`StateLimits`](crate::runtimes::StateLimits).
+    pub state_limit_breaches: Vec<StateLimitBreach>,
+}
+
+/// How the runner moved from a node to a frontier target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FrontierEdgeKind {
+    /// An unconditional edge.
+    Static,
+    /// A target returned by a conditional edge.
+    Conditional,
+    /// A target set by a [`FrontierCommand`](crate::control::FrontierCommand).
+    Command,
+}
+
+impl FrontierEdgeKind {
+    /// Short lowercase name (`static`, `conditional`, `command`).
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Conditional => "conditional",
+            Self::Command => "command",
+        }
+    }
+
+    /// Parse a name produced by [`label`](Self::label).
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "static" => Some(Self::Static),
+            "conditional" => Some(Self::Conditional),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+}
+
+/// An edge the runner followed when computing [`StepReport::next_frontier`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FrontierEdge {
+    /// Node that ran.
+    pub from: NodeKind,
+    /// Target added to the frontier.
+    pub to: NodeKind,
+    /// Kind of edge followed.
+    pub kind: FrontierEdgeKind,
+    /// Label of the edge: from
+    /// [`GraphBuilder::add_edge_labeled`](crate::graphs::GraphBuilder::add_edge_labeled)
+    /// for static edges, or
+    /// [`ConditionalEdge::with_label`](crate::graphs::ConditionalEdge::with_label)
+    /// for conditional ones.
+    pub label: Option<String>,
+    /// Metadata of a static edge's [`EdgeLabel`].
+    pub metadata: FxHashMap<String, Value>,
+}
+
+impl FrontierEdge {
+    pub(crate) fn new(from: &NodeKind, to: NodeKind, kind: FrontierEdgeKind) -> Self {
+        Self {
+            from: from.clone(),
+            to,
+            kind,
+            label: None,
+            metadata: FxHashMap::default(),
+        }
+    }
+
+    pub(crate) fn with_edge_label(mut self, label: Option<&EdgeLabel>) -> Self {
+        if let Some(label) = label {
+            self.label = Some(label.label().to_string());
+            self.metadata = label.metadata().clone();
+        }
+        self
+    }
+
+    /// The [`ROUTING_SCOPE`] event the runner publishes for this edge.
+    #[must_use]
+    pub fn to_event(&self, session_id: &str, step: u64) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert("invocation_id".to_string(), json!(session_id));
+        metadata.insert("from".to_string(), json!(self.from.encode()));
+        metadata.insert("to".to_string(), json!(self.to.encode()));
+        metadata.insert("kind".to_string(), json!(self.kind.label()));
+        if let Some(label) = &self.label {
+            metadata.insert("label".to_string(), json!(label));
+        }
+        if !self.metadata.is_empty() {
+            metadata.insert("edge_metadata".to_string(), json!(self.metadata));
+        }
+        let message = match &self.label {
+            Some(label) => format!("{} -> {} ({label})", self.from, self.to),
+            None => format!("{} -> {}", self.from, self.to),
+        };
+        Event::Node(
+            NodeEvent::new(
+                Some(self.from.encode()),
+                Some(step),
+                ROUTING_SCOPE.to_string(),
+                message,
+            )
+            .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a routing event published by the runner, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node_event) = event else {
+            return None;
+        };
+        if node_event.scope() != ROUTING_SCOPE {
+            return None;
+        }
+        let metadata = node_event.metadata();
+        Some(Self {
+            from: NodeKind::decode(metadata.get("from")?.as_str()?),
+            to: NodeKind::decode(metadata.get("to")?.as_str()?),
+            kind: FrontierEdgeKind::from_label(metadata.get("kind")?.as_str()?)?,
+            label: metadata
+                .get("label")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            metadata: match metadata.get("edge_metadata") {
+                Some(Value::Object(map)) => map.clone().into_iter().collect(),
+                _ => FxHashMap::default(),
+            },
+        })
+    }
 }
 
 /// Options for controlling step execution behavior.
@@ -86,6 +240,10 @@ pub enum PausedReason {
     AfterNode(NodeKind),
     /// Paused after completing the specified step number.
     AfterStep(u64),
+    /// Paused after a step that left the state over its
+    /// [`StateLimits`](crate::runtimes::StateLimits), under
+    /// [`StateLimitPolicy::Pause`](crate::runtimes::StateLimitPolicy::Pause).
+    StateLimitExceeded(Vec<StateLimitBreach>),
 }
 
 /// Extended step report when execution is paused.
@@ -119,4 +277,121 @@ pub(crate) struct SchedulerOutcome {
     pub ran_nodes: Vec<NodeKind>,
     pub skipped_nodes: Vec<NodeKind>,
     pub partials: Vec<NodePartial>,
+    /// Accumulated outcome of micro-barriers applied while nodes were running.
+    pub micro_barrier: BarrierOutcome,
+    pub node_metrics: Vec<(NodeKind, NodeMetrics)>,
+    pub node_timings: Vec<(NodeKind, NodeTiming)>,
+    pub saturation: SuperstepSaturation,
+}
+
+/// Aggregated [`NodeMetrics`] for a single node across a session's steps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeMetricsSummary {
+    /// Number of supersteps in which the node ran.
+    pub invocations: u64,
+    /// Sum of run durations, in microseconds.
+    pub total_duration_micros: u64,
+    /// Longest single run, in microseconds.
+    pub max_duration_micros: u64,
+    /// Total events emitted through the node's context.
+    pub events_emitted: u64,
+    /// Summed token usage, if the node reported any.
+    pub token_usage: Option<TokenUsage>,
+}
+
+impl NodeMetricsSummary {
+    /// Sum of run durations.
+    #[must_use]
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_micros(self.total_duration_micros)
+    }
+
+    /// Mean run duration, or zero if the node never ran.
+    #[must_use]
+    pub fn mean_duration(&self) -> Duration {
+        if self.invocations == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_micros(self.total_duration_micros / self.invocations)
+        }
+    }
+
+    pub(crate) fn record(&mut self, metrics: &NodeMetrics) {
+        self.invocations += 1;
+        self.total_duration_micros = self
+            .total_duration_micros
+            .saturating_add(metrics.duration_micros);
+        self.max_duration_micros = self.max_duration_micros.max(metrics.duration_micros);
+        self.events_emitted += metrics.events_emitted;
+        if let Some(usage) = metrics.token_usage {
+            *self.token_usage.get_or_insert_with(TokenUsage::default) += usage;
+        }
+    }
+}
+
+/// Per-node metrics aggregated over the steps a runner executed for a session.
+///
+/// Returned by [`AppRunner::session_metrics`](crate::runtimes::AppRunner::session_metrics).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use weavegraph::runtimes::AppRunner;
+/// use weavegraph::types::NodeKind;
+///
+/// fn slowest(runner: &AppRunner, session_id: &str) {
+///     let metrics = runner.session_metrics(session_id).unwrap();
+///     if let Some((kind, summary)) = metrics
+///         .nodes
+///         .iter()
+///         .max_by_key(|(_, summary)| summary.total_duration_micros)
+///     {
+///         println!("{kind} spent {:?} over {} runs", summary.total_duration(), summary.invocations);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SessionMetrics {
+    /// Number of supersteps executed.
+    pub steps: u64,
+    /// Aggregates keyed by node.
+    pub nodes: FxHashMap<NodeKind, NodeMetricsSummary>,
+}
+
+impl SessionMetrics {
+    /// Aggregates for one node, if it ran.
+    #[must_use]
+    pub fn node(&self, kind: &NodeKind) -> Option<&NodeMetricsSummary> {
+        self.nodes.get(kind)
+    }
+
+    /// Sum of node run durations across all nodes.
+    ///
+    /// Parallel nodes overlap in wall-clock time, so this can exceed the
+    /// session's elapsed time.
+    #[must_use]
+    pub fn total_node_duration(&self) -> Duration {
+        Duration::from_micros(self.nodes.values().map(|n| n.total_duration_micros).sum())
+    }
+
+    /// Summed token usage across all nodes, if any node reported usage.
+    #[must_use]
+    pub fn total_token_usage(&self) -> Option<TokenUsage> {
+        self.nodes
+            .values()
+            .filter_map(|n| n.token_usage)
+            .reduce(|mut acc, usage| {
+                acc += usage;
+                acc
+            })
+    }
+
+    pub(crate) fn record(&mut self, report: &StepReport) {
+        self.steps += 1;
+        for (kind, metrics) in &report.node_metrics {
+            self.nodes.entry(kind.clone()).or_default().record(metrics);
+        }
+    }
 }
@@ -0,0 +1,220 @@
+//! Asynchronous hooks that run between supersteps.
+//!
+//! A [`SuperstepHook`] receives a [`SuperstepUpdate`] (the step report and a
+//! snapshot of the state) after each superstep commits. Hooks are meant for
+//! pushing progress to external systems such as job queues or UI backends,
+//! so the runner keeps them from stalling the run:
+//!
+//! - Each call runs on its own task. The runner waits at most
+//!   [`SuperstepHookOptions::budget`] for it, then moves on while the call
+//!   finishes in the background.
+//! - Calls to one hook never overlap. Updates that arrive while a call is in
+//!   flight, or within [`SuperstepHookOptions::debounce`] of the previous
+//!   call, are coalesced: only the latest is kept.
+//! - When a run finishes, the latest coalesced update is delivered, so the
+//!   external system always sees the final step.
+//!
+//! Panics inside hooks are caught and logged as warnings.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use weavegraph::runtimes::RuntimeConfig;
+//! use weavegraph::runtimes::hooks::{SuperstepHookOptions, SuperstepUpdate};
+//!
+//! let config = RuntimeConfig::default()
+//!     .on_superstep(|update: SuperstepUpdate| async move {
+//!         println!("{} finished step {}", update.session_id, update.report.step);
+//!     })
+//!     .on_superstep_with(
+//!         |_update: SuperstepUpdate| async move { /* push to a job queue */ },
+//!         SuperstepHookOptions::default().with_debounce(Duration::from_secs(1)),
+//!     );
+//! assert_eq!(config.superstep_hooks().len(), 2);
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+
+use crate::runtimes::execution::StepReport;
+use crate::state::StateSnapshot;
+
+/// How long the runner waits for a hook call by default.
+pub const DEFAULT_HOOK_BUDGET: Duration = Duration::from_millis(50);
+
+/// What a [`SuperstepHook`] receives after a superstep.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SuperstepUpdate {
+    /// Session the step belongs to.
+    pub session_id: String,
+    /// Report of the step that just committed.
+    pub report: StepReport,
+    /// State after the step's barrier.
+    pub state: StateSnapshot,
+}
+
+/// Asynchronous callback run between supersteps.
+///
+/// Closures of the form `Fn(SuperstepUpdate) -> impl Future<Output = ()>`
+/// implement this trait.
+#[async_trait]
+pub trait SuperstepHook: Send + Sync + 'static {
+    /// Handle the update for one superstep.
+    async fn on_superstep(&self, update: SuperstepUpdate);
+}
+
+#[async_trait]
+impl<F, Fut> SuperstepHook for F
+where
+    F: Fn(SuperstepUpdate) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    async fn on_superstep(&self, update: SuperstepUpdate) {
+        self(update).await;
+    }
+}
+
+/// Pacing for a [`SuperstepHook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SuperstepHookOptions {
+    /// Minimum time between the starts of two calls.
+    pub debounce: Duration,
+    /// How long the runner waits for a call before continuing without it.
+    pub budget: Duration,
+}
+
+impl Default for SuperstepHookOptions {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::ZERO,
+            budget: DEFAULT_HOOK_BUDGET,
+        }
+    }
+}
+
+impl SuperstepHookOptions {
+    #[must_use]
+    /// Start calls at most once per `debounce`; updates in between are coalesced.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    #[must_use]
+    /// Wait at most `budget` for each call before continuing the run.
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+}
+
+/// A hook registered on a [`RuntimeConfig`](crate::runtimes::RuntimeConfig).
+#[derive(Clone)]
+pub struct RegisteredHook {
+    /// The hook to call.
+    pub hook: Arc<dyn SuperstepHook>,
+    /// How calls are paced.
+    pub options: SuperstepHookOptions,
+}
+
+impl std::fmt::Debug for RegisteredHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredHook")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Per-session delivery state for one registered hook.
+pub(crate) struct HookDriver {
+    hook: Arc<dyn SuperstepHook>,
+    options: SuperstepHookOptions,
+    in_flight: Option<JoinHandle<()>>,
+    last_started: Option<Instant>,
+    pending: Option<SuperstepUpdate>,
+}
+
+impl HookDriver {
+    pub(crate) fn new(registered: &RegisteredHook) -> Self {
+        Self {
+            hook: Arc::clone(&registered.hook),
+            options: registered.options,
+            in_flight: None,
+            last_started: None,
+            pending: None,
+        }
+    }
+
+    /// Deliver `update`, or keep it as the pending update if the hook is
+    /// busy or inside its debounce window.
+    pub(crate) async fn dispatch(&mut self, update: SuperstepUpdate) {
+        if self.in_flight.as_ref().is_some_and(JoinHandle::is_finished)
+            && let Some(handle) = self.in_flight.take()
+        {
+            log_join(handle.await);
+        }
+        let debouncing = self
+            .last_started
+            .is_some_and(|started| started.elapsed() < self.options.debounce);
+        if self.in_flight.is_some() || debouncing {
+            self.pending = Some(update);
+            return;
+        }
+        self.pending = None;
+        self.last_started = Some(Instant::now());
+        let hook = Arc::clone(&self.hook);
+        let handle = tokio::spawn(async move { hook.on_superstep(update).await });
+        self.in_flight = self.wait(handle).await;
+    }
+
+    /// Deliver the pending update, if any, after the call in flight.
+    pub(crate) async fn flush(mut self) {
+        let Some(update) = self.pending.take() else {
+            if let Some(handle) = self.in_flight.take() {
+                self.wait(handle).await;
+            }
+            return;
+        };
+        let previous = self.in_flight.take();
+        let hook = Arc::clone(&self.hook);
+        let handle = tokio::spawn(async move {
+            if let Some(previous) = previous {
+                log_join(previous.await);
+            }
+            hook.on_superstep(update).await;
+        });
+        self.wait(handle).await;
+    }
+
+    /// Wait up to the budget; return the handle if the call is still running.
+    async fn wait(&self, mut handle: JoinHandle<()>) -> Option<JoinHandle<()>> {
+        match tokio::time::timeout(self.options.budget, &mut handle).await {
+            Ok(result) => {
+                log_join(result);
+                None
+            }
+            Err(_) => {
+                tracing::debug!(
+                    budget_ms = self.options.budget.as_millis() as u64,
+                    "superstep hook exceeded its budget; continuing in the background"
+                );
+                Some(handle)
+            }
+        }
+    }
+}
+
+fn log_join(result: Result<(), tokio::task::JoinError>) {
+    if let Err(err) = result
+        && err.is_panic()
+    {
+        tracing::warn!(error = %err, "superstep hook panicked");
+    }
+}
@@ -0,0 +1,318 @@
+//! Versioned migrations for persisted session state.
+//!
+//! As an app evolves, checkpoints written by older releases keep the `extra`
+//! layout (and typed slots) of the release that wrote them. A
+//! [`StateMigrations`] registry holds numbered upgrade steps; a runner
+//! configured with
+//! [`RuntimeConfig::with_state_migrations`](crate::runtimes::RuntimeConfig::with_state_migrations)
+//! runs the pending steps on every checkpoint it loads, before the session is
+//! resumed.
+//!
+//! The schema version of a state lives in its `extra` channel under
+//! [`STATE_SCHEMA_VERSION_KEY`]. A state without the key is version `0`, so
+//! the first migration of an app that predates the registry starts at `0`.
+//! Fresh sessions are stamped with the registry's current version. Migrations
+//! edit state in place without bumping channel versions, so a resumed session
+//! does not re-run nodes just because its schema was upgraded.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde_json::json;
+//! use weavegraph::channels::Channel;
+//! use weavegraph::runtimes::StateMigrations;
+//!
+//! let migrations = StateMigrations::new().migration(0, "rename user_name to user", |state| {
+//!     let extra = state.extra.get_mut();
+//!     if let Some(name) = extra.remove("user_name") {
+//!         extra.insert("user".into(), json!({ "name": name }));
+//!     }
+//!     Ok(())
+//! });
+//! assert_eq!(migrations.current_version(), 1);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::channels::Channel;
+use crate::state::VersionedState;
+
+use super::Checkpoint;
+
+/// `extra` key holding the schema version of a session's state.
+pub const STATE_SCHEMA_VERSION_KEY: &str = "__weavegraph_schema_version__";
+
+/// Upgrade function for one migration step.
+pub type MigrationFn = Arc<dyn Fn(&mut VersionedState) -> Result<(), String> + Send + Sync>;
+
+/// Errors raised while upgrading a state to the current schema.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum StateMigrationError {
+    /// A migration step returned an error.
+    #[error("state migration v{from} -> v{} ({description}) failed: {message}", from + 1)]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::migration::failed))
+    )]
+    Failed {
+        /// Version the failing step upgrades from.
+        from: u32,
+        /// Description of the failing step.
+        description: String,
+        /// Error returned by the step.
+        message: String,
+    },
+
+    /// No step is registered to upgrade from `version`.
+    #[error("no state migration registered from schema v{version} (current v{current})")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::migration::missing_step),
+            help(
+                "Register a migration for every version between the oldest stored state and the current one."
+            )
+        )
+    )]
+    MissingStep {
+        /// Version with no outgoing step.
+        version: u32,
+        /// Current schema version of the registry.
+        current: u32,
+    },
+
+    /// The state was written by a newer schema than this registry knows.
+    #[error("state schema v{found} is newer than the current v{current}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::migration::newer_version),
+            help("The checkpoint was written by a newer release; downgrades are not supported.")
+        )
+    )]
+    NewerVersion {
+        /// Version recorded in the state.
+        found: u32,
+        /// Current schema version of the registry.
+        current: u32,
+    },
+
+    /// The version stored under [`STATE_SCHEMA_VERSION_KEY`] is not a `u32`.
+    #[error("invalid state schema version: {value}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::migration::invalid_version))
+    )]
+    InvalidVersion {
+        /// The stored value.
+        value: Value,
+    },
+}
+
+/// One migration step that ran (or would run, in a dry run).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AppliedMigration {
+    /// Version the step upgraded from; it upgraded to `from + 1`.
+    pub from: u32,
+    /// Description given at registration.
+    pub description: String,
+}
+
+/// Outcome of upgrading one state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MigrationReport {
+    /// Schema version the state had before migrating.
+    pub from_version: u32,
+    /// Schema version the state has after migrating.
+    pub to_version: u32,
+    /// Steps run, oldest first. Empty when the state was already current.
+    pub applied: Vec<AppliedMigration>,
+}
+
+impl MigrationReport {
+    /// Whether any step ran.
+    #[must_use]
+    pub fn migrated(&self) -> bool {
+        !self.applied.is_empty()
+    }
+}
+
+#[derive(Clone)]
+struct MigrationStep {
+    description: String,
+    apply: MigrationFn,
+}
+
+/// Registry of numbered state migrations.
+///
+/// Each step upgrades a state from version `n` to `n + 1`. The current
+/// version is one past the highest registered step, or `0` when the registry
+/// is empty.
+#[derive(Clone, Default)]
+pub struct StateMigrations {
+    steps: BTreeMap<u32, MigrationStep>,
+}
+
+impl fmt::Debug for StateMigrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateMigrations")
+            .field("current_version", &self.current_version())
+            .field("steps", &self.labels())
+            .finish()
+    }
+}
+
+impl StateMigrations {
+    /// An empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the step that upgrades version `from` to `from + 1`.
+    ///
+    /// Registering a second step for the same `from` replaces the first.
+    #[must_use]
+    pub fn migration<F>(mut self, from: u32, description: impl Into<String>, apply: F) -> Self
+    where
+        F: Fn(&mut VersionedState) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.steps.insert(
+            from,
+            MigrationStep {
+                description: description.into(),
+                apply: Arc::new(apply),
+            },
+        );
+        self
+    }
+
+    /// Schema version states are upgraded to.
+    #[must_use]
+    pub fn current_version(&self) -> u32 {
+        self.steps.keys().next_back().map_or(0, |from| from + 1)
+    }
+
+    /// Whether no steps are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// `v{from}: {description}` for every step, oldest first.
+    #[must_use]
+    pub fn labels(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .map(|(from, step)| format!("v{from}: {}", step.description))
+            .collect()
+    }
+
+    /// Schema version recorded in `state`, or `0` if none is recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateMigrationError::InvalidVersion`] if the stored value is
+    /// not a `u32`.
+    pub fn version_of(state: &VersionedState) -> Result<u32, StateMigrationError> {
+        match state.extra.snapshot().get(STATE_SCHEMA_VERSION_KEY) {
+            None => Ok(0),
+            Some(value) => value
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| StateMigrationError::InvalidVersion {
+                    value: value.clone(),
+                }),
+        }
+    }
+
+    /// Record the current version in a state that needs no migration, such
+    /// as the initial state of a new session.
+    pub fn stamp(&self, state: &mut VersionedState) {
+        state.extra.get_mut().insert(
+            STATE_SCHEMA_VERSION_KEY.to_string(),
+            Value::from(self.current_version()),
+        );
+    }
+
+    /// Upgrade `state` to the current version in place.
+    ///
+    /// Steps run in order; if one fails, `state` is left as the previous
+    /// step produced it and the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StateMigrationError`] if the stored version is invalid or
+    /// newer than the registry, a step is missing, or a step fails.
+    pub fn migrate(
+        &self,
+        state: &mut VersionedState,
+    ) -> Result<MigrationReport, StateMigrationError> {
+        let from_version = Self::version_of(state)?;
+        let current = self.current_version();
+        if from_version > current {
+            return Err(StateMigrationError::NewerVersion {
+                found: from_version,
+                current,
+            });
+        }
+        let mut applied = Vec::new();
+        for version in from_version..current {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or(StateMigrationError::MissingStep { version, current })?;
+            (step.apply)(state).map_err(|message| StateMigrationError::Failed {
+                from: version,
+                description: step.description.clone(),
+                message,
+            })?;
+            state.extra.get_mut().insert(
+                STATE_SCHEMA_VERSION_KEY.to_string(),
+                Value::from(version + 1),
+            );
+            applied.push(AppliedMigration {
+                from: version,
+                description: step.description.clone(),
+            });
+        }
+        Ok(MigrationReport {
+            from_version,
+            to_version: current,
+            applied,
+        })
+    }
+
+    /// Upgrade the state held by a loaded checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// See [`migrate`](Self::migrate).
+    pub fn migrate_checkpoint(
+        &self,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<MigrationReport, StateMigrationError> {
+        self.migrate(&mut checkpoint.state)
+    }
+
+    /// Run the migrations against a copy of `checkpoint` without changing it.
+    ///
+    /// Use this to validate stored sessions before rolling out a release
+    /// that adds migrations.
+    ///
+    /// # Errors
+    ///
+    /// See [`migrate`](Self::migrate).
+    pub fn dry_run(&self, checkpoint: &Checkpoint) -> Result<MigrationReport, StateMigrationError> {
+        self.migrate(&mut checkpoint.state.clone())
+    }
+}
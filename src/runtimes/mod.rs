@@ -18,6 +18,8 @@
 //!
 //! - **[`InMemoryCheckpointer`]** - Volatile storage for testing and development
 //! - **[`SQLiteCheckpointer`]** - Durable SQLite-backed persistence
+//! - **`ObjectStoreCheckpointer`** - One JSON object per step in S3, GCS, Azure,
+//!   or local storage (`object-store` feature)
 //!
 //! # Usage Example
 //!
@@ -42,6 +44,9 @@
 //! ```
 
 pub mod checkpointer;
+#[cfg(feature = "object-store")]
+#[cfg_attr(docsrs, doc(cfg(feature = "object-store")))]
+pub mod checkpointer_object_store;
 #[cfg(feature = "postgres")]
 #[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
 pub mod checkpointer_postgres;
@@ -52,23 +57,37 @@ mod checkpointer_postgres_helpers;
 pub mod checkpointer_sqlite;
 #[cfg(feature = "sqlite")]
 mod checkpointer_sqlite_helpers;
+pub mod compaction;
+pub mod debug;
 pub mod execution;
+pub mod hooks;
 #[cfg(feature = "metrics")]
 #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
 pub mod metrics_observer;
+pub mod migration;
 pub mod observer;
 pub mod persistence;
+pub mod profiling;
 pub mod replay;
+pub mod run_summary;
 pub mod runner;
 pub mod runtime_config;
 pub mod session;
+pub mod simulation;
+pub mod state_limits;
+pub mod step_query;
 mod streaming;
+pub mod triggers;
 pub mod types;
+pub mod what_if;
 
 pub use checkpointer::{
-    Checkpoint, Checkpointer, CheckpointerError, CheckpointerType, InMemoryCheckpointer,
-    restore_session_state,
+    ChannelArchive, Checkpoint, Checkpointer, CheckpointerError, CheckpointerType,
+    InMemoryCheckpointer, MessageMatch, NodeInputCapture, SessionStats, restore_session_state,
 };
+#[cfg(feature = "object-store")]
+#[cfg_attr(docsrs, doc(cfg(feature = "object-store")))]
+pub use checkpointer_object_store::ObjectStoreCheckpointer;
 #[cfg(feature = "postgres")]
 #[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
 pub use checkpointer_postgres::{
@@ -77,13 +96,33 @@ pub use checkpointer_postgres::{
 };
 #[cfg(feature = "sqlite")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
-pub use checkpointer_sqlite::{PageInfo, SQLiteCheckpointer, StepQuery, StepQueryResult};
+pub use checkpointer_sqlite::SQLiteCheckpointer;
+pub use compaction::{COMPACTION_MARKER_KEY, CompactionMarker, CompactionPolicy, CompactionReport};
+pub use debug::DebugSession;
+pub use step_query::{
+    DEFAULT_STEP_QUERY_LIMIT, MAX_STEP_QUERY_LIMIT, PageInfo, StepHistory, StepQuery,
+    StepQueryResult,
+};
+
+pub use migration::{
+    AppliedMigration, MigrationFn, MigrationReport, STATE_SCHEMA_VERSION_KEY, StateMigrationError,
+    StateMigrations,
+};
 
 // Re-export execution types
-pub use execution::{PausedReason, PausedReport, StepOptions, StepReport, StepResult};
+pub use execution::{
+    FrontierEdge, FrontierEdgeKind, NodeMetricsSummary, PausedReason, PausedReport, SessionMetrics,
+    StepOptions, StepReport, StepResult,
+};
+
+pub use hooks::{
+    DEFAULT_HOOK_BUDGET, RegisteredHook, SuperstepHook, SuperstepHookOptions, SuperstepUpdate,
+};
+
+pub use profiling::{NodeProfile, PROFILE_STEP_LIMIT, SessionProfile, SuperstepProfile};
 
 // Re-export session types
-pub use session::{SessionInit, SessionState, StateVersions};
+pub use session::{SessionHandle, SessionInit, SessionState, StateVersions};
 
 // Re-export runner
 pub use runner::{AppRunner, AppRunnerBuilder, RunMetadata};
@@ -94,8 +133,21 @@ pub use replay::{
     compare_final_state_with, compare_replay_runs, compare_replay_runs_with,
     compare_replay_runs_with_profile, normalize_event, normalize_state, normalize_state_with,
 };
-pub use runtime_config::{EventBusConfig, RuntimeConfig, SinkConfig};
+pub use run_summary::RunSummary;
+pub use runtime_config::{CheckpointFailurePolicy, EventBusConfig, RuntimeConfig, SinkConfig};
+pub use simulation::{
+    SimulatedAction, SimulationError, SimulationReport, SimulationRunner, StubKind,
+};
+pub use state_limits::{StateLimit, StateLimitBreach, StateLimitPolicy, StateLimits, StateSize};
+#[cfg(feature = "postgres")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+pub use triggers::PostgresNotifyTrigger;
+pub use triggers::{
+    StateFactory, StreamTrigger, TriggerError, TriggerMessage, TriggerReport, TriggerSource,
+    TriggerWorker,
+};
 pub use types::{SessionId, StepNumber};
+pub use what_if::{Divergence, DivergenceKind, WhatIfAnalysis, WhatIfError, WhatIfReport};
 
 #[cfg(feature = "metrics")]
 pub use metrics_observer::MetricsObserver;
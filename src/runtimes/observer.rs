@@ -47,9 +47,10 @@
 //!
 //! In this release, `step_duration_ms` in [`NodeFinishMeta`] reflects the elapsed
 //! time for the **entire superstep** that contained the node, not the per-node
-//! wall time. Nodes within the same superstep share the step's duration. Per-node
-//! timing would require scheduler-level instrumentation and is planned for a
-//! future release.
+//! wall time. Nodes within the same superstep share the step's duration. For
+//! per-node start times, queue wait, and run time, enable
+//! [`AppRunnerBuilder::profile_supersteps`](crate::runtimes::AppRunnerBuilder::profile_supersteps)
+//! and read [`StepReport::profile`](crate::runtimes::StepReport::profile).
 
 use std::fmt;
 use std::panic::RefUnwindSafe;
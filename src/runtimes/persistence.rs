@@ -15,14 +15,16 @@ This module intentionally does NOT perform I/O. It is pure data
 transformation and (de)serialization glue.
 */
 
+use bytes::Bytes;
 use chrono::Utc;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
-    channels::{Channel, ExtrasChannel, MessagesChannel},
+    channels::{Blob, BlobsChannel, Channel, ExtrasChannel, MessagesChannel, base64_bytes},
     message::Message,
+    node::NodeMetrics,
     runtimes::checkpointer::Checkpoint,
     state::VersionedState,
     types::NodeKind,
@@ -45,6 +47,7 @@ where
 
 /// Channel that stores a vector collection (e.g., messages) with version metadata.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PersistedVecChannel<T> {
     /// Version counter for change-detection.
     pub version: u32,
@@ -64,6 +67,7 @@ impl<T> Default for PersistedVecChannel<T> {
 
 /// Channel that stores a map collection (e.g., extra) with version metadata.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PersistedMapChannel<V> {
     /// Version counter for change-detection.
     pub version: u32,
@@ -72,6 +76,12 @@ pub struct PersistedMapChannel<V> {
     pub map: FxHashMap<String, V>,
 }
 
+impl<V> PersistedMapChannel<V> {
+    fn is_initial(&self) -> bool {
+        self.version == 1 && self.map.is_empty()
+    }
+}
+
 impl<V> Default for PersistedMapChannel<V> {
     fn default() -> Self {
         Self {
@@ -83,6 +93,7 @@ impl<V> Default for PersistedMapChannel<V> {
 
 /// Complete persisted shape of the in‑memory VersionedState.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PersistedState {
     /// Persisted messages channel.
     pub messages: PersistedVecChannel<Message>,
@@ -91,15 +102,158 @@ pub struct PersistedState {
     /// Persisted errors channel.
     #[serde(default)]
     pub errors: PersistedVecChannel<crate::channels::errors::ErrorEvent>,
+    /// Persisted blobs channel; omitted while it is empty and unversioned.
+    #[serde(default, skip_serializing_if = "PersistedMapChannel::is_initial")]
+    pub blobs: PersistedMapChannel<PersistedBlob>,
+}
+
+/// A binary payload in persisted state.
+///
+/// `data` carries the payload inline (base64 in JSON). Backends with binary
+/// columns move it out with [`PersistedState::take_blob_payloads`], store each
+/// distinct payload once under its digest, and put it back with
+/// [`PersistedState::restore_blob_payloads`] when loading.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PersistedBlob {
+    /// Media type of the payload, if one was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Payload size in bytes.
+    pub len: u64,
+    /// Content address of the payload; see [`Blob::digest`].
+    pub digest: String,
+    /// The payload, unless the backend stores it separately.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "base64_bytes::option"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    pub data: Option<Bytes>,
+}
+
+impl From<&Blob> for PersistedBlob {
+    fn from(blob: &Blob) -> Self {
+        Self {
+            content_type: blob.content_type().map(str::to_string),
+            len: blob.len() as u64,
+            digest: blob.digest(),
+            data: Some(blob.data().clone()),
+        }
+    }
+}
+
+impl PersistedState {
+    /// Remove the inline blob payloads, returning each distinct payload keyed by digest.
+    pub fn take_blob_payloads(&mut self) -> FxHashMap<String, Bytes> {
+        let mut payloads = FxHashMap::default();
+        for blob in self.blobs.map.values_mut() {
+            if let Some(data) = blob.data.take() {
+                payloads.entry(blob.digest.clone()).or_insert(data);
+            }
+        }
+        payloads
+    }
+
+    /// Digests of blobs whose payload is not inline, sorted and deduplicated.
+    #[must_use]
+    pub fn missing_blob_payloads(&self) -> Vec<String> {
+        let mut digests: Vec<String> = self
+            .blobs
+            .map
+            .values()
+            .filter(|blob| blob.data.is_none())
+            .map(|blob| blob.digest.clone())
+            .collect();
+        digests.sort_unstable();
+        digests.dedup();
+        digests
+    }
+
+    /// Reattach payloads removed by [`take_blob_payloads`](Self::take_blob_payloads).
+    ///
+    /// Blobs whose digest is absent from `payloads` are left without data and
+    /// fail the conversion back into a [`VersionedState`].
+    pub fn restore_blob_payloads(&mut self, payloads: &FxHashMap<String, Bytes>) {
+        for blob in self.blobs.map.values_mut() {
+            if blob.data.is_none() {
+                blob.data = payloads.get(&blob.digest).cloned();
+            }
+        }
+    }
 }
 
 /// Wrapper for the scheduler versions_seen structure.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PersistedVersionsSeen(pub FxHashMap<String, FxHashMap<String, u64>>);
 
+/// Scheduler bookkeeping kept beside `versions_seen`: pending join arrivals,
+/// frontier slot origins, and quota run counts. Every field defaults to empty,
+/// so checkpoints written before these fields existed still load.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PersistedSchedulerBookkeeping {
+    /// Join target → origin → step of arrival, all `NodeKind::encode()`d.
+    #[serde(default)]
+    pub pending_joins: FxHashMap<String, FxHashMap<String, u64>>,
+    /// Encoded origin of each frontier slot; `null` marks a merged slot.
+    #[serde(default)]
+    pub frontier_origins: Vec<Option<String>>,
+    /// Encoded node → times run, for nodes with quotas.
+    #[serde(default)]
+    pub run_counts: FxHashMap<String, u64>,
+}
+
+impl From<&Checkpoint> for PersistedSchedulerBookkeeping {
+    fn from(cp: &Checkpoint) -> Self {
+        PersistedSchedulerBookkeeping {
+            pending_joins: cp.pending_joins.clone(),
+            frontier_origins: cp.frontier_origins.clone(),
+            run_counts: cp.run_counts.clone(),
+        }
+    }
+}
+
+/// Execution metrics for one node, keyed by its encoded [`NodeKind`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PersistedNodeMetrics {
+    /// Node encoded with `NodeKind::encode()`.
+    pub node: String,
+    /// The recorded metrics.
+    #[serde(flatten)]
+    pub metrics: NodeMetrics,
+}
+
+impl PersistedNodeMetrics {
+    /// Encode runtime `(NodeKind, NodeMetrics)` pairs.
+    #[must_use]
+    pub fn encode_all(metrics: &[(NodeKind, NodeMetrics)]) -> Vec<Self> {
+        metrics
+            .iter()
+            .map(|(kind, metrics)| Self {
+                node: kind.encode(),
+                metrics: *metrics,
+            })
+            .collect()
+    }
+
+    /// Decode persisted entries back into `(NodeKind, NodeMetrics)` pairs.
+    #[must_use]
+    pub fn decode_all(persisted: Vec<Self>) -> Vec<(NodeKind, NodeMetrics)> {
+        persisted
+            .into_iter()
+            .map(|p| (NodeKind::decode(&p.node), p.metrics))
+            .collect()
+    }
+}
+
 /// Full persisted checkpoint representation.
 /// (Step history tables may store multiple instances of this shape.)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PersistedCheckpoint {
     /// Unique session identifier.
     pub session_id: String,
@@ -111,6 +265,9 @@ pub struct PersistedCheckpoint {
     pub frontier: Vec<String>,
     /// Scheduler version-gating state.
     pub versions_seen: PersistedVersionsSeen,
+    /// Scheduler join, frontier-origin, and quota bookkeeping.
+    #[serde(flatten)]
+    pub scheduler: PersistedSchedulerBookkeeping,
     /// Maximum concurrent nodes for this session.
     pub concurrency_limit: usize,
     /// RFC3339 string form of creation time (keeps chrono::DateTime out of serialized shape).
@@ -124,6 +281,9 @@ pub struct PersistedCheckpoint {
     /// Channels that were updated in this step
     #[serde(default)]
     pub updated_channels: Vec<String>,
+    /// Per-node execution metrics for this step
+    #[serde(default)]
+    pub node_metrics: Vec<PersistedNodeMetrics>,
 }
 
 use thiserror::Error;
@@ -187,6 +347,15 @@ impl From<&VersionedState> for PersistedState {
                 version: s.errors.version(),
                 items: s.errors.snapshot(),
             },
+            blobs: PersistedMapChannel {
+                version: s.blobs.version(),
+                map: s
+                    .blobs
+                    .snapshot()
+                    .iter()
+                    .map(|(key, blob)| (key.clone(), PersistedBlob::from(blob)))
+                    .collect(),
+            },
         }
     }
 }
@@ -195,10 +364,29 @@ impl TryFrom<PersistedState> for VersionedState {
     type Error = PersistenceError;
 
     fn try_from(p: PersistedState) -> Result<Self> {
+        let mut blobs = FxHashMap::default();
+        for (key, blob) in p.blobs.map {
+            let data = blob.data.ok_or_else(|| {
+                PersistenceError::Other(format!("blob {key:?} ({}) has no payload", blob.digest))
+            })?;
+            if data.len() as u64 != blob.len {
+                return Err(PersistenceError::Other(format!(
+                    "blob {key:?} payload is {} bytes, expected {}",
+                    data.len(),
+                    blob.len
+                )));
+            }
+            let mut restored = Blob::new(data);
+            if let Some(content_type) = blob.content_type {
+                restored = restored.with_content_type(content_type);
+            }
+            blobs.insert(key, restored);
+        }
         Ok(VersionedState {
             messages: MessagesChannel::new(p.messages.items, p.messages.version),
             extra: ExtrasChannel::new(p.extra.map, p.extra.version),
             errors: crate::channels::ErrorsChannel::new(p.errors.items, p.errors.version),
+            blobs: BlobsChannel::new(blobs, p.blobs.version),
         })
     }
 }
@@ -227,11 +415,13 @@ impl From<&Checkpoint> for PersistedCheckpoint {
             state: PersistedState::from(&cp.state),
             frontier: cp.frontier.iter().map(|k| k.encode()).collect(),
             versions_seen: PersistedVersionsSeen(cp.versions_seen.clone()),
+            scheduler: PersistedSchedulerBookkeeping::from(cp),
             concurrency_limit: cp.concurrency_limit,
             created_at: cp.created_at.to_rfc3339(),
             ran_nodes: cp.ran_nodes.iter().map(|k| k.encode()).collect(),
             skipped_nodes: cp.skipped_nodes.iter().map(|k| k.encode()).collect(),
             updated_channels: cp.updated_channels.clone(),
+            node_metrics: PersistedNodeMetrics::encode_all(&cp.node_metrics),
         }
     }
 }
@@ -257,11 +447,15 @@ impl TryFrom<PersistedCheckpoint> for Checkpoint {
             state,
             frontier,
             versions_seen: p.versions_seen.0,
+            pending_joins: p.scheduler.pending_joins,
+            frontier_origins: p.scheduler.frontier_origins,
+            run_counts: p.scheduler.run_counts,
             concurrency_limit: p.concurrency_limit,
             created_at: parsed_dt,
             ran_nodes,
             skipped_nodes,
             updated_channels: p.updated_channels,
+            node_metrics: PersistedNodeMetrics::decode_all(p.node_metrics),
         })
     }
 }
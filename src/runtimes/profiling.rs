@@ -0,0 +1,341 @@
+//! Per-superstep profiling records and Chrome trace export.
+//!
+//! When a runner is built with
+//! [`AppRunnerBuilder::profile_supersteps`](crate::runtimes::AppRunnerBuilder::profile_supersteps),
+//! every superstep produces a [`SuperstepProfile`]: when each node started and
+//! finished, how long it queued for a concurrency slot, and how long the
+//! barrier, its reducers, and frontier routing took. The runner keeps the
+//! profiles of each session, retrievable with
+//! [`AppRunner::session_profile`](crate::runtimes::AppRunner::session_profile).
+//!
+//! [`SessionProfile::to_chrome_trace`] renders the records in the Chrome trace
+//! event format, which `chrome://tracing` and [Perfetto](https://ui.perfetto.dev)
+//! open directly.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use weavegraph::runtimes::AppRunner;
+//! # use weavegraph::app::App;
+//! # use weavegraph::state::VersionedState;
+//! # async fn example(app: App) -> Result<(), Box<dyn std::error::Error>> {
+//!
+//! let mut runner = AppRunner::builder()
+//!     .app(app)
+//!     .profile_supersteps(true)
+//!     .build()
+//!     .await;
+//! runner
+//!     .create_session("profiled".into(), VersionedState::new_with_user_message("hi"))
+//!     .await?;
+//! runner.run_until_complete("profiled").await?;
+//!
+//! let trace = runner.session_profile("profiled")?.to_chrome_trace();
+//! std::fs::write("profile.json", serde_json::to_vec(&trace)?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::schedulers::NodeTiming;
+use crate::types::NodeKind;
+
+/// Number of superstep profiles a runner keeps per session; older ones are dropped.
+pub const PROFILE_STEP_LIMIT: usize = 1024;
+
+/// Trace thread carrying superstep phases; node runs use lanes from 1 upwards.
+const PHASE_LANE: u64 = 0;
+
+/// Timing of one node run within a superstep.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct NodeProfile {
+    /// The node that ran.
+    pub node: NodeKind,
+    /// Time from the start of the superstep until the node started, in microseconds.
+    pub start_offset_micros: u64,
+    /// Time the node waited for a free concurrency slot, in microseconds.
+    pub queue_wait_micros: u64,
+    /// Time spent in [`Node::run`](crate::node::Node::run), in microseconds.
+    pub duration_micros: u64,
+}
+
+impl NodeProfile {
+    /// Time from the start of the superstep until the node finished, in microseconds.
+    #[must_use]
+    pub fn end_offset_micros(&self) -> u64 {
+        self.start_offset_micros
+            .saturating_add(self.duration_micros)
+    }
+}
+
+/// Where the time of one superstep went.
+///
+/// Phases are measured back to back: `schedule_micros` covers node execution
+/// (including barriers applied to incremental partials while nodes ran),
+/// followed by the final barrier and frontier routing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SuperstepProfile {
+    /// The superstep these records belong to.
+    pub step: u64,
+    /// When the superstep started, in microseconds since the Unix epoch.
+    pub started_at_micros: u64,
+    /// Time from the start of the superstep until the next frontier was computed, in microseconds.
+    pub duration_micros: u64,
+    /// Time spent running nodes, in microseconds.
+    pub schedule_micros: u64,
+    /// Time spent in the barrier after all nodes finished, in microseconds.
+    pub barrier_micros: u64,
+    /// Time spent in reducers across all barriers of the superstep, in microseconds.
+    pub reducer_micros: u64,
+    /// Time spent resolving commands and conditional edges, in microseconds.
+    pub frontier_micros: u64,
+    /// Node runs, in scheduling order.
+    pub nodes: Vec<NodeProfile>,
+}
+
+impl SuperstepProfile {
+    /// Summed time node runs waited for a free concurrency slot, in microseconds.
+    #[must_use]
+    pub fn total_queue_wait_micros(&self) -> u64 {
+        self.nodes
+            .iter()
+            .fold(0, |acc, node| acc.saturating_add(node.queue_wait_micros))
+    }
+
+    /// Summed node run time, in microseconds.
+    #[must_use]
+    pub fn total_execution_micros(&self) -> u64 {
+        self.nodes
+            .iter()
+            .fold(0, |acc, node| acc.saturating_add(node.duration_micros))
+    }
+}
+
+/// The superstep profiles a runner recorded for one session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SessionProfile {
+    /// The profiled session.
+    pub session_id: String,
+    /// Profiles of the most recent supersteps, oldest first.
+    pub steps: Vec<SuperstepProfile>,
+}
+
+impl SessionProfile {
+    /// Render the profile as a Chrome trace (`{"traceEvents": [...]}`).
+    ///
+    /// Each superstep and its phases are complete (`"X"`) events on thread 0;
+    /// node runs are laid out on threads 1 and up so concurrent runs never
+    /// overlap on one lane, each preceded by its queue wait when it had one.
+    /// Timestamps are microseconds since the Unix epoch.
+    #[must_use]
+    pub fn to_chrome_trace(&self) -> Value {
+        let mut events = vec![
+            metadata_event("process_name", 0, json!({ "name": self.session_id })),
+            metadata_event("thread_name", PHASE_LANE, json!({ "name": "supersteps" })),
+        ];
+        let mut lanes_named = 0;
+        for profile in &self.steps {
+            let base = profile.started_at_micros;
+            let args = json!({ "step": profile.step });
+            events.push(complete_event(
+                &format!("superstep {}", profile.step),
+                "superstep",
+                base,
+                profile.duration_micros,
+                PHASE_LANE,
+                args.clone(),
+            ));
+            let mut offset = base;
+            for (name, micros) in [
+                ("schedule", profile.schedule_micros),
+                ("barrier", profile.barrier_micros),
+                ("frontier", profile.frontier_micros),
+            ] {
+                events.push(complete_event(
+                    name,
+                    "phase",
+                    offset,
+                    micros,
+                    PHASE_LANE,
+                    args.clone(),
+                ));
+                offset = offset.saturating_add(micros);
+            }
+            if profile.reducer_micros > 0 {
+                // Reducers run inside barriers; nest them at the end of the
+                // final one, clamped so micro-barrier time cannot spill out.
+                let barrier_end = base
+                    .saturating_add(profile.schedule_micros)
+                    .saturating_add(profile.barrier_micros);
+                let dur = profile.reducer_micros.min(profile.barrier_micros);
+                events.push(complete_event(
+                    "reducers",
+                    "phase",
+                    barrier_end.saturating_sub(dur),
+                    dur,
+                    PHASE_LANE,
+                    json!({ "step": profile.step, "total_micros": profile.reducer_micros }),
+                ));
+            }
+
+            let mut lane_free_at: Vec<u64> = Vec::new();
+            for node in &profile.nodes {
+                let queued_from = node
+                    .start_offset_micros
+                    .saturating_sub(node.queue_wait_micros);
+                let lane = match lane_free_at.iter().position(|free| *free <= queued_from) {
+                    Some(lane) => lane,
+                    None => {
+                        lane_free_at.push(0);
+                        lane_free_at.len() - 1
+                    }
+                };
+                lane_free_at[lane] = node.end_offset_micros();
+                let tid = lane as u64 + 1;
+                if lane >= lanes_named {
+                    lanes_named = lane + 1;
+                    events.push(metadata_event(
+                        "thread_name",
+                        tid,
+                        json!({ "name": format!("lane {lane}") }),
+                    ));
+                }
+                let node_name = node.node.to_string();
+                let node_args = json!({ "step": profile.step, "node": node_name });
+                if node.queue_wait_micros > 0 {
+                    events.push(complete_event(
+                        &format!("{node_name} (queued)"),
+                        "queue",
+                        base.saturating_add(queued_from),
+                        node.queue_wait_micros,
+                        tid,
+                        node_args.clone(),
+                    ));
+                }
+                events.push(complete_event(
+                    &node_name,
+                    "node",
+                    base.saturating_add(node.start_offset_micros),
+                    node.duration_micros,
+                    tid,
+                    node_args,
+                ));
+            }
+        }
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+}
+
+fn complete_event(name: &str, cat: &str, ts: u64, dur: u64, tid: u64, args: Value) -> Value {
+    json!({
+        "name": name,
+        "cat": cat,
+        "ph": "X",
+        "ts": ts,
+        "dur": dur,
+        "pid": 0,
+        "tid": tid,
+        "args": args,
+    })
+}
+
+fn metadata_event(name: &str, tid: u64, args: Value) -> Value {
+    json!({ "name": name, "ph": "M", "pid": 0, "tid": tid, "args": args })
+}
+
+pub(crate) fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+/// Clock readings taken while a superstep runs, turned into a [`SuperstepProfile`].
+pub(crate) struct SuperstepTimer {
+    started: Instant,
+    started_at_micros: u64,
+    schedule: Duration,
+    barrier: Duration,
+}
+
+impl SuperstepTimer {
+    pub(crate) fn start() -> Self {
+        let started_at_micros = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, micros);
+        Self {
+            started: Instant::now(),
+            started_at_micros,
+            schedule: Duration::ZERO,
+            barrier: Duration::ZERO,
+        }
+    }
+
+    /// Mark the end of node execution.
+    pub(crate) fn scheduled(&mut self) {
+        self.schedule = self.started.elapsed();
+    }
+
+    /// Mark the end of the final barrier.
+    pub(crate) fn barrier_applied(&mut self) {
+        self.barrier = self.started.elapsed().saturating_sub(self.schedule);
+    }
+
+    /// Mark the end of frontier routing and assemble the profile.
+    pub(crate) fn finish(
+        self,
+        step: u64,
+        reducer_micros: u64,
+        node_timings: &[(NodeKind, NodeTiming)],
+    ) -> SuperstepProfile {
+        let elapsed = self.started.elapsed();
+        let nodes = node_timings
+            .iter()
+            .map(|(node, timing)| NodeProfile {
+                node: node.clone(),
+                start_offset_micros: micros(
+                    timing.started_at.saturating_duration_since(self.started),
+                ),
+                queue_wait_micros: micros(timing.queue_wait),
+                duration_micros: micros(timing.duration),
+            })
+            .collect();
+        SuperstepProfile {
+            step,
+            started_at_micros: self.started_at_micros,
+            duration_micros: micros(elapsed),
+            schedule_micros: micros(self.schedule),
+            barrier_micros: micros(self.barrier),
+            reducer_micros,
+            frontier_micros: micros(elapsed.saturating_sub(self.schedule + self.barrier)),
+            nodes,
+        }
+    }
+}
+
+/// Bounded per-session profile log kept by the runner.
+#[derive(Debug, Default)]
+pub(crate) struct ProfileLog {
+    steps: VecDeque<SuperstepProfile>,
+}
+
+impl ProfileLog {
+    pub(crate) fn record(&mut self, profile: SuperstepProfile) {
+        self.steps.push_back(profile);
+        if self.steps.len() > PROFILE_STEP_LIMIT {
+            self.steps.pop_front();
+        }
+    }
+
+    pub(crate) fn to_session_profile(&self, session_id: &str) -> SessionProfile {
+        SessionProfile {
+            session_id: session_id.to_string(),
+            steps: self.steps.iter().cloned().collect(),
+        }
+    }
+}
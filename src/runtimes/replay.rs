@@ -112,14 +112,40 @@ pub fn normalize_event(event: &Event) -> Value {
 /// Normalize a final state into a JSON value for stable comparison and diffs.
 #[must_use]
 pub fn normalize_state(state: &VersionedState) -> Value {
-    json!({
+    let mut value = json!({
         "messages": state.messages.snapshot(),
         "messages_version": state.messages.version(),
         "extra": state.extra.snapshot(),
         "extra_version": state.extra.version(),
         "errors": state.errors.snapshot(),
         "errors_version": state.errors.version(),
-    })
+    });
+    normalize_blobs(state, &mut value);
+    value
+}
+
+/// Add blobs, summarized by media type, length, and digest, when there are any.
+fn normalize_blobs(state: &VersionedState, value: &mut Value) {
+    if state.blobs.is_empty() {
+        return;
+    }
+    let blobs: serde_json::Map<String, Value> = state
+        .blobs
+        .snapshot()
+        .iter()
+        .map(|(key, blob)| {
+            let summary = json!({
+                "content_type": blob.content_type(),
+                "len": blob.len(),
+                "digest": blob.digest(),
+            });
+            (key.clone(), summary)
+        })
+        .collect();
+    if let Value::Object(object) = value {
+        object.insert("blobs".into(), Value::Object(blobs));
+        object.insert("blobs_version".into(), json!(state.blobs.version()));
+    }
 }
 
 /// Compare two final states with default normalization.
@@ -339,14 +365,16 @@ pub fn normalize_state_with(state: &VersionedState, profile: &StateNormalizeProf
     for key in profile.ignored_keys() {
         extra.remove(key);
     }
-    json!({
+    let mut value = json!({
         "messages": state.messages.snapshot(),
         "messages_version": state.messages.version(),
         "extra": extra,
         "extra_version": state.extra.version(),
         "errors": state.errors.snapshot(),
         "errors_version": state.errors.version(),
-    })
+    });
+    normalize_blobs(state, &mut value);
+    value
 }
 
 /// Compare two final states using a caller-provided normalization profile.
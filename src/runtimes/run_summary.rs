@@ -0,0 +1,160 @@
+//! Aggregate statistics for one run of a session.
+//!
+//! [`AppRunner::run_until_complete_with_summary`](crate::runtimes::AppRunner::run_until_complete_with_summary)
+//! and [`App::invoke_with_summary`](crate::app::App::invoke_with_summary)
+//! return a [`RunSummary`] alongside the final state. Every run that completes
+//! also publishes it as a node event with [`RUN_SUMMARY_SCOPE`], just before the
+//! stream-end or invocation-end event, so subscribers need not recompute
+//! statistics from the step events. [`RunSummary::from_event`] parses it back.
+//!
+//! A summary covers the supersteps executed by that call only: a session
+//! resumed from a checkpoint reports the steps run after resuming.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::channels::errors::ErrorSeverity;
+use crate::event_bus::{Event, NodeEvent, RUN_SUMMARY_SCOPE};
+use crate::node::TokenUsage;
+use crate::runtimes::execution::{NodeMetricsSummary, StepReport};
+use crate::types::NodeKind;
+
+/// Statistics for one completed run of a session.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use weavegraph::channels::errors::ErrorSeverity;
+/// use weavegraph::state::VersionedState;
+/// # async fn example(app: weavegraph::app::App) -> Result<(), Box<dyn std::error::Error>> {
+/// let (final_state, summary) = app
+///     .invoke_with_summary(VersionedState::new_with_user_message("hi"))
+///     .await?;
+/// println!(
+///     "{} steps, {} node runs, {} errors in {:?}",
+///     summary.steps,
+///     summary.total_node_runs(),
+///     summary.error_count(ErrorSeverity::Error),
+///     summary.duration(),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RunSummary {
+    /// Session the run belongs to.
+    pub session_id: String,
+    /// Supersteps executed during the run.
+    pub steps: u64,
+    /// Session step counter when the run completed.
+    pub final_step: u64,
+    /// Wall-clock duration of the run, in microseconds.
+    pub duration_micros: u64,
+    /// Runs and latencies per node, keyed by [`NodeKind::encode`].
+    pub nodes: BTreeMap<String, NodeMetricsSummary>,
+    /// Number of supersteps in which each channel was updated.
+    pub channels_updated: BTreeMap<String, u64>,
+    /// Errors recorded at barriers, by severity.
+    pub errors_by_severity: BTreeMap<ErrorSeverity, u64>,
+    /// Summed token usage, if any node reported usage.
+    pub token_usage: Option<TokenUsage>,
+}
+
+impl RunSummary {
+    /// Aggregates for one node, if it ran.
+    #[must_use]
+    pub fn node(&self, kind: &NodeKind) -> Option<&NodeMetricsSummary> {
+        self.nodes.get(&kind.encode())
+    }
+
+    /// Node runs across all nodes.
+    #[must_use]
+    pub fn total_node_runs(&self) -> u64 {
+        self.nodes.values().map(|node| node.invocations).sum()
+    }
+
+    /// Errors recorded with `severity`.
+    #[must_use]
+    pub fn error_count(&self, severity: ErrorSeverity) -> u64 {
+        self.errors_by_severity.get(&severity).copied().unwrap_or(0)
+    }
+
+    /// Wall-clock duration of the run.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_micros(self.duration_micros)
+    }
+
+    /// The event the runner publishes for this summary, with the summary as
+    /// `summary` metadata and the session id as `invocation_id`.
+    #[must_use]
+    pub fn to_event(&self) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert("invocation_id".to_string(), json!(self.session_id));
+        metadata.insert(
+            "summary".to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        );
+        let message = format!(
+            "run summary steps={} node_runs={} errors={} duration_ms={}",
+            self.steps,
+            self.total_node_runs(),
+            self.errors_by_severity.values().sum::<u64>(),
+            self.duration_micros / 1000
+        );
+        Event::Node(
+            NodeEvent::new(
+                None,
+                Some(self.final_step),
+                RUN_SUMMARY_SCOPE.to_string(),
+                message,
+            )
+            .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a summary published by the runner, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node_event) = event else {
+            return None;
+        };
+        if node_event.scope() != RUN_SUMMARY_SCOPE {
+            return None;
+        }
+        serde_json::from_value(node_event.metadata().get("summary")?.clone()).ok()
+    }
+
+    pub(crate) fn new(session_id: &str) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn record(&mut self, report: &StepReport) {
+        self.steps += 1;
+        self.final_step = report.step;
+        for (kind, metrics) in &report.node_metrics {
+            let node = self.nodes.entry(kind.encode()).or_default();
+            node.record(metrics);
+            if let Some(usage) = metrics.token_usage {
+                *self.token_usage.get_or_insert_with(TokenUsage::default) += usage;
+            }
+        }
+        for channel in &report.barrier_outcome.updated_channels {
+            *self
+                .channels_updated
+                .entry((*channel).to_string())
+                .or_default() += 1;
+        }
+        for error in &report.barrier_outcome.errors {
+            *self.errors_by_severity.entry(error.severity).or_default() += 1;
+        }
+    }
+}
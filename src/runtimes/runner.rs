@@ -10,34 +10,66 @@
 //! the constituent modules.
 
 use crate::app::{App, BarrierOutcome};
-use crate::channels::Channel;
-use crate::channels::errors::{ErrorEvent, ErrorScope, WeaveError};
-use crate::control::{FrontierCommand, NodeRoute};
+use crate::channels::errors::{ErrorEvent, ErrorScope, ErrorSeverity, WeaveError};
+use crate::channels::{Channel, ChannelVersionOverflow};
+use crate::control::{COMMAND_INBOX_KEY, CommandInbox, FrontierCommand, NodeRoute};
+use crate::event_bus::aggregate::step_metrics_event;
 use crate::event_bus::emitter::{EmitterError, EventEmitter};
 use crate::event_bus::event::Event;
-use crate::event_bus::{EventBus, EventStream};
-use crate::node::NodePartial;
-use crate::runtimes::CheckpointerType;
+use crate::event_bus::{
+    AttemptRetraction, EXTRA_CONFLICT_SCOPE, EventBus, EventHubMetrics, EventStream, FlushError,
+};
+use crate::feature_flags::{FeatureFlags, NO_FLAGS};
+use crate::graphs::{
+    DerivedCache, DerivedValues, DynamicGraph, JoinPolicy, OutputValidationError, QuotaFallback,
+    RoutingContext, SlaTracker,
+};
+use crate::llm::audit::LlmAuditLog;
+use crate::llm::{LlmAuditRecord, LlmCall};
+use crate::message::Message;
+use crate::node::{CancellationToken, NodeMetrics, NodePartial, PartialStream};
+use crate::redaction::RedactionPolicy;
+use crate::runtimes::compaction::{
+    COMPACTION_MARKER_KEY, CompactionMarker, CompactionPolicy, CompactionReport,
+};
 use crate::runtimes::execution::{
-    PausedReason, PausedReport, SchedulerOutcome, StepOptions, StepReport, StepResult,
+    FrontierEdge, FrontierEdgeKind, PausedReason, PausedReport, SchedulerOutcome, SessionMetrics,
+    StepOptions, StepReport, StepResult,
 };
+use crate::runtimes::hooks::{HookDriver, SuperstepUpdate};
+use crate::runtimes::migration::StateMigrationError;
 use crate::runtimes::observer::{
     CheckpointLoadMeta, CheckpointSaveMeta, EventBusEmitMeta, InvocationFinishMeta,
     InvocationOutcome, InvocationStartMeta, NodeFinishMeta, NodeOutcome, RuntimeObserver,
 };
-use crate::runtimes::session::{SessionInit, SessionState, StateVersions};
-use crate::runtimes::streaming::{StreamEndReason, emit_invocation_end, finalize_event_stream};
+use crate::runtimes::profiling::{ProfileLog, SessionProfile, SuperstepTimer};
+use crate::runtimes::run_summary::RunSummary;
+use crate::runtimes::session::{SessionHandle, SessionInit, SessionState, StateVersions};
+use crate::runtimes::state_limits::{StateLimitBreach, StateLimitPolicy, StateSize};
+use crate::runtimes::streaming::{
+    StreamEndReason, close_event_stream, drain_event_sinks, emit_invocation_end, emit_stream_end,
+    finalize_event_stream,
+};
+use crate::runtimes::types::SessionId;
 use crate::runtimes::{
-    Checkpoint, Checkpointer, CheckpointerError, InMemoryCheckpointer, restore_session_state,
+    ChannelArchive, Checkpoint, Checkpointer, CheckpointerError, InMemoryCheckpointer,
+    NodeInputCapture, restore_session_state,
 };
-use crate::schedulers::{Scheduler, SchedulerError, SchedulerRunContext, SchedulerState};
-use crate::state::VersionedState;
+use crate::runtimes::{CheckpointFailurePolicy, CheckpointerType};
+use crate::schedulers::saturation::SchedulerTelemetry;
+use crate::schedulers::{
+    DEFAULT_CANCEL_GRACE_PERIOD, Scheduler, SchedulerError, SchedulerMetrics, SchedulerRunContext,
+    SchedulerState, SuperstepSaturation,
+};
+use crate::state::{StateSnapshot, VersionedState};
 use crate::types::NodeKind;
 use crate::utils::clock::Clock;
 use rustc_hash::FxHashMap;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::{mpsc, watch};
 use tokio::task::JoinError;
 use tracing::instrument;
 
@@ -45,6 +77,36 @@ use tracing::instrument;
 // Private helpers
 // ============================================================================
 
+/// Fold a barrier outcome into an accumulator, keeping channel names unique.
+fn merge_barrier_outcome(acc: &mut BarrierOutcome, outcome: BarrierOutcome) {
+    for channel in outcome.updated_channels {
+        if !acc.updated_channels.contains(&channel) {
+            acc.updated_channels.push(channel);
+        }
+    }
+    acc.errors.extend(outcome.errors);
+    acc.frontier_commands.extend(outcome.frontier_commands);
+    acc.conflicts.extend(outcome.conflicts);
+    acc.reducer_micros = acc.reducer_micros.saturating_add(outcome.reducer_micros);
+}
+
+/// Apply `policy` to an audit record, rooted at `llm_audit`.
+///
+/// If the redacted form no longer decodes as a record (a rule replaced a
+/// non-string field), the call contents are withheld rather than stored
+/// unredacted.
+fn redact_audit_record(policy: &RedactionPolicy, mut record: LlmAuditRecord) -> LlmAuditRecord {
+    let redacted = serde_json::to_value(&record).ok().and_then(|mut value| {
+        policy.redact_value(&mut value, "llm_audit");
+        serde_json::from_value(value).ok()
+    });
+    redacted.unwrap_or_else(|| {
+        record.call =
+            LlmCall::default().with_error("withheld: redaction produced an invalid record");
+        record
+    })
+}
+
 /// An [`EventEmitter`] wrapper that calls an observer's `on_event_bus_emit`
 /// hook after each successful (or failed) emit attempt.
 ///
@@ -202,6 +264,42 @@ pub struct AppRunner {
     clock: Option<Arc<dyn Clock>>,
     checkpointer_descriptor: String,
     observer: Option<Arc<dyn RuntimeObserver>>,
+    /// Per-session latest-step publishers handed out by [`AppRunner::watch_steps`].
+    step_watchers: FxHashMap<String, watch::Sender<StepReport>>,
+    /// Receives a state snapshot after every barrier (see [`App::invoke_stream_values`]).
+    state_values: Option<mpsc::UnboundedSender<StateSnapshot>>,
+    /// Per-session node metrics aggregated from step reports.
+    session_metrics: FxHashMap<String, SessionMetrics>,
+    /// How long finishing runs wait for event sinks to drain.
+    event_flush_timeout: Duration,
+    /// Graph whose latest revision is adopted before each step.
+    dynamic_graph: Option<Arc<DynamicGraph>>,
+    /// Revision of `dynamic_graph` that `app` was taken from.
+    graph_version: u64,
+    /// Rolling saturation samples, shared with the event bus's metrics.
+    scheduler_telemetry: Arc<SchedulerTelemetry>,
+    /// Per-session SLA bookkeeping (run start, breaches already reported).
+    sla_trackers: FxHashMap<String, SlaTracker>,
+    /// Node that `resume_with_input` restarts sessions from.
+    reentry_node: NodeKind,
+    /// Feature flags resolved per session when it was created or resumed.
+    session_flags: FxHashMap<String, Arc<FeatureFlags>>,
+    /// Whether a step metrics event is published after every barrier.
+    publish_step_metrics: bool,
+    /// Whether a superstep profile is recorded for every step.
+    profile_supersteps: bool,
+    /// Per-session superstep profiles, when `profile_supersteps` is set.
+    session_profiles: FxHashMap<String, ProfileLog>,
+    /// Per-session step most recently executed and how often it was attempted.
+    step_attempts: FxHashMap<String, (u64, u32)>,
+    /// Cancels runs of this runner; see [`AppRunnerBuilder::cancellation_token`].
+    cancellation: Option<CancellationToken>,
+    /// How long node runs may continue after cancellation.
+    cancel_grace_period: Duration,
+    /// Per-session delivery state of the configured superstep hooks.
+    superstep_hooks: FxHashMap<String, Vec<HookDriver>>,
+    /// Per-session cache of derived channel values.
+    derived_caches: FxHashMap<String, Arc<DerivedCache>>,
 }
 
 /// Errors that can occur during workflow execution.
@@ -247,6 +345,36 @@ pub enum RunnerError {
         node: NodeKind,
     },
 
+    /// No named entry point with this name was declared on the graph.
+    #[error("unknown entry point: {name}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::unknown_entry_point),
+            help("Declare the entry point with GraphBuilder::add_entry before compiling.")
+        )
+    )]
+    UnknownEntryPoint {
+        /// The requested entry point name.
+        name: String,
+    },
+
+    /// A starting frontier override named a node that cannot start a session.
+    #[error("invalid frontier override node: {node}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::invalid_frontier_override),
+            help(
+                "Frontier overrides may only contain registered custom nodes; Start and End are virtual."
+            )
+        )
+    )]
+    InvalidFrontierOverride {
+        /// The rejected node.
+        node: NodeKind,
+    },
+
     /// Execution paused unexpectedly during run_until_complete.
     #[error("unexpected pause during run_until_complete")]
     #[cfg_attr(
@@ -279,6 +407,91 @@ pub enum RunnerError {
     )]
     Checkpointer(#[from] CheckpointerError),
 
+    /// A loaded checkpoint could not be upgraded to the current state schema.
+    #[error("session {session_id}: {source}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::runner::state_migration))
+    )]
+    StateMigration {
+        /// Session whose checkpoint failed to migrate.
+        session_id: String,
+        /// The migration failure.
+        #[source]
+        source: StateMigrationError,
+    },
+
+    /// A superstep's checkpoint could not be saved, so the step was rolled back.
+    ///
+    /// Raised only under a transactional
+    /// [`CheckpointFailurePolicy`](crate::runtimes::CheckpointFailurePolicy).
+    /// The session is left at its last committed step; calling `run_step`
+    /// again re-executes the superstep.
+    #[error(
+        "checkpoint for step {step} failed after {attempts} attempt(s); step rolled back: {source}"
+    )]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::checkpoint_rollback),
+            help(
+                "The session is at its last persisted step; run the step again once the checkpointer is healthy."
+            )
+        )
+    )]
+    CheckpointRollback {
+        /// The superstep that was rolled back.
+        step: u64,
+        /// Save attempts made, across retries and reruns.
+        attempts: u32,
+        /// The last save error.
+        #[source]
+        source: CheckpointerError,
+    },
+
+    /// A node was scheduled after using up its run quota, and its
+    /// [`QuotaFallback`](crate::graphs::QuotaFallback) is `Fail`.
+    #[error("session {session_id}: node {node} exhausted its quota of {max_runs} run(s)")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::node_quota_exhausted),
+            help(
+                "Raise the quota, or give the node a Skip or RouteTo fallback with NodeQuotas::max_runs_or."
+            )
+        )
+    )]
+    NodeQuotaExhausted {
+        /// The session being run.
+        session_id: String,
+        /// The node whose quota ran out.
+        node: NodeKind,
+        /// The node's run limit.
+        max_runs: u32,
+    },
+
+    /// A step left the state over its [`StateLimits`](crate::runtimes::StateLimits)
+    /// under [`StateLimitPolicy::Pause`]. The step was committed; the session
+    /// can be compacted or edited and resumed.
+    #[error("session {session_id}: state exceeded its limits at step {step}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::state_limit_exceeded),
+            help(
+                "Compact the session with AppRunner::compact_session, raise the limits, or choose the Compact or RejectOutput policy."
+            )
+        )
+    )]
+    StateLimitExceeded {
+        /// The session being run.
+        session_id: String,
+        /// The step whose barrier exceeded the limits.
+        step: u64,
+        /// One entry per exceeded limit, naming the offending node.
+        breaches: Vec<StateLimitBreach>,
+    },
+
     /// Barrier application failed.
     #[error("app barrier error: {0}")]
     #[cfg_attr(feature = "diagnostics", diagnostic(code(weavegraph::runner::barrier)))]
@@ -291,6 +504,34 @@ pub enum RunnerError {
         diagnostic(code(weavegraph::runner::scheduler))
     )]
     Scheduler(#[from] SchedulerError),
+
+    /// A node output failed validation under
+    /// [`OutputValidationPolicy::Fail`](crate::graphs::OutputValidationPolicy::Fail).
+    #[error(transparent)]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::runner::output_validation))
+    )]
+    OutputValidation(#[from] OutputValidationError),
+
+    /// A node's [`on_register`](crate::node::Node::on_register) hook failed.
+    #[error("node {kind} failed to register: {source}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::runner::node_lifecycle),
+            help(
+                "Check the resources the node acquires in on_register (connections, model handles)."
+            )
+        )
+    )]
+    NodeLifecycle {
+        /// The node whose hook failed.
+        kind: NodeKind,
+        /// The error returned by the hook.
+        #[source]
+        source: crate::node::NodeError,
+    },
 }
 
 /// Runtime metadata useful for audit, replay, and checkpoint labels.
@@ -313,8 +554,18 @@ struct RunnerRuntimeMetadata {
     clock: Option<Arc<dyn Clock>>,
     checkpointer_descriptor: String,
     observer: Option<Arc<dyn RuntimeObserver>>,
+    event_flush_timeout: Duration,
+    dynamic_graph: Option<Arc<DynamicGraph>>,
+    reentry_node: NodeKind,
+    publish_step_metrics: bool,
+    profile_supersteps: bool,
+    cancellation: Option<CancellationToken>,
+    cancel_grace_period: Duration,
 }
 
+/// How long a finishing run waits for event sinks to drain by default.
+pub const DEFAULT_EVENT_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CompletionEventPolicy {
     CloseStream,
@@ -408,6 +659,13 @@ pub struct AppRunnerBuilder {
     start_listener: bool,
     clock: Option<Arc<dyn Clock>>,
     observer: Option<Arc<dyn RuntimeObserver>>,
+    event_flush_timeout: Duration,
+    dynamic_graph: Option<Arc<DynamicGraph>>,
+    reentry_node: NodeKind,
+    publish_step_metrics: bool,
+    profile_supersteps: bool,
+    cancellation: Option<CancellationToken>,
+    cancel_grace_period: Duration,
 }
 
 impl Default for AppRunnerBuilder {
@@ -424,6 +682,12 @@ impl AppRunnerBuilder {
     /// - `autosave`: `true`
     /// - `event_bus`: Uses the app's runtime config when built
     /// - `start_listener`: `true`
+    /// - `event_flush_timeout`: [`DEFAULT_EVENT_FLUSH_TIMEOUT`]
+    /// - `reentry_node`: [`NodeKind::Start`]
+    /// - `publish_step_metrics`: `false`
+    /// - `profile_supersteps`: `false`
+    /// - `cancellation_token`: none
+    /// - `cancel_grace_period`: [`DEFAULT_CANCEL_GRACE_PERIOD`]
     #[must_use]
     pub fn new() -> Self {
         Self {
@@ -435,6 +699,13 @@ impl AppRunnerBuilder {
             start_listener: true,
             clock: None,
             observer: None,
+            event_flush_timeout: DEFAULT_EVENT_FLUSH_TIMEOUT,
+            dynamic_graph: None,
+            reentry_node: NodeKind::Start,
+            publish_step_metrics: false,
+            profile_supersteps: false,
+            cancellation: None,
+            cancel_grace_period: DEFAULT_CANCEL_GRACE_PERIOD,
         }
     }
 
@@ -456,6 +727,20 @@ impl AppRunnerBuilder {
         self
     }
 
+    /// Run the latest revision of a [`DynamicGraph`].
+    ///
+    /// Replaces any app set with [`app()`](Self::app). Before each step the
+    /// runner switches to the newest revision, runs `on_register` for nodes
+    /// added since the previous one, and emits a `runner.graph_update`
+    /// diagnostic, so nodes discovered after startup can be routed to
+    /// mid-session.
+    #[must_use]
+    pub fn dynamic_graph(mut self, graph: Arc<DynamicGraph>) -> Self {
+        self.app = Some(graph.app());
+        self.dynamic_graph = Some(graph);
+        self
+    }
+
     /// Set the checkpointer type for state persistence.
     ///
     /// Defaults to [`CheckpointerType::InMemory`].
@@ -547,6 +832,94 @@ impl AppRunnerBuilder {
         self
     }
 
+    /// How long a finishing run waits for sinks to handle trailing events.
+    ///
+    /// When a run completes or fails, the runner emits its completion marker
+    /// and then waits up to this long in [`EventBus::flush`] before returning.
+    /// A timeout is logged, not reported as an error. Use [`Duration::ZERO`]
+    /// to skip waiting.
+    ///
+    /// Defaults to [`DEFAULT_EVENT_FLUSH_TIMEOUT`].
+    #[must_use]
+    pub fn event_flush_timeout(mut self, timeout: Duration) -> Self {
+        self.event_flush_timeout = timeout;
+        self
+    }
+
+    /// Node that [`AppRunner::resume_with_input`] restarts sessions from.
+    ///
+    /// [`NodeKind::Start`] (the default) re-enters through the graph's start
+    /// edges; a registered node restarts directly at that node.
+    #[must_use]
+    pub fn reentry_node(mut self, node: NodeKind) -> Self {
+        self.reentry_node = node;
+        self
+    }
+
+    /// Publish a [`STEP_METRICS_SCOPE`](crate::event_bus::STEP_METRICS_SCOPE)
+    /// event after every barrier with per-node durations and error tag counts.
+    ///
+    /// These feed the node latency and error rollups of an
+    /// [`AggregatingSink`](crate::event_bus::AggregatingSink). Off by default.
+    #[must_use]
+    pub fn publish_step_metrics(mut self, publish: bool) -> Self {
+        self.publish_step_metrics = publish;
+        self
+    }
+
+    /// Record a [`SuperstepProfile`](crate::runtimes::SuperstepProfile) for every step: node start and end
+    /// times, queue wait, and barrier, reducer, and frontier durations.
+    ///
+    /// Profiles are attached to each [`StepReport`] and kept per session for
+    /// [`AppRunner::session_profile`], which can render them as a Chrome
+    /// trace. Off by default.
+    #[must_use]
+    pub fn profile_supersteps(mut self, enabled: bool) -> Self {
+        self.profile_supersteps = enabled;
+        self
+    }
+
+    /// Cancel running steps when `token` is cancelled.
+    ///
+    /// Every node run gets a child of `token` through
+    /// [`NodeContext::cancellation_token`](crate::node::NodeContext::cancellation_token).
+    /// On cancellation the scheduler publishes a
+    /// [`CANCELLATION_SCOPE`](crate::event_bus::CANCELLATION_SCOPE) event,
+    /// waits up to [`cancel_grace_period`](Self::cancel_grace_period) for
+    /// nodes to return, drops the rest, and the run fails with
+    /// [`SchedulerError::Cancelled`]. The step's outputs are discarded, so the
+    /// session resumes from its last checkpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use weavegraph::node::CancellationToken;
+    /// use weavegraph::runtimes::AppRunner;
+    /// # async fn example(app: weavegraph::app::App) {
+    /// let token = CancellationToken::new();
+    /// let runner = AppRunner::builder()
+    ///     .app(app)
+    ///     .cancellation_token(token.clone())
+    ///     .build()
+    ///     .await;
+    /// // Later, e.g. when the client disconnects:
+    /// token.cancel();
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// How long node runs may keep going after cancellation before they are
+    /// dropped. Defaults to [`DEFAULT_CANCEL_GRACE_PERIOD`].
+    #[must_use]
+    pub fn cancel_grace_period(mut self, grace_period: Duration) -> Self {
+        self.cancel_grace_period = grace_period;
+        self
+    }
+
     /// Build the [`AppRunner`].
     ///
     /// # Panics
@@ -562,9 +935,14 @@ impl AppRunnerBuilder {
     /// Build the [`AppRunner`], returning `None` if no app was provided.
     pub async fn try_build(self) -> Option<AppRunner> {
         let app = self.app?;
-        let event_bus = self
+        let mut event_bus = self
             .event_bus
             .unwrap_or_else(|| app.runtime_config().event_bus.build_event_bus());
+        if event_bus.redaction().is_none()
+            && let Some(policy) = app.runtime_config().redaction()
+        {
+            event_bus = event_bus.with_redaction(policy);
+        }
         let clock = self.clock.or_else(|| app.runtime_config().clock());
         let checkpointer_descriptor = if self.checkpointer_custom.is_some() {
             "custom".to_string()
@@ -575,6 +953,13 @@ impl AppRunnerBuilder {
             clock,
             checkpointer_descriptor,
             observer: self.observer,
+            event_flush_timeout: self.event_flush_timeout,
+            dynamic_graph: self.dynamic_graph,
+            reentry_node: self.reentry_node,
+            publish_step_metrics: self.publish_step_metrics,
+            profile_supersteps: self.profile_supersteps,
+            cancellation: self.cancellation,
+            cancel_grace_period: self.cancel_grace_period,
         };
 
         Some(
@@ -720,6 +1105,8 @@ impl AppRunner {
         if start_listener {
             event_bus.listen_for_events();
         }
+        let scheduler_telemetry = Arc::new(SchedulerTelemetry::default());
+        event_bus.attach_scheduler_telemetry(Arc::clone(&scheduler_telemetry));
         Self {
             app,
             sessions: FxHashMap::default(),
@@ -730,9 +1117,43 @@ impl AppRunner {
             clock: runtime_metadata.clock,
             checkpointer_descriptor: runtime_metadata.checkpointer_descriptor,
             observer: runtime_metadata.observer,
+            step_watchers: FxHashMap::default(),
+            state_values: None,
+            session_metrics: FxHashMap::default(),
+            event_flush_timeout: runtime_metadata.event_flush_timeout,
+            graph_version: runtime_metadata
+                .dynamic_graph
+                .as_ref()
+                .map_or(0, |graph| graph.current().0),
+            dynamic_graph: runtime_metadata.dynamic_graph,
+            scheduler_telemetry,
+            sla_trackers: FxHashMap::default(),
+            reentry_node: runtime_metadata.reentry_node,
+            session_flags: FxHashMap::default(),
+            publish_step_metrics: runtime_metadata.publish_step_metrics,
+            profile_supersteps: runtime_metadata.profile_supersteps,
+            session_profiles: FxHashMap::default(),
+            step_attempts: FxHashMap::default(),
+            cancellation: runtime_metadata.cancellation,
+            cancel_grace_period: runtime_metadata.cancel_grace_period,
+            superstep_hooks: FxHashMap::default(),
+            derived_caches: FxHashMap::default(),
         }
     }
 
+    /// Wait until every sink has handled the events emitted so far.
+    ///
+    /// Runs do this automatically before returning; call it directly after
+    /// emitting events outside a run, or before
+    /// [`finish_iterative_session`](Self::finish_iterative_session).
+    ///
+    /// # Errors
+    ///
+    /// See [`EventBus::flush`].
+    pub async fn flush_events(&self) -> Result<(), FlushError> {
+        self.event_bus.flush(self.event_flush_timeout).await
+    }
+
     /// Subscribe to the underlying event stream.
     ///
     /// Returns a handle that yields events as they are emitted by workflow nodes.
@@ -753,6 +1174,227 @@ impl AppRunner {
         session_id: String,
         initial_state: VersionedState,
     ) -> Result<SessionInit, RunnerError> {
+        self.sync_dynamic_graph().await?;
+        self.init_session(session_id, initial_state, None).await
+    }
+
+    /// Create or resume a session and return a typed handle to drive it.
+    ///
+    /// Behaves like [`create_session`](Self::create_session); the returned
+    /// [`SessionHandle`] reports the [`SessionInit`] through
+    /// [`init`](SessionHandle::init) and replaces the string-keyed calls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::runtimes::{AppRunner, SessionInit};
+    /// use weavegraph::state::VersionedState;
+    /// # async fn example(app: weavegraph::app::App) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut runner = AppRunner::builder().app(app).build().await;
+    /// let mut session = runner
+    ///     .open_session("s1", VersionedState::new_with_user_message("hi"))
+    ///     .await?;
+    /// assert_eq!(session.init(), Some(&SessionInit::Fresh));
+    /// let final_state = session.run().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn open_session(
+        &mut self,
+        session_id: impl Into<SessionId>,
+        initial_state: VersionedState,
+    ) -> Result<SessionHandle<'_>, RunnerError> {
+        let id = session_id.into();
+        let init = self
+            .create_session(id.as_str().to_string(), initial_state)
+            .await?;
+        Ok(SessionHandle::new(self, id, Some(init)))
+    }
+
+    /// Handle for a session this runner has already created or resumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the runner has no session
+    /// with this id.
+    pub fn session(&mut self, session_id: &SessionId) -> Result<SessionHandle<'_>, RunnerError> {
+        if !self.sessions.contains_key(session_id.as_str()) {
+            return Err(RunnerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        Ok(SessionHandle::new(self, session_id.clone(), None))
+    }
+
+    /// Save a session's current state unless autosave already has.
+    pub(crate) async fn checkpoint_session(&self, session_id: &str) -> Result<(), RunnerError> {
+        let session_state =
+            self.sessions
+                .get(session_id)
+                .ok_or_else(|| RunnerError::SessionNotFound {
+                    session_id: session_id.to_string(),
+                })?;
+        if self.autosave {
+            return Ok(());
+        }
+        if let Some(checkpointer) = &self.checkpointer {
+            checkpointer
+                .save(self.redact_checkpoint(Checkpoint::from_session(session_id, session_state)))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Initialize a new session that starts from a named entry point.
+    ///
+    /// Entry points are declared with
+    /// [`GraphBuilder::add_entry`](crate::graphs::GraphBuilder::add_entry). The
+    /// entry's nodes become the first frontier instead of the edges leaving
+    /// [`NodeKind::Start`]. When a checkpoint exists for `session_id`, the
+    /// session resumes from its saved frontier and the entry point only needs
+    /// to exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::UnknownEntryPoint`] if the graph declares no entry
+    /// point named `entry`.
+    #[instrument(skip(self, initial_state, session_id), err)]
+    pub async fn create_session_with_entry(
+        &mut self,
+        session_id: String,
+        initial_state: VersionedState,
+        entry: &str,
+    ) -> Result<SessionInit, RunnerError> {
+        self.sync_dynamic_graph().await?;
+        let frontier = self.app.entry_points().get(entry).cloned().ok_or_else(|| {
+            RunnerError::UnknownEntryPoint {
+                name: entry.to_string(),
+            }
+        })?;
+        self.init_session(session_id, initial_state, Some(frontier))
+            .await
+    }
+
+    /// Initialize a new session whose first frontier is `frontier` instead of
+    /// the edges leaving [`NodeKind::Start`].
+    ///
+    /// Useful for exercising a single branch of a graph in tests or for
+    /// running a plan computed outside the graph. Nodes run in the given order
+    /// and routing continues from them as usual. When a checkpoint exists for
+    /// `session_id`, the session resumes from its saved frontier instead; the
+    /// override is still validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::NoStartNodes`] if `frontier` is empty and
+    /// [`RunnerError::InvalidFrontierOverride`] if it contains a virtual
+    /// Start/End node or a node not registered on the graph.
+    #[instrument(skip(self, initial_state, session_id), err)]
+    pub async fn create_session_with_frontier(
+        &mut self,
+        session_id: String,
+        initial_state: VersionedState,
+        frontier: Vec<NodeKind>,
+    ) -> Result<SessionInit, RunnerError> {
+        self.sync_dynamic_graph().await?;
+        self.validate_frontier_override(&frontier)?;
+        self.init_session(session_id, initial_state, Some(frontier))
+            .await
+    }
+
+    /// Install a session rebuilt outside any [`Checkpointer`] as live state.
+    ///
+    /// Use this to hydrate sessions from your own store (an event-sourced log,
+    /// a document database, ...) and still execute them with this runner. Build
+    /// the [`SessionState`] directly or from a [`Checkpoint`] with
+    /// [`restore_session_state`], whose documentation describes the format.
+    /// An in-memory session with the same id is replaced.
+    ///
+    /// The state is upgraded by the configured state migrations and, when the
+    /// runner has a checkpointer, saved to it, so later resumes and
+    /// checkpointed history continue from the adopted step. Run the session
+    /// with [`run_step`](Self::run_step) or
+    /// [`run_until_complete`](Self::run_until_complete); an empty frontier, or
+    /// one holding only [`NodeKind::End`], is already complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::InvalidFrontierOverride`] if the frontier holds
+    /// [`NodeKind::Start`] or a node not registered on the graph,
+    /// [`RunnerError::StateMigration`] if the state cannot be migrated, and
+    /// [`RunnerError::Checkpointer`] if saving it fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::runtimes::{AppRunner, Checkpoint, restore_session_state};
+    /// # async fn example(app: weavegraph::app::App, stored: Checkpoint) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut runner = AppRunner::builder().app(app).build().await;
+    /// runner
+    ///     .adopt_session("s1".to_string(), restore_session_state(&stored))
+    ///     .await?;
+    /// let final_state = runner.run_until_complete("s1").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, session_id, session_state), err)]
+    pub async fn adopt_session(
+        &mut self,
+        session_id: String,
+        mut session_state: SessionState,
+    ) -> Result<(), RunnerError> {
+        self.sync_dynamic_graph().await?;
+        if let Some(node) = session_state.frontier.iter().find(|node| {
+            **node != NodeKind::End && (!node.is_custom() || !self.app.nodes().contains_key(node))
+        }) {
+            return Err(RunnerError::InvalidFrontierOverride { node: node.clone() });
+        }
+        self.app
+            .register_nodes(self.event_bus.get_emitter())
+            .await?;
+
+        let mut stored = Checkpoint::from_session(&session_id, &session_state);
+        self.migrate_checkpoint(&session_id, &mut stored)?;
+        session_state.state = stored.state;
+        if let Some(cp) = &self.checkpointer {
+            cp.save(self.redact_checkpoint(Checkpoint::from_session(&session_id, &session_state)))
+                .await
+                .map_err(RunnerError::Checkpointer)?;
+        }
+        self.sessions.insert(session_id.clone(), session_state);
+        self.session_metrics.remove(&session_id);
+        self.sla_trackers.remove(&session_id);
+        self.session_profiles.remove(&session_id);
+        self.step_attempts.remove(&session_id);
+        self.derived_caches.remove(&session_id);
+        self.resolve_feature_flags(&session_id).await;
+        Ok(())
+    }
+
+    /// Check that every node of a frontier override can start a session.
+    fn validate_frontier_override(&self, frontier: &[NodeKind]) -> Result<(), RunnerError> {
+        if frontier.is_empty() {
+            return Err(RunnerError::NoStartNodes);
+        }
+        match frontier
+            .iter()
+            .find(|node| !node.is_custom() || !self.app.nodes().contains_key(node))
+        {
+            Some(node) => Err(RunnerError::InvalidFrontierOverride { node: node.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    async fn init_session(
+        &mut self,
+        session_id: String,
+        initial_state: VersionedState,
+        entry_frontier: Option<Vec<NodeKind>>,
+    ) -> Result<SessionInit, RunnerError> {
+        self.app
+            .register_nodes(self.event_bus.get_emitter())
+            .await?;
+
         // If checkpointer present and session exists, load instead of creating anew
         let restored_checkpoint = if let Some(cp) = &self.checkpointer {
             cp.load_latest(&session_id)
@@ -762,42 +1404,36 @@ impl AppRunner {
             None
         };
 
-        if let Some(stored) = restored_checkpoint {
-            let restored = restore_session_state(&stored);
-            let restored_step = stored.step;
-            self.sessions.insert(session_id.clone(), restored);
-            if let Some(obs) = &self.observer {
-                let backend = self.checkpointer_descriptor.as_str();
-                let sid = session_id.as_str();
-                call_observer_hook(
-                    || {
-                        obs.on_checkpoint_load(&CheckpointLoadMeta {
-                            session_id: sid,
-                            backend,
-                            step: restored_step,
-                        })
-                    },
-                    "on_checkpoint_load",
-                );
-            }
+        if let Some(mut stored) = restored_checkpoint {
+            self.migrate_checkpoint(&session_id, &mut stored)?;
+            self.adopt_checkpoint(&session_id, &stored);
+            self.resolve_feature_flags(&session_id).await;
             return Ok(SessionInit::Resumed {
                 checkpoint_step: stored.step,
             });
         }
 
-        let frontier = self
-            .app
-            .edges()
-            .get(&NodeKind::Start)
-            .cloned()
-            .unwrap_or_default();
+        let frontier = entry_frontier.unwrap_or_else(|| {
+            self.app
+                .edges()
+                .get(&NodeKind::Start)
+                .cloned()
+                .unwrap_or_default()
+        });
         if frontier.is_empty() {
             return Err(RunnerError::NoStartNodes);
         }
-        let default_limit = std::thread::available_parallelism()
+        let mut default_limit = std::thread::available_parallelism()
             .map(|n| n.get())
             .unwrap_or(1);
+        if let Some(adaptive) = self.app.runtime_config().adaptive_concurrency() {
+            default_limit = default_limit.clamp(adaptive.min(), adaptive.max());
+        }
         let scheduler = Scheduler::new(default_limit);
+        let mut initial_state = initial_state;
+        if let Some(migrations) = self.app.runtime_config().state_migrations() {
+            migrations.stamp(&mut initial_state);
+        }
         let session_state = SessionState {
             state: initial_state,
             step: 0,
@@ -807,11 +1443,17 @@ impl AppRunner {
         };
         self.sessions
             .insert(session_id.clone(), session_state.clone());
+        self.session_metrics.remove(&session_id);
+        self.sla_trackers.remove(&session_id);
+        self.session_profiles.remove(&session_id);
+        self.step_attempts.remove(&session_id);
+        self.derived_caches.remove(&session_id);
         if let Some(cp) = &self.checkpointer {
             let _ = cp
-                .save(Checkpoint::from_session(&session_id, &session_state))
+                .save(self.redact_checkpoint(Checkpoint::from_session(&session_id, &session_state)))
                 .await;
         }
+        self.resolve_feature_flags(&session_id).await;
         Ok(SessionInit::Fresh)
     }
 
@@ -843,9 +1485,10 @@ impl AppRunner {
     ///
     /// The existing session state is updated through the same deterministic barrier
     /// path used for node outputs. The frontier is then reset to `entry_node` and the
-    /// scheduler's version-gating state is cleared so the entry path executes for this
-    /// logical invocation even when two consecutive input patches serialize to the
-    /// same state.
+    /// scheduler's version-gating and join bookkeeping are cleared so the entry path
+    /// executes for this logical invocation even when two consecutive input patches
+    /// serialize to the same state. Node quota run counts are kept, so
+    /// [`NodeQuotas`](crate::graphs::NodeQuotas) limits apply across invocations.
     ///
     /// Use [`create_iterative_session`](Self::create_iterative_session) before the
     /// first call, including after process restart, so the latest checkpoint is loaded
@@ -862,22 +1505,456 @@ impl AppRunner {
         self.set_iterative_frontier(session_id, frontier)?;
         self.run_until_complete_with_policy(session_id, CompletionEventPolicy::KeepStreamOpen)
             .await
+            .map(|(state, _)| state)
     }
 
-    /// Emit the terminal stream marker for a completed iterative session.
+    /// Append a user message to a completed or paused session and run it again.
     ///
-    /// `invoke_next` keeps long-lived event subscriptions open between logical
-    /// inputs. Call this after the final input when a subscriber should receive
-    /// [`STREAM_END_SCOPE`](crate::event_bus::STREAM_END_SCOPE) and the stream
-    /// should close cleanly.
-    pub fn finish_iterative_session(&mut self, session_id: &str) -> Result<(), RunnerError> {
-        let (_, _, final_step) = self.finalize_state_snapshot(session_id)?;
-        self.emit_completion_event(
-            session_id,
-            StreamEndReason::Completed { step: final_step },
-            CompletionEventPolicy::CloseStream,
-        );
-        Ok(())
+    /// The message is applied through the barrier like node output, the
+    /// frontier is reset to the runner's
+    /// [`reentry_node`](AppRunnerBuilder::reentry_node), and the session runs
+    /// to completion. As with [`invoke_next`](Self::invoke_next), the event
+    /// stream stays open between turns and version gating is reset so the
+    /// re-entry path always executes. A session that is not loaded in this
+    /// runner is resumed from its latest checkpoint first, so a multi-turn
+    /// chat survives process restarts.
+    ///
+    /// # Errors
+    ///
+    /// * `SessionNotFound` - The session is neither loaded nor checkpointed
+    /// * `NoStartNodes` / `InvalidIterativeEntry` - The re-entry node cannot start a run
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::channels::Channel;
+    /// use weavegraph::message::Message;
+    /// use weavegraph::runtimes::AppRunner;
+    /// # async fn example(mut runner: AppRunner) -> Result<(), Box<dyn std::error::Error>> {
+    /// let state = runner
+    ///     .resume_with_input("chat-1", Message::user("And in French?"))
+    ///     .await?;
+    /// println!("{} messages so far", state.messages.snapshot().len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, message), err)]
+    pub async fn resume_with_input(
+        &mut self,
+        session_id: &str,
+        message: Message,
+    ) -> Result<VersionedState, RunnerError> {
+        self.sync_dynamic_graph().await?;
+        let frontier = self.frontier_for_iterative_entry(&self.reentry_node)?;
+        if !self.sessions.contains_key(session_id) {
+            let stored = match &self.checkpointer {
+                Some(cp) => cp
+                    .load_latest(session_id)
+                    .await
+                    .map_err(RunnerError::Checkpointer)?,
+                None => None,
+            };
+            let mut stored = stored.ok_or_else(|| RunnerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            })?;
+            self.app
+                .register_nodes(self.event_bus.get_emitter())
+                .await?;
+            self.migrate_checkpoint(session_id, &mut stored)?;
+            self.adopt_checkpoint(session_id, &stored);
+            self.resolve_feature_flags(session_id).await;
+        }
+        self.apply_iterative_input(session_id, NodePartial::new().with_messages(vec![message]))
+            .await?;
+        self.set_iterative_frontier(session_id, frontier)?;
+        self.run_until_complete_with_policy(session_id, CompletionEventPolicy::KeepStreamOpen)
+            .await
+            .map(|(state, _)| state)
+    }
+
+    /// Emit the terminal stream marker for a completed iterative session.
+    ///
+    /// `invoke_next` keeps long-lived event subscriptions open between logical
+    /// inputs. Call this after the final input when a subscriber should receive
+    /// [`STREAM_END_SCOPE`](crate::event_bus::STREAM_END_SCOPE) and the stream
+    /// should close cleanly.
+    pub fn finish_iterative_session(&mut self, session_id: &str) -> Result<(), RunnerError> {
+        let (_, _, final_step) = self.finalize_state_snapshot(session_id)?;
+        self.finalize_event_stream(session_id, StreamEndReason::Completed { step: final_step });
+        Ok(())
+    }
+
+    /// Edit a session's state between steps (for example while paused for approval).
+    ///
+    /// `edit` receives a copy of the session state. Channels whose contents the
+    /// closure changes get a version bump (unless the closure already moved the
+    /// version), so version-gated nodes observe the edit on the next step.
+    /// Each effective edit:
+    ///
+    /// - appends an audit [`ErrorEvent`] tagged `manual_edit` to the errors channel,
+    /// - emits a `runner.state_edit` diagnostic on the event bus,
+    /// - saves a checkpoint for the current step when autosave is enabled.
+    ///
+    /// Returns the names of the channels the closure changed; an edit that
+    /// changes nothing records nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the session does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use serde_json::json;
+    /// use weavegraph::channels::Channel;
+    /// use weavegraph::runtimes::{AppRunner, StepOptions, StepResult};
+    /// use weavegraph::types::NodeKind;
+    /// # async fn example(mut runner: AppRunner) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = StepOptions {
+    ///     interrupt_before: vec![NodeKind::Custom("publish".into())],
+    ///     ..StepOptions::default()
+    /// };
+    /// if let StepResult::Paused(_) = runner.run_step("s1", options).await? {
+    ///     runner
+    ///         .update_session_state("s1", |state| {
+    ///             state.extra.get_mut().insert("approved".into(), json!(true));
+    ///         })
+    ///         .await?;
+    /// }
+    /// runner.run_until_complete("s1").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_session_state<F>(
+        &mut self,
+        session_id: &str,
+        edit: F,
+    ) -> Result<Vec<&'static str>, RunnerError>
+    where
+        F: FnOnce(&mut VersionedState),
+    {
+        let session_state =
+            self.sessions
+                .get_mut(session_id)
+                .ok_or_else(|| RunnerError::SessionNotFound {
+                    session_id: session_id.to_string(),
+                })?;
+        let before = session_state.state.clone();
+        let mut edited = before.clone();
+        edit(&mut edited);
+
+        fn bump_if_changed<T: PartialEq, C: Channel<T>>(
+            before: &C,
+            after: &mut C,
+            name: &'static str,
+            changed: &mut Vec<&'static str>,
+        ) -> Result<(), ChannelVersionOverflow> {
+            if before.snapshot() != after.snapshot() {
+                if before.version() == after.version() {
+                    after.bump_version()?;
+                }
+                changed.push(name);
+            }
+            Ok(())
+        }
+        let overflow = |err: ChannelVersionOverflow| RunnerError::AppBarrier(Box::new(err));
+        let mut changed = Vec::new();
+        bump_if_changed(
+            &before.messages,
+            &mut edited.messages,
+            "messages",
+            &mut changed,
+        )
+        .map_err(overflow)?;
+        bump_if_changed(&before.extra, &mut edited.extra, "extra", &mut changed)
+            .map_err(overflow)?;
+        bump_if_changed(&before.errors, &mut edited.errors, "errors", &mut changed)
+            .map_err(overflow)?;
+        bump_if_changed(&before.blobs, &mut edited.blobs, "blobs", &mut changed)
+            .map_err(overflow)?;
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        let step = session_state.step;
+        let audit = ErrorEvent {
+            when: chrono::Utc::now(),
+            scope: ErrorScope::Runner {
+                session: session_id.to_string(),
+                step,
+            },
+            error: WeaveError::msg("session state edited manually"),
+            tags: vec!["runner".into(), "manual_edit".into()],
+            context: serde_json::json!({ "updated_channels": changed }),
+            severity: ErrorSeverity::Info,
+        };
+        edited.errors.get_mut().push(audit);
+        edited.errors.bump_version().map_err(overflow)?;
+        session_state.state = edited;
+
+        let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+            "runner.state_edit",
+            format!(
+                "session {session_id} state edited at step {step}: {}",
+                changed.join(", ")
+            ),
+        ));
+        self.maybe_checkpoint(session_id, step, None).await;
+        Ok(changed)
+    }
+
+    /// Archive old messages and error events of a session and drop them from
+    /// its live state.
+    ///
+    /// Everything but the newest items allowed by `policy` is written to the
+    /// checkpointer as a [`ChannelArchive`]; only after the archive is stored
+    /// are the channels trimmed, a [`CompactionMarker`] recorded in `extra`
+    /// under [`COMPACTION_MARKER_KEY`], and a checkpoint saved (when autosave
+    /// is on). Archived items pass through the configured redaction policy.
+    /// When nothing is old enough no archive is written and the state is left
+    /// untouched.
+    ///
+    /// # Errors
+    ///
+    /// * `SessionNotFound` - No such session
+    /// * `Checkpointer` - No checkpointer is configured, or saving the archive
+    ///   failed (the session is left unchanged)
+    /// * `AppBarrier` - A channel version would overflow
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::runtimes::{AppRunner, CompactionPolicy};
+    /// # async fn example(mut runner: AppRunner) -> Result<(), Box<dyn std::error::Error>> {
+    /// let report = runner
+    ///     .compact_session("s1", CompactionPolicy::new().keep_last_messages(10))
+    ///     .await?;
+    /// println!("archived {} messages", report.archived_messages);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn compact_session(
+        &mut self,
+        session_id: &str,
+        policy: CompactionPolicy,
+    ) -> Result<CompactionReport, RunnerError> {
+        let session_state =
+            self.sessions
+                .get(session_id)
+                .ok_or_else(|| RunnerError::SessionNotFound {
+                    session_id: session_id.to_string(),
+                })?;
+        let step = session_state.step;
+        let mut edited = session_state.state.clone();
+        let marker = CompactionMarker::from_snapshot(&edited.snapshot()).unwrap_or_default();
+
+        let message_cut = edited
+            .messages
+            .len()
+            .saturating_sub(policy.messages_to_keep());
+        let error_cut = edited.errors.len().saturating_sub(policy.errors_to_keep());
+        if message_cut == 0 && error_cut == 0 {
+            return Ok(CompactionReport {
+                archive_sequence: None,
+                archived_messages: 0,
+                archived_errors: 0,
+                marker,
+            });
+        }
+        let checkpointer = self.checkpointer.clone().ok_or(RunnerError::Checkpointer(
+            CheckpointerError::Unsupported {
+                operation: "save_archive",
+            },
+        ))?;
+
+        // Redact a copy so the archive never holds more than checkpoints would.
+        let mut archived =
+            VersionedState::new_with_messages(edited.messages.get_mut()[..message_cut].to_vec());
+        *archived.errors.get_mut() = edited.errors.get_mut()[..error_cut].to_vec();
+        if let Some(redaction) = &self.app.runtime_config().redaction {
+            redaction.redact_state(&mut archived);
+        }
+        let sequence = marker.archives;
+        checkpointer
+            .save_archive(ChannelArchive {
+                session_id: session_id.to_string(),
+                sequence,
+                step,
+                messages_offset: marker.archived_messages,
+                messages: std::mem::take(archived.messages.get_mut()),
+                errors_offset: marker.archived_errors,
+                errors: std::mem::take(archived.errors.get_mut()),
+                created_at: chrono::Utc::now(),
+            })
+            .await?;
+
+        let overflow = |err: ChannelVersionOverflow| RunnerError::AppBarrier(Box::new(err));
+        let marker = CompactionMarker {
+            archives: sequence + 1,
+            archived_messages: marker.archived_messages + message_cut as u64,
+            archived_errors: marker.archived_errors + error_cut as u64,
+            last_step: step,
+        };
+        if message_cut > 0 {
+            edited.messages.get_mut().drain(..message_cut);
+            edited.messages.bump_version().map_err(overflow)?;
+        }
+        if error_cut > 0 {
+            edited.errors.get_mut().drain(..error_cut);
+            edited.errors.bump_version().map_err(overflow)?;
+        }
+        edited.extra.get_mut().insert(
+            COMPACTION_MARKER_KEY.to_string(),
+            serde_json::to_value(&marker).expect("compaction marker serializes"),
+        );
+        edited.extra.bump_version().map_err(overflow)?;
+        if let Some(session_state) = self.sessions.get_mut(session_id) {
+            session_state.state = edited;
+        }
+
+        let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+            "runner.compaction",
+            format!(
+                "session {session_id} compacted at step {step}: archive {sequence}, \
+                 {message_cut} messages, {error_cut} errors"
+            ),
+        ));
+        self.maybe_checkpoint(session_id, step, None).await;
+        Ok(CompactionReport {
+            archive_sequence: Some(sequence),
+            archived_messages: message_cut,
+            archived_errors: error_cut,
+            marker,
+        })
+    }
+
+    /// Ask the configured provider for the session's feature flags.
+    ///
+    /// Provider errors are reported as a `runner.feature_flags` diagnostic and
+    /// leave the session without flags.
+    async fn resolve_feature_flags(&mut self, session_id: &str) {
+        self.session_flags.remove(session_id);
+        let Some(provider) = self.app.runtime_config().feature_flags() else {
+            return;
+        };
+        match provider.resolve(session_id).await {
+            Ok(flags) => {
+                self.session_flags
+                    .insert(session_id.to_string(), Arc::new(flags));
+            }
+            Err(err) => {
+                tracing::warn!(session_id, error = %err, "feature flag resolution failed");
+                let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+                    "runner.feature_flags",
+                    format!("session {session_id}: {err}"),
+                ));
+            }
+        }
+    }
+
+    /// Upgrade a loaded checkpoint with the configured state migrations.
+    ///
+    /// Each applied step is reported as a `runner.state_migration` diagnostic.
+    fn migrate_checkpoint(
+        &self,
+        session_id: &str,
+        stored: &mut Checkpoint,
+    ) -> Result<(), RunnerError> {
+        let Some(migrations) = self.app.runtime_config().state_migrations() else {
+            return Ok(());
+        };
+        let report = migrations.migrate_checkpoint(stored).map_err(|source| {
+            RunnerError::StateMigration {
+                session_id: session_id.to_string(),
+                source,
+            }
+        })?;
+        let emitter = self.event_bus.get_emitter();
+        for step in &report.applied {
+            tracing::info!(
+                session_id,
+                from = step.from,
+                to = step.from + 1,
+                description = %step.description,
+                "state migrated"
+            );
+            let _ = emitter.emit(Event::diagnostic(
+                "runner.state_migration",
+                format!(
+                    "session {session_id}: v{} -> v{}: {}",
+                    step.from,
+                    step.from + 1,
+                    step.description
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apply quota fallbacks to frontier entries whose run quota is used up.
+    ///
+    /// Each exhausted quota is reported as a `runner.node_quota` diagnostic.
+    fn enforce_node_quotas(
+        &self,
+        session_id: &str,
+        session_state: &mut SessionState,
+        step: u64,
+    ) -> Result<(), RunnerError> {
+        let quotas = self.app.node_quotas();
+        if quotas.is_empty() {
+            return Ok(());
+        }
+        let emitter = self.event_bus.get_emitter();
+        let hits = quotas
+            .enforce(&mut session_state.frontier, &session_state.scheduler_state)
+            .map_err(|hit| {
+                let _ = emitter.emit(Event::diagnostic(
+                    "runner.node_quota",
+                    format!(
+                        "session {session_id} step {step}: {} exhausted {} run(s); failing",
+                        hit.node, hit.max_runs
+                    ),
+                ));
+                RunnerError::NodeQuotaExhausted {
+                    session_id: session_id.to_string(),
+                    node: hit.node,
+                    max_runs: hit.max_runs,
+                }
+            })?;
+        for hit in hits {
+            let action = match &hit.fallback {
+                QuotaFallback::RouteTo(target) => format!("routed to {target}"),
+                _ => "skipped".to_string(),
+            };
+            tracing::warn!(session_id, step, node = %hit.node, max_runs = hit.max_runs, %action, "node quota exhausted");
+            let _ = emitter.emit(Event::diagnostic(
+                "runner.node_quota",
+                format!(
+                    "session {session_id} step {step}: {} exhausted {} run(s); {action}",
+                    hit.node, hit.max_runs
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Load a stored checkpoint as the session's live state.
+    fn adopt_checkpoint(&mut self, session_id: &str, stored: &Checkpoint) {
+        self.sessions
+            .insert(session_id.to_string(), restore_session_state(stored));
+        if let Some(obs) = &self.observer {
+            let backend = self.checkpointer_descriptor.as_str();
+            call_observer_hook(
+                || {
+                    obs.on_checkpoint_load(&CheckpointLoadMeta {
+                        session_id,
+                        backend,
+                        step: stored.step,
+                    })
+                },
+                "on_checkpoint_load",
+            );
+        }
     }
 
     fn set_iterative_frontier(
@@ -893,7 +1970,12 @@ impl AppRunner {
                 })?;
 
         session_state.frontier = frontier;
-        session_state.scheduler_state = SchedulerState::default();
+        // Quota run counts are per session, so they survive the reset.
+        let run_counts = std::mem::take(&mut session_state.scheduler_state.run_counts);
+        session_state.scheduler_state = SchedulerState {
+            run_counts,
+            ..SchedulerState::default()
+        };
         Ok(())
     }
 
@@ -959,6 +2041,37 @@ impl AppRunner {
         Ok(())
     }
 
+    /// Adopt the latest [`DynamicGraph`] revision, if one was published since
+    /// the last step.
+    async fn sync_dynamic_graph(&mut self) -> Result<(), RunnerError> {
+        let Some(graph) = &self.dynamic_graph else {
+            return Ok(());
+        };
+        let (version, app) = graph.current();
+        if version == self.graph_version {
+            return Ok(());
+        }
+        let added = graph.nodes_added_since(self.graph_version);
+        app.register_added_nodes(&added, self.event_bus.get_emitter())
+            .await?;
+        let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+            "runner.graph_update",
+            format!(
+                "graph revision {} -> {version} ({}), added nodes: {}",
+                self.graph_version,
+                app.graph_definition_hash(),
+                added
+                    .iter()
+                    .map(NodeKind::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+        self.app = app;
+        self.graph_version = version;
+        Ok(())
+    }
+
     /// Execute one superstep for the given session
     #[instrument(skip(self, options), err)]
     pub async fn run_step(
@@ -966,6 +2079,7 @@ impl AppRunner {
         session_id: &str,
         options: StepOptions,
     ) -> Result<StepResult, RunnerError> {
+        self.sync_dynamic_graph().await?;
         // Phase 3.1 (Clone Reduction - A): capture minimal snapshots without cloning full session
         let (current_step, current_frontier, current_versions) = {
             let current_session_state =
@@ -993,8 +2107,12 @@ impl AppRunner {
                 skipped_nodes: current_frontier.clone(),
                 barrier_outcome: BarrierOutcome::default(),
                 next_frontier: vec![],
+                frontier_edges: vec![],
                 state_versions: current_versions,
                 completed: true,
+                node_metrics: Vec::new(),
+                profile: None,
+                state_limit_breaches: Vec::new(),
             }));
         }
 
@@ -1026,84 +2144,241 @@ impl AppRunner {
                     session_id: session_id.to_string(),
                 })?;
 
-        // Execute one superstep; on error, emit an ErrorEvent and rethrow
-        let step_report = match self.run_one_superstep(session_id, &mut session_state).await {
-            Ok(rep) => rep,
-            Err(e) => {
-                // Build error event
-                let event = match &e {
-                    RunnerError::Scheduler(source) => match source {
-                        crate::schedulers::SchedulerError::NodeNotFound { kind, step } => {
-                            ErrorEvent {
-                                when: chrono::Utc::now(),
-                                scope: ErrorScope::Scheduler { step: *step },
-                                error: WeaveError::msg(format!(
-                                    "node {:?} not found in registry",
-                                    kind
-                                )),
-                                tags: vec!["scheduler".into(), "node_not_found".into()],
-                                context: serde_json::json!({
-                                    "kind": kind.encode()
-                                }),
+        // SLA run time counts from the first step this runner executes.
+        if !self.app.sla().is_empty() {
+            self.sla_trackers.entry(session_id.to_string()).or_default();
+        }
+
+        // Under a transactional checkpoint policy the committed state is kept
+        // so a step whose checkpoint cannot be saved is rolled back.
+        let policy = self.app.runtime_config().checkpoint_failure_policy();
+        let committed = (self.autosave && self.checkpointer.is_some() && policy.is_transactional())
+            .then(|| session_state.clone());
+        let max_reruns = match policy {
+            CheckpointFailurePolicy::RollbackAndRerun { max_reruns } => max_reruns,
+            _ => 0,
+        };
+        let mut reruns = 0;
+        let mut save_attempts = 0;
+        let step_report = loop {
+            let attempt = self.next_attempt(session_id, session_state.step + 1);
+            if !self.app.derived_channels().is_empty() {
+                self.derived_caches
+                    .entry(session_id.to_string())
+                    .or_default()
+                    .begin_step(session_state.step + 1);
+            }
+            // Execute one superstep; on error, emit an ErrorEvent and rethrow
+            let mut pre_partial_state = None;
+            let step_report = match self
+                .run_one_superstep(
+                    session_id,
+                    &mut session_state,
+                    attempt,
+                    &mut pre_partial_state,
+                )
+                .await
+            {
+                Ok(rep) => rep,
+                Err(e) => {
+                    // Partials yielded during the failed step are not kept.
+                    if let Some(state) = pre_partial_state {
+                        session_state.state = state;
+                    }
+                    self.retract_attempt(session_id, session_state.step, attempt, "step_error");
+                    // Build error event
+                    let event = match &e {
+                        RunnerError::Scheduler(source) => match source {
+                            crate::schedulers::SchedulerError::NodeNotFound { kind, step } => {
+                                ErrorEvent {
+                                    when: chrono::Utc::now(),
+                                    scope: ErrorScope::Scheduler { step: *step },
+                                    error: WeaveError::msg(format!(
+                                        "node {:?} not found in registry",
+                                        kind
+                                    )),
+                                    tags: vec!["scheduler".into(), "node_not_found".into()],
+                                    context: serde_json::json!({
+                                        "kind": kind.encode()
+                                    }),
+                                    severity: ErrorSeverity::Fatal,
+                                }
                             }
-                        }
-                        crate::schedulers::SchedulerError::NodeRun { kind, step, source } => {
-                            ErrorEvent {
+                            crate::schedulers::SchedulerError::NodeRun { kind, step, source } => {
+                                ErrorEvent {
+                                    when: chrono::Utc::now(),
+                                    scope: ErrorScope::Node {
+                                        kind: kind.encode().to_string(),
+                                        step: *step,
+                                    },
+                                    error: WeaveError::msg(format!("{}", source)),
+                                    tags: vec!["node".into()],
+                                    context: serde_json::json!({}),
+                                    severity: ErrorSeverity::Fatal,
+                                }
+                            }
+                            crate::schedulers::SchedulerError::NodePanic {
+                                kind,
+                                step,
+                                message,
+                                trace,
+                            } => ErrorEvent {
                                 when: chrono::Utc::now(),
                                 scope: ErrorScope::Node {
                                     kind: kind.encode().to_string(),
                                     step: *step,
                                 },
-                                error: WeaveError::msg(format!("{}", source)),
-                                tags: vec!["node".into()],
-                                context: serde_json::json!({}),
+                                error: WeaveError::msg(format!("node panicked: {message}")),
+                                tags: vec!["node".into(), "panic".into()],
+                                context: serde_json::json!({ "backtrace": trace }),
+                                severity: ErrorSeverity::Fatal,
+                            },
+                            crate::schedulers::SchedulerError::Cancelled { step, aborted } => {
+                                ErrorEvent {
+                                    when: chrono::Utc::now(),
+                                    scope: ErrorScope::Scheduler { step: *step },
+                                    error: WeaveError::msg(format!("{}", source)),
+                                    tags: vec!["scheduler".into(), "cancelled".into()],
+                                    context: serde_json::json!({
+                                        "aborted": aborted.iter().map(NodeKind::encode).collect::<Vec<_>>()
+                                    }),
+                                    severity: ErrorSeverity::Error,
+                                }
                             }
-                        }
-                        crate::schedulers::SchedulerError::Join(_) => ErrorEvent {
+                            crate::schedulers::SchedulerError::Join(_) => ErrorEvent {
+                                when: chrono::Utc::now(),
+                                scope: ErrorScope::Scheduler {
+                                    step: session_state.step,
+                                },
+                                error: WeaveError::msg(format!("{}", e)),
+                                tags: vec!["scheduler".into()],
+                                context: serde_json::json!({}),
+                                severity: ErrorSeverity::Fatal,
+                            },
+                        },
+                        _ => ErrorEvent {
                             when: chrono::Utc::now(),
-                            scope: ErrorScope::Scheduler {
+                            scope: ErrorScope::Runner {
+                                session: session_id.to_string(),
                                 step: session_state.step,
                             },
                             error: WeaveError::msg(format!("{}", e)),
-                            tags: vec!["scheduler".into()],
-                            context: serde_json::json!({}),
-                        },
-                    },
-                    _ => ErrorEvent {
-                        when: chrono::Utc::now(),
-                        scope: ErrorScope::Runner {
-                            session: session_id.to_string(),
-                            step: session_state.step,
+                            tags: vec!["runner".into()],
+                            context: serde_json::json!({
+                                "frontier": session_state.frontier.iter().map(|k| k.encode()).collect::<Vec<_>>()
+                            }),
+                            severity: ErrorSeverity::Fatal,
                         },
-                        error: WeaveError::msg(format!("{}", e)),
-                        tags: vec!["runner".into()],
-                        context: serde_json::json!({
-                            "frontier": session_state.frontier.iter().map(|k| k.encode()).collect::<Vec<_>>()
-                        }),
-                    },
-                };
-                // Inject via barrier mechanics by applying a synthetic NodePartial with errors field
-                let mut update_state = session_state.state.clone();
-                let partial = NodePartial::new().with_errors(vec![event]);
-                // Apply directly using reducer registry through App
-                let _ = self
-                    .app
-                    .apply_barrier(&mut update_state, &[], vec![partial])
-                    .await;
-                session_state.state = update_state;
-                // Save back to sessions map so callers can inspect accumulated errors
-                self.sessions.insert(session_id.to_string(), session_state);
-                // Re-persist if autosave
-                if self.autosave
-                    && let Some(cp) = &self.checkpointer
-                    && let Some(s) = self.sessions.get(session_id)
-                {
-                    let _ = cp.save(Checkpoint::from_session(session_id, s)).await;
+                    };
+                    // Inject via barrier mechanics by applying a synthetic NodePartial with errors field
+                    let mut update_state = session_state.state.clone();
+                    let partial = NodePartial::new().with_errors(vec![event]);
+                    // Apply directly using reducer registry through App
+                    let _ = self
+                        .app
+                        .apply_barrier(&mut update_state, &[], vec![partial])
+                        .await;
+                    session_state.state = update_state;
+                    // Save back to sessions map so callers can inspect accumulated errors
+                    self.sessions.insert(session_id.to_string(), session_state);
+                    // Re-persist if autosave
+                    if self.autosave
+                        && let Some(cp) = &self.checkpointer
+                        && let Some(s) = self.sessions.get(session_id)
+                    {
+                        let _ = cp
+                            .save(self.redact_checkpoint(Checkpoint::from_session(session_id, s)))
+                            .await;
+                    }
+                    return Err(e);
                 }
-                return Err(e);
+            };
+
+            // Persist the staged step before committing it to the session map.
+            let (attempts, failure) = self
+                .persist_staged_step(session_id, &session_state, &step_report)
+                .await;
+            save_attempts += attempts;
+            let (Some(source), Some(committed)) = (failure, &committed) else {
+                break step_report;
+            };
+            if reruns < max_reruns {
+                reruns += 1;
+                let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+                    "runner.checkpoint_rerun",
+                    format!(
+                        "session {session_id} step {} checkpoint failed ({source}); \
+                         re-running superstep ({reruns}/{max_reruns})",
+                        step_report.step
+                    ),
+                ));
+                self.retract_attempt(session_id, step_report.step, attempt, "checkpoint_rerun");
+                session_state = committed.clone();
+                continue;
             }
+            self.retract_attempt(session_id, step_report.step, attempt, "checkpoint_rollback");
+            self.sessions
+                .insert(session_id.to_string(), committed.clone());
+            let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+                "runner.checkpoint_rollback",
+                format!(
+                    "session {session_id} step {} rolled back after {save_attempts} failed \
+                     checkpoint attempt(s): {source}",
+                    step_report.step
+                ),
+            ));
+            return Err(RunnerError::CheckpointRollback {
+                step: step_report.step,
+                attempts: save_attempts,
+                source,
+            });
         };
 
+        self.publish_step_report(session_id, &step_report);
+        self.session_metrics
+            .entry(session_id.to_string())
+            .or_default()
+            .record(&step_report);
+        if let Some(profile) = &step_report.profile {
+            self.session_profiles
+                .entry(session_id.to_string())
+                .or_default()
+                .record(profile.clone());
+        }
+        self.check_sla(session_id, &session_state, &step_report);
+        if self.publish_step_metrics {
+            let _ = self
+                .event_bus
+                .get_emitter()
+                .emit(step_metrics_event(session_id, &step_report));
+        }
+        if let Some(values) = &self.state_values {
+            let _ = values.send(session_state.state.snapshot());
+        }
+        self.run_superstep_hooks(session_id, &session_state, &step_report)
+            .await;
+        if !step_report.state_limit_breaches.is_empty() {
+            session_state = self
+                .apply_state_limit_policy(session_id, session_state, &step_report)
+                .await?;
+            if !step_report.completed
+                && self
+                    .app
+                    .runtime_config()
+                    .state_limits()
+                    .is_some_and(|limits| limits.policy() == StateLimitPolicy::Pause)
+            {
+                self.sessions
+                    .insert(session_id.to_string(), session_state.clone());
+                return Ok(StepResult::Paused(PausedReport {
+                    session_state,
+                    reason: PausedReason::StateLimitExceeded(
+                        step_report.state_limit_breaches.clone(),
+                    ),
+                }));
+            }
+        }
+
         // Evaluate post-execution interrupts BEFORE reinserting to minimize clones
         // If an interrupt triggers, we insert a clone for persistence and move original into PausedReport.
         if let Some(node) = step_report
@@ -1114,8 +2389,6 @@ impl AppRunner {
             // Persist a clone, return original in PausedReport
             let persisted = session_state.clone();
             self.sessions.insert(session_id.to_string(), persisted);
-            // Re-persist via helper
-            self.maybe_checkpoint(session_id, step_report.step).await;
             return Ok(StepResult::Paused(PausedReport {
                 session_state,
                 reason: PausedReason::AfterNode(node.clone()),
@@ -1124,8 +2397,6 @@ impl AppRunner {
         if options.interrupt_each_step {
             let persisted = session_state.clone();
             self.sessions.insert(session_id.to_string(), persisted);
-            // Re-persist via helper
-            self.maybe_checkpoint(session_id, step_report.step).await;
             return Ok(StepResult::Paused(PausedReport {
                 session_state,
                 reason: PausedReason::AfterStep(step_report.step),
@@ -1134,18 +2405,59 @@ impl AppRunner {
 
         // Normal completion path: reinsert owned session_state directly (no clone)
         self.sessions.insert(session_id.to_string(), session_state);
-        // Persist via helper
-        self.maybe_checkpoint(session_id, step_report.step).await;
         Ok(StepResult::Completed(step_report))
     }
 
+    /// Record an execution of `step` and return its attempt number.
+    ///
+    /// Re-running the step most recently executed for the session, whether
+    /// by a checkpoint rerun or a caller retrying after a rollback, counts up.
+    fn next_attempt(&mut self, session_id: &str, step: u64) -> u32 {
+        let entry = self
+            .step_attempts
+            .entry(session_id.to_string())
+            .or_insert((step, 0));
+        if entry.0 != step {
+            *entry = (step, 0);
+        }
+        entry.1 += 1;
+        entry.1
+    }
+
+    /// Derived channels of the app bound to `snapshot` and the session's cache.
+    fn derived_values(
+        &self,
+        session_id: &str,
+        snapshot: &Arc<StateSnapshot>,
+    ) -> Option<DerivedValues> {
+        let cache = self.derived_caches.get(session_id)?;
+        Some(DerivedValues::with_cache(
+            self.app.derived_channels().clone(),
+            Arc::clone(cache),
+            Arc::clone(snapshot),
+        ))
+    }
+
+    /// Publish an [`AttemptRetraction`] for a discarded step attempt.
+    fn retract_attempt(&self, session_id: &str, step: u64, attempt: u32, reason: &str) {
+        let _ = self
+            .event_bus
+            .get_emitter()
+            .emit(AttemptRetraction::new(session_id, step, attempt, reason).to_event());
+    }
+
     /// Schedule one step: invoke scheduler and normalize outputs to ordered partials.
+    ///
+    /// Before the first yielded partial is applied, the state is copied into
+    /// `pre_partial_state` so a failed step can be rolled back.
     #[inline]
     async fn schedule_step(
         &self,
         session_id: &str,
         session_state: &mut SessionState,
         step: u64,
+        attempt: u32,
+        pre_partial_state: &mut Option<VersionedState>,
     ) -> Result<SchedulerOutcome, RunnerError> {
         let snapshot = session_state.state.snapshot();
         // If an observer is attached, wrap the emitter to fire on_event_bus_emit for each emit.
@@ -1157,40 +2469,178 @@ impl AppRunner {
         } else {
             self.event_bus.get_emitter()
         };
-        let result = session_state
-            .scheduler
-            .superstep(
-                &mut session_state.scheduler_state,
-                self.app.nodes(),
-                session_state.frontier.clone(),
-                snapshot.clone(),
-                step,
-                SchedulerRunContext {
-                    event_emitter: emitter,
-                    clock: self.clock.clone(),
-                    invocation_id: Some(session_id.to_string()),
-                },
-            )
-            .await?;
+        let (partial_stream, partial_rx, live_tx) = PartialStream::channel(snapshot.clone());
+        let run_context = SchedulerRunContext::new(emitter)
+            .with_invocation_id(session_id)
+            .with_attempt(attempt)
+            .with_partial_stream(partial_stream);
+        let run_context = match self.clock.clone() {
+            Some(clock) => run_context.with_clock(clock),
+            None => run_context,
+        };
+        let run_context = match self.app.runtime_config().rng_seed() {
+            Some(seed) => run_context.with_rng_seed(seed),
+            None => run_context,
+        };
+        let run_context = match self.session_flags.get(session_id) {
+            Some(flags) => run_context.with_feature_flags(Arc::clone(flags)),
+            None => run_context,
+        };
+        let run_context = run_context.with_snapshot_views(self.app.snapshot_views().clone());
+        let derived = self
+            .derived_caches
+            .contains_key(session_id)
+            .then(|| Arc::new(snapshot.clone()))
+            .and_then(|input| self.derived_values(session_id, &input));
+        let run_context = match derived {
+            Some(derived) => run_context.with_derived(derived),
+            None => run_context,
+        };
+        let run_context = match &self.cancellation {
+            Some(token) => run_context.with_cancellation(token.clone(), self.cancel_grace_period),
+            None => run_context,
+        };
+        let llm_audit = self
+            .app
+            .runtime_config()
+            .llm_audit()
+            .then(|| Arc::new(LlmAuditLog::default()));
+        let run_context = match &llm_audit {
+            Some(log) => run_context.with_llm_audit(Arc::clone(log)),
+            None => run_context,
+        };
+        let captured_input = self
+            .app
+            .runtime_config()
+            .node_input_capture()
+            .then(|| snapshot.clone());
+        let superstep = session_state.scheduler.superstep(
+            &mut session_state.scheduler_state,
+            self.app.nodes(),
+            session_state.frontier.clone(),
+            snapshot,
+            step,
+            run_context,
+        );
+        tokio::pin!(superstep);
+
+        // Apply incremental partials at micro-barriers while nodes are still running.
+        let mut micro_barrier = BarrierOutcome::default();
+        let result = loop {
+            tokio::select! {
+                biased;
+                Ok((kind, mut partial)) = partial_rx.recv_async() => {
+                    self.app.validate_output(&kind, step, &mut partial)?;
+                    pre_partial_state.get_or_insert_with(|| session_state.state.clone());
+                    let outcome = self
+                        .app
+                        .apply_barrier(&mut session_state.state, std::slice::from_ref(&kind), vec![partial])
+                        .await
+                        .map_err(RunnerError::AppBarrier)?;
+                    merge_barrier_outcome(&mut micro_barrier, outcome);
+                    live_tx.send_replace(session_state.state.snapshot());
+                }
+                result = &mut superstep => break result,
+            }
+        };
+        // Calls made by nodes that then failed are audited too.
+        if let Some(log) = &llm_audit {
+            self.persist_llm_audit(log.drain()).await;
+        }
+        let result = result?;
+        // Drain partials yielded right before their node returned.
+        while let Ok((kind, mut partial)) = partial_rx.try_recv() {
+            self.app.validate_output(&kind, step, &mut partial)?;
+            pre_partial_state.get_or_insert_with(|| session_state.state.clone());
+            let outcome = self
+                .app
+                .apply_barrier(
+                    &mut session_state.state,
+                    std::slice::from_ref(&kind),
+                    vec![partial],
+                )
+                .await
+                .map_err(RunnerError::AppBarrier)?;
+            merge_barrier_outcome(&mut micro_barrier, outcome);
+        }
 
-        let mut partials_by_kind: FxHashMap<NodeKind, NodePartial> = FxHashMap::default();
-        for (k, partial) in result.outputs {
-            partials_by_kind.insert(k, partial);
+        // Outputs arrive in `ran_nodes` order, one per run.
+        let mut partials = Vec::with_capacity(result.outputs.len());
+        for (k, mut partial) in result.outputs {
+            self.app.validate_output(&k, step, &mut partial)?;
+            partials.push(partial);
         }
         let executed_nodes = result.ran_nodes.clone();
-        let partials = executed_nodes
-            .iter()
-            .cloned()
-            .filter_map(|k| partials_by_kind.remove(&k))
-            .collect();
+        if let Some(input) = captured_input
+            && !executed_nodes.is_empty()
+        {
+            self.capture_node_inputs(session_id, step, &executed_nodes, input)
+                .await;
+        }
 
         Ok(SchedulerOutcome {
             ran_nodes: executed_nodes,
             skipped_nodes: result.skipped_nodes,
             partials,
+            micro_barrier,
+            node_metrics: result.node_metrics,
+            node_timings: result.node_timings,
+            saturation: result.saturation,
         })
     }
 
+    /// Persist the redacted snapshot the nodes of `step` received.
+    ///
+    /// Capture is best-effort debugging data, so failures are logged rather
+    /// than failing the step.
+    async fn capture_node_inputs(
+        &self,
+        session_id: &str,
+        step: u64,
+        nodes: &[NodeKind],
+        mut input: StateSnapshot,
+    ) {
+        if let Some(policy) = &self.app.runtime_config().redaction {
+            policy.redact_snapshot(&mut input);
+        }
+        tracing::trace!(
+            target: "weavegraph::node_inputs",
+            session_id,
+            step,
+            nodes = ?nodes,
+            messages = input.messages.len(),
+            extra_keys = input.extra.len(),
+            "captured node inputs"
+        );
+        let Some(checkpointer) = &self.checkpointer else {
+            return;
+        };
+        let capture = NodeInputCapture::new(session_id, step, nodes.to_vec(), input);
+        if let Err(err) = checkpointer.save_node_inputs(capture).await {
+            tracing::warn!(session_id, step, error = %err, "failed to persist node inputs");
+        }
+    }
+
+    /// Persist the LLM calls recorded during a superstep, after redaction.
+    ///
+    /// Like node input capture, auditing is best-effort: failures are logged
+    /// rather than failing the step.
+    async fn persist_llm_audit(&self, records: Vec<LlmAuditRecord>) {
+        let Some(checkpointer) = &self.checkpointer else {
+            return;
+        };
+        for record in records {
+            let record = match &self.app.runtime_config().redaction {
+                Some(policy) => redact_audit_record(policy, record),
+                None => record,
+            };
+            let (session_id, step) = (record.session_id.clone(), record.step);
+            if let Err(err) = checkpointer.save_llm_audit(record).await {
+                tracing::warn!(%session_id, step, error = %err, "failed to persist LLM audit record");
+            }
+        }
+    }
+
     /// Apply barrier and update session state with the results.
     #[tracing::instrument(skip(self, session_state, partials, ran), err)]
     async fn apply_barrier_and_update(
@@ -1209,19 +2659,136 @@ impl AppRunner {
         Ok(outcome)
     }
 
+    /// Apply the barrier like [`apply_barrier_and_update`](Self::apply_barrier_and_update),
+    /// then check the state against the runtime's [`StateLimits`].
+    ///
+    /// Breaches are attributed to the node with the largest output. Under
+    /// [`StateLimitPolicy::RejectOutput`] the barrier is re-applied without the
+    /// largest remaining outputs until the state fits or no output is left, and
+    /// each rejection is recorded as a warning in the errors channel.
+    async fn apply_barrier_within_limits(
+        &self,
+        session_state: &mut SessionState,
+        ran: &[NodeKind],
+        partials: Vec<NodePartial>,
+        step: u64,
+    ) -> Result<(BarrierOutcome, Vec<StateLimitBreach>), RunnerError> {
+        let Some(limits) = self
+            .app
+            .runtime_config()
+            .state_limits()
+            .filter(|limits| !limits.is_empty())
+        else {
+            let outcome = self
+                .apply_barrier_and_update(session_state, ran, partials)
+                .await?;
+            return Ok((outcome, Vec::new()));
+        };
+        let output_bytes: Vec<usize> = partials.iter().map(StateSize::of_output).collect();
+        let largest = |kept: &[bool]| {
+            (0..output_bytes.len())
+                .filter(|&i| kept[i] && output_bytes[i] > 0)
+                .max_by_key(|&i| (output_bytes[i], std::cmp::Reverse(i)))
+        };
+        let mut kept = vec![true; partials.len()];
+        if limits.policy() != StateLimitPolicy::RejectOutput {
+            let outcome = self
+                .apply_barrier_and_update(session_state, ran, partials)
+                .await?;
+            let size = StateSize::of_state(&mut session_state.state);
+            let breaches = limits.breaches(&size, step, largest(&kept).map(|i| &ran[i]));
+            return Ok((outcome, breaches));
+        }
+
+        // Apply into a scratch copy and only commit a state that fits, so the
+        // common within-limits step costs no more than an unchecked barrier.
+        let mut scratch = session_state.state.clone();
+        let mut outcome = self
+            .app
+            .apply_barrier_partials(&mut scratch, ran, &partials)
+            .await
+            .map_err(RunnerError::AppBarrier)?;
+        let size = StateSize::of_state(&mut scratch);
+        let breaches = limits.breaches(&size, step, largest(&kept).map(|i| &ran[i]));
+        if breaches.is_empty() {
+            session_state.state = scratch;
+            return Ok((outcome, breaches));
+        }
+
+        let mut rejected = Vec::new();
+        while let Some(index) = largest(&kept) {
+            kept[index] = false;
+            rejected.push(ran[index].clone());
+            let (kept_ran, kept_partials): (Vec<NodeKind>, Vec<NodePartial>) = ran
+                .iter()
+                .zip(&partials)
+                .zip(&kept)
+                .filter(|(_, keep)| **keep)
+                .map(|((kind, partial), _)| (kind.clone(), partial.clone()))
+                .unzip();
+            scratch = session_state.state.clone();
+            outcome = self
+                .app
+                .apply_barrier_partials(&mut scratch, &kept_ran, &kept_partials)
+                .await
+                .map_err(RunnerError::AppBarrier)?;
+            if limits
+                .exceeded(&StateSize::of_state(&mut scratch))
+                .is_empty()
+            {
+                break;
+            }
+        }
+        session_state.state = scratch;
+        let events = rejected
+            .iter()
+            .map(|node| ErrorEvent {
+                when: chrono::Utc::now(),
+                scope: ErrorScope::Node {
+                    kind: node.encode().to_string(),
+                    step,
+                },
+                error: WeaveError::msg("output rejected: state limits exceeded"),
+                tags: vec!["state_limit".into(), "rejected_output".into()],
+                context: serde_json::json!({
+                    "limits": breaches.iter().map(|b| b.limit.label()).collect::<Vec<_>>()
+                }),
+                severity: ErrorSeverity::Warning,
+            })
+            .collect();
+        let recorded = self
+            .apply_barrier_and_update(
+                session_state,
+                &[],
+                vec![NodePartial::new().with_errors(events)],
+            )
+            .await?;
+        merge_barrier_outcome(&mut outcome, recorded);
+        Ok((outcome, breaches))
+    }
+
     /// Compute next frontier from barrier outcome, resolving commands and conditional edges.
+    ///
+    /// Targets are merged according to their [`JoinPolicy`]; the origin of each
+    /// per-incoming-edge slot is recorded in the scheduler state. Also returns
+    /// every edge followed, for [`StepReport::frontier_edges`].
     #[inline]
     fn compute_next_frontier(
         &self,
-        session_state: &SessionState,
+        session_id: &str,
+        session_state: &mut SessionState,
         ran: &[NodeKind],
         barrier: &BarrierOutcome,
         step: u64,
-    ) -> Vec<NodeKind> {
+        flags: &FeatureFlags,
+    ) -> (Vec<NodeKind>, Vec<FrontierEdge>) {
         let mut next_frontier: Vec<NodeKind> = Vec::new();
+        let mut frontier_edges: Vec<FrontierEdge> = Vec::new();
+        let mut origins: Vec<Option<NodeKind>> = Vec::new();
         let graph_edges = self.app.edges();
         let conditional_edges = self.app.conditional_edges();
-        let state_snapshot = session_state.state.snapshot();
+        let state_snapshot = Arc::new(session_state.state.snapshot());
+        let derived = self.derived_values(session_id, &state_snapshot);
 
         let mut frontier_commands_by_node: FxHashMap<NodeKind, Vec<FrontierCommand>> =
             FxHashMap::default();
@@ -1233,8 +2800,19 @@ impl AppRunner {
         }
 
         for id in ran.iter() {
-            let default_edges = graph_edges.get(id).cloned().unwrap_or_default();
-            let mut next_targets: Vec<NodeKind> = Vec::new();
+            let default_edges: Vec<FrontierEdge> = graph_edges
+                .get(id)
+                .into_iter()
+                .flatten()
+                .map(|to| {
+                    FrontierEdge::new(id, to.clone(), FrontierEdgeKind::Static)
+                        .with_edge_label(self.app.edge_label(id, to))
+                })
+                .collect();
+            let command_edge = |entry: &NodeRoute| {
+                FrontierEdge::new(id, entry.to_node_kind(), FrontierEdgeKind::Command)
+            };
+            let mut next_targets: Vec<FrontierEdge> = Vec::new();
             let mut frontier_replaced = false;
 
             if let Some(commands) = frontier_commands_by_node.get(id) {
@@ -1253,29 +2831,39 @@ impl AppRunner {
                                 );
                                 continue;
                             }
-                            next_targets = entries.iter().map(NodeRoute::to_node_kind).collect();
+                            next_targets = entries.iter().map(command_edge).collect();
                             frontier_replaced = true;
                         }
                         FrontierCommand::Append(entries) => {
                             if next_targets.is_empty() && !frontier_replaced {
                                 next_targets.extend(default_edges.clone());
                             }
-                            next_targets.extend(entries.iter().map(NodeRoute::to_node_kind));
+                            next_targets.extend(entries.iter().map(command_edge));
                         }
                     }
                 }
 
                 if next_targets.is_empty() && !frontier_replaced {
-                    next_targets.extend(default_edges.clone());
+                    next_targets.extend(default_edges);
                 }
             } else {
-                next_targets.extend(default_edges.clone());
+                next_targets.extend(default_edges);
             }
 
             if !frontier_replaced {
                 for conditional_edge in conditional_edges.iter().filter(|ce| ce.from() == id) {
                     tracing::debug!(from = ?conditional_edge.from(), step, "evaluating conditional edge");
-                    let target_node_names = (conditional_edge.predicate())(state_snapshot.clone());
+                    let routing_context = RoutingContext::new(id, &state_snapshot)
+                        .with_step(step)
+                        .with_ran_nodes(ran)
+                        .with_barrier(barrier)
+                        .with_config(self.app.runtime_config())
+                        .with_feature_flags(flags);
+                    let routing_context = match &derived {
+                        Some(derived) => routing_context.with_derived(derived),
+                        None => routing_context,
+                    };
+                    let target_node_names = conditional_edge.evaluate(&routing_context);
 
                     for target_name in target_node_names {
                         let target = if target_name == "End" {
@@ -1288,20 +2876,45 @@ impl AppRunner {
 
                         tracing::debug!(target = ?target, step, "conditional edge routed");
 
-                        next_targets.push(target);
+                        let mut edge = FrontierEdge::new(id, target, FrontierEdgeKind::Conditional);
+                        edge.label = conditional_edge.label().map(str::to_string);
+                        next_targets.push(edge);
                     }
                 }
             }
 
-            for target in next_targets {
+            for edge in next_targets {
+                let target = edge.to.clone();
                 let is_valid_target = match &target {
                     NodeKind::End | NodeKind::Start => true,
                     NodeKind::Custom(_) => self.app.nodes().contains_key(&target),
                 };
 
                 if is_valid_target {
-                    if !next_frontier.contains(&target) {
-                        next_frontier.push(target);
+                    frontier_edges.push(edge);
+                    match self.app.join_policy(&target) {
+                        JoinPolicy::PerIncomingEdge => {
+                            let slot = Some(id.clone());
+                            let seen = next_frontier
+                                .iter()
+                                .zip(&origins)
+                                .any(|(kind, origin)| *kind == target && *origin == slot);
+                            if !seen {
+                                next_frontier.push(target);
+                                origins.push(slot);
+                            }
+                        }
+                        JoinPolicy::WaitForAll => {
+                            session_state
+                                .scheduler_state
+                                .record_join_arrival(&target, id, step);
+                        }
+                        _ => {
+                            if !next_frontier.contains(&target) {
+                                next_frontier.push(target);
+                                origins.push(None);
+                            }
+                        }
                     }
                 } else {
                     tracing::warn!(
@@ -1314,60 +2927,169 @@ impl AppRunner {
             }
         }
 
-        next_frontier
+        // Release AND-joins whose predecessors have all arrived, in a stable order.
+        let join_policies = self.app.join_policies();
+        for target in join_policies.wait_for_all() {
+            let required = join_policies.required_upstreams(self.app.edges(), target);
+            let scheduler_state = &mut session_state.scheduler_state;
+            if scheduler_state.join_ready(target, &required) {
+                scheduler_state.clear_join(target);
+                if !next_frontier.contains(target) {
+                    next_frontier.push(target.clone());
+                    origins.push(None);
+                }
+            }
+        }
+
+        session_state.scheduler_state.set_frontier_origins(&origins);
+        (next_frontier, frontier_edges)
+    }
+
+    /// Apply the configured [`RedactionPolicy`](crate::redaction::RedactionPolicy) to a checkpoint before it is saved.
+    ///
+    /// Only the persisted copy is redacted; live session state is untouched.
+    fn redact_checkpoint(&self, mut checkpoint: Checkpoint) -> Checkpoint {
+        if let Some(policy) = &self.app.runtime_config().redaction {
+            policy.redact_state(&mut checkpoint.state);
+        }
+        checkpoint
     }
 
     /// Conditionally persist a checkpoint for the given session if autosave is enabled.
-    async fn maybe_checkpoint(&self, session_id: &str, step: u64) {
+    async fn maybe_checkpoint(&self, session_id: &str, step: u64, report: Option<&StepReport>) {
+        if let Some(session_state) = self.sessions.get(session_id) {
+            let _ = self
+                .save_checkpoint(session_id, session_state, step, report)
+                .await;
+        }
+    }
+
+    /// Save a checkpoint of `session_state` if autosave is enabled.
+    ///
+    /// Returns `Ok(())` without saving when persistence is off.
+    async fn save_checkpoint(
+        &self,
+        session_id: &str,
+        session_state: &SessionState,
+        step: u64,
+        report: Option<&StepReport>,
+    ) -> Result<(), CheckpointerError> {
+        if !self.autosave {
+            return Ok(());
+        }
+        let Some(checkpointer) = &self.checkpointer else {
+            return Ok(());
+        };
         let checkpoint_span = tracing::info_span!("checkpoint", step);
         checkpoint_span
             .in_scope(|| async {
-                if self.autosave
-                    && let Some(checkpointer) = &self.checkpointer
-                    && let Some(session_state) = self.sessions.get(session_id)
+                let start = std::time::Instant::now();
+                let checkpoint = match report {
+                    Some(report) => Checkpoint::from_step_report(session_id, session_state, report),
+                    None => Checkpoint::from_session(session_id, session_state),
+                };
+                let result = checkpointer.save(self.redact_checkpoint(checkpoint)).await;
+                let duration_ms = start.elapsed().as_millis() as u64;
+                if result.is_ok()
+                    && let Some(obs) = &self.observer
                 {
-                    let start = std::time::Instant::now();
-                    let result = checkpointer
-                        .save(Checkpoint::from_session(session_id, session_state))
-                        .await;
-                    let duration_ms = start.elapsed().as_millis() as u64;
-                    if result.is_ok()
-                        && let Some(obs) = &self.observer
-                    {
-                        let backend = self.checkpointer_descriptor.as_str();
-                        call_observer_hook(
-                            || {
-                                obs.on_checkpoint_save(&CheckpointSaveMeta {
-                                    session_id,
-                                    backend,
-                                    step,
-                                    duration_ms,
-                                })
-                            },
-                            "on_checkpoint_save",
-                        );
-                    }
+                    let backend = self.checkpointer_descriptor.as_str();
+                    call_observer_hook(
+                        || {
+                            obs.on_checkpoint_save(&CheckpointSaveMeta {
+                                session_id,
+                                backend,
+                                step,
+                                duration_ms,
+                            })
+                        },
+                        "on_checkpoint_save",
+                    );
                 }
+                result
             })
-            .await;
+            .await
+    }
+
+    /// Persist a staged superstep according to the checkpoint failure policy.
+    ///
+    /// Returns the number of save attempts and, when the step must be rolled
+    /// back, the last error. Under [`CheckpointFailurePolicy::Continue`] a
+    /// failure is logged and the step commits.
+    async fn persist_staged_step(
+        &self,
+        session_id: &str,
+        staged: &SessionState,
+        report: &StepReport,
+    ) -> (u32, Option<CheckpointerError>) {
+        let policy = self.app.runtime_config().checkpoint_failure_policy();
+        let (max_attempts, backoff) = match policy {
+            CheckpointFailurePolicy::Retry {
+                max_attempts,
+                backoff,
+            } => (max_attempts.max(1), backoff),
+            _ => (1, Duration::ZERO),
+        };
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let Err(err) = self
+                .save_checkpoint(session_id, staged, report.step, Some(report))
+                .await
+            else {
+                return (attempts, None);
+            };
+            if !policy.is_transactional() {
+                tracing::warn!(
+                    session_id,
+                    step = report.step,
+                    error = %err,
+                    "checkpoint save failed; continuing with unsaved step"
+                );
+                return (attempts, None);
+            }
+            if attempts >= max_attempts {
+                return (attempts, Some(err));
+            }
+            tracing::warn!(
+                session_id,
+                step = report.step,
+                attempt = attempts,
+                error = %err,
+                "checkpoint save failed; retrying"
+            );
+            tokio::time::sleep(backoff).await;
+        }
     }
 
     /// Helper method that executes exactly one superstep on the given session state.
     ///
     /// Applies barrier outcomes (including frontier commands) and returns the updated
     /// step report with deterministic routing decisions.
+    /// `pre_partial_state` receives the state as it was before any yielded
+    /// partial was applied.
     #[instrument(skip(self, session_state), err)]
     async fn run_one_superstep(
         &self,
         session_id: &str,
         session_state: &mut SessionState,
+        attempt: u32,
+        pre_partial_state: &mut Option<VersionedState>,
     ) -> Result<StepReport, RunnerError> {
         session_state.step += 1;
         let step = session_state.step;
         let step_start = std::time::Instant::now();
+        let mut timer = self.profile_supersteps.then(SuperstepTimer::start);
 
         tracing::debug!(step, "starting superstep");
 
+        // Commands queued before this point are delivered to this step's snapshots.
+        let command_watermark =
+            CommandInbox::from_value(session_state.state.extra.get_mut().get(COMMAND_INBOX_KEY))
+                .next_sequence;
+
+        self.enforce_node_quotas(session_id, session_state, step)?;
+
         // Phase 1: schedule and normalize outputs
         let schedule_span = tracing::info_span!(
             "schedule",
@@ -1375,8 +3097,20 @@ impl AppRunner {
             frontier_len = session_state.frontier.len()
         );
         let scheduler_outcome = schedule_span
-            .in_scope(|| self.schedule_step(session_id, session_state, step))
+            .in_scope(|| {
+                self.schedule_step(session_id, session_state, step, attempt, pre_partial_state)
+            })
             .await?;
+        if let Some(timer) = &mut timer {
+            timer.scheduled();
+        }
+
+        let quotas = self.app.node_quotas();
+        for node in &scheduler_outcome.ran_nodes {
+            if quotas.tracks(node) {
+                session_state.scheduler_state.record_run(node);
+            }
+        }
 
         // Phase 2: apply barrier and update state
         let errors_in_partials = scheduler_outcome
@@ -1390,27 +3124,51 @@ impl AppRunner {
             ran_nodes_len = scheduler_outcome.ran_nodes.len(),
             errors_in_partials
         );
-        let barrier_outcome = barrier_span
+        let (final_barrier, state_limit_breaches) = barrier_span
             .in_scope(|| {
-                self.apply_barrier_and_update(
+                self.apply_barrier_within_limits(
                     session_state,
                     &scheduler_outcome.ran_nodes,
                     scheduler_outcome.partials,
+                    step,
                 )
             })
             .await?;
+        self.app
+            .consume_commands(
+                &mut session_state.state,
+                &scheduler_outcome.ran_nodes,
+                command_watermark,
+            )
+            .map_err(|err| RunnerError::AppBarrier(Box::new(err)))?;
+        // Micro-barrier effects come first so frontier commands keep arrival order.
+        let mut barrier_outcome = scheduler_outcome.micro_barrier;
+        merge_barrier_outcome(&mut barrier_outcome, final_barrier);
+        if let Some(timer) = &mut timer {
+            timer.barrier_applied();
+        }
+        for conflict in &barrier_outcome.conflicts {
+            let _ = self.event_bus.get_emitter().emit(Event::diagnostic(
+                EXTRA_CONFLICT_SCOPE,
+                format!("step={step} {conflict}"),
+            ));
+        }
 
         // Phase 3: compute next frontier
         let commands_count = barrier_outcome.frontier_commands.len();
         let conditional_edges_evaluated = self.app.conditional_edges().len();
         let frontier_span =
             tracing::info_span!("frontier", commands_count, conditional_edges_evaluated);
-        let next_frontier = frontier_span.in_scope(|| {
+        let (next_frontier, frontier_edges) = frontier_span.in_scope(|| {
             self.compute_next_frontier(
+                session_id,
                 session_state,
                 &scheduler_outcome.ran_nodes,
                 &barrier_outcome,
                 step,
+                self.session_flags
+                    .get(session_id)
+                    .map_or(&NO_FLAGS, |flags| flags.as_ref()),
             )
         });
 
@@ -1421,12 +3179,28 @@ impl AppRunner {
             "barrier applied"
         );
         tracing::debug!(step, next_frontier = ?next_frontier, "computed next frontier");
+        let emitter = self.event_bus.get_emitter();
+        for edge in frontier_edges.iter().filter(|edge| edge.label.is_some()) {
+            let _ = emitter.emit(edge.to_event(session_id, step));
+        }
+        let profile = timer.map(|timer| {
+            timer.finish(
+                step,
+                barrier_outcome.reducer_micros,
+                &scheduler_outcome.node_timings,
+            )
+        });
 
         let completed =
             next_frontier.is_empty() || next_frontier.iter().all(|n| *n == NodeKind::End);
 
         // Update session state
         session_state.frontier = next_frontier.clone();
+        self.record_saturation(
+            session_state,
+            scheduler_outcome.saturation,
+            &scheduler_outcome.node_metrics,
+        );
 
         let state_versions = StateVersions {
             messages_version: session_state.state.messages.version(),
@@ -1472,8 +3246,12 @@ impl AppRunner {
             skipped_nodes: scheduler_outcome.skipped_nodes,
             barrier_outcome,
             next_frontier,
+            frontier_edges,
             state_versions,
             completed,
+            node_metrics: scheduler_outcome.node_metrics,
+            profile,
+            state_limit_breaches,
         })
     }
 
@@ -1487,6 +3265,31 @@ impl AppRunner {
         &mut self,
         session_id: &str,
     ) -> Result<VersionedState, RunnerError> {
+        self.run_until_complete_with_policy(session_id, CompletionEventPolicy::CloseStream)
+            .await
+            .map(|(state, _)| state)
+    }
+
+    /// Like [`run_until_complete`](Self::run_until_complete), also returning
+    /// the [`RunSummary`] published at the end of the run.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use weavegraph::runtimes::AppRunner;
+    /// # async fn example(mut runner: AppRunner) -> Result<(), Box<dyn std::error::Error>> {
+    /// let (final_state, summary) = runner.run_until_complete_with_summary("s1").await?;
+    /// for (node, stats) in &summary.nodes {
+    ///     println!("{node}: {} runs, mean {:?}", stats.invocations, stats.mean_duration());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, session_id), err)]
+    pub async fn run_until_complete_with_summary(
+        &mut self,
+        session_id: &str,
+    ) -> Result<(VersionedState, RunSummary), RunnerError> {
         self.run_until_complete_with_policy(session_id, CompletionEventPolicy::CloseStream)
             .await
     }
@@ -1495,7 +3298,7 @@ impl AppRunner {
         &mut self,
         session_id: &str,
         completion_policy: CompletionEventPolicy,
-    ) -> Result<VersionedState, RunnerError> {
+    ) -> Result<(VersionedState, RunSummary), RunnerError> {
         tracing::info!(session = %session_id, "workflow run started");
 
         let graph_id = self.app.graph_definition_hash();
@@ -1513,6 +3316,7 @@ impl AppRunner {
             );
         }
         let invocation_start = std::time::Instant::now();
+        let mut summary = RunSummary::new(session_id);
 
         loop {
             // Check if we're done before trying to run
@@ -1545,7 +3349,8 @@ impl AppRunner {
                             error: reason,
                         },
                         completion_policy,
-                    );
+                    )
+                    .await;
                     if let Some(obs) = &self.observer {
                         let duration_ms = invocation_start.elapsed().as_millis() as u64;
                         let graph_id = self.app.graph_definition_hash();
@@ -1569,10 +3374,31 @@ impl AppRunner {
 
             match step_result {
                 StepResult::Completed(report) => {
+                    summary.record(&report);
                     if report.completed {
                         break;
                     }
                 }
+                StepResult::Paused(PausedReport {
+                    reason: PausedReason::StateLimitExceeded(breaches),
+                    session_state,
+                }) => {
+                    let err = RunnerError::StateLimitExceeded {
+                        session_id: session_id.to_string(),
+                        step: session_state.step,
+                        breaches,
+                    };
+                    self.emit_completion_event(
+                        session_id,
+                        StreamEndReason::Error {
+                            step: Some(session_state.step),
+                            error: err.to_string(),
+                        },
+                        completion_policy,
+                    )
+                    .await;
+                    return Err(err);
+                }
                 StepResult::Paused(_) => {
                     // This shouldn't happen with default options, but handle gracefully
                     let step = self.sessions.get(session_id).map(|state| state.step);
@@ -1583,7 +3409,8 @@ impl AppRunner {
                             error: "execution paused unexpectedly".to_string(),
                         },
                         completion_policy,
-                    );
+                    )
+                    .await;
                     return Err(RunnerError::UnexpectedPause);
                 }
             }
@@ -1627,11 +3454,16 @@ impl AppRunner {
             );
         }
 
+        summary.final_step = final_step;
+        summary.duration_micros =
+            u64::try_from(invocation_start.elapsed().as_micros()).unwrap_or(u64::MAX);
+        let _ = self.event_bus.get_emitter().emit(summary.to_event());
         self.emit_completion_event(
             session_id,
             StreamEndReason::Completed { step: final_step },
             completion_policy,
-        );
+        )
+        .await;
         if let Some(obs) = &self.observer {
             let duration_ms = invocation_start.elapsed().as_millis() as u64;
             let gid = graph_id.as_str();
@@ -1647,7 +3479,7 @@ impl AppRunner {
                 "on_invocation_finish",
             );
         }
-        Ok(final_state)
+        Ok((final_state, summary))
     }
 
     /// Get a snapshot of the current session state.
@@ -1664,6 +3496,86 @@ impl AppRunner {
         self.sessions.get(session_id)
     }
 
+    /// Run every node's [`on_shutdown`](crate::node::Node::on_shutdown) hook.
+    ///
+    /// Delegates to [`App::shutdown_nodes`]; the app is shared by every runner
+    /// built from it, so call this once no runner needs the nodes anymore.
+    /// Creating another session afterwards registers the nodes again.
+    pub async fn shutdown(&self) {
+        self.app.shutdown_nodes().await;
+    }
+
+    /// Watch step progress for a session.
+    ///
+    /// The returned receiver always holds the most recent [`StepReport`] for the
+    /// session; observers read it with [`watch::Receiver::borrow`] instead of
+    /// consuming the event stream or polling [`get_session`](Self::get_session).
+    /// Before the first step completes, the receiver holds a report describing
+    /// the session's current step and frontier with no nodes ran.
+    ///
+    /// Intermediate reports may be skipped if the observer is slower than the
+    /// workflow. The receiver closes when the runner is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the session does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use weavegraph::app::App;
+    /// # use weavegraph::runtimes::AppRunner;
+    /// # use weavegraph::state::VersionedState;
+    /// # async fn example(app: App) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut runner = AppRunner::builder().app(app).build().await;
+    /// runner
+    ///     .create_session("s1".into(), VersionedState::new_with_user_message("hi"))
+    ///     .await?;
+    /// let mut steps = runner.watch_steps("s1")?;
+    /// tokio::spawn(async move {
+    ///     while steps.changed().await.is_ok() {
+    ///         let report = steps.borrow();
+    ///         println!("step {} ran {:?}", report.step, report.ran_nodes);
+    ///     }
+    /// });
+    /// runner.run_until_complete("s1").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_steps(
+        &mut self,
+        session_id: &str,
+    ) -> Result<watch::Receiver<StepReport>, RunnerError> {
+        if let Some(sender) = self.step_watchers.get(session_id) {
+            return Ok(sender.subscribe());
+        }
+        let session_state =
+            self.sessions
+                .get(session_id)
+                .ok_or_else(|| RunnerError::SessionNotFound {
+                    session_id: session_id.to_string(),
+                })?;
+        let initial = StepReport {
+            step: session_state.step,
+            ran_nodes: Vec::new(),
+            skipped_nodes: Vec::new(),
+            barrier_outcome: BarrierOutcome::default(),
+            next_frontier: session_state.frontier.clone(),
+            frontier_edges: Vec::new(),
+            state_versions: StateVersions {
+                messages_version: session_state.state.messages.version(),
+                extra_version: session_state.state.extra.version(),
+            },
+            completed: self.is_session_complete(session_state),
+            node_metrics: Vec::new(),
+            profile: None,
+            state_limit_breaches: Vec::new(),
+        };
+        let (sender, receiver) = watch::channel(initial);
+        self.step_watchers.insert(session_id.to_string(), sender);
+        Ok(receiver)
+    }
+
     /// List all active session IDs.
     ///
     /// # Returns
@@ -1699,6 +3611,207 @@ impl AppRunner {
             || session_state.frontier.iter().all(|n| *n == NodeKind::End)
     }
 
+    /// Forward a state snapshot to `sender` after every completed superstep.
+    pub(crate) fn set_state_values_sender(&mut self, sender: mpsc::UnboundedSender<StateSnapshot>) {
+        self.state_values = Some(sender);
+    }
+
+    /// Per-node metrics aggregated over the steps this runner executed for a session.
+    ///
+    /// Covers durations, emitted event counts, and token usage reported via
+    /// [`NodeContext::record_token_usage`](crate::node::NodeContext::record_token_usage).
+    /// Steps executed before a session was resumed from a checkpoint are not
+    /// included; their metrics are available on the stored checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the session does not exist.
+    pub fn session_metrics(&self, session_id: &str) -> Result<SessionMetrics, RunnerError> {
+        if !self.sessions.contains_key(session_id) {
+            return Err(RunnerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        Ok(self
+            .session_metrics
+            .get(session_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Superstep profiles recorded for a session by a runner built with
+    /// [`profile_supersteps`](AppRunnerBuilder::profile_supersteps).
+    ///
+    /// Holds up to [`PROFILE_STEP_LIMIT`](crate::runtimes::PROFILE_STEP_LIMIT)
+    /// of the most recent steps; empty when profiling is off. Use
+    /// [`SessionProfile::to_chrome_trace`] to view them in `chrome://tracing`
+    /// or Perfetto.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the session does not exist.
+    pub fn session_profile(&self, session_id: &str) -> Result<SessionProfile, RunnerError> {
+        if !self.sessions.contains_key(session_id) {
+            return Err(RunnerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        Ok(self
+            .session_profiles
+            .get(session_id)
+            .map(|log| log.to_session_profile(session_id))
+            .unwrap_or_else(|| SessionProfile {
+                session_id: session_id.to_string(),
+                steps: Vec::new(),
+            }))
+    }
+
+    /// Rolling scheduler saturation metrics across this runner's sessions.
+    ///
+    /// Reports per-superstep queue wait and concurrency utilization plus node
+    /// latency percentiles over recent runs. The same snapshot is included in
+    /// [`event_bus_metrics`](Self::event_bus_metrics).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use weavegraph::runtimes::AppRunner;
+    /// # fn example(runner: &AppRunner) {
+    /// let metrics = runner.scheduler_metrics();
+    /// println!(
+    ///     "utilization={:.2} p90={}us",
+    ///     metrics.utilization(),
+    ///     metrics.latency_p90_micros
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn scheduler_metrics(&self) -> SchedulerMetrics {
+        self.scheduler_telemetry.snapshot()
+    }
+
+    /// Metrics of this runner's event bus, including [`scheduler_metrics`](Self::scheduler_metrics).
+    #[must_use]
+    pub fn event_bus_metrics(&self) -> EventHubMetrics {
+        self.event_bus.metrics()
+    }
+
+    /// Record a superstep's saturation and apply adaptive concurrency, if configured.
+    fn record_saturation(
+        &self,
+        session_state: &mut SessionState,
+        saturation: SuperstepSaturation,
+        node_metrics: &[(NodeKind, NodeMetrics)],
+    ) {
+        self.scheduler_telemetry.record(
+            saturation,
+            node_metrics
+                .iter()
+                .map(|(_, metrics)| metrics.duration_micros),
+        );
+        let Some(adaptive) = self.app.runtime_config().adaptive_concurrency() else {
+            return;
+        };
+        let current = session_state.scheduler.concurrency_limit;
+        let next = adaptive.next_limit(current, &self.scheduler_telemetry.snapshot());
+        if next != current {
+            tracing::debug!(current, next, "adjusted superstep concurrency limit");
+            session_state.scheduler.concurrency_limit = next;
+        }
+        self.scheduler_telemetry.set_concurrency_limit(next);
+    }
+
+    /// Publish breaches of the graph's SLA detected at this step's barrier.
+    fn check_sla(&mut self, session_id: &str, session_state: &SessionState, report: &StepReport) {
+        let sla = self.app.sla();
+        if sla.is_empty() {
+            return;
+        }
+        let tracker = self.sla_trackers.entry(session_id.to_string()).or_default();
+        let breaches = sla.evaluate(
+            tracker,
+            report.step,
+            &report.node_metrics,
+            session_state.state.errors.len(),
+        );
+        let emitter = self.event_bus.get_emitter();
+        for breach in breaches {
+            tracing::warn!(
+                session_id,
+                step = breach.step,
+                metric = breach.metric.label(),
+                observed = breach.observed,
+                limit = breach.limit,
+                "sla breached"
+            );
+            let _ = emitter.emit(breach.to_event());
+        }
+    }
+
+    /// Publish the state limit breaches of a committed step and apply the
+    /// configured policy, returning the (possibly compacted) session state.
+    ///
+    /// Rejected outputs were already dropped at the barrier; compaction
+    /// failures are reported as a `runner.state_limit` diagnostic.
+    async fn apply_state_limit_policy(
+        &mut self,
+        session_id: &str,
+        session_state: SessionState,
+        report: &StepReport,
+    ) -> Result<SessionState, RunnerError> {
+        let policy = self
+            .app
+            .runtime_config()
+            .state_limits()
+            .map(|limits| limits.policy())
+            .unwrap_or_default();
+        let emitter = self.event_bus.get_emitter();
+        for breach in &report.state_limit_breaches {
+            tracing::warn!(
+                session_id,
+                step = breach.step,
+                limit = breach.limit.label(),
+                observed = breach.observed,
+                max = breach.max,
+                node = ?breach.node,
+                policy = policy.label(),
+                "state limit exceeded"
+            );
+            let _ = emitter.emit(breach.to_event());
+        }
+        let StateLimitPolicy::Compact(compaction) = policy else {
+            return Ok(session_state);
+        };
+        self.sessions.insert(session_id.to_string(), session_state);
+        if let Err(err) = self.compact_session(session_id, compaction).await {
+            tracing::warn!(session_id, step = report.step, error = %err, "state limit compaction failed");
+            let _ = emitter.emit(Event::diagnostic(
+                "runner.state_limit",
+                format!(
+                    "session {session_id} step {}: compaction failed: {err}",
+                    report.step
+                ),
+            ));
+        }
+        self.sessions
+            .remove(session_id)
+            .ok_or_else(|| RunnerError::SessionNotFound {
+                session_id: session_id.to_string(),
+            })
+    }
+
+    /// Publish a step report to `watch_steps` observers, cloning only when someone is watching.
+    fn publish_step_report(&mut self, session_id: &str, report: &StepReport) {
+        let Some(sender) = self.step_watchers.get(session_id) else {
+            return;
+        };
+        if sender.receiver_count() == 0 {
+            self.step_watchers.remove(session_id);
+            return;
+        }
+        sender.send_replace(report.clone());
+    }
+
     /// Return the final state clone, channel versions, and last step for the session.
     /// Logging should occur after retrieval by the caller.
     #[inline]
@@ -1731,16 +3844,58 @@ impl AppRunner {
         );
     }
 
-    fn emit_completion_event(
+    /// Hand the committed step to the configured superstep hooks.
+    async fn run_superstep_hooks(
+        &mut self,
+        session_id: &str,
+        session_state: &SessionState,
+        report: &StepReport,
+    ) {
+        let registered = self.app.runtime_config().superstep_hooks();
+        if registered.is_empty() {
+            return;
+        }
+        let drivers = self
+            .superstep_hooks
+            .entry(session_id.to_string())
+            .or_insert_with(|| registered.iter().map(HookDriver::new).collect());
+        let update = SuperstepUpdate {
+            session_id: session_id.to_string(),
+            report: report.clone(),
+            state: session_state.state.snapshot(),
+        };
+        for driver in drivers.iter_mut() {
+            driver.dispatch(update.clone()).await;
+        }
+    }
+
+    /// Deliver the updates superstep hooks have not seen yet.
+    async fn flush_superstep_hooks(&mut self, session_id: &str) {
+        if let Some(drivers) = self.superstep_hooks.remove(session_id) {
+            for driver in drivers {
+                driver.flush().await;
+            }
+        }
+    }
+
+    /// Emit the completion marker, wait for sinks to drain, then close the
+    /// stream if the policy asks for it.
+    async fn emit_completion_event(
         &mut self,
         session_id: &str,
         reason: StreamEndReason,
         policy: CompletionEventPolicy,
     ) {
+        self.flush_superstep_hooks(session_id).await;
         match policy {
-            CompletionEventPolicy::CloseStream => self.finalize_event_stream(session_id, reason),
+            CompletionEventPolicy::CloseStream => {
+                emit_stream_end(&self.event_bus, session_id, reason);
+                drain_event_sinks(&self.event_bus, session_id, self.event_flush_timeout).await;
+                close_event_stream(&self.event_bus, &mut self.event_stream_taken);
+            }
             CompletionEventPolicy::KeepStreamOpen => {
                 emit_invocation_end(&self.event_bus, session_id, reason);
+                drain_event_sinks(&self.event_bus, session_id, self.event_flush_timeout).await;
             }
         }
     }
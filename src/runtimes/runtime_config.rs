@@ -1,10 +1,18 @@
 //! Runtime configuration types for controlling event bus, sinks, and diagnostics.
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
 
 use crate::event_bus::{EventBus, EventSink, MemorySink, StdOutSink};
+use crate::feature_flags::FeatureFlagProvider;
+use crate::redaction::RedactionPolicy;
+use crate::schedulers::AdaptiveConcurrency;
 use crate::utils::clock::Clock;
 
-use super::Checkpointer;
+use super::hooks::{RegisteredHook, SuperstepHook, SuperstepHookOptions};
+use super::{Checkpointer, StateLimits, StateMigrations};
 
 /// Configuration for a single [`AppRunner`](crate::runtimes::runner::AppRunner) instance.
 #[derive(Clone)]
@@ -19,6 +27,28 @@ pub struct RuntimeConfig {
     pub event_bus: EventBusConfig,
     /// Optional runtime clock injected into node execution contexts.
     pub clock: Option<Arc<dyn Clock>>,
+    /// Optional redaction applied to emitted events and persisted checkpoints.
+    pub redaction: Option<Arc<RedactionPolicy>>,
+    /// Free-form configuration values readable by routing predicates.
+    pub values: BTreeMap<String, Value>,
+    /// Base seed for [`NodeContext::rng`](crate::node::NodeContext::rng); the session id is used when `None`.
+    pub rng_seed: Option<u64>,
+    /// What a runner does when the checkpoint for a superstep cannot be saved.
+    pub checkpoint_failure_policy: CheckpointFailurePolicy,
+    /// Adjusts the superstep concurrency limit from observed node latency when set.
+    pub adaptive_concurrency: Option<AdaptiveConcurrency>,
+    /// Source of per-session feature flags.
+    pub feature_flags: Option<Arc<dyn FeatureFlagProvider>>,
+    /// Migrations run on every checkpoint a runner loads.
+    pub state_migrations: Option<Arc<StateMigrations>>,
+    /// Persist the redacted snapshot each superstep's nodes received.
+    pub capture_node_inputs: bool,
+    /// Persist the LLM calls nodes report to their context.
+    pub capture_llm_audit: bool,
+    /// Hooks run between supersteps, in registration order.
+    pub superstep_hooks: Vec<RegisteredHook>,
+    /// Caps on the size of session state.
+    pub state_limits: Option<StateLimits>,
 }
 
 impl std::fmt::Debug for RuntimeConfig {
@@ -29,6 +59,23 @@ impl std::fmt::Debug for RuntimeConfig {
             .field("sqlite_db_name", &self.sqlite_db_name)
             .field("event_bus", &self.event_bus)
             .field("clock", &self.clock.is_some())
+            .field("redaction", &self.redaction.is_some())
+            .field("values", &self.values)
+            .field("rng_seed", &self.rng_seed)
+            .field("checkpoint_failure_policy", &self.checkpoint_failure_policy)
+            .field("adaptive_concurrency", &self.adaptive_concurrency)
+            .field(
+                "feature_flags",
+                &self
+                    .feature_flags
+                    .as_ref()
+                    .map(|provider| provider.describe()),
+            )
+            .field("state_migrations", &self.state_migrations)
+            .field("capture_node_inputs", &self.capture_node_inputs)
+            .field("capture_llm_audit", &self.capture_llm_audit)
+            .field("superstep_hooks", &self.superstep_hooks.len())
+            .field("state_limits", &self.state_limits)
             .finish()
     }
 }
@@ -41,6 +88,17 @@ impl Default for RuntimeConfig {
             sqlite_db_name: Self::resolve_sqlite_db_name(None),
             event_bus: EventBusConfig::default(),
             clock: None,
+            redaction: None,
+            values: BTreeMap::new(),
+            rng_seed: None,
+            checkpoint_failure_policy: CheckpointFailurePolicy::default(),
+            adaptive_concurrency: None,
+            feature_flags: None,
+            state_migrations: None,
+            capture_node_inputs: false,
+            capture_llm_audit: false,
+            superstep_hooks: Vec::new(),
+            state_limits: None,
         }
     }
 }
@@ -62,6 +120,17 @@ impl RuntimeConfig {
             sqlite_db_name: Self::resolve_sqlite_db_name(sqlite_db_name),
             event_bus: EventBusConfig::default(),
             clock: None,
+            redaction: None,
+            values: BTreeMap::new(),
+            rng_seed: None,
+            checkpoint_failure_policy: CheckpointFailurePolicy::default(),
+            adaptive_concurrency: None,
+            feature_flags: None,
+            state_migrations: None,
+            capture_node_inputs: false,
+            capture_llm_audit: false,
+            superstep_hooks: Vec::new(),
+            state_limits: None,
         }
     }
 
@@ -91,6 +160,305 @@ impl RuntimeConfig {
         self.clock.clone()
     }
 
+    #[must_use]
+    /// Redact sensitive data before events reach sinks and before state is checkpointed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::redaction::RedactionPolicy;
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default()
+    ///     .with_redaction(RedactionPolicy::new().redact_key("api_key").redact_path("extra.user.ssn"));
+    /// assert!(config.redaction().is_some());
+    /// ```
+    pub fn with_redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.redaction = Some(Arc::new(policy));
+        self
+    }
+
+    #[must_use]
+    /// Return the configured redaction policy, if any.
+    pub fn redaction(&self) -> Option<Arc<RedactionPolicy>> {
+        self.redaction.clone()
+    }
+
+    #[must_use]
+    /// Set a configuration value readable from [`RoutingContext::config_value`](crate::graphs::RoutingContext::config_value).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default().with_value("max_rounds", 5);
+    /// assert_eq!(config.value("max_rounds"), Some(&serde_json::json!(5)));
+    /// ```
+    pub fn with_value(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.values.insert(key.into(), value.into());
+        self
+    }
+
+    #[must_use]
+    /// Return a configuration value set with [`with_value`](Self::with_value).
+    pub fn value(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    #[must_use]
+    /// Seed [`NodeContext::rng`](crate::node::NodeContext::rng) from `seed` instead of the session id.
+    ///
+    /// Node generators are still distinct per step and node, but identical
+    /// across sessions that share the seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default().with_rng_seed(42);
+    /// assert_eq!(config.rng_seed(), Some(42));
+    /// ```
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    #[must_use]
+    /// Return the configured RNG base seed, if any.
+    pub fn rng_seed(&self) -> Option<u64> {
+        self.rng_seed
+    }
+
+    #[must_use]
+    /// Choose how a runner reacts when a superstep's checkpoint cannot be saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use weavegraph::runtimes::{CheckpointFailurePolicy, RuntimeConfig};
+    ///
+    /// let config = RuntimeConfig::default().with_checkpoint_failure_policy(
+    ///     CheckpointFailurePolicy::Retry {
+    ///         max_attempts: 3,
+    ///         backoff: Duration::from_millis(100),
+    ///     },
+    /// );
+    /// assert!(config.checkpoint_failure_policy().is_transactional());
+    /// ```
+    pub fn with_checkpoint_failure_policy(mut self, policy: CheckpointFailurePolicy) -> Self {
+        self.checkpoint_failure_policy = policy;
+        self
+    }
+
+    #[must_use]
+    /// Return the configured checkpoint failure policy.
+    pub fn checkpoint_failure_policy(&self) -> CheckpointFailurePolicy {
+        self.checkpoint_failure_policy
+    }
+
+    #[must_use]
+    /// Let each session raise or lower its superstep concurrency limit from observed node latency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use weavegraph::runtimes::RuntimeConfig;
+    /// use weavegraph::schedulers::AdaptiveConcurrency;
+    ///
+    /// let config = RuntimeConfig::default().with_adaptive_concurrency(AdaptiveConcurrency::new(
+    ///     1,
+    ///     8,
+    ///     Duration::from_millis(500),
+    /// ));
+    /// assert_eq!(config.adaptive_concurrency().map(|a| a.max()), Some(8));
+    /// ```
+    pub fn with_adaptive_concurrency(mut self, adaptive: AdaptiveConcurrency) -> Self {
+        self.adaptive_concurrency = Some(adaptive);
+        self
+    }
+
+    #[must_use]
+    /// Resolve per-session feature flags from `provider`.
+    ///
+    /// Runners ask the provider once when a session is created or resumed;
+    /// nodes read the result through
+    /// [`NodeContext::feature_flags`](crate::node::NodeContext::feature_flags)
+    /// and conditional edges through
+    /// [`RoutingContext::feature_flags`](crate::graphs::RoutingContext::feature_flags).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::feature_flags::EnvFlags;
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default().with_feature_flags(EnvFlags::new());
+    /// assert!(config.feature_flags().is_some());
+    /// ```
+    pub fn with_feature_flags(mut self, provider: impl FeatureFlagProvider + 'static) -> Self {
+        self.feature_flags = Some(Arc::new(provider));
+        self
+    }
+
+    #[must_use]
+    /// Upgrade checkpoints written under older state schemas when they are loaded.
+    ///
+    /// Fresh sessions are stamped with the registry's current version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::{RuntimeConfig, StateMigrations};
+    ///
+    /// let config = RuntimeConfig::default().with_state_migrations(
+    ///     StateMigrations::new().migration(0, "add defaults", |_state| Ok(())),
+    /// );
+    /// assert_eq!(config.state_migrations().map(|m| m.current_version()), Some(1));
+    /// ```
+    pub fn with_state_migrations(mut self, migrations: StateMigrations) -> Self {
+        self.state_migrations = Some(Arc::new(migrations));
+        self
+    }
+
+    #[must_use]
+    /// Persist the state snapshot each superstep's nodes received.
+    ///
+    /// Off by default: snapshots can be large and may contain personal data.
+    /// When enabled, runners pass each superstep's input through the
+    /// redaction policy and store it with
+    /// [`Checkpointer::save_node_inputs`](crate::runtimes::Checkpointer::save_node_inputs);
+    /// read it back with
+    /// [`Checkpointer::load_node_inputs`](crate::runtimes::Checkpointer::load_node_inputs).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default().with_node_input_capture(true);
+    /// assert!(config.node_input_capture());
+    /// ```
+    pub fn with_node_input_capture(mut self, enabled: bool) -> Self {
+        self.capture_node_inputs = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Whether node input snapshots are persisted.
+    pub fn node_input_capture(&self) -> bool {
+        self.capture_node_inputs
+    }
+
+    #[must_use]
+    /// Persist an audit record of every LLM call nodes report.
+    ///
+    /// Off by default: prompts and responses can be large and may contain
+    /// personal data. When enabled, calls reported through
+    /// [`NodeContext::record_llm_call`](crate::node::NodeContext::record_llm_call)
+    /// pass through the redaction policy and are stored with
+    /// [`Checkpointer::save_llm_audit`](crate::runtimes::Checkpointer::save_llm_audit)
+    /// after each superstep; read them back with
+    /// [`Checkpointer::load_llm_audit`](crate::runtimes::Checkpointer::load_llm_audit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::default().with_llm_audit(true);
+    /// assert!(config.llm_audit());
+    /// ```
+    pub fn with_llm_audit(mut self, enabled: bool) -> Self {
+        self.capture_llm_audit = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Whether LLM calls are persisted to the audit log.
+    pub fn llm_audit(&self) -> bool {
+        self.capture_llm_audit
+    }
+
+    #[must_use]
+    /// Run `hook` after every superstep with the default
+    /// [`SuperstepHookOptions`].
+    ///
+    /// See [`hooks`](crate::runtimes::hooks) for how calls are paced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::RuntimeConfig;
+    /// use weavegraph::runtimes::hooks::SuperstepUpdate;
+    ///
+    /// let config = RuntimeConfig::default().on_superstep(|update: SuperstepUpdate| async move {
+    ///     println!("step {} done", update.report.step);
+    /// });
+    /// assert_eq!(config.superstep_hooks().len(), 1);
+    /// ```
+    pub fn on_superstep(self, hook: impl SuperstepHook) -> Self {
+        self.on_superstep_with(hook, SuperstepHookOptions::default())
+    }
+
+    #[must_use]
+    /// Run `hook` after every superstep, paced by `options`.
+    pub fn on_superstep_with(
+        mut self,
+        hook: impl SuperstepHook,
+        options: SuperstepHookOptions,
+    ) -> Self {
+        self.superstep_hooks.push(RegisteredHook {
+            hook: Arc::new(hook),
+            options,
+        });
+        self
+    }
+
+    #[must_use]
+    /// Return the registered superstep hooks.
+    pub fn superstep_hooks(&self) -> &[RegisteredHook] {
+        &self.superstep_hooks
+    }
+
+    #[must_use]
+    /// Cap the size of session state, applying `limits`' policy when a
+    /// superstep exceeds them.
+    ///
+    /// See [`state_limits`](crate::runtimes::state_limits) for how breaches
+    /// are detected and attributed.
+    pub fn with_state_limits(mut self, limits: StateLimits) -> Self {
+        self.state_limits = Some(limits);
+        self
+    }
+
+    #[must_use]
+    /// Return the state size limits, if any.
+    pub fn state_limits(&self) -> Option<StateLimits> {
+        self.state_limits
+    }
+
+    #[must_use]
+    /// Return the state migration registry, if any.
+    pub fn state_migrations(&self) -> Option<Arc<StateMigrations>> {
+        self.state_migrations.clone()
+    }
+
+    #[must_use]
+    /// Return the feature flag provider, if any.
+    pub fn feature_flags(&self) -> Option<Arc<dyn FeatureFlagProvider>> {
+        self.feature_flags.clone()
+    }
+
+    #[must_use]
+    /// Return the adaptive concurrency settings, if enabled.
+    pub fn adaptive_concurrency(&self) -> Option<AdaptiveConcurrency> {
+        self.adaptive_concurrency
+    }
+
     #[must_use]
     /// Return a descriptor for the configured clock mode.
     pub fn clock_mode(&self) -> &'static str {
@@ -119,6 +487,46 @@ impl RuntimeConfig {
         ));
         parts.push(format!("clock:{}", self.clock_mode()));
         parts.extend(self.event_bus.metadata_signature());
+        parts.extend(
+            self.values
+                .iter()
+                .map(|(key, value)| format!("value:{key}={value}")),
+        );
+        if let Some(seed) = self.rng_seed {
+            parts.push(format!("rng_seed:{seed}"));
+        }
+        if self.checkpoint_failure_policy != CheckpointFailurePolicy::Continue {
+            parts.push(format!(
+                "checkpoint_failure:{:?}",
+                self.checkpoint_failure_policy
+            ));
+        }
+        if let Some(provider) = &self.feature_flags {
+            parts.push(format!("feature_flags:{}", provider.describe()));
+        }
+        if let Some(adaptive) = &self.adaptive_concurrency {
+            parts.push(format!("adaptive_concurrency:{adaptive:?}"));
+        }
+        if self.capture_node_inputs {
+            parts.push("capture_node_inputs".to_string());
+        }
+        if self.capture_llm_audit {
+            parts.push("capture_llm_audit".to_string());
+        }
+        if let Some(limits) = &self.state_limits {
+            parts.push(format!("state_limits:{}", limits.signature()));
+        }
+        if let Some(migrations) = &self.state_migrations {
+            parts.push(format!("state_schema:{}", migrations.current_version()));
+        }
+        if let Some(policy) = &self.redaction {
+            parts.extend(
+                policy
+                    .signature()
+                    .into_iter()
+                    .map(|p| format!("redaction:{p}")),
+            );
+        }
         hash_parts(&parts)
     }
 
@@ -142,6 +550,50 @@ impl RuntimeConfig {
     }
 }
 
+// ============================================================================
+// Checkpoint failure handling
+// ============================================================================
+
+/// What an [`AppRunner`](crate::runtimes::AppRunner) does when saving the
+/// checkpoint of a completed superstep fails.
+///
+/// With a persistent checkpointer and autosave on, a runner stages each
+/// superstep's new state and saves it before committing it to the in-memory
+/// session. Under the transactional policies a save that keeps failing rolls
+/// the session back to its last committed step and fails the step with
+/// [`RunnerError::CheckpointRollback`](crate::runtimes::runner::RunnerError::CheckpointRollback),
+/// so memory and storage never diverge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CheckpointFailurePolicy {
+    /// Commit the step anyway and log the failure (best-effort persistence).
+    #[default]
+    Continue,
+    /// Retry the save, waiting `backoff` between attempts, then roll back.
+    Retry {
+        /// Total save attempts, including the first (at least 1).
+        max_attempts: u32,
+        /// Delay between attempts.
+        backoff: Duration,
+    },
+    /// Roll back and execute the superstep again from the committed state,
+    /// up to `max_reruns` times, then fail.
+    ///
+    /// Nodes run again on every rerun, so their side effects repeat.
+    RollbackAndRerun {
+        /// Superstep re-executions after the first failed save.
+        max_reruns: u32,
+    },
+}
+
+impl CheckpointFailurePolicy {
+    /// Whether a failed save rolls the step back instead of committing it.
+    #[must_use]
+    pub fn is_transactional(&self) -> bool {
+        !matches!(self, Self::Continue)
+    }
+}
+
 fn hash_parts(parts: &[String]) -> String {
     const FNV_OFFSET: u64 = 0xcbf29ce484222325;
     const FNV_PRIME: u64 = 0x100000001b3;
@@ -173,6 +625,10 @@ pub struct EventBusConfig {
     /// Ordered list of sink targets that will receive events.
     pub sinks: Vec<SinkConfig>,
     diagnostics: DiagnosticsConfig,
+    sink_disable_threshold: Option<u64>,
+    sequence_numbers: bool,
+    strict_ordering: bool,
+    priority_lanes: bool,
 }
 
 impl EventBusConfig {
@@ -190,6 +646,10 @@ impl EventBusConfig {
             },
             sinks,
             diagnostics: DiagnosticsConfig::default_with_capacity(buffer_capacity),
+            sink_disable_threshold: None,
+            sequence_numbers: false,
+            strict_ordering: false,
+            priority_lanes: false,
         }
     }
 
@@ -236,6 +696,17 @@ impl EventBusConfig {
                 .map(|(index, sink)| format!("event_sink:{index}:{sink:?}")),
         );
         parts.extend(self.diagnostics.metadata_signature());
+        if let Some(threshold) = self.sink_disable_threshold {
+            parts.push(format!("sink_disable_threshold:{threshold}"));
+        }
+        if self.strict_ordering {
+            parts.push("event_ordering:strict".to_string());
+        } else if self.sequence_numbers {
+            parts.push("event_ordering:sequenced".to_string());
+        }
+        if self.priority_lanes {
+            parts.push("event_lanes:priority".to_string());
+        }
         parts
     }
 
@@ -246,6 +717,57 @@ impl EventBusConfig {
         self
     }
 
+    #[must_use]
+    /// Disable sinks after `threshold` consecutive failures.
+    ///
+    /// See [`EventBus::with_sink_disable_threshold`].
+    pub fn with_sink_disable_threshold(mut self, threshold: u64) -> Self {
+        self.sink_disable_threshold = Some(threshold.max(1));
+        self
+    }
+
+    /// Returns the configured sink auto-disable threshold, if any.
+    pub fn sink_disable_threshold(&self) -> Option<u64> {
+        self.sink_disable_threshold
+    }
+
+    #[must_use]
+    /// Stamp events with per-session sequence numbers.
+    ///
+    /// See [`EventBus::with_sequence_numbers`].
+    pub fn with_sequence_numbers(mut self) -> Self {
+        self.sequence_numbers = true;
+        self
+    }
+
+    #[must_use]
+    /// Deliver events to sinks in per-session sequence order.
+    ///
+    /// See [`EventBus::with_strict_ordering`].
+    pub fn with_strict_ordering(mut self) -> Self {
+        self.strict_ordering = true;
+        self
+    }
+
+    /// Returns whether sinks receive events in per-session sequence order.
+    pub fn strict_ordering(&self) -> bool {
+        self.strict_ordering
+    }
+
+    #[must_use]
+    /// Deliver high-priority events to sinks ahead of regular traffic.
+    ///
+    /// See [`EventBus::with_priority_lanes`].
+    pub fn with_priority_lanes(mut self) -> Self {
+        self.priority_lanes = true;
+        self
+    }
+
+    /// Returns whether sinks receive high-priority events first.
+    pub fn priority_lanes(&self) -> bool {
+        self.priority_lanes
+    }
+
     #[must_use]
     /// Build and return the configured [`EventBus`].
     pub fn build_event_bus(&self) -> EventBus {
@@ -263,13 +785,29 @@ impl EventBusConfig {
         if sinks.is_empty() {
             sinks.push(Box::new(StdOutSink::default()));
         }
-        EventBus::with_capacity_and_diag(
+        let bus = EventBus::with_capacity_and_diag(
             sinks,
             self.buffer_capacity(),
             self.diagnostics.effective_capacity(self.buffer_capacity()),
             self.diagnostics.enabled,
             self.diagnostics.emit_to_events,
-        )
+        );
+        let bus = match self.sink_disable_threshold {
+            Some(threshold) => bus.with_sink_disable_threshold(threshold),
+            None => bus,
+        };
+        let bus = if self.priority_lanes {
+            bus.with_priority_lanes()
+        } else {
+            bus
+        };
+        if self.strict_ordering {
+            bus.with_strict_ordering()
+        } else if self.sequence_numbers {
+            bus.with_sequence_numbers()
+        } else {
+            bus
+        }
     }
 }
 
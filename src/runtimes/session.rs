@@ -3,6 +3,9 @@
 //! This module defines the core types for managing session state during workflow
 //! execution, including state persistence across steps and session initialization.
 
+use super::execution::{StepOptions, StepResult};
+use super::runner::{AppRunner, RunnerError};
+use super::types::SessionId;
 use crate::schedulers::{Scheduler, SchedulerState};
 use crate::state::VersionedState;
 use crate::types::NodeKind;
@@ -71,3 +74,97 @@ pub struct StateVersions {
     /// Version counter for the extra data channel.
     pub extra_version: u32,
 }
+
+// ============================================================================
+// Handles
+// ============================================================================
+
+/// A session of an [`AppRunner`], addressed by a typed [`SessionId`].
+///
+/// Returned by [`AppRunner::open_session`] and [`AppRunner::session`]. The
+/// handle borrows the runner mutably, so the id it wraps is always one the
+/// runner knows about and no string can be mistyped between calls. The
+/// string-keyed runner methods remain available and behave identically.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use weavegraph::runtimes::{AppRunner, StepOptions, StepResult};
+/// use weavegraph::state::VersionedState;
+/// # async fn example(app: weavegraph::app::App) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut runner = AppRunner::builder().app(app).build().await;
+///
+/// let mut session = runner
+///     .open_session("support-42", VersionedState::new_with_user_message("hi"))
+///     .await?;
+/// if let StepResult::Completed(report) = session.step(StepOptions::default()).await? {
+///     println!("ran {:?}", report.ran_nodes);
+/// }
+/// let id = session.pause().await?;
+///
+/// // Later, on the same runner:
+/// let final_state = runner.session(&id)?.run().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SessionHandle<'r> {
+    runner: &'r mut AppRunner,
+    id: SessionId,
+    init: Option<SessionInit>,
+}
+
+impl<'r> SessionHandle<'r> {
+    pub(crate) fn new(runner: &'r mut AppRunner, id: SessionId, init: Option<SessionInit>) -> Self {
+        Self { runner, id, init }
+    }
+
+    /// The session's id.
+    #[must_use]
+    pub fn id(&self) -> &SessionId {
+        &self.id
+    }
+
+    /// How the session was initialized, for handles from
+    /// [`AppRunner::open_session`].
+    #[must_use]
+    pub fn init(&self) -> Option<&SessionInit> {
+        self.init.as_ref()
+    }
+
+    /// Execute one superstep; see [`AppRunner::run_step`].
+    pub async fn step(&mut self, options: StepOptions) -> Result<StepResult, RunnerError> {
+        self.runner.run_step(self.id.as_str(), options).await
+    }
+
+    /// Run until the workflow completes; see [`AppRunner::run_until_complete`].
+    pub async fn run(&mut self) -> Result<VersionedState, RunnerError> {
+        self.runner.run_until_complete(self.id.as_str()).await
+    }
+
+    /// The session's current state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RunnerError::SessionNotFound`] if the session was removed,
+    /// e.g. by [`AppRunner::finish_iterative_session`].
+    pub fn state(&self) -> Result<&VersionedState, RunnerError> {
+        self.runner
+            .get_session(self.id.as_str())
+            .map(|session| &session.state)
+            .ok_or_else(|| RunnerError::SessionNotFound {
+                session_id: self.id.to_string(),
+            })
+    }
+
+    /// Stop driving the session, making sure it can be resumed later.
+    ///
+    /// With autosave every completed step is already checkpointed; otherwise
+    /// the current state is saved to the configured checkpointer now. The
+    /// session stays loaded in the runner. Returns the id to reopen it with
+    /// [`AppRunner::session`], or with [`AppRunner::open_session`] on a runner
+    /// sharing the checkpointer.
+    pub async fn pause(self) -> Result<SessionId, RunnerError> {
+        self.runner.checkpoint_session(self.id.as_str()).await?;
+        Ok(self.id)
+    }
+}
@@ -0,0 +1,357 @@
+//! Simulation runs: execute a graph with its side-effecting nodes stubbed out.
+//!
+//! Nodes registered with
+//! [`GraphBuilder::add_side_effect_node`](crate::graphs::GraphBuilder::add_side_effect_node)
+//! send email, charge cards, or write to other systems. [`SimulationRunner`]
+//! replaces each of them with a stub, runs every other node for real against
+//! an in-memory checkpointer, and returns a [`SimulationReport`] listing the
+//! external actions the run would have taken. Use it to check a graph change
+//! before rolling it out.
+//!
+//! A side-effecting node without a stub returns an empty [`NodePartial`].
+//! Supply a stub node with [`SimulationRunner::stub`] when the stub needs to
+//! look at its input, or a recorded output with
+//! [`SimulationRunner::stub_output`].
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::graphs::GraphBuilder;
+//! use weavegraph::node::NodePartial;
+//! use weavegraph::runtimes::SimulationRunner;
+//! use weavegraph::state::VersionedState;
+//! use weavegraph::types::NodeKind;
+//!
+//! # struct Draft;
+//! # #[async_trait::async_trait]
+//! # impl weavegraph::node::Node for Draft {
+//! #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<NodePartial, weavegraph::node::NodeError> {
+//! #         Ok(NodePartial::default())
+//! #     }
+//! # }
+//! # struct SendEmail;
+//! # #[async_trait::async_trait]
+//! # impl weavegraph::node::Node for SendEmail {
+//! #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<NodePartial, weavegraph::node::NodeError> {
+//! #         unreachable!("never runs in a simulation")
+//! #     }
+//! # }
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let draft = NodeKind::Custom("draft".into());
+//! let send = NodeKind::Custom("send".into());
+//! let app = GraphBuilder::new()
+//!     .add_node(draft.clone(), Draft)
+//!     .add_side_effect_node(send.clone(), SendEmail)
+//!     .add_edge(NodeKind::Start, draft.clone())
+//!     .add_edge(draft.clone(), send.clone())
+//!     .add_edge(send.clone(), NodeKind::End)
+//!     .compile()
+//!     .unwrap();
+//!
+//! let report = SimulationRunner::new(app)
+//!     .run(VersionedState::new_with_user_message("hi"))
+//!     .await
+//!     .unwrap();
+//! assert_eq!(report.executed_nodes, vec![draft]);
+//! assert!(report.would_run(&send));
+//! # }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use crate::app::App;
+use crate::event_bus::EventBus;
+use crate::graphs::{GraphBuilder, GraphCompileError};
+use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::runtimes::runner::RunnerError;
+use crate::runtimes::{AppRunner, CheckpointerType, StepOptions, StepResult};
+use crate::state::{StateSnapshot, VersionedState};
+use crate::types::NodeKind;
+
+/// Session id used for the single session a simulation runs.
+const SIMULATION_SESSION_ID: &str = "simulation";
+
+/// How a side-effecting node produced its output in a simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StubKind {
+    /// No stub was supplied; the node returned an empty partial.
+    Empty,
+    /// A recorded output from [`SimulationRunner::stub_output`].
+    Recorded,
+    /// A stub node from [`SimulationRunner::stub`].
+    Node,
+}
+
+/// One invocation of a side-effecting node during a simulation.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SimulatedAction {
+    /// The stubbed node.
+    pub node: NodeKind,
+    /// Superstep the node would have run in.
+    pub step: u64,
+    /// Snapshot the real node would have acted on.
+    pub input: StateSnapshot,
+    /// Output the stub returned in place of the real node.
+    pub output: NodePartial,
+    /// Where the output came from.
+    pub stub: StubKind,
+}
+
+/// Outcome of [`SimulationRunner::run`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SimulationReport {
+    /// State at the end of the simulated run.
+    pub final_state: VersionedState,
+    /// Side-effecting node invocations, ordered by step then node.
+    pub actions: Vec<SimulatedAction>,
+    /// Nodes that ran for real, in execution order; repeated for each run.
+    pub executed_nodes: Vec<NodeKind>,
+    /// Number of supersteps executed.
+    pub steps: u64,
+}
+
+impl SimulationReport {
+    /// Actions recorded for `node`, in step order.
+    pub fn actions_for<'a>(
+        &'a self,
+        node: &'a NodeKind,
+    ) -> impl Iterator<Item = &'a SimulatedAction> + 'a {
+        self.actions
+            .iter()
+            .filter(move |action| &action.node == node)
+    }
+
+    /// Whether the run would have invoked the side-effecting `node`.
+    #[must_use]
+    pub fn would_run(&self, node: &NodeKind) -> bool {
+        self.actions_for(node).next().is_some()
+    }
+}
+
+/// Errors returned by [`SimulationRunner::run`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum SimulationError {
+    /// A stub was supplied for a node not registered as side-effecting.
+    #[error("cannot stub {0}: it is not a side-effecting node")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::simulation::not_side_effecting),
+            help("Register the node with `GraphBuilder::add_side_effect_node`.")
+        )
+    )]
+    NotSideEffecting(NodeKind),
+
+    /// The graph with stubs in place failed validation.
+    #[error("simulated graph is invalid: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::simulation::invalid))
+    )]
+    Invalid(#[from] GraphCompileError),
+
+    /// The simulated run failed.
+    #[error("simulated run failed: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::simulation::runner))
+    )]
+    Runner(#[from] RunnerError),
+}
+
+#[derive(Clone)]
+enum NodeStub {
+    Recorded(NodePartial),
+    Node(Arc<dyn Node>),
+}
+
+/// Runs a graph with its side-effecting nodes replaced by stubs.
+///
+/// See the [module documentation](self) for an example.
+#[must_use]
+pub struct SimulationRunner {
+    app: App,
+    stubs: FxHashMap<NodeKind, NodeStub>,
+    event_bus: Option<EventBus>,
+}
+
+impl SimulationRunner {
+    /// Simulate `app`; side-effecting nodes return empty partials unless stubbed.
+    pub fn new(app: App) -> Self {
+        Self {
+            app,
+            stubs: FxHashMap::default(),
+            event_bus: None,
+        }
+    }
+
+    /// Run `stub` in place of the side-effecting `node`.
+    pub fn stub(mut self, node: NodeKind, stub: impl Node + 'static) -> Self {
+        self.stubs.insert(node, NodeStub::Node(Arc::new(stub)));
+        self
+    }
+
+    /// Return `output` every time the side-effecting `node` would run.
+    pub fn stub_output(mut self, node: NodeKind, output: NodePartial) -> Self {
+        self.stubs.insert(node, NodeStub::Recorded(output));
+        self
+    }
+
+    /// Publish the simulation's events to `bus` instead of one built from the
+    /// app's [`EventBusConfig`](crate::runtimes::EventBusConfig).
+    pub fn event_bus(mut self, bus: EventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Run the simulation from `initial_state` to completion.
+    ///
+    /// The run uses an in-memory checkpointer without autosave, so nothing
+    /// is written to the app's configured checkpointer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SimulationError::NotSideEffecting`] if a stub names a node
+    /// not registered as side-effecting, and [`SimulationError::Runner`] if a
+    /// node or stub fails.
+    pub async fn run(
+        self,
+        initial_state: VersionedState,
+    ) -> Result<SimulationReport, SimulationError> {
+        let mut stubbed: Vec<&NodeKind> = self.stubs.keys().collect();
+        stubbed.sort_by_key(|node| node.encode());
+        if let Some(node) = stubbed
+            .into_iter()
+            .find(|node| !self.app.is_side_effecting(node))
+        {
+            return Err(SimulationError::NotSideEffecting(node.clone()));
+        }
+
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let mut builder = GraphBuilder::from_app(&self.app);
+        for node in self.app.side_effecting_nodes() {
+            let stub = SimulatedNode {
+                kind: node.clone(),
+                stub: self.stubs.get(node).cloned(),
+                actions: Arc::clone(&actions),
+            };
+            builder = builder.add_shared_node(node.clone(), Arc::new(stub));
+        }
+        let simulated = builder.compile()?;
+
+        let event_bus = self
+            .event_bus
+            .unwrap_or_else(|| self.app.runtime_config().event_bus.build_event_bus());
+        let mut runner = AppRunner::builder()
+            .app(simulated)
+            .checkpointer(CheckpointerType::InMemory)
+            .autosave(false)
+            .event_bus(event_bus)
+            .start_listener(true)
+            .build()
+            .await;
+        runner
+            .create_session(SIMULATION_SESSION_ID.to_string(), initial_state)
+            .await?;
+
+        let mut executed_nodes = Vec::new();
+        loop {
+            match runner
+                .run_step(SIMULATION_SESSION_ID, StepOptions::default())
+                .await?
+            {
+                StepResult::Completed(report) => {
+                    executed_nodes.extend(
+                        report
+                            .ran_nodes
+                            .into_iter()
+                            .filter(|node| !self.app.is_side_effecting(node)),
+                    );
+                    if report.completed {
+                        break;
+                    }
+                }
+                StepResult::Paused(_) => return Err(RunnerError::UnexpectedPause.into()),
+            }
+        }
+
+        let session = runner.get_session(SIMULATION_SESSION_ID).ok_or_else(|| {
+            RunnerError::SessionNotFound {
+                session_id: SIMULATION_SESSION_ID.to_string(),
+            }
+        })?;
+        let final_state = session.state.clone();
+        let steps = session.step;
+        runner.shutdown().await;
+
+        let mut actions = std::mem::take(&mut *actions.lock().unwrap_or_else(|e| e.into_inner()));
+        actions.sort_by(|a: &SimulatedAction, b| {
+            (a.step, a.node.encode()).cmp(&(b.step, b.node.encode()))
+        });
+        Ok(SimulationReport {
+            final_state,
+            actions,
+            executed_nodes,
+            steps,
+        })
+    }
+}
+
+impl std::fmt::Debug for SimulationRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut stubbed: Vec<String> = self.stubs.keys().map(NodeKind::encode).collect();
+        stubbed.sort();
+        f.debug_struct("SimulationRunner")
+            .field("side_effecting", &self.app.side_effecting_nodes().len())
+            .field("stubbed", &stubbed)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Stand-in for a side-effecting node that records each invocation.
+struct SimulatedNode {
+    kind: NodeKind,
+    stub: Option<NodeStub>,
+    actions: Arc<Mutex<Vec<SimulatedAction>>>,
+}
+
+#[async_trait]
+impl Node for SimulatedNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let step = ctx.step;
+        let (output, stub) = match &self.stub {
+            None => (NodePartial::default(), StubKind::Empty),
+            Some(NodeStub::Recorded(output)) => (output.clone(), StubKind::Recorded),
+            Some(NodeStub::Node(node)) => (node.run(snapshot.clone(), ctx).await?, StubKind::Node),
+        };
+        self.actions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(SimulatedAction {
+                node: self.kind.clone(),
+                step,
+                input: snapshot,
+                output: output.clone(),
+                stub,
+            });
+        Ok(output)
+    }
+
+    fn definition_label(&self) -> &'static str {
+        "simulation_stub"
+    }
+}
@@ -0,0 +1,431 @@
+//! Guardrails on the size of session state.
+//!
+//! Every checkpoint carries the full state, so a single runaway node that
+//! appends thousands of messages or dumps large values into `extra` can grow
+//! checkpoints until the store falls over. [`StateLimits`] caps the serialized
+//! size, the number of messages, and the number of `extra` keys of a session.
+//!
+//! The runner measures the state after each superstep's barrier. When a limit
+//! is exceeded it publishes a [`StateLimitBreach`] event per limit, logs a
+//! warning, records the breaches in
+//! [`StepReport::state_limit_breaches`](crate::runtimes::StepReport::state_limit_breaches),
+//! and applies the configured [`StateLimitPolicy`]. The offending node is the
+//! node that ran in that superstep whose output was largest.
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::runtimes::{CompactionPolicy, RuntimeConfig, StateLimitPolicy, StateLimits};
+//!
+//! let config = RuntimeConfig::default().with_state_limits(
+//!     StateLimits::new()
+//!         .max_bytes(4 * 1024 * 1024)
+//!         .max_messages(500)
+//!         .on_exceeded(StateLimitPolicy::Compact(
+//!             CompactionPolicy::new().keep_last_messages(100),
+//!         )),
+//! );
+//! assert_eq!(config.state_limits().unwrap().limit_messages(), Some(500));
+//! ```
+
+use std::io;
+
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::channels::Channel;
+use crate::event_bus::{Event, NodeEvent, STATE_LIMIT_SCOPE};
+use crate::node::NodePartial;
+use crate::runtimes::compaction::CompactionPolicy;
+use crate::state::{StateSnapshot, VersionedState};
+use crate::types::NodeKind;
+
+// ============================================================================
+// Policy
+// ============================================================================
+
+/// What the runner does when a superstep leaves the state over its limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StateLimitPolicy {
+    /// Commit the step, then stop with
+    /// [`PausedReason::StateLimitExceeded`](crate::runtimes::PausedReason::StateLimitExceeded)
+    /// from `run_step`, or
+    /// [`RunnerError::StateLimitExceeded`](crate::runtimes::runner::RunnerError::StateLimitExceeded)
+    /// from `run_until_complete`. The session can be compacted or edited and
+    /// resumed; it pauses again after every step that ends over the limits.
+    #[default]
+    Pause,
+    /// Commit the step, then archive old messages and errors with
+    /// [`AppRunner::compact_session`](crate::runtimes::AppRunner::compact_session).
+    /// Needs a checkpointer; without one the failure is logged and the run continues.
+    Compact(CompactionPolicy),
+    /// Discard the output of the offending node and apply the rest of the
+    /// superstep. If the state is still over the limits, the next largest
+    /// output is discarded, and so on. Each rejection is recorded as a
+    /// warning in the errors channel. Rejected nodes still follow their static
+    /// and conditional edges; output applied earlier through
+    /// [`NodeContext::yield_partial`](crate::node::NodeContext::yield_partial)
+    /// is kept.
+    RejectOutput,
+}
+
+impl StateLimitPolicy {
+    /// Stable label used in diagnostics (`pause`, `compact`, `reject_output`).
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Compact(_) => "compact",
+            Self::RejectOutput => "reject_output",
+        }
+    }
+}
+
+/// A limit enforced by [`StateLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StateLimit {
+    /// Serialized JSON size of messages, `extra`, errors, and blobs.
+    Bytes,
+    /// Number of messages.
+    Messages,
+    /// Number of keys in `extra`.
+    ExtraKeys,
+}
+
+impl StateLimit {
+    /// Stable label (`bytes`, `messages`, `extra_keys`).
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bytes => "bytes",
+            Self::Messages => "messages",
+            Self::ExtraKeys => "extra_keys",
+        }
+    }
+
+    /// Parse a label produced by [`label`](Self::label).
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "bytes" => Some(Self::Bytes),
+            "messages" => Some(Self::Messages),
+            "extra_keys" => Some(Self::ExtraKeys),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Size
+// ============================================================================
+
+/// Measured size of a session's state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StateSize {
+    /// Serialized JSON size of messages, `extra`, errors, and blobs.
+    pub bytes: usize,
+    /// Number of messages.
+    pub messages: usize,
+    /// Number of keys in `extra`.
+    pub extra_keys: usize,
+}
+
+impl StateSize {
+    /// Measure `snapshot`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::runtimes::StateSize;
+    /// use weavegraph::state::VersionedState;
+    ///
+    /// let size = StateSize::of(&VersionedState::new_with_user_message("hi").snapshot());
+    /// assert_eq!(size.messages, 1);
+    /// assert!(size.bytes > 0);
+    /// ```
+    #[must_use]
+    pub fn of(snapshot: &StateSnapshot) -> Self {
+        Self {
+            bytes: json_len(&snapshot.messages)
+                + json_len(&snapshot.extra)
+                + json_len(&snapshot.errors)
+                + json_len(&snapshot.blobs),
+            messages: snapshot.messages.len(),
+            extra_keys: snapshot.extra.len(),
+        }
+    }
+
+    /// Measure `state` without cloning its channels.
+    pub(crate) fn of_state(state: &mut VersionedState) -> Self {
+        Self {
+            bytes: json_len(state.messages.get_mut())
+                + json_len(state.extra.get_mut())
+                + json_len(state.errors.get_mut())
+                + json_len(state.blobs.get_mut()),
+            messages: state.messages.len(),
+            extra_keys: state.extra.len(),
+        }
+    }
+
+    /// Serialized size of what `partial` writes to the state.
+    pub(crate) fn of_output(partial: &NodePartial) -> usize {
+        partial.messages.as_ref().map_or(0, json_len)
+            + partial.extra.as_ref().map_or(0, json_len)
+            + partial.errors.as_ref().map_or(0, json_len)
+            + partial.blobs.as_ref().map_or(0, json_len)
+    }
+
+    fn get(&self, limit: StateLimit) -> usize {
+        match limit {
+            StateLimit::Bytes => self.bytes,
+            StateLimit::Messages => self.messages,
+            StateLimit::ExtraKeys => self.extra_keys,
+        }
+    }
+}
+
+/// Length of `value` serialized as JSON, without buffering it.
+fn json_len<T: Serialize + ?Sized>(value: &T) -> usize {
+    struct Counter(usize);
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut counter = Counter(0);
+    // Values that cannot be serialized would fail the checkpoint, not this count.
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+// ============================================================================
+// Limits
+// ============================================================================
+
+/// Caps on the size of session state.
+///
+/// Set with
+/// [`RuntimeConfig::with_state_limits`](crate::runtimes::RuntimeConfig::with_state_limits).
+/// Limits left unset are not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[must_use]
+pub struct StateLimits {
+    max_bytes: Option<usize>,
+    max_messages: Option<usize>,
+    max_extra_keys: Option<usize>,
+    policy: StateLimitPolicy,
+}
+
+impl StateLimits {
+    /// No limits; breaches pause the session once limits are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow at most `bytes` of serialized state.
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Allow at most `count` messages.
+    pub fn max_messages(mut self, count: usize) -> Self {
+        self.max_messages = Some(count);
+        self
+    }
+
+    /// Allow at most `count` keys in `extra`.
+    pub fn max_extra_keys(mut self, count: usize) -> Self {
+        self.max_extra_keys = Some(count);
+        self
+    }
+
+    /// Apply `policy` when a limit is exceeded.
+    pub fn on_exceeded(mut self, policy: StateLimitPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Maximum serialized size, if limited.
+    #[must_use]
+    pub fn limit_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Maximum number of messages, if limited.
+    #[must_use]
+    pub fn limit_messages(&self) -> Option<usize> {
+        self.max_messages
+    }
+
+    /// Maximum number of `extra` keys, if limited.
+    #[must_use]
+    pub fn limit_extra_keys(&self) -> Option<usize> {
+        self.max_extra_keys
+    }
+
+    /// Policy applied when a limit is exceeded.
+    #[must_use]
+    pub fn policy(&self) -> StateLimitPolicy {
+        self.policy
+    }
+
+    /// Whether no limit is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.max_bytes.is_none() && self.max_messages.is_none() && self.max_extra_keys.is_none()
+    }
+
+    /// Limits `size` exceeds, with their maximums.
+    #[must_use]
+    pub fn exceeded(&self, size: &StateSize) -> Vec<(StateLimit, usize)> {
+        [
+            (StateLimit::Bytes, self.max_bytes),
+            (StateLimit::Messages, self.max_messages),
+            (StateLimit::ExtraKeys, self.max_extra_keys),
+        ]
+        .into_iter()
+        .filter_map(|(limit, max)| max.filter(|max| size.get(limit) > *max).map(|m| (limit, m)))
+        .collect()
+    }
+
+    /// One breach per limit `size` exceeds at `step`, attributed to `node`.
+    pub(crate) fn breaches(
+        &self,
+        size: &StateSize,
+        step: u64,
+        node: Option<&NodeKind>,
+    ) -> Vec<StateLimitBreach> {
+        self.exceeded(size)
+            .into_iter()
+            .map(|(limit, max)| StateLimitBreach {
+                limit,
+                node: node.cloned(),
+                step,
+                max,
+                observed: size.get(limit),
+            })
+            .collect()
+    }
+
+    /// `"<label>=<max>"` per set limit, for the runtime config hash.
+    pub(crate) fn signature(&self) -> String {
+        let mut parts: Vec<String> = [
+            (StateLimit::Bytes, self.max_bytes),
+            (StateLimit::Messages, self.max_messages),
+            (StateLimit::ExtraKeys, self.max_extra_keys),
+        ]
+        .into_iter()
+        .filter_map(|(limit, max)| max.map(|max| format!("{}={max}", limit.label())))
+        .collect();
+        parts.push(format!("policy={:?}", self.policy));
+        parts.join(",")
+    }
+}
+
+// ============================================================================
+// Breach
+// ============================================================================
+
+/// A state limit exceeded at a superstep's barrier.
+///
+/// Published as a node event with scope
+/// [`STATE_LIMIT_SCOPE`](crate::event_bus::STATE_LIMIT_SCOPE).
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::event_bus::Event;
+/// use weavegraph::runtimes::StateLimitBreach;
+///
+/// fn report(event: &Event) {
+///     if let Some(breach) = StateLimitBreach::from_event(event) {
+///         eprintln!(
+///             "step {}: {} {} > {} (node {:?})",
+///             breach.step,
+///             breach.limit.label(),
+///             breach.observed,
+///             breach.max,
+///             breach.node
+///         );
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StateLimitBreach {
+    /// The limit that was exceeded.
+    pub limit: StateLimit,
+    /// The node with the largest output in the superstep, if any node ran.
+    pub node: Option<NodeKind>,
+    /// Superstep whose barrier detected the breach.
+    pub step: u64,
+    /// Configured maximum.
+    pub max: usize,
+    /// Observed value.
+    pub observed: usize,
+}
+
+impl StateLimitBreach {
+    /// The event the runner publishes for this breach.
+    #[must_use]
+    pub fn to_event(&self) -> Event {
+        let mut metadata = FxHashMap::default();
+        metadata.insert("limit".to_string(), json!(self.limit.label()));
+        metadata.insert("max".to_string(), json!(self.max));
+        metadata.insert("observed".to_string(), json!(self.observed));
+        if let Some(node) = &self.node {
+            metadata.insert("node".to_string(), json!(node.encode()));
+        }
+        let subject = match &self.node {
+            Some(node) => format!(" node={node}"),
+            None => String::new(),
+        };
+        let message = format!(
+            "state limit exceeded limit={}{subject} observed={} max={}",
+            self.limit.label(),
+            self.observed,
+            self.max
+        );
+        Event::Node(
+            NodeEvent::new(
+                self.node.as_ref().map(|node| format!("{node:?}")),
+                Some(self.step),
+                STATE_LIMIT_SCOPE.to_string(),
+                message,
+            )
+            .with_metadata(metadata),
+        )
+    }
+
+    /// Parse a breach published by the runner, or `None` for any other event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Option<Self> {
+        let Event::Node(node_event) = event else {
+            return None;
+        };
+        if node_event.scope() != STATE_LIMIT_SCOPE {
+            return None;
+        }
+        let metadata = node_event.metadata();
+        Some(Self {
+            limit: StateLimit::from_label(metadata.get("limit")?.as_str()?)?,
+            node: metadata
+                .get("node")
+                .and_then(Value::as_str)
+                .map(NodeKind::decode),
+            step: node_event.step()?,
+            max: usize::try_from(metadata.get("max")?.as_u64()?).ok()?,
+            observed: usize::try_from(metadata.get("observed")?.as_u64()?).ok()?,
+        })
+    }
+}
@@ -0,0 +1,116 @@
+//! Step-history queries shared by the SQL checkpointers.
+//!
+//! [`SQLiteCheckpointer`](crate::runtimes::SQLiteCheckpointer) and
+//! `PostgresCheckpointer` keep every step of a session. Both implement
+//! [`StepHistory`], so the same [`StepQuery`] filters, pagination caps and
+//! optimistic-concurrency rules apply whichever backend is configured.
+
+use async_trait::async_trait;
+
+use super::checkpointer::{Checkpoint, Checkpointer, Result};
+use crate::types::NodeKind;
+
+/// Page size used when [`StepQuery::limit`] is unset.
+pub const DEFAULT_STEP_QUERY_LIMIT: u32 = 100;
+
+/// Largest page a [`StepQuery`] returns; larger limits are capped.
+pub const MAX_STEP_QUERY_LIMIT: u32 = 1000;
+
+/// Query parameters for filtering step history.
+#[derive(Debug, Clone, Default)]
+pub struct StepQuery {
+    /// Maximum number of results to return (default 100, capped at 1000)
+    pub limit: Option<u32>,
+    /// Number of results to skip (for pagination)
+    pub offset: Option<u32>,
+    /// Filter by minimum step number (inclusive)
+    pub min_step: Option<u64>,
+    /// Filter by maximum step number (inclusive)
+    pub max_step: Option<u64>,
+    /// Only return steps that executed the specified node
+    pub ran_node: Option<NodeKind>,
+    /// Only return steps that skipped the specified node
+    pub skipped_node: Option<NodeKind>,
+}
+
+impl StepQuery {
+    /// Effective `(limit, offset)` after applying defaults and the cap.
+    #[cfg(any(feature = "sqlite", feature = "postgres"))]
+    pub(crate) fn page(&self) -> (u32, u32) {
+        let limit = self
+            .limit
+            .unwrap_or(DEFAULT_STEP_QUERY_LIMIT)
+            .min(MAX_STEP_QUERY_LIMIT);
+        (limit, self.offset.unwrap_or(0))
+    }
+}
+
+/// Pagination information for query results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageInfo {
+    /// Total number of matching records
+    pub total_count: u64,
+    /// Number of records returned in this page
+    pub page_size: u32,
+    /// Zero-based offset of the first record in this page
+    pub offset: u32,
+    /// Whether there are more records after this page
+    pub has_next_page: bool,
+}
+
+impl PageInfo {
+    /// Page metadata for `page_size` records read at `offset` out of `total_count`.
+    #[cfg(any(feature = "sqlite", feature = "postgres"))]
+    pub(crate) fn new(total_count: u64, page_size: u32, offset: u32) -> Self {
+        Self {
+            total_count,
+            page_size,
+            offset,
+            has_next_page: u64::from(offset) + u64::from(page_size) < total_count,
+        }
+    }
+}
+
+/// Paginated query result for step history.
+#[derive(Debug, Clone)]
+pub struct StepQueryResult {
+    /// The matching checkpoints, newest step first
+    pub checkpoints: Vec<Checkpoint>,
+    /// Pagination metadata
+    pub page_info: PageInfo,
+}
+
+/// Checkpointers that keep and query full step history.
+///
+/// Both SQL backends follow the same contract:
+///
+/// - [`query_steps`](Self::query_steps) returns newest steps first; node
+///   filters match exact node kinds, and pages are capped at
+///   [`MAX_STEP_QUERY_LIMIT`].
+/// - [`save_with_concurrency_check`](Self::save_with_concurrency_check) saves
+///   only if the session's latest step equals `expected_last_step` (`0` for a
+///   session with no checkpoints yet). Re-saving an existing step replaces it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use weavegraph::runtimes::{Checkpoint, StepHistory, StepQuery};
+///
+/// async fn advance<C: StepHistory>(store: &C, next: Checkpoint) -> bool {
+///     let expected = next.step.saturating_sub(1);
+///     store.save_with_concurrency_check(next, Some(expected)).await.is_ok()
+/// }
+/// ```
+#[async_trait]
+pub trait StepHistory: Checkpointer {
+    /// Query a session's step history with filtering and pagination.
+    async fn query_steps(&self, session_id: &str, query: StepQuery) -> Result<StepQueryResult>;
+
+    /// Save `checkpoint` if the session's latest step equals
+    /// `expected_last_step`; `None` skips the check.
+    async fn save_with_concurrency_check(
+        &self,
+        checkpoint: Checkpoint,
+        expected_last_step: Option<u64>,
+    ) -> Result<()>;
+}
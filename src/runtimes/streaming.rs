@@ -3,6 +3,8 @@
 //! This module handles the lifecycle of event streams during workflow
 //! execution, including finalization and cleanup.
 
+use std::time::Duration;
+
 use crate::event_bus::{Event, EventBus, INVOCATION_END_SCOPE, STREAM_END_SCOPE};
 
 /// Internal reason for ending an event stream.
@@ -45,6 +47,12 @@ pub(crate) fn finalize_event_stream(
     reason: StreamEndReason,
     event_stream_taken: &mut bool,
 ) {
+    emit_stream_end(event_bus, session_id, reason);
+    close_event_stream(event_bus, event_stream_taken);
+}
+
+/// Emit the [`STREAM_END_SCOPE`] marker without closing the channel.
+pub(crate) fn emit_stream_end(event_bus: &EventBus, session_id: &str, reason: StreamEndReason) {
     let message = reason.format_message(session_id);
 
     if let Err(err) = event_bus
@@ -59,13 +67,29 @@ pub(crate) fn finalize_event_stream(
             "failed to emit stream termination event"
         );
     }
+}
 
+/// Close the event channel if a subscriber took the stream.
+pub(crate) fn close_event_stream(event_bus: &EventBus, event_stream_taken: &mut bool) {
     if *event_stream_taken {
         event_bus.close_channel();
         *event_stream_taken = false;
     }
 }
 
+/// Wait for sinks to handle every event emitted so far, logging instead of failing.
+///
+/// Completion markers are emitted before this runs, so sinks see them too.
+pub(crate) async fn drain_event_sinks(event_bus: &EventBus, session_id: &str, timeout: Duration) {
+    if let Err(err) = event_bus.flush(timeout).await {
+        tracing::warn!(
+            session = %session_id,
+            error = %err,
+            "event sinks did not drain before the run returned"
+        );
+    }
+}
+
 /// Emit a logical invocation completion marker without closing the event channel.
 pub(crate) fn emit_invocation_end(event_bus: &EventBus, session_id: &str, reason: StreamEndReason) {
     let message = reason.format_message(session_id);
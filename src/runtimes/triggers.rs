@@ -0,0 +1,441 @@
+//! Event-driven triggers that start sessions from external sources.
+//!
+//! A [`TriggerSource`] yields [`TriggerMessage`]s, for example from an async
+//! stream ([`StreamTrigger`]) or Postgres `LISTEN/NOTIFY`
+//! (`PostgresNotifyTrigger`, `postgres` feature). A [`TriggerWorker`] turns
+//! each message into an initial [`VersionedState`] with a user factory and
+//! runs it as its own session, with a bound on concurrently running sessions.
+//! Other queues, such as Redis lists, plug in by implementing [`TriggerSource`].
+//!
+//! # Examples
+//!
+//! ```
+//! use futures_util::stream;
+//! use serde_json::json;
+//! use weavegraph::runtimes::{StreamTrigger, TriggerWorker};
+//! use weavegraph::state::VersionedState;
+//! # use weavegraph::graphs::GraphBuilder;
+//! # use weavegraph::types::NodeKind;
+//! # struct Noop;
+//! # #[async_trait::async_trait]
+//! # impl weavegraph::node::Node for Noop {
+//! #     async fn run(&self, _: weavegraph::state::StateSnapshot, _: weavegraph::node::NodeContext) -> Result<weavegraph::node::NodePartial, weavegraph::node::NodeError> {
+//! #         Ok(weavegraph::node::NodePartial::default())
+//! #     }
+//! # }
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! # let app = GraphBuilder::new()
+//! #     .add_node(NodeKind::Custom("noop".into()), Noop)
+//! #     .add_edge(NodeKind::Start, NodeKind::Custom("noop".into()))
+//! #     .add_edge(NodeKind::Custom("noop".into()), NodeKind::End)
+//! #     .compile()
+//! #     .unwrap();
+//! let worker = TriggerWorker::new(app, |message| {
+//!     let text = message.payload["text"].as_str().unwrap_or_default();
+//!     Ok(VersionedState::new_with_user_message(text))
+//! })
+//! .with_max_concurrent_sessions(4);
+//!
+//! let source = StreamTrigger::new(stream::iter(vec![json!({"text": "a"}), json!({"text": "b"})]));
+//! let report = worker.run(source).await;
+//! assert_eq!(report.succeeded, 2);
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::app::App;
+use crate::state::VersionedState;
+use crate::utils::id_generator::IdGenerator;
+
+// ============================================================================
+// Messages and errors
+// ============================================================================
+
+/// One unit of work received from a [`TriggerSource`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TriggerMessage {
+    /// Source-assigned identifier; used to derive the session id when present.
+    pub id: Option<String>,
+    /// Message body. Non-JSON payloads are carried as JSON strings.
+    pub payload: Value,
+}
+
+impl TriggerMessage {
+    /// A message without an identifier.
+    #[must_use]
+    pub fn new(payload: Value) -> Self {
+        Self { id: None, payload }
+    }
+
+    /// Attach a source identifier.
+    #[must_use]
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+}
+
+/// Errors reported by trigger sources and state factories.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum TriggerError {
+    /// The source failed to receive a message.
+    #[error("trigger source error: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::triggers::source))
+    )]
+    Source(String),
+
+    /// The state factory rejected a message.
+    #[error("trigger message rejected: {0}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::triggers::rejected),
+            help("The state factory returned an error; the message was not run.")
+        )
+    )]
+    Rejected(String),
+}
+
+// ============================================================================
+// Sources
+// ============================================================================
+
+/// A source of trigger messages.
+#[async_trait]
+pub trait TriggerSource: Send {
+    /// Receive the next message. `None` means the source is exhausted and the
+    /// worker should stop after in-flight sessions finish.
+    async fn next_message(&mut self) -> Option<Result<TriggerMessage, TriggerError>>;
+}
+
+/// Trigger source backed by any async stream of payloads.
+pub struct StreamTrigger {
+    stream: BoxStream<'static, Result<TriggerMessage, TriggerError>>,
+}
+
+impl StreamTrigger {
+    /// Wrap a stream of JSON payloads.
+    pub fn new(stream: impl Stream<Item = Value> + Send + 'static) -> Self {
+        Self::from_messages(stream.map(TriggerMessage::new))
+    }
+
+    /// Wrap a stream of messages, e.g. to attach identifiers.
+    pub fn from_messages(stream: impl Stream<Item = TriggerMessage> + Send + 'static) -> Self {
+        Self {
+            stream: stream.map(Ok).boxed(),
+        }
+    }
+
+    /// Wrap a fallible stream; errors are counted and skipped.
+    pub fn from_results(
+        stream: impl Stream<Item = Result<TriggerMessage, TriggerError>> + Send + 'static,
+    ) -> Self {
+        Self {
+            stream: stream.boxed(),
+        }
+    }
+}
+
+#[async_trait]
+impl TriggerSource for StreamTrigger {
+    async fn next_message(&mut self) -> Option<Result<TriggerMessage, TriggerError>> {
+        self.stream.next().await
+    }
+}
+
+impl std::fmt::Debug for StreamTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamTrigger").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub use postgres_trigger::PostgresNotifyTrigger;
+
+#[cfg(feature = "postgres")]
+mod postgres_trigger {
+    use super::{TriggerError, TriggerMessage, TriggerSource};
+    use async_trait::async_trait;
+    use serde_json::Value;
+    use sqlx::postgres::PgListener;
+
+    /// Trigger source that receives Postgres `NOTIFY` payloads.
+    ///
+    /// Payloads that parse as JSON are passed through; anything else arrives
+    /// as a JSON string. The listener reconnects on connection loss, but
+    /// notifications sent while disconnected are lost, so use a durable queue
+    /// when every message must run.
+    #[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
+    pub struct PostgresNotifyTrigger {
+        listener: PgListener,
+    }
+
+    impl PostgresNotifyTrigger {
+        /// Connect and `LISTEN` on each of `channels`.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`TriggerError::Source`] if connecting or listening fails.
+        pub async fn connect(database_url: &str, channels: &[&str]) -> Result<Self, TriggerError> {
+            let mut listener = PgListener::connect(database_url)
+                .await
+                .map_err(|e| TriggerError::Source(e.to_string()))?;
+            listener
+                .listen_all(channels.iter().copied())
+                .await
+                .map_err(|e| TriggerError::Source(e.to_string()))?;
+            Ok(Self { listener })
+        }
+    }
+
+    #[async_trait]
+    impl TriggerSource for PostgresNotifyTrigger {
+        async fn next_message(&mut self) -> Option<Result<TriggerMessage, TriggerError>> {
+            Some(match self.listener.recv().await {
+                Ok(notification) => {
+                    let raw = notification.payload();
+                    let payload = serde_json::from_str(raw)
+                        .unwrap_or_else(|_| Value::String(raw.to_string()));
+                    Ok(TriggerMessage::new(payload))
+                }
+                Err(err) => Err(TriggerError::Source(err.to_string())),
+            })
+        }
+    }
+
+    impl std::fmt::Debug for PostgresNotifyTrigger {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PostgresNotifyTrigger")
+                .finish_non_exhaustive()
+        }
+    }
+}
+
+// ============================================================================
+// Worker
+// ============================================================================
+
+/// Builds the initial state for a triggered session.
+pub type StateFactory =
+    Arc<dyn Fn(&TriggerMessage) -> Result<VersionedState, TriggerError> + Send + Sync + 'static>;
+
+/// Counts reported by [`TriggerWorker::run`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TriggerReport {
+    /// Messages received from the source.
+    pub received: u64,
+    /// Sessions that ran to completion.
+    pub succeeded: u64,
+    /// Sessions that ended with a runner error.
+    pub failed: u64,
+    /// Messages the state factory rejected.
+    pub rejected: u64,
+    /// Errors returned by the source itself.
+    pub source_errors: u64,
+}
+
+/// Runs one session per trigger message with bounded concurrency.
+#[derive(Clone)]
+#[must_use]
+pub struct TriggerWorker {
+    app: Arc<App>,
+    state_factory: StateFactory,
+    max_concurrent_sessions: usize,
+    session_prefix: String,
+    error_backoff: Duration,
+    max_error_backoff: Duration,
+}
+
+impl TriggerWorker {
+    /// Create a worker that runs `app` with state built by `state_factory`.
+    ///
+    /// Defaults to 8 concurrent sessions, a `trigger-` session prefix, and a
+    /// source error backoff from 100ms up to 30s.
+    pub fn new<F>(app: App, state_factory: F) -> Self
+    where
+        F: Fn(&TriggerMessage) -> Result<VersionedState, TriggerError> + Send + Sync + 'static,
+    {
+        Self {
+            app: Arc::new(app),
+            state_factory: Arc::new(state_factory),
+            max_concurrent_sessions: 8,
+            session_prefix: "trigger-".to_string(),
+            error_backoff: Duration::from_millis(100),
+            max_error_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Limit how many sessions run at once. The source is not polled while
+    /// the limit is reached. Values below 1 are treated as 1.
+    pub fn with_max_concurrent_sessions(mut self, limit: usize) -> Self {
+        self.max_concurrent_sessions = limit.max(1);
+        self
+    }
+
+    /// Prefix for session ids. Messages with an id run as `<prefix><id>`, so
+    /// with a persistent custom checkpointer a redelivered message resumes its
+    /// session; messages without an id get a fresh run id.
+    pub fn with_session_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.session_prefix = prefix.into();
+        self
+    }
+
+    /// Wait `initial` after a source error before polling the source again,
+    /// doubling the delay on each consecutive error up to `max`. The delay
+    /// resets once the source yields a message. A zero `initial` disables it.
+    pub fn with_error_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.error_backoff = initial;
+        self.max_error_backoff = max.max(initial);
+        self
+    }
+
+    /// Consume `source` until it is exhausted, then wait for in-flight sessions.
+    pub async fn run(&self, source: impl TriggerSource) -> TriggerReport {
+        self.run_until(source, std::future::pending()).await
+    }
+
+    /// Like [`run`](Self::run), but stop receiving when `shutdown` resolves.
+    /// In-flight sessions still run to completion.
+    pub async fn run_until(
+        &self,
+        mut source: impl TriggerSource,
+        shutdown: impl Future<Output = ()>,
+    ) -> TriggerReport {
+        let permits = Arc::new(Semaphore::new(self.max_concurrent_sessions));
+        let mut sessions = JoinSet::new();
+        let mut report = TriggerReport::default();
+        let mut consecutive_errors = 0u32;
+        tokio::pin!(shutdown);
+
+        loop {
+            // Reap finished sessions so the JoinSet does not grow unbounded.
+            while let Some(done) = sessions.try_join_next() {
+                record(&mut report, done);
+            }
+            let permit = tokio::select! {
+                permit = Arc::clone(&permits).acquire_owned() => {
+                    permit.expect("trigger semaphore is never closed")
+                }
+                () = &mut shutdown => break,
+            };
+            let message = tokio::select! {
+                message = source.next_message() => message,
+                () = &mut shutdown => break,
+            };
+            let message = match message {
+                None => break,
+                Some(Err(err)) => {
+                    report.source_errors += 1;
+                    let delay = self.error_delay(consecutive_errors);
+                    consecutive_errors = consecutive_errors.saturating_add(1);
+                    tracing::warn!(
+                        target: "weavegraph::triggers",
+                        error = %err,
+                        retry_in = ?delay,
+                        "trigger source error"
+                    );
+                    drop(permit);
+                    tokio::select! {
+                        () = tokio::time::sleep(delay) => continue,
+                        () = &mut shutdown => break,
+                    }
+                }
+                Some(Ok(message)) => message,
+            };
+            consecutive_errors = 0;
+            report.received += 1;
+
+            let state = match (self.state_factory)(&message) {
+                Ok(state) => state,
+                Err(err) => {
+                    report.rejected += 1;
+                    tracing::warn!(
+                        target: "weavegraph::triggers",
+                        id = ?message.id,
+                        error = %err,
+                        "trigger message rejected"
+                    );
+                    continue;
+                }
+            };
+            let session_id = match &message.id {
+                Some(id) => format!("{}{id}", self.session_prefix),
+                None => format!(
+                    "{}{}",
+                    self.session_prefix,
+                    IdGenerator::new().generate_run_id()
+                ),
+            };
+            let app = Arc::clone(&self.app);
+            sessions.spawn(async move {
+                let _permit = permit;
+                let result = app.invoke_session(session_id.clone(), state).await;
+                if let Err(err) = &result {
+                    tracing::warn!(
+                        target: "weavegraph::triggers",
+                        session = %session_id,
+                        error = %err,
+                        "triggered session failed"
+                    );
+                }
+                result.is_ok()
+            });
+        }
+
+        while let Some(done) = sessions.join_next().await {
+            record(&mut report, done);
+        }
+        report
+    }
+
+    /// Backoff before polling again after `previous` consecutive source errors.
+    fn error_delay(&self, previous: u32) -> Duration {
+        self.error_backoff
+            .saturating_mul(2u32.saturating_pow(previous))
+            .min(self.max_error_backoff)
+    }
+}
+
+fn record(report: &mut TriggerReport, done: Result<bool, tokio::task::JoinError>) {
+    match done {
+        Ok(true) => report.succeeded += 1,
+        Ok(false) => report.failed += 1,
+        Err(err) => {
+            report.failed += 1;
+            tracing::warn!(
+                target: "weavegraph::triggers",
+                error = %err,
+                "triggered session panicked"
+            );
+        }
+    }
+}
+
+impl std::fmt::Debug for TriggerWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TriggerWorker")
+            .field("max_concurrent_sessions", &self.max_concurrent_sessions)
+            .field("session_prefix", &self.session_prefix)
+            .field("error_backoff", &self.error_backoff)
+            .field("max_error_backoff", &self.max_error_backoff)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,452 @@
+//! What-if analysis: evaluate graph changes against recorded sessions.
+//!
+//! [`WhatIfAnalysis`] walks a session's checkpoint history step by step and
+//! asks, for each step, what an alternative graph would have done given the
+//! state that was actually recorded:
+//!
+//! - A node replaced with [`WhatIfAnalysis::replace_node`] is run on the
+//!   recorded input of every step it ran in. Its state change diverges when
+//!   it is not part of the step's recorded change: a message it appends is not
+//!   among the messages the step appended, or an `extra` value it writes
+//!   differs from the recorded one.
+//! - Conditional edges replaced with [`WhatIfAnalysis::replace_routing`] are
+//!   evaluated on the recorded post-step state, next to the graph's own
+//!   conditional edges from the same node. Different targets are a routing
+//!   divergence.
+//!
+//! Every other node is never run; its recorded effect is taken from the
+//! checkpoints. Because each step starts from recorded state, the report
+//! lists every step where the change would have made a difference, not only
+//! the first. Steps after the first divergence show what the change would do
+//! to traffic that reached them, not what the changed graph would have done
+//! end to end.
+//!
+//! Routing contexts carry the step, the nodes that ran, the channels the step
+//! updated, and the graph's runtime configuration; recorded barrier errors and
+//! feature flags are not available.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use weavegraph::graphs::ConditionalEdge;
+//! use weavegraph::runtimes::{Checkpoint, WhatIfAnalysis};
+//! use weavegraph::types::NodeKind;
+//!
+//! # async fn example(app: weavegraph::app::App, history: Vec<Checkpoint>) -> Result<(), Box<dyn std::error::Error>> {
+//! let review = NodeKind::Custom("review".into());
+//! let report = WhatIfAnalysis::new(app)
+//!     .replace_routing(ConditionalEdge::new(
+//!         review.clone(),
+//!         Arc::new(|_snapshot| vec![NodeKind::end_target()]),
+//!     ))
+//!     .analyze(&history)
+//!     .await?;
+//! if let Some(first) = report.first_divergence() {
+//!     println!("diverges at step {} in {}", first.step, first.node);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use crate::app::{App, BarrierOutcome};
+use crate::channels::Channel;
+use crate::event_bus::EventBus;
+use crate::graphs::{ConditionalEdge, RoutingContext};
+use crate::message::Message;
+use crate::node::{Node, NodeContext, NodePartial};
+use crate::runtimes::step_query::{MAX_STEP_QUERY_LIMIT, StepHistory, StepQuery};
+use crate::runtimes::{Checkpoint, CheckpointerError};
+use crate::types::NodeKind;
+
+/// How an alternative graph differs from a recorded step.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DivergenceKind {
+    /// Replaced conditional edges chose different targets.
+    Routing {
+        /// Targets the graph's own conditional edges choose, sorted.
+        recorded: Vec<String>,
+        /// Targets the replacement edges choose, sorted.
+        alternative: Vec<String>,
+    },
+    /// A replaced node's state change is not part of the recorded change.
+    State {
+        /// Messages the replaced node appends that the step did not append.
+        messages: Vec<Message>,
+        /// `extra` keys the replaced node writes with a different value, sorted.
+        extra_keys: Vec<String>,
+        /// The replaced node's output.
+        output: NodePartial,
+    },
+    /// A replaced node returned an error.
+    NodeFailed {
+        /// The error's message.
+        error: String,
+    },
+}
+
+/// A step where the alternative graph differs from the recording.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Divergence {
+    /// Recorded step the difference appears in.
+    pub step: u64,
+    /// Replaced node, or source node of the replaced edges.
+    pub node: NodeKind,
+    /// What differs.
+    pub kind: DivergenceKind,
+}
+
+/// Outcome of [`WhatIfAnalysis::analyze`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct WhatIfReport {
+    /// Session the history belongs to.
+    pub session_id: String,
+    /// Number of recorded steps compared.
+    pub steps_analyzed: u64,
+    /// Differences found, ordered by step then node.
+    pub divergences: Vec<Divergence>,
+}
+
+impl WhatIfReport {
+    /// Earliest difference, if any.
+    #[must_use]
+    pub fn first_divergence(&self) -> Option<&Divergence> {
+        self.divergences.first()
+    }
+
+    /// Whether the alternative graph behaves like the recording at every step.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Errors returned by [`WhatIfAnalysis`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
+pub enum WhatIfError {
+    /// The history has no checkpoints.
+    #[error("checkpoint history is empty")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::what_if::empty_history))
+    )]
+    EmptyHistory,
+
+    /// The history mixes sessions or skips steps.
+    #[error("checkpoint history is not contiguous: step {next} follows step {previous}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::what_if::gap),
+            help("Pass every step of one session, for example from `StepHistory::query_steps`.")
+        )
+    )]
+    Gap {
+        /// Last step before the gap.
+        previous: u64,
+        /// First step after the gap.
+        next: u64,
+    },
+
+    /// A replacement names a node the graph does not have.
+    #[error("cannot replace {0}: the graph has no such node")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::what_if::unknown_node))
+    )]
+    UnknownNode(NodeKind),
+
+    /// The history could not be loaded.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Checkpointer(#[from] CheckpointerError),
+
+    /// A replaced node's output could not be applied to the recorded state.
+    #[error("step {step}: applying the output of {node} failed: {message}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(weavegraph::what_if::barrier))
+    )]
+    Barrier {
+        /// Recorded step.
+        step: u64,
+        /// Replaced node.
+        node: NodeKind,
+        /// The barrier error.
+        message: String,
+    },
+}
+
+/// Compares an alternative graph against recorded checkpoint history.
+///
+/// See the [module documentation](self) for how steps are compared.
+#[must_use]
+pub struct WhatIfAnalysis {
+    app: App,
+    nodes: FxHashMap<NodeKind, Arc<dyn Node>>,
+    routing: FxHashMap<NodeKind, Vec<ConditionalEdge>>,
+}
+
+impl WhatIfAnalysis {
+    /// Analyze changes to `app`, the graph the history was recorded with.
+    pub fn new(app: App) -> Self {
+        Self {
+            app,
+            nodes: FxHashMap::default(),
+            routing: FxHashMap::default(),
+        }
+    }
+
+    /// Run `node` wherever the recording ran `kind`.
+    pub fn replace_node(mut self, kind: NodeKind, node: impl Node + 'static) -> Self {
+        self.nodes.insert(kind, Arc::new(node));
+        self
+    }
+
+    /// Use `edge` instead of the graph's conditional edges from its source node.
+    ///
+    /// Several replacements for the same source node are all evaluated.
+    pub fn replace_routing(mut self, edge: ConditionalEdge) -> Self {
+        self.routing
+            .entry(edge.from().clone())
+            .or_default()
+            .push(edge);
+        self
+    }
+
+    /// Load every recorded step of `session_id` from `store` and analyze it.
+    ///
+    /// # Errors
+    ///
+    /// See [`analyze`](Self::analyze); also fails if the history cannot be read.
+    pub async fn analyze_session<C>(
+        &self,
+        store: &C,
+        session_id: &str,
+    ) -> Result<WhatIfReport, WhatIfError>
+    where
+        C: StepHistory + ?Sized,
+    {
+        let mut history = Vec::new();
+        loop {
+            let page = store
+                .query_steps(
+                    session_id,
+                    StepQuery {
+                        limit: Some(MAX_STEP_QUERY_LIMIT),
+                        offset: Some(history.len() as u32),
+                        ..StepQuery::default()
+                    },
+                )
+                .await?;
+            history.extend(page.checkpoints);
+            if !page.page_info.has_next_page {
+                break;
+            }
+        }
+        history.sort_by_key(|checkpoint| checkpoint.step);
+        self.analyze(&history).await
+    }
+
+    /// Compare the alternative graph against `history`, ordered by step.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WhatIfError::EmptyHistory`] or [`WhatIfError::Gap`] for
+    /// unusable history, and [`WhatIfError::UnknownNode`] if a replaced node
+    /// is not part of the graph.
+    pub async fn analyze(&self, history: &[Checkpoint]) -> Result<WhatIfReport, WhatIfError> {
+        let first = history.first().ok_or(WhatIfError::EmptyHistory)?;
+        let mut replaced: Vec<&NodeKind> = self.nodes.keys().collect();
+        replaced.sort_by_key(|node| node.encode());
+        if let Some(node) = replaced
+            .into_iter()
+            .find(|node| !self.app.nodes().contains_key(*node))
+        {
+            return Err(WhatIfError::UnknownNode(node.clone()));
+        }
+
+        let emitter = EventBus::with_sinks(Vec::new()).get_emitter();
+        let mut divergences = Vec::new();
+        for pair in history.windows(2) {
+            let (before, after) = (&pair[0], &pair[1]);
+            if after.session_id != first.session_id || after.step != before.step + 1 {
+                return Err(WhatIfError::Gap {
+                    previous: before.step,
+                    next: after.step,
+                });
+            }
+            let mut ran = after.ran_nodes.clone();
+            ran.sort_by_key(NodeKind::encode);
+            for node in &ran {
+                if let Some(alternative) = self.nodes.get(node) {
+                    let mut ctx =
+                        NodeContext::new(format!("{:?}", node), after.step, Arc::clone(&emitter));
+                    ctx.invocation_id = Some(after.session_id.clone());
+                    if let Some(kind) = self
+                        .compare_node(node, alternative, before, after, ctx)
+                        .await?
+                    {
+                        divergences.push(Divergence {
+                            step: after.step,
+                            node: node.clone(),
+                            kind,
+                        });
+                    }
+                }
+                if let Some(kind) = self.compare_routing(node, after) {
+                    divergences.push(Divergence {
+                        step: after.step,
+                        node: node.clone(),
+                        kind,
+                    });
+                }
+            }
+        }
+
+        Ok(WhatIfReport {
+            session_id: first.session_id.clone(),
+            steps_analyzed: history.len().saturating_sub(1) as u64,
+            divergences,
+        })
+    }
+
+    /// Run `alternative` on the recorded input of `after` and compare its change.
+    async fn compare_node(
+        &self,
+        node: &NodeKind,
+        alternative: &Arc<dyn Node>,
+        before: &Checkpoint,
+        after: &Checkpoint,
+        ctx: NodeContext,
+    ) -> Result<Option<DivergenceKind>, WhatIfError> {
+        let input = self
+            .app
+            .snapshot_views()
+            .snapshot_for(node, &before.state.snapshot());
+        let output = match alternative.run(input, ctx).await {
+            Ok(output) => output,
+            Err(err) => {
+                return Ok(Some(DivergenceKind::NodeFailed {
+                    error: err.to_string(),
+                }));
+            }
+        };
+
+        let mut state = before.state.clone();
+        self.app
+            .apply_barrier(&mut state, std::slice::from_ref(node), vec![output.clone()])
+            .await
+            .map_err(|err| WhatIfError::Barrier {
+                step: after.step,
+                node: node.clone(),
+                message: err.to_string(),
+            })?;
+
+        let base = before.state.messages.len();
+        let recorded_messages = after.state.messages.snapshot();
+        let recorded_appended = recorded_messages.get(base..).unwrap_or_default();
+        let messages: Vec<Message> = state
+            .messages
+            .snapshot()
+            .into_iter()
+            .skip(base)
+            .filter(|message| !recorded_appended.contains(message))
+            .collect();
+
+        let previous = before.state.extra.snapshot();
+        let recorded = after.state.extra.snapshot();
+        let mut extra_keys: Vec<String> = state
+            .extra
+            .snapshot()
+            .into_iter()
+            .filter(|(key, value)| {
+                previous.get(key) != Some(value) && recorded.get(key) != Some(value)
+            })
+            .map(|(key, _)| key)
+            .collect();
+        extra_keys.sort();
+
+        if messages.is_empty() && extra_keys.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(DivergenceKind::State {
+            messages,
+            extra_keys,
+            output,
+        }))
+    }
+
+    /// Evaluate replaced and original conditional edges from `node` on the state after `after`.
+    fn compare_routing(&self, node: &NodeKind, after: &Checkpoint) -> Option<DivergenceKind> {
+        let alternatives = self.routing.get(node)?;
+        let snapshot = after.state.snapshot();
+        let barrier = BarrierOutcome {
+            updated_channels: after
+                .updated_channels
+                .iter()
+                .filter_map(|channel| channel_name(channel))
+                .collect(),
+            ..BarrierOutcome::default()
+        };
+        let ctx = RoutingContext::new(node, &snapshot)
+            .with_step(after.step)
+            .with_ran_nodes(&after.ran_nodes)
+            .with_barrier(&barrier)
+            .with_config(self.app.runtime_config());
+
+        let mut recorded: Vec<String> = self
+            .app
+            .conditional_edges()
+            .iter()
+            .filter(|edge| edge.from() == node)
+            .flat_map(|edge| edge.evaluate(&ctx))
+            .collect();
+        let mut alternative: Vec<String> = alternatives
+            .iter()
+            .flat_map(|edge| edge.evaluate(&ctx))
+            .collect();
+        recorded.sort();
+        recorded.dedup();
+        alternative.sort();
+        alternative.dedup();
+        (recorded != alternative).then_some(DivergenceKind::Routing {
+            recorded,
+            alternative,
+        })
+    }
+}
+
+impl std::fmt::Debug for WhatIfAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut nodes: Vec<String> = self.nodes.keys().map(NodeKind::encode).collect();
+        nodes.sort();
+        let mut routing: Vec<String> = self.routing.keys().map(NodeKind::encode).collect();
+        routing.sort();
+        f.debug_struct("WhatIfAnalysis")
+            .field("replaced_nodes", &nodes)
+            .field("replaced_routing", &routing)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Static name of a recorded channel, as used in [`BarrierOutcome::updated_channels`].
+fn channel_name(channel: &str) -> Option<&'static str> {
+    match channel {
+        "messages" => Some("messages"),
+        "extra" => Some("extra"),
+        "errors" => Some("errors"),
+        "blobs" => Some("blobs"),
+        _ => None,
+    }
+}
@@ -1,6 +1,9 @@
 //! Frontier-based workflow scheduler with version gating and bounded concurrency.
+pub mod saturation;
 pub mod scheduler;
 
+pub use saturation::{AdaptiveConcurrency, NodeTiming, SchedulerMetrics, SuperstepSaturation};
 pub use scheduler::{
-    Scheduler, SchedulerError, SchedulerRunContext, SchedulerState, StepRunResult,
+    DEFAULT_CANCEL_GRACE_PERIOD, Scheduler, SchedulerError, SchedulerRunContext, SchedulerState,
+    StepRunResult, install_node_panic_hook,
 };
@@ -0,0 +1,307 @@
+//! Scheduler saturation metrics and adaptive concurrency.
+//!
+//! Every superstep records how long tasks queued behind the concurrency limit,
+//! how busy the limit's slots were, and how long each node ran. Runners keep a
+//! rolling window of these samples and expose it through
+//! [`AppRunner::scheduler_metrics`](crate::runtimes::AppRunner::scheduler_metrics)
+//! and [`EventBus::metrics`](crate::event_bus::EventBus::metrics), ready to be
+//! exported as gauges for an autoscaler.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of node latency samples kept for percentile estimates.
+const LATENCY_WINDOW: usize = 1024;
+/// Number of supersteps kept for utilization and queue-wait aggregates.
+const STEP_WINDOW: usize = 64;
+
+/// Saturation measurements for one superstep.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::schedulers::SuperstepSaturation;
+///
+/// let step = SuperstepSaturation::default();
+/// assert_eq!(step.utilization(), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SuperstepSaturation {
+    /// The superstep these measurements belong to.
+    pub step: u64,
+    /// Concurrency limit the superstep ran with.
+    pub concurrency_limit: usize,
+    /// Number of node runs scheduled.
+    pub tasks: usize,
+    /// Wall-clock time from scheduling the first task to the last completion, in microseconds.
+    pub wall_micros: u64,
+    /// Summed node run time, in microseconds.
+    pub busy_micros: u64,
+    /// Summed time tasks waited for a free concurrency slot, in microseconds.
+    pub total_queue_wait_micros: u64,
+    /// Longest time a task waited for a free concurrency slot, in microseconds.
+    pub max_queue_wait_micros: u64,
+}
+
+impl SuperstepSaturation {
+    /// Fraction of the available slot time (`concurrency_limit × wall time`)
+    /// spent running nodes, between `0.0` and `1.0`.
+    #[must_use]
+    pub fn utilization(&self) -> f64 {
+        ratio(self.busy_micros, self.capacity_micros())
+    }
+
+    /// Mean time a task waited for a free concurrency slot.
+    #[must_use]
+    pub fn mean_queue_wait(&self) -> Duration {
+        match self.tasks {
+            0 => Duration::ZERO,
+            tasks => Duration::from_micros(self.total_queue_wait_micros / tasks as u64),
+        }
+    }
+
+    /// Whether more tasks were scheduled than the limit could run at once.
+    #[must_use]
+    pub fn saturated(&self) -> bool {
+        self.tasks > self.concurrency_limit
+    }
+
+    fn capacity_micros(&self) -> u64 {
+        self.wall_micros
+            .saturating_mul(self.concurrency_limit.max(1) as u64)
+    }
+}
+
+/// When one node run started and how long it queued and ran.
+///
+/// Reported per run in [`StepRunResult::node_timings`](crate::schedulers::StepRunResult::node_timings)
+/// so profilers can lay node runs out on a timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NodeTiming {
+    /// When the run acquired a concurrency slot and started.
+    pub started_at: Instant,
+    /// Time spent waiting for a free concurrency slot.
+    pub queue_wait: Duration,
+    /// Time spent in [`Node::run`](crate::node::Node::run).
+    pub duration: Duration,
+}
+
+/// Rolling scheduler saturation metrics for a runner.
+///
+/// Aggregates cover the most recent supersteps and node runs, so they track
+/// current load rather than lifetime averages.
+///
+/// # Examples
+///
+/// ```
+/// use weavegraph::schedulers::SchedulerMetrics;
+///
+/// let metrics = SchedulerMetrics::default();
+/// assert_eq!(metrics.steps, 0);
+/// assert!(metrics.last_step.is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SchedulerMetrics {
+    /// Supersteps recorded since the runner was built.
+    pub steps: u64,
+    /// Concurrency limit of the most recent superstep, after any adaptive adjustment.
+    pub concurrency_limit: usize,
+    /// Measurements of the most recent superstep.
+    pub last_step: Option<SuperstepSaturation>,
+    /// Summed node run time over the recent supersteps, in microseconds.
+    pub window_busy_micros: u64,
+    /// Available slot time over the recent supersteps, in microseconds.
+    pub window_capacity_micros: u64,
+    /// Longest queue wait over the recent supersteps, in microseconds.
+    pub max_queue_wait_micros: u64,
+    /// Median node latency over the recent node runs, in microseconds.
+    pub latency_p50_micros: u64,
+    /// 90th percentile node latency over the recent node runs, in microseconds.
+    pub latency_p90_micros: u64,
+    /// 99th percentile node latency over the recent node runs, in microseconds.
+    pub latency_p99_micros: u64,
+}
+
+impl SchedulerMetrics {
+    /// Concurrency utilization over the recent supersteps, between `0.0` and `1.0`.
+    #[must_use]
+    pub fn utilization(&self) -> f64 {
+        ratio(self.window_busy_micros, self.window_capacity_micros)
+    }
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        (numerator as f64 / denominator as f64).min(1.0)
+    }
+}
+
+// ============================================================================
+// Adaptive concurrency
+// ============================================================================
+
+/// Adjusts a session's superstep concurrency limit from observed node latency.
+///
+/// After each superstep the limit drops by a quarter (at least one) when the
+/// recent 90th percentile node latency exceeds the target, and grows by one
+/// when latency is within target and the superstep queued tasks behind the
+/// limit. The limit always stays within `min..=max`.
+///
+/// Enable with
+/// [`RuntimeConfig::with_adaptive_concurrency`](crate::runtimes::RuntimeConfig::with_adaptive_concurrency).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use weavegraph::schedulers::AdaptiveConcurrency;
+///
+/// let adaptive = AdaptiveConcurrency::new(2, 16, Duration::from_millis(250));
+/// assert_eq!(adaptive.min(), 2);
+/// assert_eq!(adaptive.max(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveConcurrency {
+    min: usize,
+    max: usize,
+    target_latency: Duration,
+}
+
+impl AdaptiveConcurrency {
+    /// Keep the limit within `min..=max`, aiming for a p90 node latency of `target_latency`.
+    ///
+    /// A `min` of zero is raised to one and `max` is raised to at least `min`.
+    #[must_use]
+    pub fn new(min: usize, max: usize, target_latency: Duration) -> Self {
+        let min = min.max(1);
+        Self {
+            min,
+            max: max.max(min),
+            target_latency,
+        }
+    }
+
+    /// Lowest limit the adjustment will choose.
+    #[must_use]
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Highest limit the adjustment will choose.
+    #[must_use]
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Target 90th percentile node latency.
+    #[must_use]
+    pub fn target_latency(&self) -> Duration {
+        self.target_latency
+    }
+
+    /// The limit to use for the next superstep.
+    #[must_use]
+    pub fn next_limit(&self, current: usize, metrics: &SchedulerMetrics) -> usize {
+        let current = current.clamp(self.min, self.max);
+        let p90 = Duration::from_micros(metrics.latency_p90_micros);
+        if p90 > self.target_latency {
+            return current.saturating_sub((current / 4).max(1)).max(self.min);
+        }
+        match metrics.last_step {
+            Some(last) if last.saturated() => (current + 1).min(self.max),
+            _ => current,
+        }
+    }
+}
+
+// ============================================================================
+// Tracker
+// ============================================================================
+
+/// Rolling window of superstep samples shared between a runner and its event bus.
+#[derive(Debug, Default)]
+pub(crate) struct SchedulerTelemetry {
+    inner: Mutex<TelemetryWindow>,
+}
+
+#[derive(Debug, Default)]
+struct TelemetryWindow {
+    steps: u64,
+    concurrency_limit: usize,
+    last_step: Option<SuperstepSaturation>,
+    recent_steps: VecDeque<SuperstepSaturation>,
+    latencies: VecDeque<u64>,
+}
+
+impl SchedulerTelemetry {
+    /// Record a superstep and the run time of each node it executed.
+    pub(crate) fn record(
+        &self,
+        saturation: SuperstepSaturation,
+        latencies: impl IntoIterator<Item = u64>,
+    ) {
+        let mut window = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        window.steps += 1;
+        window.concurrency_limit = saturation.concurrency_limit;
+        window.last_step = Some(saturation);
+        window.recent_steps.push_back(saturation);
+        if window.recent_steps.len() > STEP_WINDOW {
+            window.recent_steps.pop_front();
+        }
+        for latency in latencies {
+            window.latencies.push_back(latency);
+            if window.latencies.len() > LATENCY_WINDOW {
+                window.latencies.pop_front();
+            }
+        }
+    }
+
+    /// Note the limit chosen for the next superstep.
+    pub(crate) fn set_concurrency_limit(&self, limit: usize) {
+        let mut window = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        window.concurrency_limit = limit;
+    }
+
+    pub(crate) fn snapshot(&self) -> SchedulerMetrics {
+        let window = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut sorted: Vec<u64> = window.latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        SchedulerMetrics {
+            steps: window.steps,
+            concurrency_limit: window.concurrency_limit,
+            last_step: window.last_step,
+            window_busy_micros: window.recent_steps.iter().map(|s| s.busy_micros).sum(),
+            window_capacity_micros: window
+                .recent_steps
+                .iter()
+                .map(SuperstepSaturation::capacity_micros)
+                .sum(),
+            max_queue_wait_micros: window
+                .recent_steps
+                .iter()
+                .map(|s| s.max_queue_wait_micros)
+                .max()
+                .unwrap_or(0),
+            latency_p50_micros: percentile(&sorted, 50),
+            latency_p90_micros: percentile(&sorted, 90),
+            latency_p99_micros: percentile(&sorted, 99),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
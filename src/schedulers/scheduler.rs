@@ -38,16 +38,145 @@
 //! # }
 //! ```
 
-use crate::event_bus::EventEmitter;
-use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::event_bus::{CANCELLATION_SCOPE, Event, EventEmitter, NodeEvent};
+use crate::feature_flags::FeatureFlags;
+use crate::graphs::{DerivedValues, SnapshotViews};
+use crate::llm::audit::LlmAuditLog;
+use crate::node::{
+    CancellationToken, Node, NodeContext, NodeError, NodeMetrics, NodeMetricsRecorder, NodePartial,
+    PartialStream,
+};
+use crate::schedulers::saturation::{NodeTiming, SuperstepSaturation};
 use crate::state::StateSnapshot;
 use crate::types::NodeKind;
 use crate::utils::clock::Clock;
+use futures_util::FutureExt;
 use futures_util::stream::{self, StreamExt};
 use rustc_hash::FxHashMap;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Once};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tracing::instrument;
+use tracing::{Instrument, instrument};
+
+// ============================================================================
+// Panic capture
+// ============================================================================
+
+thread_local! {
+    /// Depth of node futures currently being polled on this thread.
+    static NODE_POLL_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Backtrace recorded by the panic hook for the most recent node panic.
+    static NODE_PANIC_TRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Install a process-wide panic hook that records a backtrace when a node panics.
+///
+/// Node panics are always caught at the node boundary and reported as
+/// [`SchedulerError::NodePanic`] with the panic payload as `message`. Its
+/// `trace` is empty unless this hook is installed.
+///
+/// This replaces the global hook set with [`std::panic::set_hook`]. The hook it
+/// replaces still runs for every panic, so existing panic output and reporting
+/// are unchanged, but a hook the application installs *afterwards* replaces
+/// this one. Installing more than once has no effect, and panics outside node
+/// polling are ignored by the recorder.
+///
+/// # Examples
+///
+/// ```
+/// weavegraph::schedulers::install_node_panic_hook();
+/// ```
+pub fn install_node_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if NODE_POLL_DEPTH.with(Cell::get) > 0 {
+                let trace = Backtrace::force_capture().to_string();
+                NODE_PANIC_TRACE.with(|slot| *slot.borrow_mut() = Some(trace));
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Future wrapper that marks node polling so the panic hook knows to record.
+struct NodePoll<F>(F);
+
+impl<F: std::future::Future + Unpin> std::future::Future for NodePoll<F> {
+    type Output = F::Output;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        struct DepthGuard;
+        impl Drop for DepthGuard {
+            fn drop(&mut self) {
+                NODE_POLL_DEPTH.with(|depth| depth.set(depth.get() - 1));
+            }
+        }
+        NODE_POLL_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        let _guard = DepthGuard;
+        std::pin::Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+// ============================================================================
+// Cancellation
+// ============================================================================
+
+/// How long node runs may keep going after cancellation before they are dropped.
+pub const DEFAULT_CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// How a scheduled node run ended.
+enum NodeOutcome {
+    /// The node returned or panicked.
+    Finished(std::thread::Result<Result<NodePartial, NodeError>>),
+    /// Cancellation arrived before the node got a concurrency slot.
+    NotStarted,
+    /// The node ignored cancellation for the whole grace period and was dropped.
+    Aborted,
+}
+
+/// Publish a [`CANCELLATION_SCOPE`] event for `phase`.
+fn emit_cancellation(
+    emitter: &Arc<dyn EventEmitter>,
+    node_id: Option<String>,
+    step: u64,
+    phase: &str,
+    mut metadata: FxHashMap<String, serde_json::Value>,
+) {
+    metadata.insert("phase".into(), phase.into());
+    let message = match phase {
+        "cooperative" => "cancellation requested",
+        _ => "node aborted after grace period",
+    };
+    let event = NodeEvent::new(
+        node_id,
+        Some(step),
+        CANCELLATION_SCOPE.to_string(),
+        message.to_string(),
+    )
+    .with_metadata(metadata);
+    // Cancellation proceeds even if nobody is listening.
+    let _ = emitter.emit(Event::Node(event));
+}
 
 /// Result of executing a single superstep in the scheduler.
 ///
@@ -86,8 +215,14 @@ pub struct StepRunResult {
     pub ran_nodes: Vec<NodeKind>,
     /// Nodes that were skipped this step (End nodes or no new versions seen).
     pub skipped_nodes: Vec<NodeKind>,
-    /// Outputs from nodes that ran: (node_kind, NodePartial)
+    /// Outputs from nodes that ran, in `ran_nodes` order: (node_kind, NodePartial)
     pub outputs: Vec<(NodeKind, NodePartial)>,
+    /// Execution metrics for nodes that ran, in `ran_nodes` order.
+    pub node_metrics: Vec<(NodeKind, NodeMetrics)>,
+    /// Start time, queue wait, and run time for nodes that ran, in `ran_nodes` order.
+    pub node_timings: Vec<(NodeKind, NodeTiming)>,
+    /// Queue wait and concurrency utilization measured for this step.
+    pub saturation: SuperstepSaturation,
 }
 
 /// Runtime context passed to a scheduler superstep.
@@ -100,6 +235,24 @@ pub struct SchedulerRunContext {
     pub clock: Option<Arc<dyn Clock>>,
     /// Optional invocation identifier injected into node contexts.
     pub invocation_id: Option<String>,
+    /// Incremental partial stream injected into node contexts by the runner.
+    pub(crate) partial_stream: Option<PartialStream>,
+    /// Optional base seed for [`NodeContext::rng`].
+    pub rng_seed: Option<u64>,
+    /// Execution attempt of this superstep, starting at 1; see [`NodeContext::attempt`].
+    pub attempt: u32,
+    /// Collects [`NodeContext::record_llm_call`] records when LLM auditing is enabled.
+    pub(crate) llm_audit: Option<Arc<LlmAuditLog>>,
+    /// Feature flags injected into node contexts.
+    pub feature_flags: Option<Arc<FeatureFlags>>,
+    /// Token that cancels the superstep; each node run gets a child of it.
+    pub cancellation: Option<CancellationToken>,
+    /// How long node runs may continue after cancellation before being dropped.
+    pub cancel_grace_period: Duration,
+    /// Message windows for nodes that should not receive the full snapshot.
+    pub snapshot_views: SnapshotViews,
+    /// Derived channels bound to the superstep's input snapshot.
+    pub derived: Option<DerivedValues>,
 }
 
 impl SchedulerRunContext {
@@ -110,6 +263,15 @@ impl SchedulerRunContext {
             event_emitter,
             clock: None,
             invocation_id: None,
+            partial_stream: None,
+            rng_seed: None,
+            attempt: 1,
+            llm_audit: None,
+            feature_flags: None,
+            cancellation: None,
+            cancel_grace_period: DEFAULT_CANCEL_GRACE_PERIOD,
+            snapshot_views: SnapshotViews::default(),
+            derived: None,
         }
     }
 
@@ -126,6 +288,70 @@ impl SchedulerRunContext {
         self.invocation_id = Some(invocation_id.into());
         self
     }
+
+    /// Set the base seed for [`NodeContext::rng`].
+    #[must_use]
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Mark the superstep as re-execution number `attempt` of its step.
+    #[must_use]
+    pub fn with_attempt(mut self, attempt: u32) -> Self {
+        self.attempt = attempt.max(1);
+        self
+    }
+
+    /// Collect audited LLM calls of the superstep into `log`.
+    #[must_use]
+    pub(crate) fn with_llm_audit(mut self, log: Arc<LlmAuditLog>) -> Self {
+        self.llm_audit = Some(log);
+        self
+    }
+
+    /// Attach the session's feature flags.
+    #[must_use]
+    pub fn with_feature_flags(mut self, flags: Arc<FeatureFlags>) -> Self {
+        self.feature_flags = Some(flags);
+        self
+    }
+
+    /// Give nodes with a [`SnapshotView`](crate::graphs::SnapshotView) only
+    /// the messages their view selects.
+    #[must_use]
+    pub fn with_snapshot_views(mut self, views: SnapshotViews) -> Self {
+        self.snapshot_views = views;
+        self
+    }
+
+    /// Let nodes read derived channels through [`NodeContext::derived`].
+    #[must_use]
+    pub fn with_derived(mut self, derived: DerivedValues) -> Self {
+        self.derived = Some(derived);
+        self
+    }
+
+    /// Cancel the superstep when `token` is cancelled.
+    ///
+    /// Node runs observe a child of `token` through
+    /// [`NodeContext::cancellation_token`] and get `grace_period` to return;
+    /// runs still pending after that are dropped. The superstep then fails
+    /// with [`SchedulerError::Cancelled`].
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken, grace_period: Duration) -> Self {
+        self.cancellation = Some(token);
+        self.cancel_grace_period = grace_period;
+        self
+    }
+
+    /// Attach an incremental partial stream so nodes can call
+    /// [`NodeContext::yield_partial`].
+    #[must_use]
+    pub(crate) fn with_partial_stream(mut self, partial_stream: PartialStream) -> Self {
+        self.partial_stream = Some(partial_stream);
+        self
+    }
 }
 
 /// Tracks version information for nodes to enable intelligent scheduling.
@@ -142,6 +368,11 @@ impl SchedulerRunContext {
 /// - Inner key: Channel name ("messages", "extra", etc.)
 /// - Value: Last version number the node processed for that channel
 ///
+/// Pending AND-join arrivals, the origins of per-incoming-edge frontier slots,
+/// and run counts of nodes with quotas are kept in their own fields so
+/// `versions_seen` only ever holds channel versions; see
+/// [`join_arrivals`](Self::join_arrivals) and [`run_count`](Self::run_count).
+///
 /// # Examples
 ///
 /// ```rust
@@ -163,10 +394,151 @@ impl SchedulerRunContext {
 /// // Later checks will use this information for gating
 /// assert!(!scheduler.should_run(&sched_state, "node_a", &snapshot));
 /// ```
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SchedulerState {
     /// `versions_seen[node_id][channel]` stores the last version observed when the node ran.
     pub versions_seen: FxHashMap<String, FxHashMap<String, u64>>,
+    /// `pending_joins[target][origin]` stores the step at which `origin`
+    /// routed to the wait-for-all join `target`; both are `NodeKind::encode()`d.
+    #[serde(default)]
+    pub pending_joins: FxHashMap<String, FxHashMap<String, u64>>,
+    /// Encoded origin of each per-incoming-edge frontier slot; `None` marks a
+    /// merged slot.
+    #[serde(default)]
+    pub frontier_origins: Vec<Option<String>>,
+    /// Times each node with a quota has run in this session, keyed by
+    /// `NodeKind::encode()`.
+    #[serde(default)]
+    pub run_counts: FxHashMap<String, u64>,
+}
+
+/// `versions_seen` entry prefix that held join arrivals before they had their own field.
+const LEGACY_JOIN_ARRIVALS_PREFIX: &str = "__weavegraph_join__:";
+/// `versions_seen` entry that held frontier slot origins before they had their own field.
+const LEGACY_FRONTIER_ORIGINS_KEY: &str = "__weavegraph_incoming__";
+/// `versions_seen` entry that held quota run counts before they had their own field.
+const LEGACY_RUN_COUNTS_KEY: &str = "__weavegraph_runs__";
+
+impl SchedulerState {
+    /// Move bookkeeping that older checkpoints stored under reserved
+    /// `versions_seen` keys into its own fields.
+    pub(crate) fn migrate_legacy_entries(&mut self) {
+        let legacy: Vec<String> = self
+            .versions_seen
+            .keys()
+            .filter(|key| key.starts_with("__weavegraph_"))
+            .cloned()
+            .collect();
+        for key in legacy {
+            let Some(entries) = self.versions_seen.remove(&key) else {
+                continue;
+            };
+            if let Some(target) = key.strip_prefix(LEGACY_JOIN_ARRIVALS_PREFIX) {
+                self.pending_joins
+                    .entry(target.to_string())
+                    .or_default()
+                    .extend(entries);
+            } else if key == LEGACY_RUN_COUNTS_KEY {
+                for (node, count) in entries {
+                    *self.run_counts.entry(node).or_default() += count;
+                }
+            } else if key == LEGACY_FRONTIER_ORIGINS_KEY && self.frontier_origins.is_empty() {
+                for slot in entries.keys() {
+                    let Some((index, origin)) = slot.split_once(':') else {
+                        continue;
+                    };
+                    let Ok(index) = index.parse::<usize>() else {
+                        continue;
+                    };
+                    if self.frontier_origins.len() <= index {
+                        self.frontier_origins.resize(index + 1, None);
+                    }
+                    self.frontier_origins[index] = Some(origin.to_string());
+                }
+            }
+        }
+    }
+
+    /// Record that `origin` routed to the wait-for-all join `target` at `step`.
+    pub(crate) fn record_join_arrival(&mut self, target: &NodeKind, origin: &NodeKind, step: u64) {
+        self.pending_joins
+            .entry(target.encode())
+            .or_default()
+            .insert(origin.encode(), step);
+    }
+
+    /// Nodes that have routed to the wait-for-all join `target` since it last
+    /// ran, sorted by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use weavegraph::schedulers::SchedulerState;
+    /// use weavegraph::types::NodeKind;
+    ///
+    /// let state = SchedulerState::default();
+    /// assert!(state.join_arrivals(&NodeKind::Custom("merge".into())).is_empty());
+    /// ```
+    #[must_use]
+    pub fn join_arrivals(&self, target: &NodeKind) -> Vec<NodeKind> {
+        let mut arrivals: Vec<NodeKind> = self
+            .pending_joins
+            .get(&target.encode())
+            .map(|arrivals| arrivals.keys().map(|key| NodeKind::decode(key)).collect())
+            .unwrap_or_default();
+        arrivals.sort_by_key(NodeKind::encode);
+        arrivals
+    }
+
+    /// Whether `target` has pending arrivals that include every node in `required`.
+    pub(crate) fn join_ready(&self, target: &NodeKind, required: &[NodeKind]) -> bool {
+        self.pending_joins
+            .get(&target.encode())
+            .is_some_and(|arrivals| {
+                required
+                    .iter()
+                    .all(|origin| arrivals.contains_key(&origin.encode()))
+            })
+    }
+
+    /// Forget the arrivals recorded for `target`.
+    pub(crate) fn clear_join(&mut self, target: &NodeKind) {
+        self.pending_joins.remove(&target.encode());
+    }
+
+    /// Times `node` has run in this session, as tracked for
+    /// [`NodeQuotas`](crate::graphs::NodeQuotas).
+    ///
+    /// Only nodes with a quota are counted; others always report `0`.
+    #[must_use]
+    pub fn run_count(&self, node: &NodeKind) -> u64 {
+        self.run_counts.get(&node.encode()).copied().unwrap_or(0)
+    }
+
+    /// Count one run of `node`.
+    pub(crate) fn record_run(&mut self, node: &NodeKind) {
+        *self.run_counts.entry(node.encode()).or_default() += 1;
+    }
+
+    /// Record the origin of each frontier slot; `None` marks a merged slot.
+    pub(crate) fn set_frontier_origins(&mut self, origins: &[Option<NodeKind>]) {
+        self.frontier_origins = if origins.iter().all(Option::is_none) {
+            Vec::new()
+        } else {
+            origins
+                .iter()
+                .map(|origin| origin.as_ref().map(NodeKind::encode))
+                .collect()
+        };
+    }
+
+    /// Origin recorded for frontier slot `index`, if any.
+    pub(crate) fn frontier_origin(&self, index: usize) -> Option<NodeKind> {
+        self.frontier_origins
+            .get(index)?
+            .as_deref()
+            .map(NodeKind::decode)
+    }
 }
 
 /// High-performance frontier scheduler with version gating and bounded concurrency.
@@ -240,15 +612,25 @@ pub struct Scheduler {
 ///             eprintln!("Node {:?} failed at step {}: {}", kind, step, source);
 ///             // Handle node-specific failure
 ///         }
+///         SchedulerError::NodePanic { kind, step, message, .. } => {
+///             eprintln!("Node {:?} panicked at step {}: {}", kind, step, message);
+///             // Handle a bug inside node code
+///         }
+///         SchedulerError::Cancelled { step, aborted } => {
+///             eprintln!("Run cancelled at step {}; dropped {:?}", step, aborted);
+///             // Handle a cancelled run
+///         }
 ///         SchedulerError::Join(join_error) => {
 ///             eprintln!("Task coordination failed: {}", join_error);
 ///             // Handle system-level failure
 ///         }
+///         other => eprintln!("Scheduler error: {}", other),
 ///     }
 /// }
 /// ```
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "diagnostics", derive(miette::Diagnostic))]
+#[non_exhaustive]
 pub enum SchedulerError {
     /// A node in the frontier was not found in the registry.
     ///
@@ -292,6 +674,55 @@ pub enum SchedulerError {
         source: NodeError,
     },
 
+    /// A node panicked while running.
+    ///
+    /// The panic is caught at the node boundary so it cannot unwind through
+    /// the runner. The runner records it in the errors channel like a
+    /// [`NodeRun`](Self::NodeRun) failure and the session stays resumable.
+    #[error("node {kind:?} panicked at step {step}: {message}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::scheduler::node_panic),
+            help(
+                "Inspect `trace` for the panic location; nodes should return NodeError instead of panicking."
+            )
+        )
+    )]
+    NodePanic {
+        /// The node kind that panicked.
+        kind: NodeKind,
+        /// The workflow step at which the node panicked.
+        step: u64,
+        /// The panic payload rendered as text.
+        message: String,
+        /// Backtrace captured at the panic site, or empty unless
+        /// [`install_node_panic_hook`] was called.
+        trace: String,
+    },
+
+    /// The run was cancelled during the superstep.
+    ///
+    /// Nothing the step's nodes produced is applied, so the session resumes
+    /// from the step's starting state. `aborted` lists the nodes that were
+    /// dropped because they did not return within the grace period.
+    #[error("run cancelled at step {step}")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(weavegraph::scheduler::cancelled),
+            help(
+                "Nodes listed in `aborted` ignored cancellation; race long awaits against `NodeContext::cancelled`."
+            )
+        )
+    )]
+    Cancelled {
+        /// The workflow step that was cancelled.
+        step: u64,
+        /// Nodes dropped after the grace period, in completion order.
+        aborted: Vec<NodeKind>,
+    },
+
     /// A task join operation failed.
     ///
     /// This error occurs when there's a problem with the async task coordination,
@@ -331,10 +762,11 @@ impl Scheduler {
 
     /// Helper to expose channel versions as generic (name, version) pairs.
     #[inline]
-    fn channel_versions(snap: &StateSnapshot) -> [(&'static str, u64); 2] {
+    fn channel_versions(snap: &StateSnapshot) -> [(&'static str, u64); 3] {
         [
             ("messages", snap.messages_version as u64),
             ("extra", snap.extra_version as u64),
+            ("blobs", snap.blobs_version as u64),
         ]
     }
 
@@ -551,7 +983,8 @@ impl Scheduler {
     /// # Error Handling
     ///
     /// - **Node Failures**: If any node returns an error, the entire superstep fails
-    /// - **Task Panics**: Panicking nodes result in `SchedulerError::Join`
+    /// - **Node Panics**: Panicking nodes are caught and reported as
+    ///   `SchedulerError::NodePanic` with the panic message and backtrace
     /// - **Missing Nodes**: Panics if frontier contains nodes not in registry
     #[instrument(skip(self, state, nodes, frontier, snap, run_context))]
     pub async fn superstep(
@@ -563,19 +996,32 @@ impl Scheduler {
         step: u64,
         run_context: SchedulerRunContext,
     ) -> Result<StepRunResult, SchedulerError> {
+        if run_context
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(SchedulerError::Cancelled {
+                step,
+                aborted: Vec::new(),
+            });
+        }
+
         // Partition frontier into to_run vs skipped using a skip predicate and version gating.
         let channels = Self::channel_versions(&snap);
         // Skip virtual Start and End nodes (they are not executed, only structural)
         let skip_predicate = |k: &NodeKind| matches!(k, NodeKind::Start | NodeKind::End);
         let mut to_run: Vec<NodeKind> = Vec::new();
+        let mut incoming: Vec<Option<NodeKind>> = Vec::new();
         let mut skipped_kinds: Vec<NodeKind> = Vec::new();
-        for k in frontier.into_iter() {
+        for (index, k) in frontier.into_iter().enumerate() {
             if skip_predicate(&k) {
                 skipped_kinds.push(k);
                 continue;
             }
             let id_str = format!("{:?}", k);
             if self.should_run_with(state, &id_str, &channels) {
+                incoming.push(state.frontier_origin(index));
                 to_run.push(k);
             } else {
                 skipped_kinds.push(k);
@@ -597,56 +1043,211 @@ impl Scheduler {
             }
         }
 
+        let scheduled_at = Instant::now();
+        let grace_period = run_context.cancel_grace_period;
         let tasks = to_run_ids
             .iter()
             .cloned()
-            .zip(to_run.clone().into_iter())
-            .map(|(id_str, kind)| {
+            .zip(to_run.clone().into_iter().zip(incoming))
+            .enumerate()
+            .map(|(index, (id_str, (kind, incoming)))| {
                 // SAFETY: We validated all nodes exist above, so this unwrap is safe.
                 let node = nodes.get(&kind).unwrap().clone();
                 let event_emitter = Arc::clone(&run_context.event_emitter);
                 let clock = run_context.clock.clone();
                 let invocation_id = run_context.invocation_id.clone();
+                let partial_stream = run_context
+                    .partial_stream
+                    .as_ref()
+                    .map(|stream| stream.for_node(kind.clone()));
+                let recorder = Arc::new(NodeMetricsRecorder::default());
+                let cancellation = run_context
+                    .cancellation
+                    .as_ref()
+                    .map_or_else(CancellationToken::new, CancellationToken::child_token);
                 let ctx = NodeContext {
                     node_id: id_str.clone(),
                     step,
                     event_emitter,
                     clock,
                     invocation_id,
+                    partial_stream,
+                    metrics: Some(Arc::clone(&recorder)),
+                    rng_seed: run_context.rng_seed,
+                    attempt: run_context.attempt,
+                    llm_audit: run_context.llm_audit.clone(),
+                    incoming,
+                    feature_flags: run_context.feature_flags.clone(),
+                    derived: run_context.derived.clone(),
+                    cancellation: cancellation.clone(),
                 };
-                let s = snap.clone();
+                let span = crate::telemetry::node_span(
+                    &id_str,
+                    step,
+                    run_context.invocation_id.as_deref(),
+                    &run_context.event_emitter,
+                );
+                let s = run_context.snapshot_views.snapshot_for(&kind, &snap);
                 async move {
-                    // Return Result and let caller collect
-                    let out = node.run(s, ctx).await;
-                    (kind, out)
+                    // Return Result and let caller collect; panics are caught at the node boundary.
+                    // Tasks start when a concurrency slot frees up.
+                    let started = Instant::now();
+                    let queue_wait = started.duration_since(scheduled_at);
+                    let out = if cancellation.is_cancelled() {
+                        NodeOutcome::NotStarted
+                    } else {
+                        let run = NodePoll(Box::pin(node.run(s, ctx).instrument(span)));
+                        // Cooperative phase first; the grace timer starts on cancellation.
+                        let hard_abort = async {
+                            cancellation.cancelled().await;
+                            tokio::time::sleep(grace_period).await;
+                        };
+                        tokio::select! {
+                            biased;
+                            out = AssertUnwindSafe(run).catch_unwind() => NodeOutcome::Finished(out),
+                            () = hard_abort => NodeOutcome::Aborted,
+                        }
+                    };
+                    let timing = NodeTiming {
+                        started_at: started,
+                        queue_wait,
+                        duration: started.elapsed(),
+                    };
+                    (index, kind, out, recorder.finish(timing.duration), timing)
                 }
             });
 
         // Execute with bounded concurrency; completion order may differ.
-        let mut outputs: Vec<(NodeKind, NodePartial)> = Vec::new();
+        let mut completed: Vec<(usize, NodeKind, NodePartial, NodeMetrics, NodeTiming)> =
+            Vec::new();
         let mut stream = stream::iter(tasks).buffer_unordered(self.concurrency_limit);
-        while let Some((kind, res)) = stream.next().await {
+        let mut saturation = SuperstepSaturation {
+            step,
+            concurrency_limit: self.concurrency_limit,
+            tasks: to_run.len(),
+            ..SuperstepSaturation::default()
+        };
+        let cancellation = run_context.cancellation.clone();
+        let mut pending = vec![true; to_run.len()];
+        let mut cancelling = false;
+        let mut aborted: Vec<NodeKind> = Vec::new();
+        loop {
+            let next = match cancellation.as_ref().filter(|_| !cancelling) {
+                Some(token) => tokio::select! {
+                    biased;
+                    () = token.cancelled() => None,
+                    next = stream.next() => Some(next),
+                },
+                None => Some(stream.next().await),
+            };
+            if !cancelling
+                && cancellation
+                    .as_ref()
+                    .is_some_and(CancellationToken::is_cancelled)
+            {
+                cancelling = true;
+                let nodes: Vec<&String> = to_run_ids
+                    .iter()
+                    .zip(&pending)
+                    .filter_map(|(id, pending)| pending.then_some(id))
+                    .collect();
+                let grace_ms = u64::try_from(grace_period.as_millis()).unwrap_or(u64::MAX);
+                let mut metadata = FxHashMap::default();
+                metadata.insert("nodes".into(), serde_json::json!(nodes));
+                metadata.insert("grace_period_ms".into(), serde_json::json!(grace_ms));
+                emit_cancellation(
+                    &run_context.event_emitter,
+                    None,
+                    step,
+                    "cooperative",
+                    metadata,
+                );
+            }
+            let Some(next) = next else {
+                continue;
+            };
+            let Some((index, kind, res, metrics, timing)) = next else {
+                break;
+            };
+            pending[index] = false;
+            let queue_wait_micros =
+                u64::try_from(timing.queue_wait.as_micros()).unwrap_or(u64::MAX);
+            saturation.total_queue_wait_micros = saturation
+                .total_queue_wait_micros
+                .saturating_add(queue_wait_micros);
+            saturation.max_queue_wait_micros =
+                saturation.max_queue_wait_micros.max(queue_wait_micros);
+            saturation.busy_micros = saturation
+                .busy_micros
+                .saturating_add(metrics.duration_micros);
             match res {
-                Ok(part) => outputs.push((kind, part)),
-                Err(e) => {
+                NodeOutcome::Finished(Ok(Ok(part))) => {
+                    completed.push((index, kind, part, metrics, timing));
+                }
+                // Nodes typically bail out with an error once cancelled.
+                NodeOutcome::Finished(Ok(Err(_))) if cancelling => {}
+                NodeOutcome::NotStarted => {}
+                NodeOutcome::Aborted => {
+                    emit_cancellation(
+                        &run_context.event_emitter,
+                        Some(to_run_ids[index].clone()),
+                        step,
+                        "abort",
+                        FxHashMap::default(),
+                    );
+                    aborted.push(kind);
+                }
+                NodeOutcome::Finished(Ok(Err(e))) => {
                     return Err(SchedulerError::NodeRun {
                         kind,
                         step,
                         source: e,
                     });
                 }
+                NodeOutcome::Finished(Err(payload)) => {
+                    let trace = NODE_PANIC_TRACE
+                        .with(|slot| slot.borrow_mut().take())
+                        .unwrap_or_default();
+                    return Err(SchedulerError::NodePanic {
+                        kind,
+                        step,
+                        message: panic_message(payload.as_ref()),
+                        trace,
+                    });
+                }
             }
         }
 
+        if cancelling {
+            return Err(SchedulerError::Cancelled { step, aborted });
+        }
+
+        saturation.wall_micros =
+            u64::try_from(scheduled_at.elapsed().as_micros()).unwrap_or(u64::MAX);
+
         // Record versions seen for nodes that ran.
         for id in &to_run_ids {
             self.record_seen_with(state, id, &channels);
         }
 
+        // Restore scheduling order; a node can run more than once per step.
+        completed.sort_by_key(|(index, ..)| *index);
+        let mut outputs = Vec::with_capacity(completed.len());
+        let mut node_metrics = Vec::with_capacity(completed.len());
+        let mut node_timings = Vec::with_capacity(completed.len());
+        for (_, kind, part, metrics, timing) in completed {
+            outputs.push((kind.clone(), part));
+            node_metrics.push((kind.clone(), metrics));
+            node_timings.push((kind, timing));
+        }
+
         Ok(StepRunResult {
             ran_nodes: to_run,
             skipped_nodes: skipped_kinds,
             outputs,
+            node_metrics,
+            node_timings,
+            saturation,
         })
     }
 }
@@ -0,0 +1,75 @@
+//! JSON Schemas for the data shapes Weavegraph persists and emits.
+//!
+//! External tooling (log processors, database consumers written in other
+//! languages, contract tests) can use these schemas as a machine-readable
+//! contract for:
+//!
+//! - [`PersistedState`]: the `state_json` column written by the SQL checkpointers
+//! - [`PersistedCheckpoint`]: the full serialized checkpoint shape
+//! - [`Event`]: the serde representation of bus events (as forwarded by
+//!   [`ChannelSink`](crate::event_bus::ChannelSink) consumers that serialize events)
+//! - [`Message`]: a single chat message
+//!
+//! The normalized event envelope written by [`JsonLinesSink`](crate::event_bus::JsonLinesSink)
+//! is documented separately in `docs/schemas/event.json`.
+//!
+//! Requires the `schema` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use weavegraph::schema;
+//!
+//! let state = schema::persisted_state_schema();
+//! assert_eq!(state.get("title"), Some(&serde_json::json!("PersistedState")));
+//!
+//! for (name, schema) in schema::all_schemas() {
+//!     let rendered = serde_json::to_string_pretty(&schema).unwrap();
+//!     assert!(!rendered.is_empty(), "{name} schema should render");
+//! }
+//! ```
+
+use schemars::{Schema, schema_for};
+
+use crate::event_bus::Event;
+use crate::message::Message;
+use crate::runtimes::persistence::{PersistedCheckpoint, PersistedState};
+
+/// JSON Schema for [`PersistedState`].
+#[must_use]
+pub fn persisted_state_schema() -> Schema {
+    schema_for!(PersistedState)
+}
+
+/// JSON Schema for [`PersistedCheckpoint`], the serialized form of a
+/// [`Checkpoint`](crate::runtimes::Checkpoint).
+#[must_use]
+pub fn checkpoint_schema() -> Schema {
+    schema_for!(PersistedCheckpoint)
+}
+
+/// JSON Schema for the serde representation of [`Event`].
+#[must_use]
+pub fn event_schema() -> Schema {
+    schema_for!(Event)
+}
+
+/// JSON Schema for [`Message`].
+#[must_use]
+pub fn message_schema() -> Schema {
+    schema_for!(Message)
+}
+
+/// All published schemas keyed by a stable file-friendly name.
+///
+/// Names are `persisted_state`, `checkpoint`, `event`, and `message`, in that
+/// order, so tooling can write them out as `<name>.schema.json`.
+#[must_use]
+pub fn all_schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        ("persisted_state", persisted_state_schema()),
+        ("checkpoint", checkpoint_schema()),
+        ("event", event_schema()),
+        ("message", message_schema()),
+    ]
+}
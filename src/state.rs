@@ -11,10 +11,11 @@
 //!
 //! # Channels
 //!
-//! State is organized into three main channels:
+//! State is organized into four main channels:
 //! - **Messages**: Conversation messages and chat data
 //! - **Extra**: Custom metadata and intermediate results
 //! - **Errors**: Error events and diagnostic information
+//! - **Blobs**: Named binary payloads such as images or audio
 //!
 //! # Examples
 //!
@@ -36,15 +37,17 @@
 //! ```
 
 use rustc_hash::FxHashMap;
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use thiserror::Error;
 
 use crate::{
-    channels::{Channel, ErrorsChannel, ExtrasChannel, MessagesChannel},
+    channels::{Blob, BlobsChannel, Channel, ErrorsChannel, ExtrasChannel, MessagesChannel},
+    control::{COMMAND_INBOX_KEY, CommandInbox, NodeCommand},
     message::{Message, Role},
+    types::{ChannelType, NodeKind},
 };
 
 /// Lifecycle classification for a state slot.
@@ -310,6 +313,8 @@ pub struct VersionedState {
     pub extra: ExtrasChannel,
     /// Error channel for diagnostic information
     pub errors: ErrorsChannel,
+    /// Blob channel for binary payloads
+    pub blobs: BlobsChannel,
 }
 
 /// Immutable snapshot of workflow state at a specific point in time.
@@ -327,6 +332,8 @@ pub struct VersionedState {
 /// - `extra_version`: Version of extra channel when snapshot was taken
 /// - `errors`: Cloned error events at snapshot time
 /// - `errors_version`: Version of errors channel when snapshot was taken
+/// - `blobs`: Binary payloads at snapshot time (the bytes are shared, not copied)
+/// - `blobs_version`: Version of blobs channel when snapshot was taken
 ///
 /// # Usage
 ///
@@ -351,7 +358,10 @@ pub struct VersionedState {
 /// assert_eq!(snapshot.extra.get("key"), Some(&json!("value")));
 /// assert!(state.extra.snapshot().is_empty());
 /// ```
-#[derive(Clone, Debug)]
+///
+/// Snapshots serialize to JSON with the same field names, which is the
+/// boundary used when nodes are implemented outside Rust.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StateSnapshot {
     /// Messages at the time of snapshot
     pub messages: Vec<Message>,
@@ -365,6 +375,16 @@ pub struct StateSnapshot {
     pub errors: Vec<crate::channels::errors::ErrorEvent>,
     /// Version of errors channel when snapshot was taken
     pub errors_version: u32,
+    /// Binary payloads at the time of snapshot
+    #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+    pub blobs: FxHashMap<String, Blob>,
+    /// Version of blobs channel when snapshot was taken
+    #[serde(default = "initial_version")]
+    pub blobs_version: u32,
+}
+
+fn initial_version() -> u32 {
+    1
 }
 
 impl VersionedState {
@@ -404,6 +424,7 @@ impl VersionedState {
             messages: MessagesChannel::new(messages, 1),
             extra: ExtrasChannel::default(),
             errors: ErrorsChannel::default(),
+            blobs: BlobsChannel::default(),
         }
     }
 
@@ -447,6 +468,7 @@ impl VersionedState {
             messages: MessagesChannel::new(messages, 1),
             extra: ExtrasChannel::default(),
             errors: ErrorsChannel::default(),
+            blobs: BlobsChannel::default(),
         }
     }
 
@@ -543,6 +565,26 @@ impl VersionedState {
         self
     }
 
+    /// Stores a binary payload in the blobs channel under `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use weavegraph::channels::{Blob, Channel};
+    /// use weavegraph::state::VersionedState;
+    ///
+    /// let mut state = VersionedState::new_with_user_message("Describe this image");
+    /// state.add_blob("upload", Blob::new(vec![1, 2, 3]).with_content_type("image/png"));
+    ///
+    /// assert_eq!(state.blobs.len(), 1);
+    /// assert_eq!(state.blobs.total_bytes(), 3);
+    /// ```
+    #[must_use = "consider using the returned self for method chaining"]
+    pub fn add_blob(&mut self, key: &str, blob: Blob) -> &mut Self {
+        self.blobs.get_mut().insert(key.to_string(), blob);
+        self
+    }
+
     /// Adds a typed value to the extra channel using a schema-versioned key.
     ///
     /// The value is serialized to JSON and stored under
@@ -606,11 +648,23 @@ impl VersionedState {
             extra_version: self.extra.version(),
             errors: self.errors.snapshot(),
             errors_version: self.errors.version(),
+            blobs: self.blobs.snapshot(),
+            blobs_version: self.blobs.version(),
         }
     }
 }
 
 impl StateSnapshot {
+    /// Commands queued for `node` before this snapshot was taken, highest
+    /// priority first.
+    ///
+    /// See [`NodeCommand`]. The runner clears
+    /// them after `node` runs.
+    #[must_use]
+    pub fn commands_for(&self, node: &NodeKind) -> Vec<NodeCommand> {
+        CommandInbox::from_value(self.extra.get(COMMAND_INBOX_KEY)).for_node(node)
+    }
+
     /// Read an optional typed value from the extra channel.
     ///
     /// Returns `Ok(None)` when the slot is absent. Deserialization errors are
@@ -645,6 +699,121 @@ impl StateSnapshot {
     }
 }
 
+// ============================================================================
+// Fingerprints
+// ============================================================================
+
+/// Prefix of the combined fingerprint input; bump it if the encoding changes.
+const FINGERPRINT_VERSION: &str = "weavegraph-state-v1";
+
+impl VersionedState {
+    /// Content hash of the whole state: 32 lowercase hex digits.
+    ///
+    /// Two states with equal channel contents have equal fingerprints on every
+    /// platform and run, regardless of map iteration order. Channel version
+    /// counters are not part of the hash, so a node that rewrites a value
+    /// with itself leaves the fingerprint unchanged. See
+    /// [`StateSnapshot::fingerprint`] for the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use weavegraph::channels::Channel;
+    /// use weavegraph::state::VersionedState;
+    ///
+    /// let a = VersionedState::new_with_user_message("hi");
+    /// let mut b = VersionedState::new_with_user_message("hi");
+    /// b.messages.set_version(7);
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        self.snapshot().fingerprint()
+    }
+
+    /// Content hash of one channel, in the same format as
+    /// [`fingerprint`](Self::fingerprint).
+    #[must_use]
+    pub fn channel_fingerprint(&self, channel: ChannelType) -> String {
+        match channel {
+            ChannelType::Message => messages_fingerprint(&self.messages.snapshot()),
+            ChannelType::Extra => extra_fingerprint(&self.extra.snapshot()),
+            ChannelType::Error => errors_fingerprint(&self.errors.snapshot()),
+            ChannelType::Blob => blobs_fingerprint(&self.blobs.snapshot()),
+        }
+    }
+}
+
+impl StateSnapshot {
+    /// Content hash of the snapshot's channels: 32 lowercase hex digits.
+    ///
+    /// Each channel is encoded as canonical JSON (object keys sorted at every
+    /// level, see [`canonical_json`](crate::utils::json_ext::canonical_json))
+    /// and hashed with the 128-bit FNV-1a also used by [`Blob::digest`]; blobs
+    /// contribute their name, content type, and digest. The combined
+    /// fingerprint hashes the four channel fingerprints. Version counters are
+    /// ignored. It is not a cryptographic hash.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        let channels = [
+            ("messages", messages_fingerprint(&self.messages)),
+            ("extra", extra_fingerprint(&self.extra)),
+            ("errors", errors_fingerprint(&self.errors)),
+            ("blobs", blobs_fingerprint(&self.blobs)),
+        ];
+        let mut input = String::from(FINGERPRINT_VERSION);
+        for (name, hash) in channels {
+            input.push_str(&format!("\n{name}={hash}"));
+        }
+        crate::channels::digest(input.as_bytes())
+    }
+
+    /// Content hash of one channel of the snapshot.
+    #[must_use]
+    pub fn channel_fingerprint(&self, channel: ChannelType) -> String {
+        match channel {
+            ChannelType::Message => messages_fingerprint(&self.messages),
+            ChannelType::Extra => extra_fingerprint(&self.extra),
+            ChannelType::Error => errors_fingerprint(&self.errors),
+            ChannelType::Blob => blobs_fingerprint(&self.blobs),
+        }
+    }
+}
+
+fn json_fingerprint(value: &Value) -> String {
+    crate::channels::digest(crate::utils::json_ext::canonical_json(value).as_bytes())
+}
+
+fn messages_fingerprint(messages: &[Message]) -> String {
+    json_fingerprint(&serde_json::to_value(messages).unwrap_or(Value::Null))
+}
+
+fn extra_fingerprint(extra: &FxHashMap<String, Value>) -> String {
+    let object = extra
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    json_fingerprint(&Value::Object(object))
+}
+
+fn errors_fingerprint(errors: &[crate::channels::errors::ErrorEvent]) -> String {
+    json_fingerprint(&serde_json::to_value(errors).unwrap_or(Value::Null))
+}
+
+fn blobs_fingerprint(blobs: &FxHashMap<String, Blob>) -> String {
+    let object = blobs
+        .iter()
+        .map(|(name, blob)| {
+            let entry = serde_json::json!({
+                "content_type": blob.content_type(),
+                "digest": blob.digest(),
+            });
+            (name.clone(), entry)
+        })
+        .collect();
+    json_fingerprint(&Value::Object(object))
+}
+
 /// Builder for constructing VersionedState with fluent API.
 ///
 /// `VersionedStateBuilder` provides an ergonomic way to construct workflow state
@@ -675,6 +844,7 @@ impl StateSnapshot {
 pub struct VersionedStateBuilder {
     messages: Vec<Message>,
     extra: FxHashMap<String, Value>,
+    blobs: FxHashMap<String, Blob>,
 }
 
 impl VersionedStateBuilder {
@@ -792,6 +962,12 @@ impl VersionedStateBuilder {
         self
     }
 
+    /// Adds a binary payload to the blobs channel.
+    pub fn with_blob(mut self, key: &str, blob: Blob) -> Self {
+        self.blobs.insert(key.to_string(), blob);
+        self
+    }
+
     /// Adds a typed value to the extra channel using a schema-versioned key.
     pub fn with_typed_extra<T: Serialize>(
         mut self,
@@ -838,6 +1014,7 @@ impl VersionedStateBuilder {
             messages: MessagesChannel::new(self.messages, 1),
             extra: ExtrasChannel::new(self.extra, 1),
             errors: ErrorsChannel::default(),
+            blobs: BlobsChannel::new(self.blobs, 1),
         }
     }
 }
@@ -4,6 +4,11 @@ use crate::event_bus::Event;
 use std::io::IsTerminal;
 use std::sync::OnceLock;
 
+mod tracing_layer;
+
+pub use tracing_layer::EventBusLayer;
+pub(crate) use tracing_layer::node_span;
+
 /// ANSI escape code for green context text in telemetry output.
 pub const CONTEXT_COLOR: &str = "\x1b[32m"; // green
 /// ANSI escape code for magenta line text in telemetry output.
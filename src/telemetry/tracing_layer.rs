@@ -0,0 +1,249 @@
+//! Bridge from `tracing` to the workflow event stream.
+//!
+//! The scheduler runs every node inside a `weavegraph.node` span that knows
+//! which event emitter the run uses. [`EventBusLayer`] watches for `tracing`
+//! events recorded inside such spans, including those from third-party
+//! libraries a node calls, and republishes them on that emitter so they show
+//! up next to `ctx.emit` events.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+use tracing::field::{Field, Visit};
+use tracing::span::Attributes;
+use tracing::{Event as TracingEvent, Id, Level, Span, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::event_bus::{Event, EventEmitter, TRACING_SCOPE};
+
+/// Span name the scheduler uses for node executions.
+pub(crate) const NODE_SPAN_NAME: &str = "weavegraph.node";
+
+// ============================================================================
+// Node spans
+// ============================================================================
+
+/// Per-span data attached by [`EventBusLayer`] to node execution spans.
+#[derive(Clone)]
+struct NodeSpanData {
+    node_id: String,
+    step: u64,
+    invocation_id: Option<String>,
+    emitter: Arc<dyn EventEmitter>,
+}
+
+thread_local! {
+    /// Node data handed from [`node_span`] to the layer's `on_new_span`.
+    static PENDING_NODE: RefCell<Option<NodeSpanData>> = const { RefCell::new(None) };
+    /// Set while the layer emits, so logging inside emitters cannot recurse.
+    static FORWARDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Create the span a node runs in.
+///
+/// The emitter cannot be recorded as a span field, so it is parked in a
+/// thread-local for the duration of span creation, where [`EventBusLayer`]
+/// picks it up. Without the layer installed this is an ordinary span.
+pub(crate) fn node_span(
+    node_id: &str,
+    step: u64,
+    invocation_id: Option<&str>,
+    emitter: &Arc<dyn EventEmitter>,
+) -> Span {
+    PENDING_NODE.with(|slot| {
+        *slot.borrow_mut() = Some(NodeSpanData {
+            node_id: node_id.to_string(),
+            step,
+            invocation_id: invocation_id.map(str::to_string),
+            emitter: Arc::clone(emitter),
+        });
+    });
+    let span = tracing::info_span!(
+        target: "weavegraph::node",
+        NODE_SPAN_NAME,
+        node = node_id,
+        step,
+        invocation_id = invocation_id,
+    );
+    PENDING_NODE.with(|slot| slot.borrow_mut().take());
+    span
+}
+
+// ============================================================================
+// Layer
+// ============================================================================
+
+/// `tracing` layer that forwards events recorded during node runs to the
+/// run's event stream.
+///
+/// Events are published as node events with scope [`TRACING_SCOPE`], the
+/// enclosing node's id and step, and metadata holding `level`, `target`,
+/// `fields`, and `invocation_id` when the run has one. Events outside node
+/// runs are ignored, as are events from `weavegraph`'s own targets unless
+/// [`include_framework_events`](Self::include_framework_events) is set.
+///
+/// Node spans are `INFO`-level spans with target `weavegraph::node`; the
+/// subscriber's filter must enable them for events to be attributed.
+///
+/// # Examples
+///
+/// ```
+/// use tracing::Level;
+/// use tracing_subscriber::prelude::*;
+/// use weavegraph::telemetry::EventBusLayer;
+///
+/// let subscriber = tracing_subscriber::registry()
+///     .with(EventBusLayer::new().with_max_level(Level::DEBUG));
+/// let _guard = tracing::subscriber::set_default(subscriber);
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct EventBusLayer {
+    max_level: Level,
+    include_framework_events: bool,
+}
+
+impl Default for EventBusLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBusLayer {
+    /// Forward `INFO` and more severe events from non-framework targets.
+    pub fn new() -> Self {
+        Self {
+            max_level: Level::INFO,
+            include_framework_events: false,
+        }
+    }
+
+    /// Forward events at `level` and more severe.
+    pub fn with_max_level(mut self, level: Level) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// Also forward events whose target starts with `weavegraph`.
+    pub fn include_framework_events(mut self, include: bool) -> Self {
+        self.include_framework_events = include;
+        self
+    }
+
+    fn accepts(&self, event: &TracingEvent<'_>) -> bool {
+        let metadata = event.metadata();
+        *metadata.level() <= self.max_level
+            && (self.include_framework_events || !is_framework_target(metadata.target()))
+    }
+}
+
+impl<S> Layer<S> for EventBusLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != NODE_SPAN_NAME {
+            return;
+        }
+        let Some(data) = PENDING_NODE.with(|slot| slot.borrow().clone()) else {
+            return;
+        };
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(data);
+        }
+    }
+
+    fn on_event(&self, event: &TracingEvent<'_>, ctx: Context<'_, S>) {
+        if !self.accepts(event) || FORWARDING.with(Cell::get) {
+            return;
+        }
+        let Some(data) = ctx.event_scope(event).and_then(|scope| {
+            scope
+                .into_iter()
+                .find_map(|span| span.extensions().get::<NodeSpanData>().cloned())
+        }) else {
+            return;
+        };
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let mut meta = FxHashMap::default();
+        meta.insert("level".to_string(), Value::from(metadata.level().as_str()));
+        meta.insert("target".to_string(), Value::from(metadata.target()));
+        if !visitor.fields.is_empty() {
+            meta.insert(
+                "fields".to_string(),
+                Value::Object(visitor.fields.into_iter().collect()),
+            );
+        }
+        if let Some(invocation_id) = data.invocation_id {
+            meta.insert("invocation_id".to_string(), Value::String(invocation_id));
+        }
+
+        FORWARDING.with(|flag| flag.set(true));
+        // Forwarding is best effort; a closed bus must not fail the node.
+        let _ = data.emitter.emit(Event::node_message_with_metadata(
+            data.node_id,
+            data.step,
+            TRACING_SCOPE,
+            visitor.message,
+            meta,
+        ));
+        FORWARDING.with(|flag| flag.set(false));
+    }
+}
+
+fn is_framework_target(target: &str) -> bool {
+    target == "weavegraph" || target.starts_with("weavegraph::")
+}
+
+/// Collects the `message` field and all other fields as JSON values.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<(String, Value)>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                Value::String(text) => text,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.push((field.name().to_string(), value));
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, Value::String(format!("{value:?}")));
+    }
+}
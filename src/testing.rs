@@ -0,0 +1,182 @@
+//! Test doubles for exercising graphs without real node implementations.
+//!
+//! [`ScriptedNode`] plays back a queue of predefined [`NodePartial`]s, one per
+//! run, and records the snapshot each run received. Delays and errors can be
+//! injected on specific supersteps to test timeouts, recovery, and error
+//! handling. Clones share their script and recordings, so keep a clone to
+//! inspect the node after handing another to a
+//! [`GraphBuilder`](crate::graphs::GraphBuilder).
+//!
+//! # Examples
+//!
+//! ```
+//! use weavegraph::channels::Channel;
+//! use weavegraph::graphs::GraphBuilder;
+//! use weavegraph::message::Message;
+//! use weavegraph::node::NodePartial;
+//! use weavegraph::state::VersionedState;
+//! use weavegraph::testing::ScriptedNode;
+//! use weavegraph::types::NodeKind;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let reply = ScriptedNode::new()
+//!     .then(NodePartial::new().with_messages(vec![Message::assistant("hello")]));
+//! let app = GraphBuilder::new()
+//!     .add_node(NodeKind::Custom("reply".into()), reply.clone())
+//!     .add_edge(NodeKind::Start, NodeKind::Custom("reply".into()))
+//!     .add_edge(NodeKind::Custom("reply".into()), NodeKind::End)
+//!     .compile()?;
+//!
+//! let state = app.invoke(VersionedState::new_with_user_message("hi")).await?;
+//! assert_eq!(state.messages.len(), 2);
+//! assert_eq!(reply.call_count(), 1);
+//! assert_eq!(reply.snapshots()[0].messages[0].content, "hi");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+
+use crate::node::{Node, NodeContext, NodeError, NodePartial};
+use crate::state::StateSnapshot;
+
+/// One recorded run of a [`ScriptedNode`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ScriptedCall {
+    /// Superstep the node ran in.
+    pub step: u64,
+    /// Execution attempt within the step, starting at 1.
+    pub attempt: u32,
+    /// State the node received.
+    pub snapshot: StateSnapshot,
+}
+
+#[derive(Debug, Default)]
+struct Script {
+    outputs: VecDeque<NodePartial>,
+    delays: FxHashMap<u64, Duration>,
+    errors: FxHashMap<u64, NodeError>,
+    calls: Vec<ScriptedCall>,
+}
+
+/// Node that returns predefined outputs and records what it was given.
+///
+/// Each run records a [`ScriptedCall`], then applies the delay and error
+/// injected for its step, then returns the next queued output, or an empty
+/// [`NodePartial`] once the queue is exhausted. An injected error fails only
+/// the first run of its step and does not consume an output, so the next
+/// run receives the output the failed one would have returned.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedNode {
+    script: Arc<Mutex<Script>>,
+}
+
+impl ScriptedNode {
+    /// A node with an empty script.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A node returning `outputs` in order.
+    #[must_use]
+    pub fn with_outputs(outputs: impl IntoIterator<Item = NodePartial>) -> Self {
+        Self::new().then_all(outputs)
+    }
+
+    /// Queue `output` after the outputs already scripted.
+    #[must_use]
+    pub fn then(self, output: NodePartial) -> Self {
+        self.lock().outputs.push_back(output);
+        self
+    }
+
+    /// Queue `outputs` after the outputs already scripted.
+    #[must_use]
+    pub fn then_all(self, outputs: impl IntoIterator<Item = NodePartial>) -> Self {
+        self.lock().outputs.extend(outputs);
+        self
+    }
+
+    /// Sleep for `delay` before answering in superstep `step`.
+    #[must_use]
+    pub fn delay_on_step(self, step: u64, delay: Duration) -> Self {
+        self.lock().delays.insert(step, delay);
+        self
+    }
+
+    /// Fail the first run in superstep `step` with `error`.
+    #[must_use]
+    pub fn fail_on_step(self, step: u64, error: NodeError) -> Self {
+        self.lock().errors.insert(step, error);
+        self
+    }
+
+    /// Every recorded run, oldest first.
+    #[must_use]
+    pub fn calls(&self) -> Vec<ScriptedCall> {
+        self.lock().calls.clone()
+    }
+
+    /// Snapshots received by every recorded run, oldest first.
+    #[must_use]
+    pub fn snapshots(&self) -> Vec<StateSnapshot> {
+        self.lock()
+            .calls
+            .iter()
+            .map(|call| call.snapshot.clone())
+            .collect()
+    }
+
+    /// Number of recorded runs.
+    #[must_use]
+    pub fn call_count(&self) -> usize {
+        self.lock().calls.len()
+    }
+
+    /// Number of scripted outputs not yet returned.
+    #[must_use]
+    pub fn remaining_outputs(&self) -> usize {
+        self.lock().outputs.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Script> {
+        self.script.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl Node for ScriptedNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let delay = {
+            let mut script = self.lock();
+            script.calls.push(ScriptedCall {
+                step: ctx.step,
+                attempt: ctx.attempt(),
+                snapshot,
+            });
+            script.delays.get(&ctx.step).copied()
+        };
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        let mut script = self.lock();
+        if let Some(error) = script.errors.remove(&ctx.step) {
+            return Err(error);
+        }
+        Ok(script.outputs.pop_front().unwrap_or_default())
+    }
+}
@@ -222,6 +222,94 @@ impl From<&str> for NodeKind {
     }
 }
 
+impl From<String> for NodeKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Start" => NodeKind::Start,
+            "End" => NodeKind::End,
+            _ => NodeKind::Custom(s),
+        }
+    }
+}
+
+/// Compares against the [`Display`](fmt::Display) form: `"Start"`, `"End"`, or the custom name.
+///
+/// ```rust
+/// # use weavegraph::types::NodeKind;
+/// assert_eq!(NodeKind::Custom("router".into()), "router");
+/// assert_eq!(NodeKind::End, "End");
+/// assert!("router" == NodeKind::Custom("router".into()));
+/// ```
+impl PartialEq<str> for NodeKind {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Self::Start => other == "Start",
+            Self::End => other == "End",
+            Self::Custom(name) => name == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for NodeKind {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<NodeKind> for str {
+    fn eq(&self, other: &NodeKind) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<NodeKind> for &str {
+    fn eq(&self, other: &NodeKind) -> bool {
+        other == *self
+    }
+}
+
+/// Build a [`NodeKind`] from a string literal or the `Start` / `End` keywords.
+///
+/// `node_kind!("name")` is shorthand for `NodeKind::Custom("name".into())`
+/// (or the virtual node when the name is `"Start"` or `"End"`). The name must
+/// be a literal or constant, and an empty name is rejected at compile time.
+/// Names that never reach [`GraphBuilder::add_node`](crate::graphs::GraphBuilder::add_node)
+/// are reported by
+/// [`GraphBuilder::unregistered_nodes`](crate::graphs::GraphBuilder::unregistered_nodes)
+/// and by graph compilation.
+///
+/// # Examples
+///
+/// ```rust
+/// use weavegraph::node_kind;
+/// use weavegraph::types::NodeKind;
+///
+/// const ROUTER: &str = "router";
+///
+/// assert_eq!(node_kind!("fetch"), NodeKind::Custom("fetch".into()));
+/// assert_eq!(node_kind!(ROUTER), "router");
+/// assert_eq!(node_kind!(Start), NodeKind::Start);
+/// assert_eq!(node_kind!(End), NodeKind::End);
+/// ```
+///
+/// ```compile_fail
+/// let _ = weavegraph::node_kind!("");
+/// ```
+#[macro_export]
+macro_rules! node_kind {
+    (Start) => {
+        $crate::types::NodeKind::Start
+    };
+    (End) => {
+        $crate::types::NodeKind::End
+    };
+    ($name:expr) => {{
+        const NAME: &str = $name;
+        const _: () = assert!(!NAME.is_empty(), "node names must not be empty");
+        $crate::types::NodeKind::from(NAME)
+    }};
+}
+
 /// Identifies the type of data channel used for state management.
 ///
 /// `ChannelType` represents the different categories of state data that
@@ -258,6 +346,12 @@ pub enum ChannelType {
     /// Provides a flexible key-value store for custom data that nodes
     /// need to share, including configuration and intermediate computations.
     Extra,
+
+    /// Channel for named binary payloads such as images or audio.
+    ///
+    /// Stores raw bytes rather than JSON values, so multimodal data is not
+    /// inflated by base64 in memory or in SQL checkpoints.
+    Blob,
 }
 
 impl fmt::Display for ChannelType {
@@ -266,6 +360,7 @@ impl fmt::Display for ChannelType {
             Self::Message => write!(f, "message"),
             Self::Error => write!(f, "error"),
             Self::Extra => write!(f, "extra"),
+            Self::Blob => write!(f, "blob"),
         }
     }
 }
@@ -347,6 +347,58 @@ pub fn has_structure(value: &Value, expected_keys: &[&str]) -> bool {
     }
 }
 
+/// Serialize `value` as compact JSON with object keys sorted at every level.
+///
+/// The output does not depend on map insertion order or on whether
+/// `serde_json` was built with `preserve_order`, so it is suitable as input to
+/// a content hash.
+///
+/// # Examples
+///
+/// ```rust
+/// use weavegraph::utils::json_ext::canonical_json;
+/// use serde_json::json;
+///
+/// let value = json!({"b": 1, "a": {"d": [true, null], "c": "x"}});
+/// assert_eq!(canonical_json(&value), r#"{"a":{"c":"x","d":[true,null]},"b":1}"#);
+/// ```
+#[must_use]
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
 /// Convert a HashMap to a JSON object.
 ///
 /// # Parameters
@@ -2,13 +2,13 @@ use async_trait::async_trait;
 use futures_util::StreamExt;
 use rustc_hash::FxHashMap;
 use serde_json::Value;
-use weavegraph::channels::Channel;
-use weavegraph::event_bus::STREAM_END_SCOPE;
+use weavegraph::channels::{Blob, Channel, ChannelVersionOverflow};
+use weavegraph::event_bus::{RUN_SUMMARY_SCOPE, STREAM_END_SCOPE};
 use weavegraph::graphs::GraphBuilder;
 use weavegraph::message::{Message, Role};
 use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
 use weavegraph::state::VersionedState;
-use weavegraph::types::NodeKind;
+use weavegraph::types::{ChannelType, NodeKind};
 
 mod common;
 use common::*;
@@ -56,16 +56,82 @@ async fn test_apply_barrier_no_update() {
 }
 
 #[tokio::test]
-async fn test_apply_barrier_saturating_version() {
+async fn test_apply_barrier_blobs_update_last_writer_wins() {
     let app = make_app();
     let state = &mut state_with_user("hi");
-    // push messages version to max to verify saturating add behavior
+    let run_ids = vec![NodeKind::Custom("a".into()), NodeKind::Custom("b".into())];
+    let partials = vec![
+        NodePartial::new().with_blob("frame", Blob::new(vec![1; 4])),
+        NodePartial::new().with_blob(
+            "frame",
+            Blob::new(vec![2; 8]).with_content_type("image/webp"),
+        ),
+    ];
+    let outcome = app.apply_barrier(state, &run_ids, partials).await.unwrap();
+    assert_eq!(outcome.updated_channels, vec!["blobs"]);
+    assert_eq!(state.blobs.version(), 2);
+    assert_eq!(state.blobs.total_bytes(), 8);
+    assert_eq!(
+        state.blobs.snapshot()["frame"].content_type(),
+        Some("image/webp")
+    );
+
+    // Re-writing identical bytes is not a change.
+    let same = NodePartial::new().with_blob(
+        "frame",
+        Blob::new(vec![2; 8]).with_content_type("image/webp"),
+    );
+    let outcome = app
+        .apply_barrier(state, &run_ids[..1], vec![same])
+        .await
+        .unwrap();
+    assert!(outcome.updated_channels.is_empty());
+    assert_eq!(state.blobs.version(), 2);
+}
+
+#[tokio::test]
+async fn test_apply_barrier_version_overflow_is_an_error() {
+    let app = make_app();
+    let state = &mut state_with_user("hi");
+    // versions never wrap: the barrier reports the overflowing channel instead
     state.messages.set_version(u32::MAX);
     let partial = NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, "x")]);
-    app.apply_barrier(state, &[NodeKind::Start], vec![partial])
+    let err = app
+        .apply_barrier(state, &[NodeKind::Start], vec![partial])
         .await
-        .unwrap();
+        .unwrap_err();
+    let overflow = err
+        .downcast_ref::<ChannelVersionOverflow>()
+        .expect("overflow error");
+    assert_eq!(overflow.channel, ChannelType::Message);
+    assert_eq!(overflow.version, u32::MAX);
     assert_eq!(state.messages.version(), u32::MAX);
+    assert_eq!(state.messages.len(), 1);
+}
+
+#[tokio::test]
+async fn test_apply_barrier_overflow_leaves_every_channel_unchanged() {
+    let app = make_app();
+    let state = &mut state_with_user("hi");
+    state.extra.set_version(u32::MAX);
+    let before = state.clone();
+    let mut extra = FxHashMap::default();
+    extra.insert("k".to_string(), Value::from(1));
+    let partial = NodePartial::new()
+        .with_messages(vec![Message::with_role(Role::Assistant, "x")])
+        .with_extra(extra);
+    let err = app
+        .apply_barrier(state, &[NodeKind::Start], vec![partial])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ChannelVersionOverflow>()
+            .expect("overflow error")
+            .channel,
+        ChannelType::Extra
+    );
+    // The message append that preceded the overflow was not applied either.
+    assert_eq!(*state, before);
 }
 
 #[tokio::test]
@@ -137,6 +203,7 @@ async fn invoke_streaming_closes_stream() {
 
     let mut stream = events.into_async_stream();
     let mut seen_non_terminal = 0;
+    let mut summaries = 0;
     let mut sentinel_seen = false;
     while let Some(event) = stream.next().await {
         if event.scope_label() == Some(STREAM_END_SCOPE) {
@@ -145,16 +212,133 @@ async fn invoke_streaming_closes_stream() {
                 "STREAM_END_SCOPE should appear exactly once"
             );
             sentinel_seen = true;
+        } else if event.scope_label() == Some(RUN_SUMMARY_SCOPE) {
+            assert!(!sentinel_seen, "run summary should precede the sentinel");
+            summaries += 1;
         } else {
             seen_non_terminal += 1;
         }
     }
 
     assert_eq!(seen_non_terminal, 1);
+    assert_eq!(summaries, 1);
     assert!(sentinel_seen, "expected terminal sentinel event");
     invocation.join().await.unwrap();
 }
 
+/// Waits for the run to be cancelled, then returns an error.
+struct WaitForCancel;
+
+#[async_trait]
+impl Node for WaitForCancel {
+    async fn run(
+        &self,
+        _: weavegraph::state::StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        ctx.cancelled().await;
+        Err(NodeError::Other("request cancelled".into()))
+    }
+}
+
+#[tokio::test]
+async fn invocation_cancel_stops_run_cooperatively() {
+    use weavegraph::event_bus::CANCELLATION_SCOPE;
+    use weavegraph::runtimes::runner::RunnerError;
+    use weavegraph::schedulers::SchedulerError;
+
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("fetch".into()), WaitForCancel)
+        .add_edge(NodeKind::Start, NodeKind::Custom("fetch".into()))
+        .add_edge(NodeKind::Custom("fetch".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let (invocation, events) = app
+        .invoke_streaming(VersionedState::new_with_user_message("hello"))
+        .await;
+    let mut stream = events.into_async_stream();
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    invocation.cancel();
+
+    let result = invocation.join().await;
+    assert!(
+        matches!(
+            result,
+            Err(RunnerError::Scheduler(SchedulerError::Cancelled { step: 1, ref aborted }))
+                if aborted.is_empty()
+        ),
+        "{result:?}"
+    );
+    let mut cancellation_events = 0;
+    while let Some(event) = stream.next().await {
+        if event.scope_label() == Some(CANCELLATION_SCOPE) {
+            cancellation_events += 1;
+        }
+    }
+    assert_eq!(
+        cancellation_events, 1,
+        "only the cooperative phase was needed"
+    );
+}
+
+#[tokio::test]
+async fn invoke_stream_values_yields_snapshot_per_superstep() {
+    let app = GraphBuilder::new()
+        .add_node(
+            NodeKind::Custom("a".into()),
+            SimpleMessageNode::new("from a"),
+        )
+        .add_node(
+            NodeKind::Custom("b".into()),
+            SimpleMessageNode::new("from b"),
+        )
+        .add_edge(NodeKind::Start, NodeKind::Custom("a".into()))
+        .add_edge(NodeKind::Custom("a".into()), NodeKind::Custom("b".into()))
+        .add_edge(NodeKind::Custom("b".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let values: Vec<_> = app
+        .invoke_stream_values(state_with_user("hello"))
+        .await
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let lengths: Vec<_> = values.iter().map(|s| s.messages.len()).collect();
+    assert_eq!(lengths, vec![1, 2, 3]);
+    assert_eq!(values[1].messages.last().unwrap().content, "from a");
+    assert_eq!(values[2].messages.last().unwrap().content, "from b");
+    assert!(values[2].messages_version > values[0].messages_version);
+}
+
+#[tokio::test]
+async fn invoke_stream_values_ends_with_the_run_error() {
+    use weavegraph::runtimes::runner::RunnerError;
+
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("fail".into()), FailingNode::default())
+        .add_edge(NodeKind::Start, NodeKind::Custom("fail".into()))
+        .add_edge(NodeKind::Custom("fail".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let values: Vec<_> = app
+        .invoke_stream_values(state_with_user("hello"))
+        .await
+        .collect()
+        .await;
+
+    assert_eq!(values.len(), 2, "{values:?}");
+    assert_eq!(values[0].as_ref().unwrap().messages.len(), 1);
+    assert!(
+        matches!(values[1], Err(RunnerError::Scheduler(_))),
+        "{:?}",
+        values[1]
+    );
+}
+
 #[tokio::test]
 async fn test_apply_barrier_multiple_updates() {
     let app = make_app();
@@ -431,3 +615,138 @@ async fn test_invoke_with_sinks_multiple() {
     // 3. Type system allows Vec<Box<dyn EventSink>> as expected
     // Event counting is inherently racy in tests due to EventBus Drop behavior
 }
+
+fn extra_partial(key: &str, value: Value) -> NodePartial {
+    let mut map = FxHashMap::default();
+    map.insert(key.to_string(), value);
+    NodePartial::new().with_extra(map)
+}
+
+fn conflict_app(policy: weavegraph::reducers::ConflictPolicy) -> weavegraph::app::App {
+    GraphBuilder::new()
+        .add_edge(NodeKind::Start, NodeKind::End)
+        .with_conflict_policy("result", policy)
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_apply_barrier_resolves_extra_conflicts_by_policy() {
+    use serde_json::json;
+    use weavegraph::reducers::ConflictPolicy;
+
+    let run_ids = vec![NodeKind::Custom("a".into()), NodeKind::Custom("b".into())];
+    let cases = [
+        (ConflictPolicy::LastWins, json!(2)),
+        (ConflictPolicy::FirstWins, json!(1)),
+        (ConflictPolicy::MergeArray, json!([1, 2])),
+        (
+            ConflictPolicy::custom("sum", |_, writes| {
+                Ok(json!(
+                    writes.iter().filter_map(|w| w.value.as_i64()).sum::<i64>()
+                ))
+            }),
+            json!(3),
+        ),
+    ];
+
+    for (policy, expected) in cases {
+        let label = policy.label();
+        let app = conflict_app(policy);
+        let state = &mut state_with_user("hi");
+        let outcome = app
+            .apply_barrier(
+                state,
+                &run_ids,
+                vec![
+                    extra_partial("result", json!(1)),
+                    extra_partial("result", json!(2)),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(state.extra.snapshot()["result"], expected, "{label}");
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].key, "result");
+        assert_eq!(outcome.conflicts[0].nodes, run_ids);
+        assert_eq!(outcome.conflicts[0].policy, label);
+    }
+}
+
+#[tokio::test]
+async fn test_apply_barrier_error_policy_rejects_conflict() {
+    use serde_json::json;
+    use weavegraph::reducers::{ConflictPolicy, ExtraConflictError};
+
+    let app = conflict_app(ConflictPolicy::Error);
+    let state = &mut state_with_user("hi");
+    let err = app
+        .apply_barrier(
+            state,
+            &[NodeKind::Custom("a".into()), NodeKind::Custom("b".into())],
+            vec![
+                extra_partial("result", json!(1)),
+                extra_partial("result", json!(2)),
+            ],
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ExtraConflictError>(),
+        Some(ExtraConflictError::Rejected { key, .. }) if key == "result"
+    ));
+    assert!(!state.extra.snapshot().contains_key("result"));
+}
+
+#[tokio::test]
+async fn test_apply_barrier_identical_writes_are_not_conflicts() {
+    use serde_json::json;
+    use weavegraph::reducers::ConflictPolicy;
+
+    let app = conflict_app(ConflictPolicy::Error);
+    let state = &mut state_with_user("hi");
+    let outcome = app
+        .apply_barrier(
+            state,
+            &[NodeKind::Custom("a".into()), NodeKind::Custom("b".into())],
+            vec![
+                extra_partial("result", json!("same")),
+                extra_partial("result", json!("same")),
+                extra_partial("other", json!(1)),
+            ],
+        )
+        .await
+        .unwrap();
+    assert!(outcome.conflicts.is_empty());
+    assert_eq!(state.extra.snapshot()["result"], json!("same"));
+}
+
+#[tokio::test]
+async fn invoke_with_summary_reports_the_run() {
+    let app = GraphBuilder::new()
+        .add_node(
+            NodeKind::Custom("test".into()),
+            SimpleMessageNode::new("test output"),
+        )
+        .add_edge(NodeKind::Start, NodeKind::Custom("test".into()))
+        .add_edge(NodeKind::Custom("test".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let (final_state, summary) = app
+        .invoke_with_summary(state_with_user("test input"))
+        .await
+        .unwrap();
+
+    assert_eq!(final_state.messages.len(), 2);
+    assert_eq!(summary.steps, 1);
+    assert_eq!(
+        summary
+            .node(&NodeKind::Custom("test".into()))
+            .unwrap()
+            .invocations,
+        1
+    );
+    assert!(summary.errors_by_severity.is_empty());
+    assert_eq!(summary.token_usage, None);
+}
@@ -2,7 +2,7 @@ use chrono::{TimeZone, Utc};
 use serde_json::json;
 
 use weavegraph::channels::errors::*;
-use weavegraph::channels::{Channel, ErrorsChannel};
+use weavegraph::channels::{Channel, ChannelVersionOverflow, ErrorsChannel, MessagesChannel};
 use weavegraph::types::ChannelType;
 
 /********************
@@ -84,6 +84,7 @@ fn error_event_defaults_and_roundtrip() {
         error: WeaveError::msg("oops"),
         tags: vec!["t1".into(), "t2".into()],
         context: json!({"info": true}),
+        severity: ErrorSeverity::Warning,
     };
 
     let ser = serde_json::to_string(&ev).unwrap();
@@ -91,6 +92,39 @@ fn error_event_defaults_and_roundtrip() {
     assert_eq!(de, ev);
 }
 
+#[test]
+fn error_event_severity_defaults_to_error_and_is_omitted() {
+    let ev = ErrorEvent::app(WeaveError::msg("x"));
+    assert_eq!(ev.severity, ErrorSeverity::Error);
+    let ser = serde_json::to_value(&ev).unwrap();
+    assert!(ser.get("severity").is_none());
+
+    let fatal = serde_json::to_value(ev.with_severity(ErrorSeverity::Fatal)).unwrap();
+    assert_eq!(fatal["severity"], "fatal");
+    assert!(ErrorSeverity::Info < ErrorSeverity::Warning);
+    assert!(ErrorSeverity::Error < ErrorSeverity::Fatal);
+}
+
+#[test]
+fn error_events_ext_filters_by_severity_and_step() {
+    let events = vec![
+        ErrorEvent::node("a", 1, WeaveError::msg("w")).with_severity(ErrorSeverity::Warning),
+        ErrorEvent::scheduler(2, WeaveError::msg("e")),
+        ErrorEvent::runner("s", 4, WeaveError::msg("f")).with_severity(ErrorSeverity::Fatal),
+        ErrorEvent::app(WeaveError::msg("i")).with_severity(ErrorSeverity::Info),
+    ];
+    let messages = |found: Vec<&ErrorEvent>| {
+        found
+            .into_iter()
+            .map(|e| e.error.message.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(messages(events.fatal()), ["f"]);
+    assert_eq!(messages(events.since_step(2)), ["e", "f"]);
+    assert_eq!(messages(events.at_least(ErrorSeverity::Error)), ["e", "f"]);
+    assert_eq!(events[3].step(), None);
+}
+
 #[test]
 fn error_event_defaults_are_empty_when_missing() {
     let when = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 9).unwrap();
@@ -239,6 +273,7 @@ fn error_event_constructors_serialize_correctly() {
         error: WeaveError::msg("test"),
         tags: vec!["tag1".to_string()],
         context: json!({"key": "value"}),
+        severity: ErrorSeverity::Error,
     };
 
     let constructed = ErrorEvent::node("Test", 1, WeaveError::msg("test"))
@@ -693,3 +728,20 @@ fn pretty_print_uses_auto_mode_by_default() {
         "pretty_print should be equivalent to pretty_print_with_mode(Auto)"
     );
 }
+
+#[test]
+fn bump_version_is_checked() {
+    let mut ch = MessagesChannel::default();
+    let start = ch.version();
+    assert_eq!(ch.bump_version(), Ok(start + 1));
+
+    ch.set_version(u32::MAX);
+    assert_eq!(
+        ch.bump_version(),
+        Err(ChannelVersionOverflow {
+            channel: ChannelType::Message,
+            version: u32::MAX,
+        })
+    );
+    assert_eq!(ch.version(), u32::MAX);
+}
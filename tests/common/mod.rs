@@ -3,6 +3,8 @@
 pub mod asserts;
 pub mod fixtures;
 pub mod nodes;
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub mod step_history;
 pub mod testing;
 
 pub use asserts::*;
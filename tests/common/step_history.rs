@@ -0,0 +1,220 @@
+#![allow(dead_code)]
+//! Conformance checks every [`StepHistory`] backend must pass.
+//!
+//! Backend test files call [`assert_step_history_conformance`] with a fresh
+//! checkpointer and a prefix that keeps session ids unique in shared databases.
+
+use chrono::Utc;
+use rustc_hash::FxHashMap;
+use weavegraph::channels::Channel;
+use weavegraph::runtimes::{
+    Checkpoint, CheckpointerError, MAX_STEP_QUERY_LIMIT, StepHistory, StepQuery,
+};
+use weavegraph::types::NodeKind;
+
+use super::fixtures::state_with_user;
+
+fn checkpoint(session_id: &str, step: u64, ran: &[&str], skipped: &[&str]) -> Checkpoint {
+    let nodes = |names: &[&str]| -> Vec<NodeKind> {
+        names
+            .iter()
+            .map(|name| NodeKind::Custom((*name).to_string()))
+            .collect()
+    };
+    Checkpoint {
+        session_id: session_id.to_string(),
+        step,
+        state: state_with_user(&format!("step {step}")),
+        frontier: vec![NodeKind::End],
+        versions_seen: FxHashMap::default(),
+        concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
+        created_at: Utc::now(),
+        ran_nodes: nodes(ran),
+        skipped_nodes: nodes(skipped),
+        updated_channels: vec!["messages".to_string()],
+        node_metrics: vec![],
+    }
+}
+
+fn steps(checkpoints: &[Checkpoint]) -> Vec<u64> {
+    checkpoints.iter().map(|c| c.step).collect()
+}
+
+/// Run every conformance check against `store`.
+pub async fn assert_step_history_conformance<C: StepHistory>(store: &C, prefix: &str) {
+    filters_match_exact_nodes(store, &format!("{prefix}_filters")).await;
+    pagination_is_capped_and_safe(store, &format!("{prefix}_pages")).await;
+    concurrency_check_guards_latest_step(store, &format!("{prefix}_occ")).await;
+    late_writes_do_not_regress_latest(store, &format!("{prefix}_late")).await;
+}
+
+async fn filters_match_exact_nodes<C: StepHistory>(store: &C, session: &str) {
+    for step in 1..=5 {
+        let ran = if step % 2 == 1 { "a" } else { "ab" };
+        let skipped: &[&str] = if step == 2 { &["b"] } else { &[] };
+        store
+            .save(checkpoint(session, step, &[ran], skipped))
+            .await
+            .unwrap();
+    }
+
+    let ran_a = store
+        .query_steps(
+            session,
+            StepQuery {
+                ran_node: Some(NodeKind::Custom("a".into())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        steps(&ran_a.checkpoints),
+        vec![5, 3, 1],
+        "newest first, exact match"
+    );
+    assert_eq!(ran_a.page_info.total_count, 3);
+
+    let skipped_b = store
+        .query_steps(
+            session,
+            StepQuery {
+                skipped_node: Some(NodeKind::Custom("b".into())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(steps(&skipped_b.checkpoints), vec![2]);
+
+    let window = store
+        .query_steps(
+            session,
+            StepQuery {
+                min_step: Some(2),
+                max_step: Some(4),
+                ran_node: Some(NodeKind::Custom("ab".into())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(steps(&window.checkpoints), vec![4, 2]);
+    assert_eq!(
+        window.checkpoints[0].ran_nodes,
+        vec![NodeKind::Custom("ab".into())]
+    );
+}
+
+async fn pagination_is_capped_and_safe<C: StepHistory>(store: &C, session: &str) {
+    for step in 1..=5 {
+        store
+            .save(checkpoint(session, step, &[], &[]))
+            .await
+            .unwrap();
+    }
+    let page = |limit, offset| StepQuery {
+        limit,
+        offset,
+        ..Default::default()
+    };
+
+    let first = store
+        .query_steps(session, page(Some(2), None))
+        .await
+        .unwrap();
+    assert_eq!(steps(&first.checkpoints), vec![5, 4]);
+    assert!(first.page_info.has_next_page);
+
+    let last = store
+        .query_steps(session, page(Some(2), Some(4)))
+        .await
+        .unwrap();
+    assert_eq!(steps(&last.checkpoints), vec![1]);
+    assert_eq!(last.page_info.offset, 4);
+    assert!(!last.page_info.has_next_page);
+
+    let capped = store
+        .query_steps(session, page(Some(MAX_STEP_QUERY_LIMIT + 1), None))
+        .await
+        .unwrap();
+    assert_eq!(capped.page_info.page_size, 5);
+    assert!(!capped.page_info.has_next_page);
+
+    let beyond = store
+        .query_steps(session, page(None, Some(u32::MAX)))
+        .await
+        .unwrap();
+    assert!(beyond.checkpoints.is_empty());
+    assert_eq!(beyond.page_info.total_count, 5);
+    assert!(!beyond.page_info.has_next_page);
+}
+
+async fn concurrency_check_guards_latest_step<C: StepHistory>(store: &C, session: &str) {
+    store
+        .save_with_concurrency_check(checkpoint(session, 1, &[], &[]), Some(0))
+        .await
+        .expect("a new session expects step 0");
+
+    let err = store
+        .save_with_concurrency_check(checkpoint(session, 2, &[], &[]), Some(0))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CheckpointerError::Conflict {
+                expected: 0,
+                found: 1,
+                ..
+            }
+        ),
+        "{err}"
+    );
+
+    store
+        .save_with_concurrency_check(checkpoint(session, 2, &[], &[]), Some(1))
+        .await
+        .unwrap();
+
+    // Re-saving the latest step replaces it.
+    let mut again = checkpoint(session, 2, &["retry"], &[]);
+    again
+        .state
+        .extra
+        .get_mut()
+        .insert("attempt".into(), serde_json::json!(2));
+    store
+        .save_with_concurrency_check(again, Some(2))
+        .await
+        .unwrap();
+    let latest = store.load_latest(session).await.unwrap().unwrap();
+    assert_eq!(latest.step, 2);
+    assert_eq!(
+        latest.state.extra.snapshot()["attempt"],
+        serde_json::json!(2)
+    );
+
+    // `None` skips the check.
+    store
+        .save_with_concurrency_check(checkpoint(session, 3, &[], &[]), None)
+        .await
+        .unwrap();
+    assert_eq!(store.load_latest(session).await.unwrap().unwrap().step, 3);
+}
+
+async fn late_writes_do_not_regress_latest<C: StepHistory>(store: &C, session: &str) {
+    store.save(checkpoint(session, 5, &[], &[])).await.unwrap();
+    store.save(checkpoint(session, 3, &[], &[])).await.unwrap();
+
+    let latest = store.load_latest(session).await.unwrap().unwrap();
+    assert_eq!(latest.step, 5);
+    let history = store
+        .query_steps(session, StepQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(steps(&history.checkpoints), vec![5, 3]);
+}
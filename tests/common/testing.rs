@@ -174,5 +174,7 @@ pub fn create_test_snapshot(messages_version: u32, extra_version: u32) -> StateS
         extra_version,
         errors: vec![],
         errors_version: 1,
+        blobs: FxHashMap::default(),
+        blobs_version: 1,
     }
 }
@@ -0,0 +1,184 @@
+//! Compatibility with formats persisted by earlier releases.
+//!
+//! Every directory under `tests/fixtures/compat/` was written by a released
+//! version running `examples/compat_fixtures.rs`; see the README there.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+use weavegraph::channels::Channel;
+use weavegraph::event_bus::Event;
+use weavegraph::message::Role;
+use weavegraph::runtimes::persistence::PersistedCheckpoint;
+use weavegraph::runtimes::{Checkpoint, StateMigrationError, StateMigrations};
+use weavegraph::types::NodeKind;
+use weavegraph::utils::json_ext::JsonSerializable;
+
+fn corpora() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/compat");
+    let mut dirs: Vec<PathBuf> = fs::read_dir(&root)
+        .expect("compat fixture directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    assert!(!dirs.is_empty(), "no compat corpora in {}", root.display());
+    dirs
+}
+
+fn load_checkpoint(dir: &Path) -> Checkpoint {
+    let raw = fs::read_to_string(dir.join("checkpoint.json")).unwrap();
+    let persisted = PersistedCheckpoint::from_json_str(&raw)
+        .unwrap_or_else(|e| panic!("{}: {e}", dir.display()));
+    Checkpoint::try_from(persisted).unwrap()
+}
+
+/// What every corpus recorded for the fixture workflow.
+fn assert_fixture_state(dir: &Path, checkpoint: &Checkpoint) {
+    let label = dir.display();
+    assert_eq!(checkpoint.session_id, "compat-session", "{label}");
+    assert_eq!(checkpoint.step, 2, "{label}");
+    assert_eq!(checkpoint.frontier, vec![NodeKind::End], "{label}");
+
+    let messages = checkpoint.state.messages.snapshot();
+    assert_eq!(messages.len(), 2, "{label}");
+    assert_eq!(messages[0].role, Role::User, "{label}");
+    assert_eq!(messages[1].content, "Hello from the archive.", "{label}");
+
+    let extra = checkpoint.state.extra.snapshot();
+    assert_eq!(extra["reply_count"], json!(1), "{label}");
+    assert_eq!(extra["profile"]["tier"], json!("gold"), "{label}");
+
+    let errors = checkpoint.state.errors.snapshot();
+    assert_eq!(errors.len(), 1, "{label}");
+    assert_eq!(
+        errors[0].error.message, "policy service unavailable",
+        "{label}"
+    );
+    assert_eq!(errors[0].tags, vec!["transient".to_string()], "{label}");
+}
+
+#[test]
+fn checkpoint_json_from_every_release_loads() {
+    for dir in corpora() {
+        let checkpoint = load_checkpoint(&dir);
+        assert_fixture_state(&dir, &checkpoint);
+
+        // Re-saving with the current code loads back to the same checkpoint.
+        let rewritten = PersistedCheckpoint::from(&checkpoint)
+            .to_json_string()
+            .unwrap();
+        let reloaded =
+            Checkpoint::try_from(PersistedCheckpoint::from_json_str(&rewritten).unwrap()).unwrap();
+        assert_fixture_state(&dir, &reloaded);
+    }
+}
+
+#[test]
+fn events_from_every_release_deserialize_unchanged() {
+    for dir in corpora() {
+        let raw = fs::read_to_string(dir.join("events.jsonl")).unwrap();
+        let mut llm_chunks = 0;
+        for line in raw.lines() {
+            let stored: Value = serde_json::from_str(line).unwrap();
+            let event: Event = serde_json::from_value(stored.clone())
+                .unwrap_or_else(|e| panic!("{}: {e}: {line}", dir.display()));
+            if let Event::LLM(llm) = &event {
+                assert_eq!(llm.stream_id(), Some("reply-1"));
+                llm_chunks += 1;
+            }
+            assert_eq!(
+                serde_json::to_value(&event).unwrap(),
+                stored,
+                "{}: event changed shape on re-serialization",
+                dir.display()
+            );
+        }
+        assert_eq!(llm_chunks, 2, "{}", dir.display());
+    }
+}
+
+#[test]
+fn unversioned_release_state_migrates_or_reports_typed_errors() {
+    for dir in corpora() {
+        let mut checkpoint = load_checkpoint(&dir);
+        assert_eq!(StateMigrations::version_of(&checkpoint.state), Ok(0));
+
+        let migrations =
+            StateMigrations::new().migration(0, "rename reply_count to replies", |state| {
+                let extra = state.extra.get_mut();
+                let count = extra.remove("reply_count").ok_or("reply_count missing")?;
+                extra.insert("replies".into(), count);
+                Ok(())
+            });
+        let report = migrations.migrate_checkpoint(&mut checkpoint).unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, 1));
+        assert_eq!(checkpoint.state.extra.snapshot()["replies"], json!(1));
+
+        // A release with no migrations refuses state stamped by a newer schema.
+        let err = StateMigrations::new()
+            .migrate_checkpoint(&mut checkpoint)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StateMigrationError::NewerVersion {
+                found: 1,
+                current: 0
+            }
+        );
+    }
+}
+
+#[cfg(feature = "sqlite-migrations")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sqlite_databases_from_every_release_upgrade_and_resume() {
+    use weavegraph::message::Message;
+    use weavegraph::runtimes::{Checkpointer, SQLiteCheckpointer, StepQuery};
+
+    for dir in corpora() {
+        let temp = tempfile::tempdir().unwrap();
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            temp.path().join("compat.db").display()
+        );
+        let dump = fs::read_to_string(dir.join("sqlite.sql")).unwrap();
+        {
+            let pool = sqlx::SqlitePool::connect(&url).await.unwrap();
+            sqlx::raw_sql(&dump).execute(&pool).await.unwrap();
+            pool.close().await;
+        }
+
+        // Connecting applies the migrations added since the release.
+        let checkpointer = SQLiteCheckpointer::connect(&url)
+            .await
+            .unwrap_or_else(|e| panic!("{}: {e}", dir.display()));
+        let latest = checkpointer
+            .load_latest("compat-session")
+            .await
+            .unwrap()
+            .expect("session survives the upgrade");
+        assert_fixture_state(&dir, &latest);
+
+        let history = checkpointer
+            .query_steps("compat-session", StepQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(history.checkpoints.len(), 3, "{}", dir.display());
+
+        let mut next = latest.clone();
+        next.step = 3;
+        next.state
+            .messages
+            .get_mut()
+            .push(Message::user("Still there?"));
+        checkpointer.save(next).await.unwrap();
+        let resumed = checkpointer
+            .load_latest("compat-session")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resumed.step, 3);
+        assert_eq!(resumed.state.messages.snapshot().len(), 3);
+    }
+}
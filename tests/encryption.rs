@@ -0,0 +1,170 @@
+#![cfg(feature = "encryption")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use weavegraph::encryption::{EncryptionError, EncryptionKey, Envelope, StaticKeyProvider};
+use weavegraph::event_bus::{
+    ChannelSink, EncryptedSink, Event, EventSink, JsonLinesSink, LLMStreamingEvent, NodeEvent,
+    SEALED_METADATA_KEY, open_event, seal_event,
+};
+
+fn session_node(session: &str, message: &str) -> NodeEvent {
+    let mut metadata = FxHashMap::default();
+    metadata.insert("invocation_id".to_string(), json!(session));
+    metadata.insert("customer".to_string(), json!("jane@example.com"));
+    NodeEvent::new(
+        Some("billing".to_string()),
+        Some(1),
+        "charge".to_string(),
+        message.to_string(),
+    )
+    .with_metadata(metadata)
+}
+
+fn session_event(session: &str, message: &str) -> Event {
+    Event::Node(session_node(session, message))
+}
+
+fn keys() -> StaticKeyProvider {
+    StaticKeyProvider::new()
+        .with_key("acme", EncryptionKey::new("acme-1", [1; 32]))
+        .with_key("globex", EncryptionKey::new("globex-1", [2; 32]))
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_encrypted_sink_seals_each_session_with_its_own_key() {
+    let keys = Arc::new(keys());
+    let (tx, rx) = flume::unbounded();
+    let mut sink = EncryptedSink::new(ChannelSink::new(tx), keys.clone());
+
+    let original =
+        Event::Node(session_node("acme", "card ending 4242").with_payload(json!({"amount": 42})));
+    sink.handle(&original).unwrap();
+    sink.handle(&session_event("globex", "card ending 1111"))
+        .unwrap();
+
+    let acme = rx.recv().unwrap();
+    let globex = rx.recv().unwrap();
+    assert_eq!(acme.message(), "");
+    assert_eq!(acme.sequence_key(), Some("acme"));
+    let Event::Node(node) = &acme else {
+        panic!("expected a node event")
+    };
+    assert!(node.payload().is_none());
+    assert!(!node.metadata().contains_key("customer"));
+    assert_eq!(node.metadata()[SEALED_METADATA_KEY]["tenant"], "acme");
+    assert_eq!(node.metadata()[SEALED_METADATA_KEY]["key_id"], "acme-1");
+    let Event::Node(other) = &globex else {
+        panic!("expected a node event")
+    };
+    assert_eq!(other.metadata()[SEALED_METADATA_KEY]["key_id"], "globex-1");
+
+    assert_eq!(open_event(&acme, keys.as_ref()).unwrap(), original);
+    assert_eq!(
+        open_event(&globex, keys.as_ref()).unwrap().message(),
+        "card ending 1111"
+    );
+
+    let acme_only =
+        StaticKeyProvider::new().with_key("acme", EncryptionKey::new("acme-1", [1; 32]));
+    assert!(matches!(
+        open_event(&globex, &acme_only),
+        Err(EncryptionError::UnknownKey { .. })
+    ));
+}
+
+#[test]
+fn test_encrypted_sink_refuses_tenants_without_keys() {
+    let (tx, rx) = flume::unbounded();
+    let mut sink = EncryptedSink::new(ChannelSink::new(tx), Arc::new(keys()));
+
+    assert!(sink.handle(&session_event("initech", "secret")).is_err());
+    assert!(
+        sink.handle(&Event::node_message("billing", "secret"))
+            .is_err()
+    );
+    assert!(rx.try_recv().is_err());
+
+    let (tx, rx) = flume::unbounded();
+    let mut routed = EncryptedSink::new(ChannelSink::new(tx), Arc::new(keys()))
+        .with_tenant_resolver(|_| Some("acme".to_string()));
+    routed.handle(&session_event("initech", "secret")).unwrap();
+    let Event::Node(node) = rx.recv().unwrap() else {
+        panic!("expected a node event")
+    };
+    assert_eq!(node.metadata()[SEALED_METADATA_KEY]["tenant"], "acme");
+}
+
+#[test]
+fn test_json_lines_output_carries_no_plaintext() {
+    let buffer = SharedBuffer::default();
+    let keys = Arc::new(keys());
+    let mut sink = EncryptedSink::new(JsonLinesSink::new(Box::new(buffer.clone())), keys.clone())
+        .with_tenant_resolver(|_| Some("globex".to_string()));
+
+    sink.handle(&session_event("s-1", "card ending 4242"))
+        .unwrap();
+    sink.handle(&Event::LLM(LLMStreamingEvent::chunk_event(
+        Some("s-1".to_string()),
+        Some("writer".to_string()),
+        None,
+        "dear jane",
+        FxHashMap::default(),
+    )))
+    .unwrap();
+    sink.handle(&Event::diagnostic("runner", "step 1 done"))
+        .unwrap();
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(!output.contains("4242"));
+    assert!(!output.contains("jane"));
+    assert!(output.contains("step 1 done"));
+    assert!(output.contains("\"invocation_id\":\"s-1\""));
+}
+
+#[test]
+fn test_open_event_survives_rotation_and_rejects_tampering() {
+    let old = StaticKeyProvider::new().with_key("acme", EncryptionKey::new("acme-1", [1; 32]));
+    let sealed = seal_event(&session_event("acme", "hello"), "acme", &old).unwrap();
+
+    let rotated = old.with_key("acme", EncryptionKey::new("acme-2", [3; 32]));
+    assert_eq!(open_event(&sealed, &rotated).unwrap().message(), "hello");
+    let resealed = seal_event(&session_event("acme", "hello"), "acme", &rotated).unwrap();
+    let Event::Node(node) = &resealed else {
+        panic!("expected a node event")
+    };
+    assert_eq!(node.metadata()[SEALED_METADATA_KEY]["key_id"], "acme-2");
+
+    let Event::Node(node) = sealed else {
+        panic!("expected a node event")
+    };
+    let moved = Event::Node(
+        NodeEvent::new(None, None, "refund".into(), String::new())
+            .with_metadata(node.metadata().clone()),
+    );
+    assert!(matches!(
+        open_event(&moved, &rotated),
+        Err(EncryptionError::Authentication { .. })
+    ));
+
+    let mut envelope = Envelope::seal(&rotated, "acme", b"data", b"aad").unwrap();
+    envelope.tenant = "globex".to_string();
+    assert!(envelope.open(&rotated, b"aad").is_err());
+}
@@ -9,8 +9,9 @@ use std::sync::Mutex;
 use std::time::Duration;
 use weavegraph::channels::Channel;
 use weavegraph::event_bus::{
-    ChannelSink, Event, EventBus, EventEmitter, EventSink, INVOCATION_END_SCOPE, JsonLinesSink,
-    LLMStreamingEvent, MemorySink, NodeEvent, STREAM_END_SCOPE,
+    ChannelSink, EncodedSink, Event, EventBus, EventDecoder, EventEmitter, EventFormat, EventSink,
+    FlushError, INVOCATION_END_SCOPE, JsonLinesSink, LLMStreamingEvent, MemorySink, NodeEvent,
+    STREAM_END_SCOPE,
 };
 use weavegraph::node::NodeContext;
 
@@ -474,6 +475,7 @@ async fn event_hub_metrics_track_drops() {
     };
 
     assert_eq!(missed, 1);
+    assert_eq!(stream.dropped(), 1);
 
     let metrics = hub.metrics();
     assert_eq!(metrics.capacity, 1);
@@ -1027,3 +1029,542 @@ async fn test_jsonlines_sink_with_eventbus() {
     let json1: Value = serde_json::from_str(lines[0]).unwrap();
     assert_eq!(json1["message"], "message1");
 }
+
+fn sample_events_for_codec() -> Vec<Event> {
+    let mut metadata = FxHashMap::default();
+    metadata.insert("tokens".to_string(), json!(3));
+    vec![
+        Event::node_message_with_meta("node-a", 2, "scope", "hello"),
+        Event::diagnostic("codec", "diagnostic"),
+        Event::LLM(LLMStreamingEvent::chunk_event(
+            Some("s1".into()),
+            Some("llm".into()),
+            Some("stream".into()),
+            "chunk",
+            metadata,
+        )),
+    ]
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn assert_codec_roundtrip(format: EventFormat) {
+    let buffer = SharedBuffer::default();
+    let mut sink = EncodedSink::new(Box::new(buffer.clone()), format);
+    let events = sample_events_for_codec();
+    for event in &events {
+        sink.handle(event).unwrap();
+    }
+    assert_eq!(sink.name(), format!("EncodedSink({})", format.name()));
+
+    let bytes = buffer.0.lock().unwrap().clone();
+    let decoded: Vec<Event> = EventDecoder::new(std::io::Cursor::new(bytes), format)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(decoded, events);
+}
+
+#[test]
+fn encoded_sink_json_roundtrip() {
+    assert_codec_roundtrip(EventFormat::Json);
+}
+
+#[test]
+fn encoded_sink_leaves_flushing_to_the_bus() {
+    let buffer = SharedBuffer::default();
+    let writer = std::io::BufWriter::new(buffer.clone());
+    let mut sink = EncodedSink::new(Box::new(writer), EventFormat::Json);
+    for event in &sample_events_for_codec() {
+        sink.handle(event).unwrap();
+    }
+    assert!(buffer.0.lock().unwrap().is_empty());
+
+    sink.flush().unwrap();
+    assert!(!buffer.0.lock().unwrap().is_empty());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn encoded_sink_msgpack_roundtrip() {
+    assert_codec_roundtrip(EventFormat::MsgPack);
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn encoded_sink_cbor_roundtrip() {
+    assert_codec_roundtrip(EventFormat::Cbor);
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn event_decoder_rejects_truncated_frame() {
+    let mut bytes = Vec::new();
+    EventFormat::MsgPack
+        .write_frame(&mut bytes, &Event::diagnostic("codec", "hello"))
+        .unwrap();
+    bytes.truncate(bytes.len() - 1);
+    let mut decoder = EventDecoder::new(std::io::Cursor::new(bytes), EventFormat::MsgPack);
+    assert!(decoder.next().unwrap().is_err());
+}
+
+/// Sink that takes a while per event and buffers until flushed.
+#[derive(Clone, Default)]
+struct SlowBufferedSink {
+    delay: Duration,
+    buffered: Arc<Mutex<Vec<String>>>,
+    flushed: Arc<Mutex<Vec<String>>>,
+}
+
+impl EventSink for SlowBufferedSink {
+    fn handle(&mut self, event: &Event) -> std::io::Result<()> {
+        std::thread::sleep(self.delay);
+        self.buffered
+            .lock()
+            .unwrap()
+            .push(event.message().to_string());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let mut buffered = self.buffered.lock().unwrap();
+        self.flushed.lock().unwrap().append(&mut buffered);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "SlowBufferedSink".to_string()
+    }
+}
+
+#[tokio::test]
+async fn flush_drains_pending_events_then_flushes_sink() {
+    let sink = SlowBufferedSink {
+        delay: Duration::from_millis(15),
+        ..Default::default()
+    };
+    let flushed = Arc::clone(&sink.flushed);
+    let bus = EventBus::with_sink(sink);
+    bus.listen_for_events();
+
+    let emitter = bus.get_emitter();
+    for i in 0..5 {
+        emitter
+            .emit(Event::diagnostic("flush", format!("event-{i}")))
+            .unwrap();
+    }
+    bus.flush(Duration::from_secs(5)).await.unwrap();
+
+    let flushed = flushed.lock().unwrap().clone();
+    assert_eq!(
+        flushed,
+        (0..5).map(|i| format!("event-{i}")).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn flush_reports_sinks_that_miss_the_timeout() {
+    let bus = EventBus::with_sink(SlowBufferedSink {
+        delay: Duration::from_millis(200),
+        ..Default::default()
+    });
+    bus.listen_for_events();
+    bus.get_emitter()
+        .emit(Event::diagnostic("flush", "slow"))
+        .unwrap();
+
+    let err = bus.flush(Duration::from_millis(10)).await.unwrap_err();
+    match err {
+        FlushError::Timeout { pending, .. } => assert_eq!(pending, vec!["SlowBufferedSink"]),
+        other => panic!("expected timeout, got {other:?}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn flush_returns_under_steady_publishing() {
+    let sink = SlowBufferedSink {
+        delay: Duration::from_millis(1),
+        ..Default::default()
+    };
+    let flushed = Arc::clone(&sink.flushed);
+    let bus = EventBus::with_sink(sink);
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+    emitter.emit(Event::diagnostic("flush", "before")).unwrap();
+
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let publisher = {
+        let stop = Arc::clone(&stop);
+        let emitter = bus.get_emitter();
+        std::thread::spawn(move || {
+            while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+                let _ = emitter.emit(Event::diagnostic("flush", "steady"));
+                std::thread::sleep(Duration::from_micros(100));
+            }
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let result = bus.flush(Duration::from_secs(5)).await;
+    stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    publisher.join().unwrap();
+    result.unwrap();
+    assert_eq!(
+        flushed.lock().unwrap().first().map(String::as_str),
+        Some("before")
+    );
+}
+
+#[tokio::test]
+async fn flush_without_listener_returns_immediately() {
+    let bus = EventBus::with_sink(MemorySink::new());
+    bus.flush(Duration::from_millis(1)).await.unwrap();
+}
+
+#[tokio::test]
+async fn stream_filters_compose_across_receive_methods() {
+    let bus = EventBus::with_sink(MemorySink::new());
+    let emitter = bus.get_emitter();
+    let llm = bus.subscribe().only_llm().into_async_stream();
+    let mut scoped = bus
+        .subscribe()
+        .filter_scope("search")
+        .filter(|event| event.message().starts_with("hit"));
+
+    emitter
+        .emit(Event::node_message("search", "miss"))
+        .expect("emit");
+    emitter
+        .emit(Event::node_message("plan", "hit elsewhere"))
+        .expect("emit");
+    emitter
+        .emit(Event::LLM(LLMStreamingEvent::chunk_event(
+            None,
+            None,
+            None,
+            "tok",
+            FxHashMap::default(),
+        )))
+        .expect("emit");
+    emitter
+        .emit(Event::node_message("search", "hit 1"))
+        .expect("emit");
+
+    assert_eq!(scoped.try_recv().expect("event").message(), "hit 1");
+    assert!(scoped.try_recv().is_err());
+    pin_mut!(llm);
+    assert_eq!(llm.next().await.expect("llm").message(), "tok");
+}
+
+#[tokio::test]
+async fn tee_and_batch_fan_out_events() {
+    let bus = EventBus::with_sink(MemorySink::new());
+    let emitter = bus.get_emitter();
+    let mut copies = bus.subscribe().filter_scope("work").tee(2).into_iter();
+    let mut first = copies.next().expect("first copy");
+    let batches = copies
+        .next()
+        .expect("second copy")
+        .buffer_batched(Duration::from_millis(50));
+    pin_mut!(batches);
+
+    for i in 0..3 {
+        emitter
+            .emit(Event::node_message("work", format!("item {i}")))
+            .expect("emit");
+    }
+    emitter
+        .emit(Event::node_message("other", "ignored"))
+        .expect("emit");
+
+    let batch = batches.next().await.expect("batch");
+    let messages: Vec<&str> = batch.iter().map(Event::message).collect();
+    assert_eq!(messages, vec!["item 0", "item 1", "item 2"]);
+    for i in 0..3 {
+        assert_eq!(
+            first.recv().await.expect("event").message(),
+            format!("item {i}")
+        );
+    }
+
+    emitter
+        .emit(Event::node_message("work", "late"))
+        .expect("emit");
+    let batch = batches.next().await.expect("second batch");
+    assert_eq!(batch.len(), 1);
+}
+
+#[derive(serde::Serialize)]
+struct Progress {
+    done: u32,
+    total: u32,
+}
+
+#[tokio::test]
+async fn emit_json_and_emit_typed_keep_structured_payloads() {
+    let sink = MemorySink::new();
+    let sink_snapshot = sink.clone();
+    let bus = EventBus::with_sink(sink);
+    bus.listen_for_events();
+
+    let ctx = NodeContext::new("worker", 3, bus.get_emitter());
+    ctx.emit_json("progress", json!({"done": 1, "total": 4}))
+        .unwrap();
+    ctx.emit_typed("progress", &Progress { done: 2, total: 4 })
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    bus.stop_listener().await;
+
+    let entries = sink_snapshot.snapshot();
+    assert_eq!(entries.len(), 2);
+    let Event::Node(first) = &entries[0] else {
+        panic!("expected node event");
+    };
+    assert_eq!(first.node_id(), Some("worker"));
+    assert_eq!(first.step(), Some(3));
+    assert_eq!(first.scope(), "progress");
+    assert_eq!(first.message(), "");
+    assert_eq!(first.payload(), Some(&json!({"done": 1, "total": 4})));
+
+    let value = entries[1].to_json_value();
+    assert_eq!(value["payload"], json!({"done": 2, "total": 4}));
+    assert_eq!(value["metadata"]["node_id"], "worker");
+    assert_eq!(entries[1].to_string(), r#"[worker@3] {"done":2,"total":4}"#);
+
+    let round_trip: Event =
+        serde_json::from_str(&serde_json::to_string(&entries[1]).unwrap()).unwrap();
+    assert_eq!(round_trip, entries[1]);
+
+    let plain = Event::node_message("scope", "text").to_json_value();
+    assert!(plain.get("payload").is_none());
+}
+
+fn session_event(session: &str, message: &str) -> Event {
+    let mut metadata = FxHashMap::default();
+    metadata.insert("invocation_id".to_string(), json!(session));
+    Event::node_message_with_metadata("worker", 1, "work", message, metadata)
+}
+
+#[test]
+fn sequencing_hub_numbers_events_per_session() {
+    use weavegraph::event_bus::EventHub;
+
+    let hub = EventHub::new(16);
+    let mut unsequenced = hub.subscribe();
+    hub.publish(Event::diagnostic("run", "before")).unwrap();
+    assert_eq!(unsequenced.try_recv().unwrap().sequence(), None);
+
+    hub.enable_sequencing();
+    hub.publish(session_event("a", "a1")).unwrap();
+    hub.publish(session_event("b", "b1")).unwrap();
+    hub.publish(session_event("a", "a2")).unwrap();
+    hub.publish(Event::diagnostic("run", "global")).unwrap();
+    hub.publish(session_event("a", "a3").with_sequence(99))
+        .unwrap();
+
+    let received: Vec<_> = std::iter::from_fn(|| unsequenced.try_recv().ok())
+        .map(|event| (event.sequence_key().map(str::to_owned), event.sequence()))
+        .collect();
+    assert_eq!(
+        received,
+        vec![
+            (Some("a".to_string()), Some(1)),
+            (Some("b".to_string()), Some(1)),
+            (Some("a".to_string()), Some(2)),
+            (None, Some(1)),
+            (Some("a".to_string()), Some(99)),
+        ]
+    );
+
+    let json = session_event("a", "x").with_sequence(7).to_json_value();
+    assert_eq!(json["sequence"], 7);
+}
+
+#[tokio::test]
+async fn strict_ordering_reorders_sink_delivery_per_session() {
+    let sink = MemorySink::new();
+    let bus = EventBus::with_sink(sink.clone()).with_strict_ordering();
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+
+    // Simulate concurrent publishers whose events entered the channel out of order.
+    for (session, sequence) in [("a", 2), ("b", 1), ("a", 3), ("a", 1)] {
+        emitter
+            .emit(session_event(session, &format!("{session}{sequence}")).with_sequence(sequence))
+            .unwrap();
+    }
+
+    bus.flush(Duration::from_secs(1)).await.unwrap();
+    let delivered: Vec<_> = sink
+        .snapshot()
+        .iter()
+        .map(|event| event.message().to_string())
+        .collect();
+    assert_eq!(delivered, ["b1", "a1", "a2", "a3"]);
+    assert_eq!(bus.metrics().sequence_gaps, 0);
+}
+
+#[tokio::test]
+async fn strict_ordering_skips_missing_sequences_on_flush() {
+    let sink = MemorySink::new();
+    let bus = EventBus::with_sink(sink.clone()).with_strict_ordering();
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+
+    for sequence in [1, 4, 3] {
+        emitter
+            .emit(session_event("a", &format!("a{sequence}")).with_sequence(sequence))
+            .unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(sink.snapshot().len(), 1, "a3 and a4 wait for a2");
+
+    bus.flush(Duration::from_secs(1)).await.unwrap();
+    let delivered: Vec<_> = sink
+        .snapshot()
+        .iter()
+        .map(|event| event.message().to_string())
+        .collect();
+    assert_eq!(delivered, ["a1", "a3", "a4"]);
+    assert_eq!(bus.metrics().sequence_gaps, 1);
+}
+
+/// Records messages, blocking every `handle` until the gate opens.
+#[derive(Clone, Default)]
+struct GatedSink {
+    gate: Arc<(Mutex<bool>, std::sync::Condvar)>,
+    delivered: Arc<Mutex<Vec<String>>>,
+}
+
+impl GatedSink {
+    fn open(&self) {
+        let (open, wake) = &*self.gate;
+        *open.lock().unwrap() = true;
+        wake.notify_all();
+    }
+}
+
+impl EventSink for GatedSink {
+    fn handle(&mut self, event: &Event) -> std::io::Result<()> {
+        let (open, wake) = &*self.gate;
+        let _guard = wake
+            .wait_while(open.lock().unwrap(), |open| !*open)
+            .unwrap();
+        self.delivered
+            .lock()
+            .unwrap()
+            .push(event.message().to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn priority_lanes_deliver_critical_events_ahead_of_chunks() {
+    let sink = GatedSink::default();
+    let bus = EventBus::with_sink(sink.clone()).with_priority_lanes();
+    assert!(bus.priority_lanes());
+    let mut subscriber = bus.subscribe();
+    let emitter = bus.get_emitter();
+
+    for index in 0..50 {
+        emitter
+            .emit(Event::LLM(LLMStreamingEvent::chunk_event(
+                Some("s".to_string()),
+                None,
+                None,
+                format!("chunk{index}"),
+                FxHashMap::default(),
+            )))
+            .unwrap();
+    }
+    emitter
+        .emit(Event::diagnostic("run", "sink failed"))
+        .unwrap();
+    emitter
+        .emit(Event::node_message("guard", "injection blocked").with_high_priority())
+        .unwrap();
+    sink.open();
+    bus.flush(Duration::from_secs(1)).await.unwrap();
+
+    let delivered = sink.delivered.lock().unwrap().clone();
+    assert_eq!(delivered.len(), 52, "no event is delivered twice");
+    let alert = delivered.iter().position(|m| m == "sink failed").unwrap();
+    let guard = delivered
+        .iter()
+        .position(|m| m == "injection blocked")
+        .unwrap();
+    // The worker may already hold the first chunk when the alerts arrive.
+    assert!(alert <= 1, "alert delivered at {alert}");
+    assert_eq!(guard, alert + 1, "the priority lane keeps its own order");
+    let chunks: Vec<_> = delivered
+        .iter()
+        .filter(|m| m.starts_with("chunk"))
+        .cloned()
+        .collect();
+    let expected: Vec<_> = (0..50).map(|index| format!("chunk{index}")).collect();
+    assert_eq!(chunks, expected);
+
+    // Subscribers see channel order with each event once.
+    let mut received = Vec::new();
+    while let Ok(event) = subscriber.try_recv() {
+        received.push(event.message().to_string());
+    }
+    assert_eq!(received.len(), 52);
+    assert_eq!(received[50], "sink failed");
+}
+
+#[tokio::test]
+async fn priority_lanes_with_strict_ordering_count_no_gaps() {
+    let sink = MemorySink::new();
+    let bus = EventBus::with_sink(sink.clone())
+        .with_priority_lanes()
+        .with_strict_ordering();
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+
+    emitter.emit(session_event("a", "a1")).unwrap();
+    emitter
+        .emit(session_event("a", "a2").with_high_priority())
+        .unwrap();
+    emitter.emit(session_event("a", "a3")).unwrap();
+    bus.flush(Duration::from_secs(1)).await.unwrap();
+
+    let mut delivered: Vec<_> = sink
+        .snapshot()
+        .iter()
+        .map(|event| event.message().to_string())
+        .collect();
+    delivered.sort();
+    assert_eq!(delivered, ["a1", "a2", "a3"]);
+    assert_eq!(bus.metrics().sequence_gaps, 0);
+}
+
+#[test]
+fn event_priority_classifies_critical_events() {
+    use weavegraph::event_bus::{EventPriority, RETRACT_SCOPE};
+
+    let chunk = LLMStreamingEvent::chunk_event(None, None, None, "tok", FxHashMap::default());
+    assert_eq!(Event::LLM(chunk).priority(), EventPriority::Normal);
+    let error = LLMStreamingEvent::error_event(None, None, None, "boom");
+    assert_eq!(Event::LLM(error).priority(), EventPriority::High);
+    assert_eq!(
+        Event::node_message(RETRACT_SCOPE, "step 1 attempt 1 retracted").priority(),
+        EventPriority::High
+    );
+    assert_eq!(
+        Event::node_message("work", "done").priority(),
+        EventPriority::Normal
+    );
+}
@@ -0,0 +1,144 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use weavegraph::event_bus::{
+    BrokerPublishError, BrokerPublisher, BrokerRecord, BrokerSink, Event, EventBus, EventFormat,
+};
+
+/// Records every published batch; fails the first `failures` calls.
+#[derive(Clone, Default)]
+struct RecordingPublisher {
+    batches: Arc<Mutex<Vec<Vec<BrokerRecord>>>>,
+    failures: Arc<Mutex<usize>>,
+}
+
+impl RecordingPublisher {
+    fn failing(failures: usize) -> Self {
+        Self {
+            failures: Arc::new(Mutex::new(failures)),
+            ..Self::default()
+        }
+    }
+
+    fn batches(&self) -> Vec<Vec<BrokerRecord>> {
+        self.batches.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl BrokerPublisher for RecordingPublisher {
+    async fn publish(&self, batch: Vec<BrokerRecord>) -> Result<(), BrokerPublishError> {
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            return Err(BrokerPublishError::new(batch, "broker unavailable"));
+        }
+        self.batches.lock().unwrap().push(batch);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "recording".to_string()
+    }
+}
+
+fn session_event(session: &str, message: &str) -> Event {
+    let metadata: FxHashMap<_, _> = [("invocation_id".to_string(), json!(session))]
+        .into_iter()
+        .collect();
+    Event::node_message_with_metadata("node", 1, "test", message, metadata)
+}
+
+#[tokio::test]
+async fn broker_sink_batches_events_partitioned_by_session() {
+    let publisher = RecordingPublisher::default();
+    let sink = BrokerSink::new(publisher.clone(), "events.{session}")
+        .with_batch_size(2)
+        .with_linger(Duration::from_secs(3600));
+    let bus = EventBus::with_sink(sink);
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+    for (session, message) in [("a", "1"), ("b", "2"), ("a", "3"), ("b", "4")] {
+        emitter.emit(session_event(session, message)).unwrap();
+    }
+    emitter.emit(Event::diagnostic("test", "5")).unwrap();
+    bus.flush(Duration::from_secs(5)).await.unwrap();
+
+    let batches = publisher.batches();
+    let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![2, 2, 1]);
+    let records: Vec<BrokerRecord> = batches.into_iter().flatten().collect();
+    let routing: Vec<(&str, Option<&str>)> = records
+        .iter()
+        .map(|r| (r.destination.as_str(), r.key.as_deref()))
+        .collect();
+    assert_eq!(
+        routing,
+        vec![
+            ("events.a", Some("a")),
+            ("events.b", Some("b")),
+            ("events.a", Some("a")),
+            ("events.b", Some("b")),
+            ("events._", None),
+        ]
+    );
+    let decoded = EventFormat::Json.decode(&records[2].payload).unwrap();
+    assert_eq!(decoded.message(), "3");
+}
+
+#[tokio::test]
+async fn failed_batches_are_reported_and_retried() {
+    let publisher = RecordingPublisher::failing(1);
+    let sink = BrokerSink::new(publisher.clone(), "events").with_batch_size(1);
+    let bus = EventBus::with_sink(sink);
+    let mut diagnostics = bus.diagnostics();
+    bus.listen_for_events();
+    let emitter = bus.get_emitter();
+
+    emitter.emit(Event::diagnostic("test", "first")).unwrap();
+    let diagnostic = diagnostics.recv().await.unwrap();
+    assert_eq!(diagnostic.sink, "BrokerSink(recording)");
+    assert!(diagnostic.error.contains("broker unavailable"));
+    assert!(diagnostic.error.contains("1 record(s) pending"));
+
+    emitter.emit(Event::diagnostic("test", "second")).unwrap();
+    bus.flush(Duration::from_secs(5)).await.unwrap();
+    let delivered: Vec<String> = publisher
+        .batches()
+        .into_iter()
+        .flatten()
+        .map(|r| {
+            EventFormat::Json
+                .decode(&r.payload)
+                .unwrap()
+                .message()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(delivered, vec!["first", "second"]);
+}
+
+#[tokio::test]
+async fn pending_records_beyond_the_limit_are_dropped() {
+    let publisher = RecordingPublisher::failing(usize::MAX);
+    let mut sink = BrokerSink::new(publisher, "events")
+        .with_batch_size(1)
+        .with_max_pending(2);
+    let errors = tokio::task::spawn_blocking(move || {
+        use weavegraph::event_bus::EventSink;
+        (0..3)
+            .map(|i| {
+                sink.handle(&Event::diagnostic("test", i.to_string()))
+                    .unwrap_err()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap();
+    assert!(errors[1].ends_with("2 record(s) pending"));
+    assert!(errors[2].ends_with("2 record(s) pending, 1 dropped"));
+}
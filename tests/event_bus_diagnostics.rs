@@ -189,3 +189,118 @@ async fn emit_to_events_toggle_behavior() {
         "expected a single diagnostic event to be emitted"
     );
 }
+
+#[tokio::test]
+async fn sink_disabled_after_consecutive_failures_and_reenabled() {
+    let bus = bus_with_diagnostics(true, false, 16, 16).with_sink_disable_threshold(2);
+    bus.add_sink(NamedFailingSink);
+    let _events = bus.subscribe();
+    let mut diags = bus.diagnostics();
+    let emitter = bus.get_emitter();
+
+    for i in 0..4 {
+        emitter
+            .emit(Event::node_message("scope", format!("msg{i}")))
+            .unwrap();
+    }
+
+    let first = diags.recv().await.expect("first diagnostic");
+    assert_eq!(first.consecutive_failures, 1);
+    assert!(!first.disabled);
+    let second = diags.recv().await.expect("second diagnostic");
+    assert_eq!(second.consecutive_failures, 2);
+    assert!(second.disabled);
+    assert!(
+        diags
+            .next_timeout(std::time::Duration::from_millis(100))
+            .await
+            .is_none(),
+        "disabled sink should not report further failures"
+    );
+
+    let metrics = bus.metrics();
+    assert_eq!(metrics.disabled_sinks(), 1);
+    let health = &metrics.sinks[0];
+    assert_eq!(health.sink, "custom.named");
+    assert_eq!(health.error_count, 2);
+    assert!(health.disabled);
+
+    assert!(bus.enable_sink("custom.named"));
+    assert!(!bus.enable_sink("custom.named"));
+    emitter.emit(Event::node_message("scope", "again")).unwrap();
+    let resumed = diags.recv().await.expect("diagnostic after re-enable");
+    assert_eq!(resumed.consecutive_failures, 1);
+    assert!(!resumed.disabled);
+}
+
+/// Fails on every other event so consecutive failures never accumulate.
+struct FlakySink {
+    calls: u32,
+}
+
+impl EventSink for FlakySink {
+    fn handle(&mut self, _event: &Event) -> io::Result<()> {
+        self.calls += 1;
+        if self.calls % 2 == 1 {
+            Err(io::Error::other("flaky"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn name(&self) -> String {
+        "flaky".to_string()
+    }
+}
+
+#[tokio::test]
+async fn successful_delivery_resets_consecutive_failures() {
+    let bus = EventBusConfig::new(16, vec![SinkConfig::Memory])
+        .with_sink_disable_threshold(2)
+        .build_event_bus();
+    assert_eq!(bus.sink_disable_threshold(), Some(2));
+    bus.add_sink(FlakySink { calls: 0 });
+    let _events = bus.subscribe();
+    let mut diags = bus.diagnostics();
+    let emitter = bus.get_emitter();
+
+    for i in 0..6 {
+        emitter
+            .emit(Event::node_message("scope", format!("msg{i}")))
+            .unwrap();
+    }
+    for _ in 0..3 {
+        let diag = diags.recv().await.expect("flaky diagnostic");
+        assert_eq!(diag.consecutive_failures, 1);
+        assert!(!diag.disabled);
+    }
+    bus.stop_listener().await;
+
+    let health = bus.sink_health();
+    let flaky = health.iter().find(|h| h.sink == "flaky").unwrap();
+    assert_eq!(flaky.error_count, 3);
+    assert_eq!(flaky.consecutive_failures, 0);
+    assert!(!flaky.disabled);
+}
+
+#[tokio::test]
+async fn sink_health_tracks_disabled_sinks_without_diagnostics() {
+    let bus = bus_with_diagnostics(false, false, 16, 16).with_sink_disable_threshold(2);
+    bus.add_sink(NamedFailingSink);
+    let _events = bus.subscribe();
+    let emitter = bus.get_emitter();
+
+    for i in 0..3 {
+        emitter
+            .emit(Event::node_message("scope", format!("msg{i}")))
+            .unwrap();
+    }
+    bus.flush(std::time::Duration::from_secs(1)).await.unwrap();
+
+    let metrics = bus.metrics();
+    assert_eq!(metrics.disabled_sinks(), 1);
+    let health = &metrics.sinks[0];
+    assert_eq!(health.sink, "custom.named");
+    assert_eq!(health.consecutive_failures, 2);
+    assert!(health.disabled);
+}
@@ -0,0 +1,99 @@
+PRAGMA foreign_keys=OFF;
+BEGIN TRANSACTION;
+CREATE TABLE _sqlx_migrations (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+INSERT INTO _sqlx_migrations VALUES(1,'init','2026-10-17 07:02:26',1,X'ecc7bf9266d8330ecd18fddd0379e8ecf2200811b2c41cab167c81215e37290c15efb376684d9f16a8f109145e4399fe',1562979);
+CREATE TABLE sessions (
+    id                       TEXT PRIMARY KEY, -- session_id
+    created_at               TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+    updated_at               TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+
+    -- Concurrency limit used when the session was created (for reference / resume)
+    concurrency_limit        INTEGER NOT NULL,
+
+    -- Denormalized latest checkpoint snapshot (mirrors most recent row in steps)
+    last_step                INTEGER NOT NULL DEFAULT 0,
+    last_state_json          TEXT,   -- Full VersionedState JSON (messages, extra, versions)
+    last_frontier_json       TEXT,   -- JSON array of node kinds
+    last_versions_seen_json  TEXT    -- JSON object: { "<node_id>": { "messages": <u64>, "extra": <u64>, ... } }
+);
+INSERT INTO sessions VALUES('compat-session','2026-10-17T07:02:26.693Z','2026-10-17T07:02:26.696Z',1,2,'{"messages":{"version":2,"items":[{"role":"user","content":"Say hello"},{"role":"assistant","content":"Hello from the archive."}]},"extra":{"version":2,"map":{"profile":{"tags":["a","b"],"tier":"gold"},"reply_count":1}},"errors":{"version":1,"items":[{"when":"2026-10-17T07:02:26.695714233Z","scope":{"scope":"node","kind":"audit","step":2},"error":{"message":"policy service unavailable","details":null},"tags":["transient"],"context":{"attempt":1}}]}}','["End"]','{"Custom(\"reply\")":{"messages":1,"extra":1},"Custom(\"audit\")":{"messages":2,"extra":2}}');
+CREATE TABLE steps (
+    session_id             TEXT    NOT NULL,
+    step                   INTEGER NOT NULL,
+    created_at             TEXT    NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+
+    -- Durable snapshot data
+    state_json             TEXT    NOT NULL, -- Full VersionedState JSON
+    frontier_json          TEXT    NOT NULL, -- JSON array
+    versions_seen_json     TEXT    NOT NULL, -- JSON object of objects
+
+    -- Execution metadata (from StepReport)
+    ran_nodes_json         TEXT    NOT NULL, -- JSON array
+    skipped_nodes_json     TEXT    NOT NULL, -- JSON array
+    updated_channels_json  TEXT,             -- JSON array of updated channel names (may be empty/NULL)
+
+    -- Optional future fields (placeholders for forward-compat):
+    -- error_json          TEXT,  -- structured error info if barrier failed
+    -- pause_reason_json   TEXT,  -- if an interrupt paused the session at this step
+
+    PRIMARY KEY (session_id, step),
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+INSERT INTO steps VALUES('compat-session',0,'2026-10-17T07:02:26.693Z','{"messages":{"version":1,"items":[{"role":"user","content":"Say hello"}]},"extra":{"version":1,"map":{}},"errors":{"version":1,"items":[]}}','["Custom:reply"]','{}','[]','[]','[]');
+INSERT INTO steps VALUES('compat-session',1,'2026-10-17T07:02:26.695Z','{"messages":{"version":2,"items":[{"role":"user","content":"Say hello"},{"role":"assistant","content":"Hello from the archive."}]},"extra":{"version":2,"map":{"profile":{"tags":["a","b"],"tier":"gold"},"reply_count":1}},"errors":{"version":1,"items":[]}}','["Custom:audit"]','{"Custom(\"reply\")":{"messages":1,"extra":1}}','[]','[]','[]');
+INSERT INTO steps VALUES('compat-session',2,'2026-10-17T07:02:26.696Z','{"messages":{"version":2,"items":[{"role":"user","content":"Say hello"},{"role":"assistant","content":"Hello from the archive."}]},"extra":{"version":2,"map":{"profile":{"tags":["a","b"],"tier":"gold"},"reply_count":1}},"errors":{"version":1,"items":[{"when":"2026-10-17T07:02:26.695714233Z","scope":{"scope":"node","kind":"audit","step":2},"error":{"message":"policy service unavailable","details":null},"tags":["transient"],"context":{"attempt":1}}]}}','["End"]','{"Custom(\"reply\")":{"messages":1,"extra":1},"Custom(\"audit\")":{"messages":2,"extra":2}}','[]','[]','[]');
+CREATE VIEW v_latest_checkpoints AS
+SELECT
+    s.id AS session_id,
+    s.concurrency_limit,
+    s.created_at AS session_created_at,
+    s.updated_at AS session_updated_at,
+    st.step,
+    st.created_at AS step_created_at,
+    st.state_json,
+    st.frontier_json,
+    st.versions_seen_json,
+    st.ran_nodes_json,
+    st.skipped_nodes_json,
+    st.updated_channels_json
+FROM sessions s
+LEFT JOIN steps st
+  ON st.session_id = s.id
+ AND st.step = s.last_step;
+CREATE TRIGGER trg_steps_after_insert
+AFTER INSERT ON steps
+BEGIN
+    UPDATE sessions
+    SET
+        updated_at              = strftime('%Y-%m-%dT%H:%M:%fZ','now'),
+        last_step               = NEW.step,
+        last_state_json         = NEW.state_json,
+        last_frontier_json      = NEW.frontier_json,
+        last_versions_seen_json = NEW.versions_seen_json
+    WHERE id = NEW.session_id;
+END;
+CREATE TRIGGER trg_steps_after_update
+AFTER UPDATE ON steps
+WHEN (SELECT last_step FROM sessions WHERE id = NEW.session_id) = NEW.step
+BEGIN
+    UPDATE sessions
+    SET
+        updated_at              = strftime('%Y-%m-%dT%H:%M:%fZ','now'),
+        last_state_json         = NEW.state_json,
+        last_frontier_json      = NEW.frontier_json,
+        last_versions_seen_json = NEW.versions_seen_json
+    WHERE id = NEW.session_id;
+END;
+CREATE INDEX idx_sessions_updated_at ON sessions(updated_at DESC);
+CREATE INDEX idx_steps_session_step_desc
+    ON steps(session_id, step DESC);
+CREATE INDEX idx_steps_session_step_asc
+    ON steps(session_id, step ASC);
+COMMIT;
@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use serde_json::json;
+use weavegraph::channels::Channel;
+use weavegraph::graphs::templates::{LinearPipeline, MapReduce, ReflectionLoop, SupervisorWorkers};
+use weavegraph::message::{Message, Role};
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::utils::collections::new_extra_map;
+
+mod common;
+use common::*;
+
+fn contents(state: &VersionedState) -> Vec<String> {
+    state
+        .messages
+        .snapshot()
+        .into_iter()
+        .filter(|m| m.role != Role::User)
+        .map(|m| m.content)
+        .collect()
+}
+
+/// Sends work to `research` until it has reported once, then finishes.
+struct Supervisor;
+
+#[async_trait]
+impl Node for Supervisor {
+    async fn run(&self, snapshot: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let reported = snapshot.messages.iter().any(|m| m.content == "researched");
+        let mut extra = new_extra_map();
+        extra.insert(
+            "next".into(),
+            json!(if reported { "End" } else { "research" }),
+        );
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+struct Doubler;
+
+#[async_trait]
+impl Node for Doubler {
+    async fn run(&self, snapshot: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let n = snapshot.extra["item"].as_i64().unwrap();
+        let mut extra = new_extra_map();
+        extra.insert("double".into(), json!(n * 2));
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+struct Sum;
+
+#[async_trait]
+impl Node for Sum {
+    async fn run(&self, snapshot: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let total: i64 = snapshot.extra["map_results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["double"].as_i64().unwrap())
+            .sum();
+        let mut extra = new_extra_map();
+        extra.insert("total".into(), json!(total));
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+/// Approves once the draft has been revised `needed` times.
+struct Critic {
+    needed: usize,
+}
+
+#[async_trait]
+impl Node for Critic {
+    async fn run(&self, snapshot: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let revisions = snapshot
+            .messages
+            .iter()
+            .filter(|m| m.content == "revised")
+            .count();
+        let mut extra = new_extra_map();
+        extra.insert("approved".into(), json!(revisions >= self.needed));
+        Ok(NodePartial::new()
+            .with_messages(vec![Message::with_role(Role::Assistant, "critiqued")])
+            .with_extra(extra))
+    }
+}
+
+#[tokio::test]
+async fn test_linear_pipeline_runs_stages_in_order() {
+    let app = LinearPipeline::new()
+        .stage("a", SimpleMessageNode::new("a"))
+        .stage("b", SimpleMessageNode::new("b"))
+        .stage("c", SimpleMessageNode::new("c"))
+        .into_builder()
+        .compile()
+        .unwrap();
+
+    let state = app.invoke(state_with_user("go")).await.unwrap();
+    assert_eq!(contents(&state), vec!["a", "b", "c"]);
+}
+
+#[tokio::test]
+async fn test_supervisor_routes_to_worker_and_finishes() {
+    let app = SupervisorWorkers::new("supervisor", Supervisor)
+        .worker("research", SimpleMessageNode::new("researched"))
+        .worker("write", SimpleMessageNode::new("written"))
+        .into_builder()
+        .compile()
+        .unwrap();
+
+    let state = app.invoke(state_with_user("go")).await.unwrap();
+    assert_eq!(contents(&state), vec!["researched"]);
+    assert_eq!(state.extra.snapshot()["next"], json!("End"));
+}
+
+#[tokio::test]
+async fn test_map_reduce_runs_mapper_per_item_in_order() {
+    let app = MapReduce::new(Doubler, Sum)
+        .concurrency(2)
+        .into_builder()
+        .compile()
+        .unwrap();
+
+    let state = app
+        .invoke(state_with_extra(&[("items", json!([1, 2, 3, 4]))]))
+        .await
+        .unwrap();
+    let extra = state.extra.snapshot();
+    assert_eq!(
+        extra["map_results"],
+        json!([{"double": 2}, {"double": 4}, {"double": 6}, {"double": 8}])
+    );
+    assert_eq!(extra["total"], json!(20));
+}
+
+#[tokio::test]
+async fn test_map_reduce_requires_items() {
+    let app = MapReduce::new(Doubler, Sum)
+        .into_builder()
+        .compile()
+        .unwrap();
+    assert!(app.invoke(state_with_user("no items")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_reflection_loop_stops_when_approved() {
+    let app = ReflectionLoop::new(
+        SimpleMessageNode::new("drafted"),
+        Critic { needed: 2 },
+        SimpleMessageNode::new("revised"),
+    )
+    .into_builder()
+    .compile()
+    .unwrap();
+
+    let state = app.invoke(state_with_user("go")).await.unwrap();
+    assert_eq!(
+        contents(&state),
+        vec![
+            "drafted",
+            "critiqued",
+            "revised",
+            "critiqued",
+            "revised",
+            "critiqued"
+        ]
+    );
+    assert_eq!(state.extra.snapshot()["approved"], json!(true));
+}
+
+#[tokio::test]
+async fn test_reflection_loop_stops_at_max_revisions() {
+    let app = ReflectionLoop::new(
+        SimpleMessageNode::new("drafted"),
+        Critic { needed: 10 },
+        SimpleMessageNode::new("revised"),
+    )
+    .max_revisions(1)
+    .into_builder()
+    .compile()
+    .unwrap();
+
+    let state = app.invoke(state_with_user("go")).await.unwrap();
+    assert_eq!(
+        contents(&state),
+        vec!["drafted", "critiqued", "revised", "critiqued"]
+    );
+    assert_eq!(state.extra.snapshot()["approved"], json!(false));
+}
@@ -2,9 +2,10 @@ mod common;
 
 use common::*;
 use std::sync::Arc;
-use weavegraph::graphs::{EdgePredicate, GraphBuilder};
+use weavegraph::graphs::{ConditionalEdge, EdgePredicate, GraphBuilder, RoutingContext};
 use weavegraph::node::NodePartial;
 use weavegraph::reducers::Reducer;
+use weavegraph::runtimes::RuntimeConfig;
 use weavegraph::state::VersionedState;
 use weavegraph::types::{ChannelType, NodeKind};
 
@@ -60,6 +61,32 @@ fn test_add_conditional_edge() {
     assert_eq!((ce.predicate())(snap), vec!["Y".to_string()]);
 }
 
+#[test]
+fn test_conditional_edge_with_context_evaluates_routing_context() {
+    let from = NodeKind::Custom("classify".into());
+    let edge = ConditionalEdge::with_context(
+        from.clone(),
+        Arc::new(|ctx| {
+            let target = ctx
+                .config_value("target")
+                .and_then(|v| v.as_str())
+                .unwrap_or("fallback");
+            vec![format!("{target}@{}", ctx.step())]
+        }),
+    );
+    assert!(edge.uses_context());
+
+    let snapshot = empty_snapshot();
+    let config = RuntimeConfig::default().with_value("target", "review");
+    let ctx = RoutingContext::new(&from, &snapshot)
+        .with_step(2)
+        .with_config(&config);
+    assert_eq!(edge.evaluate(&ctx), vec!["review@2".to_string()]);
+
+    // The snapshot-only view still works, without step or config data.
+    assert_eq!((edge.predicate())(snapshot), vec!["fallback@0".to_string()]);
+}
+
 #[test]
 fn test_graph_builder_new() {
     let err = GraphBuilder::new().compile().err().unwrap();
@@ -649,3 +676,367 @@ fn test_topological_sort_deterministic() {
     assert!(x_pos < y_pos);
     assert!(y_pos < z_pos);
 }
+
+#[test]
+fn test_named_entry_points_count_as_reachable() {
+    let main = NodeKind::Custom("main".into());
+    let side = NodeKind::Custom("side".into());
+    let app = GraphBuilder::new()
+        .add_node(main.clone(), NoopNode)
+        .add_node(side.clone(), NoopNode)
+        .add_edge(NodeKind::Start, main.clone())
+        .add_edge(main, NodeKind::End)
+        .add_entry("side", [side.clone()])
+        .add_edge(side.clone(), NodeKind::End)
+        .compile()
+        .expect("side is reachable via its entry point");
+
+    assert_eq!(app.entry_points().get("side"), Some(&vec![side]));
+}
+
+#[test]
+fn test_entry_points_without_start_edges_compile() {
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("A".into()), NoopNode)
+        .add_entry("only", [NodeKind::Custom("A".into())])
+        .add_edge(NodeKind::Custom("A".into()), NodeKind::End)
+        .compile();
+    assert!(app.is_ok());
+}
+
+#[test]
+fn test_invalid_entry_points_rejected() {
+    use weavegraph::graphs::GraphCompileError;
+
+    let empty = GraphBuilder::new()
+        .add_node(NodeKind::Custom("A".into()), NoopNode)
+        .add_edge(NodeKind::Start, NodeKind::Custom("A".into()))
+        .add_edge(NodeKind::Custom("A".into()), NodeKind::End)
+        .add_entry("nothing", Vec::new())
+        .compile();
+    assert!(matches!(
+        empty.err(),
+        Some(GraphCompileError::EmptyEntryPoint(name)) if name == "nothing"
+    ));
+
+    let unknown = GraphBuilder::new()
+        .add_node(NodeKind::Custom("A".into()), NoopNode)
+        .add_edge(NodeKind::Start, NodeKind::Custom("A".into()))
+        .add_edge(NodeKind::Custom("A".into()), NodeKind::End)
+        .add_entry("bad", [NodeKind::End])
+        .compile();
+    match unknown.err() {
+        Some(GraphCompileError::InvalidEntryNode { entry, node }) => {
+            assert_eq!(entry, "bad");
+            assert_eq!(node, NodeKind::End);
+        }
+        other => panic!("Expected InvalidEntryNode, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_invalid_joins_rejected() {
+    use weavegraph::graphs::GraphCompileError;
+
+    let a = NodeKind::Custom("A".into());
+    let base = || {
+        GraphBuilder::new()
+            .add_node(NodeKind::Custom("A".into()), NoopNode)
+            .add_edge(NodeKind::Start, NodeKind::Custom("A".into()))
+            .add_edge(NodeKind::Custom("A".into()), NodeKind::End)
+    };
+
+    let empty = base().add_join(a.clone(), Vec::new()).compile();
+    assert!(matches!(empty.err(), Some(GraphCompileError::EmptyJoin(node)) if node == a));
+
+    match base()
+        .add_join(a.clone(), [NodeKind::Start])
+        .compile()
+        .err()
+    {
+        Some(GraphCompileError::InvalidJoinNode { join, node }) => {
+            assert_eq!(join, a);
+            assert_eq!(node, NodeKind::Start);
+        }
+        other => panic!("Expected InvalidJoinNode, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_dynamic_graph_applies_validated_revisions() {
+    use weavegraph::graphs::{DynamicGraph, DynamicGraphError, GraphCompileError, GraphPatch};
+
+    let a = NodeKind::Custom("A".into());
+    let app = GraphBuilder::new()
+        .add_node(a.clone(), NoopNode)
+        .add_edge(NodeKind::Start, a.clone())
+        .add_edge(a.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let initial_hash = app.graph_definition_hash();
+    let graph = DynamicGraph::new(app);
+    assert_eq!(graph.version(), 0);
+
+    assert!(matches!(
+        graph.apply(GraphPatch::new()),
+        Err(DynamicGraphError::EmptyPatch)
+    ));
+    assert!(matches!(
+        graph.apply(GraphPatch::new().add_node(a.clone(), NoopNode)),
+        Err(DynamicGraphError::DuplicateNode(kind)) if kind == a
+    ));
+    let b = NodeKind::Custom("B".into());
+    assert!(matches!(
+        graph.apply(GraphPatch::new().add_node(b.clone(), NoopNode)),
+        Err(DynamicGraphError::Invalid(
+            GraphCompileError::UnreachableNodes { .. }
+        ))
+    ));
+    assert_eq!(
+        graph.version(),
+        0,
+        "rejected patches leave the graph unchanged"
+    );
+
+    let revision = graph
+        .apply(
+            GraphPatch::new()
+                .add_node(b.clone(), NoopNode)
+                .add_edge(a.clone(), b.clone())
+                .add_edge(b.clone(), NodeKind::End),
+        )
+        .unwrap();
+    assert_eq!(revision.version, 1);
+    assert_eq!(revision.added_nodes, vec![b.clone()]);
+    assert_ne!(revision.graph_hash, initial_hash);
+    assert_eq!(graph.app().graph_definition_hash(), revision.graph_hash);
+    assert!(graph.app().nodes().contains_key(&b));
+    assert_eq!(graph.nodes_added_since(0), vec![b]);
+    assert_eq!(graph.revisions().len(), 2);
+}
+
+#[test]
+fn test_describe_lists_graph_and_masks_secrets() {
+    let a = NodeKind::Custom("A".into());
+    let policy = weavegraph::redaction::RedactionPolicy::new()
+        .redact_key("customer")
+        .report_only();
+    let config = RuntimeConfig::default()
+        .with_value("rounds", 3)
+        .with_value("openai_api_key", "sk-live-123")
+        .with_value(
+            "database",
+            serde_json::json!({"url": "postgres://app:hunter2@db/prod"}),
+        )
+        .with_value("customer", "Ada")
+        .with_redaction(policy);
+    let app = GraphBuilder::new()
+        .add_node(a.clone(), NoopNode)
+        .add_edge(NodeKind::Start, a.clone())
+        .add_conditional_edge_spec(
+            ConditionalEdge::new(a.clone(), Arc::new(|_| vec![NodeKind::end_target()]))
+                .with_label("finish"),
+        )
+        .add_entry("direct", [a.clone()])
+        .with_reducer(ChannelType::Extra, Arc::new(StableLabelReducerA))
+        .with_runtime_config(config)
+        .compile()
+        .unwrap();
+
+    let descriptor = app.describe();
+    assert_eq!(descriptor.graph_hash, app.graph_definition_hash());
+    assert_eq!(descriptor.nodes.len(), 1);
+    assert_eq!(descriptor.nodes[0].id, "Custom:A");
+    assert!(descriptor.nodes[0].implementation.ends_with("NoopNode"));
+    assert_eq!(descriptor.edges.len(), 1);
+    assert_eq!(descriptor.edges[0].from, "Start");
+    assert_eq!(
+        descriptor.conditional_edges[0].label.as_deref(),
+        Some("finish")
+    );
+    assert!(!descriptor.conditional_edges[0].uses_context);
+    assert_eq!(descriptor.entry_points["direct"], vec!["Custom:A"]);
+    assert!(
+        descriptor.reducers["extra"]
+            .iter()
+            .any(|label| label == "stable-extra-label")
+    );
+    assert_eq!(descriptor.conflict_policies["*"], "last_wins");
+
+    let values = &descriptor.runtime.values;
+    assert_eq!(values["rounds"], 3);
+    assert_eq!(values["openai_api_key"], "[REDACTED]");
+    assert_eq!(
+        values["database"]["url"],
+        "postgres://app:[REDACTED]@db/prod"
+    );
+    // Report-only policies are still enforced for descriptors.
+    assert_ne!(values["customer"], "Ada");
+    assert!(descriptor.runtime.redaction.is_some());
+
+    let json = serde_json::to_string(&descriptor).unwrap();
+    for secret in ["sk-live-123", "hunter2", "Ada"] {
+        assert!(!json.contains(secret), "{secret} leaked");
+    }
+    let parsed: weavegraph::app::AppDescriptor = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, descriptor);
+}
+
+#[test]
+fn test_expression_evaluates_state_paths() {
+    use serde_json::json;
+    use weavegraph::graphs::Expression;
+
+    let state = VersionedState::builder()
+        .with_user_message("hi")
+        .with_assistant_message("hello")
+        .with_extra("score", json!(0.93))
+        .with_extra("review-state", json!("approved"))
+        .with_extra("tags", json!(["a", "b"]))
+        .build();
+    let snapshot = state.snapshot();
+    let from = NodeKind::Custom("grade".into());
+    let config = RuntimeConfig::default().with_value("max_steps", 3);
+    let ctx = RoutingContext::new(&from, &snapshot)
+        .with_step(4)
+        .with_config(&config);
+
+    let holds = |source: &str| Expression::parse(source).unwrap().matches(&ctx);
+    assert!(holds("extra.score > 0.8 && messages.len < 20"));
+    assert!(holds(r#"extra["review-state"] == 'approved'"#));
+    assert!(holds(
+        r#"messages.last.role == "assistant" && messages[0].content == "hi""#
+    ));
+    assert!(holds("extra.tags.len == 2 && extra.tags[-1] == 'b'"));
+    assert!(holds("step >= config.max_steps || false"));
+    assert!(holds(
+        "!(extra.missing.deeper) && errors.len == 0 && -1 < 0"
+    ));
+    assert!(!holds("extra.score < 0.5 || extra.tags.first == 'z'"));
+    assert_eq!(
+        Expression::parse("extra.tags.len").unwrap().evaluate(&ctx),
+        json!(2)
+    );
+}
+
+#[test]
+fn test_invalid_expression_edges_fail_compile() {
+    use weavegraph::graphs::{Expression, GraphCompileError};
+
+    for (source, offset) in [
+        ("extra.score >", 13),
+        ("score > 1", 0),
+        ("extra.a < 1 < 2", 12),
+        ("(extra.a", 8),
+        ("extra.a == 'open", 11),
+    ] {
+        let err = Expression::parse(source).unwrap_err();
+        assert_eq!(err.offset, offset, "{source}: {err}");
+    }
+
+    let a = NodeKind::Custom("A".into());
+    let result = GraphBuilder::new()
+        .add_node(a.clone(), NoopNode)
+        .add_edge(NodeKind::Start, a.clone())
+        .add_guarded_edge(a.clone(), NodeKind::End, "extra.done ==")
+        .compile();
+    match result.err() {
+        Some(GraphCompileError::InvalidExpression { from, source }) => {
+            assert_eq!(from, a);
+            assert_eq!(source.source_text, "extra.done ==");
+        }
+        other => panic!("Expected InvalidExpression, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_unregistered_nodes_lists_every_missing_reference() {
+    use weavegraph::graphs::GraphCompileError;
+    use weavegraph::node_kind;
+
+    let builder = GraphBuilder::new()
+        .add_node(node_kind!("fetch"), NoopNode)
+        .add_edge(node_kind!(Start), node_kind!("fetch"))
+        .add_edge(node_kind!("fetch"), node_kind!("sumarize"))
+        .add_edge(node_kind!("sumarize"), node_kind!(End))
+        .add_conditional_edge(
+            node_kind!("reviw"),
+            Arc::new(|_| vec![NodeKind::end_target()]),
+        );
+    assert_eq!(
+        builder.unregistered_nodes(),
+        vec![node_kind!("reviw"), node_kind!("sumarize")]
+    );
+    assert!(matches!(
+        builder.compile().err(),
+        Some(GraphCompileError::UnknownNode(_))
+    ));
+
+    // A conditional edge from an unregistered node alone is now a compile error.
+    let conditional_only = GraphBuilder::new()
+        .add_node(node_kind!("fetch"), NoopNode)
+        .add_edge(node_kind!(Start), node_kind!("fetch"))
+        .add_edge(node_kind!("fetch"), node_kind!(End))
+        .add_conditional_edge(
+            node_kind!("fech"),
+            Arc::new(|_| vec![NodeKind::end_target()]),
+        );
+    match conditional_only.compile().err() {
+        Some(GraphCompileError::UnknownNode(node)) => assert_eq!(node, "fech"),
+        other => panic!("Expected UnknownNode, got: {other:?}"),
+    }
+
+    let complete = GraphBuilder::new()
+        .add_node(node_kind!("fetch"), NoopNode)
+        .add_edge(node_kind!(Start), node_kind!("fetch"))
+        .add_edge(node_kind!("fetch"), node_kind!(End));
+    assert!(complete.unregistered_nodes().is_empty());
+}
+
+#[test]
+fn test_edge_labels_reach_describe_and_mermaid() {
+    use weavegraph::graphs::EdgeLabel;
+
+    let review = NodeKind::Custom("review".into());
+    let publish = NodeKind::Custom("publish step".into());
+    let builder = GraphBuilder::new()
+        .add_node(review.clone(), NoopNode)
+        .add_node(publish.clone(), NoopNode)
+        .add_edge(NodeKind::Start, review.clone())
+        .add_edge_labeled(
+            review.clone(),
+            publish.clone(),
+            EdgeLabel::new("on_success").with_metadata("owner", serde_json::json!("qa")),
+        )
+        .add_edge_labeled(publish.clone(), NodeKind::End, "done|ok");
+
+    let mermaid = builder.to_mermaid();
+    assert!(mermaid.contains("Start --> review"));
+    assert!(mermaid.contains("publish_step[\"publish step\"]"));
+    assert!(mermaid.contains("review -->|on_success| publish_step"));
+    assert!(mermaid.contains("publish_step -->|done#124;ok| End"));
+
+    let app = builder.compile().unwrap();
+    assert_eq!(
+        app.edge_label(&review, &publish).map(EdgeLabel::label),
+        Some("on_success")
+    );
+    assert!(app.edge_label(&NodeKind::Start, &review).is_none());
+
+    let descriptor = app.describe();
+    let labelled = descriptor
+        .edges
+        .iter()
+        .find(|edge| edge.from == "Custom:review")
+        .unwrap();
+    assert_eq!(labelled.label.as_deref(), Some("on_success"));
+    assert_eq!(labelled.metadata["owner"], "qa");
+    let json = serde_json::to_value(&descriptor).unwrap();
+    let start = json["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|edge| edge["from"] == "Start")
+        .unwrap();
+    assert!(start.get("label").is_none());
+}
@@ -40,3 +40,247 @@ fn test_serialization() {
     let deserialized: Message = serde_json::from_str(&json).expect("Deserialization failed");
     assert_eq!(original, deserialized);
 }
+
+#[test]
+fn test_chat_export_round_trips_tool_calls() {
+    use serde_json::{Value, json};
+    use weavegraph::llm::{CHAT_METADATA_KEY, ChatExport};
+
+    let document = json!({
+        "messages": [
+            {"role": "system", "content": "Use tools."},
+            {"role": "user", "content": [{"type": "text", "text": "Weather in "}, {"type": "text", "text": "Oslo?"}]},
+            {"role": "assistant", "content": null, "tool_calls": [
+                {"id": "call_1", "type": "function", "function": {"name": "weather", "arguments": "{\"city\":\"Oslo\"}"}}
+            ]},
+            {"role": "tool", "content": "-3C", "tool_call_id": "call_1"},
+            {"role": "assistant", "content": "It is -3C."}
+        ]
+    });
+
+    let import = ChatExport::from_json(&document.to_string()).unwrap();
+    let state = import.to_state();
+    let snapshot = state.snapshot();
+    assert_eq!(snapshot.messages.len(), 5);
+    assert_eq!(snapshot.messages[1].content, "Weather in Oslo?");
+    assert_eq!(snapshot.messages[2].content, "");
+    assert_eq!(snapshot.messages[3].role, Role::Tool);
+    let table = snapshot.extra[CHAT_METADATA_KEY].as_object().unwrap();
+    assert_eq!(table.keys().collect::<Vec<_>>(), vec!["2", "3"]);
+
+    let export = snapshot.to_chat_export();
+    assert_eq!(export.messages[2].content, None);
+    assert_eq!(export.messages[2].tool_calls[0].function.name, "weather");
+    assert_eq!(export.messages[3].tool_call_id.as_deref(), Some("call_1"));
+
+    let exported: Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+    assert_eq!(
+        exported["messages"][1],
+        json!({"role": "user", "content": "Weather in Oslo?"})
+    );
+    assert_eq!(exported["messages"][2], document["messages"][2]);
+    assert_eq!(exported["messages"][3], document["messages"][3]);
+}
+
+#[test]
+fn test_chat_export_accepts_bare_arrays_and_rejects_garbage() {
+    use weavegraph::llm::{ChatExport, ChatExportError};
+
+    let export = ChatExport::from_json(r#"[{"role":"developer","content":"be brief"}]"#).unwrap();
+    let messages = export.to_messages();
+    assert_eq!(messages[0].role, Role::Custom("developer".into()));
+
+    assert!(matches!(
+        ChatExport::from_json(r#"{"messages": 3}"#),
+        Err(ChatExportError::Json(_))
+    ));
+}
+
+#[test]
+fn test_message_assembler_records_stream_timing() {
+    use std::time::Duration;
+    use weavegraph::llm::{MessageAssembler, STREAM_TIMING_KEY, StreamTiming};
+    use weavegraph::state::VersionedState;
+
+    let state = VersionedState::new_with_user_message("Greet me");
+    let snapshot = state.snapshot();
+
+    let mut assembler = MessageAssembler::new(Role::Assistant);
+    assembler.push_at("Hi", Duration::from_millis(80));
+    assembler.push_at("", Duration::from_millis(90));
+    assembler.push_at(" there", Duration::from_millis(100));
+    // Out-of-order offsets are clamped to the previous chunk.
+    assembler.push_at("!", Duration::from_millis(95));
+    assert_eq!(assembler.content(), "Hi there!");
+
+    let partial = assembler.into_partial(&snapshot);
+    let messages = partial.messages.as_ref().unwrap();
+    assert_eq!(messages[0].content, "Hi there!");
+    let extra = partial.extra.as_ref().unwrap();
+    assert!(extra[STREAM_TIMING_KEY].get("1").is_some());
+
+    let mut recorded = snapshot.clone();
+    recorded.messages.push(messages[0].clone());
+    recorded.extra.extend(extra.clone());
+    let timing = StreamTiming::from_snapshot(&recorded, 1).unwrap();
+    assert_eq!(
+        timing.first_token_latency(),
+        Some(Duration::from_millis(80))
+    );
+    assert_eq!(
+        timing.chunk_durations(),
+        vec![
+            Duration::from_millis(80),
+            Duration::from_millis(20),
+            Duration::ZERO
+        ]
+    );
+    assert_eq!(timing.chunk_texts("Hi there!"), vec!["Hi", " there", "!"]);
+    assert!(timing.total() >= Duration::from_millis(100));
+    assert_eq!(timing.event_metadata()["chunk_count"], 3);
+    assert!(StreamTiming::from_snapshot(&recorded, 0).is_none());
+}
+
+mod summarize {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use weavegraph::app::App;
+    use weavegraph::channels::Channel;
+    use weavegraph::graphs::GraphBuilder;
+    use weavegraph::llm::{
+        LlmError, LlmProvider, LlmResponse, SUMMARY_REPLACEMENT_KEY, SummarizeHistoryNode,
+        SummaryRecord,
+    };
+    use weavegraph::message::{Message, Role};
+    use weavegraph::state::VersionedState;
+    use weavegraph::types::NodeKind;
+
+    /// Provider that records each request and answers with a numbered summary.
+    #[derive(Default)]
+    struct RecordingModel {
+        requests: Mutex<Vec<Vec<Message>>>,
+    }
+
+    #[async_trait]
+    impl LlmProvider for RecordingModel {
+        async fn chat(&self, messages: &[Message]) -> Result<LlmResponse, LlmError> {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(messages.to_vec());
+            Ok(LlmResponse {
+                content: format!(" summary {} ", requests.len()),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn app(model: Arc<RecordingModel>) -> App {
+        let compact = NodeKind::Custom("compact".into());
+        GraphBuilder::new()
+            .add_summarizer(
+                compact.clone(),
+                SummarizeHistoryNode::new(model)
+                    .with_token_threshold(20)
+                    .keep_recent(2)
+                    .with_prompt("Summarize."),
+            )
+            .add_edge(NodeKind::Start, compact.clone())
+            .add_edge(compact, NodeKind::End)
+            .compile()
+            .unwrap()
+    }
+
+    fn turn(index: usize) -> Message {
+        let role = if index.is_multiple_of(2) {
+            Role::User
+        } else {
+            Role::Assistant
+        };
+        Message::with_role(role, &format!("turn {index} with some padding text"))
+    }
+
+    #[tokio::test]
+    async fn test_summarizer_replaces_old_span_and_records_it() {
+        let model = Arc::new(RecordingModel::default());
+        let app = app(model.clone());
+        let mut history = vec![Message::system("You are terse.")];
+        history.extend((0..6).map(turn));
+
+        let state = app
+            .invoke(VersionedState::new_with_messages(history))
+            .await
+            .unwrap();
+        let messages = state.messages.snapshot();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].content, "You are terse.");
+        assert_eq!(messages[1], Message::system("summary 1"));
+        assert_eq!(messages[2].content, turn(4).content);
+        assert_eq!(messages[3].content, turn(5).content);
+
+        let request = model.requests.lock().unwrap()[0].clone();
+        assert_eq!(request[0], Message::system("Summarize."));
+        assert!(request[1].content.starts_with("user: turn 0"));
+        assert!(
+            request[1]
+                .content
+                .ends_with("assistant: turn 3 with some padding text")
+        );
+
+        let snapshot = state.snapshot();
+        assert!(!snapshot.extra.contains_key(SUMMARY_REPLACEMENT_KEY));
+        let records = SummaryRecord::from_snapshot(&snapshot);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].start, records[0].end), (1, 5));
+        assert_eq!(records[0].replaced_messages(), 4);
+        assert_eq!(records[0].step, 1);
+        assert!(records[0].replaced_tokens > records[0].summary_tokens);
+    }
+
+    #[tokio::test]
+    async fn test_summarizer_folds_previous_summary_into_the_next() {
+        let model = Arc::new(RecordingModel::default());
+        let app = app(model.clone());
+
+        let first = app
+            .invoke(VersionedState::new_with_messages(
+                (0..4).map(turn).collect(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(first.messages.snapshot()[0], Message::system("summary 1"));
+
+        let mut messages = first.messages.snapshot();
+        messages.extend((4..8).map(turn));
+        let mut next = first.clone();
+        *next.messages.get_mut() = messages;
+        let second = app.invoke(next).await.unwrap();
+
+        let messages = second.messages.snapshot();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0], Message::system("summary 2"));
+        let request = model.requests.lock().unwrap()[1].clone();
+        assert!(request[1].content.starts_with("system: summary 1"));
+
+        let records = SummaryRecord::from_snapshot(&second.snapshot());
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[1].start, records[1].end), (0, 5));
+    }
+
+    #[tokio::test]
+    async fn test_summarizer_leaves_short_histories_alone() {
+        let model = Arc::new(RecordingModel::default());
+        let state = app(model.clone())
+            .invoke(VersionedState::new_with_messages(vec![
+                Message::system("You are terse."),
+                turn(0),
+                turn(1),
+                turn(2),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(state.messages.len(), 4);
+        assert!(model.requests.lock().unwrap().is_empty());
+        assert!(SummaryRecord::from_snapshot(&state.snapshot()).is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "macros")]
+
+use weavegraph::channels::errors::WeaveError;
+use weavegraph::errors::ErrorExt;
+use weavegraph::event_bus::EventBus;
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::message::{Message, Role};
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial, node};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+#[node]
+async fn echo_last(snapshot: StateSnapshot, _ctx: NodeContext) -> Result<NodePartial, NodeError> {
+    let last = snapshot.messages.last().map_or("", |m| m.content.as_str());
+    Ok(NodePartial::new().with_messages(vec![Message::assistant(&format!("echo: {last}"))]))
+}
+
+#[node(name = "counter")]
+async fn count_messages(snapshot: StateSnapshot, ctx: NodeContext) -> NodePartial {
+    NodePartial::new().with_messages(vec![Message::assistant(&format!(
+        "{} messages at step {}",
+        snapshot.messages.len(),
+        ctx.step
+    ))])
+}
+
+#[node]
+async fn parse_threshold(
+    snapshot: StateSnapshot,
+    _: NodeContext,
+) -> Result<NodePartial, WeaveError> {
+    let raw = snapshot.messages.last().map_or("", |m| m.content.as_str());
+    raw.parse::<f32>().context("parsing threshold")?;
+    Ok(NodePartial::new())
+}
+
+#[test]
+fn generated_struct_is_named_after_function() {
+    assert_eq!(EchoLast.definition_label(), "echo_last");
+    assert_eq!(CountMessages.definition_label(), "counter");
+}
+
+#[tokio::test]
+async fn macro_nodes_run_in_graph() {
+    let echo = NodeKind::Custom("echo".into());
+    let count = NodeKind::Custom("count".into());
+    let app = GraphBuilder::new()
+        .add_node(echo.clone(), EchoLast)
+        .add_node(count.clone(), CountMessages)
+        .add_edge(NodeKind::Start, echo.clone())
+        .add_edge(echo, count.clone())
+        .add_edge(count, NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let final_state = app.invoke(state_with_user("hi")).await.unwrap();
+    let contents: Vec<_> = final_state
+        .snapshot()
+        .messages
+        .iter()
+        .filter(|m| m.role == Role::Assistant)
+        .map(|m| m.content.clone())
+        .collect();
+    assert_eq!(contents, vec!["echo: hi", "2 messages at step 2"]);
+}
+
+#[tokio::test]
+async fn macro_node_converts_errors() {
+    let error = ParseThreshold
+        .run(
+            VersionedState::new_with_user_message("high").snapshot(),
+            NodeContext::new("parse", 1, EventBus::default().get_emitter()),
+        )
+        .await
+        .unwrap_err();
+
+    let NodeError::Context(error) = error else {
+        panic!("expected context error, got {error:?}");
+    };
+    assert_eq!(error.message, "parsing threshold");
+    assert!(error.cause.is_some());
+}
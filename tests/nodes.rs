@@ -1,10 +1,13 @@
 use async_trait::async_trait;
-use weavegraph::channels::errors::ErrorEvent;
+use serde_json::json;
+use weavegraph::channels::errors::{ErrorEvent, WeaveError};
+use weavegraph::errors::ErrorExt;
 use weavegraph::event_bus::EventBus;
 use weavegraph::message::{Message, Role};
 use weavegraph::node::{
-    Node, NodeContext, NodeContextError, NodeError, NodePartial, NodeResultExt,
+    BlockingNode, Node, NodeContext, NodeContextError, NodeError, NodePartial, NodeResultExt,
 };
+use weavegraph::runtimes::CheckpointerError;
 use weavegraph::state::{StateSnapshot, VersionedState};
 use weavegraph::utils::collections::new_extra_map;
 
@@ -134,11 +137,90 @@ fn test_node_result_ext_maps_external_error() {
     }
 }
 
+/// Error with a source, standing in for a companion crate's error type.
+#[derive(Debug, thiserror::Error)]
+#[error("retrieval failed")]
+struct RetrievalError(#[source] std::io::Error);
+
+#[test]
+fn test_error_ext_builds_context_ladder_across_crates() {
+    fn retrieve() -> Result<(), RetrievalError> {
+        Err(RetrievalError(std::io::Error::other("connection reset")))
+    }
+    fn node_body() -> Result<(), NodeError> {
+        retrieve().context("loading chunks for query")?;
+        Ok(())
+    }
+
+    let NodeError::Context(ladder) = node_body().unwrap_err() else {
+        panic!("Wrong variant")
+    };
+    assert_eq!(ladder.message, "loading chunks for query");
+    let cause = ladder.cause.as_deref().unwrap();
+    assert_eq!(cause.message, "retrieval failed");
+    assert_eq!(cause.cause.as_deref().unwrap().message, "connection reset");
+
+    // Existing ladders keep their details when more context is added.
+    let inner = WeaveError::msg("quota exceeded").with_details(json!({"limit": 10}));
+    let outer = Err::<(), _>(inner.clone())
+        .with_context(|| "calling guard pipeline")
+        .unwrap_err();
+    assert_eq!(outer.cause.as_deref(), Some(&inner));
+    let event = ErrorEvent::node("guard", 3, outer);
+    assert_eq!(event.error.message, "calling guard pipeline");
+}
+
+#[test]
+fn test_checkpointer_errors_convert_into_node_errors() {
+    fn node_body() -> Result<(), NodeError> {
+        Err(CheckpointerError::NotFound {
+            session_id: "s-1".into(),
+        })?
+    }
+
+    let err = node_body().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "checkpointer error: session not found: s-1"
+    );
+    assert!(matches!(
+        err,
+        NodeError::Checkpointer(CheckpointerError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_node_context_yield_partial_without_runner() {
+    let (ctx, _event_bus) = make_ctx(1);
+    let result = ctx.yield_partial(NodePartial::new());
+    assert!(matches!(
+        result,
+        Err(NodeContextError::PartialStreamUnavailable)
+    ));
+    assert!(ctx.live_snapshot().is_none());
+}
+
 #[test]
 fn test_node_context_error_variant() {
     let err = NodeContextError::EventBusUnavailable;
     match err {
         NodeContextError::EventBusUnavailable => (),
+        NodeContextError::PartialStreamUnavailable => panic!("Wrong variant"),
+        NodeContextError::PayloadSerialization { .. } => panic!("Wrong variant"),
+        _ => panic!("Wrong variant"),
+    }
+}
+
+#[tokio::test]
+async fn test_node_context_emit_typed_rejects_non_json_payload() {
+    let (ctx, _event_bus) = make_ctx(1);
+    let mut payload = std::collections::BTreeMap::new();
+    payload.insert((1, 2), "tuple keys are not JSON object keys");
+
+    let result = ctx.emit_typed("progress", &payload);
+    match result {
+        Err(NodeContextError::PayloadSerialization { scope, .. }) => assert_eq!(scope, "progress"),
+        other => panic!("Expected PayloadSerialization, got: {other:?}"),
     }
 }
 
@@ -185,3 +267,73 @@ async fn test_node_trait_eventbus_error() {
         Err(NodeError::EventBus(NodeContextError::EventBusUnavailable))
     ));
 }
+
+#[test]
+fn test_node_partial_json_round_trip_omits_unset_channels() {
+    let partial = NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, "hi")]);
+    let json = serde_json::to_value(&partial).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"messages": [{"role": "assistant", "content": "hi"}]})
+    );
+
+    let decoded: NodePartial = serde_json::from_value(serde_json::json!({
+        "extra": {"k": 1}
+    }))
+    .unwrap();
+    assert!(decoded.messages.is_none());
+    assert_eq!(decoded.extra.unwrap()["k"], serde_json::json!(1));
+}
+
+#[test]
+fn test_state_snapshot_json_round_trip() {
+    let snapshot = VersionedState::builder()
+        .with_user_message("hello")
+        .with_extra("k", serde_json::json!("v"))
+        .build()
+        .snapshot();
+    let decoded: StateSnapshot =
+        serde_json::from_value(serde_json::to_value(&snapshot).unwrap()).unwrap();
+    assert_eq!(decoded.messages, snapshot.messages);
+    assert_eq!(decoded.extra, snapshot.extra);
+    assert_eq!(decoded.messages_version, snapshot.messages_version);
+}
+
+#[tokio::test]
+async fn test_blocking_node_runs_function_off_runtime() {
+    let node = BlockingNode::new(|snapshot: StateSnapshot, ctx: NodeContext| {
+        assert!(tokio::runtime::Handle::try_current().is_ok());
+        let content = format!("{}:{}", ctx.node_id, snapshot.messages.len());
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, &content)]))
+    });
+    let (ctx, _bus) = make_ctx(1);
+    let state = VersionedState::new_with_user_message("hi");
+    let partial = node.run(state.snapshot(), ctx).await.unwrap();
+    assert_eq!(partial.messages.unwrap()[0].content, "test-node:1");
+
+    let failing = BlockingNode::new(|_: StateSnapshot, _: NodeContext| {
+        Err(NodeError::ValidationFailed("nope".into()))
+    });
+    let (ctx, _bus) = make_ctx(1);
+    let err = failing.run(state.snapshot(), ctx).await.unwrap_err();
+    assert!(matches!(err, NodeError::ValidationFailed(_)));
+}
+
+#[tokio::test]
+async fn test_node_context_rng_seed_varies_by_step_node_and_invocation() {
+    let (ctx, _bus) = make_ctx(1);
+    assert_eq!(ctx.rng_seed(), make_ctx(1).0.rng_seed());
+    assert_ne!(ctx.rng_seed(), make_ctx(2).0.rng_seed());
+
+    let mut other = make_ctx(1).0;
+    other.node_id = "other-node".into();
+    assert_ne!(ctx.rng_seed(), other.rng_seed());
+
+    let mut with_invocation = make_ctx(1).0;
+    with_invocation.invocation_id = Some("session-1".into());
+    assert_ne!(ctx.rng_seed(), with_invocation.rng_seed());
+    assert_eq!(
+        with_invocation.clone().with_rng_seed(9).rng_seed(),
+        ctx.clone().with_rng_seed(9).rng_seed()
+    );
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use weavegraph::channels::Channel;
+use weavegraph::event_bus::{Event, EventBus, MemorySink};
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::message::{Message, Role};
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::redaction::{RedactionMode, RedactionPolicy};
+use weavegraph::runtimes::{AppRunner, Checkpointer, InMemoryCheckpointer, RuntimeConfig};
+use weavegraph::state::StateSnapshot;
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+const EMAIL_PATTERN: &str = r"[\w.+-]+@[\w-]+\.[\w.]+";
+
+#[derive(Debug, Clone)]
+struct SecretNode;
+
+#[async_trait]
+impl Node for SecretNode {
+    async fn run(
+        &self,
+        _snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        ctx.emit("secret", "notify alice@example.com")?;
+        let mut extra = FxHashMap::default();
+        extra.insert(
+            "user".to_string(),
+            json!({"name": "Alice", "ssn": "123-45-6789"}),
+        );
+        extra.insert("api_key".to_string(), json!("sk-live-123"));
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+fn secret_policy() -> RedactionPolicy {
+    RedactionPolicy::new()
+        .redact_key("api_key")
+        .redact_path("extra.user.ssn")
+        .mask_pattern("email", EMAIL_PATTERN)
+        .unwrap()
+}
+
+fn make_secret_app(policy: RedactionPolicy) -> weavegraph::app::App {
+    let secret = NodeKind::Custom("secret".into());
+    GraphBuilder::new()
+        .add_node(secret.clone(), SecretNode)
+        .add_edge(NodeKind::Start, secret.clone())
+        .add_edge(secret, NodeKind::End)
+        .with_runtime_config(RuntimeConfig::default().with_redaction(policy))
+        .compile()
+        .unwrap()
+}
+
+#[test]
+fn test_redact_event_masks_message_and_metadata() {
+    let policy = secret_policy();
+    let mut metadata = FxHashMap::default();
+    metadata.insert("api_key".to_string(), json!("sk-123"));
+    metadata.insert("invocation_id".to_string(), json!("run-1"));
+    let mut event =
+        Event::node_message_with_metadata("node", 1, "scope", "contact bob@example.com", metadata);
+
+    let findings = policy.redact_event(&mut event);
+
+    assert_eq!(findings.len(), 2);
+    assert_eq!(event.message(), "contact [REDACTED]");
+    let value = event.to_json_value();
+    assert_eq!(value["metadata"]["api_key"], "[REDACTED]");
+    assert_eq!(value["metadata"]["invocation_id"], "run-1");
+}
+
+#[test]
+fn test_redact_event_masks_structured_payload() {
+    let policy = secret_policy();
+    let mut event = Event::node_payload(
+        "scope",
+        json!({"api_key": "sk-123", "contact": "bob@example.com", "count": 2}),
+    );
+
+    let findings = policy.redact_event(&mut event);
+
+    assert_eq!(findings.len(), 2);
+    let Event::Node(node) = &event else {
+        panic!("expected node event");
+    };
+    assert_eq!(
+        node.payload(),
+        Some(&json!({"api_key": "[REDACTED]", "contact": "[REDACTED]", "count": 2}))
+    );
+}
+
+#[test]
+fn test_redact_snapshot_applies_path_rules_to_messages() {
+    let policy = RedactionPolicy::new()
+        .redact_path("messages.0.content")
+        .mask_pattern("email", EMAIL_PATTERN)
+        .unwrap();
+    let mut snapshot = state_with_user("my ssn is 123-45-6789").snapshot();
+    snapshot
+        .messages
+        .push(Message::assistant("write to bob@example.com"));
+
+    let findings = policy.redact_snapshot(&mut snapshot);
+
+    assert_eq!(snapshot.messages[0].content, "[REDACTED]");
+    assert_eq!(snapshot.messages[0].role, Role::User);
+    assert_eq!(snapshot.messages[1].content, "write to [REDACTED]");
+    let locations: Vec<_> = findings.iter().map(|f| f.location.as_str()).collect();
+    assert_eq!(locations, ["messages.0.content", "messages.1.content"]);
+}
+
+#[test]
+fn test_invalid_pattern_is_rejected() {
+    let err = RedactionPolicy::new()
+        .mask_pattern("broken", "(unclosed")
+        .unwrap_err();
+    assert!(err.to_string().contains("broken"));
+}
+
+#[tokio::test]
+async fn test_runner_redacts_events_and_checkpoints_but_not_live_state() {
+    let app = make_secret_app(secret_policy());
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let event_bus = EventBus::with_sink(MemorySink::new());
+    let mut events = event_bus.subscribe();
+
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(checkpointer.clone())
+        .event_bus(event_bus)
+        .build()
+        .await;
+    runner
+        .create_session("redact".to_string(), state_with_user("hi"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("redact").await.unwrap();
+
+    let live = final_state.extra.snapshot();
+    assert_eq!(live["user"]["ssn"], "123-45-6789");
+    assert_eq!(live["api_key"], "sk-live-123");
+
+    let persisted = checkpointer.load_latest("redact").await.unwrap().unwrap();
+    let stored = persisted.state.extra.snapshot();
+    assert_eq!(stored["user"]["ssn"], "[REDACTED]");
+    assert_eq!(stored["user"]["name"], "Alice");
+    assert_eq!(stored["api_key"], "[REDACTED]");
+
+    let mut node_event = None;
+    for _ in 0..10 {
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .expect("event stream should receive an event")
+            .expect("event stream should stay open");
+        if event.scope_label() == Some("secret") {
+            node_event = Some(event);
+            break;
+        }
+    }
+    let node_event = node_event.expect("secret event should be captured");
+    assert_eq!(node_event.message(), "notify [REDACTED]");
+}
+
+#[tokio::test]
+async fn test_report_mode_records_findings_without_modifying_data() {
+    let policy = secret_policy().report_only();
+    assert_eq!(policy.mode(), RedactionMode::Report);
+    let app = make_secret_app(policy);
+    let policy = app.runtime_config().redaction().unwrap();
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("report".to_string(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner.run_until_complete("report").await.unwrap();
+
+    let persisted = checkpointer.load_latest("report").await.unwrap().unwrap();
+    let stored = persisted.state.extra.snapshot();
+    assert_eq!(stored["user"]["ssn"], "123-45-6789");
+    assert_eq!(stored["api_key"], "sk-live-123");
+
+    let findings = policy.reported_findings();
+    assert!(findings.iter().any(|f| f.rule == "path:extra.user.ssn"));
+    assert!(findings.iter().any(|f| f.rule == "key:api_key"));
+    assert!(findings.iter().any(|f| f.rule == "pattern:email"));
+}
@@ -2,10 +2,13 @@ use rustc_hash::FxHashMap;
 use serde_json::Value;
 use std::sync::Arc;
 
-use weavegraph::channels::Channel;
+use weavegraph::channels::errors::{ErrorEvent, ErrorEventsExt, ErrorSeverity, WeaveError};
+use weavegraph::channels::{Blob, Channel};
 use weavegraph::message::{Message, Role};
 use weavegraph::node::NodePartial;
-use weavegraph::reducers::{AddMessages, MapMerge, Reducer, ReducerRegistry};
+use weavegraph::reducers::{
+    AddMessages, BoundedErrors, MapMerge, MergeBlobs, Reducer, ReducerRegistry,
+};
 use weavegraph::state::VersionedState;
 
 mod common;
@@ -31,6 +34,11 @@ fn channel_guard(channel: ChannelType, partial: &NodePartial) -> bool {
             .map(|m| !m.is_empty())
             .unwrap_or(false),
         ChannelType::Error => false,
+        ChannelType::Blob => partial
+            .blobs
+            .as_ref()
+            .map(|m| !m.is_empty())
+            .unwrap_or(false),
     }
 }
 
@@ -355,3 +363,91 @@ fn test_reducer_channel_isolation() {
         Some(&Value::String("isolated_value".into()))
     );
 }
+
+/********************
+ * BoundedErrors tests
+ ********************/
+
+#[test]
+fn test_bounded_errors_filters_and_keeps_newest_per_severity() {
+    let reducer = BoundedErrors::new()
+        .with_min_severity(ErrorSeverity::Warning)
+        .with_limit(ErrorSeverity::Warning, 2);
+    let mut state = base_state();
+    for step in 1..=4 {
+        let partial = NodePartial::new().with_errors(vec![
+            ErrorEvent::node("n", step, WeaveError::msg("info")).with_severity(ErrorSeverity::Info),
+            ErrorEvent::node("n", step, WeaveError::msg("warn"))
+                .with_severity(ErrorSeverity::Warning),
+            ErrorEvent::node("n", step, WeaveError::msg("fatal"))
+                .with_severity(ErrorSeverity::Fatal),
+        ]);
+        reducer.apply(&mut state, &partial);
+    }
+
+    let errors = state.errors.snapshot();
+    assert!(errors.iter().all(|e| e.severity != ErrorSeverity::Info));
+    let warning_steps: Vec<_> = errors
+        .at_least(ErrorSeverity::Warning)
+        .into_iter()
+        .filter(|e| e.severity == ErrorSeverity::Warning)
+        .filter_map(ErrorEvent::step)
+        .collect();
+    assert_eq!(warning_steps, vec![3, 4]);
+    assert_eq!(errors.fatal().len(), 4);
+}
+
+#[test]
+fn test_registry_replace_swaps_error_reducer() {
+    let mut registry = ReducerRegistry::default();
+    registry.replace(
+        ChannelType::Error,
+        Arc::new(BoundedErrors::new().with_limit(ErrorSeverity::Error, 1)),
+    );
+    let mut state = base_state();
+    let partial = NodePartial::new().with_errors(vec![
+        ErrorEvent::app(WeaveError::msg("first")),
+        ErrorEvent::app(WeaveError::msg("second")),
+    ]);
+    registry
+        .try_update(ChannelType::Error, &mut state, &partial)
+        .unwrap();
+
+    let errors = state.errors.snapshot();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error.message, "second");
+    assert_eq!(
+        registry.reducer_labels()["error"],
+        vec![std::any::type_name::<BoundedErrors>().to_string()]
+    );
+}
+
+#[test]
+fn test_merge_blobs_inserts_replaces_and_clears() {
+    let mut state = base_state();
+    let reducer = MergeBlobs;
+    reducer.apply(
+        &mut state,
+        &NodePartial::new()
+            .with_blob(
+                "image",
+                Blob::new(vec![1, 2, 3]).with_content_type("image/png"),
+            )
+            .with_blob("audio", Blob::new(vec![9; 16])),
+    );
+    assert_eq!(state.blobs.len(), 2);
+    assert_eq!(state.blobs.total_bytes(), 19);
+
+    reducer.apply(
+        &mut state,
+        &NodePartial::new()
+            .with_blob("image", Blob::new(vec![4, 5]))
+            .clear_blobs(["audio"]),
+    );
+    let blobs = state.blobs.snapshot();
+    assert_eq!(blobs.len(), 1);
+    assert_eq!(blobs["image"].data().as_ref(), &[4, 5]);
+    assert_eq!(blobs["image"].content_type(), None);
+    // Reducers never bump versions; the barrier does.
+    assert_eq!(state.blobs.version(), 1);
+}
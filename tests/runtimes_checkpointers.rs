@@ -150,10 +150,14 @@ async fn test_sqlite_checkpointer_roundtrip() {
         frontier: vec![NodeKind::End],
         versions_seen: versions_seen.clone(),
         concurrency_limit: 4,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec!["messages".to_string()],
+        node_metrics: vec![],
     };
 
     // Save (async trait method)
@@ -188,6 +192,53 @@ async fn test_sqlite_checkpointer_roundtrip() {
     assert_eq!(session_state.scheduler.concurrency_limit, 4);
 }
 
+#[test]
+fn test_restore_moves_legacy_bookkeeping_out_of_versions_seen() {
+    let mut checkpoint = Checkpoint::from_session(
+        "legacy",
+        &SessionState {
+            state: VersionedState::new_with_user_message("hi"),
+            step: 3,
+            frontier: vec![NodeKind::Custom("join".into())],
+            scheduler: Scheduler::new(1),
+            scheduler_state: SchedulerState::default(),
+        },
+    );
+    checkpoint.versions_seen = FxHashMap::from_iter([
+        (
+            "Custom:a".to_string(),
+            FxHashMap::from_iter([("messages".to_string(), 2_u64)]),
+        ),
+        (
+            "__weavegraph_join__:Custom:join".to_string(),
+            FxHashMap::from_iter([("Custom:a".to_string(), 2_u64)]),
+        ),
+        (
+            "__weavegraph_incoming__".to_string(),
+            FxHashMap::from_iter([("0:Custom:a".to_string(), 0_u64)]),
+        ),
+        (
+            "__weavegraph_runs__".to_string(),
+            FxHashMap::from_iter([("Custom:a".to_string(), 2_u64)]),
+        ),
+    ]);
+
+    let restored = restore_session_state(&checkpoint).scheduler_state;
+    assert_eq!(
+        restored.versions_seen.keys().collect::<Vec<_>>(),
+        vec!["Custom:a"]
+    );
+    assert_eq!(
+        restored.join_arrivals(&NodeKind::Custom("join".into())),
+        vec![NodeKind::Custom("a".into())]
+    );
+    assert_eq!(
+        restored.frontier_origins,
+        vec![Some("Custom:a".to_string())]
+    );
+    assert_eq!(restored.run_count(&NodeKind::Custom("a".into())), 2);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_sqlite_list_sessions() {
     let cp = SQLiteCheckpointer::connect("sqlite::memory:")
@@ -203,10 +254,14 @@ async fn test_sqlite_list_sessions() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: vec![],
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec![],
+            node_metrics: vec![],
         };
         cp.save(cp_struct).await.unwrap();
     }
@@ -238,10 +293,14 @@ async fn test_sqlite_step_execution_metadata() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 2,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![NodeKind::End],
         updated_channels: vec!["messages".to_string()],
+        node_metrics: vec![],
     };
 
     cp.save(checkpoint.clone()).await.expect("save checkpoint");
@@ -280,6 +339,9 @@ async fn test_sqlite_query_steps_pagination() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: if step % 2 == 0 {
                 vec![NodeKind::Start]
@@ -288,6 +350,7 @@ async fn test_sqlite_query_steps_pagination() {
             },
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec!["messages".to_string()],
+            node_metrics: vec![],
         };
         cp.save(checkpoint).await.expect("save checkpoint");
     }
@@ -312,6 +375,90 @@ async fn test_sqlite_query_steps_pagination() {
     assert_eq!(result.checkpoints[1].step, 4);
 }
 
+#[tokio::test]
+async fn test_inmemory_checkpointer_delete_and_stats() {
+    let cp_store = InMemoryCheckpointer::new();
+    let session = SessionState {
+        state: state_with_user("x"),
+        step: 2,
+        frontier: vec![NodeKind::End],
+        scheduler: Scheduler::new(1),
+        scheduler_state: SchedulerState::default(),
+    };
+    for id in ["alpha", "beta"] {
+        cp_store
+            .save(Checkpoint::from_session(id, &session))
+            .await
+            .unwrap();
+    }
+
+    let stats = cp_store.stats().await.unwrap();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].session_id, "alpha");
+    assert_eq!(stats[0].step_count, 1);
+    assert_eq!(stats[0].latest_step, 2);
+    assert!(stats[0].approx_bytes > 0);
+
+    assert_eq!(cp_store.delete_steps_before("alpha", 10).await.unwrap(), 0);
+    assert!(cp_store.load_latest("alpha").await.unwrap().is_some());
+
+    assert!(cp_store.delete_session("alpha").await.unwrap());
+    assert!(!cp_store.delete_session("alpha").await.unwrap());
+    assert!(cp_store.load_latest("alpha").await.unwrap().is_none());
+    assert_eq!(cp_store.list_sessions().await.unwrap(), vec!["beta"]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sqlite_delete_steps_and_sessions() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory");
+
+    for step in 1..=4 {
+        let checkpoint = Checkpoint {
+            session_id: "retention".into(),
+            step,
+            state: state_with_user(&format!("step {step}")),
+            frontier: vec![NodeKind::End],
+            versions_seen: FxHashMap::default(),
+            concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
+            created_at: Utc::now(),
+            ran_nodes: vec![NodeKind::Start],
+            skipped_nodes: vec![],
+            updated_channels: vec!["messages".to_string()],
+            node_metrics: vec![],
+        };
+        cp.save(checkpoint).await.expect("save checkpoint");
+    }
+
+    let stats = cp.stats().await.expect("stats");
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].step_count, 4);
+    assert_eq!(stats[0].latest_step, 4);
+    let bytes_before = stats[0].approx_bytes;
+    assert!(bytes_before > 0);
+
+    // Pruning never removes the latest checkpoint.
+    assert_eq!(cp.delete_steps_before("retention", 10).await.unwrap(), 3);
+    let remaining = cp
+        .query_steps("retention", StepQuery::default())
+        .await
+        .expect("query steps");
+    assert_eq!(remaining.page_info.total_count, 1);
+    assert_eq!(remaining.checkpoints[0].step, 4);
+    let stats = cp.stats().await.expect("stats");
+    assert!(stats[0].approx_bytes < bytes_before);
+
+    assert!(cp.delete_session("retention").await.unwrap());
+    assert!(!cp.delete_session("retention").await.unwrap());
+    assert!(cp.load_latest("retention").await.unwrap().is_none());
+    assert!(cp.list_sessions().await.unwrap().is_empty());
+    assert!(cp.stats().await.unwrap().is_empty());
+}
+
 // Concurrency behavior: ensure async RwLock in InMemoryCheckpointer allows many concurrent saves
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_inmemory_checkpointer_concurrent_operations() {
@@ -344,3 +491,126 @@ async fn test_inmemory_checkpointer_concurrent_operations() {
     assert_eq!(sessions[0], "session_0");
     assert_eq!(sessions.last().unwrap(), "session_99");
 }
+
+fn conversation(session_id: &str, step: u64, messages: &[(&str, &str)]) -> Checkpoint {
+    let mut builder = VersionedState::builder();
+    for (role, content) in messages {
+        builder = builder.with_message(role, content);
+    }
+    let session = SessionState {
+        state: builder.build(),
+        step,
+        frontier: vec![NodeKind::End],
+        scheduler: Scheduler::new(1),
+        scheduler_state: SchedulerState::default(),
+    };
+    Checkpoint::from_session(session_id, &session)
+}
+
+/// Save two conversations and check `search_messages` against them.
+async fn assert_message_search(cp: &dyn Checkpointer) {
+    cp.save(conversation(
+        "billing",
+        1,
+        &[("user", "Refund order 77 for cust-4821")],
+    ))
+    .await
+    .expect("save");
+    cp.save(conversation(
+        "billing",
+        2,
+        &[
+            ("user", "Refund order 77 for cust-4821"),
+            ("assistant", "Refund for CUST-4821 failed: ERR_TIMEOUT"),
+        ],
+    ))
+    .await
+    .expect("save");
+    cp.save(conversation(
+        "support",
+        1,
+        &[
+            ("user", "cust-4821 cannot log in"),
+            ("assistant", "Password reset sent"),
+        ],
+    ))
+    .await
+    .expect("save");
+
+    let hits = cp.search_messages(None, "cust-4821").await.expect("search");
+    let found: Vec<_> = hits
+        .iter()
+        .map(|hit| (hit.session_id.as_str(), hit.step, hit.index))
+        .collect();
+    assert_eq!(
+        found,
+        [("billing", 2, 0), ("billing", 2, 1), ("support", 1, 0)]
+    );
+    assert_eq!(hits[1].message.role, Role::Assistant);
+    assert_eq!(
+        hits[1].message.content,
+        "Refund for CUST-4821 failed: ERR_TIMEOUT"
+    );
+
+    let hits = cp
+        .search_messages(Some("billing"), "err_timeout 4821")
+        .await
+        .expect("search");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].index, 1);
+    assert!(
+        cp.search_messages(Some("support"), "refund")
+            .await
+            .expect("search")
+            .is_empty()
+    );
+    assert!(
+        cp.search_messages(None, "refu")
+            .await
+            .expect("search")
+            .is_empty(),
+        "words match whole"
+    );
+    assert!(
+        cp.search_messages(None, " - ")
+            .await
+            .expect("search")
+            .is_empty()
+    );
+
+    assert!(cp.delete_session("billing").await.expect("delete"));
+    let hits = cp.search_messages(None, "4821").await.expect("search");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].session_id, "support");
+}
+
+#[tokio::test]
+async fn test_inmemory_search_messages() {
+    assert_message_search(&InMemoryCheckpointer::new()).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sqlite_search_messages() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory")
+        .with_message_search();
+    assert!(cp.message_search());
+    assert_message_search(&cp).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sqlite_search_messages_requires_indexing() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory");
+    cp.save(conversation("quiet", 1, &[("user", "cust-4821")]))
+        .await
+        .expect("save");
+    assert!(
+        cp.search_messages(None, "cust-4821")
+            .await
+            .expect("search")
+            .is_empty()
+    );
+}
@@ -0,0 +1,75 @@
+use weavegraph::channels::Channel;
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::runtimes::DebugSession;
+use weavegraph::state::VersionedState;
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+fn two_step_app() -> weavegraph::app::App {
+    let emit = NodeKind::Custom("emit".into());
+    let reply = NodeKind::Custom("reply".into());
+    GraphBuilder::new()
+        .add_node(emit.clone(), EmitterNode)
+        .add_node(reply.clone(), SimpleMessageNode::new("all done"))
+        .add_edge(NodeKind::Start, emit.clone())
+        .add_edge(emit, reply.clone())
+        .add_edge(reply, NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_debug_session_steps_through_the_graph() {
+    let mut session = DebugSession::new(two_step_app(), state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_eq!(session.step_number(), 0);
+    assert_eq!(session.frontier(), &[NodeKind::Custom("emit".into())]);
+    assert!(session.last_report().is_none());
+    assert!(session.events_since_last_step().is_empty());
+
+    let report = session.step().await.unwrap();
+    assert_eq!(report.ran_nodes, vec![NodeKind::Custom("emit".into())]);
+    assert_eq!(session.step_number(), 1);
+    assert_eq!(session.frontier(), &[NodeKind::Custom("reply".into())]);
+    assert_eq!(session.state().messages.len(), 2);
+    let messages: Vec<_> = session
+        .events_since_last_step()
+        .iter()
+        .map(|event| event.message().to_string())
+        .collect();
+    assert!(messages.contains(&"First event".to_string()));
+    assert!(messages.contains(&"Third event".to_string()));
+
+    session.step().await.unwrap();
+    assert!(session.is_complete());
+    assert_eq!(
+        session.state().messages.snapshot().last().unwrap().content,
+        "all done"
+    );
+    assert!(
+        !session
+            .events_since_last_step()
+            .iter()
+            .any(|event| event.message() == "First event")
+    );
+
+    let report = session.step().await.unwrap();
+    assert!(report.completed);
+    assert!(report.ran_nodes.is_empty());
+    assert_eq!(session.step_number(), 2);
+}
+
+#[tokio::test]
+async fn test_debug_session_runs_to_end() {
+    let mut session =
+        DebugSession::new(two_step_app(), VersionedState::new_with_user_message("hi"))
+            .await
+            .unwrap();
+    assert_eq!(session.run_to_end().await.unwrap(), 2);
+    assert!(session.is_complete());
+    assert!(session.last_report().unwrap().completed);
+    assert_eq!(session.run_to_end().await.unwrap(), 0);
+}
@@ -5,7 +5,7 @@ use proptest::prelude::{Just, Strategy, any, prop};
 use proptest::prop_oneof;
 use rustc_hash::FxHashMap;
 use serde_json::Value;
-use weavegraph::channels::Channel;
+use weavegraph::channels::{Blob, Channel};
 use weavegraph::runtimes::checkpointer::Checkpoint;
 use weavegraph::runtimes::persistence::*;
 use weavegraph::state::VersionedState;
@@ -43,6 +43,49 @@ fn test_state_deserialize_without_errors_channel() {
     assert!(persisted.errors.items.is_empty());
 }
 
+#[test]
+fn test_blob_state_round_trip_and_payload_externalization() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let mut vs = state_with_user("hello");
+    vs.blobs.get_mut().insert(
+        "image".into(),
+        Blob::new(bytes.clone()).with_content_type("image/png"),
+    );
+    vs.blobs
+        .get_mut()
+        .insert("copy".into(), Blob::new(bytes.clone()));
+    vs.blobs.set_version(3);
+
+    // JSON carries the payload inline as base64.
+    let json = PersistedState::from(&vs).to_json_string().unwrap();
+    let value: Value = serde_json::from_str(&json).unwrap();
+    let image = &value["blobs"]["map"]["image"];
+    assert_eq!(image["len"], 256);
+    assert_eq!(image["content_type"], "image/png");
+    assert!(image["data"].as_str().unwrap().starts_with("AAECAwQF"));
+    let back = VersionedState::try_from(PersistedState::from_json_str(&json).unwrap()).unwrap();
+    assert_eq!(back.blobs, vs.blobs);
+
+    // Backends can move identical payloads out once and put them back.
+    let mut persisted = PersistedState::from(&vs);
+    let payloads = persisted.take_blob_payloads();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(
+        persisted.missing_blob_payloads(),
+        vec![Blob::new(bytes).digest()]
+    );
+    assert!(!persisted.to_json_string().unwrap().contains("AAECAwQF"));
+    assert!(VersionedState::try_from(persisted.clone()).is_err());
+    persisted.restore_blob_payloads(&payloads);
+    assert_eq!(VersionedState::try_from(persisted).unwrap().blobs, vs.blobs);
+
+    // States without blobs keep their previous shape.
+    let plain = PersistedState::from(&state_with_user("hi"))
+        .to_json_string()
+        .unwrap();
+    assert!(!plain.contains("blobs"));
+}
+
 #[test]
 fn test_checkpoint_round_trip() {
     // Build synthetic checkpoint
@@ -67,6 +110,9 @@ fn test_checkpoint_round_trip() {
             ),
         ]),
         concurrency_limit: 4,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: chrono::Utc::now(),
         ran_nodes: vec![
             weavegraph::types::NodeKind::Start,
@@ -74,6 +120,7 @@ fn test_checkpoint_round_trip() {
         ],
         skipped_nodes: vec![weavegraph::types::NodeKind::End],
         updated_channels: vec!["messages".to_string(), "extra".to_string()],
+        node_metrics: vec![],
     };
     let persisted = PersistedCheckpoint::from(&cp);
     let json = persisted.to_json_string().unwrap();
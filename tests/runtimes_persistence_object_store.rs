@@ -0,0 +1,145 @@
+//! Object storage checkpointer tests against `object_store::memory::InMemory`.
+//!
+//! ```bash
+//! cargo test --features object-store-parquet --test runtimes_persistence_object_store
+//! ```
+
+#![cfg(feature = "object-store")]
+
+use std::sync::Arc;
+
+use object_store::ObjectStoreExt;
+use object_store::memory::InMemory;
+use weavegraph::channels::Channel;
+use weavegraph::runtimes::checkpointer::{ChannelArchive, Checkpoint, Checkpointer};
+use weavegraph::runtimes::{ObjectStoreCheckpointer, SessionState};
+use weavegraph::schedulers::{Scheduler, SchedulerState};
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+fn checkpoint(session_id: &str, step: u64, message: &str) -> Checkpoint {
+    let session = SessionState {
+        state: state_with_user(message),
+        step,
+        frontier: vec![NodeKind::Custom("next".into())],
+        scheduler: Scheduler::new(2),
+        scheduler_state: SchedulerState::default(),
+    };
+    Checkpoint::from_session(session_id, &session)
+}
+
+fn checkpointer() -> ObjectStoreCheckpointer {
+    ObjectStoreCheckpointer::new(Arc::new(InMemory::new()), "wg/test")
+}
+
+#[tokio::test]
+async fn test_object_store_writes_one_object_per_step_and_loads_latest() {
+    let store = checkpointer();
+    for step in [1, 2, 10] {
+        store
+            .save(checkpoint("sess", step, &format!("step {step}")))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(store.list_steps("sess").await.unwrap(), vec![1, 2, 10]);
+    assert_eq!(
+        store.checkpoint_path("sess", 2).as_ref(),
+        "wg/test/sessions/sess/00000000000000000002.json"
+    );
+
+    let latest = store.load_latest("sess").await.unwrap().unwrap();
+    assert_eq!(latest.step, 10);
+    assert_eq!(latest.frontier, vec![NodeKind::Custom("next".into())]);
+    assert_eq!(latest.state.messages.snapshot()[0].content, "step 10");
+
+    let step_two = store.load_step("sess", 2).await.unwrap().unwrap();
+    assert_eq!(step_two.state.messages.snapshot()[0].content, "step 2");
+    assert!(store.load_step("sess", 3).await.unwrap().is_none());
+    assert!(store.load_latest("missing").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_object_store_lists_prunes_and_deletes_sessions() {
+    let store = checkpointer();
+    for step in 1..=3 {
+        store.save(checkpoint("b/slash", step, "b")).await.unwrap();
+    }
+    store.save(checkpoint("a", 1, "a")).await.unwrap();
+
+    assert_eq!(store.list_sessions().await.unwrap(), vec!["a", "b/slash"]);
+
+    let stats = store.stats().await.unwrap();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[1].session_id, "b/slash");
+    assert_eq!(stats[1].step_count, 3);
+    assert_eq!(stats[1].latest_step, 3);
+    assert!(stats[1].approx_bytes > 0);
+
+    assert_eq!(store.delete_steps_before("b/slash", 10).await.unwrap(), 2);
+    assert_eq!(store.list_steps("b/slash").await.unwrap(), vec![3]);
+
+    assert!(store.delete_session("b/slash").await.unwrap());
+    assert!(!store.delete_session("b/slash").await.unwrap());
+    assert_eq!(store.list_sessions().await.unwrap(), vec!["a"]);
+}
+
+#[tokio::test]
+async fn test_object_store_archives_are_separate_from_steps() {
+    let store = checkpointer();
+    store.save(checkpoint("sess", 1, "live")).await.unwrap();
+    let mut archive = ChannelArchive::new("sess", 0, 1);
+    archive.messages = state_with_user("old").messages.snapshot();
+    store.save_archive(archive).await.unwrap();
+
+    assert_eq!(
+        store.archive_path("sess", 0).as_ref(),
+        "wg/test/archives/sess/00000000000000000000.json"
+    );
+    assert_eq!(store.list_steps("sess").await.unwrap(), vec![1]);
+    assert_eq!(store.list_sessions().await.unwrap(), vec!["sess"]);
+    let archives = store.load_archives("sess").await.unwrap();
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0].messages[0].content, "old");
+
+    assert!(store.delete_session("sess").await.unwrap());
+    assert!(store.load_archives("sess").await.unwrap().is_empty());
+}
+
+#[cfg(feature = "object-store-parquet")]
+#[tokio::test]
+async fn test_object_store_exports_session_history_to_parquet() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let store = checkpointer();
+    for step in 1..=3 {
+        store.save(checkpoint("sess", step, "hi")).await.unwrap();
+    }
+
+    let location = store.export_parquet("sess").await.unwrap();
+    assert_eq!(location.as_ref(), "wg/test/exports/sess.parquet");
+
+    let bytes = store
+        .store()
+        .get(&location)
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+    let reader = SerializedFileReader::new(bytes).unwrap();
+    let metadata = reader.metadata().file_metadata();
+    assert_eq!(metadata.num_rows(), 3);
+    let columns: Vec<&str> = metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.name())
+        .collect();
+    assert_eq!(columns[..3], ["session_id", "step", "created_at"]);
+    assert!(columns.contains(&"state_json"));
+
+    assert!(store.export_parquet("missing").await.is_err());
+}
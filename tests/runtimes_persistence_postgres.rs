@@ -76,10 +76,14 @@ async fn test_postgres_checkpointer_roundtrip() {
         frontier: vec![NodeKind::End],
         versions_seen: versions_seen.clone(),
         concurrency_limit: 4,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec!["messages".to_string()],
+        node_metrics: vec![],
     };
 
     cp.save(cp_struct.clone()).await.expect("save");
@@ -124,10 +128,14 @@ async fn test_list_sessions_and_empty_load() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: vec![],
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec![],
+            node_metrics: vec![],
         };
         cp.save(cp_struct).await.unwrap();
     }
@@ -162,6 +170,9 @@ async fn test_step_execution_metadata_query_and_pagination() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: if step % 2 == 0 {
                 vec![NodeKind::Start]
@@ -170,6 +181,7 @@ async fn test_step_execution_metadata_query_and_pagination() {
             },
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec!["messages".to_string()],
+            node_metrics: vec![],
         };
         cp.save(checkpoint).await.expect("save checkpoint");
     }
@@ -213,10 +225,14 @@ async fn test_error_persistence_roundtrip() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec!["errors".into()],
+        node_metrics: vec![],
     };
     cp.save(checkpoint).await.unwrap();
     let loaded = cp.load_latest(&session_id).await.unwrap().unwrap();
@@ -239,10 +255,14 @@ async fn test_idempotent_save() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
 
     // Save the same checkpoint twice - should not fail (upsert behavior)
@@ -268,10 +288,14 @@ async fn test_concurrency_check() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
     cp.save(checkpoint1).await.expect("save step 1");
 
@@ -283,10 +307,14 @@ async fn test_concurrency_check() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
     cp.save_with_concurrency_check(checkpoint2.clone(), Some(1))
         .await
@@ -300,10 +328,14 @@ async fn test_concurrency_check() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
     let result = cp.save_with_concurrency_check(checkpoint3, Some(1)).await;
     assert!(result.is_err(), "should fail with wrong expected step");
@@ -329,10 +361,14 @@ async fn test_out_of_order_writes_do_not_regress_latest() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
 
     cp.save(checkpoint5).await.expect("save step 5");
@@ -351,10 +387,14 @@ async fn test_out_of_order_writes_do_not_regress_latest() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
 
     cp.save(checkpoint2)
@@ -385,10 +425,14 @@ async fn test_concurrent_writers_only_one_wins_concurrency_check() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec![],
+        node_metrics: vec![],
     };
     cp.save(checkpoint1).await.expect("save step 1");
 
@@ -406,10 +450,14 @@ async fn test_concurrent_writers_only_one_wins_concurrency_check() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: vec![],
             skipped_nodes: vec![],
             updated_channels: vec![],
+            node_metrics: vec![],
         }
     };
 
@@ -459,3 +507,62 @@ async fn test_concurrent_writers_only_one_wins_concurrency_check() {
         "latest marker should match one of the winning writers"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_postgres_step_history_conformance() {
+    let cp = connect_or_fail().await;
+    let prefix = unique_session_id("conformance");
+    common::step_history::assert_step_history_conformance(&cp, &prefix).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_search_messages_scoped_to_session() {
+    let cp = connect_or_fail().await.with_message_search();
+    let session_id = unique_session_id("search");
+    let mut state = state_with_user("Refund order 77 for cust-4821");
+    state
+        .messages
+        .get_mut()
+        .push(weavegraph::message::Message::with_role(
+            Role::Assistant,
+            "Refund for CUST-4821 failed: ERR_TIMEOUT",
+        ));
+    let checkpoint = Checkpoint {
+        session_id: session_id.clone(),
+        step: 1,
+        state,
+        frontier: vec![NodeKind::End],
+        versions_seen: FxHashMap::default(),
+        concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
+        created_at: Utc::now(),
+        ran_nodes: vec![],
+        skipped_nodes: vec![],
+        updated_channels: vec![],
+        node_metrics: vec![],
+    };
+    cp.save(checkpoint).await.expect("save");
+
+    let hits = cp
+        .search_messages(Some(&session_id), "cust-4821")
+        .await
+        .expect("search");
+    assert_eq!(hits.len(), 2);
+    let hits = cp
+        .search_messages(Some(&session_id), "err_timeout")
+        .await
+        .expect("search");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].index, 1);
+    assert_eq!(hits[0].message.role, Role::Assistant);
+
+    cp.delete_session(&session_id).await.expect("delete");
+    assert!(
+        cp.search_messages(Some(&session_id), "4821")
+            .await
+            .expect("search")
+            .is_empty()
+    );
+}
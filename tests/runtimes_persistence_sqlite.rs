@@ -1,10 +1,16 @@
 #[cfg(feature = "sqlite")]
 use chrono::Utc;
 use rustc_hash::FxHashMap;
-use weavegraph::channels::Channel;
+use std::time::Duration;
 use weavegraph::channels::errors::{ErrorEvent, WeaveError};
-use weavegraph::message::Role;
-use weavegraph::runtimes::{Checkpoint, Checkpointer, SQLiteCheckpointer, StepQuery};
+use weavegraph::channels::{Blob, Channel};
+use weavegraph::llm::{LlmAuditRecord, LlmCall, LlmResponse};
+use weavegraph::message::{Message, Role};
+use weavegraph::node::{NodeMetrics, TokenUsage};
+use weavegraph::runtimes::{
+    ChannelArchive, Checkpoint, Checkpointer, NodeInputCapture, SQLiteCheckpointer, StepQuery,
+};
+use weavegraph::state::VersionedState;
 use weavegraph::types::NodeKind;
 
 mod common;
@@ -34,10 +40,17 @@ async fn test_sqlite_checkpointer_roundtrip() {
         frontier: vec![NodeKind::End],
         versions_seen: versions_seen.clone(),
         concurrency_limit: 4,
+        pending_joins: FxHashMap::from_iter([(
+            "Custom:join".into(),
+            FxHashMap::from_iter([("Custom:a".into(), 1_u64)]),
+        )]),
+        frontier_origins: vec![Some("Start".into()), None],
+        run_counts: FxHashMap::from_iter([("Custom:critic".into(), 2_u64)]),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec!["messages".to_string()],
+        node_metrics: vec![],
     };
 
     cp.save(cp_struct.clone()).await.expect("save");
@@ -57,6 +70,10 @@ async fn test_sqlite_checkpointer_roundtrip() {
             .copied(),
         Some(1)
     );
+    assert_eq!(loaded.pending_joins, cp_struct.pending_joins);
+    assert_eq!(loaded.frontier_origins, cp_struct.frontier_origins);
+    assert_eq!(loaded.run_counts, cp_struct.run_counts);
+    assert_eq!(loaded.versions_seen.len(), 1);
     assert_eq!(loaded.state.messages.snapshot()[0].role, Role::User);
     assert_extra_has(&loaded.state, "k");
     assert_eq!(
@@ -80,10 +97,14 @@ async fn test_list_sessions_and_empty_load() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: vec![],
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec![],
+            node_metrics: vec![],
         };
         cp.save(cp_struct).await.unwrap();
     }
@@ -113,6 +134,9 @@ async fn test_step_execution_metadata_query_and_pagination() {
             frontier: vec![NodeKind::End],
             versions_seen: FxHashMap::default(),
             concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
             created_at: Utc::now(),
             ran_nodes: if step % 2 == 0 {
                 vec![NodeKind::Start]
@@ -121,6 +145,7 @@ async fn test_step_execution_metadata_query_and_pagination() {
             },
             skipped_nodes: vec![NodeKind::End],
             updated_channels: vec!["messages".to_string()],
+            node_metrics: vec![],
         };
         cp.save(checkpoint).await.expect("save checkpoint");
     }
@@ -164,10 +189,14 @@ async fn test_error_persistence_roundtrip() {
         frontier: vec![NodeKind::End],
         versions_seen: FxHashMap::default(),
         concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
         created_at: Utc::now(),
         ran_nodes: vec![NodeKind::Start],
         skipped_nodes: vec![],
         updated_channels: vec!["errors".into()],
+        node_metrics: vec![],
     };
     cp.save(checkpoint).await.unwrap();
     let loaded = cp.load_latest("err_sess").await.unwrap().unwrap();
@@ -175,3 +204,223 @@ async fn test_error_persistence_roundtrip() {
     assert_eq!(errors.len(), 1);
     assert_eq!(errors[0].error.message, "boom");
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_node_metrics_roundtrip() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory");
+    let node = NodeKind::Custom("llm".into());
+    let metrics =
+        NodeMetrics::new(Duration::from_millis(12), 3).with_token_usage(TokenUsage::new(40, 8));
+    let checkpoint = Checkpoint {
+        session_id: "metrics".into(),
+        step: 1,
+        state: state_with_user("hello"),
+        frontier: vec![NodeKind::End],
+        versions_seen: FxHashMap::default(),
+        concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
+        created_at: Utc::now(),
+        ran_nodes: vec![node.clone()],
+        skipped_nodes: vec![],
+        updated_channels: vec![],
+        node_metrics: vec![(node.clone(), metrics)],
+    };
+    cp.save(checkpoint).await.expect("save");
+
+    let result = cp
+        .query_steps("metrics", StepQuery::default())
+        .await
+        .expect("query steps");
+    assert_eq!(result.checkpoints[0].node_metrics, vec![(node, metrics)]);
+    assert_eq!(
+        result.checkpoints[0].node_metrics[0].1.duration(),
+        Duration::from_millis(12)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_channel_archives_roundtrip_and_delete_with_session() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect");
+    let state = state_with_user("archived");
+    let checkpoint = Checkpoint {
+        session_id: "arch".into(),
+        step: 4,
+        state: state.clone(),
+        frontier: vec![],
+        versions_seen: FxHashMap::default(),
+        concurrency_limit: 1,
+        pending_joins: FxHashMap::default(),
+        frontier_origins: Vec::new(),
+        run_counts: FxHashMap::default(),
+        created_at: Utc::now(),
+        ran_nodes: vec![],
+        skipped_nodes: vec![],
+        updated_channels: vec![],
+        node_metrics: vec![],
+    };
+    cp.save(checkpoint).await.expect("save");
+
+    for sequence in [1, 0] {
+        let mut archive = ChannelArchive::new("arch", sequence, 4);
+        archive.messages_offset = sequence * 10;
+        archive.messages = state.messages.snapshot();
+        archive.errors = vec![ErrorEvent::app(WeaveError::msg("old"))];
+        cp.save_archive(archive).await.expect("save archive");
+    }
+
+    let archives = cp.load_archives("arch").await.expect("load archives");
+    assert_eq!(archives.len(), 2);
+    assert_eq!(archives[0].sequence, 0);
+    assert_eq!(archives[1].messages_offset, 10);
+    assert_eq!(archives[1].messages[0].content, "archived");
+    assert_eq!(archives[1].errors[0].error.message, "old");
+
+    assert!(cp.delete_session("arch").await.expect("delete"));
+    assert!(cp.load_archives("arch").await.expect("reload").is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_node_inputs_roundtrip_and_delete_with_session() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect");
+    let input = state_with_user("seen").snapshot();
+    for step in [2, 1, 2] {
+        let capture = NodeInputCapture::new(
+            "inputs",
+            step,
+            vec![NodeKind::Custom(format!("n{step}"))],
+            input.clone(),
+        );
+        cp.save_node_inputs(capture).await.expect("save inputs");
+    }
+
+    let captures = cp.load_node_inputs("inputs").await.expect("load inputs");
+    assert_eq!(captures.len(), 2);
+    assert_eq!(captures[0].step, 1);
+    assert_eq!(captures[1].nodes, vec![NodeKind::Custom("n2".into())]);
+    assert_eq!(captures[1].input.messages[0].content, "seen");
+
+    assert!(cp.delete_session("inputs").await.expect("delete"));
+    assert!(
+        cp.load_node_inputs("inputs")
+            .await
+            .expect("reload")
+            .is_empty()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_llm_audit_roundtrip_and_delete_with_session() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect");
+    let call = |reply: &str| {
+        LlmCall::new(vec![Message::user("question")]).with_response(&LlmResponse {
+            content: reply.into(),
+            ..Default::default()
+        })
+    };
+    for (step, attempt, index, reply) in [
+        (2, 1, 0, "b"),
+        (1, 2, 0, "a2"),
+        (1, 1, 0, "a1"),
+        (2, 1, 0, "b'"),
+    ] {
+        let record =
+            LlmAuditRecord::new("audit", step, attempt, "ask", call(reply)).with_index(index);
+        cp.save_llm_audit(record).await.expect("save audit");
+    }
+
+    let records = cp.load_llm_audit("audit").await.expect("load audit");
+    let replies: Vec<_> = records
+        .iter()
+        .map(|r| (r.step, r.attempt, r.call.response.clone().unwrap()))
+        .collect();
+    assert_eq!(
+        replies,
+        vec![
+            (1, 1, "a1".into()),
+            (1, 2, "a2".into()),
+            (2, 1, "b'".into())
+        ]
+    );
+    assert_eq!(records[0].call.prompt[0].content, "question");
+
+    assert!(cp.delete_session("audit").await.expect("delete"));
+    assert!(cp.load_llm_audit("audit").await.expect("reload").is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sqlite_step_history_conformance() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory");
+    common::step_history::assert_step_history_conformance(&cp, "sqlite").await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_blob_payloads_stored_once_restored_and_pruned() {
+    let cp = SQLiteCheckpointer::connect("sqlite::memory:")
+        .await
+        .expect("connect sqlite memory");
+    let checkpoint_at = |step: u64, fill: u8| {
+        let state = VersionedState::builder()
+            .with_user_message("describe")
+            .with_blob(
+                "frame",
+                Blob::new(vec![fill; 8192]).with_content_type("image/raw"),
+            )
+            .build();
+        Checkpoint {
+            session_id: "blobs".into(),
+            step,
+            state,
+            frontier: vec![NodeKind::End],
+            versions_seen: FxHashMap::default(),
+            concurrency_limit: 1,
+            pending_joins: FxHashMap::default(),
+            frontier_origins: Vec::new(),
+            run_counts: FxHashMap::default(),
+            created_at: Utc::now(),
+            ran_nodes: vec![],
+            skipped_nodes: vec![],
+            updated_channels: vec![],
+            node_metrics: vec![],
+        }
+    };
+    let approx_bytes = |stats: Vec<weavegraph::runtimes::SessionStats>| stats[0].approx_bytes;
+
+    cp.save(checkpoint_at(1, 1)).await.unwrap();
+    cp.save(checkpoint_at(2, 1)).await.unwrap();
+    // Two steps share one raw payload instead of two base64 copies.
+    let bytes = approx_bytes(cp.stats().await.unwrap());
+    assert!((8192..2 * 8192).contains(&bytes), "approx_bytes = {bytes}");
+
+    let latest = cp.load_latest("blobs").await.unwrap().unwrap();
+    let frame = &latest.state.blobs.snapshot()["frame"];
+    assert_eq!(frame.data().as_ref(), &[1; 8192][..]);
+    assert_eq!(frame.content_type(), Some("image/raw"));
+    let history = cp.query_steps("blobs", StepQuery::default()).await.unwrap();
+    assert!(
+        history
+            .checkpoints
+            .iter()
+            .all(|c| c.state.blobs == latest.state.blobs)
+    );
+
+    cp.save(checkpoint_at(3, 2)).await.unwrap();
+    assert!(approx_bytes(cp.stats().await.unwrap()) >= 2 * 8192);
+    // Pruning the steps that referenced the first payload drops it.
+    assert_eq!(cp.delete_steps_before("blobs", 3).await.unwrap(), 2);
+    let bytes = approx_bytes(cp.stats().await.unwrap());
+    assert!((8192..2 * 8192).contains(&bytes), "approx_bytes = {bytes}");
+    let latest = cp.load_latest("blobs").await.unwrap().unwrap();
+    assert_eq!(latest.state.blobs.snapshot()["frame"].data()[0], 2);
+}
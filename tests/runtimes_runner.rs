@@ -7,21 +7,32 @@ use std::time::Duration;
 use async_trait::async_trait;
 use serde_json::json;
 use weavegraph::channels::Channel;
+use weavegraph::channels::errors::{ErrorEvent, ErrorScope, ErrorSeverity, WeaveError};
 use weavegraph::event_bus::{
-    EventBus, EventStream, INVOCATION_END_SCOPE, MemorySink, STREAM_END_SCOPE,
+    AggregatingSink, AttemptRetraction, Event, EventBus, EventRollup, EventStream,
+    INVOCATION_END_SCOPE, MemorySink, RUN_SUMMARY_SCOPE, STEP_METRICS_SCOPE, STREAM_END_SCOPE,
 };
-use weavegraph::graphs::{EdgePredicate, GraphBuilder};
+use weavegraph::feature_flags::{FeatureFlagError, FeatureFlagProvider, FeatureFlags, StaticFlags};
+use weavegraph::graphs::{
+    DerivedChannel, EdgePredicate, GraphBuilder, GraphCompileError, NodeQuotas, QuotaFallback,
+    SlaBreach, SlaMetric, SlaPolicy, SnapshotView, StateScope,
+};
+use weavegraph::llm::{LlmError, LlmProvider, LlmResponse};
 use weavegraph::message::{Message, Role};
-use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial, TokenUsage};
+use weavegraph::runtimes::runner::RunnerError;
 use weavegraph::runtimes::{
-    AppRunner, Checkpoint, Checkpointer, CheckpointerType, PausedReason, RuntimeConfig,
-    SessionInit, SessionState, StepOptions, StepResult,
+    AppRunner, COMPACTION_MARKER_KEY, Checkpoint, CheckpointFailurePolicy, Checkpointer,
+    CheckpointerError, CheckpointerType, CompactionMarker, CompactionPolicy, InMemoryCheckpointer,
+    PausedReason, RunSummary, RuntimeConfig, STATE_SCHEMA_VERSION_KEY, SessionId, SessionInit,
+    SessionState, StateLimit, StateLimitBreach, StateLimitPolicy, StateLimits, StateMigrationError,
+    StateMigrations, StepOptions, StepResult, SuperstepHookOptions, SuperstepUpdate,
 };
-use weavegraph::schedulers::{Scheduler, SchedulerState};
+use weavegraph::schedulers::{AdaptiveConcurrency, Scheduler, SchedulerState};
 use weavegraph::state::{StateSnapshot, VersionedState};
-use weavegraph::types::NodeKind;
+use weavegraph::types::{ChannelType, NodeKind};
 use weavegraph::utils::clock::MockClock;
-use weavegraph::{FrontierCommand, NodeRoute};
+use weavegraph::{FrontierCommand, NodeCommand, NodeRoute};
 
 mod common;
 use common::*;
@@ -431,6 +442,141 @@ async fn test_run_until_complete() {
     assert_message_contains(&final_state, "ran:test:step:1");
 }
 
+#[tokio::test]
+async fn test_watch_steps_tracks_latest_report() {
+    let app = make_test_app();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    assert!(runner.watch_steps("missing").is_err());
+
+    runner
+        .create_session("watched".into(), state_with_user("hello"))
+        .await
+        .unwrap();
+    let mut steps = runner.watch_steps("watched").unwrap();
+    {
+        let initial = steps.borrow_and_update();
+        assert_eq!(initial.step, 0);
+        assert!(initial.ran_nodes.is_empty());
+        assert_eq!(initial.next_frontier, vec![NodeKind::Custom("test".into())]);
+        assert!(!initial.completed);
+    }
+
+    runner.run_until_complete("watched").await.unwrap();
+
+    assert!(steps.has_changed().unwrap());
+    let latest = steps.borrow_and_update();
+    assert_eq!(latest.step, 1);
+    assert_eq!(latest.ran_nodes, vec![NodeKind::Custom("test".into())]);
+    assert!(latest.completed);
+
+    let late = runner.watch_steps("watched").unwrap();
+    assert_eq!(late.borrow().step, 1);
+}
+
+#[tokio::test]
+async fn test_create_session_with_named_entry() {
+    let chat = NodeKind::Custom("chat".into());
+    let ingest = NodeKind::Custom("ingest".into());
+    let app = GraphBuilder::new()
+        .add_node(chat.clone(), TestNode { name: "chat" })
+        .add_node(ingest.clone(), TestNode { name: "ingest" })
+        .add_edge(NodeKind::Start, chat.clone())
+        .add_edge(chat, NodeKind::End)
+        .add_entry("ingest", [ingest.clone()])
+        .add_edge(ingest, NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app.clone())
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+
+    let err = runner
+        .create_session_with_entry("s".into(), state_with_user("hi"), "missing")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RunnerError::UnknownEntryPoint { name } if name == "missing"));
+    assert!(runner.get_session("s").is_none());
+
+    runner
+        .create_session_with_entry("s".into(), state_with_user("hi"), "ingest")
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("s").await.unwrap();
+    assert_message_contains(&final_state, "ran:ingest:step:1");
+    assert!(
+        !final_state
+            .messages
+            .snapshot()
+            .iter()
+            .any(|m| m.content.contains("ran:chat"))
+    );
+
+    let via_app = app
+        .invoke_entry("ingest", state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_message_contains(&via_app, "ran:ingest:step:1");
+}
+
+#[tokio::test]
+async fn test_create_session_with_frontier_override() {
+    let chat = NodeKind::Custom("chat".into());
+    let review = NodeKind::Custom("review".into());
+    let app = GraphBuilder::new()
+        .add_node(chat.clone(), TestNode { name: "chat" })
+        .add_node(review.clone(), TestNode { name: "review" })
+        .add_edge(NodeKind::Start, chat.clone())
+        .add_edge(chat, review.clone())
+        .add_edge(review.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app.clone())
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+
+    let err = runner
+        .create_session_with_frontier("s".into(), state_with_user("hi"), Vec::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RunnerError::NoStartNodes));
+    for bad in [NodeKind::End, NodeKind::Custom("missing".into())] {
+        let err = runner
+            .create_session_with_frontier("s".into(), state_with_user("hi"), vec![bad.clone()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::InvalidFrontierOverride { node } if node == bad));
+    }
+    assert!(runner.get_session("s").is_none());
+
+    runner
+        .create_session_with_frontier("s".into(), state_with_user("hi"), vec![review.clone()])
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("s").await.unwrap();
+    assert_message_contains(&final_state, "ran:review:step:1");
+    assert!(
+        !final_state
+            .messages
+            .snapshot()
+            .iter()
+            .any(|m| m.content.contains("ran:chat"))
+    );
+
+    let via_app = app
+        .invoke_with_frontier(vec![review], state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_message_contains(&via_app, "ran:review:step:1");
+}
+
 #[tokio::test]
 async fn test_iterative_invocation_processes_identical_inputs() {
     let app = make_iterative_app();
@@ -1018,6 +1164,376 @@ async fn test_interrupt_before() {
     }
 }
 
+#[tokio::test]
+async fn test_update_session_state_while_paused() {
+    let probe = Arc::new(ProbeCheckpointer::default());
+    let mut runner = AppRunner::builder()
+        .app(make_test_app())
+        .checkpointer_custom(probe.clone())
+        .build()
+        .await;
+    runner
+        .create_session("edit".into(), state_with_user("hello"))
+        .await
+        .unwrap();
+    let options = StepOptions {
+        interrupt_before: vec![NodeKind::Custom("test".into())],
+        ..Default::default()
+    };
+    let paused = runner.run_step("edit", options).await.unwrap();
+    assert!(matches!(paused, StepResult::Paused(_)));
+
+    let unchanged = runner.update_session_state("edit", |_| {}).await.unwrap();
+    assert!(unchanged.is_empty());
+    let saves_before = probe.save_calls();
+
+    let extra_version = runner.get_session("edit").unwrap().state.extra.version();
+    let changed = runner
+        .update_session_state("edit", |state| {
+            state.extra.get_mut().insert("approved".into(), json!(true));
+        })
+        .await
+        .unwrap();
+    assert_eq!(changed, vec!["extra"]);
+    assert_eq!(probe.save_calls(), saves_before + 1);
+
+    let session = runner.get_session("edit").unwrap();
+    assert_eq!(session.state.extra.version(), extra_version + 1);
+    let audit = session.state.errors.snapshot();
+    assert_eq!(audit.len(), 1);
+    assert!(audit[0].tags.iter().any(|t| t == "manual_edit"));
+    assert_eq!(audit[0].context["updated_channels"], json!(["extra"]));
+
+    let persisted = probe.load_latest("edit").await.unwrap().unwrap();
+    assert_eq!(persisted.state.extra.snapshot()["approved"], json!(true));
+
+    let final_state = runner.run_until_complete("edit").await.unwrap();
+    assert_eq!(final_state.extra.snapshot()["approved"], json!(true));
+    assert!(
+        runner
+            .update_session_state("missing", |_| {})
+            .await
+            .is_err()
+    );
+}
+
+struct PanicOnceNode {
+    panicked: AtomicUsize,
+}
+
+#[async_trait]
+impl Node for PanicOnceNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        if self.panicked.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("first attempt fails");
+        }
+        Ok(
+            NodePartial::new()
+                .with_messages(vec![Message::with_role(Role::Assistant, "recovered")]),
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_node_panic_recorded_and_session_resumable() {
+    let flaky = NodeKind::Custom("flaky".into());
+    let app = GraphBuilder::new()
+        .add_node(
+            flaky.clone(),
+            PanicOnceNode {
+                panicked: AtomicUsize::new(0),
+            },
+        )
+        .add_edge(NodeKind::Start, flaky.clone())
+        .add_edge(flaky, NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("panic".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+
+    let err = runner
+        .run_step("panic", StepOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RunnerError::Scheduler(weavegraph::schedulers::SchedulerError::NodePanic { .. })
+    ));
+    let errors = runner.get_session("panic").unwrap().state.errors.snapshot();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].tags.iter().any(|t| t == "panic"));
+    assert!(errors[0].context["backtrace"].is_string());
+
+    let result = runner.run_step("panic", StepOptions::default()).await;
+    assert!(matches!(result, Ok(StepResult::Completed(_))));
+    assert_message_contains(&runner.get_session("panic").unwrap().state, "recovered");
+}
+
+struct LifecycleNode {
+    registered: Arc<AtomicUsize>,
+    shut_down: Arc<AtomicUsize>,
+    fail_first: bool,
+}
+
+#[async_trait]
+impl Node for LifecycleNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        Ok(NodePartial::new())
+    }
+
+    async fn on_register(&self, ctx: NodeContext) -> Result<(), NodeError> {
+        assert_eq!(ctx.step, 0);
+        if self.registered.fetch_add(1, Ordering::SeqCst) == 0 && self.fail_first {
+            return Err(NodeError::ValidationFailed("pool unavailable".into()));
+        }
+        Ok(())
+    }
+
+    async fn on_shutdown(&self) {
+        self.shut_down.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_node_lifecycle_hooks_run_once_per_app() {
+    let registered = Arc::new(AtomicUsize::new(0));
+    let shut_down = Arc::new(AtomicUsize::new(0));
+    let kind = NodeKind::Custom("pooled".into());
+    let app = GraphBuilder::new()
+        .add_node(
+            kind.clone(),
+            LifecycleNode {
+                registered: Arc::clone(&registered),
+                shut_down: Arc::clone(&shut_down),
+                fail_first: true,
+            },
+        )
+        .add_edge(NodeKind::Start, kind.clone())
+        .add_edge(kind.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+
+    let err = runner
+        .create_session("a".into(), state_with_user("hi"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RunnerError::NodeLifecycle { kind: k, .. } if k == kind));
+
+    runner
+        .create_session("a".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner
+        .create_session("b".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_eq!(registered.load(Ordering::SeqCst), 2);
+
+    runner.shutdown().await;
+    runner.shutdown().await;
+    assert_eq!(shut_down.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_failed_registration_shuts_down_registered_nodes() {
+    let counters = || (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (a_registered, a_shut_down) = counters();
+    let (b_registered, b_shut_down) = counters();
+    let a = NodeKind::Custom("a".into());
+    let b = NodeKind::Custom("b".into());
+    let app = GraphBuilder::new()
+        .add_node(
+            a.clone(),
+            LifecycleNode {
+                registered: Arc::clone(&a_registered),
+                shut_down: Arc::clone(&a_shut_down),
+                fail_first: false,
+            },
+        )
+        .add_node(
+            b.clone(),
+            LifecycleNode {
+                registered: Arc::clone(&b_registered),
+                shut_down: Arc::clone(&b_shut_down),
+                fail_first: true,
+            },
+        )
+        .add_edge(NodeKind::Start, a.clone())
+        .add_edge(a, b.clone())
+        .add_edge(b.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+
+    let err = runner
+        .create_session("s".into(), state_with_user("hi"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RunnerError::NodeLifecycle { kind, .. } if kind == b));
+    assert_eq!(a_shut_down.load(Ordering::SeqCst), 1);
+    assert_eq!(b_shut_down.load(Ordering::SeqCst), 0);
+
+    runner
+        .create_session("s".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_eq!(a_registered.load(Ordering::SeqCst), 2);
+    runner.shutdown().await;
+    assert_eq!(a_shut_down.load(Ordering::SeqCst), 2);
+    assert_eq!(b_shut_down.load(Ordering::SeqCst), 1);
+}
+
+struct MeteredNode;
+
+#[async_trait]
+impl Node for MeteredNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        ctx.emit("metered", "calling model")?;
+        ctx.emit("metered", "model replied")?;
+        ctx.record_token_usage(TokenUsage::new(100, 20));
+        ctx.record_token_usage(TokenUsage::new(10, 5));
+        Ok(NodePartial::new())
+    }
+}
+
+#[tokio::test]
+async fn test_node_metrics_in_step_report_checkpoint_and_session() {
+    let metered = NodeKind::Custom("metered".into());
+    let app = GraphBuilder::new()
+        .add_node(metered.clone(), MeteredNode)
+        .add_edge(NodeKind::Start, metered.clone())
+        .add_edge(metered.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let probe = Arc::new(ProbeCheckpointer::default());
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(probe.clone())
+        .build()
+        .await;
+    runner
+        .create_session("metrics".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+
+    let Ok(StepResult::Completed(report)) =
+        runner.run_step("metrics", StepOptions::default()).await
+    else {
+        panic!("expected completed step");
+    };
+    assert_eq!(report.node_metrics.len(), 1);
+    let (kind, metrics) = &report.node_metrics[0];
+    assert_eq!(kind, &metered);
+    assert_eq!(metrics.events_emitted, 2);
+    assert_eq!(metrics.token_usage, Some(TokenUsage::new(110, 25)));
+
+    let saved = probe
+        .load_latest("metrics")
+        .await
+        .unwrap()
+        .expect("checkpoint saved");
+    assert_eq!(saved.node_metrics, report.node_metrics);
+    assert_eq!(saved.ran_nodes, vec![metered.clone()]);
+
+    let session = runner.session_metrics("metrics").unwrap();
+    assert_eq!(session.steps, 1);
+    let summary = session.node(&metered).expect("metered summary");
+    assert_eq!(summary.invocations, 1);
+    assert_eq!(summary.events_emitted, 2);
+    assert_eq!(session.total_token_usage().map(|u| u.total()), Some(135));
+    assert!(matches!(
+        runner.session_metrics("missing"),
+        Err(RunnerError::SessionNotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_profile_supersteps_records_node_timings_and_chrome_trace() {
+    let a = NodeKind::Custom("a".into());
+    let b = NodeKind::Custom("b".into());
+    let app = GraphBuilder::new()
+        .add_node(a.clone(), TestNode { name: "a" })
+        .add_node(b.clone(), TestNode { name: "b" })
+        .add_edge(NodeKind::Start, a.clone())
+        .add_edge(NodeKind::Start, b.clone())
+        .add_edge(a.clone(), NodeKind::End)
+        .add_edge(b.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .profile_supersteps(true)
+        .build()
+        .await;
+    runner
+        .create_session("profiled".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+
+    let Ok(StepResult::Completed(report)) =
+        runner.run_step("profiled", StepOptions::default()).await
+    else {
+        panic!("expected completed step");
+    };
+    let profile = report.profile.expect("profile recorded");
+    assert_eq!(profile.step, 1);
+    let nodes: Vec<_> = profile.nodes.iter().map(|n| n.node.clone()).collect();
+    assert_eq!(nodes, vec![a, b]);
+    assert!(profile.duration_micros >= profile.schedule_micros + profile.barrier_micros);
+    for node in &profile.nodes {
+        assert!(node.end_offset_micros() <= profile.schedule_micros);
+    }
+
+    let session = runner.session_profile("profiled").unwrap();
+    assert_eq!(session.steps, vec![profile]);
+    let trace = session.to_chrome_trace();
+    let names: Vec<&str> = trace["traceEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|e| e["ph"] == "X")
+        .filter_map(|e| e["name"].as_str())
+        .collect();
+    for expected in ["superstep 1", "schedule", "barrier", "frontier", "a", "b"] {
+        assert!(names.contains(&expected), "missing {expected} in {names:?}");
+    }
+    assert!(matches!(
+        runner.session_profile("missing"),
+        Err(RunnerError::SessionNotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_profiling_is_off_by_default() {
+    let mut runner = AppRunner::builder().app(make_test_app()).build().await;
+    runner
+        .create_session("plain".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    let Ok(StepResult::Completed(report)) = runner.run_step("plain", StepOptions::default()).await
+    else {
+        panic!("expected completed step");
+    };
+    assert!(report.profile.is_none());
+    assert!(runner.session_profile("plain").unwrap().steps.is_empty());
+}
+
 #[tokio::test]
 async fn test_interrupt_after() {
     let app = make_test_app();
@@ -1299,3 +1815,2659 @@ async fn test_error_event_appended_on_failure() {
         assert_eq!(step, &1);
     }
 }
+
+struct ProgressNode;
+
+#[async_trait]
+impl Node for ProgressNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        for batch in 1..=3 {
+            let mut extra = weavegraph::utils::collections::new_extra_map();
+            extra.insert("batches_done".into(), json!(batch));
+            ctx.yield_partial(NodePartial::new().with_extra(extra))?;
+        }
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, "indexed")]))
+    }
+}
+
+#[tokio::test]
+async fn test_incremental_partials_applied_at_micro_barriers() {
+    let pred: EdgePredicate = Arc::new(|snap: StateSnapshot| {
+        if snap.extra.get("batches_done") == Some(&json!(3)) {
+            vec!["Done".to_string()]
+        } else {
+            vec!["End".to_string()]
+        }
+    });
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("Index".into()), ProgressNode)
+        .add_node(NodeKind::Custom("Done".into()), TestNode { name: "done" })
+        .add_edge(NodeKind::Start, NodeKind::Custom("Index".into()))
+        .add_edge(NodeKind::Custom("Done".into()), NodeKind::End)
+        .add_conditional_edge(NodeKind::Custom("Index".into()), pred)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .autosave(false)
+        .build()
+        .await;
+    runner
+        .create_session("micro".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let extra_version_before = runner.get_session("micro").unwrap().state.extra.version();
+
+    let StepResult::Completed(report) = runner
+        .run_step("micro", StepOptions::default())
+        .await
+        .unwrap()
+    else {
+        panic!("expected completed step");
+    };
+
+    assert_eq!(
+        report.barrier_outcome.updated_channels,
+        vec!["extra", "messages"]
+    );
+    assert_eq!(report.next_frontier, vec![NodeKind::Custom("Done".into())]);
+    let session = runner.get_session("micro").unwrap();
+    assert_eq!(session.state.extra.snapshot()["batches_done"], json!(3));
+    assert_eq!(session.state.extra.version(), extra_version_before + 3);
+    assert_eq!(
+        session.state.messages.snapshot().last().unwrap().content,
+        "indexed"
+    );
+}
+
+struct SlowFailingNode;
+
+#[async_trait]
+impl Node for SlowFailingNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Err(NodeError::ValidationFailed("sibling failed".into()))
+    }
+}
+
+#[tokio::test]
+async fn test_failed_step_discards_partials_yielded_by_siblings() {
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("Index".into()), ProgressNode)
+        .add_node(NodeKind::Custom("Fail".into()), SlowFailingNode)
+        .add_edge(NodeKind::Start, NodeKind::Custom("Index".into()))
+        .add_edge(NodeKind::Start, NodeKind::Custom("Fail".into()))
+        .add_edge(NodeKind::Custom("Index".into()), NodeKind::End)
+        .add_edge(NodeKind::Custom("Fail".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("partial-fail".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let extra_version_before = runner
+        .get_session("partial-fail")
+        .unwrap()
+        .state
+        .extra
+        .version();
+
+    assert!(
+        runner
+            .run_step("partial-fail", StepOptions::default())
+            .await
+            .is_err()
+    );
+
+    let session = runner.get_session("partial-fail").unwrap();
+    assert!(!session.state.extra.snapshot().contains_key("batches_done"));
+    assert_eq!(session.state.extra.version(), extra_version_before);
+    assert!(!session.state.errors.snapshot().is_empty());
+    let saved = checkpointer
+        .load_latest("partial-fail")
+        .await
+        .unwrap()
+        .expect("error checkpoint");
+    assert!(!saved.state.extra.snapshot().contains_key("batches_done"));
+}
+
+#[tokio::test]
+async fn test_context_edge_routes_on_step_and_config_value() {
+    let work = NodeKind::Custom("work".into());
+    let app = GraphBuilder::new()
+        .add_node(work.clone(), SimpleMessageNode::new("tick"))
+        .add_edge(NodeKind::Start, work.clone())
+        .add_conditional_edge_with_context(
+            work.clone(),
+            Arc::new(|ctx| {
+                assert_eq!(ctx.ran_nodes(), std::slice::from_ref(ctx.from()));
+                assert!(ctx.updated_channels().contains(&"messages"));
+                let rounds = ctx
+                    .config_value("rounds")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(1);
+                if ctx.step() >= rounds {
+                    vec![NodeKind::end_target()]
+                } else {
+                    vec![ctx.from().as_target()]
+                }
+            }),
+        )
+        .with_runtime_config(RuntimeConfig::default().with_value("rounds", 3))
+        .compile()
+        .unwrap();
+
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("routing".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("routing").await.unwrap();
+
+    assert_eq!(runner.get_session("routing").unwrap().step, 3);
+    let ticks = final_state
+        .messages
+        .snapshot()
+        .iter()
+        .filter(|m| m.content == "tick")
+        .count();
+    assert_eq!(ticks, 3);
+}
+
+#[derive(Clone, Default)]
+struct SlowSink {
+    seen: Arc<RwLock<Vec<String>>>,
+}
+
+impl weavegraph::event_bus::EventSink for SlowSink {
+    fn handle(&mut self, event: &weavegraph::event_bus::Event) -> std::io::Result<()> {
+        std::thread::sleep(Duration::from_millis(5));
+        self.seen
+            .write()
+            .unwrap()
+            .push(event.scope_label().unwrap_or_default().to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_run_returns_after_sinks_drain_trailing_events() {
+    let sink = SlowSink::default();
+    let seen = Arc::clone(&sink.seen);
+    let mut runner = AppRunner::builder()
+        .app(
+            GraphBuilder::new()
+                .add_node(NodeKind::Custom("emit".into()), EmitterNode)
+                .add_edge(NodeKind::Start, NodeKind::Custom("emit".into()))
+                .add_edge(NodeKind::Custom("emit".into()), NodeKind::End)
+                .compile()
+                .unwrap(),
+        )
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink))
+        .build()
+        .await;
+    runner
+        .create_session("drain".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    runner.run_until_complete("drain").await.unwrap();
+
+    // No sleep: the run itself waits for the sink.
+    let seen = seen.read().unwrap().clone();
+    assert_eq!(seen.iter().filter(|scope| *scope == "test").count(), 3);
+    assert_eq!(seen.last().map(String::as_str), Some(STREAM_END_SCOPE));
+}
+
+struct ExtraWriterNode {
+    value: i64,
+}
+
+#[async_trait]
+impl Node for ExtraWriterNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let mut extra = weavegraph::utils::collections::new_extra_map();
+        extra.insert("result".into(), json!(self.value));
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+#[tokio::test]
+async fn test_parallel_extra_conflict_emits_diagnostic() {
+    use weavegraph::event_bus::EXTRA_CONFLICT_SCOPE;
+    use weavegraph::reducers::ConflictPolicy;
+
+    let a = NodeKind::Custom("a".into());
+    let b = NodeKind::Custom("b".into());
+    let app = GraphBuilder::new()
+        .add_node(a.clone(), ExtraWriterNode { value: 1 })
+        .add_node(b.clone(), ExtraWriterNode { value: 2 })
+        .add_edge(NodeKind::Start, a.clone())
+        .add_edge(NodeKind::Start, b.clone())
+        .add_edge(a, NodeKind::End)
+        .add_edge(b, NodeKind::End)
+        .with_conflict_policy("result", ConflictPolicy::MergeArray)
+        .compile()
+        .unwrap();
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("conflict".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("conflict").await.unwrap();
+
+    assert_eq!(final_state.extra.snapshot()["result"], json!([1, 2]));
+    let diagnostics: Vec<String> = sink
+        .snapshot()
+        .iter()
+        .filter(|e| e.scope_label() == Some(EXTRA_CONFLICT_SCOPE))
+        .map(|e| e.message().to_string())
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec!["step=1 key=result nodes=a,b policy=merge_array"]
+    );
+}
+
+fn validated_app(policy: weavegraph::graphs::OutputValidationPolicy) -> weavegraph::app::App {
+    use weavegraph::graphs::{ExtraValueTypes, JsonType, RequiredExtraKeys};
+
+    let writer = NodeKind::Custom("writer".into());
+    GraphBuilder::new()
+        .add_node(writer.clone(), ExtraWriterNode { value: 7 })
+        .add_edge(NodeKind::Start, writer.clone())
+        .add_edge(writer.clone(), NodeKind::End)
+        .with_output_validator(writer.clone(), RequiredExtraKeys::new(["result"]))
+        .with_output_validator(
+            writer,
+            ExtraValueTypes::new().require("result", JsonType::String),
+        )
+        .with_output_validation_policy(policy)
+        .compile()
+        .unwrap()
+}
+
+async fn run_validated(
+    policy: weavegraph::graphs::OutputValidationPolicy,
+) -> Result<VersionedState, RunnerError> {
+    let mut runner = AppRunner::builder()
+        .app(validated_app(policy))
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("validated".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    runner.run_until_complete("validated").await
+}
+
+#[tokio::test]
+async fn test_output_validation_fail_policy_stops_the_run() {
+    use weavegraph::graphs::OutputValidationPolicy;
+
+    let err = run_validated(OutputValidationPolicy::Fail)
+        .await
+        .unwrap_err();
+    let RunnerError::OutputValidation(err) = err else {
+        panic!("expected an output validation error, got {err:?}");
+    };
+    assert_eq!(err.node, NodeKind::Custom("writer".into()));
+    assert_eq!(err.step, 1);
+    assert_eq!(err.violations.len(), 1);
+    assert_eq!(err.violations[0].validator, "extra_value_types");
+}
+
+#[tokio::test]
+async fn test_output_validation_record_and_reject_policies() {
+    use weavegraph::graphs::{OUTPUT_VALIDATION_TAG, OutputValidationPolicy};
+
+    let recorded = run_validated(OutputValidationPolicy::Record).await.unwrap();
+    assert_eq!(recorded.extra.snapshot()["result"], json!(7));
+    let errors = recorded.errors.snapshot();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].tags, vec![OUTPUT_VALIDATION_TAG.to_string()]);
+    assert_eq!(errors[0].context["validator"], json!("extra_value_types"));
+
+    let rejected = run_validated(OutputValidationPolicy::Reject).await.unwrap();
+    assert!(!rejected.extra.snapshot().contains_key("result"));
+    assert_eq!(rejected.errors.snapshot().len(), 1);
+}
+
+#[tokio::test]
+async fn test_output_validator_closure_and_describe() {
+    use weavegraph::graphs::{MaxMessages, OutputValidationPolicy, OutputViolation};
+
+    let node = NodeKind::Custom("talker".into());
+    let app = GraphBuilder::new()
+        .add_node(node.clone(), SimpleMessageNode::new("hello"))
+        .add_edge(NodeKind::Start, node.clone())
+        .add_edge(node.clone(), NodeKind::End)
+        .with_output_validator(node.clone(), MaxMessages::new(1))
+        .with_output_validator(node, |_: &NodeKind, partial: &NodePartial| -> Vec<_> {
+            partial
+                .messages
+                .iter()
+                .flatten()
+                .filter(|m| m.content.contains("hello"))
+                .map(|_| OutputViolation::new("no_greetings", "greeting not allowed"))
+                .collect()
+        })
+        .with_output_validation_policy(OutputValidationPolicy::Record)
+        .compile()
+        .unwrap();
+
+    let description = app.describe();
+    assert_eq!(
+        description.output_validators["talker"],
+        vec!["max_messages(1)".to_string(), "closure".to_string()]
+    );
+    assert_eq!(description.output_validation_policy, "record");
+
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("closure".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("closure").await.unwrap();
+    let errors = final_state.errors.snapshot();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error.message, "greeting not allowed");
+}
+
+struct PluginNode {
+    registered: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for PluginNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, "plugin")]))
+    }
+
+    async fn on_register(&self, _: NodeContext) -> Result<(), NodeError> {
+        self.registered.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_runner_adopts_dynamic_graph_revision_mid_session() {
+    use weavegraph::graphs::{DynamicGraph, GraphPatch};
+
+    let host = NodeKind::Custom("host".into());
+    let app = GraphBuilder::new()
+        .add_node(host.clone(), SimpleMessageNode::new("host"))
+        .add_edge(NodeKind::Start, host.clone())
+        .add_edge(host.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let graph = Arc::new(DynamicGraph::new(app));
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .dynamic_graph(Arc::clone(&graph))
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("plugins".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+
+    // Discovered after the session started and its nodes were registered.
+    let registered = Arc::new(AtomicUsize::new(0));
+    let plugin = NodeKind::Custom("plugin".into());
+    graph
+        .apply(
+            GraphPatch::new()
+                .add_node(
+                    plugin.clone(),
+                    PluginNode {
+                        registered: Arc::clone(&registered),
+                    },
+                )
+                .add_edge(host, plugin.clone())
+                .add_edge(plugin, NodeKind::End),
+        )
+        .unwrap();
+
+    let final_state = runner.run_until_complete("plugins").await.unwrap();
+    let contents: Vec<String> = final_state
+        .messages
+        .snapshot()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, vec!["go", "host", "plugin"]);
+    assert_eq!(registered.load(Ordering::SeqCst), 1);
+    assert!(
+        sink.snapshot()
+            .iter()
+            .any(|e| e.scope_label() == Some("runner.graph_update"))
+    );
+}
+
+struct SamplerNode;
+
+#[async_trait]
+impl Node for SamplerNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        let mut extra = weavegraph::utils::collections::new_extra_map();
+        extra.insert("sample".to_string(), json!(ctx.rng().random_u64()));
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+async fn run_sampler(session_id: &str, rng_seed: Option<u64>) -> serde_json::Value {
+    let sampler = NodeKind::Custom("sampler".into());
+    let mut config = RuntimeConfig::default();
+    if let Some(seed) = rng_seed {
+        config = config.with_rng_seed(seed);
+    }
+    let app = GraphBuilder::new()
+        .add_node(sampler.clone(), SamplerNode)
+        .add_edge(NodeKind::Start, sampler.clone())
+        .add_edge(sampler, NodeKind::End)
+        .with_runtime_config(config)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session(
+            session_id.into(),
+            VersionedState::new_with_user_message("hi"),
+        )
+        .await
+        .unwrap();
+    let state = runner.run_until_complete(session_id).await.unwrap();
+    state.extra.snapshot()["sample"].clone()
+}
+
+#[tokio::test]
+async fn test_node_rng_is_reproducible_per_session_and_configured_seed() {
+    assert_eq!(
+        run_sampler("session-a", None).await,
+        run_sampler("session-a", None).await
+    );
+    assert_ne!(
+        run_sampler("session-a", None).await,
+        run_sampler("session-b", None).await
+    );
+    assert_eq!(
+        run_sampler("session-a", Some(7)).await,
+        run_sampler("session-b", Some(7)).await
+    );
+}
+
+fn long_state(messages: usize, errors: usize) -> VersionedState {
+    let mut state = VersionedState::new_with_messages(
+        (0..messages)
+            .map(|i| Message::with_role(Role::User, &format!("m{i}")))
+            .collect(),
+    );
+    for i in 0..errors {
+        state
+            .errors
+            .get_mut()
+            .push(ErrorEvent::app(WeaveError::msg(format!("e{i}"))));
+    }
+    state
+}
+
+#[tokio::test]
+async fn test_compact_session_archives_old_items_and_tracks_offsets() {
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(make_test_app())
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("long".into(), long_state(5, 3))
+        .await
+        .unwrap();
+    let messages_version = runner.get_session("long").unwrap().state.messages.version();
+
+    let policy = CompactionPolicy::new()
+        .keep_last_messages(2)
+        .keep_last_errors(1);
+    let report = runner.compact_session("long", policy).await.unwrap();
+    assert_eq!(report.archive_sequence, Some(0));
+    assert_eq!(report.archived_messages, 3);
+    assert_eq!(report.archived_errors, 2);
+
+    let state = &runner.get_session("long").unwrap().state;
+    let contents: Vec<_> = state
+        .messages
+        .snapshot()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, vec!["m3", "m4"]);
+    assert_eq!(state.errors.snapshot()[0].error.message, "e2");
+    assert_eq!(state.messages.version(), messages_version + 1);
+    let marker = CompactionMarker::from_snapshot(&state.snapshot()).unwrap();
+    assert_eq!(marker, report.marker);
+    assert_eq!((marker.archives, marker.archived_messages), (1, 3));
+
+    // Nothing beyond the policy is left, so no second archive is written.
+    let noop = runner.compact_session("long", policy).await.unwrap();
+    assert_eq!(noop.archive_sequence, None);
+
+    runner
+        .update_session_state("long", |state| {
+            state
+                .messages
+                .get_mut()
+                .push(Message::with_role(Role::Assistant, "m5"));
+        })
+        .await
+        .unwrap();
+    let second = runner.compact_session("long", policy).await.unwrap();
+    assert_eq!(second.archive_sequence, Some(1));
+    assert_eq!(second.marker.archived_messages, 4);
+
+    let archives = checkpointer.load_archives("long").await.unwrap();
+    assert_eq!(archives.len(), 2);
+    assert_eq!(archives[0].messages.len(), 3);
+    assert_eq!(archives[0].errors.len(), 2);
+    assert_eq!(archives[1].messages_offset, 3);
+    assert_eq!(archives[1].messages[0].content, "m3");
+    assert_eq!(archives[1].errors_offset, 2);
+
+    let persisted = checkpointer.load_latest("long").await.unwrap().unwrap();
+    assert_eq!(persisted.state.messages.len(), 2);
+    assert!(
+        persisted
+            .state
+            .extra
+            .snapshot()
+            .contains_key(COMPACTION_MARKER_KEY)
+    );
+}
+
+#[tokio::test]
+async fn test_compact_session_keeps_state_when_archive_is_unsupported() {
+    let mut runner = AppRunner::builder()
+        .app(make_test_app())
+        .checkpointer_custom(Arc::new(ProbeCheckpointer::default()))
+        .build()
+        .await;
+    runner
+        .create_session("long".into(), long_state(4, 0))
+        .await
+        .unwrap();
+
+    let err = runner
+        .compact_session("long", CompactionPolicy::new().keep_last_messages(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RunnerError::Checkpointer(CheckpointerError::Unsupported {
+            operation: "save_archive"
+        })
+    ));
+    assert_eq!(runner.get_session("long").unwrap().state.messages.len(), 4);
+}
+
+/// Fails the next `failures` saves of checkpoints past step 0.
+#[derive(Default)]
+struct FlakyCheckpointer {
+    inner: InMemoryCheckpointer,
+    failures: AtomicUsize,
+    attempts: AtomicUsize,
+}
+
+impl FlakyCheckpointer {
+    fn failing(failures: usize) -> Self {
+        Self {
+            failures: AtomicUsize::new(failures),
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl Checkpointer for FlakyCheckpointer {
+    async fn save(&self, checkpoint: Checkpoint) -> weavegraph::runtimes::checkpointer::Result<()> {
+        if checkpoint.step > 0 {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            let failing = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                return Err(CheckpointerError::Backend {
+                    message: "disk full".into(),
+                });
+            }
+        }
+        self.inner.save(checkpoint).await
+    }
+
+    async fn load_latest(
+        &self,
+        session_id: &str,
+    ) -> weavegraph::runtimes::checkpointer::Result<Option<Checkpoint>> {
+        self.inner.load_latest(session_id).await
+    }
+
+    async fn list_sessions(&self) -> weavegraph::runtimes::checkpointer::Result<Vec<String>> {
+        self.inner.list_sessions().await
+    }
+}
+
+struct CountingNode(Arc<AtomicUsize>);
+
+#[async_trait]
+impl Node for CountingNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        ctx.emit("work", "ran")?;
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(Role::Assistant, "ran")]))
+    }
+}
+
+async fn flaky_runner(
+    policy: CheckpointFailurePolicy,
+    checkpointer: Arc<FlakyCheckpointer>,
+    runs: Arc<AtomicUsize>,
+) -> AppRunner {
+    let work = NodeKind::Custom("work".into());
+    let app = GraphBuilder::new()
+        .add_node(work.clone(), CountingNode(runs))
+        .add_edge(NodeKind::Start, work.clone())
+        .add_edge(work, NodeKind::End)
+        .with_runtime_config(RuntimeConfig::default().with_checkpoint_failure_policy(policy))
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(checkpointer)
+        .build()
+        .await;
+    runner
+        .create_session("tx".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner
+}
+
+#[tokio::test]
+async fn test_checkpoint_failure_continue_commits_unsaved_step() {
+    let checkpointer = Arc::new(FlakyCheckpointer::failing(1));
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut runner = flaky_runner(
+        CheckpointFailurePolicy::Continue,
+        checkpointer.clone(),
+        runs.clone(),
+    )
+    .await;
+
+    let result = runner.run_step("tx", StepOptions::default()).await.unwrap();
+    assert!(matches!(result, StepResult::Completed(_)));
+    assert_eq!(runner.get_session("tx").unwrap().step, 1);
+    assert_eq!(
+        checkpointer.load_latest("tx").await.unwrap().unwrap().step,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_checkpoint_failure_retry_then_rollback() {
+    let checkpointer = Arc::new(FlakyCheckpointer::failing(4));
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy = CheckpointFailurePolicy::Retry {
+        max_attempts: 3,
+        backoff: Duration::from_millis(1),
+    };
+    let mut runner = flaky_runner(policy, checkpointer.clone(), runs.clone()).await;
+
+    let err = runner
+        .run_step("tx", StepOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RunnerError::CheckpointRollback {
+            step: 1,
+            attempts: 3,
+            ..
+        }
+    ));
+    let session = runner.get_session("tx").unwrap();
+    assert_eq!(session.step, 0);
+    assert_eq!(session.state.messages.len(), 1);
+    assert_eq!(session.frontier, vec![NodeKind::Custom("work".into())]);
+
+    // One failure left: the retry succeeds and memory matches storage again.
+    runner.run_step("tx", StepOptions::default()).await.unwrap();
+    assert_eq!(checkpointer.attempts.load(Ordering::SeqCst), 5);
+    let persisted = checkpointer.load_latest("tx").await.unwrap().unwrap();
+    let session = runner.get_session("tx").unwrap();
+    assert_eq!(persisted.step, session.step);
+    assert_eq!(persisted.state.messages.len(), session.state.messages.len());
+}
+
+#[tokio::test]
+async fn test_checkpoint_failure_rollback_and_rerun_executes_step_again() {
+    let checkpointer = Arc::new(FlakyCheckpointer::failing(1));
+    let runs = Arc::new(AtomicUsize::new(0));
+    let policy = CheckpointFailurePolicy::RollbackAndRerun { max_reruns: 1 };
+    let mut runner = flaky_runner(policy, checkpointer.clone(), runs.clone()).await;
+
+    runner.run_step("tx", StepOptions::default()).await.unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    let session = runner.get_session("tx").unwrap();
+    assert_eq!(session.step, 1);
+    // The rerun starts from the committed state, so the node's output lands once.
+    assert_eq!(session.state.messages.len(), 2);
+    assert_eq!(
+        checkpointer.load_latest("tx").await.unwrap().unwrap().step,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_checkpoint_rerun_tags_attempt_and_retracts_discarded_events() {
+    let checkpointer = Arc::new(FlakyCheckpointer::failing(1));
+    let policy = CheckpointFailurePolicy::RollbackAndRerun { max_reruns: 1 };
+    let mut runner = flaky_runner(policy, checkpointer, Arc::new(AtomicUsize::new(0))).await;
+    let mut events = runner.event_stream().unwrap();
+
+    runner.run_step("tx", StepOptions::default()).await.unwrap();
+
+    let mut received = Vec::new();
+    let mut retractions = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        match AttemptRetraction::from_event(&event) {
+            Some(retraction) => retractions.push(retraction),
+            None if event.scope_label() == Some("work") => received.push(event),
+            None => {}
+        }
+    }
+    assert_eq!(
+        received.iter().map(Event::attempt).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        retractions,
+        vec![AttemptRetraction::new("tx", 1, 1, "checkpoint_rerun")]
+    );
+    received.retain(|event| !retractions[0].covers(event));
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].to_json_value()["metadata"]["attempt"], 2);
+}
+
+#[tokio::test]
+async fn test_step_error_publishes_retraction_for_failed_attempt() {
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("fail".into()), FailingNode::default())
+        .add_edge(NodeKind::Start, NodeKind::Custom("fail".into()))
+        .add_edge(NodeKind::Custom("fail".into()), NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    let mut events = runner.event_stream().unwrap();
+    runner
+        .create_session("err".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+
+    assert!(
+        runner
+            .run_step("err", StepOptions::default())
+            .await
+            .is_err()
+    );
+
+    let retraction = std::iter::from_fn(|| events.try_recv().ok())
+        .find_map(|event| AttemptRetraction::from_event(&event))
+        .expect("failed step should be retracted");
+    assert_eq!(
+        retraction,
+        AttemptRetraction::new("err", 1, 1, "step_error")
+    );
+}
+
+struct JoinProbe(&'static str);
+
+#[async_trait]
+impl Node for JoinProbe {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        let origin = ctx
+            .incoming_from()
+            .map_or_else(|| "-".to_string(), ToString::to_string);
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(
+            Role::Assistant,
+            &format!("{}@{}<-{origin}", self.0, ctx.step),
+        )]))
+    }
+}
+
+/// Start fans out to `a` and `b`; `a` reaches `join` directly, `b` through `b2`.
+async fn run_join(policy: weavegraph::graphs::JoinPolicy, direct_b: bool) -> Vec<String> {
+    let kind = |name: &str| NodeKind::Custom(name.into());
+    let mut builder = GraphBuilder::new()
+        .add_node(kind("a"), JoinProbe("a"))
+        .add_node(kind("b"), JoinProbe("b"))
+        .add_node(kind("join"), JoinProbe("join"))
+        .add_edge(NodeKind::Start, kind("a"))
+        .add_edge(NodeKind::Start, kind("b"))
+        .add_edge(kind("a"), kind("join"))
+        .add_edge(kind("join"), NodeKind::End)
+        .with_join_policy(kind("join"), policy);
+    builder = if direct_b {
+        builder.add_edge(kind("b"), kind("join"))
+    } else {
+        builder
+            .add_node(kind("b2"), JoinProbe("b2"))
+            .add_edge(kind("b"), kind("b2"))
+            .add_edge(kind("b2"), kind("join"))
+    };
+    let mut runner = AppRunner::builder()
+        .app(builder.compile().unwrap())
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("join".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("join").await.unwrap();
+    final_state
+        .messages
+        .snapshot()
+        .iter()
+        .filter(|m| m.content.starts_with("join@"))
+        .map(|m| m.content.clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_join_policy_dedupe_and_per_incoming_edge() {
+    use weavegraph::graphs::JoinPolicy;
+
+    assert_eq!(run_join(JoinPolicy::Dedupe, true).await, vec!["join@2<--"]);
+    assert_eq!(
+        run_join(JoinPolicy::PerIncomingEdge, true).await,
+        vec!["join@2<-a", "join@2<-b"]
+    );
+}
+
+#[tokio::test]
+async fn test_join_policy_wait_for_all_spans_supersteps() {
+    use weavegraph::graphs::JoinPolicy;
+
+    assert_eq!(
+        run_join(JoinPolicy::Dedupe, false).await,
+        vec!["join@2<--", "join@3<--"]
+    );
+    assert_eq!(
+        run_join(JoinPolicy::WaitForAll, false).await,
+        vec!["join@3<--"]
+    );
+
+    let app = GraphBuilder::new()
+        .add_node(NodeKind::Custom("a".into()), JoinProbe("a"))
+        .add_edge(NodeKind::Start, NodeKind::Custom("a".into()))
+        .add_edge(NodeKind::Custom("a".into()), NodeKind::End)
+        .with_join_policy(NodeKind::Custom("a".into()), JoinPolicy::WaitForAll)
+        .compile()
+        .unwrap();
+    assert_eq!(app.describe().join_policies["a"], "wait_for_all");
+}
+
+fn declared_join_app() -> weavegraph::app::App {
+    let kind = |name: &str| NodeKind::Custom(name.into());
+    let to_join: EdgePredicate = Arc::new(|_| vec!["join".to_string()]);
+    GraphBuilder::new()
+        .add_node(kind("a"), JoinProbe("a"))
+        .add_node(kind("b"), JoinProbe("b"))
+        .add_node(kind("b2"), JoinProbe("b2"))
+        .add_node(kind("join"), JoinProbe("join"))
+        .add_edge(NodeKind::Start, kind("a"))
+        .add_edge(NodeKind::Start, kind("b"))
+        .add_edge(kind("a"), kind("join"))
+        .add_edge(kind("b"), kind("b2"))
+        .add_conditional_edge(kind("b2"), to_join)
+        .add_edge(kind("join"), NodeKind::End)
+        .add_join(kind("join"), [kind("a"), kind("b2")])
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_declared_join_waits_for_conditional_branch_across_resume() {
+    let join = NodeKind::Custom("join".into());
+    let app = declared_join_app();
+    assert_eq!(
+        app.join_upstreams(&join),
+        vec![NodeKind::Custom("a".into()), NodeKind::Custom("b2".into())]
+    );
+    assert_eq!(app.describe().join_upstreams["join"], vec!["a", "b2"]);
+
+    let probe = Arc::new(ProbeCheckpointer::default());
+    let mut before_restart = AppRunner::builder()
+        .app(app.clone())
+        .checkpointer_custom(probe.clone())
+        .build()
+        .await;
+    before_restart
+        .create_session("declared".to_string(), state_with_user("go"))
+        .await
+        .unwrap();
+    before_restart
+        .run_step("declared", StepOptions::default())
+        .await
+        .unwrap();
+    let session = before_restart.get_session("declared").unwrap();
+    assert!(!session.frontier.contains(&join));
+    assert_eq!(
+        session.scheduler_state.join_arrivals(&join),
+        vec![NodeKind::Custom("a".into())]
+    );
+    assert!(
+        session
+            .scheduler_state
+            .versions_seen
+            .keys()
+            .all(|node| !node.starts_with("__weavegraph_"))
+    );
+    drop(before_restart);
+
+    let mut after_restart = AppRunner::builder()
+        .app(app)
+        .checkpointer_custom(probe)
+        .build()
+        .await;
+    let resumed = after_restart
+        .create_session("declared".to_string(), state_with_user("ignored"))
+        .await
+        .unwrap();
+    assert_eq!(resumed, SessionInit::Resumed { checkpoint_step: 1 });
+    let final_state = after_restart.run_until_complete("declared").await.unwrap();
+    let joins: Vec<String> = final_state
+        .messages
+        .snapshot()
+        .iter()
+        .filter(|m| m.content.starts_with("join@"))
+        .map(|m| m.content.clone())
+        .collect();
+    assert_eq!(joins, vec!["join@3<--"]);
+    assert!(
+        after_restart
+            .get_session("declared")
+            .unwrap()
+            .scheduler_state
+            .join_arrivals(&join)
+            .is_empty()
+    );
+}
+
+struct CommandSender;
+
+#[async_trait]
+impl Node for CommandSender {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let receiver = NodeKind::Custom("receiver".into());
+        Ok(NodePartial::new()
+            .with_command(NodeCommand::new(receiver.clone(), "low").with_priority(1))
+            .with_command(
+                NodeCommand::new(receiver, "high")
+                    .with_payload(json!({"limit": 3}))
+                    .with_priority(5),
+            )
+            .with_command(NodeCommand::new(NodeKind::Custom("idle".into()), "wake")))
+    }
+}
+
+struct CommandReceiver;
+
+#[async_trait]
+impl Node for CommandReceiver {
+    async fn run(&self, snapshot: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let commands = snapshot.commands_for(&NodeKind::Custom("receiver".into()));
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        let mut extra = weavegraph::utils::collections::new_extra_map();
+        extra.insert("seen".into(), json!(names));
+        extra.insert("limit".into(), commands[0].payload["limit"].clone());
+        Ok(NodePartial::new().with_extra(extra))
+    }
+}
+
+#[tokio::test]
+async fn test_node_commands_delivered_by_priority_and_cleared() {
+    let sender = NodeKind::Custom("sender".into());
+    let receiver = NodeKind::Custom("receiver".into());
+    let app = GraphBuilder::new()
+        .add_node(sender.clone(), CommandSender)
+        .add_node(receiver.clone(), CommandReceiver)
+        .add_edge(NodeKind::Start, sender.clone())
+        .add_edge(sender.clone(), receiver.clone())
+        .add_edge(receiver.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let final_state = app.invoke(state_with_user("go")).await.unwrap();
+
+    let snapshot = final_state.snapshot();
+    assert_eq!(snapshot.extra["seen"], json!(["high", "low"]));
+    assert_eq!(snapshot.extra["limit"], json!(3));
+    assert!(snapshot.commands_for(&receiver).is_empty());
+    let idle = snapshot.commands_for(&NodeKind::Custom("idle".into()));
+    assert_eq!(idle.len(), 1);
+    assert_eq!(idle[0].name, "wake");
+    assert_eq!(idle[0].from, Some(sender));
+}
+
+#[tokio::test]
+async fn test_expression_edges_route_at_runtime() {
+    let grade = NodeKind::Custom("grade".into());
+    let publish = NodeKind::Custom("publish".into());
+    let revise = NodeKind::Custom("revise".into());
+    let app = |score: f64| {
+        GraphBuilder::new()
+            .add_node(grade.clone(), SimpleMessageNode::new("graded"))
+            .add_node(publish.clone(), SimpleMessageNode::new("published"))
+            .add_node(revise.clone(), SimpleMessageNode::new("revised"))
+            .add_edge(NodeKind::Start, grade.clone())
+            .add_expression_edge(
+                grade.clone(),
+                &format!("{score} > 0.8 && messages.len < 20"),
+                publish.clone(),
+                revise.clone(),
+            )
+            .add_guarded_edge(publish.clone(), NodeKind::End, "step >= 2")
+            .add_edge(revise.clone(), NodeKind::End)
+            .compile()
+            .unwrap()
+    };
+
+    let last = |state: VersionedState| state.snapshot().messages.last().unwrap().content.clone();
+    assert_eq!(
+        last(app(0.9).invoke(state_with_user("go")).await.unwrap()),
+        "published"
+    );
+    assert_eq!(
+        last(app(0.5).invoke(state_with_user("go")).await.unwrap()),
+        "revised"
+    );
+    assert_eq!(
+        app(0.9).describe().conditional_edges[0].label.as_deref(),
+        Some("0.9 > 0.8 && messages.len < 20")
+    );
+}
+
+#[tokio::test]
+async fn test_scheduler_metrics_and_adaptive_concurrency() {
+    let mut builder = GraphBuilder::new();
+    for (name, delay_ms) in [("A", 20), ("B", 5), ("C", 1)] {
+        let kind = NodeKind::Custom(name.into());
+        builder = builder
+            .add_node(kind.clone(), DelayedNode { name, delay_ms })
+            .add_edge(NodeKind::Start, kind.clone())
+            .add_edge(kind, NodeKind::End);
+    }
+    let app = builder
+        .with_runtime_config(
+            RuntimeConfig::default().with_adaptive_concurrency(AdaptiveConcurrency::new(
+                1,
+                2,
+                Duration::from_secs(10),
+            )),
+        )
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    assert!(runner.event_bus_metrics().scheduler.is_some());
+
+    runner
+        .create_session("adaptive".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    runner.run_until_complete("adaptive").await.unwrap();
+
+    let metrics = runner.scheduler_metrics();
+    assert_eq!(metrics.steps, 1);
+    let last = metrics.last_step.expect("superstep recorded");
+    assert_eq!(last.tasks, 3);
+    assert!(metrics.latency_p90_micros >= metrics.latency_p50_micros);
+    assert!(metrics.utilization() > 0.0);
+    // Three tasks queued behind a limit of at most two, with latency far
+    // below target, so the limit grows to the configured maximum.
+    assert_eq!(metrics.concurrency_limit, 2);
+    assert_eq!(
+        runner
+            .get_session("adaptive")
+            .unwrap()
+            .scheduler
+            .concurrency_limit,
+        2
+    );
+    assert_eq!(runner.event_bus_metrics().scheduler, Some(metrics));
+}
+
+#[tokio::test]
+async fn test_sla_breaches_published_as_events() {
+    let slow = NodeKind::Custom("slow".into());
+    let app = GraphBuilder::new()
+        .add_node(
+            slow.clone(),
+            DelayedNode {
+                name: "slow",
+                delay_ms: 20,
+            },
+        )
+        .add_edge(NodeKind::Start, slow.clone())
+        .add_edge(slow.clone(), NodeKind::End)
+        .with_sla(
+            SlaPolicy::new()
+                .max_node_duration(slow.clone(), Duration::from_millis(1))
+                .max_node_duration(NodeKind::Custom("other".into()), Duration::from_millis(1))
+                .max_run_duration(Duration::from_millis(1))
+                .max_errors(1),
+        )
+        .compile()
+        .unwrap();
+    assert_eq!(app.describe().sla.len(), 4);
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("sla".into(), long_state(1, 2))
+        .await
+        .unwrap();
+    runner.run_until_complete("sla").await.unwrap();
+
+    let breaches: Vec<SlaBreach> = sink
+        .snapshot()
+        .iter()
+        .filter_map(SlaBreach::from_event)
+        .collect();
+    let metrics: Vec<SlaMetric> = breaches.iter().map(|b| b.metric).collect();
+    assert_eq!(
+        metrics,
+        vec![
+            SlaMetric::NodeDuration,
+            SlaMetric::RunDuration,
+            SlaMetric::ErrorCount
+        ]
+    );
+    let node_breach = &breaches[0];
+    assert_eq!(node_breach.node, Some(slow));
+    assert_eq!(node_breach.step, 1);
+    assert_eq!(node_breach.limit, 1_000);
+    assert!(node_breach.observed >= 20_000);
+    assert_eq!((breaches[2].observed, breaches[2].limit), (2, 1));
+}
+
+#[derive(Debug, Clone)]
+struct EchoNode(&'static str);
+
+#[async_trait]
+impl Node for EchoNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let last = snapshot
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == Role::User)
+            .map(|m| m.content.clone())
+            .unwrap_or_default();
+        Ok(NodePartial::new()
+            .with_messages(vec![Message::assistant(&format!("{}: {last}", self.0))]))
+    }
+}
+
+fn echo_app() -> weavegraph::app::App {
+    let greet = NodeKind::Custom("greet".into());
+    let echo = NodeKind::Custom("echo".into());
+    GraphBuilder::new()
+        .add_node(greet.clone(), EchoNode("greet"))
+        .add_node(echo.clone(), EchoNode("echo"))
+        .add_edge(NodeKind::Start, greet.clone())
+        .add_edge(greet, echo.clone())
+        .add_edge(echo, NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_resume_with_input_continues_chat_sessions() {
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(echo_app())
+        .checkpointer_custom(checkpointer.clone())
+        .reentry_node(NodeKind::Custom("echo".into()))
+        .build()
+        .await;
+    runner
+        .create_session("chat".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner.run_until_complete("chat").await.unwrap();
+
+    let state = runner
+        .resume_with_input("chat", Message::user("again"))
+        .await
+        .unwrap();
+    let contents: Vec<String> = state
+        .messages
+        .snapshot()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    // The second turn re-enters at `echo`, skipping `greet`.
+    assert_eq!(
+        contents,
+        vec!["hi", "greet: hi", "echo: hi", "again", "echo: again"]
+    );
+
+    // A fresh runner picks the conversation up from the checkpoint.
+    let mut restarted = AppRunner::builder()
+        .app(echo_app())
+        .checkpointer_custom(checkpointer)
+        .build()
+        .await;
+    let state = restarted
+        .resume_with_input("chat", Message::user("third"))
+        .await
+        .unwrap();
+    let messages = state.messages.snapshot();
+    assert_eq!(messages.len(), 8);
+    assert_eq!(messages[7].content, "echo: third");
+    assert_eq!(messages[6].content, "greet: third");
+
+    let missing = restarted
+        .resume_with_input("nope", Message::user("hello"))
+        .await;
+    assert!(matches!(missing, Err(RunnerError::SessionNotFound { .. })));
+}
+
+struct FlaggedNode;
+
+#[async_trait]
+impl Node for FlaggedNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        let ranker = ctx.feature_flags().variant("ranker").unwrap_or("default");
+        Ok(NodePartial::new().with_messages(vec![Message::assistant(&format!("ranker={ranker}"))]))
+    }
+}
+
+/// Puts sessions whose id starts with `beta` in the beta arm.
+struct SessionBucketFlags;
+
+#[async_trait]
+impl FeatureFlagProvider for SessionBucketFlags {
+    async fn resolve(&self, session_id: &str) -> Result<FeatureFlags, FeatureFlagError> {
+        if session_id == "broken" {
+            return Err(FeatureFlagError::Provider {
+                message: "flag service unavailable".into(),
+            });
+        }
+        Ok(FeatureFlags::from_iter([
+            ("beta".to_string(), json!(session_id.starts_with("beta"))),
+            ("ranker".to_string(), json!("bm25")),
+        ]))
+    }
+}
+
+fn flagged_app(config: RuntimeConfig) -> weavegraph::app::App {
+    let rank = NodeKind::Custom("rank".into());
+    let beta = NodeKind::Custom("beta".into());
+    let stable = NodeKind::Custom("stable".into());
+    GraphBuilder::new()
+        .add_node(rank.clone(), FlaggedNode)
+        .add_node(beta.clone(), TestNode { name: "beta" })
+        .add_node(stable.clone(), TestNode { name: "stable" })
+        .add_edge(NodeKind::Start, rank.clone())
+        .add_expression_edge(rank, "flags.beta == true", beta.clone(), stable.clone())
+        .add_edge(beta, NodeKind::End)
+        .add_edge(stable, NodeKind::End)
+        .with_runtime_config(config)
+        .compile()
+        .unwrap()
+}
+
+async fn flagged_run(app: weavegraph::app::App, session_id: &str) -> Vec<String> {
+    let mut runner = AppRunner::builder().app(app).build().await;
+    runner
+        .create_session(session_id.into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let state = runner.run_until_complete(session_id).await.unwrap();
+    state
+        .messages
+        .snapshot()
+        .into_iter()
+        .skip(1)
+        .map(|m| m.content)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_feature_flags_reach_nodes_and_routing() {
+    let config =
+        RuntimeConfig::default().with_feature_flags(StaticFlags::new().with_flag("beta", true));
+    let messages = flagged_run(flagged_app(config), "static").await;
+    assert_eq!(messages[0], "ranker=default");
+    assert!(messages[1].contains("beta"), "{messages:?}");
+
+    let config = RuntimeConfig::default().with_feature_flags(SessionBucketFlags);
+    let beta = flagged_run(flagged_app(config.clone()), "beta-1").await;
+    assert_eq!(beta[0], "ranker=bm25");
+    assert!(beta[1].contains("beta"), "{beta:?}");
+    let stable = flagged_run(flagged_app(config.clone()), "control-1").await;
+    assert!(stable[1].contains("stable"), "{stable:?}");
+
+    // Provider failures fall back to no flags rather than failing the session.
+    let broken = flagged_run(flagged_app(config), "broken").await;
+    assert_eq!(broken[0], "ranker=default");
+    assert!(broken[1].contains("stable"), "{broken:?}");
+}
+
+fn migrated_app(migrations: StateMigrations) -> weavegraph::app::App {
+    let test = NodeKind::Custom("test".into());
+    GraphBuilder::new()
+        .add_node(test.clone(), TestNode { name: "test" })
+        .add_edge(NodeKind::Start, test.clone())
+        .add_edge(test, NodeKind::End)
+        .with_runtime_config(RuntimeConfig::default().with_state_migrations(migrations))
+        .compile()
+        .unwrap()
+}
+
+fn profile_migrations() -> StateMigrations {
+    StateMigrations::new()
+        .migration(0, "nest user_name under user", |state| {
+            let extra = state.extra.get_mut();
+            let name = extra.remove("user_name").ok_or("user_name missing")?;
+            extra.insert("user".into(), json!({ "name": name }));
+            Ok(())
+        })
+        .migration(1, "default locale", |state| {
+            state.extra.get_mut().insert("locale".into(), json!("en"));
+            Ok(())
+        })
+}
+
+#[tokio::test]
+async fn test_state_migrations_upgrade_loaded_checkpoints() {
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut legacy = AppRunner::builder()
+        .app(make_test_app())
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    let initial = VersionedState::builder()
+        .with_user_message("hi")
+        .with_extra("user_name", json!("ada"))
+        .build();
+    legacy.create_session("old".into(), initial).await.unwrap();
+    legacy.run_until_complete("old").await.unwrap();
+
+    // Dry runs report the plan without touching the stored checkpoint.
+    let stored = checkpointer.load_latest("old").await.unwrap().unwrap();
+    let report = profile_migrations().dry_run(&stored).unwrap();
+    assert_eq!((report.from_version, report.to_version), (0, 2));
+    assert_eq!(report.applied.len(), 2);
+    assert_eq!(stored.state.extra.snapshot()["user_name"], json!("ada"));
+
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(migrated_app(profile_migrations()))
+        .checkpointer_custom(checkpointer.clone())
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    let init = runner
+        .create_session(
+            "old".into(),
+            VersionedState::new_with_user_message("unused"),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(init, SessionInit::Resumed { .. }));
+    let extra = runner.get_session("old").unwrap().state.extra.snapshot();
+    assert_eq!(extra["user"], json!({ "name": "ada" }));
+    assert_eq!(extra["locale"], json!("en"));
+    assert_eq!(extra[STATE_SCHEMA_VERSION_KEY], json!(2));
+    assert!(!extra.contains_key("user_name"));
+    runner.run_until_complete("old").await.unwrap();
+    let migrations: Vec<String> = sink
+        .snapshot()
+        .iter()
+        .filter(|e| e.scope_label() == Some("runner.state_migration"))
+        .map(|e| e.message().to_string())
+        .collect();
+    assert_eq!(
+        migrations,
+        vec![
+            "session old: v0 -> v1: nest user_name under user",
+            "session old: v1 -> v2: default locale",
+        ]
+    );
+
+    // New sessions start at the current schema.
+    runner
+        .create_session("new".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    let extra = runner.get_session("new").unwrap().state.extra.snapshot();
+    assert_eq!(extra[STATE_SCHEMA_VERSION_KEY], json!(2));
+
+    // A failing step refuses to resume the session.
+    let mut broken = AppRunner::builder()
+        .app(migrated_app(StateMigrations::new().migration(
+            0,
+            "needs a field",
+            |_| Err("field missing".into()),
+        )))
+        .checkpointer_custom(checkpointer)
+        .build()
+        .await;
+    let err = broken
+        .create_session("old".into(), state_with_user("hi"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RunnerError::StateMigration {
+            source: StateMigrationError::Failed { from: 0, .. },
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_node_input_capture_persists_redacted_snapshots() {
+    use weavegraph::redaction::RedactionPolicy;
+
+    let app = |capture: bool| {
+        GraphBuilder::new()
+            .add_node(NodeKind::Custom("greet".into()), EchoNode("greet"))
+            .add_node(NodeKind::Custom("echo".into()), EchoNode("echo"))
+            .add_edge(NodeKind::Start, NodeKind::Custom("greet".into()))
+            .add_edge(
+                NodeKind::Custom("greet".into()),
+                NodeKind::Custom("echo".into()),
+            )
+            .add_edge(NodeKind::Custom("echo".into()), NodeKind::End)
+            .with_runtime_config(
+                RuntimeConfig::default()
+                    .with_redaction(RedactionPolicy::new().redact_key("api_key"))
+                    .with_node_input_capture(capture),
+            )
+            .compile()
+            .unwrap()
+    };
+    let initial = VersionedState::builder()
+        .with_user_message("hi")
+        .with_extra("api_key", json!("sk-secret"))
+        .build();
+
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(app(true))
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("captured".into(), initial.clone())
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("captured").await.unwrap();
+    // Live state is never redacted.
+    assert_eq!(final_state.extra.snapshot()["api_key"], json!("sk-secret"));
+
+    let captures = checkpointer.load_node_inputs("captured").await.unwrap();
+    assert_eq!(captures.len(), 2);
+    assert_eq!(captures[0].step, 1);
+    assert_eq!(captures[0].nodes, vec![NodeKind::Custom("greet".into())]);
+    assert_eq!(captures[0].input.messages.len(), 1);
+    assert_eq!(captures[1].nodes, vec![NodeKind::Custom("echo".into())]);
+    assert_eq!(captures[1].input.messages[1].content, "greet: hi");
+    assert!(
+        captures
+            .iter()
+            .all(|c| c.input.extra["api_key"] != json!("sk-secret"))
+    );
+
+    // Capture is opt-in.
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(app(false))
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("plain".into(), initial)
+        .await
+        .unwrap();
+    runner.run_until_complete("plain").await.unwrap();
+    assert!(
+        checkpointer
+            .load_node_inputs("plain")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+struct UsageModel;
+
+#[async_trait]
+impl LlmProvider for UsageModel {
+    async fn chat(&self, messages: &[Message]) -> Result<LlmResponse, LlmError> {
+        if messages.iter().any(|m| m.content == "fail") {
+            return Err("model overloaded".into());
+        }
+        Ok(LlmResponse {
+            content: "answer".into(),
+            metadata: json!({ "usage": { "input_tokens": 7, "output_tokens": 2 } }),
+        })
+    }
+}
+
+struct AskNode;
+
+#[async_trait]
+impl Node for AskNode {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let parameters = json!({ "model": "test", "temperature": 0.2 });
+        ctx.chat_audited(&UsageModel, &snapshot.messages, parameters.clone())
+            .await
+            .map_err(NodeError::Other)?;
+        let _ = ctx
+            .chat_audited(&UsageModel, &[Message::user("fail")], parameters)
+            .await;
+        Ok(NodePartial::new())
+    }
+}
+
+#[tokio::test]
+async fn test_llm_audit_persists_redacted_calls_per_step() {
+    use weavegraph::redaction::RedactionPolicy;
+
+    let app = |audit: bool| {
+        GraphBuilder::new()
+            .add_node(NodeKind::Custom("ask".into()), AskNode)
+            .add_edge(NodeKind::Start, NodeKind::Custom("ask".into()))
+            .add_edge(NodeKind::Custom("ask".into()), NodeKind::End)
+            .with_runtime_config(
+                RuntimeConfig::default()
+                    .with_redaction(
+                        RedactionPolicy::new()
+                            .mask_pattern("key", "sk-[a-z]+")
+                            .unwrap(),
+                    )
+                    .with_llm_audit(audit),
+            )
+            .compile()
+            .unwrap()
+    };
+
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(app(true))
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("audited".into(), state_with_user("my key is sk-secret"))
+        .await
+        .unwrap();
+    runner.run_until_complete("audited").await.unwrap();
+
+    let records = checkpointer.load_llm_audit("audited").await.unwrap();
+    assert_eq!(records.len(), 2);
+    let (ok, failed) = (&records[0], &records[1]);
+    assert_eq!(
+        (ok.session_id.as_str(), ok.step, ok.attempt),
+        ("audited", 1, 1)
+    );
+    assert_eq!((ok.index, failed.index), (0, 1));
+    // Same node id as the node's events carry.
+    assert_eq!(ok.node, format!("{:?}", NodeKind::Custom("ask".into())));
+    assert_eq!(ok.call.parameters["model"], "test");
+    assert_eq!(ok.call.response.as_deref(), Some("answer"));
+    assert_eq!(ok.call.token_usage, Some(TokenUsage::new(7, 2)));
+    assert!(!ok.call.prompt[0].content.contains("sk-secret"));
+    assert_eq!(failed.call.response, None);
+    assert_eq!(failed.call.error.as_deref(), Some("model overloaded"));
+
+    // Auditing is opt-in.
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(app(false))
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("plain".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner.run_until_complete("plain").await.unwrap();
+    assert!(
+        checkpointer
+            .load_llm_audit("plain")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+fn reflection_app(quotas: NodeQuotas) -> Result<weavegraph::app::App, GraphCompileError> {
+    let draft = NodeKind::Custom("draft".into());
+    let critic = NodeKind::Custom("critic".into());
+    let publish = NodeKind::Custom("publish".into());
+    let always_redraft: EdgePredicate = Arc::new(|_| vec!["draft".to_string()]);
+    GraphBuilder::new()
+        .add_node(draft.clone(), TestNode { name: "draft" })
+        .add_node(critic.clone(), TestNode { name: "critic" })
+        .add_node(publish.clone(), TestNode { name: "publish" })
+        .add_edge(NodeKind::Start, draft.clone())
+        .add_edge(draft, critic.clone())
+        .add_conditional_edge(critic, always_redraft)
+        .add_edge(publish, NodeKind::End)
+        .with_node_quotas(quotas)
+        .compile()
+}
+
+#[tokio::test]
+async fn test_node_quotas_stop_runaway_loops() {
+    let critic = NodeKind::Custom("critic".into());
+    let publish = NodeKind::Custom("publish".into());
+
+    let sink = MemorySink::new();
+    let app = reflection_app(NodeQuotas::new().max_runs_or(
+        critic.clone(),
+        2,
+        QuotaFallback::RouteTo(publish.clone()),
+    ))
+    .unwrap();
+    assert_eq!(
+        app.describe().node_quotas["Custom:critic"],
+        "2 then route_to:Custom:publish"
+    );
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sinks(vec![Box::new(sink.clone())]))
+        .build()
+        .await;
+    runner
+        .create_session("loop".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let state = runner.run_until_complete("loop").await.unwrap();
+    let names: Vec<String> = state
+        .messages
+        .snapshot()
+        .into_iter()
+        .skip(1)
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(names.len(), 6, "{names:?}");
+    assert!(names[5].contains("publish"), "{names:?}");
+    let session = runner.get_session("loop").unwrap();
+    assert_eq!(session.scheduler_state.run_count(&critic), 2);
+    assert!(sink.snapshot().iter().any(|e| {
+        e.scope_label() == Some("runner.node_quota") && e.message().contains("routed to")
+    }));
+
+    // Without a fallback the session fails with a structured error.
+    let app = reflection_app(NodeQuotas::new().max_runs(critic.clone(), 1)).unwrap();
+    let mut runner = AppRunner::builder().app(app).build().await;
+    runner
+        .create_session("strict".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    match runner.run_until_complete("strict").await {
+        Err(RunnerError::NodeQuotaExhausted { node, max_runs, .. }) => {
+            assert_eq!((node, max_runs), (critic.clone(), 1));
+        }
+        other => panic!("expected quota error, got {other:?}"),
+    }
+
+    let err = reflection_app(NodeQuotas::new().max_runs_or(
+        critic,
+        1,
+        QuotaFallback::RouteTo(NodeKind::Custom("missing".into())),
+    ))
+    .err()
+    .unwrap();
+    assert!(
+        matches!(err, GraphCompileError::InvalidQuotaNode(node) if node == NodeKind::Custom("missing".into()))
+    );
+}
+
+#[tokio::test]
+async fn test_node_quotas_hold_across_iterative_invocations() {
+    let accumulate = NodeKind::Custom("accumulate".into());
+    let app = GraphBuilder::new()
+        .add_node(accumulate.clone(), TickAccumulatorNode)
+        .add_edge(NodeKind::Start, accumulate.clone())
+        .add_edge(accumulate.clone(), NodeKind::End)
+        .with_node_quotas(NodeQuotas::new().max_runs(accumulate.clone(), 2))
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_iterative_session("quota-turns".into(), state_with_user("go"), NodeKind::Start)
+        .await
+        .unwrap();
+
+    for tick in 1..=2 {
+        runner
+            .invoke_next("quota-turns", tick_input(tick), NodeKind::Start)
+            .await
+            .unwrap();
+    }
+    let session = runner.get_session("quota-turns").unwrap();
+    assert_eq!(session.scheduler_state.run_count(&accumulate), 2);
+
+    match runner
+        .invoke_next("quota-turns", tick_input(3), NodeKind::Start)
+        .await
+    {
+        Err(RunnerError::NodeQuotaExhausted { node, max_runs, .. }) => {
+            assert_eq!((node, max_runs), (accumulate, 2));
+        }
+        other => panic!("expected quota error on the third turn, got {other:?}"),
+    }
+}
+
+/// Reports which `extra` keys it can see and writes a summary.
+struct ScopedResearcher;
+
+#[async_trait]
+impl Node for ScopedResearcher {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        ctx.emit("researcher", "researching")?;
+        let mut seen: Vec<String> = snapshot.extra.keys().cloned().collect();
+        seen.sort();
+        let mut extra = weavegraph::utils::collections::new_extra_map();
+        extra.insert("seen".into(), json!(seen));
+        extra.insert(
+            "summary".into(),
+            json!(format!("about {}", snapshot.extra["topic"])),
+        );
+        Ok(NodePartial::new()
+            .with_messages(vec![Message::assistant("research done")])
+            .with_extra(extra))
+    }
+}
+
+#[tokio::test]
+async fn test_subgraph_sees_scoped_state_and_writes_namespaced() {
+    let researcher = NodeKind::Custom("researcher".into());
+    let child = GraphBuilder::new()
+        .add_node(researcher.clone(), ScopedResearcher)
+        .add_edge(NodeKind::Start, researcher.clone())
+        .add_edge(researcher, NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let research = NodeKind::Custom("research".into());
+    let app = GraphBuilder::new()
+        .add_subgraph(
+            research.clone(),
+            child,
+            StateScope::new("research").read_key_as("question", "topic"),
+        )
+        .add_edge(NodeKind::Start, research.clone())
+        .add_edge(research, NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sinks(vec![Box::new(sink.clone())]))
+        .build()
+        .await;
+    let initial = VersionedState::builder()
+        .with_user_message("go")
+        .with_extra("question", json!("otters"))
+        .with_extra("api_key", json!("secret"))
+        .with_extra("summary", json!("parent summary"))
+        .build();
+    runner
+        .create_session("scoped".into(), initial)
+        .await
+        .unwrap();
+    let state = runner.run_until_complete("scoped").await.unwrap();
+
+    let extra = state.extra.snapshot();
+    assert_eq!(extra["research"]["seen"], json!(["topic"]));
+    assert_eq!(extra["research"]["summary"], json!("about \"otters\""));
+    assert_eq!(extra["summary"], json!("parent summary"));
+    assert_eq!(extra["api_key"], json!("secret"));
+    let messages = state.messages.snapshot();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1].content, "research done");
+
+    let events = sink.snapshot();
+    assert!(events.iter().any(|e| e.scope_label() == Some("researcher")));
+    let stream_ends = events
+        .iter()
+        .filter(|e| e.scope_label() == Some(STREAM_END_SCOPE))
+        .count();
+    assert_eq!(stream_ends, 1, "child stream end must not be forwarded");
+}
+
+/// Records one error tagged `transient`.
+struct FlakyAudit;
+
+#[async_trait]
+impl Node for FlakyAudit {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let error = ErrorEvent::node("audit", 2, WeaveError::msg("policy service unavailable"))
+            .with_tag("transient");
+        Ok(NodePartial::new().with_errors(vec![error]))
+    }
+}
+
+#[tokio::test]
+async fn test_aggregating_sink_rolls_up_step_metrics() {
+    let work = NodeKind::Custom("work".into());
+    let audit = NodeKind::Custom("audit".into());
+    let app = GraphBuilder::new()
+        .add_node(work.clone(), TestNode { name: "work" })
+        .add_node(audit.clone(), FlakyAudit)
+        .add_edge(NodeKind::Start, work.clone())
+        .add_edge(work, audit.clone())
+        .add_edge(audit, NodeKind::End)
+        .compile()
+        .unwrap();
+
+    let summaries = MemorySink::new();
+    let rollups = AggregatingSink::new().with_summaries(Duration::ZERO, summaries.clone());
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sinks(vec![Box::new(rollups.clone())]))
+        .publish_step_metrics(true)
+        .build()
+        .await;
+    runner
+        .create_session("rollup".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    runner.run_until_complete("rollup").await.unwrap();
+
+    let rollup = rollups.snapshot();
+    assert_eq!(rollup.events_by_scope[STEP_METRICS_SCOPE], 2);
+    assert_eq!(rollup.error_count("transient"), 1);
+    assert_eq!(rollup.node_latency["Custom:work"].runs, 1);
+    assert!(rollup.average_latency("Custom:audit").is_some());
+    assert!(rollup.events_per_sec(STEP_METRICS_SCOPE) > 0.0);
+
+    let last = summaries
+        .snapshot()
+        .last()
+        .and_then(EventRollup::from_event);
+    let last = last.expect("a summary per handled event");
+    assert_eq!(last.error_count("transient"), 1);
+
+    rollups.reset();
+    assert_eq!(rollups.snapshot().total_events, 0);
+}
+
+/// Records the conversation it was given.
+struct SeenMessages;
+
+#[async_trait]
+impl Node for SeenMessages {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let seen: Vec<&str> = snapshot
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        let mut extra = weavegraph::utils::collections::new_extra_map();
+        extra.insert("seen".into(), json!(seen));
+        Ok(NodePartial::new()
+            .with_extra(extra)
+            .with_messages(vec![Message::assistant("reply")]))
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_view_windows_node_messages() {
+    let reader = NodeKind::Custom("reader".into());
+    let app = GraphBuilder::new()
+        .add_node_with_view(
+            reader.clone(),
+            SeenMessages,
+            SnapshotView::new().roles([Role::User]).last_messages(2),
+        )
+        .add_edge(NodeKind::Start, reader.clone())
+        .add_edge(reader, NodeKind::End)
+        .compile()
+        .unwrap();
+    assert_eq!(
+        app.describe().snapshot_views["Custom:reader"],
+        "roles=user last=2"
+    );
+
+    let mut initial = state_with_user("one");
+    initial.messages.get_mut().extend([
+        Message::assistant("two"),
+        Message::user("three"),
+        Message::assistant("four"),
+        Message::user("five"),
+    ]);
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("window".into(), initial)
+        .await
+        .unwrap();
+    let state = runner.run_until_complete("window").await.unwrap();
+
+    assert_eq!(state.extra.snapshot()["seen"], json!(["three", "five"]));
+    // Writes still append to the full conversation.
+    let messages = state.messages.snapshot();
+    assert_eq!(messages.len(), 6);
+    assert_eq!(messages[5].content, "reply");
+}
+
+#[test]
+fn test_snapshot_view_token_budget_keeps_newest_tail() {
+    let mut state = VersionedState::new_with_messages(vec![
+        Message::user("aaaaaaaa"),
+        Message::assistant("bbbb"),
+        Message::user("cccc"),
+    ]);
+    state.extra.get_mut().insert("k".into(), json!(1));
+    let snapshot = state.snapshot();
+
+    let windowed = SnapshotView::new().max_tokens(2).apply(&snapshot);
+    let contents: Vec<&str> = windowed
+        .messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(contents, vec!["bbbb", "cccc"]);
+    assert_eq!(windowed.messages_version, snapshot.messages_version);
+    assert_eq!(windowed.extra, snapshot.extra);
+
+    // A budget too small for the newest message yields an empty window.
+    assert!(
+        SnapshotView::new()
+            .max_tokens(0)
+            .apply(&snapshot)
+            .messages
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_session_handle_drives_and_pauses_session() {
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(make_test_app())
+        .checkpointer_custom(checkpointer.clone())
+        .autosave(false)
+        .build()
+        .await;
+
+    let mut session = runner
+        .open_session("typed", state_with_user("hi"))
+        .await
+        .unwrap();
+    assert_eq!(session.id().as_str(), "typed");
+    assert_eq!(session.init(), Some(&SessionInit::Fresh));
+    let StepResult::Completed(report) = session.step(StepOptions::default()).await.unwrap() else {
+        panic!("step should complete");
+    };
+    assert_eq!(report.ran_nodes, vec![NodeKind::Custom("test".into())]);
+    assert_eq!(session.state().unwrap().messages.len(), 2);
+
+    // Without autosave, pausing checkpoints the current step.
+    let id = session.pause().await.unwrap();
+    assert_eq!(
+        checkpointer
+            .load_latest("typed")
+            .await
+            .unwrap()
+            .unwrap()
+            .step,
+        1
+    );
+
+    let mut session = runner.session(&id).unwrap();
+    assert_eq!(session.init(), None);
+    let final_state = session.run().await.unwrap();
+    assert_eq!(final_state.messages.len(), 2);
+
+    let missing = runner.session(&SessionId::new("nope")).err();
+    assert!(matches!(missing, Some(RunnerError::SessionNotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_frontier_edges_report_labels_and_publish_routing_events() {
+    use weavegraph::event_bus::ROUTING_SCOPE;
+    use weavegraph::graphs::{ConditionalEdge, EdgeLabel};
+    use weavegraph::runtimes::{FrontierEdge, FrontierEdgeKind};
+
+    let work = NodeKind::Custom("work".into());
+    let review = NodeKind::Custom("review".into());
+    let app = GraphBuilder::new()
+        .add_node(work.clone(), TestNode { name: "work" })
+        .add_node(review.clone(), TestNode { name: "review" })
+        .add_edge(NodeKind::Start, work.clone())
+        .add_edge_labeled(
+            work.clone(),
+            review.clone(),
+            EdgeLabel::new("handoff").with_metadata("team", json!("qa")),
+        )
+        .add_conditional_edge_spec(
+            ConditionalEdge::new(review.clone(), Arc::new(|_| vec![NodeKind::end_target()]))
+                .with_label("approve"),
+        )
+        .compile()
+        .unwrap();
+
+    let events = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(events.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("routes".into(), state_with_user("go"))
+        .await
+        .unwrap();
+
+    let StepResult::Completed(first) = runner
+        .run_step("routes", StepOptions::default())
+        .await
+        .unwrap()
+    else {
+        panic!("expected completed step");
+    };
+    assert_eq!(first.frontier_edges.len(), 1);
+    let handoff = &first.frontier_edges[0];
+    assert_eq!((&handoff.from, &handoff.to), (&work, &review));
+    assert_eq!(handoff.kind, FrontierEdgeKind::Static);
+    assert_eq!(handoff.label.as_deref(), Some("handoff"));
+    assert_eq!(handoff.metadata["team"], "qa");
+
+    let StepResult::Completed(second) = runner
+        .run_step("routes", StepOptions::default())
+        .await
+        .unwrap()
+    else {
+        panic!("expected completed step");
+    };
+    assert_eq!(second.frontier_edges[0].kind, FrontierEdgeKind::Conditional);
+    assert_eq!(second.frontier_edges[0].to, NodeKind::End);
+    assert_eq!(second.frontier_edges[0].label.as_deref(), Some("approve"));
+
+    runner.flush_events().await.unwrap();
+    let routed: Vec<FrontierEdge> = events
+        .snapshot()
+        .iter()
+        .filter(|event| event.scope_label() == Some(ROUTING_SCOPE))
+        .filter_map(FrontierEdge::from_event)
+        .collect();
+    assert_eq!(
+        routed,
+        vec![handoff.clone(), second.frontier_edges[0].clone()]
+    );
+    let first_event = handoff.to_event("routes", 1);
+    assert_eq!(first_event.sequence_key(), Some("routes"));
+    assert_eq!(first_event.message(), "work -> review (handoff)");
+}
+
+fn make_chain_app(config: RuntimeConfig) -> weavegraph::app::App {
+    let kinds = ["a", "b", "c"].map(|name| NodeKind::Custom(name.into()));
+    GraphBuilder::new()
+        .add_node(kinds[0].clone(), TestNode { name: "a" })
+        .add_node(kinds[1].clone(), TestNode { name: "b" })
+        .add_node(kinds[2].clone(), TestNode { name: "c" })
+        .add_edge(NodeKind::Start, kinds[0].clone())
+        .add_edge(kinds[0].clone(), kinds[1].clone())
+        .add_edge(kinds[1].clone(), kinds[2].clone())
+        .add_edge(kinds[2].clone(), NodeKind::End)
+        .with_runtime_config(config)
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn superstep_hooks_receive_every_step_with_state() {
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let config = RuntimeConfig::default().on_superstep(move |update: SuperstepUpdate| {
+        let sink = Arc::clone(&sink);
+        async move {
+            sink.lock().unwrap().push((
+                update.session_id,
+                update.report.step,
+                update.state.messages.len(),
+            ));
+        }
+    });
+    let mut runner = AppRunner::builder()
+        .app(make_chain_app(config))
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("hooked".into(), state_with_user("hello"))
+        .await
+        .unwrap();
+    runner.run_until_complete("hooked").await.unwrap();
+
+    let seen = seen.lock().unwrap().clone();
+    assert_eq!(
+        seen,
+        vec![
+            ("hooked".to_string(), 1, 2),
+            ("hooked".to_string(), 2, 3),
+            ("hooked".to_string(), 3, 4),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn slow_superstep_hooks_are_coalesced_within_budget() {
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let config = RuntimeConfig::default().on_superstep_with(
+        move |update: SuperstepUpdate| {
+            let sink = Arc::clone(&sink);
+            async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                sink.lock().unwrap().push(update.report.step);
+            }
+        },
+        SuperstepHookOptions::default().with_budget(Duration::from_millis(10)),
+    );
+    let mut runner = AppRunner::builder()
+        .app(make_chain_app(config))
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("slow".into(), state_with_user("hello"))
+        .await
+        .unwrap();
+
+    let started = std::time::Instant::now();
+    runner.run_until_complete("slow").await.unwrap();
+    assert!(started.elapsed() < Duration::from_millis(250));
+    assert!(seen.lock().unwrap().is_empty());
+
+    // Step 2 arrived while step 1 was in flight and was replaced by step 3,
+    // which is delivered after the run once step 1's call finishes.
+    tokio::time::sleep(Duration::from_millis(900)).await;
+    assert_eq!(*seen.lock().unwrap(), vec![1, 3]);
+}
+
+#[tokio::test]
+async fn panicking_superstep_hook_does_not_fail_the_run() {
+    let config = RuntimeConfig::default()
+        .on_superstep(|_update: SuperstepUpdate| async { panic!("hook failure") });
+    let mut runner = AppRunner::builder()
+        .app(make_chain_app(config))
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("panics".into(), state_with_user("hello"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("panics").await.unwrap();
+    assert_eq!(final_state.messages.len(), 4);
+}
+
+struct DerivedProbeNode {
+    seen: Arc<std::sync::Mutex<Vec<u64>>>,
+}
+
+#[async_trait]
+impl Node for DerivedProbeNode {
+    async fn run(
+        &self,
+        _snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let count = ctx.derived_as::<u64>("counted").unwrap().unwrap();
+        self.seen.lock().unwrap().push(count);
+        Ok(NodePartial::new().with_messages(vec![Message::assistant("tick")]))
+    }
+}
+
+#[tokio::test]
+async fn derived_channels_are_shared_by_nodes_and_edges_and_cached_by_version() {
+    let computations = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&computations);
+    let counted = DerivedChannel::new("counted", [ChannelType::Message], move |snapshot| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        json!(snapshot.messages.len())
+    });
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let work = NodeKind::Custom("work".into());
+    let app = GraphBuilder::new()
+        .add_node(
+            work.clone(),
+            DerivedProbeNode {
+                seen: Arc::clone(&seen),
+            },
+        )
+        .add_edge(NodeKind::Start, work.clone())
+        .add_conditional_edge_with_context(
+            work.clone(),
+            Arc::new(|ctx| {
+                if ctx.derived_as::<u64>("counted").unwrap() >= Some(4) {
+                    vec![NodeKind::end_target()]
+                } else {
+                    vec![ctx.from().as_target()]
+                }
+            }),
+        )
+        .with_derived_channel(counted)
+        .with_derived_channel(DerivedChannel::last_user_message())
+        .compile()
+        .unwrap();
+    assert_eq!(
+        app.describe().derived_channels["counted"],
+        vec!["message".to_string()]
+    );
+
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("derived".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    runner.run_until_complete("derived").await.unwrap();
+
+    // Nodes see the pre-barrier count; each post-barrier value computed for
+    // routing is reused as the next step's input.
+    assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3]);
+    assert_eq!(computations.load(Ordering::SeqCst), 4);
+
+    // Sessions do not share cached values, even at identical versions.
+    runner
+        .create_session("other".into(), state_with_user("again"))
+        .await
+        .unwrap();
+    runner.run_until_complete("other").await.unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3, 1, 2, 3]);
+    assert_eq!(computations.load(Ordering::SeqCst), 8);
+}
+
+/// Appends `count` assistant messages.
+struct FloodNode {
+    count: usize,
+}
+
+#[async_trait]
+impl Node for FloodNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        Ok(NodePartial::new().with_messages(
+            (0..self.count)
+                .map(|i| Message::with_role(Role::Assistant, &format!("flood {i}")))
+                .collect(),
+        ))
+    }
+}
+
+/// `small` and `flood` run in the same superstep, then `after`.
+fn make_flood_app(limits: StateLimits) -> weavegraph::app::App {
+    let small = NodeKind::Custom("small".into());
+    let flood = NodeKind::Custom("flood".into());
+    let after = NodeKind::Custom("after".into());
+    GraphBuilder::new()
+        .add_node(small.clone(), SimpleMessageNode::new("small"))
+        .add_node(flood.clone(), FloodNode { count: 10 })
+        .add_node(after.clone(), SimpleMessageNode::new("after"))
+        .add_edge(NodeKind::Start, small.clone())
+        .add_edge(NodeKind::Start, flood.clone())
+        .add_edge(small, after.clone())
+        .add_edge(flood, after.clone())
+        .add_edge(after, NodeKind::End)
+        .with_runtime_config(RuntimeConfig::default().with_state_limits(limits))
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_state_limits_pause_and_name_the_offending_node() {
+    let limits = StateLimits::new().max_messages(5).max_extra_keys(10);
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(make_flood_app(limits))
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("step".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let StepResult::Paused(paused) = runner
+        .run_step("step", StepOptions::default())
+        .await
+        .unwrap()
+    else {
+        panic!("expected a pause");
+    };
+    let PausedReason::StateLimitExceeded(breaches) = paused.reason else {
+        panic!("expected a state limit pause");
+    };
+    assert_eq!(breaches.len(), 1);
+    assert_eq!(breaches[0].limit, StateLimit::Messages);
+    assert_eq!(breaches[0].node, Some(NodeKind::Custom("flood".into())));
+    assert_eq!(
+        (breaches[0].step, breaches[0].observed, breaches[0].max),
+        (1, 12, 5)
+    );
+    // The step is committed, so the session can be compacted and resumed.
+    assert_eq!(paused.session_state.state.messages.len(), 12);
+    assert_eq!(runner.get_session("step").unwrap().step, 1);
+
+    runner
+        .create_session("run".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let err = runner.run_until_complete("run").await.unwrap_err();
+    let RunnerError::StateLimitExceeded { step, breaches, .. } = err else {
+        panic!("expected StateLimitExceeded, got {err:?}");
+    };
+    assert_eq!((step, breaches.len()), (1, 1));
+
+    let published: Vec<StateLimitBreach> = sink
+        .snapshot()
+        .iter()
+        .filter_map(StateLimitBreach::from_event)
+        .collect();
+    assert_eq!(published.len(), 2);
+    assert_eq!(published[1], breaches[0]);
+}
+
+#[tokio::test]
+async fn test_state_limits_reject_the_offending_output() {
+    let limits = StateLimits::new()
+        .max_messages(5)
+        .on_exceeded(StateLimitPolicy::RejectOutput);
+    let mut runner = AppRunner::builder()
+        .app(make_flood_app(limits))
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    runner
+        .create_session("reject".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("reject").await.unwrap();
+
+    let contents: Vec<_> = final_state
+        .messages
+        .snapshot()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, vec!["go", "small", "after"]);
+    let errors = final_state.errors.snapshot();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].severity, ErrorSeverity::Warning);
+    assert!(errors[0].tags.contains(&"rejected_output".to_string()));
+    assert!(matches!(
+        &errors[0].scope,
+        ErrorScope::Node { kind, step: 1 } if kind.contains("flood")
+    ));
+}
+
+#[tokio::test]
+async fn test_state_limits_compact_the_session() {
+    let limits = StateLimits::new()
+        .max_messages(5)
+        .on_exceeded(StateLimitPolicy::Compact(
+            CompactionPolicy::new().keep_last_messages(2),
+        ));
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(make_flood_app(limits))
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .create_session("compact".into(), state_with_user("go"))
+        .await
+        .unwrap();
+    let final_state = runner.run_until_complete("compact").await.unwrap();
+
+    // Compacted to 2 messages after step 1, then `after` appended one.
+    assert_eq!(final_state.messages.len(), 3);
+    let marker = CompactionMarker::from_snapshot(&final_state.snapshot()).unwrap();
+    assert_eq!((marker.archived_messages, marker.last_step), (10, 1));
+    assert_eq!(
+        checkpointer.load_archives("compact").await.unwrap().len(),
+        1
+    );
+}
+
+fn make_relay_app() -> weavegraph::app::App {
+    let first = NodeKind::Custom("first".into());
+    let second = NodeKind::Custom("second".into());
+    GraphBuilder::new()
+        .add_node(first.clone(), SimpleMessageNode::new("first"))
+        .add_node(second.clone(), SimpleMessageNode::new("second"))
+        .add_edge(NodeKind::Start, first.clone())
+        .add_edge(first, second.clone())
+        .add_edge(second, NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_adopt_session_runs_externally_restored_state() {
+    // State an external store recorded after `first` ran.
+    let mut state = state_with_user("hi");
+    state.messages.get_mut().push(Message::assistant("first"));
+    let external = SessionState {
+        state,
+        step: 1,
+        frontier: vec![NodeKind::Custom("second".into())],
+        scheduler: Scheduler::new(2),
+        scheduler_state: SchedulerState::default(),
+    };
+    let checkpointer = Arc::new(InMemoryCheckpointer::new());
+    let mut runner = AppRunner::builder()
+        .app(make_relay_app())
+        .checkpointer_custom(checkpointer.clone())
+        .build()
+        .await;
+    runner
+        .adopt_session("adopted".into(), external)
+        .await
+        .unwrap();
+    assert_eq!(
+        checkpointer
+            .load_latest("adopted")
+            .await
+            .unwrap()
+            .unwrap()
+            .step,
+        1
+    );
+
+    let final_state = runner.run_until_complete("adopted").await.unwrap();
+    let contents: Vec<_> = final_state
+        .messages
+        .snapshot()
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    assert_eq!(contents, vec!["hi", "first", "second"]);
+    let session = runner.get_session("adopted").unwrap();
+    assert_eq!(session.step, 2);
+    assert_eq!(session.scheduler.concurrency_limit, 2);
+}
+
+#[tokio::test]
+async fn test_adopt_session_rejects_unrunnable_frontiers() {
+    let mut runner = AppRunner::builder()
+        .app(make_relay_app())
+        .checkpointer(CheckpointerType::InMemory)
+        .build()
+        .await;
+    for node in [NodeKind::Start, NodeKind::Custom("missing".into())] {
+        let session = SessionState {
+            state: state_with_user("hi"),
+            step: 0,
+            frontier: vec![node.clone()],
+            scheduler: Scheduler::new(1),
+            scheduler_state: SchedulerState::default(),
+        };
+        let err = runner
+            .adopt_session("bad".into(), session)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, RunnerError::InvalidFrontierOverride { node: rejected } if *rejected == node)
+        );
+    }
+    assert!(runner.get_session("bad").is_none());
+}
+
+struct WarningNode;
+
+#[async_trait]
+impl Node for WarningNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        Ok(NodePartial::new()
+            .with_messages(vec![Message::assistant("checked")])
+            .with_errors(vec![
+                ErrorEvent::node("check", 2, WeaveError::msg("slow upstream"))
+                    .with_severity(ErrorSeverity::Warning),
+            ]))
+    }
+}
+
+#[tokio::test]
+async fn test_run_summary_is_returned_and_published() {
+    let metered = NodeKind::Custom("metered".into());
+    let check = NodeKind::Custom("check".into());
+    let app = GraphBuilder::new()
+        .add_node(metered.clone(), MeteredNode)
+        .add_node(check.clone(), WarningNode)
+        .add_edge(NodeKind::Start, metered.clone())
+        .add_edge(metered.clone(), check.clone())
+        .add_edge(check.clone(), NodeKind::End)
+        .compile()
+        .unwrap();
+    let sink = MemorySink::new();
+    let mut runner = AppRunner::builder()
+        .app(app)
+        .checkpointer(CheckpointerType::InMemory)
+        .event_bus(EventBus::with_sink(sink.clone()))
+        .build()
+        .await;
+    runner
+        .create_session("summary".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+    let (final_state, summary) = runner
+        .run_until_complete_with_summary("summary")
+        .await
+        .unwrap();
+
+    assert_eq!(final_state.messages.len(), 2);
+    assert_eq!(summary.session_id, "summary");
+    assert_eq!((summary.steps, summary.final_step), (2, 2));
+    assert_eq!(summary.total_node_runs(), 2);
+    assert_eq!(summary.node(&metered).unwrap().events_emitted, 2);
+    assert_eq!(summary.node(&check).unwrap().invocations, 1);
+    assert_eq!(summary.channels_updated.get("messages"), Some(&1));
+    assert_eq!(summary.error_count(ErrorSeverity::Warning), 1);
+    assert_eq!(summary.error_count(ErrorSeverity::Error), 0);
+    assert_eq!(summary.token_usage, Some(TokenUsage::new(110, 25)));
+
+    let events = sink.snapshot();
+    let published: Vec<RunSummary> = events.iter().filter_map(RunSummary::from_event).collect();
+    assert_eq!(published, vec![summary]);
+    let summary_at = events
+        .iter()
+        .position(|e| e.scope_label() == Some(RUN_SUMMARY_SCOPE))
+        .unwrap();
+    let end_at = events
+        .iter()
+        .position(|e| e.scope_label() == Some(STREAM_END_SCOPE))
+        .unwrap();
+    assert!(summary_at < end_at);
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use serde_json::json;
+use weavegraph::channels::Channel;
+use weavegraph::event_bus::{EventBus, MemorySink};
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::message::{Message, Role};
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::runtimes::{SimulationError, SimulationRunner, StubKind};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+/// A side-effecting node that counts how often it really ran.
+#[derive(Clone, Default)]
+struct ChargeCard {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Node for ChargeCard {
+    async fn run(
+        &self,
+        _snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(NodePartial::new().with_extra(extra(json!({"receipt": "real"}))))
+    }
+}
+
+/// Copies `extra.receipt` into an assistant message.
+struct Confirm;
+
+#[async_trait]
+impl Node for Confirm {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let receipt = snapshot
+            .extra
+            .get("receipt")
+            .and_then(|value| value.as_str())
+            .unwrap_or("none")
+            .to_string();
+        Ok(NodePartial::new().with_messages(vec![Message::with_role(
+            Role::Assistant,
+            &format!("receipt {receipt}"),
+        )]))
+    }
+}
+
+/// Stub that echoes the number of messages it was given.
+struct EchoStub;
+
+#[async_trait]
+impl Node for EchoStub {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let receipt = format!("stub-{}", snapshot.messages.len());
+        Ok(NodePartial::new().with_extra(extra(json!({"receipt": receipt}))))
+    }
+}
+
+fn extra(value: serde_json::Value) -> rustc_hash::FxHashMap<String, serde_json::Value> {
+    value
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+fn checkout_app(charge: ChargeCard) -> weavegraph::app::App {
+    let draft = NodeKind::Custom("draft".into());
+    let charge_kind = NodeKind::Custom("charge".into());
+    let confirm = NodeKind::Custom("confirm".into());
+    GraphBuilder::new()
+        .add_node(draft.clone(), SimpleMessageNode::new("order drafted"))
+        .add_side_effect_node(charge_kind.clone(), charge)
+        .add_node(confirm.clone(), Confirm)
+        .add_edge(NodeKind::Start, draft.clone())
+        .add_edge(draft, charge_kind.clone())
+        .add_edge(charge_kind, confirm.clone())
+        .add_edge(confirm, NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+fn quiet_bus() -> EventBus {
+    EventBus::with_sink(MemorySink::new())
+}
+
+#[tokio::test]
+async fn test_simulation_stubs_side_effects_and_runs_pure_nodes() {
+    let charge = ChargeCard::default();
+    let app = checkout_app(charge.clone());
+    let charge_kind = NodeKind::Custom("charge".into());
+    assert!(app.is_side_effecting(&charge_kind));
+    assert!(!app.is_side_effecting(&NodeKind::Custom("draft".into())));
+
+    let report = SimulationRunner::new(app)
+        .event_bus(quiet_bus())
+        .run(state_with_user("buy"))
+        .await
+        .unwrap();
+
+    assert_eq!(charge.calls.load(Ordering::SeqCst), 0);
+    assert_eq!(
+        report.executed_nodes,
+        vec![
+            NodeKind::Custom("draft".into()),
+            NodeKind::Custom("confirm".into())
+        ]
+    );
+    assert_eq!(report.steps, 3);
+    assert_eq!(report.actions.len(), 1);
+    let action = &report.actions[0];
+    assert_eq!(action.node, charge_kind);
+    assert_eq!(action.step, 2);
+    assert_eq!(action.stub, StubKind::Empty);
+    assert_eq!(action.input.messages.len(), 2);
+    assert!(action.output.extra.is_none());
+
+    let messages = report.final_state.messages.snapshot();
+    assert_eq!(messages.last().unwrap().content, "receipt none");
+}
+
+#[tokio::test]
+async fn test_simulation_uses_recorded_outputs_and_stub_nodes() {
+    let charge_kind = NodeKind::Custom("charge".into());
+
+    let recorded = SimulationRunner::new(checkout_app(ChargeCard::default()))
+        .event_bus(quiet_bus())
+        .stub_output(
+            charge_kind.clone(),
+            NodePartial::new().with_extra(extra(json!({"receipt": "recorded"}))),
+        )
+        .run(state_with_user("buy"))
+        .await
+        .unwrap();
+    assert_eq!(recorded.actions[0].stub, StubKind::Recorded);
+    assert_eq!(
+        recorded.final_state.extra.snapshot()["receipt"],
+        json!("recorded")
+    );
+    assert_eq!(
+        recorded
+            .final_state
+            .messages
+            .snapshot()
+            .last()
+            .unwrap()
+            .content,
+        "receipt recorded"
+    );
+
+    let stubbed = SimulationRunner::new(checkout_app(ChargeCard::default()))
+        .event_bus(quiet_bus())
+        .stub(charge_kind.clone(), EchoStub)
+        .run(state_with_user("buy"))
+        .await
+        .unwrap();
+    assert!(stubbed.would_run(&charge_kind));
+    assert_eq!(stubbed.actions_for(&charge_kind).count(), 1);
+    assert_eq!(stubbed.actions[0].stub, StubKind::Node);
+    assert_eq!(
+        stubbed.final_state.extra.snapshot()["receipt"],
+        json!("stub-2")
+    );
+}
+
+#[tokio::test]
+async fn test_simulation_rejects_stubs_for_pure_nodes() {
+    let err = SimulationRunner::new(checkout_app(ChargeCard::default()))
+        .event_bus(quiet_bus())
+        .stub(NodeKind::Custom("draft".into()), EchoStub)
+        .run(VersionedState::new_with_user_message("buy"))
+        .await
+        .unwrap_err();
+    match err {
+        SimulationError::NotSideEffecting(node) => {
+            assert_eq!(node, NodeKind::Custom("draft".into()))
+        }
+        other => panic!("Expected NotSideEffecting, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_describe_marks_side_effecting_nodes() {
+    let descriptor = checkout_app(ChargeCard::default()).describe();
+    let flagged: Vec<_> = descriptor
+        .nodes
+        .iter()
+        .filter(|node| node.side_effecting)
+        .map(|node| node.id.as_str())
+        .collect();
+    assert_eq!(flagged.len(), 1);
+    assert!(flagged[0].contains("charge"));
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::{StreamExt, stream};
+use serde_json::json;
+use weavegraph::app::App;
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::runtimes::{StreamTrigger, TriggerError, TriggerMessage, TriggerWorker};
+use weavegraph::state::{StateSnapshot, VersionedState};
+use weavegraph::types::NodeKind;
+
+#[derive(Default)]
+struct Gauge {
+    active: AtomicUsize,
+    peak: AtomicUsize,
+    runs: AtomicUsize,
+}
+
+struct GaugedNode(Arc<Gauge>);
+
+#[async_trait]
+impl Node for GaugedNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        let active = self.0.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.0.peak.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.0.active.fetch_sub(1, Ordering::SeqCst);
+        self.0.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(NodePartial::default())
+    }
+}
+
+fn gauged_app(gauge: Arc<Gauge>) -> App {
+    let work = NodeKind::Custom("work".into());
+    GraphBuilder::new()
+        .add_node(work.clone(), GaugedNode(gauge))
+        .add_edge(NodeKind::Start, work.clone())
+        .add_edge(work, NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+fn text_factory(message: &TriggerMessage) -> Result<VersionedState, TriggerError> {
+    message
+        .payload
+        .as_str()
+        .map(VersionedState::new_with_user_message)
+        .ok_or_else(|| TriggerError::Rejected("payload must be a string".into()))
+}
+
+#[tokio::test]
+async fn worker_runs_one_session_per_message_within_concurrency_limit() {
+    let gauge = Arc::new(Gauge::default());
+    let worker = TriggerWorker::new(gauged_app(Arc::clone(&gauge)), text_factory)
+        .with_max_concurrent_sessions(2);
+    let payloads = (0..6)
+        .map(|i| json!(format!("job {i}")))
+        .collect::<Vec<_>>();
+
+    let report = worker.run(StreamTrigger::new(stream::iter(payloads))).await;
+
+    assert_eq!(report.received, 6);
+    assert_eq!(report.succeeded, 6);
+    assert_eq!(report.failed, 0);
+    assert_eq!(gauge.runs.load(Ordering::SeqCst), 6);
+    assert!(gauge.peak.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn worker_counts_rejected_messages_and_source_errors() {
+    let gauge = Arc::new(Gauge::default());
+    let worker = TriggerWorker::new(gauged_app(Arc::clone(&gauge)), text_factory);
+    let source = StreamTrigger::from_results(stream::iter(vec![
+        Ok(TriggerMessage::new(json!("ok")).with_id("a")),
+        Ok(TriggerMessage::new(json!({"not": "text"}))),
+        Err(TriggerError::Source("connection reset".into())),
+    ]));
+
+    let report = worker.run(source).await;
+
+    assert_eq!(report.received, 2);
+    assert_eq!(report.succeeded, 1);
+    assert_eq!(report.rejected, 1);
+    assert_eq!(report.source_errors, 1);
+    assert_eq!(gauge.runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn run_until_stops_receiving_on_shutdown() {
+    let gauge = Arc::new(Gauge::default());
+    let worker = TriggerWorker::new(gauged_app(Arc::clone(&gauge)), text_factory);
+    let source = StreamTrigger::new(stream::iter(vec![json!("first")]).chain(stream::pending()));
+
+    let report = tokio::time::timeout(
+        Duration::from_secs(5),
+        worker.run_until(source, tokio::time::sleep(Duration::from_millis(100))),
+    )
+    .await
+    .expect("worker should stop on shutdown");
+
+    assert_eq!(report.received, 1);
+    assert_eq!(report.succeeded, 1);
+}
+
+#[tokio::test]
+async fn source_errors_back_off_until_shutdown() {
+    let gauge = Arc::new(Gauge::default());
+    let worker = TriggerWorker::new(gauged_app(Arc::clone(&gauge)), text_factory)
+        .with_error_backoff(Duration::from_millis(10), Duration::from_millis(40));
+    let source = StreamTrigger::from_results(stream::repeat_with(|| {
+        Err(TriggerError::Source("connection refused".into()))
+    }));
+
+    let report = tokio::time::timeout(
+        Duration::from_secs(5),
+        worker.run_until(source, tokio::time::sleep(Duration::from_millis(150))),
+    )
+    .await
+    .expect("worker should stop on shutdown");
+
+    // 10 + 20 + 40 + 40 ms of backoff fit before shutdown; without it the
+    // worker would spin on the failing source.
+    assert!(
+        (3..=8).contains(&report.source_errors),
+        "unexpected source error count: {}",
+        report.source_errors
+    );
+    assert_eq!(report.received, 0);
+}
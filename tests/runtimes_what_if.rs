@@ -0,0 +1,272 @@
+#![cfg(feature = "sqlite")]
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+use weavegraph::graphs::{ConditionalEdge, GraphBuilder};
+use weavegraph::message::Message;
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::runtimes::{
+    AppRunner, Checkpoint, DivergenceKind, SQLiteCheckpointer, SessionState, WhatIfAnalysis,
+    WhatIfError,
+};
+use weavegraph::schedulers::{Scheduler, SchedulerState};
+use weavegraph::state::StateSnapshot;
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+/// Labels the conversation from the last user message.
+struct Classify {
+    keyword: &'static str,
+}
+
+#[async_trait]
+impl Node for Classify {
+    async fn run(
+        &self,
+        snapshot: StateSnapshot,
+        _ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        let text = snapshot
+            .messages
+            .last()
+            .map(|m| m.content.clone())
+            .unwrap_or_default();
+        let label = if text.contains(self.keyword) {
+            "billing"
+        } else {
+            "other"
+        };
+        Ok(NodePartial::new()
+            .with_extra([("label".to_string(), json!(label))].into_iter().collect()))
+    }
+}
+
+fn classify() -> NodeKind {
+    NodeKind::Custom("classify".into())
+}
+
+fn billing() -> NodeKind {
+    NodeKind::Custom("billing".into())
+}
+
+fn route_on_label(snapshot: StateSnapshot) -> Vec<String> {
+    if snapshot.extra.get("label") == Some(&json!("billing")) {
+        vec![billing().as_target()]
+    } else {
+        vec![NodeKind::end_target()]
+    }
+}
+
+fn support_app() -> weavegraph::app::App {
+    GraphBuilder::new()
+        .add_node(classify(), Classify { keyword: "refund" })
+        .add_node(billing(), SimpleMessageNode::new("billing handled"))
+        .add_edge(NodeKind::Start, classify())
+        .add_conditional_edge(classify(), Arc::new(route_on_label))
+        .add_edge(billing(), NodeKind::End)
+        .compile()
+        .unwrap()
+}
+
+async fn record(store: Arc<SQLiteCheckpointer>, session: &str, text: &str) {
+    let mut runner = AppRunner::builder()
+        .app(support_app())
+        .checkpointer_custom(store)
+        .build()
+        .await;
+    runner
+        .create_session(session.to_string(), state_with_user(text))
+        .await
+        .unwrap();
+    runner.run_until_complete(session).await.unwrap();
+}
+
+#[tokio::test]
+async fn unchanged_graph_reports_no_divergence() {
+    let store = Arc::new(
+        SQLiteCheckpointer::connect("sqlite::memory:")
+            .await
+            .unwrap(),
+    );
+    record(Arc::clone(&store), "refund", "I want a refund").await;
+
+    let report = WhatIfAnalysis::new(support_app())
+        .replace_node(classify(), Classify { keyword: "refund" })
+        .replace_routing(ConditionalEdge::new(classify(), Arc::new(route_on_label)))
+        .analyze_session(store.as_ref(), "refund")
+        .await
+        .unwrap();
+
+    assert_eq!(report.session_id, "refund");
+    assert_eq!(report.steps_analyzed, 2);
+    assert!(report.is_unchanged());
+}
+
+#[tokio::test]
+async fn replaced_node_and_routing_divergences_are_reported_per_step() {
+    let store = Arc::new(
+        SQLiteCheckpointer::connect("sqlite::memory:")
+            .await
+            .unwrap(),
+    );
+    record(Arc::clone(&store), "refund", "I want a refund").await;
+
+    // A stricter classifier no longer recognises the request as billing.
+    let report = WhatIfAnalysis::new(support_app())
+        .replace_node(
+            classify(),
+            Classify {
+                keyword: "chargeback",
+            },
+        )
+        .replace_routing(ConditionalEdge::new(
+            classify(),
+            Arc::new(|_| vec![NodeKind::end_target()]),
+        ))
+        .analyze_session(store.as_ref(), "refund")
+        .await
+        .unwrap();
+
+    assert_eq!(report.divergences.len(), 2);
+    let first = report.first_divergence().unwrap();
+    assert_eq!((first.step, &first.node), (1, &classify()));
+    let DivergenceKind::State {
+        messages,
+        extra_keys,
+        output,
+    } = &first.kind
+    else {
+        panic!("expected a state divergence, got {:?}", first.kind);
+    };
+    assert!(messages.is_empty());
+    assert_eq!(extra_keys, &vec!["label".to_string()]);
+    assert_eq!(output.extra.as_ref().unwrap()["label"], json!("other"));
+
+    let routing = &report.divergences[1];
+    assert_eq!((routing.step, &routing.node), (1, &classify()));
+    let DivergenceKind::Routing {
+        recorded,
+        alternative,
+    } = &routing.kind
+    else {
+        panic!("expected a routing divergence, got {:?}", routing.kind);
+    };
+    assert_eq!(recorded, &vec![billing().as_target()]);
+    assert_eq!(alternative, &vec![NodeKind::end_target()]);
+}
+
+#[tokio::test]
+async fn replaced_message_node_is_compared_with_appended_messages() {
+    let store = Arc::new(
+        SQLiteCheckpointer::connect("sqlite::memory:")
+            .await
+            .unwrap(),
+    );
+    record(Arc::clone(&store), "refund", "I want a refund").await;
+
+    let report = WhatIfAnalysis::new(support_app())
+        .replace_node(billing(), SimpleMessageNode::new("refund issued"))
+        .analyze_session(store.as_ref(), "refund")
+        .await
+        .unwrap();
+
+    assert_eq!(report.divergences.len(), 1);
+    let divergence = &report.divergences[0];
+    assert_eq!((divergence.step, &divergence.node), (2, &billing()));
+    let DivergenceKind::State { messages, .. } = &divergence.kind else {
+        panic!("expected a state divergence");
+    };
+    assert_eq!(messages, &vec![Message::assistant("refund issued")]);
+}
+
+/// Records the node id it was invoked under.
+struct EchoNodeId;
+
+#[async_trait]
+impl Node for EchoNodeId {
+    async fn run(
+        &self,
+        _snapshot: StateSnapshot,
+        ctx: NodeContext,
+    ) -> Result<NodePartial, NodeError> {
+        Ok(NodePartial::new().with_extra(
+            [("node_id".to_string(), json!(ctx.node_id))]
+                .into_iter()
+                .collect(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn replacement_nodes_see_the_scheduler_node_id() {
+    let echo = || {
+        GraphBuilder::new()
+            .add_node(classify(), EchoNodeId)
+            .add_edge(NodeKind::Start, classify())
+            .add_edge(classify(), NodeKind::End)
+            .compile()
+            .unwrap()
+    };
+    let store = Arc::new(
+        SQLiteCheckpointer::connect("sqlite::memory:")
+            .await
+            .unwrap(),
+    );
+    let mut runner = AppRunner::builder()
+        .app(echo())
+        .checkpointer_custom(Arc::clone(&store) as _)
+        .build()
+        .await;
+    runner
+        .create_session("echo".to_string(), state_with_user("hi"))
+        .await
+        .unwrap();
+    runner.run_until_complete("echo").await.unwrap();
+
+    let report = WhatIfAnalysis::new(echo())
+        .replace_node(classify(), EchoNodeId)
+        .analyze_session(store.as_ref(), "echo")
+        .await
+        .unwrap();
+
+    assert!(report.is_unchanged(), "{:?}", report.divergences);
+}
+
+#[tokio::test]
+async fn unusable_history_and_unknown_nodes_are_rejected() {
+    let analysis = WhatIfAnalysis::new(support_app());
+    assert!(matches!(
+        analysis.analyze(&[]).await,
+        Err(WhatIfError::EmptyHistory)
+    ));
+
+    let at = |step| {
+        let session = SessionState {
+            state: state_with_user("hi"),
+            step,
+            frontier: vec![NodeKind::Start],
+            scheduler: Scheduler::new(1),
+            scheduler_state: SchedulerState::default(),
+        };
+        Checkpoint::from_session("gap", &session)
+    };
+    assert!(matches!(
+        analysis.analyze(&[at(0), at(2)]).await,
+        Err(WhatIfError::Gap {
+            previous: 0,
+            next: 2
+        })
+    ));
+
+    let unknown = NodeKind::Custom("missing".into());
+    let err = WhatIfAnalysis::new(support_app())
+        .replace_node(unknown.clone(), Classify { keyword: "x" })
+        .analyze(&[at(0)])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WhatIfError::UnknownNode(node) if node == unknown));
+}
@@ -2,11 +2,13 @@ use async_trait::async_trait;
 use rustc_hash::FxHashMap;
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Duration;
 use weavegraph::event_bus::EventBus;
 use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
 use weavegraph::schedulers::scheduler::{
     Scheduler, SchedulerRunContext, SchedulerState, StepRunResult,
 };
+use weavegraph::schedulers::{AdaptiveConcurrency, SchedulerMetrics, SuperstepSaturation};
 use weavegraph::state::StateSnapshot;
 use weavegraph::types::NodeKind;
 use weavegraph::utils::clock::MockClock;
@@ -69,6 +71,50 @@ async fn test_superstep_propagates_node_error() {
     }
 }
 
+struct PanickingNode;
+
+#[async_trait]
+impl Node for PanickingNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        panic!("node exploded");
+    }
+}
+
+#[tokio::test]
+async fn test_superstep_reports_node_panic() {
+    weavegraph::schedulers::install_node_panic_hook();
+    let sched = Scheduler::new(4);
+    let mut state = SchedulerState::default();
+    let mut nodes: FxHashMap<NodeKind, Arc<dyn Node>> = FxHashMap::default();
+    nodes.insert(NodeKind::Custom("BOOM".into()), Arc::new(PanickingNode));
+    let event_bus = EventBus::default();
+
+    let res = sched
+        .superstep(
+            &mut state,
+            &nodes,
+            vec![NodeKind::Custom("BOOM".into())],
+            create_test_snapshot(1, 1),
+            3,
+            SchedulerRunContext::new(event_bus.get_emitter()),
+        )
+        .await;
+    match res {
+        Err(weavegraph::schedulers::scheduler::SchedulerError::NodePanic {
+            kind,
+            step,
+            message,
+            trace,
+        }) => {
+            assert_eq!(kind, NodeKind::Custom("BOOM".into()));
+            assert_eq!(step, 3);
+            assert_eq!(message, "node exploded");
+            assert!(!trace.is_empty(), "backtrace should be captured");
+        }
+        other => panic!("expected SchedulerError::NodePanic, got: {:?}", other),
+    }
+}
+
 #[test]
 fn test_should_run_and_record_seen() {
     let sched = Scheduler::new(4);
@@ -276,3 +322,161 @@ async fn test_scheduler_run_context_injects_clock_and_invocation_id() {
         Some(&json!("scheduler-session"))
     );
 }
+
+#[tokio::test]
+async fn test_superstep_reports_queue_wait_and_utilization() {
+    let nodes = make_delayed_registry();
+    let frontier = vec![NodeKind::Custom("A".into()), NodeKind::Custom("B".into())];
+    let sched = Scheduler::new(1);
+    let mut state = SchedulerState::default();
+    let event_bus = EventBus::default();
+    let res = sched
+        .superstep(
+            &mut state,
+            &nodes,
+            frontier,
+            create_test_snapshot(1, 1),
+            1,
+            SchedulerRunContext::new(event_bus.get_emitter()),
+        )
+        .await
+        .unwrap();
+
+    let saturation = res.saturation;
+    assert_eq!(saturation.step, 1);
+    assert_eq!(saturation.tasks, 2);
+    assert!(saturation.saturated());
+    // B waits behind A's 30ms run for the single slot.
+    assert!(saturation.max_queue_wait_micros >= 25_000);
+    assert!(saturation.busy_micros <= saturation.wall_micros);
+    assert!(saturation.utilization() > 0.5);
+}
+
+#[test]
+fn test_adaptive_concurrency_follows_latency() {
+    let adaptive = AdaptiveConcurrency::new(2, 8, Duration::from_millis(100));
+
+    let mut slow = SchedulerMetrics::default();
+    slow.latency_p90_micros = 500_000;
+    assert_eq!(adaptive.next_limit(8, &slow), 6);
+    assert_eq!(adaptive.next_limit(2, &slow), 2);
+
+    let mut queued = SuperstepSaturation::default();
+    queued.concurrency_limit = 4;
+    queued.tasks = 6;
+    let mut fast = SchedulerMetrics::default();
+    fast.latency_p90_micros = 10_000;
+    fast.last_step = Some(queued);
+    assert_eq!(adaptive.next_limit(4, &fast), 5);
+    assert_eq!(adaptive.next_limit(8, &fast), 8);
+
+    fast.last_step = None;
+    assert_eq!(adaptive.next_limit(4, &fast), 4);
+    assert_eq!(adaptive.next_limit(20, &fast), 8);
+}
+
+/// Waits for cancellation, then bails out.
+struct CooperativeNode;
+
+#[async_trait]
+impl Node for CooperativeNode {
+    async fn run(&self, _: StateSnapshot, ctx: NodeContext) -> Result<NodePartial, NodeError> {
+        ctx.cancelled().await;
+        Err(NodeError::Other("request cancelled".into()))
+    }
+}
+
+/// Ignores cancellation entirely.
+struct StubbornNode;
+
+#[async_trait]
+impl Node for StubbornNode {
+    async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        Ok(NodePartial::new())
+    }
+}
+
+#[tokio::test]
+async fn test_superstep_cancels_cooperatively_then_aborts() {
+    use weavegraph::event_bus::CANCELLATION_SCOPE;
+    use weavegraph::node::CancellationToken;
+    use weavegraph::schedulers::SchedulerError;
+
+    let sched = Scheduler::new(4);
+    let mut state = SchedulerState::default();
+    let mut nodes: FxHashMap<NodeKind, Arc<dyn Node>> = FxHashMap::default();
+    nodes.insert(NodeKind::Custom("polite".into()), Arc::new(CooperativeNode));
+    nodes.insert(NodeKind::Custom("stubborn".into()), Arc::new(StubbornNode));
+    let event_bus = EventBus::default();
+    let mut events = event_bus.subscribe();
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        canceller.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let res = sched
+        .superstep(
+            &mut state,
+            &nodes,
+            vec![
+                NodeKind::Custom("polite".into()),
+                NodeKind::Custom("stubborn".into()),
+            ],
+            create_test_snapshot(1, 1),
+            2,
+            SchedulerRunContext::new(event_bus.get_emitter())
+                .with_cancellation(token.clone(), Duration::from_millis(50)),
+        )
+        .await;
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "stubborn node must be dropped"
+    );
+    match res {
+        Err(SchedulerError::Cancelled { step, aborted }) => {
+            assert_eq!(step, 2);
+            assert_eq!(aborted, vec![NodeKind::Custom("stubborn".into())]);
+        }
+        other => panic!("expected SchedulerError::Cancelled, got: {:?}", other),
+    }
+
+    let mut phases = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let weavegraph::event_bus::Event::Node(node) = event
+            && node.scope() == CANCELLATION_SCOPE
+        {
+            phases.push((
+                node.metadata()["phase"].clone(),
+                node.node_id().map(str::to_string),
+            ));
+        }
+    }
+    assert_eq!(
+        phases,
+        vec![
+            (json!("cooperative"), None),
+            (json!("abort"), Some(r#"Custom("stubborn")"#.to_string())),
+        ]
+    );
+
+    // Once cancelled, later steps fail without running anything.
+    let again = sched
+        .superstep(
+            &mut state,
+            &nodes,
+            vec![NodeKind::Custom("polite".into())],
+            create_test_snapshot(1, 1),
+            3,
+            SchedulerRunContext::new(event_bus.get_emitter())
+                .with_cancellation(token, Duration::from_millis(50)),
+        )
+        .await;
+    assert!(matches!(
+        again,
+        Err(SchedulerError::Cancelled { step: 3, ref aborted }) if aborted.is_empty()
+    ));
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "schema")]
+
+use serde_json::json;
+use weavegraph::schema;
+
+#[test]
+fn test_schema_titles_match_models() {
+    assert_eq!(
+        schema::persisted_state_schema().get("title"),
+        Some(&json!("PersistedState"))
+    );
+    assert_eq!(
+        schema::checkpoint_schema().get("title"),
+        Some(&json!("PersistedCheckpoint"))
+    );
+    assert_eq!(schema::event_schema().get("title"), Some(&json!("Event")));
+    assert_eq!(
+        schema::message_schema().get("title"),
+        Some(&json!("Message"))
+    );
+}
+
+#[test]
+fn test_checkpoint_schema_describes_session_fields() {
+    let schema = serde_json::to_value(schema::checkpoint_schema()).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    for field in ["session_id", "step", "state", "frontier", "versions_seen"] {
+        assert!(properties.contains_key(field), "missing property {field}");
+    }
+}
+
+#[test]
+fn test_all_schemas_names_are_stable() {
+    let names: Vec<_> = schema::all_schemas().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["persisted_state", "checkpoint", "event", "message"]);
+}
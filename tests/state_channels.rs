@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use weavegraph::channels::Channel;
+use weavegraph::channels::{Blob, Channel};
 use weavegraph::message::{Message, Role};
 use weavegraph::node::NodePartial;
-use weavegraph::state::{StateKey, StateSlotError, VersionedState};
+use weavegraph::state::{StateKey, StateSlotError, StateSnapshot, VersionedState};
+use weavegraph::types::ChannelType;
 
 use proptest::prelude::*;
 
@@ -73,6 +74,36 @@ fn test_new_with_messages_initializes_fields() {
     assert_eq!(snapshot.errors_version, 1);
 }
 
+#[test]
+fn test_snapshot_json_carries_blobs_as_base64() {
+    let state = VersionedState::builder()
+        .with_user_message("what is in this picture?")
+        .with_blob(
+            "photo",
+            Blob::new(&b"hello"[..]).with_content_type("image/jpeg"),
+        )
+        .build();
+    let snapshot = state.snapshot();
+    assert_eq!(snapshot.blobs_version, 1);
+
+    let value = serde_json::to_value(&snapshot).unwrap();
+    assert_eq!(
+        value["blobs"]["photo"],
+        json!({ "content_type": "image/jpeg", "data": "aGVsbG8=" })
+    );
+    let back: StateSnapshot = serde_json::from_value(value).unwrap();
+    assert_eq!(back.blobs, snapshot.blobs);
+
+    // Snapshots from callers that predate blobs still deserialize.
+    let mut legacy =
+        serde_json::to_value(VersionedState::new_with_user_message("hi").snapshot()).unwrap();
+    assert!(legacy.get("blobs").is_none());
+    legacy.as_object_mut().unwrap().remove("blobs_version");
+    let back: StateSnapshot = serde_json::from_value(legacy).unwrap();
+    assert!(back.blobs.is_empty());
+    assert_eq!(back.blobs_version, 1);
+}
+
 #[test]
 fn test_snapshot_is_deep_copy() {
     let mut s = VersionedState::new_with_user_message("x");
@@ -381,3 +412,78 @@ proptest! {
         );
     }
 }
+
+fn state_with_extra_in_order(keys: &[usize]) -> VersionedState {
+    let mut state = VersionedState::new_with_user_message("hi");
+    for key in keys {
+        state
+            .extra
+            .get_mut()
+            .insert(format!("key-{key}"), json!({"n": key, "tags": ["a", "b"]}));
+    }
+    state
+}
+
+#[test]
+fn test_fingerprint_ignores_versions_and_tracks_each_channel() {
+    let base = state_with_extra_in_order(&[1, 2, 3]);
+    let fingerprint = base.fingerprint();
+    assert_eq!(fingerprint.len(), 32);
+    assert_eq!(base.snapshot().fingerprint(), fingerprint);
+
+    let mut bumped = base.clone();
+    bumped.messages.set_version(9);
+    bumped.extra.set_version(4);
+    assert_eq!(bumped.fingerprint(), fingerprint);
+
+    let mut changed = base.clone();
+    changed.messages.get_mut().push(Message::assistant("hello"));
+    assert_ne!(changed.fingerprint(), fingerprint);
+    assert_ne!(
+        changed.channel_fingerprint(ChannelType::Message),
+        base.channel_fingerprint(ChannelType::Message)
+    );
+    assert_eq!(
+        changed.channel_fingerprint(ChannelType::Extra),
+        base.channel_fingerprint(ChannelType::Extra)
+    );
+
+    let mut with_blob = base.clone();
+    with_blob.blobs.get_mut().insert(
+        "image".into(),
+        Blob::new(vec![1, 2, 3]).with_content_type("image/png"),
+    );
+    let mut other_type = base.clone();
+    other_type.blobs.get_mut().insert(
+        "image".into(),
+        Blob::new(vec![1, 2, 3]).with_content_type("image/jpeg"),
+    );
+    assert_ne!(with_blob.fingerprint(), fingerprint);
+    assert_ne!(
+        with_blob.channel_fingerprint(ChannelType::Blob),
+        other_type.channel_fingerprint(ChannelType::Blob)
+    );
+}
+
+#[test]
+fn test_fingerprint_is_stable_across_releases() {
+    // Changing this value breaks stored fingerprints; bump the encoding version instead.
+    let state = VersionedState::builder()
+        .with_user_message("What's the weather like?")
+        .with_extra("location", json!({"city": "Oslo", "units": "celsius"}))
+        .build();
+    assert_eq!(state.fingerprint(), "634498a3cdcb8c1d12787089d9c2e10d");
+}
+
+proptest! {
+    #[test]
+    fn prop_fingerprint_ignores_extra_insertion_order(
+        keys in prop::collection::vec(0usize..1000, 0..32),
+    ) {
+        let forward = state_with_extra_in_order(&keys);
+        let reversed: Vec<_> = keys.iter().rev().copied().collect();
+        let backward = state_with_extra_in_order(&reversed);
+        prop_assert_eq!(forward.snapshot().extra, backward.snapshot().extra);
+        prop_assert_eq!(forward.fingerprint(), backward.fingerprint());
+    }
+}
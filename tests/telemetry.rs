@@ -190,3 +190,66 @@ fn formatter_mode_auto_default() {
     let render2 = default_fmt.render_event(&ev);
     assert_eq!(render1.join_lines(), render2.join_lines());
 }
+
+mod event_bus_layer {
+    use async_trait::async_trait;
+    use futures_util::StreamExt;
+    use tracing_subscriber::prelude::*;
+    use weavegraph::event_bus::{Event, STREAM_END_SCOPE, TRACING_SCOPE};
+    use weavegraph::graphs::GraphBuilder;
+    use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+    use weavegraph::state::{StateSnapshot, VersionedState};
+    use weavegraph::telemetry::EventBusLayer;
+    use weavegraph::types::NodeKind;
+
+    struct LoggingNode;
+
+    #[async_trait]
+    impl Node for LoggingNode {
+        async fn run(&self, _: StateSnapshot, _: NodeContext) -> Result<NodePartial, NodeError> {
+            tracing::info!(target: "http_client", attempt = 2, "request sent");
+            tracing::debug!(target: "http_client", "too verbose");
+            Ok(NodePartial::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_node_scoped_tracing_events_to_event_stream() {
+        let subscriber = tracing_subscriber::registry().with(EventBusLayer::new());
+        let _guard = tracing::subscriber::set_default(subscriber);
+        tracing::info!(target: "http_client", "outside any node");
+
+        let logger = NodeKind::Custom("logger".into());
+        let app = GraphBuilder::new()
+            .add_node(logger.clone(), LoggingNode)
+            .add_edge(NodeKind::Start, logger.clone())
+            .add_edge(logger, NodeKind::End)
+            .compile()
+            .unwrap();
+        let (handle, stream) = app
+            .invoke_streaming(VersionedState::new_with_user_message("hi"))
+            .await;
+        let events: Vec<Event> = stream
+            .into_async_stream()
+            .take_while(|event| std::future::ready(event.scope_label() != Some(STREAM_END_SCOPE)))
+            .collect()
+            .await;
+        handle.join().await.unwrap();
+
+        let forwarded: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Node(node) if node.scope() == TRACING_SCOPE => Some(node),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(forwarded.len(), 1, "{forwarded:?}");
+        let event = forwarded[0];
+        assert_eq!(event.message(), "request sent");
+        assert_eq!(event.node_id(), Some("Custom(\"logger\")"));
+        assert_eq!(event.step(), Some(1));
+        assert_eq!(event.metadata()["level"], "INFO");
+        assert_eq!(event.metadata()["target"], "http_client");
+        assert_eq!(event.metadata()["fields"]["attempt"], 2);
+    }
+}
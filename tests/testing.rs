@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use weavegraph::event_bus::EventBus;
+use weavegraph::graphs::GraphBuilder;
+use weavegraph::message::Message;
+use weavegraph::node::{Node, NodeContext, NodeError, NodePartial};
+use weavegraph::runtimes::{AppRunner, StepResult};
+use weavegraph::state::VersionedState;
+use weavegraph::testing::ScriptedNode;
+use weavegraph::types::NodeKind;
+
+mod common;
+use common::*;
+
+fn say(text: &str) -> NodePartial {
+    NodePartial::new().with_messages(vec![Message::assistant(text)])
+}
+
+fn ctx(step: u64) -> NodeContext {
+    NodeContext::new("scripted", step, EventBus::default().get_emitter())
+}
+
+#[tokio::test]
+async fn scripted_node_plays_back_outputs_and_records_snapshots() {
+    let node = ScriptedNode::with_outputs([say("one")]).then(say("two"));
+    let state = VersionedState::new_with_user_message("hi");
+
+    for (step, expected) in [(1, "one"), (2, "two")] {
+        let output = node.run(state.snapshot(), ctx(step)).await.unwrap();
+        assert_eq!(output.messages.unwrap()[0].content, expected);
+    }
+    // Exhausted scripts answer with an empty partial.
+    let output = node.run(state.snapshot(), ctx(3)).await.unwrap();
+    assert!(output.messages.is_none());
+
+    let steps: Vec<u64> = node.calls().iter().map(|call| call.step).collect();
+    assert_eq!(steps, vec![1, 2, 3]);
+    assert_eq!(node.snapshots()[0].messages[0].content, "hi");
+    assert_eq!(node.remaining_outputs(), 0);
+}
+
+#[tokio::test]
+async fn scripted_node_delays_on_the_configured_step() {
+    let node = ScriptedNode::new().delay_on_step(2, Duration::from_millis(50));
+    let snapshot = VersionedState::new_with_user_message("hi").snapshot();
+
+    let started = std::time::Instant::now();
+    node.run(snapshot.clone(), ctx(1)).await.unwrap();
+    assert!(started.elapsed() < Duration::from_millis(50));
+    let started = std::time::Instant::now();
+    node.run(snapshot, ctx(2)).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn injected_error_fails_only_the_first_run_of_its_step() {
+    let scripted = NodeKind::Custom("scripted".into());
+    let node = ScriptedNode::with_outputs([say("recovered")])
+        .fail_on_step(1, NodeError::ValidationFailed("flaky upstream".into()));
+    let app = GraphBuilder::new()
+        .add_node(scripted.clone(), node.clone())
+        .add_edge(NodeKind::Start, scripted.clone())
+        .add_edge(scripted, NodeKind::End)
+        .compile()
+        .unwrap();
+    let mut runner = AppRunner::builder().app(app).build().await;
+    runner
+        .create_session("retry".into(), state_with_user("hi"))
+        .await
+        .unwrap();
+
+    assert!(runner.run_step("retry", Default::default()).await.is_err());
+    assert!(matches!(
+        runner.run_step("retry", Default::default()).await.unwrap(),
+        StepResult::Completed(_)
+    ));
+
+    let runs: Vec<(u64, u32)> = node
+        .calls()
+        .iter()
+        .map(|call| (call.step, call.attempt))
+        .collect();
+    assert_eq!(runs, vec![(1, 1), (2, 1)]);
+    let state = &runner.get_session("retry").unwrap().state;
+    assert_eq!(state.messages.snapshot()[1].content, "recovered");
+    assert_eq!(node.remaining_outputs(), 0);
+}
@@ -1,3 +1,4 @@
+use weavegraph::node_kind;
 use weavegraph::runtimes::types::{SessionId, StepNumber};
 use weavegraph::types::{ChannelType, NodeKind};
 
@@ -37,6 +38,26 @@ fn test_nodekind_encode_decode() {
     }
 }
 
+#[test]
+fn test_node_kind_macro_and_string_equality() {
+    const REVIEW: &str = "review";
+
+    assert_eq!(node_kind!("fetch"), NodeKind::Custom("fetch".into()));
+    assert_eq!(node_kind!(REVIEW), NodeKind::Custom("review".into()));
+    assert_eq!(node_kind!(Start), NodeKind::Start);
+    assert_eq!(node_kind!("End"), NodeKind::End);
+
+    assert_eq!(NodeKind::from(String::from("fetch")), node_kind!("fetch"));
+    assert_eq!(NodeKind::from(String::from("Start")), NodeKind::Start);
+
+    let fetch = node_kind!("fetch");
+    assert!(fetch == "fetch");
+    assert!("fetch" == fetch);
+    assert!(fetch != "Fetch");
+    assert!(NodeKind::Start == "Start");
+    assert!(*"End" == NodeKind::End);
+}
+
 #[test]
 fn test_display() {
     assert_eq!(NodeKind::Start.to_string(), "Start");
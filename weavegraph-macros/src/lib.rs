@@ -0,0 +1,182 @@
+//! Procedural macros for [weavegraph](https://docs.rs/weavegraph).
+//!
+//! Use these through the `macros` feature of `weavegraph`, which re-exports
+//! them; the generated code refers to `::weavegraph` paths.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{FnArg, Ident, ItemFn, LitStr, Pat, ReturnType, Token, Type};
+
+/// Turn an async function into a `weavegraph::node::Node`.
+///
+/// The function must take a `StateSnapshot` and a `NodeContext` and return
+/// either `NodePartial` or `Result<NodePartial, E>` with
+/// `E: Into<NodeError>`. The macro keeps the function as written and adds a
+/// unit struct named after it in `PascalCase` that implements `Node` by
+/// calling it inside a tracing span. `#[node(name = "...")]` sets the
+/// `definition_label` (the function name by default).
+#[proc_macro_attribute]
+pub fn node(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as NodeArgs);
+    let function = syn::parse_macro_input!(item as ItemFn);
+    expand(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Arguments of `#[node(...)]`.
+struct NodeArgs {
+    name: Option<LitStr>,
+}
+
+impl Parse for NodeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        let args = Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?;
+        for arg in args {
+            if !arg.path.is_ident("name") {
+                return Err(syn::Error::new_spanned(
+                    arg.path,
+                    "unknown `node` argument; expected `name = \"...\"`",
+                ));
+            }
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) = arg.value
+            else {
+                return Err(syn::Error::new_spanned(
+                    arg.value,
+                    "`name` must be a string literal",
+                ));
+            };
+            name = Some(value);
+        }
+        Ok(Self { name })
+    }
+}
+
+fn expand(args: NodeArgs, function: ItemFn) -> syn::Result<TokenStream2> {
+    let signature = &function.sig;
+    if signature.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            signature.fn_token,
+            "`#[node]` functions must be `async`",
+        ));
+    }
+    if !signature.generics.params.is_empty() || signature.generics.where_clause.is_some() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "`#[node]` functions cannot be generic",
+        ));
+    }
+    if signature.inputs.len() != 2 {
+        return Err(syn::Error::new(
+            signature.inputs.span(),
+            "`#[node]` functions take `(StateSnapshot, NodeContext)`",
+        ));
+    }
+    for input in &signature.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "`#[node]` functions cannot take `self`",
+                ));
+            }
+            FnArg::Typed(typed) if !matches!(*typed.pat, Pat::Ident(_) | Pat::Wild(_)) => {
+                return Err(syn::Error::new_spanned(
+                    &typed.pat,
+                    "`#[node]` parameters must be plain bindings",
+                ));
+            }
+            FnArg::Typed(_) => {}
+        }
+    }
+
+    let function_name = &signature.ident;
+    let struct_name = Ident::new(
+        &pascal_case(&function_name.to_string()),
+        function_name.span(),
+    );
+    let label = args
+        .name
+        .unwrap_or_else(|| LitStr::new(&function_name.to_string(), Span::call_site()));
+    let span_name = LitStr::new(&function_name.to_string(), function_name.span());
+    let visibility = &function.vis;
+    let struct_doc = format!("Node running [`{function_name}`].");
+    let snapshot = format_ident!("__weavegraph_snapshot");
+    let ctx = format_ident!("__weavegraph_ctx");
+
+    let call = quote! { #function_name(#snapshot, #ctx) };
+    let result = if returns_result(&signature.output) {
+        quote! {
+            #call.await.map_err(::core::convert::Into::<::weavegraph::node::NodeError>::into)
+        }
+    } else {
+        quote! { ::core::result::Result::Ok(#call.await) }
+    };
+
+    Ok(quote! {
+        #function
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #visibility struct #struct_name;
+
+        #[::weavegraph::__private::async_trait]
+        impl ::weavegraph::node::Node for #struct_name {
+            async fn run(
+                &self,
+                #snapshot: ::weavegraph::state::StateSnapshot,
+                #ctx: ::weavegraph::node::NodeContext,
+            ) -> ::core::result::Result<::weavegraph::node::NodePartial, ::weavegraph::node::NodeError> {
+                use ::weavegraph::__private::tracing::Instrument as _;
+                let span = ::weavegraph::__private::tracing::info_span!(
+                    #span_name,
+                    node = #label,
+                    node_id = %#ctx.node_id,
+                    step = #ctx.step,
+                );
+                async move { #result }.instrument(span).await
+            }
+
+            fn definition_label(&self) -> &'static str {
+                #label
+            }
+        }
+    })
+}
+
+/// Whether the declared return type is spelled `Result<...>`.
+fn returns_result(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Result")
+}
+
+/// `summarize_history` -> `SummarizeHistory`.
+fn pascal_case(name: &str) -> String {
+    name.trim_start_matches("r#")
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}